@@ -1,10 +1,14 @@
 use std::time::Duration;
 
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 
 use opensession_api::*;
 
+use crate::device::DeviceSigningKey;
+use crate::throttle::{UploadRateLimit, throttled_body};
+
 pub type Result<T> = std::result::Result<T, ApiClientError>;
 
 #[derive(Debug, Error)]
@@ -20,6 +24,88 @@ pub enum ApiClientError {
     },
     #[error("response decode error: {0}")]
     Decode(reqwest::Error),
+    #[error("request encode error: {0}")]
+    Encode(serde_json::Error),
+    /// The server refused an upload because a team storage quota is full.
+    #[error("team quota exceeded: {0}")]
+    QuotaExceeded(String),
+    #[error(
+        "session body does not match its upload hash (expected sha256 {expected}, got {actual})"
+    )]
+    IntegrityMismatch { expected: String, actual: String },
+}
+
+/// Outcome of [`ApiClient::fetch_session_body`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum RawSessionBody {
+    /// The caller's cached copy (matching `If-None-Match`) is current.
+    NotModified,
+    /// A fresh body plus the ETag to send on the next fetch, if any.
+    Fetched { body: Vec<u8>, etag: Option<String> },
+    /// Events after the first `since_event`, to append to the cached body.
+    /// `etag` names the full, merged body.
+    Delta {
+        since_event: usize,
+        delta: Vec<u8>,
+        etag: Option<String>,
+    },
+}
+
+/// Chunked response body returned by [`ApiClient::stream_session_body`] and
+/// [`ApiClient::export_account`].
+pub struct SessionBodyStream {
+    resp: reqwest::Response,
+    integrity: Option<(String, Sha256)>,
+}
+
+impl SessionBodyStream {
+    fn new(resp: reqwest::Response) -> Self {
+        let integrity = upload_sha256(&resp).map(|expected| (expected, Sha256::new()));
+        Self { resp, integrity }
+    }
+
+    /// The next chunk of JSONL bytes, or `None` once the body is complete.
+    /// Chunks do not align with line boundaries.
+    ///
+    /// When the server sent the body's upload hash, the final call fails with
+    /// [`ApiClientError::IntegrityMismatch`] if the streamed bytes differ.
+    pub async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        let chunk = self.resp.chunk().await.map_err(ApiClientError::Transport)?;
+        match chunk {
+            Some(bytes) => {
+                if let Some((_, hasher)) = self.integrity.as_mut() {
+                    hasher.update(&bytes);
+                }
+                Ok(Some(bytes.to_vec()))
+            }
+            None => match self.integrity.take() {
+                Some((expected, hasher)) => {
+                    verify_sha256(&expected, hex::encode(hasher.finalize()))?;
+                    Ok(None)
+                }
+                None => Ok(None),
+            },
+        }
+    }
+}
+
+/// Upload-time SHA-256 the server attached to a full session body, if any.
+fn upload_sha256(resp: &reqwest::Response) -> Option<String> {
+    resp.headers()
+        .get(SESSION_BODY_SHA256_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_ascii_lowercase())
+}
+
+fn verify_sha256(expected: &str, actual: String) -> Result<()> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(ApiClientError::IntegrityMismatch {
+            expected: expected.to_string(),
+            actual,
+        })
+    }
 }
 
 /// Typed HTTP client for the OpenSession API.
@@ -31,6 +117,9 @@ pub struct ApiClient {
     client: reqwest::Client,
     base_url: String,
     auth_token: Option<String>,
+    device_headers: reqwest::header::HeaderMap,
+    upload_rate_limit: Option<UploadRateLimit>,
+    signing_key: Option<DeviceSigningKey>,
 }
 
 impl ApiClient {
@@ -44,6 +133,9 @@ impl ApiClient {
             client,
             base_url: base_url.trim_end_matches('/').to_string(),
             auth_token: None,
+            device_headers: reqwest::header::HeaderMap::new(),
+            upload_rate_limit: None,
+            signing_key: None,
         })
     }
 
@@ -53,6 +145,9 @@ impl ApiClient {
             client,
             base_url: base_url.trim_end_matches('/').to_string(),
             auth_token: None,
+            device_headers: reqwest::header::HeaderMap::new(),
+            upload_rate_limit: None,
+            signing_key: None,
         }
     }
 
@@ -62,50 +157,880 @@ impl ApiClient {
             self.auth_token = None;
             return;
         }
-        self.auth_token = Some(normalized.to_string());
+        self.auth_token = Some(normalized.to_string());
+    }
+
+    pub fn auth_token(&self) -> Option<&str> {
+        self.auth_token.as_deref()
+    }
+
+    /// Identify this machine on authenticated requests so the server can list
+    /// and revoke it. Values that are not valid header text are dropped.
+    pub fn set_device(&mut self, id: &str, name: Option<&str>) {
+        use reqwest::header::HeaderValue;
+
+        self.device_headers.clear();
+        let Ok(id) = HeaderValue::from_str(id.trim()) else {
+            return;
+        };
+        self.device_headers.insert(DEVICE_ID_HEADER, id);
+        if let Some(name) = name.and_then(|name| HeaderValue::from_str(name.trim()).ok()) {
+            self.device_headers.insert(DEVICE_NAME_HEADER, name);
+        }
+    }
+
+    /// [`set_device`](Self::set_device) from the id persisted at `id_path`
+    /// (created on first use) and the local host name.
+    pub fn identify_device(&mut self, id_path: &std::path::Path) -> std::io::Result<()> {
+        let id = crate::device::load_or_create_device_id(id_path)?;
+        self.set_device(&id, crate::device::local_device_name().as_deref());
+        Ok(())
+    }
+
+    /// The device id sent with requests, if one is set.
+    pub fn device_id(&self) -> Option<&str> {
+        self.device_headers
+            .get(DEVICE_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+    }
+
+    /// Sign session uploads that carry no signature with `key`, the device's
+    /// registered key; `None` sends them unsigned.
+    pub fn set_signing_key(&mut self, key: Option<DeviceSigningKey>) {
+        self.signing_key = key;
+    }
+
+    /// `req` with a device signature, when a signing key is set and the
+    /// request is not signed yet.
+    fn signed_upload(&self, req: &UploadRequest) -> Option<UploadRequest> {
+        let key = self.signing_key.as_ref()?;
+        if req.signature.is_some() {
+            return None;
+        }
+        let mut signed = req.clone();
+        signed.signature = Some(key.sign(&req.signing_message()));
+        Some(signed)
+    }
+
+    /// Cap the bandwidth of session uploads and handoff publishes; `None` lifts it.
+    pub fn set_upload_rate_limit(&mut self, limit: Option<UploadRateLimit>) {
+        self.upload_rate_limit = limit;
+    }
+
+    pub fn upload_rate_limit(&self) -> Option<UploadRateLimit> {
+        self.upload_rate_limit
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Access the underlying `reqwest::Client`.
+    pub fn reqwest_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/api{}", self.base_url, path)
+    }
+
+    /// Attach `body` as JSON, streamed through the upload rate limit when one is set.
+    fn upload_json<T: Serialize>(
+        &self,
+        request: reqwest::RequestBuilder,
+        body: &T,
+    ) -> Result<reqwest::RequestBuilder> {
+        let Some(limit) = self.upload_rate_limit else {
+            return Ok(request.json(body));
+        };
+        let bytes = serde_json::to_vec(body).map_err(ApiClientError::Encode)?;
+        Ok(request
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(throttled_body(bytes, limit)))
+    }
+
+    fn token_or_err(&self) -> Result<&str> {
+        self.auth_token
+            .as_deref()
+            .ok_or(ApiClientError::AuthTokenMissing)
+    }
+
+    /// Attach auth and device headers when a token is set; public reads work without one.
+    fn optional_auth(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.auth_token.as_deref() {
+            Some(token) => request
+                .bearer_auth(token)
+                .headers(self.device_headers.clone()),
+            None => request,
+        }
+    }
+
+    // ── Health ────────────────────────────────────────────────────────────
+
+    pub async fn health(&self) -> Result<HealthResponse> {
+        let resp = self
+            .client
+            .get(self.url("/health"))
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn capabilities(&self) -> Result<CapabilitiesResponse> {
+        let resp = self
+            .client
+            .get(self.url("/capabilities"))
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    /// JSON Schema for a HAIL session object.
+    pub async fn session_schema(&self) -> Result<serde_json::Value> {
+        let resp = self
+            .client
+            .get(self.url("/schema/session.json"))
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    /// OpenAPI 3.1 description of the server's API.
+    pub async fn openapi_document(&self) -> Result<serde_json::Value> {
+        let resp = self
+            .client
+            .get(self.url("/openapi.json"))
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    // ── Parsing & validation ──────────────────────────────────────────────
+
+    /// Check a HAIL JSONL body without uploading it. Problems are reported in
+    /// the response, not as an error.
+    pub async fn validate_session(&self, jsonl: &str) -> Result<ValidateSessionResponse> {
+        let resp = self
+            .client
+            .post(self.url("/validate"))
+            .header(reqwest::header::CONTENT_TYPE, "application/jsonl")
+            .body(jsonl.to_string())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn parse_preview(&self, req: &ParsePreviewRequest) -> Result<ParsePreviewResponse> {
+        let resp = self
+            .optional_auth(self.client.post(self.url("/parse/preview")))
+            .json(req)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn local_review_bundle(&self, review_id: &str) -> Result<LocalReviewBundle> {
+        let resp = self
+            .client
+            .get(self.url(&format!("/review/local/{review_id}")))
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    // ── Auth ──────────────────────────────────────────────────────────────
+
+    pub async fn login(&self, req: &LoginRequest) -> Result<AuthTokenResponse> {
+        let resp = self
+            .client
+            .post(self.url("/auth/login"))
+            .json(req)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn register(&self, req: &AuthRegisterRequest) -> Result<AuthTokenResponse> {
+        let resp = self
+            .client
+            .post(self.url("/auth/register"))
+            .json(req)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn verify(&self) -> Result<VerifyResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .post(self.url("/auth/verify"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn me(&self) -> Result<UserSettingsResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .get(self.url("/auth/me"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    /// Stream everything the server stores for the account, one
+    /// [`AccountExportRecord`] per line.
+    pub async fn export_account(&self) -> Result<SessionBodyStream> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .get(self.url("/auth/export"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = match resp.text().await {
+                Ok(body) => body,
+                Err(err) => format!("<failed to read response body: {err}>"),
+            };
+            return Err(ApiClientError::UnexpectedStatus { status, body });
+        }
+        Ok(SessionBodyStream::new(resp))
+    }
+
+    /// Permanently delete the account and everything it uploaded.
+    pub async fn delete_account(&self) -> Result<DeleteAccountResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .delete(self.url("/auth/me"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn refresh(&self, req: &RefreshRequest) -> Result<AuthTokenResponse> {
+        let resp = self
+            .client
+            .post(self.url("/auth/refresh"))
+            .json(req)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn logout(&self, req: &LogoutRequest) -> Result<OkResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .post(self.url("/auth/logout"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .json(req)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn change_password(&self, req: &ChangePasswordRequest) -> Result<OkResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .put(self.url("/auth/password"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .json(req)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn issue_api_key(&self) -> Result<IssueApiKeyResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .post(self.url("/auth/api-keys/issue"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn list_devices(&self) -> Result<ListDevicesResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .get(self.url("/auth/devices"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn revoke_device(&self, id: &str) -> Result<OkResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .delete(self.url(&format!("/auth/devices/{id}")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    /// Register the upload signing key of the device `id` (the calling one).
+    pub async fn register_device_key(
+        &self,
+        id: &str,
+        req: &RegisterDeviceKeyRequest,
+    ) -> Result<OkResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .put(self.url(&format!("/auth/devices/{id}/signing-key")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .json(req)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn auth_providers(&self) -> Result<oauth::AuthProvidersResponse> {
+        let resp = self
+            .client
+            .get(self.url("/auth/providers"))
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    /// Start linking an OAuth provider; open the returned URL in a browser.
+    pub async fn link_oauth(&self, provider: &str) -> Result<OAuthLinkResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .post(self.url(&format!("/auth/oauth/{provider}/link")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn list_git_credentials(&self) -> Result<ListGitCredentialsResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .get(self.url("/auth/git-credentials"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn create_git_credential(
+        &self,
+        req: &CreateGitCredentialRequest,
+    ) -> Result<GitCredentialSummary> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .post(self.url("/auth/git-credentials"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .json(req)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn delete_git_credential(&self, id: &str) -> Result<OkResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .delete(self.url(&format!("/auth/git-credentials/{id}")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    // ── Sessions ──────────────────────────────────────────────────────────
+
+    pub async fn upload_session(&self, req: &UploadRequest) -> Result<UploadResponse> {
+        let token = self.token_or_err()?;
+        let signed = self.signed_upload(req);
+        let req = signed.as_ref().unwrap_or(req);
+        let request = self
+            .client
+            .post(self.url("/sessions"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone());
+        let resp = self
+            .upload_json(request, req)?
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    /// Upload several sessions with one `POST /sessions/batch`.
+    ///
+    /// Per-item failures are reported in the response rather than as an error.
+    pub async fn upload_sessions_batch(
+        &self,
+        req: &BatchUploadRequest,
+    ) -> Result<BatchUploadResponse> {
+        let token = self.token_or_err()?;
+        let signed = self.signing_key.is_some().then(|| BatchUploadRequest {
+            sessions: req
+                .sessions
+                .iter()
+                .map(|item| self.signed_upload(item).unwrap_or_else(|| item.clone()))
+                .collect(),
+        });
+        let req = signed.as_ref().unwrap_or(req);
+        let request = self
+            .client
+            .post(self.url("/sessions/batch"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone());
+        let resp = self
+            .upload_json(request, req)?
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    /// Storage used by `team_id`'s members and the server's team quotas.
+    pub async fn team_usage(&self, team_id: &str) -> Result<TeamUsageResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .get(self.url(&format!("/teams/{team_id}/usage")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    /// List sessions visible to the caller; build `query` with [`crate::SessionQuery`].
+    pub async fn list_sessions(&self, query: &SessionListQuery) -> Result<SessionListResponse> {
+        let resp = self
+            .optional_auth(self.client.get(self.url("/sessions")))
+            .query(&session_list_params(query))
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    /// Repository names that have sessions visible to the caller.
+    pub async fn list_session_repos(&self) -> Result<SessionRepoListResponse> {
+        let resp = self
+            .optional_auth(self.client.get(self.url("/sessions/repos")))
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn get_session(&self, id: &str) -> Result<SessionDetail> {
+        let resp = self
+            .optional_auth(self.client.get(self.url(&format!("/sessions/{id}"))))
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    /// Delete a session (admin only).
+    pub async fn delete_session(&self, id: &str) -> Result<OkResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .delete(self.url(&format!("/admin/sessions/{id}")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn get_session_raw(&self, id: &str) -> Result<serde_json::Value> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .get(self.url(&format!("/sessions/{id}/raw")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    /// Conditionally download a session body.
+    ///
+    /// Pass the ETag stored alongside a cached body as `if_none_match`; the
+    /// server answers `304` when the cached copy is still current. Pass the
+    /// cached body's event count as `since_event` to receive only newer
+    /// events; servers that cannot slice the body return it in full. Full
+    /// bodies are checked against the hash recorded at upload when the server
+    /// provides one.
+    pub async fn fetch_session_body(
+        &self,
+        id: &str,
+        if_none_match: Option<&str>,
+        since_event: Option<usize>,
+    ) -> Result<RawSessionBody> {
+        let token = self.token_or_err()?;
+        let mut req = self
+            .client
+            .get(self.url(&format!("/sessions/{id}/raw")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone());
+        if let Some(etag) = if_none_match {
+            req = req.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(since_event) = since_event {
+            req = req.query(&[("since_event", since_event)]);
+        }
+        let resp = req.send().await.map_err(ApiClientError::Transport)?;
+
+        let status = resp.status();
+        if status == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(RawSessionBody::NotModified);
+        }
+        if !status.is_success() {
+            let body = match resp.text().await {
+                Ok(body) => body,
+                Err(err) => format!("<failed to read response body: {err}>"),
+            };
+            return Err(ApiClientError::UnexpectedStatus { status, body });
+        }
+        let etag = resp
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let delta_since = resp
+            .headers()
+            .get(SESSION_DELTA_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<usize>().ok());
+        let expected_sha256 = upload_sha256(&resp);
+        let body = resp
+            .bytes()
+            .await
+            .map_err(ApiClientError::Transport)?
+            .to_vec();
+        Ok(match delta_since {
+            Some(since_event) => RawSessionBody::Delta {
+                since_event,
+                delta: body,
+                etag,
+            },
+            None => {
+                if let Some(expected) = expected_sha256.as_deref() {
+                    verify_sha256(expected, hex::encode(Sha256::digest(&body)))?;
+                }
+                RawSessionBody::Fetched { body, etag }
+            }
+        })
+    }
+
+    /// Stream a session's HAIL JSONL body without buffering it in memory.
+    pub async fn stream_session_body(&self, id: &str) -> Result<SessionBodyStream> {
+        let resp = self
+            .optional_auth(self.client.get(self.url(&format!("/sessions/{id}/raw"))))
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = match resp.text().await {
+                Ok(body) => body,
+                Err(err) => format!("<failed to read response body: {err}>"),
+            };
+            return Err(ApiClientError::UnexpectedStatus { status, body });
+        }
+        Ok(SessionBodyStream::new(resp))
+    }
+
+    /// Run several typed reads with one `POST /query`; see [`QueryRequest`].
+    pub async fn query(&self, req: &QueryRequest) -> Result<QueryResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .post(self.url("/query"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .json(req)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    // ── Attachments ───────────────────────────────────────────────────────
+
+    /// Attach a file (at most [`MAX_ATTACHMENT_BYTES`]) to one of your sessions.
+    pub async fn upload_attachment(
+        &self,
+        session_id: &str,
+        file_name: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<SessionAttachment> {
+        let query = AttachmentUploadQuery {
+            name: file_name.to_string(),
+            kind: None,
+            recorded_at: None,
+        };
+        self.upload_attachment_with(session_id, &query, content_type, data)
+            .await
+    }
+
+    /// [`Self::upload_attachment`] with an explicit kind or recording start.
+    pub async fn upload_attachment_with(
+        &self,
+        session_id: &str,
+        query: &AttachmentUploadQuery,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<SessionAttachment> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .post(self.url(&format!("/sessions/{session_id}/attachments")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .query(query)
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(data)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn list_attachments(
+        &self,
+        session_id: &str,
+    ) -> Result<SessionAttachmentListResponse> {
+        let resp = self
+            .optional_auth(
+                self.client
+                    .get(self.url(&format!("/sessions/{session_id}/attachments"))),
+            )
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn download_attachment(&self, session_id: &str, id: &str) -> Result<Vec<u8>> {
+        let resp = self
+            .optional_auth(
+                self.client
+                    .get(self.url(&format!("/sessions/{session_id}/attachments/{id}"))),
+            )
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = match resp.text().await {
+                Ok(body) => body,
+                Err(err) => format!("<failed to read response body: {err}>"),
+            };
+            return Err(ApiClientError::UnexpectedStatus { status, body });
+        }
+        Ok(resp
+            .bytes()
+            .await
+            .map_err(ApiClientError::Transport)?
+            .to_vec())
+    }
+
+    // ── Handoffs ──────────────────────────────────────────────────────────
+
+    /// Deliver a handoff artifact to another user's inbox.
+    pub async fn publish_handoff(&self, req: &PublishHandoffRequest) -> Result<HandoffInboxItem> {
+        let token = self.token_or_err()?;
+        let request = self
+            .client
+            .post(self.url("/handoffs"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone());
+        let resp = self
+            .upload_json(request, req)?
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
+
+    pub async fn handoff_inbox(
+        &self,
+        status: Option<HandoffStatus>,
+        limit: Option<u32>,
+    ) -> Result<HandoffInboxResponse> {
+        let token = self.token_or_err()?;
+        let mut req = self
+            .client
+            .get(self.url("/handoffs/inbox"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone());
+        if let Some(status) = status {
+            req = req.query(&[("status", status.as_str())]);
+        }
+        if let Some(limit) = limit {
+            req = req.query(&[("limit", limit)]);
+        }
+        let resp = req.send().await.map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
     }
 
-    pub fn auth_token(&self) -> Option<&str> {
-        self.auth_token.as_deref()
+    pub async fn claim_handoff(&self, id: &str) -> Result<HandoffInboxItem> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .post(self.url(&format!("/handoffs/{id}/claim")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
     }
 
-    pub fn base_url(&self) -> &str {
-        &self.base_url
+    pub async fn complete_handoff(
+        &self,
+        id: &str,
+        req: &CompleteHandoffRequest,
+    ) -> Result<HandoffInboxItem> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .post(self.url(&format!("/handoffs/{id}/complete")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .json(req)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
     }
 
-    /// Access the underlying `reqwest::Client`.
-    pub fn reqwest_client(&self) -> &reqwest::Client {
-        &self.client
-    }
+    // ── Metadata sync ─────────────────────────────────────────────────────
 
-    fn url(&self, path: &str) -> String {
-        format!("{}/api{}", self.base_url, path)
+    pub async fn list_metadata(&self) -> Result<SyncMetadataResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .get(self.url("/sync/metadata"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
     }
 
-    fn token_or_err(&self) -> Result<&str> {
-        self.auth_token
-            .as_deref()
-            .ok_or(ApiClientError::AuthTokenMissing)
+    /// Push local metadata changes; the response is the merged server state.
+    pub async fn sync_metadata(&self, req: &SyncMetadataRequest) -> Result<SyncMetadataResponse> {
+        let token = self.token_or_err()?;
+        let resp = self
+            .client
+            .post(self.url("/sync/metadata"))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .json(req)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
     }
 
-    // ── Health ────────────────────────────────────────────────────────────
-
-    pub async fn health(&self) -> Result<HealthResponse> {
+    /// Your bookmarked events of a session.
+    pub async fn list_event_bookmarks(
+        &self,
+        session_id: &str,
+    ) -> Result<EventBookmarkListResponse> {
+        let token = self.token_or_err()?;
         let resp = self
             .client
-            .get(self.url("/health"))
+            .get(self.url(&format!("/sessions/{session_id}/bookmarks")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .send()
             .await
             .map_err(ApiClientError::Transport)?;
         parse_response(resp).await
     }
 
-    // ── Auth ──────────────────────────────────────────────────────────────
-
-    pub async fn login(&self, req: &LoginRequest) -> Result<AuthTokenResponse> {
+    /// Bookmark an event, or replace its note.
+    pub async fn put_event_bookmark(
+        &self,
+        session_id: &str,
+        event_id: &str,
+        req: &PutEventBookmarkRequest,
+    ) -> Result<EventBookmark> {
+        let token = self.token_or_err()?;
         let resp = self
             .client
-            .post(self.url("/auth/login"))
+            .put(self.url(&format!(
+                "/sessions/{session_id}/events/{event_id}/bookmark"
+            )))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .json(req)
             .send()
             .await
@@ -113,71 +1038,106 @@ impl ApiClient {
         parse_response(resp).await
     }
 
-    pub async fn register(&self, req: &AuthRegisterRequest) -> Result<AuthTokenResponse> {
+    pub async fn delete_event_bookmark(
+        &self,
+        session_id: &str,
+        event_id: &str,
+    ) -> Result<OkResponse> {
+        let token = self.token_or_err()?;
         let resp = self
             .client
-            .post(self.url("/auth/register"))
-            .json(req)
+            .delete(self.url(&format!(
+                "/sessions/{session_id}/events/{event_id}/bookmark"
+            )))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .send()
             .await
             .map_err(ApiClientError::Transport)?;
         parse_response(resp).await
     }
 
-    pub async fn verify(&self) -> Result<VerifyResponse> {
+    // ── Live sharing ──────────────────────────────────────────────────────
+
+    /// Start sharing a running session with your teams, or restart your share.
+    pub async fn start_live(
+        &self,
+        session_id: &str,
+        req: &StreamEventsRequest,
+    ) -> Result<LiveSessionInfo> {
         let token = self.token_or_err()?;
         let resp = self
             .client
-            .post(self.url("/auth/verify"))
+            .post(self.url(&format!("/sessions/{session_id}/live")))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .json(req)
             .send()
             .await
             .map_err(ApiClientError::Transport)?;
         parse_response(resp).await
     }
 
-    pub async fn me(&self) -> Result<UserSettingsResponse> {
+    /// Relay new events to everyone watching a session you share live.
+    pub async fn append_live_events(
+        &self,
+        session_id: &str,
+        req: &StreamEventsRequest,
+    ) -> Result<StreamEventsResponse> {
         let token = self.token_or_err()?;
         let resp = self
             .client
-            .get(self.url("/auth/me"))
+            .post(self.url(&format!("/sessions/{session_id}/events")))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .json(req)
             .send()
             .await
             .map_err(ApiClientError::Transport)?;
         parse_response(resp).await
     }
 
-    pub async fn refresh(&self, req: &RefreshRequest) -> Result<AuthTokenResponse> {
+    pub async fn stop_live(&self, session_id: &str) -> Result<OkResponse> {
+        let token = self.token_or_err()?;
         let resp = self
             .client
-            .post(self.url("/auth/refresh"))
-            .json(req)
+            .delete(self.url(&format!("/sessions/{session_id}/live")))
+            .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .send()
             .await
             .map_err(ApiClientError::Transport)?;
         parse_response(resp).await
     }
 
-    pub async fn logout(&self, req: &LogoutRequest) -> Result<OkResponse> {
+    /// Live sessions shared by you or a teammate.
+    pub async fn list_live(&self) -> Result<LiveSessionListResponse> {
         let token = self.token_or_err()?;
         let resp = self
             .client
-            .post(self.url("/auth/logout"))
+            .get(self.url("/live"))
             .bearer_auth(token)
-            .json(req)
+            .headers(self.device_headers.clone())
             .send()
             .await
             .map_err(ApiClientError::Transport)?;
         parse_response(resp).await
     }
 
-    pub async fn change_password(&self, req: &ChangePasswordRequest) -> Result<OkResponse> {
+    // ── Review requests ───────────────────────────────────────────────────
+
+    /// Ask another user to review an uploaded session.
+    pub async fn request_review(
+        &self,
+        session_id: &str,
+        req: &CreateReviewRequest,
+    ) -> Result<ReviewRequestItem> {
         let token = self.token_or_err()?;
         let resp = self
             .client
-            .post(self.url("/auth/change-password"))
+            .post(self.url(&format!("/sessions/{session_id}/review-requests")))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .json(req)
             .send()
             .await
@@ -185,26 +1145,34 @@ impl ApiClient {
         parse_response(resp).await
     }
 
-    pub async fn issue_api_key(&self) -> Result<IssueApiKeyResponse> {
+    pub async fn list_review_requests(
+        &self,
+        query: &ReviewRequestListQuery,
+    ) -> Result<ReviewRequestListResponse> {
         let token = self.token_or_err()?;
         let resp = self
             .client
-            .post(self.url("/auth/api-keys/issue"))
+            .get(self.url("/review-requests"))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .query(query)
             .send()
             .await
             .map_err(ApiClientError::Transport)?;
         parse_response(resp).await
     }
 
-    // ── Sessions ──────────────────────────────────────────────────────────
-
-    pub async fn upload_session(&self, req: &UploadRequest) -> Result<UploadResponse> {
+    pub async fn resolve_review_request(
+        &self,
+        id: &str,
+        req: &ResolveReviewRequest,
+    ) -> Result<ReviewRequestItem> {
         let token = self.token_or_err()?;
         let resp = self
             .client
-            .post(self.url("/sessions"))
+            .post(self.url(&format!("/review-requests/{id}/resolve")))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .json(req)
             .send()
             .await
@@ -212,69 +1180,73 @@ impl ApiClient {
         parse_response(resp).await
     }
 
-    pub async fn list_sessions(&self, query: &SessionListQuery) -> Result<SessionListResponse> {
+    // ── Telemetry ─────────────────────────────────────────────────────────
+
+    /// Send anonymous usage counters. Device headers are left off so the
+    /// report cannot be tied to this machine.
+    pub async fn report_telemetry(&self, report: &TelemetryReport) -> Result<OkResponse> {
         let token = self.token_or_err()?;
-        let mut url = self.url("/sessions");
+        let resp = self
+            .client
+            .post(self.url("/telemetry"))
+            .bearer_auth(token)
+            .json(report)
+            .send()
+            .await
+            .map_err(ApiClientError::Transport)?;
+        parse_response(resp).await
+    }
 
-        let mut params = Vec::new();
-        params.push(format!("page={}", query.page));
-        params.push(format!("per_page={}", query.per_page));
-        if let Some(ref s) = query.search {
-            params.push(format!("search={s}"));
-        }
-        if let Some(ref t) = query.tool {
-            params.push(format!("tool={t}"));
-        }
-        if let Some(ref s) = query.sort {
-            params.push(format!("sort={s}"));
-        }
-        if let Some(ref r) = query.time_range {
-            params.push(format!("time_range={r}"));
-        }
-        if !params.is_empty() {
-            url = format!("{}?{}", url, params.join("&"));
-        }
+    // ── Playbooks ─────────────────────────────────────────────────────────
 
+    pub async fn list_playbooks(&self) -> Result<PlaybookListResponse> {
+        let token = self.token_or_err()?;
         let resp = self
             .client
-            .get(&url)
+            .get(self.url("/playbooks"))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .send()
             .await
             .map_err(ApiClientError::Transport)?;
         parse_response(resp).await
     }
 
-    pub async fn get_session(&self, id: &str) -> Result<SessionDetail> {
+    pub async fn get_playbook(&self, name: &str) -> Result<Playbook> {
         let token = self.token_or_err()?;
         let resp = self
             .client
-            .get(self.url(&format!("/sessions/{id}")))
+            .get(self.url(&format!("/playbooks/{name}")))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .send()
             .await
             .map_err(ApiClientError::Transport)?;
         parse_response(resp).await
     }
 
-    pub async fn delete_session(&self, id: &str) -> Result<OkResponse> {
+    /// Create a playbook, or replace one you wrote.
+    pub async fn put_playbook(&self, name: &str, req: &PutPlaybookRequest) -> Result<Playbook> {
         let token = self.token_or_err()?;
         let resp = self
             .client
-            .delete(self.url(&format!("/sessions/{id}")))
+            .put(self.url(&format!("/playbooks/{name}")))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
+            .json(req)
             .send()
             .await
             .map_err(ApiClientError::Transport)?;
         parse_response(resp).await
     }
 
-    pub async fn get_session_raw(&self, id: &str) -> Result<serde_json::Value> {
+    pub async fn delete_playbook(&self, name: &str) -> Result<OkResponse> {
         let token = self.token_or_err()?;
         let resp = self
             .client
-            .get(self.url(&format!("/sessions/{id}/raw")))
+            .delete(self.url(&format!("/playbooks/{name}")))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .send()
             .await
             .map_err(ApiClientError::Transport)?;
@@ -288,6 +1260,7 @@ impl ApiClient {
         self.client
             .get(self.url(path))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .send()
             .await
             .map_err(ApiClientError::Transport)
@@ -298,6 +1271,7 @@ impl ApiClient {
         self.client
             .post(self.url(path))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .send()
             .await
             .map_err(ApiClientError::Transport)
@@ -313,6 +1287,7 @@ impl ApiClient {
         self.client
             .post(self.url(path))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .json(body)
             .send()
             .await
@@ -329,6 +1304,7 @@ impl ApiClient {
         self.client
             .put(self.url(path))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .json(body)
             .send()
             .await
@@ -340,6 +1316,7 @@ impl ApiClient {
         self.client
             .delete(self.url(path))
             .bearer_auth(token)
+            .headers(self.device_headers.clone())
             .send()
             .await
             .map_err(ApiClientError::Transport)
@@ -360,6 +1337,38 @@ impl ApiClient {
     }
 }
 
+/// Encode a [`SessionListQuery`] as `GET /api/sessions` query pairs.
+fn session_list_params(query: &SessionListQuery) -> Vec<(&'static str, String)> {
+    let mut params = vec![
+        ("page", query.page.to_string()),
+        ("per_page", query.per_page.to_string()),
+    ];
+    let optional = [
+        ("cursor", query.cursor.clone()),
+        ("limit", query.limit.map(|v| v.to_string())),
+        ("search", query.search.clone()),
+        ("tool", query.tool.clone()),
+        ("git_repo_name", query.git_repo_name.clone()),
+        ("sort", query.sort.as_ref().map(ToString::to_string)),
+        (
+            "time_range",
+            query.time_range.as_ref().map(ToString::to_string),
+        ),
+        ("model", query.model.clone()),
+        ("since", query.since.clone()),
+        ("before", query.before.clone()),
+        ("touches", query.touches.clone()),
+        ("has_errors", query.has_errors.map(|v| v.to_string())),
+        ("git_commit", query.git_commit.clone()),
+    ];
+    params.extend(
+        optional
+            .into_iter()
+            .filter_map(|(key, value)| value.map(|value| (key, value))),
+    );
+    params
+}
+
 async fn parse_response<T: serde::de::DeserializeOwned>(resp: reqwest::Response) -> Result<T> {
     let status = resp.status();
     if !status.is_success() {
@@ -367,6 +1376,11 @@ async fn parse_response<T: serde::de::DeserializeOwned>(resp: reqwest::Response)
             Ok(body) => body,
             Err(err) => format!("<failed to read response body: {err}>"),
         };
+        if let Ok(error) = serde_json::from_str::<ApiError>(&body)
+            && error.code == "quota_exceeded"
+        {
+            return Err(ApiClientError::QuotaExceeded(error.message));
+        }
         return Err(ApiClientError::UnexpectedStatus { status, body });
     }
     resp.json().await.map_err(ApiClientError::Decode)
@@ -374,7 +1388,9 @@ async fn parse_response<T: serde::de::DeserializeOwned>(resp: reqwest::Response)
 
 #[cfg(test)]
 mod tests {
-    use super::{ApiClient, ApiClientError};
+    use super::{ApiClient, ApiClientError, RawSessionBody, session_list_params};
+    use crate::UploadRateLimit;
+    use opensession_api::{ChangePasswordRequest, PublishHandoffRequest, SessionListQuery};
     use std::time::Duration;
     use tokio::io::{AsyncReadExt, AsyncWriteExt};
     use tokio::net::TcpListener;
@@ -398,6 +1414,43 @@ mod tests {
         format!("http://{addr}")
     }
 
+    #[test]
+    fn session_list_params_include_log_filters() {
+        let query = SessionListQuery {
+            page: 2,
+            per_page: 10,
+            cursor: Some("opaque".into()),
+            limit: None,
+            search: None,
+            tool: Some("claude-code".into()),
+            git_repo_name: None,
+            sort: None,
+            time_range: None,
+            model: Some("claude-*".into()),
+            since: Some("2026-01-01T00:00:00Z".into()),
+            before: None,
+            touches: Some("src/lib.rs".into()),
+            has_errors: Some(true),
+            git_commit: Some("abc123".into()),
+        };
+
+        let params = session_list_params(&query);
+        assert_eq!(
+            params,
+            vec![
+                ("page", "2".to_string()),
+                ("per_page", "10".to_string()),
+                ("cursor", "opaque".to_string()),
+                ("tool", "claude-code".to_string()),
+                ("model", "claude-*".to_string()),
+                ("since", "2026-01-01T00:00:00Z".to_string()),
+                ("touches", "src/lib.rs".to_string()),
+                ("has_errors", "true".to_string()),
+                ("git_commit", "abc123".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn set_auth_trims_surrounding_whitespace() {
         let mut client = ApiClient::new("https://example.com", Duration::from_secs(1))
@@ -447,6 +1500,24 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn parse_response_surfaces_quota_errors() {
+        let base_url = serve_once(
+            "HTTP/1.1 403 Forbidden\r\nContent-Length: 84\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n{\"code\":\"quota_exceeded\",\"message\":\"upload would put team `eng` at 4 of 3 sessions\"}",
+        )
+        .await;
+        let client =
+            ApiClient::new(&base_url, Duration::from_secs(1)).expect("client should construct");
+
+        let error = client.health().await.expect_err("health should fail");
+        match error {
+            ApiClientError::QuotaExceeded(message) => {
+                assert_eq!(message, "upload would put team `eng` at 4 of 3 sessions");
+            }
+            other => panic!("unexpected error variant: {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn parse_response_surfaces_decode_errors() {
         let base_url = serve_once(
@@ -468,4 +1539,255 @@ mod tests {
         let error = client.health().await.expect_err("health should fail");
         assert!(matches!(error, ApiClientError::Transport(_)));
     }
+
+    #[tokio::test]
+    async fn fetch_session_body_handles_full_not_modified_and_delta() {
+        let base_url = serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Length: 4\r\nETag: \"abc\"\r\nConnection: close\r\n\r\nbody",
+        )
+        .await;
+        let mut client =
+            ApiClient::new(&base_url, Duration::from_secs(1)).expect("client should construct");
+        client.set_auth("osk_test_token".to_string());
+
+        let fetched = client
+            .fetch_session_body("s1", None, None)
+            .await
+            .expect("fetch body");
+        assert_eq!(
+            fetched,
+            RawSessionBody::Fetched {
+                body: b"body".to_vec(),
+                etag: Some("\"abc\"".to_string()),
+            }
+        );
+
+        let base_url =
+            serve_once("HTTP/1.1 304 Not Modified\r\nETag: \"abc\"\r\nConnection: close\r\n\r\n")
+                .await;
+        let mut client =
+            ApiClient::new(&base_url, Duration::from_secs(1)).expect("client should construct");
+        client.set_auth("osk_test_token".to_string());
+
+        let cached = client
+            .fetch_session_body("s1", Some("\"abc\""), Some(3))
+            .await
+            .expect("conditional fetch");
+        assert_eq!(cached, RawSessionBody::NotModified);
+
+        let base_url = serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Length: 5\r\nETag: \"def\"\r\nX-OpenSession-Since-Event: 3\r\nConnection: close\r\n\r\ndelta",
+        )
+        .await;
+        let mut client =
+            ApiClient::new(&base_url, Duration::from_secs(1)).expect("client should construct");
+        client.set_auth("osk_test_token".to_string());
+
+        let delta = client
+            .fetch_session_body("s1", Some("\"abc\""), Some(3))
+            .await
+            .expect("delta fetch");
+        assert_eq!(
+            delta,
+            RawSessionBody::Delta {
+                since_event: 3,
+                delta: b"delta".to_vec(),
+                etag: Some("\"def\"".to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn session_body_downloads_are_checked_against_upload_hash() {
+        let base_url = serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Length: 4\r\nX-OpenSession-Body-Sha256: 230d8358dc8e8890b4c58deeb62912ee2f20357ae92a5cc861b98e68fe31acb5\r\nConnection: close\r\n\r\nbody",
+        )
+        .await;
+        let mut client =
+            ApiClient::new(&base_url, Duration::from_secs(1)).expect("client should construct");
+        client.set_auth("osk_test_token".to_string());
+        let fetched = client
+            .fetch_session_body("s1", None, None)
+            .await
+            .expect("matching body");
+        assert!(matches!(fetched, RawSessionBody::Fetched { .. }));
+
+        let base_url = serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Length: 4\r\nX-OpenSession-Body-Sha256: 00ff\r\nConnection: close\r\n\r\nbody",
+        )
+        .await;
+        let mut client =
+            ApiClient::new(&base_url, Duration::from_secs(1)).expect("client should construct");
+        client.set_auth("osk_test_token".to_string());
+        let error = client
+            .fetch_session_body("s1", None, None)
+            .await
+            .expect_err("tampered body");
+        assert!(matches!(
+            error,
+            ApiClientError::IntegrityMismatch { ref expected, .. } if expected == "00ff"
+        ));
+
+        let base_url = serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Length: 4\r\nX-OpenSession-Body-Sha256: 00ff\r\nConnection: close\r\n\r\nbody",
+        )
+        .await;
+        let client =
+            ApiClient::new(&base_url, Duration::from_secs(1)).expect("client should construct");
+        let mut stream = client.stream_session_body("s1").await.expect("stream");
+        assert_eq!(
+            stream.next_chunk().await.expect("chunk"),
+            Some(b"body".to_vec())
+        );
+        assert!(matches!(
+            stream.next_chunk().await,
+            Err(ApiClientError::IntegrityMismatch { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn change_password_puts_to_auth_password() {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let addr = listener.local_addr().expect("listener address");
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept request");
+            let mut buf = [0u8; 4096];
+            let read = stream.read(&mut buf).await.expect("read request");
+            let body = r#"{"ok":true}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream
+                .write_all(response.as_bytes())
+                .await
+                .expect("write response");
+            String::from_utf8_lossy(&buf[..read]).into_owned()
+        });
+
+        let mut client = ApiClient::new(&format!("http://{addr}"), Duration::from_secs(1))
+            .expect("client should construct");
+        client.set_auth("osk_test_token".to_string());
+        client
+            .change_password(&ChangePasswordRequest {
+                current_password: "old-pass".into(),
+                new_password: "new-pass".into(),
+            })
+            .await
+            .expect("change password");
+
+        let request = server.await.expect("server task").to_ascii_lowercase();
+        assert!(request.starts_with("put /api/auth/password "));
+    }
+
+    #[tokio::test]
+    async fn stream_session_body_yields_the_whole_body_without_auth() {
+        let base_url = serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Length: 12\r\nContent-Type: application/jsonl\r\nConnection: close\r\n\r\n{\"a\":1}\n{}\n\n",
+        )
+        .await;
+        let client =
+            ApiClient::new(&base_url, Duration::from_secs(1)).expect("client should construct");
+
+        let mut stream = client
+            .stream_session_body("s1")
+            .await
+            .expect("public raw body should stream");
+        let mut body = Vec::new();
+        while let Some(chunk) = stream.next_chunk().await.expect("read chunk") {
+            body.extend(chunk);
+        }
+        assert_eq!(body, b"{\"a\":1}\n{}\n\n");
+    }
+
+    #[tokio::test]
+    async fn authenticated_requests_carry_device_headers() {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let addr = listener.local_addr().expect("listener address");
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept request");
+            let mut buf = [0u8; 4096];
+            let read = stream.read(&mut buf).await.expect("read request");
+            let body = r#"{"devices":[]}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream
+                .write_all(response.as_bytes())
+                .await
+                .expect("write response");
+            String::from_utf8_lossy(&buf[..read]).into_owned()
+        });
+
+        let mut client = ApiClient::new(&format!("http://{addr}"), Duration::from_secs(1))
+            .expect("client should construct");
+        client.set_auth("osk_test_token".to_string());
+        client.set_device("device-1", Some("laptop"));
+
+        let devices = client.list_devices().await.expect("list devices");
+        assert!(devices.devices.is_empty());
+
+        let request = server.await.expect("server task").to_ascii_lowercase();
+        assert!(request.starts_with("get /api/auth/devices "));
+        assert!(request.contains("x-opensession-device-id: device-1"));
+        assert!(request.contains("x-opensession-device-name: laptop"));
+    }
+
+    #[tokio::test]
+    async fn rate_limited_uploads_stream_the_full_body_slowly() {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind test listener");
+        let addr = listener.local_addr().expect("listener address");
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept request");
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !request.ends_with(b"0\r\n\r\n") {
+                let read = stream.read(&mut buf).await.expect("read request");
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..read]);
+            }
+            // The response is irrelevant; only the uploaded request is inspected.
+            let response = "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n";
+            stream
+                .write_all(response.as_bytes())
+                .await
+                .expect("write response");
+            String::from_utf8_lossy(&request).into_owned()
+        });
+
+        let mut client = ApiClient::new(&format!("http://{addr}"), Duration::from_secs(10))
+            .expect("client should construct");
+        client.set_auth("osk_test_token".to_string());
+        client.set_upload_rate_limit(UploadRateLimit::from_kbps(8));
+
+        let payload = "x".repeat(8 * 1024);
+        let started = std::time::Instant::now();
+        let _ = client
+            .publish_handoff(&PublishHandoffRequest {
+                recipient: "bob".to_string(),
+                artifact_uri: "os://artifact/abc".to_string(),
+                canonical_jsonl: payload.clone(),
+                markdown: None,
+                note: None,
+            })
+            .await;
+        let elapsed = started.elapsed();
+
+        let request = server.await.expect("server task");
+        assert!(request.contains("content-type: application/json"));
+        assert_eq!(request.matches('x').count(), payload.len());
+        assert!(
+            elapsed >= Duration::from_millis(750),
+            "8 KiB at 8 KB/s finished in {elapsed:?}"
+        );
+    }
 }
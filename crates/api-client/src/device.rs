@@ -0,0 +1,161 @@
+//! Stable per-machine identity sent with authenticated requests.
+
+use std::io;
+use std::path::Path;
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+
+/// Read the device id stored at `path`, generating and persisting one on
+/// first use.
+pub fn load_or_create_device_id(path: &Path) -> io::Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(existing) if !existing.trim().is_empty() => return Ok(existing.trim().to_string()),
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    let id = uuid::Uuid::new_v4().to_string();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, format!("{id}\n"))?;
+    Ok(id)
+}
+
+/// Human-readable machine name shown next to the device id, if one is known.
+pub fn local_device_name() -> Option<String> {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .chain(std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .find(|name| !name.is_empty())
+}
+
+/// Ed25519 key this machine signs its uploads with, stored at its path as a
+/// base64 seed and registered through `PUT /api/auth/devices/{id}/signing-key`.
+pub struct DeviceSigningKey {
+    key_pair: Ed25519KeyPair,
+}
+
+impl std::fmt::Debug for DeviceSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceSigningKey")
+            .field("public_key", &self.public_key())
+            .finish()
+    }
+}
+
+impl DeviceSigningKey {
+    /// Read the key stored at `path`; `None` if the device has none yet.
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        let raw = match std::fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let seed = BASE64_STANDARD
+            .decode(raw.trim())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Self::from_seed(&seed).map(Some)
+    }
+
+    /// Read the key stored at `path`, generating and persisting one on first
+    /// use. The file is only readable by its owner.
+    pub fn load_or_create(path: &Path) -> io::Result<Self> {
+        if let Some(key) = Self::load(path)? {
+            return Ok(key);
+        }
+        let mut seed = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut seed)
+            .map_err(|_| io::Error::other("failed to generate a signing key"))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        io::Write::write_all(
+            &mut options.open(path)?,
+            format!("{}\n", BASE64_STANDARD.encode(seed)).as_bytes(),
+        )?;
+        Self::from_seed(&seed)
+    }
+
+    fn from_seed(seed: &[u8]) -> io::Result<Self> {
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(seed)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+        Ok(Self { key_pair })
+    }
+
+    /// Base64 public key to register with the server.
+    pub fn public_key(&self) -> String {
+        BASE64_STANDARD.encode(self.key_pair.public_key().as_ref())
+    }
+
+    /// Base64 signature over `message`.
+    pub fn sign(&self, message: &[u8]) -> String {
+        BASE64_STANDARD.encode(self.key_pair.sign(message).as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeviceSigningKey, load_or_create_device_id};
+
+    #[test]
+    fn device_id_is_created_once_and_reused() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("config").join("device-id");
+
+        let first = load_or_create_device_id(&path).expect("create device id");
+        let second = load_or_create_device_id(&path).expect("reuse device id");
+
+        assert_eq!(first, second);
+        assert!(uuid::Uuid::parse_str(&first).is_ok());
+    }
+
+    #[test]
+    fn blank_device_id_file_is_replaced() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("device-id");
+        std::fs::write(&path, "\n").expect("write blank id");
+
+        let id = load_or_create_device_id(&path).expect("create device id");
+
+        assert!(!id.is_empty());
+        assert_eq!(std::fs::read_to_string(&path).expect("read id").trim(), id);
+    }
+
+    #[test]
+    fn signing_key_is_created_once_and_signs_verifiably() {
+        use base64::{Engine, prelude::BASE64_STANDARD};
+        use ring::signature::{ED25519, UnparsedPublicKey};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("device-key");
+        assert!(DeviceSigningKey::load(&path).expect("load").is_none());
+
+        let created = DeviceSigningKey::load_or_create(&path).expect("create key");
+        let reloaded = DeviceSigningKey::load(&path)
+            .expect("load")
+            .expect("key exists");
+        assert_eq!(created.public_key(), reloaded.public_key());
+
+        let signature = BASE64_STANDARD
+            .decode(reloaded.sign(b"message"))
+            .expect("signature base64");
+        let public_key = BASE64_STANDARD
+            .decode(created.public_key())
+            .expect("key base64");
+        assert!(
+            UnparsedPublicKey::new(&ED25519, public_key)
+                .verify(b"message", &signature)
+                .is_ok()
+        );
+    }
+}
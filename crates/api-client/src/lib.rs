@@ -1,6 +1,11 @@
 pub mod client;
+pub mod device;
+mod query;
 pub mod retry;
+mod throttle;
 
-pub use client::{ApiClient, ApiClientError};
+pub use client::{ApiClient, ApiClientError, RawSessionBody, SessionBodyStream};
 pub use opensession_api;
+pub use query::SessionQuery;
 pub use retry::RetryConfig;
+pub use throttle::UploadRateLimit;
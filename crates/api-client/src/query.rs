@@ -0,0 +1,123 @@
+use opensession_api::{SessionListQuery, SortOrder, TimeRange};
+
+/// Builder for [`SessionListQuery`], for use with [`crate::ApiClient::list_sessions`].
+///
+/// ```ignore
+/// let query = SessionQuery::new().tool("codex").has_errors(true).limit(10).build();
+/// let page = client.list_sessions(&query).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SessionQuery {
+    query: SessionListQuery,
+}
+
+impl SessionQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Continue from `SessionListResponse::next_cursor`.
+    pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.query.cursor = Some(cursor.into());
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.query.limit = Some(limit);
+        self
+    }
+
+    pub fn search(mut self, search: impl Into<String>) -> Self {
+        self.query.search = Some(search.into());
+        self
+    }
+
+    pub fn tool(mut self, tool: impl Into<String>) -> Self {
+        self.query.tool = Some(tool.into());
+        self
+    }
+
+    pub fn repo(mut self, git_repo_name: impl Into<String>) -> Self {
+        self.query.git_repo_name = Some(git_repo_name.into());
+        self
+    }
+
+    pub fn sort(mut self, sort: SortOrder) -> Self {
+        self.query.sort = Some(sort);
+        self
+    }
+
+    pub fn time_range(mut self, time_range: TimeRange) -> Self {
+        self.query.time_range = Some(time_range);
+        self
+    }
+
+    /// Model filter; `*` matches any run of characters.
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.query.model = Some(model.into());
+        self
+    }
+
+    /// Only sessions created at or after this ISO8601 timestamp.
+    pub fn since(mut self, since: impl Into<String>) -> Self {
+        self.query.since = Some(since.into());
+        self
+    }
+
+    /// Only sessions created before this ISO8601 timestamp.
+    pub fn before(mut self, before: impl Into<String>) -> Self {
+        self.query.before = Some(before.into());
+        self
+    }
+
+    /// Only sessions that modified this file path.
+    pub fn touches(mut self, path: impl Into<String>) -> Self {
+        self.query.touches = Some(path.into());
+        self
+    }
+
+    pub fn has_errors(mut self, has_errors: bool) -> Self {
+        self.query.has_errors = Some(has_errors);
+        self
+    }
+
+    /// Commit SHA or prefix.
+    pub fn commit(mut self, git_commit: impl Into<String>) -> Self {
+        self.query.git_commit = Some(git_commit.into());
+        self
+    }
+
+    pub fn build(self) -> SessionListQuery {
+        self.query
+    }
+}
+
+impl From<SessionQuery> for SessionListQuery {
+    fn from(builder: SessionQuery) -> Self {
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SessionQuery;
+    use opensession_api::SortOrder;
+
+    #[test]
+    fn builder_sets_only_requested_filters() {
+        let query = SessionQuery::new()
+            .tool("codex")
+            .sort(SortOrder::Best)
+            .has_errors(false)
+            .limit(5)
+            .build();
+
+        assert_eq!(query.page, 1);
+        assert_eq!(query.per_page, 20);
+        assert_eq!(query.tool.as_deref(), Some("codex"));
+        assert_eq!(query.sort, Some(SortOrder::Best));
+        assert_eq!(query.has_errors, Some(false));
+        assert_eq!(query.page_size(), 5);
+        assert!(query.search.is_none() && query.cursor.is_none());
+    }
+}
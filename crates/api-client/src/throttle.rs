@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use futures_util::stream;
+use tokio::time::Instant;
+
+/// Largest chunk handed to the transport at once, so pacing stays smooth.
+const MAX_CHUNK_BYTES: usize = 64 * 1024;
+const MIN_CHUNK_BYTES: usize = 1024;
+
+/// Upload bandwidth cap for request bodies sent by [`crate::ApiClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadRateLimit {
+    bytes_per_sec: u64,
+}
+
+impl UploadRateLimit {
+    /// Limit in kilobytes (1024 bytes) per second; `0` means unlimited.
+    pub fn from_kbps(kbps: u32) -> Option<Self> {
+        (kbps > 0).then(|| Self {
+            bytes_per_sec: u64::from(kbps) * 1024,
+        })
+    }
+
+    pub fn bytes_per_sec(self) -> u64 {
+        self.bytes_per_sec
+    }
+
+    /// About eight chunks per second, clamped to a sensible size.
+    fn chunk_len(self) -> usize {
+        usize::try_from(self.bytes_per_sec / 8)
+            .unwrap_or(MAX_CHUNK_BYTES)
+            .clamp(MIN_CHUNK_BYTES, MAX_CHUNK_BYTES)
+    }
+
+    /// Earliest time the byte at `offset` may be sent.
+    fn send_at(self, started: Instant, offset: usize) -> Instant {
+        let secs = offset as f64 / self.bytes_per_sec as f64;
+        started + Duration::from_secs_f64(secs)
+    }
+}
+
+/// Stream `bytes` as a request body no faster than `limit`.
+pub(crate) fn throttled_body(bytes: Vec<u8>, limit: UploadRateLimit) -> reqwest::Body {
+    let chunk_len = limit.chunk_len();
+    let state = (bytes, 0usize, None::<Instant>);
+    let chunks = stream::unfold(state, move |(bytes, offset, started)| async move {
+        if offset >= bytes.len() {
+            return None;
+        }
+        let started = match started {
+            Some(started) => {
+                tokio::time::sleep_until(limit.send_at(started, offset)).await;
+                started
+            }
+            None => Instant::now(),
+        };
+        let end = (offset + chunk_len).min(bytes.len());
+        let chunk = bytes[offset..end].to_vec();
+        Some((Ok::<_, std::io::Error>(chunk), (bytes, end, Some(started))))
+    });
+    reqwest::Body::wrap_stream(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UploadRateLimit;
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    #[test]
+    fn zero_kbps_is_unlimited() {
+        assert_eq!(UploadRateLimit::from_kbps(0), None);
+        assert_eq!(
+            UploadRateLimit::from_kbps(2).map(UploadRateLimit::bytes_per_sec),
+            Some(2048)
+        );
+    }
+
+    #[test]
+    fn chunks_are_clamped_and_paced_by_offset() {
+        let slow = UploadRateLimit::from_kbps(1).expect("limit");
+        let fast = UploadRateLimit::from_kbps(100_000).expect("limit");
+        assert_eq!(slow.chunk_len(), super::MIN_CHUNK_BYTES);
+        assert_eq!(fast.chunk_len(), super::MAX_CHUNK_BYTES);
+
+        let started = Instant::now();
+        assert_eq!(slow.send_at(started, 0), started);
+        assert_eq!(
+            slow.send_at(started, 3 * 1024),
+            started + Duration::from_secs(3)
+        );
+    }
+}
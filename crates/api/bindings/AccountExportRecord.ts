@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DeviceSummary } from "./DeviceSummary";
+import type { MetadataEntry } from "./MetadataEntry";
+import type { SessionAttachment } from "./SessionAttachment";
+import type { SessionSummary } from "./SessionSummary";
+import type { UserSettingsResponse } from "./UserSettingsResponse";
+
+/**
+ * One line of `GET /api/auth/export`. The stream starts with the `account`
+ * line, followed by a `session` line per uploaded session, each trailed by
+ * that session's `attachment` lines.
+ */
+export type AccountExportRecord = { "kind": "account", account: UserSettingsResponse, devices: Array<DeviceSummary>, metadata: Array<MetadataEntry>, } | { "kind": "session", session: SessionSummary, 
+/**
+ * Stored HAIL JSONL body.
+ */
+body: string, } | { "kind": "attachment", attachment: SessionAttachment, content_base64: string, };
@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QuotaNotice } from "./QuotaNotice";
+import type { SessionValidationError } from "./SessionValidationError";
+import type { UploadResponse } from "./UploadResponse";
+
+/**
+ * Outcome of one item in a batch upload. Exactly one of `upload`/`error` is set.
+ */
+export type BatchUploadItemResult = { session_id: string, upload?: UploadResponse | null, error?: string | null, 
+/**
+ * Set when the item was rejected because its content matches this
+ * already-uploaded session. Retry with `force` to store it anyway.
+ */
+duplicate_of?: string | null, 
+/**
+ * Events that matched the server's or the uploader's teams' upload
+ * exclude patterns, when the item was rejected by a privacy policy.
+ */
+policy_violations?: Array<SessionValidationError>, 
+/**
+ * Set when the item was rejected because it would push one of the
+ * uploader's teams past a hard storage quota.
+ */
+quota_exceeded?: QuotaNotice | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BatchUploadItemResult } from "./BatchUploadItemResult";
+
+/**
+ * Returned by `POST /api/sessions/batch` — one result per submitted session, in order.
+ */
+export type BatchUploadResponse = { results: Array<BatchUploadItemResult>, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Returned by `GET /api/capabilities` — runtime feature availability.
+ */
+export type CapabilitiesResponse = { auth_enabled: boolean, parse_preview_enabled: boolean, register_targets: Array<string>, share_modes: Array<string>, 
+/**
+ * Base64 Ed25519 public key that verifies `SessionDetail.body_signature`.
+ */
+body_signing_key?: string | null, };
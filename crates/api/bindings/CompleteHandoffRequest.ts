@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request body for `POST /api/handoffs/{id}/complete`.
+ */
+export type CompleteHandoffRequest = { 
+/**
+ * Uploaded session that carried out the handed-off work.
+ */
+session_id?: string | null, };
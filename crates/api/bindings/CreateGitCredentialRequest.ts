@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request for `POST /api/auth/git-credentials`.
+ */
+export type CreateGitCredentialRequest = { label: string, host: string, path_prefix?: string | null, header_name: string, header_value: string, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request for `POST /api/teams/{team_id}/guest-tokens`.
+ */
+export type CreateGuestTokenRequest = { 
+/**
+ * Who or what the token is for, e.g. "grafana dashboard".
+ */
+label: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GuestTokenSummary } from "./GuestTokenSummary";
+
+/**
+ * Response for `POST /api/teams/{team_id}/guest-tokens`. The token is visible
+ * only at creation time.
+ */
+export type CreateGuestTokenResponse = { token: string, guest_token: GuestTokenSummary, };
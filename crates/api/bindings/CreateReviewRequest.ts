@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request body for `POST /api/sessions/{id}/review-requests` — ask a user to
+ * review an uploaded session.
+ */
+export type CreateReviewRequest = { 
+/**
+ * Reviewer nickname, with or without a leading `@`.
+ */
+reviewer: string, note?: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Structured desktop bridge error payload.
+ */
+export type DesktopApiError = { code: string, status: number, message: string, details?: Record<string, any> | null, };
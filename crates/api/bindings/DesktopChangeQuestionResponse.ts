@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopChangeReaderScope } from "./DesktopChangeReaderScope";
+import type { DesktopSummaryProviderId } from "./DesktopSummaryProviderId";
+
+export type DesktopChangeQuestionResponse = { session_id: string, question: string, scope: DesktopChangeReaderScope, answer: string, citations: Array<string>, provider?: DesktopSummaryProviderId | null, warning?: string | null, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopChangeReaderScope } from "./DesktopChangeReaderScope";
+
+export type DesktopChangeReaderTtsRequest = { text: string, session_id?: string | null, scope?: DesktopChangeReaderScope | null, };
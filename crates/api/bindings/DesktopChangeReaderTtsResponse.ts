@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type DesktopChangeReaderTtsResponse = { mime_type: string, audio_base64: string, warning?: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Desktop handoff build request payload.
+ */
+export type DesktopHandoffBuildRequest = { session_id: string, pin_latest: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Desktop handoff build response payload.
+ */
+export type DesktopHandoffBuildResponse = { artifact_uri: string, pinned_alias?: string | null, download_file_name?: string | null, download_content?: string | null, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopLifecycleCleanupState } from "./DesktopLifecycleCleanupState";
+
+export type DesktopLifecycleCleanupStatusResponse = { state: DesktopLifecycleCleanupState, deleted_sessions: number, deleted_summaries: number, message?: string | null, started_at?: string | null, finished_at?: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Desktop quick-share request payload.
+ */
+export type DesktopQuickShareRequest = { session_id: string, remote?: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Desktop quick-share response payload.
+ */
+export type DesktopQuickShareResponse = { source_uri: string, shared_uri: string, remote: string, push_cmd: string, pushed: boolean, auto_push_consent: boolean, };
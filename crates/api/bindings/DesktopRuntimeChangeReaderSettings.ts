@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopChangeReaderScope } from "./DesktopChangeReaderScope";
+import type { DesktopRuntimeChangeReaderVoiceSettings } from "./DesktopRuntimeChangeReaderVoiceSettings";
+
+export type DesktopRuntimeChangeReaderSettings = { enabled: boolean, scope: DesktopChangeReaderScope, qa_enabled: boolean, max_context_chars: number, voice: DesktopRuntimeChangeReaderVoiceSettings, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopChangeReaderVoiceProvider } from "./DesktopChangeReaderVoiceProvider";
+
+export type DesktopRuntimeChangeReaderVoiceSettingsUpdate = { enabled: boolean, provider: DesktopChangeReaderVoiceProvider, model: string, voice: string, api_key?: string | null, };
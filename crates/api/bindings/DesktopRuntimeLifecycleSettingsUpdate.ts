@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type DesktopRuntimeLifecycleSettingsUpdate = { enabled: boolean, session_ttl_days: number, summary_ttl_days: number, cleanup_interval_secs: bigint, };
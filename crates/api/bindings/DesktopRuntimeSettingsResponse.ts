@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopRuntimeChangeReaderSettings } from "./DesktopRuntimeChangeReaderSettings";
+import type { DesktopRuntimeLifecycleSettings } from "./DesktopRuntimeLifecycleSettings";
+import type { DesktopRuntimeSummarySettings } from "./DesktopRuntimeSummarySettings";
+import type { DesktopRuntimeSummaryUiConstraints } from "./DesktopRuntimeSummaryUiConstraints";
+import type { DesktopRuntimeVectorSearchSettings } from "./DesktopRuntimeVectorSearchSettings";
+
+/**
+ * Desktop runtime settings payload for App settings UI.
+ */
+export type DesktopRuntimeSettingsResponse = { session_default_view: string, summary: DesktopRuntimeSummarySettings, vector_search: DesktopRuntimeVectorSearchSettings, change_reader: DesktopRuntimeChangeReaderSettings, lifecycle: DesktopRuntimeLifecycleSettings, ui_constraints: DesktopRuntimeSummaryUiConstraints, };
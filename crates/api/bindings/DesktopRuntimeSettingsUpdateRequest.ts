@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopRuntimeChangeReaderSettingsUpdate } from "./DesktopRuntimeChangeReaderSettingsUpdate";
+import type { DesktopRuntimeLifecycleSettingsUpdate } from "./DesktopRuntimeLifecycleSettingsUpdate";
+import type { DesktopRuntimeSummarySettingsUpdate } from "./DesktopRuntimeSummarySettingsUpdate";
+import type { DesktopRuntimeVectorSearchSettingsUpdate } from "./DesktopRuntimeVectorSearchSettingsUpdate";
+
+/**
+ * Desktop runtime settings update request.
+ */
+export type DesktopRuntimeSettingsUpdateRequest = { session_default_view?: string | null, summary?: DesktopRuntimeSummarySettingsUpdate | null, vector_search?: DesktopRuntimeVectorSearchSettingsUpdate | null, change_reader?: DesktopRuntimeChangeReaderSettingsUpdate | null, lifecycle?: DesktopRuntimeLifecycleSettingsUpdate | null, };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopSummaryBatchExecutionMode } from "./DesktopSummaryBatchExecutionMode";
+import type { DesktopSummaryBatchScope } from "./DesktopSummaryBatchScope";
+
+export type DesktopRuntimeSummaryBatchSettings = { execution_mode: DesktopSummaryBatchExecutionMode, scope: DesktopSummaryBatchScope, recent_days: number, };
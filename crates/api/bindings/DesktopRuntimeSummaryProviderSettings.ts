@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopSummaryProviderId } from "./DesktopSummaryProviderId";
+import type { DesktopSummaryProviderTransport } from "./DesktopSummaryProviderTransport";
+
+export type DesktopRuntimeSummaryProviderSettings = { id: DesktopSummaryProviderId, transport: DesktopSummaryProviderTransport, endpoint: string, model: string, };
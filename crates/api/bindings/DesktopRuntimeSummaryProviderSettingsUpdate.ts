@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopSummaryProviderId } from "./DesktopSummaryProviderId";
+
+export type DesktopRuntimeSummaryProviderSettingsUpdate = { id: DesktopSummaryProviderId, endpoint: string, model: string, };
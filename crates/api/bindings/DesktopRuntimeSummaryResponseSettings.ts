@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopSummaryOutputShape } from "./DesktopSummaryOutputShape";
+import type { DesktopSummaryResponseStyle } from "./DesktopSummaryResponseStyle";
+
+export type DesktopRuntimeSummaryResponseSettings = { style: DesktopSummaryResponseStyle, shape: DesktopSummaryOutputShape, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopRuntimeSummaryBatchSettings } from "./DesktopRuntimeSummaryBatchSettings";
+import type { DesktopRuntimeSummaryPromptSettings } from "./DesktopRuntimeSummaryPromptSettings";
+import type { DesktopRuntimeSummaryProviderSettings } from "./DesktopRuntimeSummaryProviderSettings";
+import type { DesktopRuntimeSummaryResponseSettings } from "./DesktopRuntimeSummaryResponseSettings";
+import type { DesktopRuntimeSummaryStorageSettings } from "./DesktopRuntimeSummaryStorageSettings";
+import type { DesktopSummarySourceMode } from "./DesktopSummarySourceMode";
+
+export type DesktopRuntimeSummarySettings = { provider: DesktopRuntimeSummaryProviderSettings, prompt: DesktopRuntimeSummaryPromptSettings, response: DesktopRuntimeSummaryResponseSettings, storage: DesktopRuntimeSummaryStorageSettings, source_mode: DesktopSummarySourceMode, batch: DesktopRuntimeSummaryBatchSettings, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopRuntimeSummaryBatchSettingsUpdate } from "./DesktopRuntimeSummaryBatchSettingsUpdate";
+import type { DesktopRuntimeSummaryPromptSettingsUpdate } from "./DesktopRuntimeSummaryPromptSettingsUpdate";
+import type { DesktopRuntimeSummaryProviderSettingsUpdate } from "./DesktopRuntimeSummaryProviderSettingsUpdate";
+import type { DesktopRuntimeSummaryResponseSettingsUpdate } from "./DesktopRuntimeSummaryResponseSettingsUpdate";
+import type { DesktopRuntimeSummaryStorageSettingsUpdate } from "./DesktopRuntimeSummaryStorageSettingsUpdate";
+import type { DesktopSummarySourceMode } from "./DesktopSummarySourceMode";
+
+export type DesktopRuntimeSummarySettingsUpdate = { provider: DesktopRuntimeSummaryProviderSettingsUpdate, prompt: DesktopRuntimeSummaryPromptSettingsUpdate, response: DesktopRuntimeSummaryResponseSettingsUpdate, storage: DesktopRuntimeSummaryStorageSettingsUpdate, source_mode: DesktopSummarySourceMode, batch: DesktopRuntimeSummaryBatchSettingsUpdate, };
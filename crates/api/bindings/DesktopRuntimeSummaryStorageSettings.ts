@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopSummaryStorageBackend } from "./DesktopSummaryStorageBackend";
+import type { DesktopSummaryTriggerMode } from "./DesktopSummaryTriggerMode";
+
+export type DesktopRuntimeSummaryStorageSettings = { trigger: DesktopSummaryTriggerMode, backend: DesktopSummaryStorageBackend, };
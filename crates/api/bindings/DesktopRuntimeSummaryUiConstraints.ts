@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopSummarySourceMode } from "./DesktopSummarySourceMode";
+
+export type DesktopRuntimeSummaryUiConstraints = { source_mode_locked: boolean, source_mode_locked_value: DesktopSummarySourceMode, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopVectorChunkingMode } from "./DesktopVectorChunkingMode";
+import type { DesktopVectorSearchGranularity } from "./DesktopVectorSearchGranularity";
+import type { DesktopVectorSearchProvider } from "./DesktopVectorSearchProvider";
+
+export type DesktopRuntimeVectorSearchSettings = { enabled: boolean, provider: DesktopVectorSearchProvider, model: string, endpoint: string, granularity: DesktopVectorSearchGranularity, chunking_mode: DesktopVectorChunkingMode, chunk_size_lines: number, chunk_overlap_lines: number, top_k_chunks: number, top_k_sessions: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Session summary payload returned by desktop runtime.
+ */
+export type DesktopSessionSummaryResponse = { session_id: string, summary?: any, source_details?: any, diff_tree: any[], source_kind?: string | null, generation_kind?: string | null, error?: string | null, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopSummaryBatchState } from "./DesktopSummaryBatchState";
+
+export type DesktopSummaryBatchStatusResponse = { state: DesktopSummaryBatchState, processed_sessions: number, total_sessions: number, failed_sessions: number, message?: string | null, started_at?: string | null, finished_at?: string | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopSummaryProviderId } from "./DesktopSummaryProviderId";
+import type { DesktopSummaryProviderTransport } from "./DesktopSummaryProviderTransport";
+
+/**
+ * Local summary provider detection result for desktop setup/settings.
+ */
+export type DesktopSummaryProviderDetectResponse = { detected: boolean, provider?: DesktopSummaryProviderId | null, transport?: DesktopSummaryProviderTransport | null, model?: string | null, endpoint?: string | null, };
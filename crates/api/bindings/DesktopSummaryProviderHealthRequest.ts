@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Connection check input for the HTTP (Ollama) summary provider being edited.
+ */
+export type DesktopSummaryProviderHealthRequest = { endpoint: string, model: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Ollama summary provider reachability and installed models.
+ */
+export type DesktopSummaryProviderHealthResponse = { endpoint: string, model: string, reachable: boolean, model_installed: boolean, models: Array<string>, message?: string | null, };
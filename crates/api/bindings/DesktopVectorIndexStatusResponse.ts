@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopVectorIndexState } from "./DesktopVectorIndexState";
+
+export type DesktopVectorIndexStatusResponse = { state: DesktopVectorIndexState, processed_sessions: number, total_sessions: number, message?: string | null, started_at?: string | null, finished_at?: string | null, };
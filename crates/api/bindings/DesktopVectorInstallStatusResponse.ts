@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopVectorInstallState } from "./DesktopVectorInstallState";
+
+export type DesktopVectorInstallStatusResponse = { state: DesktopVectorInstallState, model: string, progress_pct: number, message?: string | null, };
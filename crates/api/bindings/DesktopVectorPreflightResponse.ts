@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopVectorInstallState } from "./DesktopVectorInstallState";
+import type { DesktopVectorSearchProvider } from "./DesktopVectorSearchProvider";
+
+export type DesktopVectorPreflightResponse = { provider: DesktopVectorSearchProvider, endpoint: string, model: string, ollama_reachable: boolean, model_installed: boolean, install_state: DesktopVectorInstallState, progress_pct: number, message?: string | null, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DesktopVectorSessionMatch } from "./DesktopVectorSessionMatch";
+
+export type DesktopVectorSearchResponse = { query: string, sessions: Array<DesktopVectorSessionMatch>, next_cursor?: string | null, total_candidates: number, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionSummary } from "./SessionSummary";
+
+export type DesktopVectorSessionMatch = { session: SessionSummary, score: number, chunk_id: string, start_line: number, end_line: number, snippet: string, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A machine that has sent authenticated requests for the user.
+ */
+export type DeviceSummary = { id: string, name: string | null, first_seen_at: string, last_seen_at: string, revoked_at: string | null, 
+/**
+ * Sessions uploaded from this device.
+ */
+session_count: bigint, 
+/**
+ * Whether this is the device making the request.
+ */
+current: boolean, 
+/**
+ * Base64 Ed25519 public key that signs this device's uploads.
+ */
+signing_key?: string | null, };
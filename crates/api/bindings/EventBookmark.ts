@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A bookmarked event, stored as the synced metadata entry
+ * `bookmark:<session-id>#event-<event-id>` whose value is the note.
+ */
+export type EventBookmark = { session_id: string, event_id: string, note: string | null, 
+/**
+ * Unix milliseconds of the last change.
+ */
+updated_at: bigint, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EventBookmark } from "./EventBookmark";
+
+/**
+ * Response for `GET /api/sessions/:id/bookmarks` — your bookmarked events of
+ * the session, by event id.
+ */
+export type EventBookmarkListResponse = { bookmarks: Array<EventBookmark>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Public metadata for a user-managed git credential.
+ */
+export type GitCredentialSummary = { id: string, label: string, host: string, path_prefix: string, header_name: string, created_at: string, updated_at: string, last_used_at: string | null, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Public metadata for a team's read-only guest token.
+ */
+export type GuestTokenSummary = { id: string, team_id: string, label: string, 
+/**
+ * Nickname of the member who created the token.
+ */
+created_by: string, created_at: string, last_used_at: string | null, };
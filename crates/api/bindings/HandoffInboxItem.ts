@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HandoffStatus } from "./HandoffStatus";
+
+/**
+ * A handoff delivered to a user's inbox.
+ */
+export type HandoffInboxItem = { id: string, sender_nickname: string, recipient_nickname: string, artifact_uri: string, canonical_jsonl: string, markdown: string | null, note: string | null, status: HandoffStatus, claimed_at: string | null, completed_at: string | null, 
+/**
+ * Session that picked up the handoff, set on completion.
+ */
+result_session_id: string | null, created_at: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HandoffInboxItem } from "./HandoffInboxItem";
+
+/**
+ * Response for `GET /api/handoffs/inbox`.
+ */
+export type HandoffInboxResponse = { items: Array<HandoffInboxItem>, };
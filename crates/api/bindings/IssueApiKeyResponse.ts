@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Response for API key issuance. The key is visible only at issuance time.
+ */
+export type IssueApiKeyResponse = { api_key: string, };
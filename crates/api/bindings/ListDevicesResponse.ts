@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DeviceSummary } from "./DeviceSummary";
+
+/**
+ * Response for `GET /api/auth/devices`.
+ */
+export type ListDevicesResponse = { devices: Array<DeviceSummary>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GitCredentialSummary } from "./GitCredentialSummary";
+
+/**
+ * Response for `GET /api/auth/git-credentials`.
+ */
+export type ListGitCredentialsResponse = { credentials: Array<GitCredentialSummary>, };
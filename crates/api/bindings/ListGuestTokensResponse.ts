@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GuestTokenSummary } from "./GuestTokenSummary";
+
+/**
+ * Response for `GET /api/teams/{team_id}/guest-tokens`.
+ */
+export type ListGuestTokensResponse = { guest_tokens: Array<GuestTokenSummary>, };
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LiveViewer } from "./LiveViewer";
+
+/**
+ * A session its owner is sharing while the agent is still running.
+ */
+export type LiveSessionInfo = { session_id: string, owner_nickname: string, tool: string | null, title: string | null, 
+/**
+ * RFC 3339.
+ */
+started_at: string, 
+/**
+ * RFC 3339; the last time the owner appended events.
+ */
+updated_at: string, 
+/**
+ * Events appended since sharing started, including ones no longer buffered.
+ */
+event_count: bigint, 
+/**
+ * Distinct viewers watching right now, the owner excluded.
+ */
+viewers: Array<LiveViewer>, };
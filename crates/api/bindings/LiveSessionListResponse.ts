@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LiveSessionInfo } from "./LiveSessionInfo";
+
+/**
+ * Response for `GET /api/live`.
+ */
+export type LiveSessionListResponse = { sessions: Array<LiveSessionInfo>, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LiveSessionInfo } from "./LiveSessionInfo";
+import type { LiveViewer } from "./LiveViewer";
+
+/**
+ * One server-sent event on `GET /api/sessions/:id/live`, tagged by `type`.
+ *
+ * A stream opens with a `snapshot`; a viewer that falls behind gets a fresh
+ * one instead of the updates it missed.
+ */
+export type LiveUpdate = { "type": "snapshot", info: LiveSessionInfo, agent: any, context: any, 
+/**
+ * The buffered events, oldest first.
+ */
+events: any[], } | { "type": "events", events: any[], event_count: bigint, } | { "type": "presence", viewers: Array<LiveViewer>, } | { "type": "ended", uploaded: boolean, };
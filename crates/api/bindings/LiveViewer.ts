@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Someone watching a live session.
+ */
+export type LiveViewer = { nickname: string, 
+/**
+ * RFC 3339.
+ */
+joined_at: string, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LocalReviewCommit } from "./LocalReviewCommit";
+import type { LocalReviewPrMeta } from "./LocalReviewPrMeta";
+import type { LocalReviewSession } from "./LocalReviewSession";
+
+/**
+ * Local review bundle generated from a PR range.
+ */
+export type LocalReviewBundle = { review_id: string, generated_at: string, pr: LocalReviewPrMeta, commits: Array<LocalReviewCommit>, sessions: Array<LocalReviewSession>, };
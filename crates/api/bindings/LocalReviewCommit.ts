@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LocalReviewReviewerDigest } from "./LocalReviewReviewerDigest";
+import type { LocalReviewSemanticSummary } from "./LocalReviewSemanticSummary";
+
+/**
+ * Commit row in a local review bundle.
+ */
+export type LocalReviewCommit = { sha: string, title: string, author_name: string, author_email: string, authored_at: string, session_ids: Array<string>, reviewer_digest: LocalReviewReviewerDigest, semantic_summary?: LocalReviewSemanticSummary | null, };
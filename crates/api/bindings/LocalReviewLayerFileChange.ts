@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Layer/file summary section for local review semantic payloads.
+ */
+export type LocalReviewLayerFileChange = { layer: string, summary: string, files: Array<string>, };
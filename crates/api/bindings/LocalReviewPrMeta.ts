@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * PR metadata for a local review bundle.
+ */
+export type LocalReviewPrMeta = { url: string, owner: string, repo: string, number: bigint, remote: string, base_sha: string, head_sha: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LocalReviewReviewerQa } from "./LocalReviewReviewerQa";
+
+/**
+ * Reviewer-focused digest extracted from mapped sessions for a commit.
+ */
+export type LocalReviewReviewerDigest = { qa: Array<LocalReviewReviewerQa>, modified_files: Array<string>, test_files: Array<string>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Reviewer-focused digest extracted from mapped sessions for a commit.
+ */
+export type LocalReviewReviewerQa = { question: string, answer?: string | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LocalReviewLayerFileChange } from "./LocalReviewLayerFileChange";
+
+/**
+ * Commit-level semantic summary used when session mappings are weak or absent.
+ */
+export type LocalReviewSemanticSummary = { changes: string, auth_security: string, layer_file_changes: Array<LocalReviewLayerFileChange>, source_kind: string, generation_kind: string, provider: string, model?: string | null, error?: string | null, diff_tree: any[], };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Session payload mapped into a local review bundle.
+ */
+export type LocalReviewSession = { session_id: string, ledger_ref: string, hail_path: string, commit_shas: Array<string>, session: any, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One synced metadata entry (bookmark, note, alias, saved filter, ...).
+ *
+ * Keys are namespaced by convention, e.g. `bookmark:<session-id>`, or
+ * `bookmark:<session-id>#event-<event-id>` for one event (see
+ * [`event_bookmark_key`]).
+ */
+export type MetadataEntry = { key: string, 
+/**
+ * `None` marks a deletion.
+ */
+value: string | null, 
+/**
+ * Writer's clock in unix milliseconds; the newest write wins.
+ */
+updated_at: bigint, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Response for OAuth link initiation (redirect URL).
+ */
+export type OAuthLinkResponse = { url: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Candidate parser ranked by detection confidence.
+ */
+export type ParseCandidate = { id: string, confidence: number, reason: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ParseCandidate } from "./ParseCandidate";
+
+/**
+ * Structured parser preview error response.
+ */
+export type ParsePreviewErrorResponse = { code: string, message: string, parser_candidates?: Array<ParseCandidate>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ParseSource } from "./ParseSource";
+
+/**
+ * Request body for `POST /api/parse/preview`.
+ */
+export type ParsePreviewRequest = { source: ParseSource, parser_hint?: string | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ParseCandidate } from "./ParseCandidate";
+import type { ParseSource } from "./ParseSource";
+
+/**
+ * Response body for `POST /api/parse/preview`.
+ */
+export type ParsePreviewResponse = { parser_used: string, parser_candidates: Array<ParseCandidate>, session: any, source: ParseSource, warnings: Array<string>, native_adapter?: string | null, };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PlaybookKind } from "./PlaybookKind";
+
+/**
+ * A reusable prompt or handoff template shared by everyone on a server.
+ *
+ * The body may contain `{{placeholder}}` variables filled in when applied.
+ */
+export type Playbook = { name: string, kind: PlaybookKind, title: string | null, description: string | null, body: string, author_nickname: string | null, created_at: string, updated_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a playbook body is meant for.
+ */
+export type PlaybookKind = "prompt" | "handoff_template";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Playbook } from "./Playbook";
+
+/**
+ * Response for `GET /api/playbooks`, ordered by name.
+ */
+export type PlaybookListResponse = { playbooks: Array<Playbook>, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request body for `POST /api/handoffs` — send a handoff artifact to a user's inbox.
+ */
+export type PublishHandoffRequest = { 
+/**
+ * Recipient nickname, with or without a leading `@`.
+ */
+recipient: string, 
+/**
+ * Local artifact URI (`os://artifact/<sha256>`).
+ */
+artifact_uri: string, 
+/**
+ * Canonical summary JSONL of the artifact.
+ */
+canonical_jsonl: string, markdown?: string | null, note?: string | null, };
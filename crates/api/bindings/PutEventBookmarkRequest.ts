@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request body for `PUT /api/sessions/:id/events/:event_id/bookmark`.
+ */
+export type PutEventBookmarkRequest = { note: string | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PlaybookKind } from "./PlaybookKind";
+
+/**
+ * Request body for `PUT /api/playbooks/:name` — create or replace a playbook.
+ */
+export type PutPlaybookRequest = { kind: PlaybookKind, title: string | null, description: string | null, body: string, };
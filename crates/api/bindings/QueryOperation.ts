@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionListQuery } from "./SessionListQuery";
+
+/**
+ * One read in a `POST /api/query` batch, tagged by `op`.
+ *
+ * Each operation mirrors an existing REST read, so a view can fetch
+ * everything it needs in one round trip without a bespoke endpoint.
+ */
+export type QueryOperation = { "op": "sessions" } & SessionListQuery | { "op": "session", id: string, } | { "op": "session_repos" };
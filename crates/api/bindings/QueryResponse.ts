@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QueryResult } from "./QueryResult";
+
+/**
+ * Response for `POST /api/query`; `results[i]` answers `operations[i]`.
+ */
+export type QueryResponse = { results: Array<QueryResult>, };
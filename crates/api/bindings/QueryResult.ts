@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ApiError } from "./ApiError";
+import type { SessionDetail } from "./SessionDetail";
+import type { SessionListResponse } from "./SessionListResponse";
+import type { SessionRepoListResponse } from "./SessionRepoListResponse";
+
+/**
+ * Result of one [`QueryOperation`], tagged with the same `op` (or `error`).
+ */
+export type QueryResult = { "op": "sessions" } & SessionListResponse | { "op": "session" } & SessionDetail | { "op": "session_repos" } & SessionRepoListResponse | { "op": "error" } & ApiError;
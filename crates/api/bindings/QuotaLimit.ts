@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Soft and hard limit for one resource; `None` is unlimited.
+ *
+ * Crossing the soft limit only warns; an upload that would cross the hard
+ * limit is rejected.
+ */
+export type QuotaLimit = { soft?: bigint | null, hard?: bigint | null, };
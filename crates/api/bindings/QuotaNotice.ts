@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QuotaResource } from "./QuotaResource";
+
+/**
+ * A team at or over one of its quota limits.
+ */
+export type QuotaNotice = { team_id: string, resource: QuotaResource, 
+/**
+ * Usage including the upload being checked, when there is one.
+ */
+used: bigint, limit: bigint, };
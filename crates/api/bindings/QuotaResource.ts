@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a team storage quota limits.
+ */
+export type QuotaResource = "body_bytes" | "sessions";
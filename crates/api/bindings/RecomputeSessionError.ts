@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A session whose stored body could not be re-derived.
+ */
+export type RecomputeSessionError = { session_id: string, error: string, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RecomputeSessionError } from "./RecomputeSessionError";
+
+/**
+ * Returned by `POST /api/admin/sessions/recompute`.
+ */
+export type RecomputeSessionsResponse = { scanned: number, 
+/**
+ * Sessions whose stored columns differed from the recomputed values.
+ */
+updated: number, errors: Array<RecomputeSessionError>, 
+/**
+ * Pass as `after` to process the next batch; absent once every session was scanned.
+ */
+next_after?: string | null, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request for `PUT /api/auth/devices/{id}/signing-key`.
+ */
+export type RegisterDeviceKeyRequest = { 
+/**
+ * Base64 Ed25519 public key (32 bytes).
+ */
+public_key: string, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReviewRequestStatus } from "./ReviewRequestStatus";
+
+/**
+ * Request body for `POST /api/review-requests/{id}/resolve`.
+ */
+export type ResolveReviewRequest = { 
+/**
+ * `approved` or `changes_requested`.
+ */
+status: ReviewRequestStatus, comment?: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What happens to a session once it is older than its retention period.
+ */
+export type RetentionAction = "delete" | "anonymize";
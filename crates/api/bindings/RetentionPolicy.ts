@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RetentionAction } from "./RetentionAction";
+
+/**
+ * How long a team keeps uploaded sessions.
+ */
+export type RetentionPolicy = { 
+/**
+ * Days after upload a session is kept; `0` keeps sessions forever.
+ */
+retain_days: number, action: RetentionAction, 
+/**
+ * Keep sessions the uploader or a teammate bookmarked (a
+ * `bookmark:<session-id>` metadata entry) or bookmarked an event of.
+ */
+exempt_bookmarked: boolean, 
+/**
+ * Keep sessions that are part of a handoff chain or other link.
+ */
+exempt_linked: boolean, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RetentionAction } from "./RetentionAction";
+
+/**
+ * A session the next retention run would delete or anonymize.
+ */
+export type RetentionPreviewItem = { session_id: string, title: string | null, uploaded_at: string, action: RetentionAction, };
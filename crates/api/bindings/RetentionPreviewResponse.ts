@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RetentionPolicy } from "./RetentionPolicy";
+import type { RetentionPreviewItem } from "./RetentionPreviewItem";
+
+/**
+ * Response for `GET /api/teams/:team_id/retention/preview` — a dry run over
+ * sessions uploaded by the team's members. A member in several teams is
+ * governed by the longest of their teams' policies, so `sessions` can differ
+ * from what this team's policy alone would select.
+ */
+export type RetentionPreviewResponse = { team_id: string, policy: RetentionPolicy, is_default: boolean, sessions: Array<RetentionPreviewItem>, 
+/**
+ * Expired sessions kept because they are bookmarked or linked.
+ */
+exempt: number, };
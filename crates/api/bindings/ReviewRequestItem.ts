@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReviewRequestStatus } from "./ReviewRequestStatus";
+
+/**
+ * A request for a second pair of eyes on a session.
+ */
+export type ReviewRequestItem = { id: string, session_id: string, session_title: string | null, requester_nickname: string, reviewer_nickname: string, note: string | null, status: ReviewRequestStatus, 
+/**
+ * Reviewer's comment, set when the request is resolved.
+ */
+comment: string | null, resolved_at: string | null, created_at: string, };
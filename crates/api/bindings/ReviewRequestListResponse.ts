@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReviewRequestItem } from "./ReviewRequestItem";
+
+/**
+ * Response for `GET /api/review-requests`, newest first.
+ */
+export type ReviewRequestListResponse = { items: Array<ReviewRequestItem>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Which side of a review request to list.
+ */
+export type ReviewRequestRole = "assigned" | "requested";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * State of a review request. Only `pending` requests can be resolved.
+ */
+export type ReviewRequestStatus = "pending" | "approved" | "changes_requested";
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AttachmentKind } from "./AttachmentKind";
+
+/**
+ * A file attached to a session (screenshot, log, test report, ...).
+ */
+export type SessionAttachment = { id: string, session_id: string, file_name: string, content_type: string, size_bytes: bigint, created_at: string, kind: AttachmentKind, 
+/**
+ * RFC 3339 time the recording started, for terminal recordings; playback
+ * position `t` seconds lines up with events at `recorded_at + t`.
+ */
+recorded_at?: string | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionAttachment } from "./SessionAttachment";
+
+/**
+ * Response for `GET /api/sessions/:id/attachments`, oldest first.
+ */
+export type SessionAttachmentListResponse = { attachments: Array<SessionAttachment>, };
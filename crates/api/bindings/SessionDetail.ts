@@ -0,0 +1,32 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionKind } from "./SessionKind";
+import type { SessionLink } from "./SessionLink";
+import type { StorageClass } from "./StorageClass";
+
+/**
+ * Single session detail returned by `GET /api/sessions/:id`.
+ */
+export type SessionDetail = { linked_sessions?: Array<SessionLink>, 
+/**
+ * Hex SHA-256 of the body as stored at upload; the `/raw` download must
+ * hash to the same value. Absent for bodies hosted elsewhere.
+ */
+body_sha256?: string | null, 
+/**
+ * Base64 Ed25519 signature by the server's body signing key over
+ * `opensession-body-v1\n{id}\n{body_sha256}`, when one is configured.
+ */
+body_signature?: string | null, 
+/**
+ * Whether the body is in hot storage or archived to the cold store.
+ * Cold bodies are brought back on the next `/raw` download.
+ */
+storage_class: StorageClass, id: string, user_id: string | null, nickname: string | null, tool: string, agent_provider: string | null, agent_model: string | null, title: string | null, description: string | null, tags: string | null, created_at: string, uploaded_at: string, message_count: bigint, task_count: bigint, event_count: bigint, duration_seconds: bigint, total_input_tokens: bigint, total_output_tokens: bigint, git_remote?: string | null, git_branch?: string | null, git_commit?: string | null, git_repo_name?: string | null, pr_number?: bigint | null, pr_url?: string | null, working_directory?: string | null, files_modified?: string | null, files_read?: string | null, has_errors: boolean, max_active_agents: bigint, session_score: bigint, score_plugin: string, 
+/**
+ * Machine that uploaded the session (see `GET /api/auth/devices`).
+ */
+device_id?: string | null, 
+/**
+ * The upload was signed by `device_id`'s registered signing key.
+ */
+verified_origin: boolean, kind: SessionKind, };
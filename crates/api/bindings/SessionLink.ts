@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LinkType } from "./LinkType";
+
+/**
+ * A link between two sessions (e.g., handoff chain).
+ */
+export type SessionLink = { session_id: string, linked_session_id: string, link_type: LinkType, created_at: string, };
@@ -0,0 +1,44 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SortOrder } from "./SortOrder";
+import type { TimeRange } from "./TimeRange";
+
+/**
+ * Query parameters for `GET /api/sessions` — pagination, filtering, sorting.
+ *
+ * Pagination is keyset-based: pass the previous response's `next_cursor` as
+ * `cursor`. `page`/`per_page` are deprecated and only honored when no cursor
+ * is given.
+ */
+export type SessionListQuery = { page: number, per_page: number, 
+/**
+ * Opaque cursor from a previous `SessionListResponse::next_cursor`.
+ */
+cursor: string | null, 
+/**
+ * Page size; takes precedence over `per_page`.
+ */
+limit: number | null, search: string | null, tool: string | null, git_repo_name: string | null, sort: SortOrder | null, time_range: TimeRange | null, 
+/**
+ * Filter by model (glob-like, `*` matches any run of characters).
+ */
+model: string | null, 
+/**
+ * Only sessions created at or after this ISO8601 timestamp.
+ */
+since: string | null, 
+/**
+ * Only sessions created before this ISO8601 timestamp.
+ */
+before: string | null, 
+/**
+ * Only sessions that modified this file path.
+ */
+touches: string | null, 
+/**
+ * Only sessions with (`true`) or without (`false`) errors.
+ */
+has_errors: boolean | null, 
+/**
+ * Filter by git commit (prefix match, so short SHAs work).
+ */
+git_commit: string | null, };
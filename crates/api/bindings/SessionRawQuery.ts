@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Query parameters for `GET /api/sessions/:id/raw`.
+ */
+export type SessionRawQuery = { 
+/**
+ * Return only events after the first `since_event` (plus the stats line)
+ * instead of the full body. The response then carries
+ * [`SESSION_DELTA_HEADER`].
+ */
+since_event: number | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Repo list response used by server/worker/desktop adapters.
+ */
+export type SessionRepoListResponse = { repos: Array<string>, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionKind } from "./SessionKind";
+
+/**
+ * Flat session summary returned by list/detail endpoints.
+ */
+export type SessionSummary = { id: string, user_id: string | null, nickname: string | null, tool: string, agent_provider: string | null, agent_model: string | null, title: string | null, description: string | null, tags: string | null, created_at: string, uploaded_at: string, message_count: bigint, task_count: bigint, event_count: bigint, duration_seconds: bigint, total_input_tokens: bigint, total_output_tokens: bigint, git_remote?: string | null, git_branch?: string | null, git_commit?: string | null, git_repo_name?: string | null, pr_number?: bigint | null, pr_url?: string | null, working_directory?: string | null, files_modified?: string | null, files_read?: string | null, has_errors: boolean, max_active_agents: bigint, session_score: bigint, score_plugin: string, 
+/**
+ * Machine that uploaded the session (see `GET /api/auth/devices`).
+ */
+device_id?: string | null, 
+/**
+ * The upload was signed by `device_id`'s registered signing key.
+ */
+verified_origin: boolean, kind: SessionKind, };
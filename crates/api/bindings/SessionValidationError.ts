@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One problem reported by `POST /api/validate`.
+ */
+export type SessionValidationError = { 
+/**
+ * Location of the problem, e.g. `agent.tool`, `events[3]` or `line 2`.
+ */
+path: string, 
+/**
+ * Stable machine-readable code, e.g. `missing_field`.
+ */
+code: string, message: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Sort order for session listings.
+ */
+export type SortOrder = "recent" | "popular" | "longest" | "best";
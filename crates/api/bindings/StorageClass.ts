@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Storage tier of a session body on the server.
+ */
+export type StorageClass = "hot" | "cold";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MetadataEntry } from "./MetadataEntry";
+
+/**
+ * Request body for `POST /api/sync/metadata` — push local changes.
+ */
+export type SyncMetadataRequest = { entries: Array<MetadataEntry>, };
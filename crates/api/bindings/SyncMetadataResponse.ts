@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MetadataEntry } from "./MetadataEntry";
+
+/**
+ * Response for `GET`/`POST /api/sync/metadata` — every entry the server
+ * holds for the user after applying the push, deletions included.
+ */
+export type SyncMetadataResponse = { entries: Array<MetadataEntry>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RetentionPolicy } from "./RetentionPolicy";
+
+/**
+ * Response for `GET`/`PUT /api/teams/:team_id/retention`.
+ */
+export type TeamRetentionResponse = { team_id: string, policy: RetentionPolicy, 
+/**
+ * The team has no policy of its own and follows the server default.
+ */
+is_default: boolean, };
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UploadPolicyAction } from "./UploadPolicyAction";
+
+/**
+ * Globs a team excludes from uploads by its members, on top of the
+ * server-wide upload policy.
+ */
+export type TeamUploadPolicy = { 
+/**
+ * Matched against file paths and shell command words, like
+ * `OPENSESSION_UPLOAD_EXCLUDE_PATTERNS`.
+ */
+exclude_patterns: Array<string>, action: UploadPolicyAction, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TeamUploadPolicy } from "./TeamUploadPolicy";
+
+/**
+ * Response for `GET`/`PUT`/`DELETE /api/teams/:team_id/upload-policy`.
+ */
+export type TeamUploadPolicyResponse = { team_id: string, 
+/**
+ * Empty when the team has no policy of its own.
+ */
+policy: TeamUploadPolicy, };
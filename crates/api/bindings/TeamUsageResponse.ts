@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QuotaLimit } from "./QuotaLimit";
+import type { QuotaNotice } from "./QuotaNotice";
+
+/**
+ * Response for `GET /api/teams/:team_id/usage` — storage used by sessions
+ * that the team's members uploaded, and the server's per-team quotas.
+ */
+export type TeamUsageResponse = { team_id: string, session_count: bigint, body_bytes: bigint, sessions_quota: QuotaLimit, body_bytes_quota: QuotaLimit, 
+/**
+ * Limits the team has reached, soft or hard.
+ */
+warnings: Array<QuotaNotice>, };
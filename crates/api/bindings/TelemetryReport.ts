@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request body for `POST /api/telemetry` — anonymous usage counters a daemon
+ * collected over one reporting period. Carries no session content, user or
+ * device identity.
+ */
+export type TelemetryReport = { daemon_version: string, 
+/**
+ * RFC 3339 start of the period the counters cover.
+ */
+period_start: string, 
+/**
+ * RFC 3339 end of the period the counters cover.
+ */
+period_end: string, sessions_indexed: bigint, parse_failures: bigint, upload_errors: bigint, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TelemetryTotals } from "./TelemetryTotals";
+
+/**
+ * Response for `GET /api/admin/telemetry`: fleet-wide totals for the
+ * window, plus a breakdown by daemon version (most reports first).
+ */
+export type TelemetrySummaryResponse = { days: number, reports: bigint, sessions_indexed: bigint, parse_failures: bigint, upload_errors: bigint, versions: Array<TelemetryTotals>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Counters summed over every report from one daemon version.
+ */
+export type TelemetryTotals = { daemon_version: string, reports: bigint, sessions_indexed: bigint, parse_failures: bigint, upload_errors: bigint, };
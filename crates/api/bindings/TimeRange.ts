@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Time range filter for queries.
+ */
+export type TimeRange = "24h" | "7d" | "30d" | "all";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What the server does with an upload that references an excluded pattern.
+ */
+export type UploadPolicyAction = "reject" | "scrub";
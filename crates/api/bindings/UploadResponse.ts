@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { QuotaNotice } from "./QuotaNotice";
+
+/**
+ * Returned on successful session upload — contains the new session ID and URL.
+ */
+export type UploadResponse = { id: string, url: string, session_score: bigint, score_plugin: string, 
+/**
+ * Team soft quotas this upload reached; the session was still stored.
+ */
+quota_warnings?: Array<QuotaNotice>, };
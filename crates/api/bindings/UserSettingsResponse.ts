@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LinkedProvider } from "./LinkedProvider";
+
+/**
+ * Full user profile returned by `GET /api/auth/me`.
+ */
+export type UserSettingsResponse = { user_id: string, nickname: string, created_at: string, email: string | null, avatar_url: string | null, oauth_providers: Array<LinkedProvider>, 
+/**
+ * Teams granted by the sign-in provider's group mapping.
+ */
+teams: Array<string>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionValidationError } from "./SessionValidationError";
+
+/**
+ * Returned by `POST /api/validate` — strict HAIL validation result.
+ */
+export type ValidateSessionResponse = { valid: boolean, errors: Array<SessionValidationError>, };
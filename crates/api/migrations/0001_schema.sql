@@ -33,6 +33,7 @@ CREATE TABLE IF NOT EXISTS sessions (
     total_input_tokens  INTEGER NOT NULL DEFAULT 0,
     total_output_tokens INTEGER NOT NULL DEFAULT 0,
     body_storage_key    TEXT NOT NULL,
+    body_size_bytes     INTEGER NOT NULL DEFAULT 0,
     body_url            TEXT,
     git_remote          TEXT,
     git_branch          TEXT,
@@ -46,9 +47,19 @@ CREATE TABLE IF NOT EXISTS sessions (
     has_errors          BOOLEAN DEFAULT 0,
     max_active_agents   INTEGER NOT NULL DEFAULT 1,
     session_score       INTEGER NOT NULL DEFAULT 0,
-    score_plugin        TEXT NOT NULL DEFAULT 'heuristic_v1'
+    score_plugin        TEXT NOT NULL DEFAULT 'heuristic_v1',
+    content_fingerprint TEXT,
+    device_id           TEXT,
+    body_sha256         TEXT,
+    body_signature      TEXT,
+    verified_origin     BOOLEAN NOT NULL DEFAULT 0,
+    storage_class       TEXT NOT NULL DEFAULT 'hot',
+    body_accessed_at    TEXT,
+    anonymized_at       TEXT,
+    session_kind        TEXT NOT NULL DEFAULT 'full'
 );
 CREATE INDEX IF NOT EXISTS idx_sessions_uploaded_at ON sessions(uploaded_at DESC);
+CREATE INDEX IF NOT EXISTS idx_sessions_storage_class ON sessions(storage_class, body_accessed_at);
 CREATE INDEX IF NOT EXISTS idx_sessions_tool ON sessions(tool);
 CREATE INDEX IF NOT EXISTS idx_sessions_visible_created_at
 ON sessions(created_at DESC)
@@ -63,6 +74,7 @@ CREATE INDEX IF NOT EXISTS idx_sessions_visible_longest
 ON sessions(duration_seconds DESC, created_at DESC)
 WHERE event_count > 0 OR message_count > 0;
 CREATE INDEX IF NOT EXISTS idx_sessions_session_score ON sessions(session_score DESC);
+CREATE INDEX IF NOT EXISTS idx_sessions_content_fingerprint ON sessions(content_fingerprint);
 
 -- Session links (handoff chains, etc.)
 CREATE TABLE IF NOT EXISTS session_links (
@@ -74,6 +86,23 @@ CREATE TABLE IF NOT EXISTS session_links (
 );
 CREATE INDEX IF NOT EXISTS idx_session_links_linked ON session_links(linked_session_id);
 
+-- Files attached to a session (screenshots, logs, test reports). Content is
+-- stored next to session bodies under `storage_key`.
+CREATE TABLE IF NOT EXISTS session_attachments (
+    id           TEXT PRIMARY KEY,
+    session_id   TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    user_id      TEXT,
+    file_name    TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes   INTEGER NOT NULL,
+    storage_key  TEXT NOT NULL,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now')),
+    kind         TEXT NOT NULL DEFAULT 'file',
+    recorded_at  TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_session_attachments_session
+    ON session_attachments(session_id, created_at);
+
 -- OAuth identities
 CREATE TABLE IF NOT EXISTS oauth_identities (
     user_id           TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
@@ -87,6 +116,50 @@ CREATE TABLE IF NOT EXISTS oauth_identities (
     UNIQUE (user_id, provider)
 );
 
+-- Team memberships derived from IdP group claims; replaced per provider on each login
+CREATE TABLE IF NOT EXISTS user_teams (
+    user_id    TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    team_id    TEXT NOT NULL,
+    provider   TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (user_id, team_id, provider)
+);
+
+-- Per-team retention: sessions older than retain_days are deleted or
+-- anonymized by the server's retention job. Teams without a row follow the
+-- server default.
+CREATE TABLE IF NOT EXISTS team_retention_policies (
+    team_id           TEXT PRIMARY KEY,
+    retain_days       INTEGER NOT NULL,
+    action            TEXT NOT NULL DEFAULT 'delete',
+    exempt_bookmarked BOOLEAN NOT NULL DEFAULT 1,
+    exempt_linked     BOOLEAN NOT NULL DEFAULT 1,
+    updated_by        TEXT,
+    updated_at        TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Per-team upload privacy policy: globs (one per line) checked against uploads
+-- by the team's members, on top of the server-wide patterns.
+CREATE TABLE IF NOT EXISTS team_upload_policies (
+    team_id          TEXT PRIMARY KEY,
+    exclude_patterns TEXT NOT NULL,
+    action           TEXT NOT NULL DEFAULT 'reject',
+    updated_by       TEXT,
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Read-only tokens that list a team's sessions (dashboards, stakeholders)
+CREATE TABLE IF NOT EXISTS guest_tokens (
+    id           TEXT PRIMARY KEY,
+    team_id      TEXT NOT NULL,
+    label        TEXT NOT NULL,
+    token_hash   TEXT NOT NULL UNIQUE,
+    created_by   TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now')),
+    last_used_at TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_guest_tokens_team ON guest_tokens(team_id);
+
 -- OAuth state tokens (CSRF)
 CREATE TABLE IF NOT EXISTS oauth_states (
     state      TEXT PRIMARY KEY,
@@ -115,11 +188,35 @@ CREATE TABLE IF NOT EXISTS api_keys (
     created_at   TEXT NOT NULL DEFAULT (datetime('now')),
     grace_until  TEXT,
     revoked_at   TEXT,
-    last_used_at TEXT
+    last_used_at TEXT,
+    device_id    TEXT
 );
 CREATE INDEX IF NOT EXISTS idx_api_keys_user_status ON api_keys(user_id, status);
 CREATE INDEX IF NOT EXISTS idx_api_keys_grace_until ON api_keys(grace_until);
 
+-- Machines that used a user's API keys, identified by a client-generated id.
+CREATE TABLE IF NOT EXISTS devices (
+    user_id       TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    id            TEXT NOT NULL,
+    name          TEXT,
+    first_seen_at TEXT NOT NULL DEFAULT (datetime('now')),
+    last_seen_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    revoked_at    TEXT,
+    public_key    TEXT,
+    PRIMARY KEY (user_id, id)
+);
+
+-- Per-user key-value metadata (bookmarks, notes, saved filters) synced between
+-- machines. `updated_at` is the writer's unix-ms clock; the newest write wins
+-- and a NULL `value` marks a deletion.
+CREATE TABLE IF NOT EXISTS user_metadata (
+    user_id    TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    key        TEXT NOT NULL,
+    value      TEXT,
+    updated_at INTEGER NOT NULL,
+    PRIMARY KEY (user_id, key)
+);
+
 -- User-managed git credentials for private source fetch.
 CREATE TABLE IF NOT EXISTS git_credentials (
     id               TEXT PRIMARY KEY,
@@ -151,3 +248,62 @@ CREATE TABLE IF NOT EXISTS oauth_provider_tokens (
 );
 CREATE INDEX IF NOT EXISTS idx_oauth_provider_tokens_user_provider_host
 ON oauth_provider_tokens(user_id, provider, provider_host);
+
+-- Handoff artifacts published to another user's inbox.
+CREATE TABLE IF NOT EXISTS handoffs (
+    id                TEXT PRIMARY KEY,
+    sender_id         TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    recipient_id      TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    artifact_uri      TEXT NOT NULL,
+    canonical_jsonl   TEXT NOT NULL,
+    markdown          TEXT,
+    note              TEXT,
+    status            TEXT NOT NULL DEFAULT 'pending',
+    claimed_at        TEXT,
+    completed_at      TEXT,
+    result_session_id TEXT,
+    created_at        TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_handoffs_recipient_created ON handoffs(recipient_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_handoffs_recipient_status ON handoffs(recipient_id, status);
+
+-- Shared starter prompts and handoff templates ("playbooks").
+CREATE TABLE IF NOT EXISTS playbooks (
+    name        TEXT PRIMARY KEY,
+    kind        TEXT NOT NULL DEFAULT 'prompt',
+    title       TEXT,
+    description TEXT,
+    body        TEXT NOT NULL,
+    author_id   TEXT REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Requests for another user to review an uploaded session.
+CREATE TABLE IF NOT EXISTS review_requests (
+    id           TEXT PRIMARY KEY,
+    session_id   TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    requester_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reviewer_id  TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    note         TEXT,
+    status       TEXT NOT NULL DEFAULT 'pending',
+    comment      TEXT,
+    resolved_at  TEXT,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_review_requests_reviewer ON review_requests(reviewer_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_review_requests_requester ON review_requests(requester_id, created_at);
+
+-- Anonymous daemon usage counters (opt-in `privacy.telemetry`). Rows hold no
+-- user, device or session identifiers.
+CREATE TABLE IF NOT EXISTS telemetry_reports (
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    daemon_version   TEXT NOT NULL,
+    period_start     TEXT NOT NULL,
+    period_end       TEXT NOT NULL,
+    sessions_indexed INTEGER NOT NULL DEFAULT 0,
+    parse_failures   INTEGER NOT NULL DEFAULT 0,
+    upload_errors    INTEGER NOT NULL DEFAULT 0,
+    received_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_telemetry_reports_received ON telemetry_reports(received_at);
@@ -0,0 +1,2 @@
+-- Remote ETag for cached session bodies, sent back as If-None-Match.
+ALTER TABLE body_cache ADD COLUMN etag TEXT;
@@ -0,0 +1,3 @@
+-- Where an archived source file now lives: a month `.tar.zst` and the tar member inside it.
+ALTER TABLE session_sync ADD COLUMN archive_path TEXT;
+ALTER TABLE session_sync ADD COLUMN archive_member TEXT;
@@ -0,0 +1,2 @@
+-- Set when reconciliation could not find a session's source file; cleared when a path is re-recorded.
+ALTER TABLE session_sync ADD COLUMN source_missing_at TEXT;
@@ -0,0 +1,8 @@
+-- Local copy of the per-user metadata synced through `/api/sync/metadata`.
+-- `dirty` marks writes not yet pushed; a NULL `value` is a pending or synced deletion.
+CREATE TABLE IF NOT EXISTS synced_metadata (
+    key        TEXT PRIMARY KEY,
+    value      TEXT,
+    updated_at INTEGER NOT NULL,
+    dirty      INTEGER NOT NULL DEFAULT 1
+);
@@ -0,0 +1,12 @@
+-- Commands recorded by the opt-in shell hook (`opensession shell-history hook`).
+-- `started_at` is Unix seconds; rows are matched to sessions by time window and cwd.
+CREATE TABLE IF NOT EXISTS shell_commands (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    command    TEXT NOT NULL,
+    cwd        TEXT NOT NULL,
+    started_at INTEGER NOT NULL,
+    exit_code  INTEGER,
+    shell      TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_shell_commands_started_at ON shell_commands(started_at);
@@ -0,0 +1,14 @@
+-- Files attached to local sessions; `storage_key` names a file under the
+-- local attachments directory. Same shape as the server table in 0001_schema.
+CREATE TABLE IF NOT EXISTS session_attachments (
+    id           TEXT PRIMARY KEY,
+    session_id   TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    user_id      TEXT,
+    file_name    TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes   INTEGER NOT NULL,
+    storage_key  TEXT NOT NULL,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_session_attachments_session
+    ON session_attachments(session_id, created_at);
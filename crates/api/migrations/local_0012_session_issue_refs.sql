@@ -0,0 +1,2 @@
+-- Comma-separated issue references (PROJ-123, #456) found in a session's branch, commits and prompts.
+ALTER TABLE sessions ADD COLUMN issue_refs TEXT;
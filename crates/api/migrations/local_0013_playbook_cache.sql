@@ -0,0 +1,12 @@
+-- Local copy of the server's shared playbooks, so they stay usable offline.
+-- Same columns as the API `Playbook`; refreshed wholesale on each listing.
+CREATE TABLE IF NOT EXISTS playbook_cache (
+    name            TEXT PRIMARY KEY,
+    kind            TEXT NOT NULL DEFAULT 'prompt',
+    title           TEXT,
+    description     TEXT,
+    body            TEXT NOT NULL,
+    author_nickname TEXT,
+    created_at      TEXT NOT NULL,
+    updated_at      TEXT NOT NULL
+);
@@ -0,0 +1,3 @@
+-- JSON environment snapshot (OS, toolchain versions, dirty tree, container)
+-- recorded at index time when `privacy.capture_environment` is enabled.
+ALTER TABLE sessions ADD COLUMN environment TEXT;
@@ -0,0 +1,8 @@
+-- Latest parse failure per session file, so `opensession doctor` can show
+-- files the daemon could not read. Cleared when the file parses again.
+CREATE TABLE IF NOT EXISTS parse_errors (
+    source_path TEXT PRIMARY KEY,
+    parser      TEXT,
+    error       TEXT NOT NULL,
+    failed_at   TEXT NOT NULL
+);
@@ -0,0 +1,5 @@
+-- Attachment kind (`file` or `terminal_recording`) and, for terminal
+-- recordings, the RFC 3339 time the recording started so playback can follow
+-- the session timeline.
+ALTER TABLE session_attachments ADD COLUMN kind TEXT NOT NULL DEFAULT 'file';
+ALTER TABLE session_attachments ADD COLUMN recorded_at TEXT;
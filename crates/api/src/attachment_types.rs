@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+
+/// Largest file accepted by `POST /api/sessions/:id/attachments`.
+pub const MAX_ATTACHMENT_BYTES: usize = 25 * 1024 * 1024;
+
+/// Media type of asciinema recordings (`.cast`).
+pub const ASCIICAST_CONTENT_TYPE: &str = "application/x-asciicast";
+
+/// What an attachment holds.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum AttachmentKind {
+    /// Any other file (screenshot, log, test report, ...).
+    #[default]
+    File,
+    /// An asciinema recording or tmux capture of the terminal the agent ran in.
+    TerminalRecording,
+}
+
+impl AttachmentKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::TerminalRecording => "terminal_recording",
+        }
+    }
+
+    /// Unknown values read as `File`.
+    pub fn parse(raw: &str) -> Self {
+        match raw {
+            "terminal_recording" => Self::TerminalRecording,
+            _ => Self::File,
+        }
+    }
+}
+
+/// A file attached to a session (screenshot, log, test report, ...).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct SessionAttachment {
+    pub id: String,
+    pub session_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: String,
+    #[serde(default)]
+    pub kind: AttachmentKind,
+    /// RFC 3339 time the recording started, for terminal recordings; playback
+    /// position `t` seconds lines up with events at `recorded_at + t`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recorded_at: Option<String>,
+}
+
+impl SessionAttachment {
+    /// Seconds into the recording at which an event with the RFC 3339
+    /// `timestamp` happened; `None` without a `recorded_at` or when the event
+    /// came before the recording started.
+    pub fn playback_offset_secs(&self, timestamp: &str) -> Option<f64> {
+        let started = chrono::DateTime::parse_from_rfc3339(self.recorded_at.as_deref()?).ok()?;
+        let at = chrono::DateTime::parse_from_rfc3339(timestamp).ok()?;
+        let offset = (at - started).num_milliseconds();
+        (offset >= 0).then(|| offset as f64 / 1000.0)
+    }
+}
+
+/// Response for `GET /api/sessions/:id/attachments`, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct SessionAttachmentListResponse {
+    #[serde(default)]
+    pub attachments: Vec<SessionAttachment>,
+}
+
+/// Query for `POST /api/sessions/:id/attachments`; the request body is the
+/// file content and `Content-Type` its media type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AttachmentUploadQuery {
+    /// File name shown to readers; directories are dropped.
+    pub name: String,
+    /// Defaults to `terminal_recording` for asciicast content, `file` otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<AttachmentKind>,
+    /// RFC 3339 start of a terminal recording; read from the asciicast header
+    /// when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recorded_at: Option<String>,
+}
+
+impl AttachmentUploadQuery {
+    /// `name` reduced to a safe display name: the last path segment without
+    /// control characters or quotes, at most 255 characters.
+    pub fn file_name(&self) -> Option<String> {
+        let base = self.name.rsplit(['/', '\\']).next().unwrap_or_default();
+        let cleaned = base
+            .chars()
+            .filter(|c| !c.is_control() && *c != '"')
+            .take(255)
+            .collect::<String>();
+        let cleaned = cleaned.trim();
+        (!cleaned.is_empty() && cleaned != "." && cleaned != "..").then(|| cleaned.to_string())
+    }
+}
+
+/// Whether `data` starts with an asciicast v2/v3 header line.
+pub fn is_asciicast(data: &[u8]) -> bool {
+    asciicast_header(data).is_some()
+}
+
+/// Start time (RFC 3339) from the header of an asciicast recording, or `None`
+/// when `data` is not one or the header has no `timestamp`.
+pub fn asciicast_recorded_at(data: &[u8]) -> Option<String> {
+    let timestamp = asciicast_header(data)?.get("timestamp")?.as_i64()?;
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+}
+
+fn asciicast_header(data: &[u8]) -> Option<serde_json::Value> {
+    let line = data.split(|byte| *byte == b'\n').next()?;
+    let header: serde_json::Value = serde_json::from_slice(line).ok()?;
+    matches!(header.get("version")?.as_u64()?, 2 | 3).then_some(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AttachmentKind, AttachmentUploadQuery, SessionAttachment, asciicast_recorded_at,
+        is_asciicast,
+    };
+
+    fn name(raw: &str) -> Option<String> {
+        AttachmentUploadQuery {
+            name: raw.to_string(),
+            kind: None,
+            recorded_at: None,
+        }
+        .file_name()
+    }
+
+    #[test]
+    fn file_names_are_reduced_to_a_safe_last_segment() {
+        assert_eq!(name("shot.png").as_deref(), Some("shot.png"));
+        assert_eq!(name("../../etc/passwd").as_deref(), Some("passwd"));
+        assert_eq!(name(r"C:\logs\test.log").as_deref(), Some("test.log"));
+        assert_eq!(name("a\"b\n.txt").as_deref(), Some("ab.txt"));
+        assert_eq!(name("dir/"), None);
+        assert_eq!(name(".."), None);
+    }
+
+    #[test]
+    fn asciicast_headers_give_the_recording_start() {
+        let cast = b"{\"version\": 2, \"width\": 80, \"height\": 24, \"timestamp\": 1700000000}\n[0.5, \"o\", \"$ \"]\n";
+        assert!(is_asciicast(cast));
+        assert_eq!(
+            asciicast_recorded_at(cast).as_deref(),
+            Some("2023-11-14T22:13:20Z")
+        );
+        assert!(!is_asciicast(b"plain tmux capture\n"));
+        assert_eq!(asciicast_recorded_at(b"{\"version\": 2}\n"), None);
+        assert_eq!(
+            asciicast_recorded_at(b"{\"version\": 1, \"timestamp\": 1}"),
+            None
+        );
+    }
+
+    #[test]
+    fn playback_offsets_follow_event_timestamps() {
+        let recording = SessionAttachment {
+            id: "a1".to_string(),
+            session_id: "s1".to_string(),
+            file_name: "run.cast".to_string(),
+            content_type: super::ASCIICAST_CONTENT_TYPE.to_string(),
+            size_bytes: 10,
+            created_at: "2023-11-14T22:20:00Z".to_string(),
+            kind: AttachmentKind::TerminalRecording,
+            recorded_at: Some("2023-11-14T22:13:20Z".to_string()),
+        };
+        assert_eq!(
+            recording.playback_offset_secs("2023-11-14T22:13:32.5Z"),
+            Some(12.5)
+        );
+        assert_eq!(recording.playback_offset_secs("2023-11-14T22:13:00Z"), None);
+        assert_eq!(
+            AttachmentKind::parse(AttachmentKind::TerminalRecording.as_str()),
+            AttachmentKind::TerminalRecording
+        );
+        assert_eq!(AttachmentKind::parse("other"), AttachmentKind::File);
+    }
+}
@@ -1,9 +1,10 @@
-use crate::oauth;
+use crate::{MetadataEntry, SessionAttachment, SessionSummary, oauth};
 use serde::{Deserialize, Serialize};
 
 /// Email + password registration.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct AuthRegisterRequest {
     pub email: String,
@@ -14,6 +15,7 @@ pub struct AuthRegisterRequest {
 /// Email + password login.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct LoginRequest {
     pub email: String,
@@ -23,6 +25,7 @@ pub struct LoginRequest {
 /// Returned on successful login / register / refresh.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct AuthTokenResponse {
     pub access_token: String,
@@ -35,6 +38,7 @@ pub struct AuthTokenResponse {
 /// Refresh token request.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct RefreshRequest {
     pub refresh_token: String,
@@ -43,6 +47,7 @@ pub struct RefreshRequest {
 /// Logout request (invalidate refresh token).
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct LogoutRequest {
     pub refresh_token: String,
@@ -51,6 +56,7 @@ pub struct LogoutRequest {
 /// Change password request.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct ChangePasswordRequest {
     pub current_password: String,
@@ -60,6 +66,7 @@ pub struct ChangePasswordRequest {
 /// Returned by `POST /api/auth/verify` — confirms token validity.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct VerifyResponse {
     pub user_id: String,
@@ -69,6 +76,7 @@ pub struct VerifyResponse {
 /// Full user profile returned by `GET /api/auth/me`.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct UserSettingsResponse {
     pub user_id: String,
@@ -78,11 +86,15 @@ pub struct UserSettingsResponse {
     pub avatar_url: Option<String>,
     #[serde(default)]
     pub oauth_providers: Vec<oauth::LinkedProvider>,
+    /// Teams granted by the sign-in provider's group mapping.
+    #[serde(default)]
+    pub teams: Vec<String>,
 }
 
 /// Generic success response for operations that don't return data.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct OkResponse {
     pub ok: bool,
@@ -91,14 +103,52 @@ pub struct OkResponse {
 /// Response for API key issuance. The key is visible only at issuance time.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct IssueApiKeyResponse {
     pub api_key: String,
 }
 
+/// Request header carrying a client's stable, locally generated machine id.
+pub const DEVICE_ID_HEADER: &str = "x-opensession-device-id";
+/// Request header carrying a human-readable machine name (usually the hostname).
+pub const DEVICE_NAME_HEADER: &str = "x-opensession-device-name";
+
+/// A machine that has sent authenticated requests for the user.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct DeviceSummary {
+    pub id: String,
+    pub name: Option<String>,
+    pub first_seen_at: String,
+    pub last_seen_at: String,
+    pub revoked_at: Option<String>,
+    /// Sessions uploaded from this device.
+    pub session_count: i64,
+    /// Whether this is the device making the request.
+    #[serde(default)]
+    pub current: bool,
+    /// Base64 Ed25519 public key that signs this device's uploads.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
+}
+
+/// Response for `GET /api/auth/devices`.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct ListDevicesResponse {
+    #[serde(default)]
+    pub devices: Vec<DeviceSummary>,
+}
+
 /// Public metadata for a user-managed git credential.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct GitCredentialSummary {
     pub id: String,
@@ -114,6 +164,7 @@ pub struct GitCredentialSummary {
 /// Response for `GET /api/auth/git-credentials`.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct ListGitCredentialsResponse {
     #[serde(default)]
@@ -123,6 +174,7 @@ pub struct ListGitCredentialsResponse {
 /// Request for `POST /api/auth/git-credentials`.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct CreateGitCredentialRequest {
     pub label: String,
@@ -133,10 +185,104 @@ pub struct CreateGitCredentialRequest {
     pub header_value: String,
 }
 
+/// Request for `PUT /api/auth/devices/{id}/signing-key`.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct RegisterDeviceKeyRequest {
+    /// Base64 Ed25519 public key (32 bytes).
+    pub public_key: String,
+}
+
+/// Request for `POST /api/teams/{team_id}/guest-tokens`.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct CreateGuestTokenRequest {
+    /// Who or what the token is for, e.g. "grafana dashboard".
+    pub label: String,
+}
+
+/// Public metadata for a team's read-only guest token.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct GuestTokenSummary {
+    pub id: String,
+    pub team_id: String,
+    pub label: String,
+    /// Nickname of the member who created the token.
+    pub created_by: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+/// Response for `POST /api/teams/{team_id}/guest-tokens`. The token is visible
+/// only at creation time.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct CreateGuestTokenResponse {
+    pub token: String,
+    pub guest_token: GuestTokenSummary,
+}
+
+/// Response for `GET /api/teams/{team_id}/guest-tokens`.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct ListGuestTokensResponse {
+    #[serde(default)]
+    pub guest_tokens: Vec<GuestTokenSummary>,
+}
+
 /// Response for OAuth link initiation (redirect URL).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct OAuthLinkResponse {
     pub url: String,
 }
+
+/// Media type of `GET /api/auth/export`: one [`AccountExportRecord`] per line.
+pub const ACCOUNT_EXPORT_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// One line of `GET /api/auth/export`. The stream starts with the `account`
+/// line, followed by a `session` line per uploaded session, each trailed by
+/// that session's `attachment` lines.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum AccountExportRecord {
+    Account {
+        account: UserSettingsResponse,
+        devices: Vec<DeviceSummary>,
+        metadata: Vec<MetadataEntry>,
+    },
+    Session {
+        session: Box<SessionSummary>,
+        /// Stored HAIL JSONL body.
+        body: String,
+    },
+    Attachment {
+        attachment: SessionAttachment,
+        content_base64: String,
+    },
+}
+
+/// Response for `DELETE /api/auth/me`.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct DeleteAccountResponse {
+    pub sessions_deleted: u32,
+    pub attachments_deleted: u32,
+}
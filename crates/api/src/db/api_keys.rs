@@ -1,6 +1,6 @@
 //! API key query builders.
 
-use sea_query::{Expr, OnConflict, Query, SqliteQueryBuilder};
+use sea_query::{Expr, Func, OnConflict, Query, SqliteQueryBuilder};
 
 use super::tables::{ApiKeys, Users};
 
@@ -31,8 +31,14 @@ pub fn get_user_by_valid_key_hash(key_hash: &str) -> Built {
         .build(SqliteQueryBuilder)
 }
 
-/// Insert an active API key row.
-pub fn insert_active(id: &str, user_id: &str, key_hash: &str, key_prefix: &str) -> Built {
+/// Insert an active API key row, optionally bound to the issuing device.
+pub fn insert_active(
+    id: &str,
+    user_id: &str,
+    key_hash: &str,
+    key_prefix: &str,
+    device_id: Option<&str>,
+) -> Built {
     Query::insert()
         .into_table(ApiKeys::Table)
         .columns([
@@ -41,6 +47,7 @@ pub fn insert_active(id: &str, user_id: &str, key_hash: &str, key_prefix: &str)
             ApiKeys::KeyHash,
             ApiKeys::KeyPrefix,
             ApiKeys::Status,
+            ApiKeys::DeviceId,
         ])
         .values_panic([
             id.into(),
@@ -48,6 +55,7 @@ pub fn insert_active(id: &str, user_id: &str, key_hash: &str, key_prefix: &str)
             key_hash.into(),
             key_prefix.into(),
             "active".into(),
+            device_id.map(str::to_string).into(),
         ])
         .build(SqliteQueryBuilder)
 }
@@ -80,12 +88,67 @@ pub fn insert_active_if_missing(
 }
 
 /// Move active keys to grace state for a user.
-pub fn move_active_to_grace(user_id: &str, grace_until: &str) -> Built {
-    Query::update()
+///
+/// With `device_id`, only that device's keys and keys not bound to any device
+/// are rotated, so other machines keep working.
+pub fn move_active_to_grace(user_id: &str, grace_until: &str, device_id: Option<&str>) -> Built {
+    let mut query = Query::update();
+    query
         .table(ApiKeys::Table)
         .value(ApiKeys::Status, "grace")
         .value(ApiKeys::GraceUntil, grace_until)
         .and_where(Expr::col(ApiKeys::UserId).eq(user_id))
-        .and_where(Expr::col(ApiKeys::Status).eq("active"))
+        .and_where(Expr::col(ApiKeys::Status).eq("active"));
+    if let Some(device_id) = device_id {
+        query.and_where(
+            Expr::col(ApiKeys::DeviceId)
+                .eq(device_id)
+                .or(Expr::col(ApiKeys::DeviceId).is_null()),
+        );
+    }
+    query.build(SqliteQueryBuilder)
+}
+
+/// SELECT the device a key is bound to (NULL until first use from a device),
+/// and whether it was used within the last `recent_secs` seconds.
+pub fn get_use_state(key_hash: &str, recent_secs: u32) -> Built {
+    Query::select()
+        .column(ApiKeys::DeviceId)
+        .expr(Expr::cust_with_values(
+            "\"last_used_at\" > datetime('now', ?)",
+            [format!("-{recent_secs} seconds")],
+        ))
+        .from(ApiKeys::Table)
+        .and_where(Expr::col(ApiKeys::KeyHash).eq(key_hash))
+        .build(SqliteQueryBuilder)
+}
+
+/// Bind a key to the first device that uses it and stamp `last_used_at`.
+pub fn record_use(key_hash: &str, device_id: Option<&str>) -> Built {
+    Query::update()
+        .table(ApiKeys::Table)
+        .value(ApiKeys::LastUsedAt, Expr::cust("datetime('now')"))
+        .value(
+            ApiKeys::DeviceId,
+            Func::if_null(Expr::col(ApiKeys::DeviceId), device_id.map(str::to_string)),
+        )
+        .and_where(Expr::col(ApiKeys::KeyHash).eq(key_hash))
+        .build(SqliteQueryBuilder)
+}
+
+/// Revoke every key bound to one of a user's devices, along with the user's
+/// keys not yet bound to any device, since one of those may be on it too.
+pub fn revoke_for_device(user_id: &str, device_id: &str) -> Built {
+    Query::update()
+        .table(ApiKeys::Table)
+        .value(ApiKeys::Status, "revoked")
+        .value(ApiKeys::RevokedAt, Expr::cust("datetime('now')"))
+        .and_where(Expr::col(ApiKeys::UserId).eq(user_id))
+        .and_where(
+            Expr::col(ApiKeys::DeviceId)
+                .eq(device_id)
+                .or(Expr::col(ApiKeys::DeviceId).is_null()),
+        )
+        .and_where(Expr::col(ApiKeys::Status).ne("revoked"))
         .build(SqliteQueryBuilder)
 }
@@ -0,0 +1,134 @@
+//! Session attachment query builders.
+
+use sea_query::{Cond, Expr, Order, Query, SqliteQueryBuilder};
+
+use super::tables::{SessionAttachments, Sessions};
+use crate::AttachmentKind;
+
+pub type Built = (String, sea_query::Values);
+
+const ATTACHMENT_COLUMNS: [SessionAttachments; 8] = [
+    SessionAttachments::Id,
+    SessionAttachments::SessionId,
+    SessionAttachments::FileName,
+    SessionAttachments::ContentType,
+    SessionAttachments::SizeBytes,
+    SessionAttachments::CreatedAt,
+    SessionAttachments::Kind,
+    SessionAttachments::RecordedAt,
+];
+
+/// Insert an attachment row; the content must already be stored under `storage_key`.
+#[allow(clippy::too_many_arguments)]
+pub fn insert(
+    id: &str,
+    session_id: &str,
+    user_id: Option<&str>,
+    file_name: &str,
+    content_type: &str,
+    size_bytes: i64,
+    storage_key: &str,
+    kind: AttachmentKind,
+    recorded_at: Option<&str>,
+) -> Built {
+    Query::insert()
+        .into_table(SessionAttachments::Table)
+        .columns([
+            SessionAttachments::Id,
+            SessionAttachments::SessionId,
+            SessionAttachments::UserId,
+            SessionAttachments::FileName,
+            SessionAttachments::ContentType,
+            SessionAttachments::SizeBytes,
+            SessionAttachments::StorageKey,
+            SessionAttachments::Kind,
+            SessionAttachments::RecordedAt,
+        ])
+        .values_panic([
+            id.into(),
+            session_id.into(),
+            user_id.map(str::to_string).into(),
+            file_name.into(),
+            content_type.into(),
+            size_bytes.into(),
+            storage_key.into(),
+            kind.as_str().into(),
+            recorded_at.map(str::to_string).into(),
+        ])
+        .build(SqliteQueryBuilder)
+}
+
+/// A session's attachments, oldest first.
+///
+/// Columns: id, session_id, file_name, content_type, size_bytes, created_at,
+/// kind, recorded_at.
+pub fn list_by_session(session_id: &str) -> Built {
+    Query::select()
+        .columns(ATTACHMENT_COLUMNS)
+        .from(SessionAttachments::Table)
+        .and_where(Expr::col(SessionAttachments::SessionId).eq(session_id))
+        .order_by(SessionAttachments::CreatedAt, Order::Asc)
+        .order_by(SessionAttachments::Id, Order::Asc)
+        .build(SqliteQueryBuilder)
+}
+
+/// One attachment of a session.
+///
+/// Columns: id, session_id, file_name, content_type, size_bytes, created_at,
+/// kind, recorded_at, storage_key.
+pub fn get(session_id: &str, id: &str) -> Built {
+    Query::select()
+        .columns(ATTACHMENT_COLUMNS)
+        .column(SessionAttachments::StorageKey)
+        .from(SessionAttachments::Table)
+        .and_where(Expr::col(SessionAttachments::SessionId).eq(session_id))
+        .and_where(Expr::col(SessionAttachments::Id).eq(id))
+        .build(SqliteQueryBuilder)
+}
+
+/// `storage_key` of every attachment of a session.
+pub fn list_storage_keys_for_session(session_id: &str) -> Built {
+    Query::select()
+        .column(SessionAttachments::StorageKey)
+        .from(SessionAttachments::Table)
+        .and_where(Expr::col(SessionAttachments::SessionId).eq(session_id))
+        .build(SqliteQueryBuilder)
+}
+
+/// `storage_key` of every attachment a user uploaded or that belongs to one
+/// of their sessions.
+pub fn list_storage_keys_for_user(user_id: &str) -> Built {
+    Query::select()
+        .column(SessionAttachments::StorageKey)
+        .from(SessionAttachments::Table)
+        .cond_where(
+            Cond::any()
+                .add(Expr::col(SessionAttachments::UserId).eq(user_id))
+                .add(
+                    Expr::col(SessionAttachments::SessionId).in_subquery(
+                        Query::select()
+                            .column(Sessions::Id)
+                            .from(Sessions::Table)
+                            .and_where(Expr::col(Sessions::UserId).eq(user_id))
+                            .to_owned(),
+                    ),
+                ),
+        )
+        .build(SqliteQueryBuilder)
+}
+
+pub fn delete_for_session(session_id: &str) -> Built {
+    Query::delete()
+        .from_table(SessionAttachments::Table)
+        .and_where(Expr::col(SessionAttachments::SessionId).eq(session_id))
+        .build(SqliteQueryBuilder)
+}
+
+/// Delete attachments a user uploaded; those on their own sessions go with
+/// the sessions.
+pub fn delete_by_user(user_id: &str) -> Built {
+    Query::delete()
+        .from_table(SessionAttachments::Table)
+        .and_where(Expr::col(SessionAttachments::UserId).eq(user_id))
+        .build(SqliteQueryBuilder)
+}
@@ -0,0 +1,94 @@
+//! Device (per-machine client) query builders.
+
+use sea_query::{Expr, Query, SqliteQueryBuilder};
+
+use super::tables::Devices;
+
+pub type Built = (String, sea_query::Values);
+
+/// Record a request from a device, creating the row on first use.
+///
+/// A missing `name` keeps the previously reported one.
+pub fn touch(user_id: &str, device_id: &str, name: Option<&str>) -> Built {
+    let sql = concat!(
+        "INSERT INTO \"devices\" (\"user_id\", \"id\", \"name\") VALUES (?, ?, ?) ",
+        "ON CONFLICT (\"user_id\", \"id\") DO UPDATE SET ",
+        "\"last_seen_at\" = datetime('now'), ",
+        "\"name\" = COALESCE(excluded.\"name\", \"devices\".\"name\")"
+    )
+    .to_string();
+    let values = sea_query::Values(vec![
+        user_id.into(),
+        device_id.into(),
+        name.map(str::to_string).into(),
+    ]);
+    (sql, values)
+}
+
+/// SELECT `revoked_at` for one of a user's devices, and whether it was seen
+/// within the last `recent_secs` seconds.
+pub fn get_use_state(user_id: &str, device_id: &str, recent_secs: u32) -> Built {
+    Query::select()
+        .column(Devices::RevokedAt)
+        .expr(Expr::cust_with_values(
+            "\"last_seen_at\" > datetime('now', ?)",
+            [format!("-{recent_secs} seconds")],
+        ))
+        .from(Devices::Table)
+        .and_where(Expr::col(Devices::UserId).eq(user_id))
+        .and_where(Expr::col(Devices::Id).eq(device_id))
+        .build(SqliteQueryBuilder)
+}
+
+/// SELECT the upload signing key of one of a user's devices.
+pub fn get_public_key(user_id: &str, device_id: &str) -> Built {
+    Query::select()
+        .column(Devices::PublicKey)
+        .from(Devices::Table)
+        .and_where(Expr::col(Devices::UserId).eq(user_id))
+        .and_where(Expr::col(Devices::Id).eq(device_id))
+        .build(SqliteQueryBuilder)
+}
+
+/// Set a device's upload signing key. Only applies to active devices without
+/// one: a key is never replaced, so a leaked API key cannot re-key a device.
+pub fn set_public_key(user_id: &str, device_id: &str, public_key: &str) -> Built {
+    Query::update()
+        .table(Devices::Table)
+        .value(Devices::PublicKey, public_key)
+        .and_where(Expr::col(Devices::UserId).eq(user_id))
+        .and_where(Expr::col(Devices::Id).eq(device_id))
+        .and_where(Expr::col(Devices::PublicKey).is_null())
+        .and_where(Expr::col(Devices::RevokedAt).is_null())
+        .build(SqliteQueryBuilder)
+}
+
+/// List a user's devices with the number of sessions each uploaded,
+/// most recently seen first.
+pub fn list_by_user(user_id: &str) -> Built {
+    let sql = concat!(
+        "SELECT d.\"id\", d.\"name\", d.\"first_seen_at\", d.\"last_seen_at\", d.\"revoked_at\", ",
+        "(SELECT COUNT(*) FROM \"sessions\" s ",
+        "WHERE s.\"user_id\" = d.\"user_id\" AND s.\"device_id\" = d.\"id\"), ",
+        "d.\"public_key\" ",
+        "FROM \"devices\" d ",
+        "WHERE d.\"user_id\" = ? ",
+        "ORDER BY d.\"last_seen_at\" DESC, d.\"id\" ASC"
+    )
+    .to_string();
+    let values = sea_query::Values(vec![user_id.into()]);
+    (sql, values)
+}
+
+/// Mark a device revoked, keeping the first revocation time.
+pub fn revoke(user_id: &str, device_id: &str) -> Built {
+    Query::update()
+        .table(Devices::Table)
+        .value(
+            Devices::RevokedAt,
+            Expr::cust("COALESCE(\"revoked_at\", datetime('now'))"),
+        )
+        .and_where(Expr::col(Devices::UserId).eq(user_id))
+        .and_where(Expr::col(Devices::Id).eq(device_id))
+        .build(SqliteQueryBuilder)
+}
@@ -0,0 +1,78 @@
+//! Team-scoped read-only guest token query builders.
+
+use sea_query::{Alias, Expr, JoinType, Order, Query, SqliteQueryBuilder};
+
+use super::tables::{GuestTokens, Users};
+
+pub type Built = (String, sea_query::Values);
+
+pub fn insert(id: &str, team_id: &str, label: &str, token_hash: &str, created_by: &str) -> Built {
+    Query::insert()
+        .into_table(GuestTokens::Table)
+        .columns([
+            GuestTokens::Id,
+            GuestTokens::TeamId,
+            GuestTokens::Label,
+            GuestTokens::TokenHash,
+            GuestTokens::CreatedBy,
+        ])
+        .values_panic([
+            id.into(),
+            team_id.into(),
+            label.into(),
+            token_hash.into(),
+            created_by.into(),
+        ])
+        .build(SqliteQueryBuilder)
+}
+
+/// A team's tokens, newest first.
+///
+/// Columns: id, team_id, label, created_by nickname, created_at, last_used_at.
+pub fn list_by_team(team_id: &str) -> Built {
+    let g = Alias::new("g");
+    let u = Alias::new("u");
+    Query::select()
+        .column((g.clone(), GuestTokens::Id))
+        .column((g.clone(), GuestTokens::TeamId))
+        .column((g.clone(), GuestTokens::Label))
+        .column((u.clone(), Users::Nickname))
+        .column((g.clone(), GuestTokens::CreatedAt))
+        .column((g.clone(), GuestTokens::LastUsedAt))
+        .from_as(GuestTokens::Table, g.clone())
+        .join_as(
+            JoinType::InnerJoin,
+            Users::Table,
+            u.clone(),
+            Expr::col((u, Users::Id)).equals((g.clone(), GuestTokens::CreatedBy)),
+        )
+        .and_where(Expr::col((g.clone(), GuestTokens::TeamId)).eq(team_id))
+        .order_by((g.clone(), GuestTokens::CreatedAt), Order::Desc)
+        .order_by((g, GuestTokens::Id), Order::Desc)
+        .build(SqliteQueryBuilder)
+}
+
+pub fn delete(id: &str, team_id: &str) -> Built {
+    Query::delete()
+        .from_table(GuestTokens::Table)
+        .and_where(Expr::col(GuestTokens::Id).eq(id))
+        .and_where(Expr::col(GuestTokens::TeamId).eq(team_id))
+        .build(SqliteQueryBuilder)
+}
+
+/// Columns: id, team_id.
+pub fn find_by_hash(token_hash: &str) -> Built {
+    Query::select()
+        .columns([GuestTokens::Id, GuestTokens::TeamId])
+        .from(GuestTokens::Table)
+        .and_where(Expr::col(GuestTokens::TokenHash).eq(token_hash))
+        .build(SqliteQueryBuilder)
+}
+
+pub fn touch(id: &str) -> Built {
+    Query::update()
+        .table(GuestTokens::Table)
+        .value(GuestTokens::LastUsedAt, Expr::cust("datetime('now')"))
+        .and_where(Expr::col(GuestTokens::Id).eq(id))
+        .build(SqliteQueryBuilder)
+}
@@ -0,0 +1,127 @@
+//! Handoff inbox query builders.
+
+use sea_query::{Alias, Expr, Order, Query, SqliteQueryBuilder};
+
+use super::tables::{Handoffs, Users};
+
+pub type Built = (String, sea_query::Values);
+
+/// Insert a handoff addressed to a recipient's inbox.
+#[allow(clippy::too_many_arguments)]
+pub fn insert(
+    id: &str,
+    sender_id: &str,
+    recipient_id: &str,
+    artifact_uri: &str,
+    canonical_jsonl: &str,
+    markdown: Option<&str>,
+    note: Option<&str>,
+) -> Built {
+    Query::insert()
+        .into_table(Handoffs::Table)
+        .columns([
+            Handoffs::Id,
+            Handoffs::SenderId,
+            Handoffs::RecipientId,
+            Handoffs::ArtifactUri,
+            Handoffs::CanonicalJsonl,
+            Handoffs::Markdown,
+            Handoffs::Note,
+        ])
+        .values_panic([
+            id.into(),
+            sender_id.into(),
+            recipient_id.into(),
+            artifact_uri.into(),
+            canonical_jsonl.into(),
+            markdown.map(str::to_string).into(),
+            note.map(str::to_string).into(),
+        ])
+        .build(SqliteQueryBuilder)
+}
+
+/// Base select for inbox items, joining sender and recipient nicknames.
+///
+/// Columns: id, sender nickname, recipient nickname, artifact_uri,
+/// canonical_jsonl, markdown, note, status, claimed_at, completed_at,
+/// result_session_id, created_at.
+fn inbox_item_select() -> sea_query::SelectStatement {
+    let sender = Alias::new("sender");
+    let recipient = Alias::new("recipient");
+    Query::select()
+        .column((Handoffs::Table, Handoffs::Id))
+        .expr(Expr::col((sender.clone(), Users::Nickname)))
+        .expr(Expr::col((recipient.clone(), Users::Nickname)))
+        .column((Handoffs::Table, Handoffs::ArtifactUri))
+        .column((Handoffs::Table, Handoffs::CanonicalJsonl))
+        .column((Handoffs::Table, Handoffs::Markdown))
+        .column((Handoffs::Table, Handoffs::Note))
+        .column((Handoffs::Table, Handoffs::Status))
+        .column((Handoffs::Table, Handoffs::ClaimedAt))
+        .column((Handoffs::Table, Handoffs::CompletedAt))
+        .column((Handoffs::Table, Handoffs::ResultSessionId))
+        .column((Handoffs::Table, Handoffs::CreatedAt))
+        .from(Handoffs::Table)
+        .join_as(
+            sea_query::JoinType::InnerJoin,
+            Users::Table,
+            sender.clone(),
+            Expr::col((sender, Users::Id)).equals((Handoffs::Table, Handoffs::SenderId)),
+        )
+        .join_as(
+            sea_query::JoinType::InnerJoin,
+            Users::Table,
+            recipient.clone(),
+            Expr::col((recipient, Users::Id)).equals((Handoffs::Table, Handoffs::RecipientId)),
+        )
+        .to_owned()
+}
+
+/// List handoffs addressed to a user, newest first, optionally by status.
+pub fn list_inbox(recipient_id: &str, status: Option<&str>, limit: u32) -> Built {
+    let mut query = inbox_item_select();
+    query.and_where(Expr::col((Handoffs::Table, Handoffs::RecipientId)).eq(recipient_id));
+    if let Some(status) = status {
+        query.and_where(Expr::col((Handoffs::Table, Handoffs::Status)).eq(status));
+    }
+    query
+        .order_by((Handoffs::Table, Handoffs::CreatedAt), Order::Desc)
+        .order_by((Handoffs::Table, Handoffs::Id), Order::Desc)
+        .limit(limit as u64)
+        .build(SqliteQueryBuilder)
+}
+
+/// Get a single handoff by id.
+pub fn get_by_id(id: &str) -> Built {
+    inbox_item_select()
+        .and_where(Expr::col((Handoffs::Table, Handoffs::Id)).eq(id))
+        .build(SqliteQueryBuilder)
+}
+
+/// Move a pending handoff to `claimed`. Only the recipient may claim.
+pub fn claim(id: &str, recipient_id: &str) -> Built {
+    Query::update()
+        .table(Handoffs::Table)
+        .value(Handoffs::Status, "claimed")
+        .value(Handoffs::ClaimedAt, Expr::cust("datetime('now')"))
+        .and_where(Expr::col(Handoffs::Id).eq(id))
+        .and_where(Expr::col(Handoffs::RecipientId).eq(recipient_id))
+        .and_where(Expr::col(Handoffs::Status).eq("pending"))
+        .build(SqliteQueryBuilder)
+}
+
+/// Move a claimed handoff to `completed`, linking the resulting session.
+pub fn complete(id: &str, recipient_id: &str, result_session_id: Option<&str>) -> Built {
+    Query::update()
+        .table(Handoffs::Table)
+        .value(Handoffs::Status, "completed")
+        .value(Handoffs::CompletedAt, Expr::cust("datetime('now')"))
+        .value(
+            Handoffs::ResultSessionId,
+            result_session_id.map(str::to_string),
+        )
+        .and_where(Expr::col(Handoffs::Id).eq(id))
+        .and_where(Expr::col(Handoffs::RecipientId).eq(recipient_id))
+        .and_where(Expr::col(Handoffs::Status).eq("claimed"))
+        .build(SqliteQueryBuilder)
+}
@@ -35,6 +35,50 @@ pub const LOCAL_MIGRATIONS: &[Migration] = &[
         "local_0005_lifecycle_cleanup_status",
         include_str!("../../migrations/local_0005_lifecycle_cleanup_status.sql"),
     ),
+    (
+        "local_0006_body_cache_etag",
+        include_str!("../../migrations/local_0006_body_cache_etag.sql"),
+    ),
+    (
+        "local_0007_source_archive",
+        include_str!("../../migrations/local_0007_source_archive.sql"),
+    ),
+    (
+        "local_0008_source_missing",
+        include_str!("../../migrations/local_0008_source_missing.sql"),
+    ),
+    (
+        "local_0009_synced_metadata",
+        include_str!("../../migrations/local_0009_synced_metadata.sql"),
+    ),
+    (
+        "local_0010_shell_history",
+        include_str!("../../migrations/local_0010_shell_history.sql"),
+    ),
+    (
+        "local_0011_session_attachments",
+        include_str!("../../migrations/local_0011_session_attachments.sql"),
+    ),
+    (
+        "local_0012_session_issue_refs",
+        include_str!("../../migrations/local_0012_session_issue_refs.sql"),
+    ),
+    (
+        "local_0013_playbook_cache",
+        include_str!("../../migrations/local_0013_playbook_cache.sql"),
+    ),
+    (
+        "local_0014_session_environment",
+        include_str!("../../migrations/local_0014_session_environment.sql"),
+    ),
+    (
+        "local_0015_parse_errors",
+        include_str!("../../migrations/local_0015_parse_errors.sql"),
+    ),
+    (
+        "local_0016_attachment_kind",
+        include_str!("../../migrations/local_0016_attachment_kind.sql"),
+    ),
 ];
 
 #[cfg(test)]
@@ -45,12 +89,25 @@ mod tests {
     fn schema_migration_set_is_minimal() {
         assert_eq!(MIGRATIONS.len(), 1);
         assert_eq!(MIGRATIONS[0].0, "0001_schema");
-        assert_eq!(LOCAL_MIGRATIONS.len(), 5);
+        assert_eq!(LOCAL_MIGRATIONS.len(), 16);
         assert_eq!(LOCAL_MIGRATIONS[0].0, "local_0001_schema");
         assert_eq!(LOCAL_MIGRATIONS[1].0, "local_0002_session_summaries");
         assert_eq!(LOCAL_MIGRATIONS[2].0, "local_0003_vector_index");
         assert_eq!(LOCAL_MIGRATIONS[3].0, "local_0004_summary_batch_status");
         assert_eq!(LOCAL_MIGRATIONS[4].0, "local_0005_lifecycle_cleanup_status");
+        assert_eq!(LOCAL_MIGRATIONS[5].0, "local_0006_body_cache_etag");
+        assert_eq!(LOCAL_MIGRATIONS[6].0, "local_0007_source_archive");
+        assert_eq!(LOCAL_MIGRATIONS[7].0, "local_0008_source_missing");
+    }
+
+    #[test]
+    fn d1_schema_matches_canonical_schema() {
+        // wrangler applies the repository-root copy to D1.
+        assert_eq!(
+            include_str!("../../../../migrations/0001_schema.sql"),
+            MIGRATIONS[0].1,
+            "migrations/0001_schema.sql must stay identical to crates/api/migrations/0001_schema.sql"
+        );
     }
 
     #[test]
@@ -3,12 +3,22 @@
 //! Used by: Axum server, Cloudflare Worker, local DB (TUI/Daemon).
 
 pub mod api_keys;
+pub mod attachments;
+pub mod devices;
 pub mod git_credentials;
+pub mod guest_tokens;
+pub mod handoffs;
 pub mod migrations;
 pub mod oauth;
 pub mod oauth_provider_tokens;
+pub mod playbooks;
+pub mod retention;
+pub mod review_requests;
 pub mod sessions;
 pub mod tables;
+pub mod telemetry;
+pub mod upload_policies;
+pub mod user_metadata;
 pub mod users;
 
 // Re-export tables for convenience
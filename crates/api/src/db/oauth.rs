@@ -2,7 +2,7 @@
 
 use sea_query::{Alias, Asterisk, Expr, Func, Query, SqliteQueryBuilder};
 
-use super::tables::{OauthIdentities, OauthStates};
+use super::tables::{OauthIdentities, OauthStates, UserTeams};
 
 pub type Built = (String, sea_query::Values);
 
@@ -87,6 +87,36 @@ pub fn identity_match(user_id: &str, provider: &str, provider_username: &str) ->
     (sql, values)
 }
 
+// ── Group-mapped Teams ────────────────────────────────────────────────────
+
+/// Drop the teams a provider granted a user, before re-inserting the current set.
+pub fn delete_user_teams(user_id: &str, provider: &str) -> Built {
+    Query::delete()
+        .from_table(UserTeams::Table)
+        .and_where(Expr::col(UserTeams::UserId).eq(user_id))
+        .and_where(Expr::col(UserTeams::Provider).eq(provider))
+        .build(SqliteQueryBuilder)
+}
+
+pub fn insert_user_team(user_id: &str, team_id: &str, provider: &str) -> Built {
+    Query::insert()
+        .into_table(UserTeams::Table)
+        .columns([UserTeams::UserId, UserTeams::TeamId, UserTeams::Provider])
+        .values_panic([user_id.into(), team_id.into(), provider.into()])
+        .build(SqliteQueryBuilder)
+}
+
+/// Team ids a user belongs to across providers, sorted.
+pub fn list_user_teams(user_id: &str) -> Built {
+    Query::select()
+        .distinct()
+        .column(UserTeams::TeamId)
+        .from(UserTeams::Table)
+        .and_where(Expr::col(UserTeams::UserId).eq(user_id))
+        .order_by(UserTeams::TeamId, sea_query::Order::Asc)
+        .build(SqliteQueryBuilder)
+}
+
 // ── OAuth States ──────────────────────────────────────────────────────────
 
 /// Insert an OAuth state token.
@@ -0,0 +1,90 @@
+//! Shared playbook query builders.
+
+use sea_query::{Expr, Order, Query, SqliteQueryBuilder};
+
+use super::tables::{Playbooks, Users};
+
+pub type Built = (String, sea_query::Values);
+
+/// Create a playbook or replace its content, keeping the original author and
+/// `created_at`.
+pub fn upsert(
+    name: &str,
+    kind: &str,
+    title: Option<&str>,
+    description: Option<&str>,
+    body: &str,
+    author_id: &str,
+) -> Built {
+    let sql = concat!(
+        "INSERT INTO \"playbooks\" ",
+        "(\"name\", \"kind\", \"title\", \"description\", \"body\", \"author_id\") ",
+        "VALUES (?, ?, ?, ?, ?, ?) ",
+        "ON CONFLICT (\"name\") DO UPDATE SET ",
+        "\"kind\" = excluded.\"kind\", \"title\" = excluded.\"title\", ",
+        "\"description\" = excluded.\"description\", \"body\" = excluded.\"body\", ",
+        "\"updated_at\" = datetime('now')"
+    )
+    .to_string();
+    let values = sea_query::Values(vec![
+        name.into(),
+        kind.into(),
+        title.map(str::to_string).into(),
+        description.map(str::to_string).into(),
+        body.into(),
+        author_id.into(),
+    ]);
+    (sql, values)
+}
+
+/// Base select for playbooks with the author's nickname.
+///
+/// Columns: name, kind, title, description, body, author nickname,
+/// created_at, updated_at.
+fn playbook_select() -> sea_query::SelectStatement {
+    Query::select()
+        .column((Playbooks::Table, Playbooks::Name))
+        .column((Playbooks::Table, Playbooks::Kind))
+        .column((Playbooks::Table, Playbooks::Title))
+        .column((Playbooks::Table, Playbooks::Description))
+        .column((Playbooks::Table, Playbooks::Body))
+        .column((Users::Table, Users::Nickname))
+        .column((Playbooks::Table, Playbooks::CreatedAt))
+        .column((Playbooks::Table, Playbooks::UpdatedAt))
+        .from(Playbooks::Table)
+        .left_join(
+            Users::Table,
+            Expr::col((Users::Table, Users::Id)).equals((Playbooks::Table, Playbooks::AuthorId)),
+        )
+        .to_owned()
+}
+
+/// All playbooks, ordered by name.
+pub fn list() -> Built {
+    playbook_select()
+        .order_by((Playbooks::Table, Playbooks::Name), Order::Asc)
+        .build(SqliteQueryBuilder)
+}
+
+/// One playbook by name.
+pub fn get(name: &str) -> Built {
+    playbook_select()
+        .and_where(Expr::col((Playbooks::Table, Playbooks::Name)).eq(name))
+        .build(SqliteQueryBuilder)
+}
+
+/// The author of a playbook. Columns: author_id.
+pub fn author_id(name: &str) -> Built {
+    Query::select()
+        .column(Playbooks::AuthorId)
+        .from(Playbooks::Table)
+        .and_where(Expr::col(Playbooks::Name).eq(name))
+        .build(SqliteQueryBuilder)
+}
+
+pub fn delete(name: &str) -> Built {
+    Query::delete()
+        .from_table(Playbooks::Table)
+        .and_where(Expr::col(Playbooks::Name).eq(name))
+        .build(SqliteQueryBuilder)
+}
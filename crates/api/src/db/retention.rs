@@ -0,0 +1,126 @@
+//! Team retention policy and expiry query builders.
+
+use sea_query::{Alias, Expr, Order, Query, SqliteQueryBuilder};
+
+use super::tables::{Sessions, TeamRetentionPolicies, UserTeams};
+use crate::RetentionPolicy;
+
+pub type Built = (String, sea_query::Values);
+
+/// Create or replace a team's policy.
+pub fn upsert(team_id: &str, policy: &RetentionPolicy, updated_by: &str) -> Built {
+    let sql = concat!(
+        "INSERT INTO \"team_retention_policies\" ",
+        "(\"team_id\", \"retain_days\", \"action\", \"exempt_bookmarked\", \"exempt_linked\", \"updated_by\") ",
+        "VALUES (?, ?, ?, ?, ?, ?) ",
+        "ON CONFLICT (\"team_id\") DO UPDATE SET ",
+        "\"retain_days\" = excluded.\"retain_days\", ",
+        "\"action\" = excluded.\"action\", ",
+        "\"exempt_bookmarked\" = excluded.\"exempt_bookmarked\", ",
+        "\"exempt_linked\" = excluded.\"exempt_linked\", ",
+        "\"updated_by\" = excluded.\"updated_by\", ",
+        "\"updated_at\" = datetime('now')"
+    )
+    .to_string();
+    let values = sea_query::Values(vec![
+        team_id.into(),
+        i64::from(policy.retain_days).into(),
+        policy.action.as_str().into(),
+        policy.exempt_bookmarked.into(),
+        policy.exempt_linked.into(),
+        updated_by.into(),
+    ]);
+    (sql, values)
+}
+
+/// Every team policy.
+///
+/// Columns: team_id, retain_days, action, exempt_bookmarked, exempt_linked.
+pub fn list() -> Built {
+    Query::select()
+        .columns([
+            TeamRetentionPolicies::TeamId,
+            TeamRetentionPolicies::RetainDays,
+            TeamRetentionPolicies::Action,
+            TeamRetentionPolicies::ExemptBookmarked,
+            TeamRetentionPolicies::ExemptLinked,
+        ])
+        .from(TeamRetentionPolicies::Table)
+        .order_by(TeamRetentionPolicies::TeamId, Order::Asc)
+        .build(SqliteQueryBuilder)
+}
+
+pub fn delete(team_id: &str) -> Built {
+    Query::delete()
+        .from_table(TeamRetentionPolicies::Table)
+        .and_where(Expr::col(TeamRetentionPolicies::TeamId).eq(team_id))
+        .build(SqliteQueryBuilder)
+}
+
+/// Every `(user_id, team_id)` membership, deduplicated across providers.
+pub fn list_memberships() -> Built {
+    Query::select()
+        .distinct()
+        .columns([UserTeams::UserId, UserTeams::TeamId])
+        .from(UserTeams::Table)
+        .build(SqliteQueryBuilder)
+}
+
+/// Sessions uploaded before `cutoff`, oldest first, with what retention
+/// needs to decide their fate. With `team_id`, only sessions uploaded by
+/// that team's members.
+///
+/// Only bookmarks by the uploader or a teammate count: members of `team_id`,
+/// or without it, anyone sharing a team with the uploader. Anyone else's
+/// bookmark cannot hold a session back from retention.
+///
+/// Columns: id, user_id, title, uploaded_at, bookmarked, linked, anonymized.
+pub fn list_expiry_candidates(cutoff: &str, team_id: Option<&str>) -> Built {
+    const BOOKMARKED: &str = "EXISTS (SELECT 1 FROM user_metadata m \
+         WHERE (m.key = 'bookmark:' || sessions.id \
+         OR substr(m.key, 1, length(sessions.id) + 16) = 'bookmark:' || sessions.id || '#event-') \
+         AND m.value IS NOT NULL \
+         AND (m.user_id = sessions.user_id OR m.user_id IN ";
+    let bookmarked = match team_id {
+        Some(team_id) => Expr::cust_with_values(
+            format!("{BOOKMARKED}(SELECT user_id FROM user_teams WHERE team_id = ?)))"),
+            [team_id],
+        ),
+        None => Expr::cust(format!(
+            "{BOOKMARKED}(SELECT peer.user_id FROM user_teams own \
+             JOIN user_teams peer ON peer.team_id = own.team_id \
+             WHERE own.user_id = sessions.user_id)))"
+        )),
+    };
+    let mut query = Query::select();
+    query
+        .columns([
+            Sessions::Id,
+            Sessions::UserId,
+            Sessions::Title,
+            Sessions::UploadedAt,
+        ])
+        .expr_as(bookmarked, Alias::new("bookmarked"))
+        .expr_as(
+            Expr::cust(
+                "EXISTS (SELECT 1 FROM session_links l \
+                 WHERE l.session_id = sessions.id OR l.linked_session_id = sessions.id)",
+            ),
+            Alias::new("linked"),
+        )
+        .expr_as(
+            Expr::col(Sessions::AnonymizedAt).is_not_null(),
+            Alias::new("anonymized"),
+        )
+        .from(Sessions::Table)
+        .and_where(Expr::col(Sessions::UploadedAt).lt(cutoff))
+        .order_by(Sessions::UploadedAt, Order::Asc)
+        .order_by(Sessions::Id, Order::Asc);
+    if let Some(team_id) = team_id {
+        query.and_where(Expr::cust_with_values(
+            "user_id IN (SELECT user_id FROM user_teams WHERE team_id = ?)",
+            [team_id],
+        ));
+    }
+    query.build(SqliteQueryBuilder)
+}
@@ -0,0 +1,122 @@
+//! Session review request query builders.
+
+use sea_query::{Alias, Expr, Order, Query, SqliteQueryBuilder};
+
+use super::tables::{ReviewRequests, Sessions, Users};
+
+pub type Built = (String, sea_query::Values);
+
+/// Insert a pending review request.
+pub fn insert(
+    id: &str,
+    session_id: &str,
+    requester_id: &str,
+    reviewer_id: &str,
+    note: Option<&str>,
+) -> Built {
+    Query::insert()
+        .into_table(ReviewRequests::Table)
+        .columns([
+            ReviewRequests::Id,
+            ReviewRequests::SessionId,
+            ReviewRequests::RequesterId,
+            ReviewRequests::ReviewerId,
+            ReviewRequests::Note,
+        ])
+        .values_panic([
+            id.into(),
+            session_id.into(),
+            requester_id.into(),
+            reviewer_id.into(),
+            note.map(str::to_string).into(),
+        ])
+        .build(SqliteQueryBuilder)
+}
+
+/// Base select for review requests, joining the session title and the
+/// requester and reviewer nicknames.
+///
+/// Columns: id, session_id, session title, requester nickname, reviewer
+/// nickname, note, status, comment, resolved_at, created_at.
+fn review_request_select() -> sea_query::SelectStatement {
+    let requester = Alias::new("requester");
+    let reviewer = Alias::new("reviewer");
+    Query::select()
+        .column((ReviewRequests::Table, ReviewRequests::Id))
+        .column((ReviewRequests::Table, ReviewRequests::SessionId))
+        .column((Sessions::Table, Sessions::Title))
+        .expr(Expr::col((requester.clone(), Users::Nickname)))
+        .expr(Expr::col((reviewer.clone(), Users::Nickname)))
+        .column((ReviewRequests::Table, ReviewRequests::Note))
+        .column((ReviewRequests::Table, ReviewRequests::Status))
+        .column((ReviewRequests::Table, ReviewRequests::Comment))
+        .column((ReviewRequests::Table, ReviewRequests::ResolvedAt))
+        .column((ReviewRequests::Table, ReviewRequests::CreatedAt))
+        .from(ReviewRequests::Table)
+        .left_join(
+            Sessions::Table,
+            Expr::col((Sessions::Table, Sessions::Id))
+                .equals((ReviewRequests::Table, ReviewRequests::SessionId)),
+        )
+        .join_as(
+            sea_query::JoinType::InnerJoin,
+            Users::Table,
+            requester.clone(),
+            Expr::col((requester, Users::Id))
+                .equals((ReviewRequests::Table, ReviewRequests::RequesterId)),
+        )
+        .join_as(
+            sea_query::JoinType::InnerJoin,
+            Users::Table,
+            reviewer.clone(),
+            Expr::col((reviewer, Users::Id))
+                .equals((ReviewRequests::Table, ReviewRequests::ReviewerId)),
+        )
+        .to_owned()
+}
+
+/// Review requests assigned to a reviewer, newest first, optionally by status.
+pub fn list_assigned(reviewer_id: &str, status: Option<&str>, limit: u32) -> Built {
+    list_by(ReviewRequests::ReviewerId, reviewer_id, status, limit)
+}
+
+/// Review requests sent by a requester, newest first, optionally by status.
+pub fn list_requested(requester_id: &str, status: Option<&str>, limit: u32) -> Built {
+    list_by(ReviewRequests::RequesterId, requester_id, status, limit)
+}
+
+fn list_by(column: ReviewRequests, user_id: &str, status: Option<&str>, limit: u32) -> Built {
+    let mut query = review_request_select();
+    query.and_where(Expr::col((ReviewRequests::Table, column)).eq(user_id));
+    if let Some(status) = status {
+        query.and_where(Expr::col((ReviewRequests::Table, ReviewRequests::Status)).eq(status));
+    }
+    query
+        .order_by(
+            (ReviewRequests::Table, ReviewRequests::CreatedAt),
+            Order::Desc,
+        )
+        .order_by((ReviewRequests::Table, ReviewRequests::Id), Order::Desc)
+        .limit(limit as u64)
+        .build(SqliteQueryBuilder)
+}
+
+/// Get a single review request by id.
+pub fn get_by_id(id: &str) -> Built {
+    review_request_select()
+        .and_where(Expr::col((ReviewRequests::Table, ReviewRequests::Id)).eq(id))
+        .build(SqliteQueryBuilder)
+}
+
+/// Resolve a pending review request. Only the reviewer may resolve.
+pub fn resolve(id: &str, reviewer_id: &str, status: &str, comment: Option<&str>) -> Built {
+    Query::update()
+        .table(ReviewRequests::Table)
+        .value(ReviewRequests::Status, status)
+        .value(ReviewRequests::Comment, comment.map(str::to_string))
+        .value(ReviewRequests::ResolvedAt, Expr::cust("datetime('now')"))
+        .and_where(Expr::col(ReviewRequests::Id).eq(id))
+        .and_where(Expr::col(ReviewRequests::ReviewerId).eq(reviewer_id))
+        .and_where(Expr::col(ReviewRequests::Status).eq("pending"))
+        .build(SqliteQueryBuilder)
+}
@@ -1,9 +1,14 @@
 //! Session query builders.
 
-use sea_query::{Alias, Asterisk, Expr, Func, JoinType, Order, Query, SqliteQueryBuilder};
+use sea_query::{
+    Alias, Asterisk, Expr, Func, JoinType, Order, Query, SimpleExpr, SqliteQueryBuilder,
+};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize};
 
 use super::tables::{SessionLinks, Sessions, Users};
-use crate::SessionListQuery;
+use crate::{ServiceError, SessionListQuery, SessionListResponse, SessionSummary, SortOrder};
 
 pub type Built = (String, sea_query::Values);
 
@@ -13,6 +18,82 @@ pub struct BuiltSessionListQuery {
     pub select_query: Built,
     pub page: u32,
     pub per_page: u32,
+    pub sort: SortOrder,
+}
+
+impl BuiltSessionListQuery {
+    /// Assemble the response from the executed queries.
+    ///
+    /// The select query fetches one look-ahead row; it is dropped here and
+    /// turned into `next_cursor`.
+    pub fn into_response(
+        self,
+        total: i64,
+        mut sessions: Vec<SessionSummary>,
+    ) -> SessionListResponse {
+        let next_cursor = if sessions.len() > self.per_page as usize {
+            sessions.truncate(self.per_page as usize);
+            sessions
+                .last()
+                .map(|last| SessionListCursor::after(&self.sort, last).encode())
+        } else {
+            None
+        };
+        SessionListResponse {
+            sessions,
+            total,
+            page: self.page,
+            per_page: self.per_page,
+            next_cursor,
+        }
+    }
+}
+
+/// Keyset position encoded in the opaque `cursor` query parameter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionListCursor {
+    #[serde(rename = "s")]
+    pub sort: SortOrder,
+    /// Primary sort column value for `popular`/`longest`/`best`.
+    #[serde(rename = "k", default, skip_serializing_if = "Option::is_none")]
+    pub sort_key: Option<i64>,
+    #[serde(rename = "u")]
+    pub uploaded_at: String,
+    #[serde(rename = "i")]
+    pub id: String,
+}
+
+impl SessionListCursor {
+    /// Cursor pointing just past `last` in `sort` order.
+    pub fn after(sort: &SortOrder, last: &SessionSummary) -> Self {
+        let sort_key = match sort {
+            SortOrder::Recent => None,
+            SortOrder::Popular => Some(last.message_count),
+            SortOrder::Longest => Some(last.duration_seconds),
+            SortOrder::Best => Some(last.session_score),
+        };
+        Self {
+            sort: sort.clone(),
+            sort_key,
+            uploaded_at: last.uploaded_at.clone(),
+            id: last.id.clone(),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(raw: &str) -> Result<Self, ServiceError> {
+        let invalid = || ServiceError::BadRequest("invalid session list cursor".into());
+        let bytes = URL_SAFE_NO_PAD.decode(raw.trim()).map_err(|_| invalid())?;
+        let cursor: Self = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+        if cursor.sort != SortOrder::Recent && cursor.sort_key.is_none() {
+            return Err(invalid());
+        }
+        Ok(cursor)
+    }
 }
 
 // ── Helpers ────────────────────────────────────────────────────────────────
@@ -50,6 +131,9 @@ fn session_columns(q: &mut sea_query::SelectStatement) -> &mut sea_query::Select
         .column((Sessions::Table, Sessions::MaxActiveAgents))
         .column((Sessions::Table, Sessions::SessionScore))
         .column((Sessions::Table, Sessions::ScorePlugin))
+        .column((Sessions::Table, Sessions::DeviceId))
+        .column((Sessions::Table, Sessions::VerifiedOrigin))
+        .column((Sessions::Table, Sessions::SessionKind))
 }
 
 /// Base SELECT for session listings (with users JOIN).
@@ -85,6 +169,7 @@ pub struct InsertParams<'a> {
     pub total_input_tokens: i64,
     pub total_output_tokens: i64,
     pub body_storage_key: &'a str,
+    pub body_size_bytes: i64,
     pub body_url: Option<&'a str>,
     pub git_remote: Option<&'a str>,
     pub git_branch: Option<&'a str>,
@@ -99,6 +184,12 @@ pub struct InsertParams<'a> {
     pub max_active_agents: i64,
     pub session_score: i64,
     pub score_plugin: &'a str,
+    pub content_fingerprint: Option<&'a str>,
+    pub device_id: Option<&'a str>,
+    pub body_sha256: Option<&'a str>,
+    pub body_signature: Option<&'a str>,
+    pub verified_origin: bool,
+    pub kind: crate::SessionKind,
 }
 
 /// INSERT a new session.
@@ -123,6 +214,7 @@ pub fn insert(p: &InsertParams<'_>) -> Built {
             Sessions::TotalInputTokens,
             Sessions::TotalOutputTokens,
             Sessions::BodyStorageKey,
+            Sessions::BodySizeBytes,
             Sessions::BodyUrl,
             Sessions::GitRemote,
             Sessions::GitBranch,
@@ -137,6 +229,12 @@ pub fn insert(p: &InsertParams<'_>) -> Built {
             Sessions::MaxActiveAgents,
             Sessions::SessionScore,
             Sessions::ScorePlugin,
+            Sessions::ContentFingerprint,
+            Sessions::DeviceId,
+            Sessions::BodySha256,
+            Sessions::BodySignature,
+            Sessions::VerifiedOrigin,
+            Sessions::SessionKind,
         ])
         .values_panic([
             p.id.into(),
@@ -156,6 +254,7 @@ pub fn insert(p: &InsertParams<'_>) -> Built {
             p.total_input_tokens.into(),
             p.total_output_tokens.into(),
             p.body_storage_key.into(),
+            p.body_size_bytes.into(),
             p.body_url.map(|s| s.to_string()).into(),
             p.git_remote.map(|s| s.to_string()).into(),
             p.git_branch.map(|s| s.to_string()).into(),
@@ -170,10 +269,128 @@ pub fn insert(p: &InsertParams<'_>) -> Built {
             p.max_active_agents.into(),
             p.session_score.into(),
             p.score_plugin.into(),
+            p.content_fingerprint.map(|s| s.to_string()).into(),
+            p.device_id.map(|s| s.to_string()).into(),
+            p.body_sha256.map(|s| s.to_string()).into(),
+            p.body_signature.map(|s| s.to_string()).into(),
+            p.verified_origin.into(),
+            p.kind.as_str().into(),
         ])
         .build(SqliteQueryBuilder)
 }
 
+/// Columns derived from a session body rather than taken from the upload
+/// request. `POST /api/admin/sessions/recompute` rebuilds them from stored
+/// bodies after derivation logic changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedColumns {
+    pub message_count: i64,
+    pub task_count: i64,
+    pub event_count: i64,
+    pub duration_seconds: i64,
+    pub total_input_tokens: i64,
+    pub total_output_tokens: i64,
+    pub files_modified: Option<String>,
+    pub files_read: Option<String>,
+    pub has_errors: bool,
+    pub max_active_agents: i64,
+}
+
+impl DerivedColumns {
+    pub fn from_session(session: &opensession_core::Session) -> Self {
+        let (files_modified, files_read, has_errors) =
+            opensession_core::extract::extract_file_metadata(session);
+        let stats = &session.stats;
+        Self {
+            message_count: crate::saturating_i64(stats.message_count),
+            task_count: crate::saturating_i64(stats.task_count),
+            event_count: crate::saturating_i64(stats.event_count),
+            duration_seconds: crate::saturating_i64(stats.duration_seconds),
+            total_input_tokens: crate::saturating_i64(stats.total_input_tokens),
+            total_output_tokens: crate::saturating_i64(stats.total_output_tokens),
+            files_modified,
+            files_read,
+            has_errors,
+            max_active_agents: crate::saturating_i64(
+                opensession_core::agent_metrics::max_active_agents(session) as u64,
+            ),
+        }
+    }
+}
+
+/// UPDATE a session's derived columns; only touches the row when a value changed.
+pub fn update_derived(id: &str, columns: &DerivedColumns) -> Built {
+    let changed = derived_values(columns)
+        .into_iter()
+        .fold(sea_query::Cond::any(), |cond, (column, value)| {
+            cond.add(Expr::col(column).is_not(value))
+        });
+    Query::update()
+        .table(Sessions::Table)
+        .values(derived_values(columns))
+        .and_where(Expr::col(Sessions::Id).eq(id))
+        .cond_where(changed)
+        .build(SqliteQueryBuilder)
+}
+
+fn derived_values(columns: &DerivedColumns) -> [(Sessions, SimpleExpr); 10] {
+    [
+        (Sessions::MessageCount, columns.message_count.into()),
+        (Sessions::TaskCount, columns.task_count.into()),
+        (Sessions::EventCount, columns.event_count.into()),
+        (Sessions::DurationSeconds, columns.duration_seconds.into()),
+        (
+            Sessions::TotalInputTokens,
+            columns.total_input_tokens.into(),
+        ),
+        (
+            Sessions::TotalOutputTokens,
+            columns.total_output_tokens.into(),
+        ),
+        (
+            Sessions::FilesModified,
+            columns.files_modified.clone().into(),
+        ),
+        (Sessions::FilesRead, columns.files_read.clone().into()),
+        (Sessions::HasErrors, columns.has_errors.into()),
+        (Sessions::MaxActiveAgents, columns.max_active_agents.into()),
+    ]
+}
+
+/// SELECT `id, body_storage_key` for up to `limit` sessions after `after`, in id order.
+pub fn list_bodies_after(after: Option<&str>, limit: u32) -> Built {
+    let mut query = Query::select();
+    query
+        .column(Sessions::Id)
+        .column(Sessions::BodyStorageKey)
+        .from(Sessions::Table)
+        .order_by(Sessions::Id, Order::Asc)
+        .limit(u64::from(limit));
+    if let Some(after) = after {
+        query.and_where(Expr::col(Sessions::Id).gt(after));
+    }
+    query.build(SqliteQueryBuilder)
+}
+
+/// SELECT every session uploaded by a user (with users JOIN), oldest first.
+pub fn list_by_user(user_id: &str) -> Built {
+    session_select()
+        .and_where(Expr::col((Sessions::Table, Sessions::UserId)).eq(user_id))
+        .order_by((Sessions::Table, Sessions::CreatedAt), Order::Asc)
+        .order_by((Sessions::Table, Sessions::Id), Order::Asc)
+        .build(SqliteQueryBuilder)
+}
+
+/// SELECT `id, body_storage_key` for every session uploaded by a user.
+pub fn list_storage_by_user(user_id: &str) -> Built {
+    Query::select()
+        .column(Sessions::Id)
+        .column(Sessions::BodyStorageKey)
+        .from(Sessions::Table)
+        .and_where(Expr::col(Sessions::UserId).eq(user_id))
+        .build(SqliteQueryBuilder)
+}
+
 /// SELECT a single session by id (with users JOIN).
 pub fn get_by_id(id: &str) -> Built {
     session_select()
@@ -181,20 +398,180 @@ pub fn get_by_id(id: &str) -> Built {
         .build(SqliteQueryBuilder)
 }
 
-/// SELECT `body_storage_key, body_url` for a session.
+/// SELECT `body_storage_key, body_url, body_sha256, body_signature, storage_class` for a session.
 pub fn get_storage_info(id: &str) -> Built {
     Query::select()
         .column(Sessions::BodyStorageKey)
         .column(Sessions::BodyUrl)
+        .column(Sessions::BodySha256)
+        .column(Sessions::BodySignature)
+        .column(Sessions::StorageClass)
         .from(Sessions::Table)
         .and_where(Expr::col(Sessions::Id).eq(id))
         .build(SqliteQueryBuilder)
 }
 
+/// SELECT `id, body_storage_key` for up to `limit` hot, locally stored
+/// bodies last read before `cutoff` (or uploaded before it if never read),
+/// least recently used first.
+pub fn list_idle_hot_bodies(cutoff: &str, limit: u32) -> Built {
+    let last_access = || {
+        Expr::expr(Func::coalesce([
+            Expr::col(Sessions::BodyAccessedAt).into(),
+            Expr::col(Sessions::UploadedAt).into(),
+        ]))
+    };
+    Query::select()
+        .column(Sessions::Id)
+        .column(Sessions::BodyStorageKey)
+        .from(Sessions::Table)
+        .and_where(Expr::col(Sessions::StorageClass).eq(crate::StorageClass::Hot.as_str()))
+        .and_where(
+            Expr::expr(Func::coalesce([
+                Expr::col(Sessions::BodyUrl).into(),
+                Expr::val("").into(),
+            ]))
+            .eq(""),
+        )
+        .and_where(Expr::col(Sessions::BodyStorageKey).ne(""))
+        .and_where(last_access().lt(cutoff))
+        .order_by_expr(last_access().into(), Order::Asc)
+        .limit(u64::from(limit))
+        .build(SqliteQueryBuilder)
+}
+
+/// UPDATE the storage tier of a session body.
+pub fn set_storage_class(id: &str, class: crate::StorageClass) -> Built {
+    Query::update()
+        .table(Sessions::Table)
+        .value(Sessions::StorageClass, class.as_str())
+        .and_where(Expr::col(Sessions::Id).eq(id))
+        .build(SqliteQueryBuilder)
+}
+
+/// UPDATE `body_accessed_at` to now after a body download.
+pub fn touch_body_access(id: &str) -> Built {
+    Query::update()
+        .table(Sessions::Table)
+        .value(Sessions::BodyAccessedAt, Expr::cust("datetime('now')"))
+        .and_where(Expr::col(Sessions::Id).eq(id))
+        .build(SqliteQueryBuilder)
+}
+
+/// Replacement column values for a session anonymized by retention.
+pub struct AnonymizedParams<'a> {
+    pub title: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub tags: Option<&'a str>,
+    pub working_directory: Option<&'a str>,
+    pub files_modified: Option<&'a str>,
+    pub files_read: Option<&'a str>,
+    pub body_sha256: &'a str,
+    pub body_size_bytes: i64,
+}
+
+/// UPDATE a session to its anonymized body. Git and PR columns name the
+/// work, and the upload signature and content fingerprint no longer describe
+/// the body, so all are cleared.
+pub fn set_anonymized(id: &str, p: &AnonymizedParams<'_>) -> Built {
+    Query::update()
+        .table(Sessions::Table)
+        .values([
+            (Sessions::Title, p.title.map(str::to_string).into()),
+            (
+                Sessions::Description,
+                p.description.map(str::to_string).into(),
+            ),
+            (Sessions::Tags, p.tags.map(str::to_string).into()),
+            (
+                Sessions::WorkingDirectory,
+                p.working_directory.map(str::to_string).into(),
+            ),
+            (
+                Sessions::FilesModified,
+                p.files_modified.map(str::to_string).into(),
+            ),
+            (Sessions::FilesRead, p.files_read.map(str::to_string).into()),
+            (Sessions::BodySha256, p.body_sha256.into()),
+            (Sessions::BodySizeBytes, p.body_size_bytes.into()),
+            (Sessions::BodySignature, Option::<String>::None.into()),
+            (Sessions::ContentFingerprint, Option::<String>::None.into()),
+            (Sessions::BodyUrl, Option::<String>::None.into()),
+            (Sessions::GitRemote, Option::<String>::None.into()),
+            (Sessions::GitBranch, Option::<String>::None.into()),
+            (Sessions::GitCommit, Option::<String>::None.into()),
+            (Sessions::GitRepoName, Option::<String>::None.into()),
+            (Sessions::PrNumber, Option::<i64>::None.into()),
+            (Sessions::PrUrl, Option::<String>::None.into()),
+            (Sessions::AnonymizedAt, Expr::cust("datetime('now')")),
+        ])
+        .and_where(Expr::col(Sessions::Id).eq(id))
+        .build(SqliteQueryBuilder)
+}
+
+/// SELECT `count, total body bytes` over sessions uploaded by members of
+/// `team_id` — the same membership rule as [`list_for_team`].
+pub fn team_usage(team_id: &str) -> Built {
+    Query::select()
+        .expr(Func::count(Expr::col(Asterisk)))
+        .expr(Func::coalesce([
+            Func::sum(Expr::col(Sessions::BodySizeBytes)).into(),
+            Expr::val(0).into(),
+        ]))
+        .from(Sessions::Table)
+        .and_where(Expr::cust_with_values(
+            "user_id IN (SELECT user_id FROM user_teams WHERE team_id = ?)",
+            [team_id],
+        ))
+        .build(SqliteQueryBuilder)
+}
+
+/// SELECT the earliest uploaded session id with a given content fingerprint.
+pub fn find_by_content_fingerprint(fingerprint: &str) -> Built {
+    Query::select()
+        .column(Sessions::Id)
+        .from(Sessions::Table)
+        .and_where(Expr::col(Sessions::ContentFingerprint).eq(fingerprint))
+        .order_by(Sessions::UploadedAt, Order::Asc)
+        .order_by(Sessions::Id, Order::Asc)
+        .limit(1)
+        .build(SqliteQueryBuilder)
+}
+
 /// Build paginated session list queries with dynamic filters.
-pub fn list(q: &SessionListQuery) -> BuiltSessionListQuery {
-    let per_page = q.per_page.clamp(1, 100);
-    let offset = (q.page.saturating_sub(1)) * per_page;
+///
+/// With a `cursor`, rows are fetched by keyset on `(sort key, uploaded_at, id)`
+/// so concurrent uploads never shift later pages. Without one, the deprecated
+/// `page` offset is applied.
+pub fn list(q: &SessionListQuery) -> Result<BuiltSessionListQuery, ServiceError> {
+    build_list(q, None)
+}
+
+/// [`list`] restricted to sessions uploaded by members of `team_id`.
+pub fn list_for_team(
+    q: &SessionListQuery,
+    team_id: &str,
+) -> Result<BuiltSessionListQuery, ServiceError> {
+    build_list(q, Some(team_id))
+}
+
+fn build_list(
+    q: &SessionListQuery,
+    team_id: Option<&str>,
+) -> Result<BuiltSessionListQuery, ServiceError> {
+    let per_page = q.page_size();
+    let sort = q.sort.clone().unwrap_or_default();
+    let cursor = q
+        .cursor
+        .as_deref()
+        .filter(|raw| !raw.trim().is_empty())
+        .map(SessionListCursor::decode)
+        .transpose()?;
+    if cursor.as_ref().is_some_and(|c| c.sort != sort) {
+        return Err(ServiceError::BadRequest(
+            "session list cursor does not match sort order".into(),
+        ));
+    }
 
     // Build shared WHERE conditions
     let mut count_q = Query::select()
@@ -235,6 +612,9 @@ pub fn list(q: &SessionListQuery) -> BuiltSessionListQuery {
         .column((Alias::new("s"), Sessions::MaxActiveAgents))
         .column((Alias::new("s"), Sessions::SessionScore))
         .column((Alias::new("s"), Sessions::ScorePlugin))
+        .column((Alias::new("s"), Sessions::DeviceId))
+        .column((Alias::new("s"), Sessions::VerifiedOrigin))
+        .column((Alias::new("s"), Sessions::SessionKind))
         .from_as(Sessions::Table, Alias::new("s"))
         .join_as(
             JoinType::LeftJoin,
@@ -243,33 +623,134 @@ pub fn list(q: &SessionListQuery) -> BuiltSessionListQuery {
             Expr::col((Alias::new("u"), Users::Id)).equals((Alias::new("s"), Sessions::UserId)),
         );
 
+    let mut conds = list_conditions(q);
+    if let Some(team_id) = team_id {
+        conds.push(Expr::cust_with_values(
+            "s.user_id IN (SELECT user_id FROM user_teams WHERE team_id = ?)",
+            [team_id],
+        ));
+    }
+    for cond in conds {
+        count_q.and_where(cond.clone());
+        select_q.and_where(cond);
+    }
+
+    let sort_col = match sort {
+        SortOrder::Recent => None,
+        SortOrder::Popular => Some(Sessions::MessageCount),
+        SortOrder::Longest => Some(Sessions::DurationSeconds),
+        SortOrder::Best => Some(Sessions::SessionScore),
+    };
+
+    if let Some(ref cursor) = cursor {
+        select_q.and_where(keyset_condition(cursor));
+    }
+
+    if let Some(col) = sort_col {
+        select_q.order_by((Alias::new("s"), col), Order::Desc);
+    }
+    select_q
+        .order_by((Alias::new("s"), Sessions::UploadedAt), Order::Desc)
+        .order_by((Alias::new("s"), Sessions::Id), Order::Desc);
+
+    // One look-ahead row tells `into_response` whether a next page exists.
+    select_q.limit(per_page as u64 + 1);
+    if cursor.is_none() {
+        let offset = (q.page.saturating_sub(1)) * per_page;
+        select_q.offset(offset as u64);
+    }
+
+    Ok(BuiltSessionListQuery {
+        count_query: count_q.build(SqliteQueryBuilder),
+        select_query: select_q.build(SqliteQueryBuilder),
+        page: q.page,
+        per_page,
+        sort,
+    })
+}
+
+/// Row-value comparison selecting rows strictly after `cursor` in DESC order.
+fn keyset_condition(cursor: &SessionListCursor) -> SimpleExpr {
+    let sort_col = match cursor.sort {
+        SortOrder::Recent => None,
+        SortOrder::Popular => Some("s.message_count"),
+        SortOrder::Longest => Some("s.duration_seconds"),
+        SortOrder::Best => Some("s.session_score"),
+    };
+    match (sort_col, cursor.sort_key) {
+        (Some(sort_col), Some(sort_key)) => Expr::cust_with_values(
+            format!("({sort_col}, s.uploaded_at, s.id) < (?, ?, ?)"),
+            [
+                sea_query::Value::from(sort_key),
+                cursor.uploaded_at.as_str().into(),
+                cursor.id.as_str().into(),
+            ],
+        ),
+        _ => Expr::cust_with_values(
+            "(s.uploaded_at, s.id) < (?, ?)",
+            [
+                sea_query::Value::from(cursor.uploaded_at.as_str()),
+                cursor.id.as_str().into(),
+            ],
+        ),
+    }
+}
+
+/// WHERE conditions shared by the count and select halves of [`list`].
+///
+/// Mirrors the local `LogFilter` semantics so the CLI, TUI, and server agree
+/// on what a filter means.
+pub fn list_conditions(q: &SessionListQuery) -> Vec<SimpleExpr> {
+    let col = |c: Sessions| Expr::col((Alias::new("s"), c));
+    let mut conds = Vec::new();
+
     // Base filter: non-empty sessions
-    let base_cond = Expr::col((Alias::new("s"), Sessions::EventCount))
-        .gt(0)
-        .or(Expr::col((Alias::new("s"), Sessions::MessageCount)).gt(0));
-    count_q.and_where(base_cond.clone());
-    select_q.and_where(base_cond);
+    conds.push(
+        col(Sessions::EventCount)
+            .gt(0)
+            .or(col(Sessions::MessageCount).gt(0)),
+    );
 
     if let Some(ref tool) = q.tool {
-        let cond = Expr::col((Alias::new("s"), Sessions::Tool)).eq(tool.as_str());
-        count_q.and_where(cond.clone());
-        select_q.and_where(cond);
+        conds.push(col(Sessions::Tool).eq(tool.as_str()));
     }
 
     if let Some(ref repo) = q.git_repo_name {
-        let cond = Expr::col((Alias::new("s"), Sessions::GitRepoName)).eq(repo.as_str());
-        count_q.and_where(cond.clone());
-        select_q.and_where(cond);
+        conds.push(col(Sessions::GitRepoName).eq(repo.as_str()));
     }
 
     if let Some(ref search) = q.search {
         let like = format!("%{search}%");
-        let cond = Expr::col((Alias::new("s"), Sessions::Title))
-            .like(&like)
-            .or(Expr::col((Alias::new("s"), Sessions::Description)).like(&like))
-            .or(Expr::col((Alias::new("s"), Sessions::Tags)).like(&like));
-        count_q.and_where(cond.clone());
-        select_q.and_where(cond);
+        conds.push(
+            col(Sessions::Title)
+                .like(&like)
+                .or(col(Sessions::Description).like(&like))
+                .or(col(Sessions::Tags).like(&like)),
+        );
+    }
+
+    if let Some(model) = non_blank(&q.model) {
+        conds.push(col(Sessions::AgentModel).like(model.replace('*', "%")));
+    }
+
+    if let Some(since) = non_blank(&q.since) {
+        conds.push(col(Sessions::CreatedAt).gte(since));
+    }
+
+    if let Some(before) = non_blank(&q.before) {
+        conds.push(col(Sessions::CreatedAt).lt(before));
+    }
+
+    if let Some(touches) = non_blank(&q.touches) {
+        conds.push(col(Sessions::FilesModified).like(format!("%\"{touches}\"%")));
+    }
+
+    if let Some(has_errors) = q.has_errors {
+        conds.push(col(Sessions::HasErrors).eq(has_errors));
+    }
+
+    if let Some(commit) = non_blank(&q.git_commit) {
+        conds.push(col(Sessions::GitCommit).like(format!("{commit}%")));
     }
 
     if let Some(ref time_range) = q.time_range {
@@ -280,45 +761,21 @@ pub fn list(q: &SessionListQuery) -> BuiltSessionListQuery {
             crate::TimeRange::All => None,
         };
         if let Some(interval) = interval {
-            let cond =
-                Expr::col((Alias::new("s"), Sessions::CreatedAt)).gte(Expr::cust_with_values::<
-                    _,
-                    sea_query::Value,
-                    _,
-                >(
-                    "datetime('now', ?)",
-                    [interval.into()],
-                ));
-            count_q.and_where(cond.clone());
-            select_q.and_where(cond);
-        }
-    }
-
-    // Sort
-    match q.sort.as_ref().unwrap_or(&crate::SortOrder::Recent) {
-        crate::SortOrder::Popular => {
-            select_q
-                .order_by((Alias::new("s"), Sessions::MessageCount), Order::Desc)
-                .order_by((Alias::new("s"), Sessions::CreatedAt), Order::Desc);
-        }
-        crate::SortOrder::Longest => {
-            select_q
-                .order_by((Alias::new("s"), Sessions::DurationSeconds), Order::Desc)
-                .order_by((Alias::new("s"), Sessions::CreatedAt), Order::Desc);
-        }
-        crate::SortOrder::Recent => {
-            select_q.order_by((Alias::new("s"), Sessions::CreatedAt), Order::Desc);
+            conds.push(col(Sessions::CreatedAt).gte(Expr::cust_with_values::<
+                _,
+                sea_query::Value,
+                _,
+            >(
+                "datetime('now', ?)", [interval.into()]
+            )));
         }
     }
 
-    select_q.limit(per_page as u64).offset(offset as u64);
+    conds
+}
 
-    BuiltSessionListQuery {
-        count_query: count_q.build(SqliteQueryBuilder),
-        select_query: select_q.build(SqliteQueryBuilder),
-        page: q.page,
-        per_page,
-    }
+fn non_blank(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
 }
 
 /// SELECT distinct non-empty git repo names ordered alphabetically.
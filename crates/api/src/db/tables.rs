@@ -25,6 +25,19 @@ pub enum ApiKeys {
     GraceUntil,
     RevokedAt,
     LastUsedAt,
+    DeviceId,
+}
+
+#[derive(Iden)]
+pub enum Devices {
+    Table,
+    UserId,
+    Id,
+    Name,
+    FirstSeenAt,
+    LastSeenAt,
+    RevokedAt,
+    PublicKey,
 }
 
 #[derive(Iden)]
@@ -62,6 +75,16 @@ pub enum Sessions {
     MaxActiveAgents,
     SessionScore,
     ScorePlugin,
+    ContentFingerprint,
+    DeviceId,
+    BodySha256,
+    BodySignature,
+    VerifiedOrigin,
+    StorageClass,
+    BodyAccessedAt,
+    BodySizeBytes,
+    AnonymizedAt,
+    SessionKind,
 }
 
 #[derive(Iden)]
@@ -98,6 +121,48 @@ pub enum OauthProviderTokens {
     UpdatedAt,
 }
 
+#[derive(Iden)]
+pub enum GuestTokens {
+    Table,
+    Id,
+    TeamId,
+    Label,
+    TokenHash,
+    CreatedBy,
+    CreatedAt,
+    LastUsedAt,
+}
+
+#[derive(Iden)]
+pub enum TeamRetentionPolicies {
+    Table,
+    TeamId,
+    RetainDays,
+    Action,
+    ExemptBookmarked,
+    ExemptLinked,
+    UpdatedBy,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum TeamUploadPolicies {
+    Table,
+    TeamId,
+    ExcludePatterns,
+    Action,
+    UpdatedBy,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum UserTeams {
+    Table,
+    UserId,
+    TeamId,
+    Provider,
+}
+
 #[derive(Iden)]
 pub enum OauthStates {
     Table,
@@ -133,6 +198,87 @@ pub enum GitCredentials {
     LastUsedAt,
 }
 
+#[derive(Iden)]
+pub enum Handoffs {
+    Table,
+    Id,
+    SenderId,
+    RecipientId,
+    ArtifactUri,
+    CanonicalJsonl,
+    Markdown,
+    Note,
+    Status,
+    ClaimedAt,
+    CompletedAt,
+    ResultSessionId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum SessionAttachments {
+    Table,
+    Id,
+    SessionId,
+    UserId,
+    FileName,
+    ContentType,
+    SizeBytes,
+    StorageKey,
+    CreatedAt,
+    Kind,
+    RecordedAt,
+}
+
+#[derive(Iden)]
+pub enum UserMetadata {
+    Table,
+    UserId,
+    Key,
+    Value,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum Playbooks {
+    Table,
+    Name,
+    Kind,
+    Title,
+    Description,
+    Body,
+    AuthorId,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+pub enum ReviewRequests {
+    Table,
+    Id,
+    SessionId,
+    RequesterId,
+    ReviewerId,
+    Note,
+    Status,
+    Comment,
+    ResolvedAt,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+pub enum TelemetryReports {
+    Table,
+    Id,
+    DaemonVersion,
+    PeriodStart,
+    PeriodEnd,
+    SessionsIndexed,
+    ParseFailures,
+    UploadErrors,
+    ReceivedAt,
+}
+
 #[derive(Iden)]
 pub enum BodyCache {
     Table,
@@ -0,0 +1,63 @@
+//! Anonymous daemon telemetry query builders.
+
+use sea_query::{Expr, Func, Order, Query, SqliteQueryBuilder};
+
+use super::tables::TelemetryReports;
+
+pub type Built = (String, sea_query::Values);
+
+/// Store one daemon report. No user, device or session columns exist.
+pub fn insert(
+    daemon_version: &str,
+    period_start: &str,
+    period_end: &str,
+    sessions_indexed: i64,
+    parse_failures: i64,
+    upload_errors: i64,
+) -> Built {
+    Query::insert()
+        .into_table(TelemetryReports::Table)
+        .columns([
+            TelemetryReports::DaemonVersion,
+            TelemetryReports::PeriodStart,
+            TelemetryReports::PeriodEnd,
+            TelemetryReports::SessionsIndexed,
+            TelemetryReports::ParseFailures,
+            TelemetryReports::UploadErrors,
+        ])
+        .values_panic([
+            daemon_version.into(),
+            period_start.into(),
+            period_end.into(),
+            sessions_indexed.into(),
+            parse_failures.into(),
+            upload_errors.into(),
+        ])
+        .build(SqliteQueryBuilder)
+}
+
+/// Per-version totals of reports received in the last `days` days, most
+/// reports first.
+///
+/// Columns: daemon_version, report count, sessions_indexed, parse_failures,
+/// upload_errors.
+pub fn totals_by_version(days: u32) -> Built {
+    Query::select()
+        .column(TelemetryReports::DaemonVersion)
+        .expr_as(
+            Func::count(Expr::col(TelemetryReports::Id)),
+            sea_query::Alias::new("reports"),
+        )
+        .expr(Func::sum(Expr::col(TelemetryReports::SessionsIndexed)))
+        .expr(Func::sum(Expr::col(TelemetryReports::ParseFailures)))
+        .expr(Func::sum(Expr::col(TelemetryReports::UploadErrors)))
+        .from(TelemetryReports::Table)
+        .and_where(
+            Expr::col(TelemetryReports::ReceivedAt)
+                .gte(Expr::cust(format!("datetime('now', '-{days} days')"))),
+        )
+        .group_by_col(TelemetryReports::DaemonVersion)
+        .order_by_expr(Expr::cust("reports"), Order::Desc)
+        .order_by(TelemetryReports::DaemonVersion, Order::Asc)
+        .build(SqliteQueryBuilder)
+}
@@ -0,0 +1,67 @@
+//! Team upload privacy policy query builders.
+
+use sea_query::{Expr, Query, SqliteQueryBuilder};
+
+use super::tables::TeamUploadPolicies;
+
+pub type Built = (String, sea_query::Values);
+
+/// Create or replace a team's policy. `exclude_patterns` holds one glob per line.
+pub fn upsert(team_id: &str, exclude_patterns: &str, action: &str, updated_by: &str) -> Built {
+    let sql = concat!(
+        "INSERT INTO \"team_upload_policies\" ",
+        "(\"team_id\", \"exclude_patterns\", \"action\", \"updated_by\") ",
+        "VALUES (?, ?, ?, ?) ",
+        "ON CONFLICT (\"team_id\") DO UPDATE SET ",
+        "\"exclude_patterns\" = excluded.\"exclude_patterns\", ",
+        "\"action\" = excluded.\"action\", ",
+        "\"updated_by\" = excluded.\"updated_by\", ",
+        "\"updated_at\" = datetime('now')"
+    )
+    .to_string();
+    let values = sea_query::Values(vec![
+        team_id.into(),
+        exclude_patterns.into(),
+        action.into(),
+        updated_by.into(),
+    ]);
+    (sql, values)
+}
+
+/// One team's policy.
+///
+/// Columns: exclude_patterns, action.
+pub fn get(team_id: &str) -> Built {
+    Query::select()
+        .columns([
+            TeamUploadPolicies::ExcludePatterns,
+            TeamUploadPolicies::Action,
+        ])
+        .from(TeamUploadPolicies::Table)
+        .and_where(Expr::col(TeamUploadPolicies::TeamId).eq(team_id))
+        .build(SqliteQueryBuilder)
+}
+
+/// Policies of every team `user_id` belongs to.
+///
+/// Columns: exclude_patterns, action.
+pub fn list_for_user(user_id: &str) -> Built {
+    Query::select()
+        .columns([
+            TeamUploadPolicies::ExcludePatterns,
+            TeamUploadPolicies::Action,
+        ])
+        .from(TeamUploadPolicies::Table)
+        .and_where(Expr::cust_with_values(
+            "team_id IN (SELECT team_id FROM user_teams WHERE user_id = ?)",
+            [user_id],
+        ))
+        .build(SqliteQueryBuilder)
+}
+
+pub fn delete(team_id: &str) -> Built {
+    Query::delete()
+        .from_table(TeamUploadPolicies::Table)
+        .and_where(Expr::col(TeamUploadPolicies::TeamId).eq(team_id))
+        .build(SqliteQueryBuilder)
+}
@@ -0,0 +1,65 @@
+//! Synced per-user metadata query builders.
+
+use sea_query::{Expr, Order, Query, SqliteQueryBuilder};
+
+use super::tables::UserMetadata;
+
+pub type Built = (String, sea_query::Values);
+
+/// Write one entry unless the stored one is at least as new (last writer wins).
+///
+/// A `None` value records a deletion so it can propagate to other machines.
+pub fn upsert_if_newer(user_id: &str, key: &str, value: Option<&str>, updated_at: i64) -> Built {
+    let sql = concat!(
+        "INSERT INTO \"user_metadata\" (\"user_id\", \"key\", \"value\", \"updated_at\") ",
+        "VALUES (?, ?, ?, ?) ",
+        "ON CONFLICT (\"user_id\", \"key\") DO UPDATE SET ",
+        "\"value\" = excluded.\"value\", \"updated_at\" = excluded.\"updated_at\" ",
+        "WHERE excluded.\"updated_at\" > \"user_metadata\".\"updated_at\""
+    )
+    .to_string();
+    let values = sea_query::Values(vec![
+        user_id.into(),
+        key.into(),
+        value.map(str::to_string).into(),
+        updated_at.into(),
+    ]);
+    (sql, values)
+}
+
+/// All of a user's entries, deletions included, ordered by key.
+///
+/// Columns: key, value, updated_at.
+pub fn list_by_user(user_id: &str) -> Built {
+    Query::select()
+        .columns([
+            UserMetadata::Key,
+            UserMetadata::Value,
+            UserMetadata::UpdatedAt,
+        ])
+        .from(UserMetadata::Table)
+        .and_where(Expr::col(UserMetadata::UserId).eq(user_id))
+        .order_by(UserMetadata::Key, Order::Asc)
+        .build(SqliteQueryBuilder)
+}
+
+/// A user's live entries whose key starts with `prefix`, ordered by key.
+///
+/// Columns: key, value, updated_at.
+pub fn list_live_by_prefix(user_id: &str, prefix: &str) -> Built {
+    Query::select()
+        .columns([
+            UserMetadata::Key,
+            UserMetadata::Value,
+            UserMetadata::UpdatedAt,
+        ])
+        .from(UserMetadata::Table)
+        .and_where(Expr::col(UserMetadata::UserId).eq(user_id))
+        .and_where(Expr::col(UserMetadata::Value).is_not_null())
+        .and_where(Expr::cust_with_values(
+            "substr(\"key\", 1, length(?)) = ?",
+            [prefix, prefix],
+        ))
+        .order_by(UserMetadata::Key, Order::Asc)
+        .build(SqliteQueryBuilder)
+}
@@ -112,6 +112,15 @@ pub fn update_password(user_id: &str, password_hash: &str, password_salt: &str)
         .build(SqliteQueryBuilder)
 }
 
+/// DELETE a user. Credentials, devices, tokens, metadata, handoffs and review
+/// requests cascade; playbooks they wrote keep their content without an author.
+pub fn delete(user_id: &str) -> Built {
+    Query::delete()
+        .from_table(Users::Table)
+        .and_where(Expr::col(Users::Id).eq(user_id))
+        .build(SqliteQueryBuilder)
+}
+
 // ── User settings queries ──────────────────────────────────────────────────
 
 /// Get password hash/salt for a user.
@@ -106,6 +106,31 @@ pub struct DesktopSummaryProviderDetectResponse {
     pub endpoint: Option<String>,
 }
 
+/// Connection check input for the HTTP (Ollama) summary provider being edited.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct DesktopSummaryProviderHealthRequest {
+    #[serde(default)]
+    pub endpoint: String,
+    #[serde(default)]
+    pub model: String,
+}
+
+/// Ollama summary provider reachability and installed models.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct DesktopSummaryProviderHealthResponse {
+    pub endpoint: String,
+    pub model: String,
+    pub reachable: bool,
+    pub model_installed: bool,
+    pub models: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 /// Structured desktop bridge error payload.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct DesktopApiError {
     pub code: String,
@@ -22,6 +23,10 @@ pub enum ServiceError {
     Forbidden(String),
     NotFound(String),
     Conflict(String),
+    /// A team storage quota would be exceeded.
+    QuotaExceeded(String),
+    /// A per-user limit on concurrent work is reached.
+    TooManyRequests(String),
     Internal(String),
 }
 
@@ -33,6 +38,8 @@ impl ServiceError {
             Self::Forbidden(_) => 403,
             Self::NotFound(_) => 404,
             Self::Conflict(_) => 409,
+            Self::QuotaExceeded(_) => 403,
+            Self::TooManyRequests(_) => 429,
             Self::Internal(_) => 500,
         }
     }
@@ -44,6 +51,8 @@ impl ServiceError {
             Self::Forbidden(_) => "forbidden",
             Self::NotFound(_) => "not_found",
             Self::Conflict(_) => "conflict",
+            Self::QuotaExceeded(_) => "quota_exceeded",
+            Self::TooManyRequests(_) => "too_many_requests",
             Self::Internal(_) => "internal",
         }
     }
@@ -55,6 +64,8 @@ impl ServiceError {
             | Self::Forbidden(message)
             | Self::NotFound(message)
             | Self::Conflict(message)
+            | Self::QuotaExceeded(message)
+            | Self::TooManyRequests(message)
             | Self::Internal(message) => message,
         }
     }
@@ -73,8 +84,9 @@ impl std::fmt::Display for ServiceError {
 impl std::error::Error for ServiceError {}
 
 /// API error payload.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct ApiError {
     pub code: String,
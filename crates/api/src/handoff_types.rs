@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+/// Request body for `POST /api/handoffs` — send a handoff artifact to a user's inbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct PublishHandoffRequest {
+    /// Recipient nickname, with or without a leading `@`.
+    pub recipient: String,
+    /// Local artifact URI (`os://artifact/<sha256>`).
+    pub artifact_uri: String,
+    /// Canonical summary JSONL of the artifact.
+    pub canonical_jsonl: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub markdown: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Lifecycle state of a published handoff.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum HandoffStatus {
+    #[default]
+    Pending,
+    Claimed,
+    Completed,
+}
+
+impl HandoffStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Pending => "pending",
+            Self::Claimed => "claimed",
+            Self::Completed => "completed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "claimed" => Some(Self::Claimed),
+            "completed" => Some(Self::Completed),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for HandoffStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A handoff delivered to a user's inbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct HandoffInboxItem {
+    pub id: String,
+    pub sender_nickname: String,
+    pub recipient_nickname: String,
+    pub artifact_uri: String,
+    pub canonical_jsonl: String,
+    pub markdown: Option<String>,
+    pub note: Option<String>,
+    pub status: HandoffStatus,
+    pub claimed_at: Option<String>,
+    pub completed_at: Option<String>,
+    /// Session that picked up the handoff, set on completion.
+    pub result_session_id: Option<String>,
+    pub created_at: String,
+}
+
+/// Request body for `POST /api/handoffs/{id}/complete`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct CompleteHandoffRequest {
+    /// Uploaded session that carried out the handed-off work.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Response for `GET /api/handoffs/inbox`.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct HandoffInboxResponse {
+    #[serde(default)]
+    pub items: Vec<HandoffInboxItem>,
+}
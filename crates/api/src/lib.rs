@@ -12,23 +12,41 @@ pub mod crypto;
 pub mod db;
 pub mod deploy;
 pub mod oauth;
+pub mod openapi;
 pub mod parse_preview_source;
 #[cfg(feature = "backend")]
 pub mod service;
 
+mod attachment_types;
 mod auth_types;
 mod desktop_runtime_types;
 mod errors;
+mod handoff_types;
+mod live_types;
 mod local_review_types;
+mod metadata_types;
 mod parse_preview_types;
+mod playbook_types;
+mod query_types;
+mod retention_types;
+mod review_request_types;
 mod session_types;
 mod shared_types;
+mod team_types;
+mod telemetry_types;
 
+pub use attachment_types::{
+    ASCIICAST_CONTENT_TYPE, AttachmentKind, AttachmentUploadQuery, MAX_ATTACHMENT_BYTES,
+    SessionAttachment, SessionAttachmentListResponse, asciicast_recorded_at, is_asciicast,
+};
 pub use auth_types::{
-    AuthRegisterRequest, AuthTokenResponse, ChangePasswordRequest, CreateGitCredentialRequest,
-    GitCredentialSummary, IssueApiKeyResponse, ListGitCredentialsResponse, LoginRequest,
-    LogoutRequest, OAuthLinkResponse, OkResponse, RefreshRequest, UserSettingsResponse,
-    VerifyResponse,
+    ACCOUNT_EXPORT_CONTENT_TYPE, AccountExportRecord, AuthRegisterRequest, AuthTokenResponse,
+    ChangePasswordRequest, CreateGitCredentialRequest, CreateGuestTokenRequest,
+    CreateGuestTokenResponse, DEVICE_ID_HEADER, DEVICE_NAME_HEADER, DeleteAccountResponse,
+    DeviceSummary, GitCredentialSummary, GuestTokenSummary, IssueApiKeyResponse,
+    ListDevicesResponse, ListGitCredentialsResponse, ListGuestTokensResponse, LoginRequest,
+    LogoutRequest, OAuthLinkResponse, OkResponse, RefreshRequest, RegisterDeviceKeyRequest,
+    UserSettingsResponse, VerifyResponse,
 };
 pub use desktop_runtime_types::{
     DESKTOP_IPC_CONTRACT_VERSION, DesktopChangeQuestionRequest, DesktopChangeQuestionResponse,
@@ -51,7 +69,8 @@ pub use desktop_runtime_types::{
     DesktopRuntimeVectorSearchSettingsUpdate, DesktopSessionSummaryResponse,
     DesktopSummaryBatchExecutionMode, DesktopSummaryBatchScope, DesktopSummaryBatchState,
     DesktopSummaryBatchStatusResponse, DesktopSummaryOutputShape,
-    DesktopSummaryProviderDetectResponse, DesktopSummaryProviderId,
+    DesktopSummaryProviderDetectResponse, DesktopSummaryProviderHealthRequest,
+    DesktopSummaryProviderHealthResponse, DesktopSummaryProviderId,
     DesktopSummaryProviderTransport, DesktopSummaryResponseStyle, DesktopSummarySourceMode,
     DesktopSummaryStorageBackend, DesktopSummaryTriggerMode, DesktopVectorChunkingMode,
     DesktopVectorIndexState, DesktopVectorIndexStatusResponse, DesktopVectorInstallState,
@@ -60,11 +79,23 @@ pub use desktop_runtime_types::{
     DesktopVectorSessionMatch,
 };
 pub use errors::{ApiError, DesktopApiError, ServiceError};
+pub use handoff_types::{
+    CompleteHandoffRequest, HandoffInboxItem, HandoffInboxResponse, HandoffStatus,
+    PublishHandoffRequest,
+};
+pub use live_types::{
+    LiveSessionInfo, LiveSessionListResponse, LiveUpdate, LiveViewer, MAX_LIVE_BUFFERED_EVENTS,
+};
 pub use local_review_types::{
     LocalReviewBundle, LocalReviewCommit, LocalReviewLayerFileChange, LocalReviewPrMeta,
     LocalReviewReviewerDigest, LocalReviewReviewerQa, LocalReviewSemanticSummary,
     LocalReviewSession,
 };
+pub use metadata_types::{
+    EventBookmark, EventBookmarkListResponse, MAX_METADATA_SYNC_ENTRIES, MetadataEntry,
+    PutEventBookmarkRequest, SyncMetadataRequest, SyncMetadataResponse, event_anchor,
+    event_bookmark_key, event_bookmark_prefix, event_permalink,
+};
 pub use opensession_core::trace::{
     Agent, Content, ContentBlock, Event, EventType, Session, SessionContext, Stats,
 };
@@ -72,13 +103,39 @@ pub use parse_preview_types::{
     ParseCandidate, ParsePreviewErrorResponse, ParsePreviewRequest, ParsePreviewResponse,
     ParseSource,
 };
+pub use playbook_types::{
+    MAX_PLAYBOOK_BODY_BYTES, MAX_PLAYBOOK_NAME_CHARS, Playbook, PlaybookKind, PlaybookListResponse,
+    PutPlaybookRequest, is_valid_playbook_name,
+};
+pub use query_types::{
+    MAX_QUERY_OPERATIONS, QueryOperation, QueryRequest, QueryResponse, QueryResult,
+};
+pub use retention_types::{
+    RetentionAction, RetentionPolicy, RetentionPreviewItem, RetentionPreviewResponse,
+    TeamRetentionResponse,
+};
+pub use review_request_types::{
+    CreateReviewRequest, ResolveReviewRequest, ReviewRequestItem, ReviewRequestListQuery,
+    ReviewRequestListResponse, ReviewRequestRole, ReviewRequestStatus,
+};
 pub use session_types::{
-    CapabilitiesResponse, DEFAULT_REGISTER_TARGETS, DEFAULT_SHARE_MODES, DesktopSessionListQuery,
-    HealthResponse, SessionDetail, SessionLink, SessionListQuery, SessionListResponse,
-    SessionRepoListResponse, SessionSummary, StreamEventsRequest, StreamEventsResponse,
-    UploadRequest, UploadResponse,
+    BatchUploadItemResult, BatchUploadRequest, BatchUploadResponse, CapabilitiesResponse,
+    DEFAULT_REGISTER_TARGETS, DEFAULT_SHARE_MODES, DesktopSessionListQuery, HealthResponse,
+    MAX_BATCH_UPLOAD_SESSIONS, RecomputeSessionError, RecomputeSessionsRequest,
+    RecomputeSessionsResponse, SESSION_BODY_SHA256_HEADER, SESSION_DELTA_HEADER, SessionDetail,
+    SessionKind, SessionLink, SessionListQuery, SessionListResponse, SessionRawQuery,
+    SessionRepoListResponse, SessionSummary, SessionValidationError, StorageClass,
+    StreamEventsRequest, StreamEventsResponse, UploadRequest, UploadResponse,
+    ValidateSessionResponse,
 };
 pub use shared_types::{LinkType, SortOrder, TimeRange, saturating_i64};
+pub use team_types::{
+    QuotaLimit, QuotaNotice, QuotaResource, TeamUploadPolicy, TeamUploadPolicyResponse,
+    TeamUsageResponse, UploadPolicyAction,
+};
+pub use telemetry_types::{
+    TelemetryReport, TelemetrySummaryQuery, TelemetrySummaryResponse, TelemetryTotals,
+};
 
 #[cfg(test)]
 mod schema_tests {
@@ -270,6 +327,38 @@ mod schema_tests {
         assert_eq!(decoded.share_modes, vec!["web", "git", "quick", "json"]);
     }
 
+    #[test]
+    fn query_operations_are_tagged_by_op() {
+        let req: QueryRequest = serde_json::from_str(
+            r#"{"operations":[{"op":"sessions","limit":5,"tool":"codex"},{"op":"session","id":"s1"},{"op":"session_repos"}]}"#,
+        )
+        .expect("query request should deserialize");
+
+        match &req.operations[..] {
+            [
+                QueryOperation::Sessions(query),
+                QueryOperation::Session { id },
+                QueryOperation::SessionRepos,
+            ] => {
+                assert_eq!(query.limit, Some(5));
+                assert_eq!(query.tool.as_deref(), Some("codex"));
+                assert_eq!(query.per_page, 20);
+                assert_eq!(id, "s1");
+            }
+            other => panic!("unexpected operations: {other:?}"),
+        }
+
+        let result = serde_json::to_value(QueryResult::Error(ApiError {
+            code: "not_found".to_string(),
+            message: "session not found".to_string(),
+        }))
+        .expect("result should serialize");
+        assert_eq!(
+            result,
+            serde_json::json!({"op": "error", "code": "not_found", "message": "session not found"})
+        );
+    }
+
     #[test]
     fn capabilities_defaults_are_stable() {
         assert_eq!(DEFAULT_REGISTER_TARGETS, &["local", "git"]);
@@ -284,6 +373,24 @@ mod tests {
     use std::path::PathBuf;
     use ts_rs::TS;
 
+    /// Whether a `type X = ...` declaration is a union at the top level, as
+    /// opposed to an object type whose fields happen to contain `|`.
+    fn has_top_level_union(decl: &str) -> bool {
+        let Some((_, body)) = decl.split_once(" = ") else {
+            return false;
+        };
+        let mut depth = 0i32;
+        for ch in body.chars() {
+            match ch {
+                '{' | '[' | '(' | '<' => depth += 1,
+                '}' | ']' | ')' | '>' => depth -= 1,
+                '|' if depth == 0 => return true,
+                _ => {}
+            }
+        }
+        false
+    }
+
     #[test]
     fn export_typescript() {
         let out_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -301,7 +408,7 @@ mod tests {
             ($($t:ty),+ $(,)?) => {
                 $(
                     let decl = <$t>::decl(&cfg);
-                    let is_struct_decl = decl.contains(" = {") && !decl.contains("} |");
+                    let is_struct_decl = decl.contains(" = {") && !has_top_level_union(&decl);
                     let decl = if is_struct_decl {
                         decl
                             .replacen("type ", "export interface ", 1)
@@ -334,14 +441,21 @@ mod tests {
             UserSettingsResponse,
             OkResponse,
             IssueApiKeyResponse,
+            DeviceSummary,
+            ListDevicesResponse,
             GitCredentialSummary,
             ListGitCredentialsResponse,
             CreateGitCredentialRequest,
             OAuthLinkResponse,
             UploadResponse,
+            BatchUploadItemResult,
+            BatchUploadResponse,
+            SessionValidationError,
+            ValidateSessionResponse,
             SessionSummary,
             SessionListResponse,
             SessionListQuery,
+            SessionRawQuery,
             DesktopSessionListQuery,
             SessionRepoListResponse,
             DesktopHandoffBuildRequest,
@@ -398,6 +512,8 @@ mod tests {
             DesktopRuntimeSettingsResponse,
             DesktopRuntimeSettingsUpdateRequest,
             DesktopSummaryProviderDetectResponse,
+            DesktopSummaryProviderHealthRequest,
+            DesktopSummaryProviderHealthResponse,
             DesktopSessionSummaryResponse,
             DesktopChangeReadRequest,
             DesktopChangeReadResponse,
@@ -406,8 +522,66 @@ mod tests {
             DesktopChangeReaderTtsResponse,
             DesktopChangeQuestionResponse,
             DesktopApiError,
+            SessionKind,
+            StorageClass,
             SessionDetail,
             SessionLink,
+            AttachmentKind,
+            SessionAttachment,
+            SessionAttachmentListResponse,
+            QueryOperation,
+            QueryRequest,
+            QueryResult,
+            QueryResponse,
+            PublishHandoffRequest,
+            HandoffStatus,
+            HandoffInboxItem,
+            CompleteHandoffRequest,
+            HandoffInboxResponse,
+            MetadataEntry,
+            SyncMetadataRequest,
+            SyncMetadataResponse,
+            EventBookmark,
+            EventBookmarkListResponse,
+            PutEventBookmarkRequest,
+            LiveSessionInfo,
+            LiveViewer,
+            LiveUpdate,
+            LiveSessionListResponse,
+            PlaybookKind,
+            Playbook,
+            PutPlaybookRequest,
+            PlaybookListResponse,
+            CreateReviewRequest,
+            ReviewRequestStatus,
+            ReviewRequestItem,
+            ReviewRequestRole,
+            ResolveReviewRequest,
+            ReviewRequestListResponse,
+            TelemetryReport,
+            TelemetryTotals,
+            TelemetrySummaryResponse,
+            RecomputeSessionError,
+            RecomputeSessionsResponse,
+            AccountExportRecord,
+            DeleteAccountResponse,
+            CreateGuestTokenRequest,
+            GuestTokenSummary,
+            CreateGuestTokenResponse,
+            ListGuestTokensResponse,
+            QuotaResource,
+            QuotaLimit,
+            QuotaNotice,
+            TeamUsageResponse,
+            RetentionAction,
+            RetentionPolicy,
+            TeamRetentionResponse,
+            RetentionPreviewItem,
+            RetentionPreviewResponse,
+            UploadPolicyAction,
+            TeamUploadPolicy,
+            TeamUploadPolicyResponse,
+            RegisterDeviceKeyRequest,
             ParseSource,
             ParseCandidate,
             ParsePreviewRequest,
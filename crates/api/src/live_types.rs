@@ -0,0 +1,81 @@
+use opensession_core::trace::{Agent, Event, SessionContext};
+use serde::{Deserialize, Serialize};
+
+/// Events the server keeps for one live session; viewers who join later see
+/// the most recent ones.
+pub const MAX_LIVE_BUFFERED_EVENTS: usize = 5_000;
+
+/// Someone watching a live session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct LiveViewer {
+    pub nickname: String,
+    /// RFC 3339.
+    pub joined_at: String,
+}
+
+/// A session its owner is sharing while the agent is still running.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct LiveSessionInfo {
+    pub session_id: String,
+    pub owner_nickname: String,
+    pub tool: Option<String>,
+    pub title: Option<String>,
+    /// RFC 3339.
+    pub started_at: String,
+    /// RFC 3339; the last time the owner appended events.
+    pub updated_at: String,
+    /// Events appended since sharing started, including ones no longer buffered.
+    pub event_count: u64,
+    /// Distinct viewers watching right now, the owner excluded.
+    pub viewers: Vec<LiveViewer>,
+}
+
+/// Response for `GET /api/live`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct LiveSessionListResponse {
+    pub sessions: Vec<LiveSessionInfo>,
+}
+
+/// One server-sent event on `GET /api/sessions/:id/live`, tagged by `type`.
+///
+/// A stream opens with a `snapshot`; a viewer that falls behind gets a fresh
+/// one instead of the updates it missed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum LiveUpdate {
+    Snapshot {
+        info: Box<LiveSessionInfo>,
+        #[cfg_attr(feature = "ts", ts(type = "any"))]
+        agent: Option<Agent>,
+        #[cfg_attr(feature = "ts", ts(type = "any"))]
+        context: Option<Box<SessionContext>>,
+        /// The buffered events, oldest first.
+        #[cfg_attr(feature = "ts", ts(type = "any[]"))]
+        events: Vec<Event>,
+    },
+    Events {
+        #[cfg_attr(feature = "ts", ts(type = "any[]"))]
+        events: Vec<Event>,
+        event_count: u64,
+    },
+    Presence {
+        viewers: Vec<LiveViewer>,
+    },
+    /// The owner stopped sharing; `uploaded` when the finished session is now
+    /// at `GET /api/sessions/:id`.
+    Ended {
+        uploaded: bool,
+    },
+}
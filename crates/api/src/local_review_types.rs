@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 /// Local review bundle generated from a PR range.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct LocalReviewBundle {
     pub review_id: String,
@@ -18,6 +19,7 @@ pub struct LocalReviewBundle {
 /// PR metadata for a local review bundle.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct LocalReviewPrMeta {
     pub url: String,
@@ -32,6 +34,7 @@ pub struct LocalReviewPrMeta {
 /// Reviewer-focused digest extracted from mapped sessions for a commit.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct LocalReviewReviewerQa {
     pub question: String,
@@ -42,6 +45,7 @@ pub struct LocalReviewReviewerQa {
 /// Reviewer-focused digest extracted from mapped sessions for a commit.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct LocalReviewReviewerDigest {
     #[serde(default)]
@@ -55,6 +59,7 @@ pub struct LocalReviewReviewerDigest {
 /// Commit row in a local review bundle.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct LocalReviewCommit {
     pub sha: String,
@@ -73,6 +78,7 @@ pub struct LocalReviewCommit {
 /// Layer/file summary section for local review semantic payloads.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct LocalReviewLayerFileChange {
     pub layer: String,
@@ -84,6 +90,7 @@ pub struct LocalReviewLayerFileChange {
 /// Commit-level semantic summary used when session mappings are weak or absent.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct LocalReviewSemanticSummary {
     pub changes: String,
@@ -105,6 +112,7 @@ pub struct LocalReviewSemanticSummary {
 /// Session payload mapped into a local review bundle.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct LocalReviewSession {
     pub session_id: String,
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+
+/// Most entries accepted by one `POST /api/sync/metadata`.
+pub const MAX_METADATA_SYNC_ENTRIES: usize = 500;
+
+/// One synced metadata entry (bookmark, note, alias, saved filter, ...).
+///
+/// Keys are namespaced by convention, e.g. `bookmark:<session-id>`, or
+/// `bookmark:<session-id>#event-<event-id>` for one event (see
+/// [`event_bookmark_key`]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct MetadataEntry {
+    pub key: String,
+    /// `None` marks a deletion.
+    pub value: Option<String>,
+    /// Writer's clock in unix milliseconds; the newest write wins.
+    pub updated_at: i64,
+}
+
+/// Request body for `POST /api/sync/metadata` — push local changes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct SyncMetadataRequest {
+    #[serde(default)]
+    pub entries: Vec<MetadataEntry>,
+}
+
+/// Response for `GET`/`POST /api/sync/metadata` — every entry the server
+/// holds for the user after applying the push, deletions included.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct SyncMetadataResponse {
+    #[serde(default)]
+    pub entries: Vec<MetadataEntry>,
+}
+
+/// A bookmarked event, stored as the synced metadata entry
+/// `bookmark:<session-id>#event-<event-id>` whose value is the note.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct EventBookmark {
+    pub session_id: String,
+    pub event_id: String,
+    pub note: Option<String>,
+    /// Unix milliseconds of the last change.
+    pub updated_at: i64,
+}
+
+impl EventBookmark {
+    /// The bookmark an entry holds, or `None` for other keys and deletions.
+    pub fn from_entry(entry: &MetadataEntry) -> Option<Self> {
+        let (session_id, event_id) = entry
+            .key
+            .strip_prefix("bookmark:")?
+            .split_once(EVENT_BOOKMARK_SEPARATOR)?;
+        let value = entry.value.as_deref()?;
+        Some(Self {
+            session_id: session_id.to_string(),
+            event_id: event_id.to_string(),
+            note: (!value.is_empty()).then(|| value.to_string()),
+            updated_at: entry.updated_at,
+        })
+    }
+}
+
+/// Response for `GET /api/sessions/:id/bookmarks` — your bookmarked events of
+/// the session, by event id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct EventBookmarkListResponse {
+    #[serde(default)]
+    pub bookmarks: Vec<EventBookmark>,
+}
+
+/// Request body for `PUT /api/sessions/:id/events/:event_id/bookmark`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct PutEventBookmarkRequest {
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+const EVENT_BOOKMARK_SEPARATOR: &str = "#event-";
+
+/// URL fragment (without `#`) the web viewer gives an event, e.g. `event-123`.
+pub fn event_anchor(event_id: &str) -> String {
+    format!("event-{event_id}")
+}
+
+/// Metadata key of an event bookmark: `bookmark:<session-id>#event-<event-id>`.
+pub fn event_bookmark_key(session_id: &str, event_id: &str) -> String {
+    format!("{}{event_id}", event_bookmark_prefix(session_id))
+}
+
+/// Common prefix of every event bookmark key of a session.
+pub fn event_bookmark_prefix(session_id: &str) -> String {
+    format!("bookmark:{session_id}{EVENT_BOOKMARK_SEPARATOR}")
+}
+
+/// Link to one event in the web viewer: `<base>/session/<id>#event-<event-id>`.
+pub fn event_permalink(base_url: &str, session_id: &str, event_id: &str) -> String {
+    format!(
+        "{}/session/{}#{}",
+        base_url.trim_end_matches('/'),
+        urlencoding::encode(session_id),
+        urlencoding::encode(&event_anchor(event_id))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventBookmark, MetadataEntry, event_bookmark_key, event_permalink};
+
+    fn entry(key: &str, value: Option<&str>) -> MetadataEntry {
+        MetadataEntry {
+            key: key.to_string(),
+            value: value.map(str::to_string),
+            updated_at: 7,
+        }
+    }
+
+    #[test]
+    fn event_bookmarks_round_trip_through_metadata_keys() {
+        let key = event_bookmark_key("s1", "e-42");
+        assert_eq!(key, "bookmark:s1#event-e-42");
+        let bookmark = EventBookmark::from_entry(&entry(&key, Some("went off the rails"))).unwrap();
+        assert_eq!(bookmark.session_id, "s1");
+        assert_eq!(bookmark.event_id, "e-42");
+        assert_eq!(bookmark.note.as_deref(), Some("went off the rails"));
+        assert_eq!(
+            EventBookmark::from_entry(&entry(&key, Some("")))
+                .unwrap()
+                .note,
+            None
+        );
+        assert!(EventBookmark::from_entry(&entry(&key, None)).is_none());
+        assert!(EventBookmark::from_entry(&entry("bookmark:s1", Some("1"))).is_none());
+    }
+
+    #[test]
+    fn permalinks_point_at_the_event_anchor() {
+        assert_eq!(
+            event_permalink("https://os.example.com/", "s1", "123"),
+            "https://os.example.com/session/s1#event-123"
+        );
+        assert_eq!(
+            event_permalink("", "s 1", "a/b"),
+            "/session/s%201#event-a%2Fb"
+        );
+    }
+}
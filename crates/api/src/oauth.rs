@@ -6,6 +6,8 @@
 //! This module contains only types, URL builders, and JSON parsing.
 //! No HTTP calls or DB access — those live in the backend adapters.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::ServiceError;
@@ -41,6 +43,13 @@ pub struct OAuthProviderConfig {
 
     /// External URL for browser redirects (may differ from token_url for Docker setups)
     pub external_authorize_url: Option<String>,
+
+    /// Userinfo claim listing the user's IdP groups: "groups" (Okta, Keycloak)
+    #[serde(default)]
+    pub groups_claim: Option<String>,
+    /// IdP group → team id. Members of a mapped group join that team on login.
+    #[serde(default)]
+    pub group_teams: BTreeMap<String, String>,
 }
 
 /// Maps provider-specific JSON field names to our internal fields.
@@ -66,6 +75,8 @@ pub struct OAuthUserInfo {
     pub username: String,
     pub email: Option<String>,
     pub avatar_url: Option<String>,
+    /// Values of the provider's groups claim, empty when none is configured.
+    pub groups: Vec<String>,
 }
 
 /// Normalize OAuth config values loaded from env/secrets.
@@ -279,15 +290,94 @@ pub fn extract_user_info(
         .as_str()
         .map(|s| s.to_string());
 
+    // Groups: an array of names, or a single name from IdPs that flatten one-element claims
+    let groups = match config.groups_claim.as_deref().map(|c| &userinfo_json[c]) {
+        Some(serde_json::Value::Array(values)) => values
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| s.to_string())
+            .collect(),
+        Some(serde_json::Value::String(s)) => vec![s.clone()],
+        _ => Vec::new(),
+    };
+
     Ok(OAuthUserInfo {
         provider_id: config.id.clone(),
         provider_user_id,
         username,
         email,
         avatar_url,
+        groups,
     })
 }
 
+/// Team ids the user's groups map to under `config.group_teams`, sorted and deduplicated.
+pub fn teams_for_groups(config: &OAuthProviderConfig, groups: &[String]) -> Vec<String> {
+    let mut teams: Vec<String> = groups
+        .iter()
+        .filter_map(|group| config.group_teams.get(group))
+        .cloned()
+        .collect();
+    teams.sort();
+    teams.dedup();
+    teams
+}
+
+/// Parse a group → team mapping: `"okta-platform=platform,okta-infra=infra"`.
+pub fn parse_group_teams(raw: &str) -> Result<BTreeMap<String, String>, ServiceError> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.split_once('=') {
+            Some((group, team)) if !group.trim().is_empty() && !team.trim().is_empty() => {
+                Ok((group.trim().to_string(), team.trim().to_string()))
+            }
+            _ => Err(ServiceError::BadRequest(format!(
+                "invalid group mapping '{entry}', expected group=team"
+            ))),
+        })
+        .collect()
+}
+
+// ── OIDC Discovery ──────────────────────────────────────────────────────────
+
+/// Endpoints advertised by an OpenID Connect issuer's discovery document.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcDiscovery {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub userinfo_endpoint: Option<String>,
+}
+
+/// `{issuer}/.well-known/openid-configuration`
+pub fn oidc_discovery_url(issuer_url: &str) -> String {
+    format!(
+        "{}/.well-known/openid-configuration",
+        issuer_url.trim_end_matches('/')
+    )
+}
+
+/// Parse a discovery document fetched from [`oidc_discovery_url`].
+///
+/// The advertised issuer must match the configured one, as OIDC Discovery requires.
+pub fn parse_oidc_discovery(issuer_url: &str, raw: &str) -> Result<OidcDiscovery, ServiceError> {
+    let discovery: OidcDiscovery = serde_json::from_str(raw)
+        .map_err(|e| ServiceError::Internal(format!("OIDC discovery document is invalid: {e}")))?;
+    if discovery.issuer.trim_end_matches('/') != issuer_url.trim_end_matches('/') {
+        return Err(ServiceError::Internal(format!(
+            "OIDC issuer mismatch: configured '{issuer_url}', discovery reports '{}'",
+            discovery.issuer
+        )));
+    }
+    if discovery.userinfo_endpoint.is_none() {
+        return Err(ServiceError::Internal(
+            "OIDC discovery document has no userinfo_endpoint".into(),
+        ));
+    }
+    Ok(discovery)
+}
+
 // ── Provider Presets ────────────────────────────────────────────────────────
 
 /// Create a GitHub OAuth2 provider config. Only needs client credentials.
@@ -310,6 +400,8 @@ pub fn github_preset(client_id: String, client_secret: String) -> OAuthProviderC
         },
         tls_skip_verify: false,
         external_authorize_url: None,
+        groups_claim: None,
+        group_teams: BTreeMap::new(),
     }
 }
 
@@ -347,6 +439,42 @@ pub fn gitlab_preset(
         },
         tls_skip_verify: false,
         external_authorize_url: ext_base.map(|b| format!("{b}/oauth/authorize")),
+        groups_claim: None,
+        group_teams: BTreeMap::new(),
+    }
+}
+
+/// Create a provider config for any OpenID Connect issuer (Okta, Keycloak,
+/// Azure AD, Google Workspace) from its discovery document.
+///
+/// Set `groups_claim` and `group_teams` on the result to map IdP groups to teams.
+pub fn oidc_preset(
+    id: String,
+    display_name: String,
+    discovery: &OidcDiscovery,
+    client_id: String,
+    client_secret: String,
+) -> OAuthProviderConfig {
+    OAuthProviderConfig {
+        id,
+        display_name,
+        authorize_url: discovery.authorization_endpoint.clone(),
+        token_url: discovery.token_endpoint.clone(),
+        userinfo_url: discovery.userinfo_endpoint.clone().unwrap_or_default(),
+        email_url: None,
+        client_id,
+        client_secret,
+        scopes: "openid profile email".into(),
+        field_map: OAuthFieldMap {
+            id: "sub".into(),
+            username: "preferred_username".into(),
+            email: "email".into(),
+            avatar: "picture".into(),
+        },
+        tls_skip_verify: false,
+        external_authorize_url: None,
+        groups_claim: None,
+        group_teams: BTreeMap::new(),
     }
 }
 
@@ -355,6 +483,7 @@ pub fn gitlab_preset(
 /// Available auth providers (returned by GET /api/auth/providers).
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct AuthProvidersResponse {
     pub email_password: bool,
@@ -364,6 +493,7 @@ pub struct AuthProvidersResponse {
 /// Public info about an OAuth provider.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct OAuthProviderInfo {
     pub id: String,
@@ -373,6 +503,7 @@ pub struct OAuthProviderInfo {
 /// A linked OAuth provider shown in user settings.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct LinkedProvider {
     pub provider: String,
@@ -446,7 +577,8 @@ mod tests {
     use super::{
         build_authorize_url, build_token_request_body, build_token_request_form,
         build_token_request_form_encoded, extract_user_info, github_preset, gitlab_preset,
-        normalize_oauth_config_value, parse_access_token_response,
+        normalize_oauth_config_value, oidc_discovery_url, oidc_preset, parse_access_token_response,
+        parse_group_teams, parse_oidc_discovery, teams_for_groups,
     };
 
     #[test]
@@ -622,4 +754,85 @@ mod tests {
             Some("https://gitlab.example.com/oauth/authorize")
         );
     }
+
+    #[test]
+    fn oidc_discovery_builds_provider_and_checks_issuer() {
+        let issuer = "https://example.okta.com/oauth2/default";
+        assert_eq!(
+            oidc_discovery_url(&format!("{issuer}/")),
+            "https://example.okta.com/oauth2/default/.well-known/openid-configuration"
+        );
+        let raw = r#"{
+            "issuer": "https://example.okta.com/oauth2/default",
+            "authorization_endpoint": "https://example.okta.com/oauth2/default/v1/authorize",
+            "token_endpoint": "https://example.okta.com/oauth2/default/v1/token",
+            "userinfo_endpoint": "https://example.okta.com/oauth2/default/v1/userinfo",
+            "jwks_uri": "https://example.okta.com/oauth2/default/v1/keys"
+        }"#;
+        let discovery = parse_oidc_discovery(issuer, raw).expect("discovery should parse");
+        let provider = oidc_preset(
+            "okta".into(),
+            "Okta".into(),
+            &discovery,
+            "cid".into(),
+            "secret".into(),
+        );
+        assert_eq!(
+            provider.token_url,
+            "https://example.okta.com/oauth2/default/v1/token"
+        );
+        assert_eq!(
+            provider.userinfo_url,
+            "https://example.okta.com/oauth2/default/v1/userinfo"
+        );
+        assert_eq!(provider.field_map.id, "sub");
+        assert_eq!(provider.scopes, "openid profile email");
+
+        let err = parse_oidc_discovery("https://evil.example", raw).expect_err("must fail");
+        assert!(err.message().contains("issuer mismatch"));
+        let no_userinfo =
+            r#"{"issuer":"https://idp.example","authorization_endpoint":"a","token_endpoint":"t"}"#;
+        assert!(parse_oidc_discovery("https://idp.example", no_userinfo).is_err());
+    }
+
+    #[test]
+    fn groups_claim_maps_to_teams() {
+        let discovery = parse_oidc_discovery(
+            "https://idp.example",
+            r#"{"issuer":"https://idp.example","authorization_endpoint":"a","token_endpoint":"t","userinfo_endpoint":"u"}"#,
+        )
+        .expect("discovery should parse");
+        let mut provider = oidc_preset(
+            "oidc".into(),
+            "SSO".into(),
+            &discovery,
+            "cid".into(),
+            "secret".into(),
+        );
+        provider.groups_claim = Some("groups".into());
+        provider.group_teams =
+            parse_group_teams(" eng-platform=platform, eng-infra=infra,sre=infra ,")
+                .expect("mapping should parse");
+
+        let userinfo = json!({
+            "sub": "00u1",
+            "preferred_username": "ada@example.com",
+            "email": "ada@example.com",
+            "groups": ["Everyone", "sre", "eng-platform", "eng-infra"]
+        });
+        let info = extract_user_info(&provider, &userinfo, None).expect("userinfo should parse");
+        assert_eq!(info.provider_user_id, "00u1");
+        assert_eq!(info.groups.len(), 4);
+        assert_eq!(
+            teams_for_groups(&provider, &info.groups),
+            vec!["infra".to_string(), "platform".to_string()]
+        );
+
+        let single = json!({"sub": "00u2", "groups": "sre"});
+        let info = extract_user_info(&provider, &single, None).expect("userinfo should parse");
+        assert_eq!(teams_for_groups(&provider, &info.groups), vec!["infra"]);
+
+        assert!(parse_group_teams("eng-platform").is_err());
+        assert!(parse_group_teams("=platform").is_err());
+    }
 }
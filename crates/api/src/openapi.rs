@@ -0,0 +1,637 @@
+//! OpenAPI 3.1 description of the HTTP API.
+//!
+//! The document is generated from the request/response types with the
+//! `schema` feature and committed, so the server can serve it without
+//! depending on `schemars`.
+//! Regenerate with: cargo test -p opensession-api --features schema -- openapi_document
+
+/// Committed OpenAPI document served at `GET /api/openapi.json`.
+pub const OPENAPI_JSON: &str = include_str!("../openapi/openapi.json");
+
+#[cfg(feature = "schema")]
+mod generate {
+    use schemars::{JsonSchema, SchemaGenerator, generate::SchemaSettings};
+    use serde_json::{Map, Value, json};
+
+    use crate::oauth::AuthProvidersResponse;
+    use crate::*;
+
+    #[derive(Clone, Copy)]
+    enum Auth {
+        Public,
+        Optional,
+        Required,
+    }
+
+    struct Endpoint {
+        method: &'static str,
+        path: &'static str,
+        summary: &'static str,
+        auth: Auth,
+        parameters: Vec<Value>,
+        request: Option<Value>,
+        status: &'static str,
+        response: Value,
+    }
+
+    impl Endpoint {
+        fn new(method: &'static str, path: &'static str, summary: &'static str) -> Self {
+            Self {
+                method,
+                path,
+                summary,
+                auth: Auth::Public,
+                parameters: Vec::new(),
+                request: None,
+                status: "200",
+                response: Value::Null,
+            }
+        }
+
+        fn auth(mut self, auth: Auth) -> Self {
+            self.auth = auth;
+            self
+        }
+
+        fn query(mut self, parameters: Vec<Value>) -> Self {
+            self.parameters.extend(parameters);
+            self
+        }
+
+        fn request(mut self, content: Value) -> Self {
+            self.request = Some(content);
+            self
+        }
+
+        fn created(mut self) -> Self {
+            self.status = "201";
+            self
+        }
+
+        fn response(mut self, content: Value) -> Self {
+            self.response = content;
+            self
+        }
+    }
+
+    fn json_body<T: JsonSchema>(generator: &mut SchemaGenerator) -> Value {
+        json!({ "application/json": { "schema": generator.subschema_for::<T>() } })
+    }
+
+    fn text_body(media_type: &str, description: &str) -> Value {
+        json!({ media_type: { "schema": { "type": "string", "description": description } } })
+    }
+
+    /// One query parameter per property of `T`, which must be a struct.
+    fn query_params<T: JsonSchema>(generator: &mut SchemaGenerator) -> Vec<Value> {
+        generator.subschema_for::<T>();
+        let definition = &generator.definitions()[T::schema_name().as_ref()];
+        let required = definition["required"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let Some(properties) = definition["properties"].as_object() else {
+            return Vec::new();
+        };
+        properties
+            .iter()
+            .map(|(name, schema)| {
+                let mut param = json!({
+                    "name": name,
+                    "in": "query",
+                    "required": required.contains(&Value::String(name.clone())),
+                    "schema": schema,
+                });
+                if let Some(description) = schema.get("description") {
+                    param["description"] = description.clone();
+                }
+                param
+            })
+            .collect()
+    }
+
+    fn endpoints(g: &mut SchemaGenerator) -> Vec<Endpoint> {
+        use Auth::{Optional, Required};
+
+        vec![
+            Endpoint::new("get", "/health", "Server health and version")
+                .response(json_body::<HealthResponse>(g)),
+            Endpoint::new("get", "/capabilities", "Features enabled on this server")
+                .response(json_body::<CapabilitiesResponse>(g)),
+            Endpoint::new(
+                "post",
+                "/parse/preview",
+                "Parse a remote or inline log into HAIL",
+            )
+            .auth(Optional)
+            .request(json_body::<ParsePreviewRequest>(g))
+            .response(json_body::<ParsePreviewResponse>(g)),
+            Endpoint::new("post", "/validate", "Validate a HAIL JSONL session body")
+                .request(text_body("application/jsonl", "HAIL JSONL session"))
+                .response(json_body::<ValidateSessionResponse>(g)),
+            Endpoint::new(
+                "get",
+                "/schema/session.json",
+                "JSON Schema for a HAIL session",
+            )
+            .response(text_body("application/schema+json", "JSON Schema document")),
+            Endpoint::new("get", "/openapi.json", "This OpenAPI document")
+                .response(text_body("application/json", "OpenAPI 3.1 document")),
+            Endpoint::new("post", "/query", "Run several typed reads in one request")
+                .auth(Optional)
+                .request(json_body::<QueryRequest>(g))
+                .response(json_body::<QueryResponse>(g)),
+            Endpoint::new("get", "/review/local/{review_id}", "Local PR review bundle")
+                .response(json_body::<LocalReviewBundle>(g)),
+            Endpoint::new("post", "/auth/register", "Create an email/password account")
+                .request(json_body::<AuthRegisterRequest>(g))
+                .response(json_body::<AuthTokenResponse>(g)),
+            Endpoint::new("post", "/auth/login", "Exchange email/password for tokens")
+                .request(json_body::<LoginRequest>(g))
+                .response(json_body::<AuthTokenResponse>(g)),
+            Endpoint::new("post", "/auth/refresh", "Rotate a refresh token")
+                .request(json_body::<RefreshRequest>(g))
+                .response(json_body::<AuthTokenResponse>(g)),
+            Endpoint::new("post", "/auth/logout", "Invalidate a refresh token")
+                .request(json_body::<LogoutRequest>(g))
+                .response(json_body::<OkResponse>(g)),
+            Endpoint::new("put", "/auth/password", "Change the account password")
+                .auth(Required)
+                .request(json_body::<ChangePasswordRequest>(g))
+                .response(json_body::<OkResponse>(g)),
+            Endpoint::new("post", "/auth/verify", "Check a token")
+                .auth(Required)
+                .response(json_body::<VerifyResponse>(g)),
+            Endpoint::new("get", "/auth/me", "Current user settings")
+                .auth(Required)
+                .response(json_body::<UserSettingsResponse>(g)),
+            Endpoint::new(
+                "delete",
+                "/auth/me",
+                "Delete your account and everything you uploaded",
+            )
+            .auth(Required)
+            .response(json_body::<DeleteAccountResponse>(g)),
+            Endpoint::new("get", "/auth/export", "Download all of your data")
+                .auth(Required)
+                .response(text_body(
+                    ACCOUNT_EXPORT_CONTENT_TYPE,
+                    "One AccountExportRecord JSON object per line",
+                )),
+            Endpoint::new("post", "/auth/api-keys/issue", "Issue a new API key")
+                .auth(Required)
+                .response(json_body::<IssueApiKeyResponse>(g)),
+            Endpoint::new(
+                "get",
+                "/auth/devices",
+                "Machines that used your credentials",
+            )
+            .auth(Required)
+            .response(json_body::<ListDevicesResponse>(g)),
+            Endpoint::new(
+                "delete",
+                "/auth/devices/{id}",
+                "Revoke a device and its keys",
+            )
+            .auth(Required)
+            .response(json_body::<OkResponse>(g)),
+            Endpoint::new(
+                "put",
+                "/auth/devices/{id}/signing-key",
+                "Register this device's upload signing key",
+            )
+            .auth(Required)
+            .request(json_body::<RegisterDeviceKeyRequest>(g))
+            .response(json_body::<OkResponse>(g)),
+            Endpoint::new("get", "/auth/git-credentials", "List git credentials")
+                .auth(Required)
+                .response(json_body::<ListGitCredentialsResponse>(g)),
+            Endpoint::new("post", "/auth/git-credentials", "Store a git credential")
+                .auth(Required)
+                .request(json_body::<CreateGitCredentialRequest>(g))
+                .created()
+                .response(json_body::<GitCredentialSummary>(g)),
+            Endpoint::new(
+                "delete",
+                "/auth/git-credentials/{id}",
+                "Delete a git credential",
+            )
+            .auth(Required)
+            .response(json_body::<OkResponse>(g)),
+            Endpoint::new(
+                "get",
+                "/teams/{team_id}/guest-tokens",
+                "List a team's read-only guest tokens",
+            )
+            .auth(Required)
+            .response(json_body::<ListGuestTokensResponse>(g)),
+            Endpoint::new(
+                "post",
+                "/teams/{team_id}/guest-tokens",
+                "Issue a read-only guest token for a team",
+            )
+            .auth(Required)
+            .request(json_body::<CreateGuestTokenRequest>(g))
+            .created()
+            .response(json_body::<CreateGuestTokenResponse>(g)),
+            Endpoint::new(
+                "delete",
+                "/teams/{team_id}/guest-tokens/{id}",
+                "Revoke a guest token",
+            )
+            .auth(Required)
+            .response(json_body::<OkResponse>(g)),
+            Endpoint::new(
+                "get",
+                "/teams/{team_id}/usage",
+                "A team's storage usage and quotas",
+            )
+            .auth(Required)
+            .response(json_body::<TeamUsageResponse>(g)),
+            Endpoint::new(
+                "get",
+                "/teams/{team_id}/retention",
+                "A team's session retention policy",
+            )
+            .auth(Required)
+            .response(json_body::<TeamRetentionResponse>(g)),
+            Endpoint::new(
+                "put",
+                "/teams/{team_id}/retention",
+                "Set a team's session retention policy",
+            )
+            .auth(Required)
+            .request(json_body::<RetentionPolicy>(g))
+            .response(json_body::<TeamRetentionResponse>(g)),
+            Endpoint::new(
+                "delete",
+                "/teams/{team_id}/retention",
+                "Return a team to the server's default retention",
+            )
+            .auth(Required)
+            .response(json_body::<TeamRetentionResponse>(g)),
+            Endpoint::new(
+                "get",
+                "/teams/{team_id}/retention/preview",
+                "Sessions the next retention run would delete or anonymize",
+            )
+            .auth(Required)
+            .response(json_body::<RetentionPreviewResponse>(g)),
+            Endpoint::new(
+                "get",
+                "/teams/{team_id}/upload-policy",
+                "A team's upload privacy policy",
+            )
+            .auth(Required)
+            .response(json_body::<TeamUploadPolicyResponse>(g)),
+            Endpoint::new(
+                "put",
+                "/teams/{team_id}/upload-policy",
+                "Set a team's upload privacy policy",
+            )
+            .auth(Required)
+            .request(json_body::<TeamUploadPolicy>(g))
+            .response(json_body::<TeamUploadPolicyResponse>(g)),
+            Endpoint::new(
+                "delete",
+                "/teams/{team_id}/upload-policy",
+                "Drop a team's upload privacy policy",
+            )
+            .auth(Required)
+            .response(json_body::<TeamUploadPolicyResponse>(g)),
+            Endpoint::new("get", "/auth/providers", "Available login providers")
+                .response(json_body::<AuthProvidersResponse>(g)),
+            Endpoint::new(
+                "post",
+                "/auth/oauth/{provider}/link",
+                "Link an OAuth identity",
+            )
+            .auth(Required)
+            .response(json_body::<OAuthLinkResponse>(g)),
+            Endpoint::new("get", "/sessions", "List sessions")
+                .auth(Optional)
+                .query(query_params::<SessionListQuery>(g))
+                .response(json_body::<SessionListResponse>(g)),
+            Endpoint::new("get", "/sessions/repos", "Repository names with sessions")
+                .auth(Optional)
+                .response(json_body::<SessionRepoListResponse>(g)),
+            Endpoint::new("post", "/sessions/batch", "Upload several sessions")
+                .auth(Required)
+                .request(json_body::<BatchUploadRequest>(g))
+                .response(json_body::<BatchUploadResponse>(g)),
+            Endpoint::new("get", "/sessions/{id}", "Session detail with links")
+                .response(json_body::<SessionDetail>(g)),
+            Endpoint::new(
+                "get",
+                "/sessions/{id}/raw",
+                "Download a session's HAIL JSONL body",
+            )
+            .query(query_params::<SessionRawQuery>(g))
+            .response(text_body("application/jsonl", "HAIL JSONL session")),
+            Endpoint::new(
+                "get",
+                "/sessions/{id}/attachments",
+                "A session's attachments",
+            )
+            .response(json_body::<SessionAttachmentListResponse>(g)),
+            Endpoint::new(
+                "post",
+                "/sessions/{id}/attachments",
+                "Attach a file to your session",
+            )
+            .auth(Required)
+            .query(query_params::<AttachmentUploadQuery>(g))
+            .request(text_body("application/octet-stream", "File content"))
+            .created()
+            .response(json_body::<SessionAttachment>(g)),
+            Endpoint::new(
+                "get",
+                "/sessions/{id}/attachments/{attachment_id}",
+                "Download an attachment",
+            )
+            .response(text_body("application/octet-stream", "File content")),
+            Endpoint::new(
+                "post",
+                "/sessions/{id}/live",
+                "Share a running session with your teams",
+            )
+            .auth(Required)
+            .request(json_body::<StreamEventsRequest>(g))
+            .created()
+            .response(json_body::<LiveSessionInfo>(g)),
+            Endpoint::new(
+                "get",
+                "/sessions/{id}/live",
+                "Follow a live session as server-sent events",
+            )
+            .auth(Required)
+            .response(json!({
+                "text/event-stream": { "schema": g.subschema_for::<LiveUpdate>() }
+            })),
+            Endpoint::new(
+                "delete",
+                "/sessions/{id}/live",
+                "Stop sharing a session live",
+            )
+            .auth(Required)
+            .response(json_body::<OkResponse>(g)),
+            Endpoint::new(
+                "post",
+                "/sessions/{id}/events",
+                "Append events to a session you share live",
+            )
+            .auth(Required)
+            .request(json_body::<StreamEventsRequest>(g))
+            .response(json_body::<StreamEventsResponse>(g)),
+            Endpoint::new("get", "/live", "Live sessions shared by you or a teammate")
+                .auth(Required)
+                .response(json_body::<LiveSessionListResponse>(g)),
+            Endpoint::new(
+                "get",
+                "/sessions/{id}/bookmarks",
+                "Your bookmarked events of a session",
+            )
+            .auth(Required)
+            .response(json_body::<EventBookmarkListResponse>(g)),
+            Endpoint::new(
+                "put",
+                "/sessions/{id}/events/{event_id}/bookmark",
+                "Bookmark an event",
+            )
+            .auth(Required)
+            .request(json_body::<PutEventBookmarkRequest>(g))
+            .response(json_body::<EventBookmark>(g)),
+            Endpoint::new(
+                "delete",
+                "/sessions/{id}/events/{event_id}/bookmark",
+                "Remove an event bookmark",
+            )
+            .auth(Required)
+            .response(json_body::<OkResponse>(g)),
+            Endpoint::new("get", "/sync/metadata", "Synced metadata entries")
+                .auth(Required)
+                .response(json_body::<SyncMetadataResponse>(g)),
+            Endpoint::new(
+                "post",
+                "/sync/metadata",
+                "Merge metadata entries (last writer wins)",
+            )
+            .auth(Required)
+            .request(json_body::<SyncMetadataRequest>(g))
+            .response(json_body::<SyncMetadataResponse>(g)),
+            Endpoint::new("post", "/handoffs", "Send a handoff to a user's inbox")
+                .auth(Required)
+                .request(json_body::<PublishHandoffRequest>(g))
+                .created()
+                .response(json_body::<HandoffInboxItem>(g)),
+            Endpoint::new("get", "/handoffs/inbox", "Handoffs addressed to you")
+                .auth(Required)
+                .query(vec![
+                    json!({ "name": "status", "in": "query", "required": false,
+                            "schema": g.subschema_for::<HandoffStatus>() }),
+                    json!({ "name": "limit", "in": "query", "required": false,
+                            "schema": { "type": "integer", "minimum": 1, "maximum": 200 } }),
+                ])
+                .response(json_body::<HandoffInboxResponse>(g)),
+            Endpoint::new("post", "/handoffs/{id}/claim", "Claim a pending handoff")
+                .auth(Required)
+                .response(json_body::<HandoffInboxItem>(g)),
+            Endpoint::new(
+                "post",
+                "/handoffs/{id}/complete",
+                "Complete a claimed handoff",
+            )
+            .auth(Required)
+            .request(json_body::<CompleteHandoffRequest>(g))
+            .response(json_body::<HandoffInboxItem>(g)),
+            Endpoint::new("get", "/playbooks", "Shared playbooks")
+                .auth(Required)
+                .response(json_body::<PlaybookListResponse>(g)),
+            Endpoint::new("get", "/playbooks/{name}", "One playbook")
+                .auth(Required)
+                .response(json_body::<Playbook>(g)),
+            Endpoint::new(
+                "put",
+                "/playbooks/{name}",
+                "Create a playbook or replace one you wrote",
+            )
+            .auth(Required)
+            .request(json_body::<PutPlaybookRequest>(g))
+            .response(json_body::<Playbook>(g)),
+            Endpoint::new("delete", "/playbooks/{name}", "Delete a playbook you wrote")
+                .auth(Required)
+                .response(json_body::<OkResponse>(g)),
+            Endpoint::new(
+                "post",
+                "/sessions/{id}/review-requests",
+                "Ask a user to review a session",
+            )
+            .auth(Required)
+            .request(json_body::<CreateReviewRequest>(g))
+            .created()
+            .response(json_body::<ReviewRequestItem>(g)),
+            Endpoint::new(
+                "get",
+                "/review-requests",
+                "Review requests assigned to you or sent by you",
+            )
+            .auth(Required)
+            .query(query_params::<ReviewRequestListQuery>(g))
+            .response(json_body::<ReviewRequestListResponse>(g)),
+            Endpoint::new(
+                "post",
+                "/review-requests/{id}/resolve",
+                "Approve or request changes on a review request",
+            )
+            .auth(Required)
+            .request(json_body::<ResolveReviewRequest>(g))
+            .response(json_body::<ReviewRequestItem>(g)),
+            Endpoint::new(
+                "post",
+                "/telemetry",
+                "Report anonymous daemon usage counters",
+            )
+            .auth(Required)
+            .request(json_body::<TelemetryReport>(g))
+            .created()
+            .response(json_body::<OkResponse>(g)),
+            Endpoint::new(
+                "get",
+                "/admin/telemetry",
+                "Fleet telemetry totals by daemon version (admin)",
+            )
+            .auth(Required)
+            .query(query_params::<TelemetrySummaryQuery>(g))
+            .response(json_body::<TelemetrySummaryResponse>(g)),
+            Endpoint::new(
+                "post",
+                "/admin/sessions/recompute",
+                "Rebuild derived session columns from stored bodies (admin)",
+            )
+            .auth(Required)
+            .request(json_body::<RecomputeSessionsRequest>(g))
+            .response(json_body::<RecomputeSessionsResponse>(g)),
+            Endpoint::new("delete", "/admin/sessions/{id}", "Delete a session (admin)")
+                .auth(Required)
+                .response(json_body::<OkResponse>(g)),
+        ]
+    }
+
+    fn path_params(path: &str) -> Vec<Value> {
+        path.split('/')
+            .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+            .map(|name| {
+                json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } })
+            })
+            .collect()
+    }
+
+    fn operation(endpoint: Endpoint, error: &Value) -> Value {
+        let mut parameters = path_params(endpoint.path);
+        parameters.extend(endpoint.parameters);
+
+        let mut op = Map::new();
+        op.insert("summary".into(), endpoint.summary.into());
+        if !parameters.is_empty() {
+            op.insert("parameters".into(), parameters.into());
+        }
+        if let Some(content) = endpoint.request {
+            op.insert(
+                "requestBody".into(),
+                json!({ "required": true, "content": content }),
+            );
+        }
+        op.insert(
+            "responses".into(),
+            json!({
+                endpoint.status: { "description": "Success", "content": endpoint.response },
+                "default": { "description": "Error", "content": error },
+            }),
+        );
+        let security = match endpoint.auth {
+            Auth::Public => json!([]),
+            Auth::Optional => json!([{}, { "bearerAuth": [] }]),
+            Auth::Required => json!([{ "bearerAuth": [] }]),
+        };
+        op.insert("security".into(), security);
+        Value::Object(op)
+    }
+
+    /// Build the OpenAPI document from the route table and API types.
+    pub fn openapi_document() -> Value {
+        let mut generator = SchemaSettings::draft2020_12()
+            .with(|settings| {
+                settings.definitions_path = "/components/schemas".into();
+                settings.meta_schema = None;
+            })
+            .into_generator();
+        let error = json_body::<ApiError>(&mut generator);
+
+        let mut paths = Map::new();
+        for endpoint in endpoints(&mut generator) {
+            let item = paths
+                .entry(endpoint.path)
+                .or_insert_with(|| Value::Object(Map::new()));
+            let method = endpoint.method;
+            item[method] = operation(endpoint, &error);
+        }
+
+        json!({
+            "openapi": "3.1.0",
+            "info": {
+                "title": "OpenSession API",
+                "version": "1",
+                "description": "HTTP API of opensession-server. Authenticate with `Authorization: Bearer <osk_ API key or access token>`.",
+            },
+            "servers": [{ "url": "/api" }],
+            "paths": paths,
+            "components": {
+                "schemas": generator.take_definitions(true),
+                "securitySchemes": {
+                    "bearerAuth": {
+                        "type": "http",
+                        "scheme": "bearer",
+                        "description": "API key (`osk_...`) or JWT access token",
+                    },
+                },
+            },
+        })
+    }
+}
+
+#[cfg(feature = "schema")]
+pub use generate::openapi_document;
+
+#[cfg(test)]
+mod tests {
+    use super::OPENAPI_JSON;
+
+    #[test]
+    fn committed_openapi_document_is_valid_json() {
+        let doc: serde_json::Value =
+            serde_json::from_str(OPENAPI_JSON).expect("openapi document should parse");
+        assert_eq!(doc["openapi"], "3.1.0");
+        assert!(doc["paths"]["/sessions"]["get"].is_object());
+        assert!(doc["components"]["schemas"]["SessionSummary"].is_object());
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn openapi_document_matches_api_types() {
+        let generated = format!(
+            "{}\n",
+            serde_json::to_string_pretty(&super::openapi_document())
+                .expect("openapi document should serialize")
+        );
+        if generated != OPENAPI_JSON {
+            let path =
+                std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("openapi/openapi.json");
+            std::fs::write(&path, &generated)
+                .unwrap_or_else(|e| panic!("Failed to write {}: {}", path.display(), e));
+            panic!(
+                "{} was stale and has been regenerated; re-run the tests and commit it",
+                path.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+/// Longest playbook name accepted by `PUT /api/playbooks/:name`.
+pub const MAX_PLAYBOOK_NAME_CHARS: usize = 64;
+
+/// Largest playbook body accepted by `PUT /api/playbooks/:name`.
+pub const MAX_PLAYBOOK_BODY_BYTES: usize = 64 * 1024;
+
+/// What a playbook body is meant for.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum PlaybookKind {
+    /// A starter prompt for a new agent session.
+    #[default]
+    Prompt,
+    /// A note template for handing work to someone else.
+    HandoffTemplate,
+}
+
+impl PlaybookKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Prompt => "prompt",
+            Self::HandoffTemplate => "handoff_template",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "prompt" => Some(Self::Prompt),
+            "handoff_template" => Some(Self::HandoffTemplate),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PlaybookKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A reusable prompt or handoff template shared by everyone on a server.
+///
+/// The body may contain `{{placeholder}}` variables filled in when applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct Playbook {
+    pub name: String,
+    pub kind: PlaybookKind,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub body: String,
+    pub author_nickname: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Request body for `PUT /api/playbooks/:name` — create or replace a playbook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct PutPlaybookRequest {
+    #[serde(default)]
+    pub kind: PlaybookKind,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub body: String,
+}
+
+/// Response for `GET /api/playbooks`, ordered by name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct PlaybookListResponse {
+    #[serde(default)]
+    pub playbooks: Vec<Playbook>,
+}
+
+/// Playbook names are short slugs: lowercase letters, digits, `-` and `_`,
+/// starting with a letter or digit.
+pub fn is_valid_playbook_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && name.chars().count() <= MAX_PLAYBOOK_NAME_CHARS
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_valid_playbook_name;
+
+    #[test]
+    fn playbook_names_are_slugs() {
+        assert!(is_valid_playbook_name("refactor-module"));
+        assert!(is_valid_playbook_name("bug_triage2"));
+        assert!(!is_valid_playbook_name(""));
+        assert!(!is_valid_playbook_name("-leading"));
+        assert!(!is_valid_playbook_name("Upper"));
+        assert!(!is_valid_playbook_name("../etc"));
+        assert!(!is_valid_playbook_name(&"a".repeat(65)));
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ApiError, SessionDetail, SessionListQuery, SessionListResponse, SessionRepoListResponse,
+};
+
+/// Most operations accepted by one `POST /api/query`.
+pub const MAX_QUERY_OPERATIONS: usize = 20;
+
+/// One read in a `POST /api/query` batch, tagged by `op`.
+///
+/// Each operation mirrors an existing REST read, so a view can fetch
+/// everything it needs in one round trip without a bespoke endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum QueryOperation {
+    /// Same filters as `GET /api/sessions`.
+    Sessions(Box<SessionListQuery>),
+    /// Same as `GET /api/sessions/{id}`.
+    Session { id: String },
+    /// Same as `GET /api/sessions/repos`.
+    SessionRepos,
+}
+
+/// Request body for `POST /api/query`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct QueryRequest {
+    #[serde(default)]
+    pub operations: Vec<QueryOperation>,
+}
+
+/// Result of one [`QueryOperation`], tagged with the same `op` (or `error`).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum QueryResult {
+    Sessions(Box<SessionListResponse>),
+    Session(Box<SessionDetail>),
+    SessionRepos(SessionRepoListResponse),
+    /// The operation failed; other operations in the batch are unaffected.
+    Error(ApiError),
+}
+
+/// Response for `POST /api/query`; `results[i]` answers `operations[i]`.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct QueryResponse {
+    #[serde(default)]
+    pub results: Vec<QueryResult>,
+}
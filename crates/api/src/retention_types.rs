@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+/// What happens to a session once it is older than its retention period.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum RetentionAction {
+    /// Remove the session, its body and its attachments.
+    #[default]
+    Delete,
+    /// Keep the session's stats but replace titles, paths and content with
+    /// placeholders.
+    Anonymize,
+}
+
+impl RetentionAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Anonymize => "anonymize",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "delete" => Some(Self::Delete),
+            "anonymize" => Some(Self::Anonymize),
+            _ => None,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// How long a team keeps uploaded sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct RetentionPolicy {
+    /// Days after upload a session is kept; `0` keeps sessions forever.
+    pub retain_days: u32,
+    #[serde(default)]
+    pub action: RetentionAction,
+    /// Keep sessions the uploader or a teammate bookmarked (a
+    /// `bookmark:<session-id>` metadata entry) or bookmarked an event of.
+    #[serde(default = "default_true")]
+    pub exempt_bookmarked: bool,
+    /// Keep sessions that are part of a handoff chain or other link.
+    #[serde(default = "default_true")]
+    pub exempt_linked: bool,
+}
+
+impl RetentionPolicy {
+    pub fn keeps_forever(&self) -> bool {
+        self.retain_days == 0
+    }
+
+    /// Whether switching from `current` to this policy would remove sessions
+    /// `current` keeps: a shorter period, `delete` instead of `anonymize`, or
+    /// a dropped exemption.
+    pub fn is_stricter_than(&self, current: &RetentionPolicy) -> bool {
+        let days = |policy: &RetentionPolicy| match policy.retain_days {
+            0 => u32::MAX,
+            days => days,
+        };
+        days(self) < days(current)
+            || (self.action == RetentionAction::Delete
+                && current.action == RetentionAction::Anonymize)
+            || (current.exempt_bookmarked && !self.exempt_bookmarked)
+            || (current.exempt_linked && !self.exempt_linked)
+    }
+}
+
+/// Response for `GET`/`PUT /api/teams/:team_id/retention`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct TeamRetentionResponse {
+    pub team_id: String,
+    pub policy: RetentionPolicy,
+    /// The team has no policy of its own and follows the server default.
+    pub is_default: bool,
+}
+
+/// A session the next retention run would delete or anonymize.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct RetentionPreviewItem {
+    pub session_id: String,
+    pub title: Option<String>,
+    pub uploaded_at: String,
+    pub action: RetentionAction,
+}
+
+/// Response for `GET /api/teams/:team_id/retention/preview` — a dry run over
+/// sessions uploaded by the team's members. A member in several teams is
+/// governed by the longest of their teams' policies, so `sessions` can differ
+/// from what this team's policy alone would select.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct RetentionPreviewResponse {
+    pub team_id: String,
+    pub policy: RetentionPolicy,
+    pub is_default: bool,
+    pub sessions: Vec<RetentionPreviewItem>,
+    /// Expired sessions kept because they are bookmarked or linked.
+    pub exempt: u32,
+}
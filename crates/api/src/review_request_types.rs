@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+/// Request body for `POST /api/sessions/{id}/review-requests` — ask a user to
+/// review an uploaded session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct CreateReviewRequest {
+    /// Reviewer nickname, with or without a leading `@`.
+    pub reviewer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// State of a review request. Only `pending` requests can be resolved.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum ReviewRequestStatus {
+    #[default]
+    Pending,
+    Approved,
+    ChangesRequested,
+}
+
+impl ReviewRequestStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::ChangesRequested => "changes_requested",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "approved" => Some(Self::Approved),
+            "changes_requested" => Some(Self::ChangesRequested),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ReviewRequestStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A request for a second pair of eyes on a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct ReviewRequestItem {
+    pub id: String,
+    pub session_id: String,
+    pub session_title: Option<String>,
+    pub requester_nickname: String,
+    pub reviewer_nickname: String,
+    pub note: Option<String>,
+    pub status: ReviewRequestStatus,
+    /// Reviewer's comment, set when the request is resolved.
+    pub comment: Option<String>,
+    pub resolved_at: Option<String>,
+    pub created_at: String,
+}
+
+/// Which side of a review request to list.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum ReviewRequestRole {
+    /// Requests where you are the reviewer.
+    #[default]
+    Assigned,
+    /// Requests you sent.
+    Requested,
+}
+
+/// Query for `GET /api/review-requests`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReviewRequestListQuery {
+    /// `assigned` (default) or `requested`.
+    #[serde(default)]
+    pub role: ReviewRequestRole,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ReviewRequestStatus>,
+    /// Page size, 1-200 (default 50).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// Request body for `POST /api/review-requests/{id}/resolve`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct ResolveReviewRequest {
+    /// `approved` or `changes_requested`.
+    pub status: ReviewRequestStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Response for `GET /api/review-requests`, newest first.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct ReviewRequestListResponse {
+    #[serde(default)]
+    pub items: Vec<ReviewRequestItem>,
+}
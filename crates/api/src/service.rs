@@ -55,6 +55,18 @@ pub fn generate_api_key() -> String {
     format!("osk_{}", uuid::Uuid::new_v4().simple())
 }
 
+/// Prefix of team-scoped, read-only guest tokens.
+pub const GUEST_TOKEN_PREFIX: &str = "osg_";
+
+/// Generate a new guest token with the `osg_` prefix.
+pub fn generate_guest_token() -> String {
+    format!("{GUEST_TOKEN_PREFIX}{}", uuid::Uuid::new_v4().simple())
+}
+
+pub fn is_guest_token(token: &str) -> bool {
+    token.starts_with(GUEST_TOKEN_PREFIX)
+}
+
 /// Hash an API key for persistent storage and lookup.
 pub fn hash_api_key(api_key: &str) -> String {
     crate::crypto::hash_token(api_key)
@@ -97,6 +109,11 @@ pub fn resolve_auth_token(
     if token.starts_with("osk_") {
         return Ok(AuthToken::ApiKey(token.to_string()));
     }
+    if is_guest_token(token) {
+        return Err(ServiceError::Unauthorized(
+            "guest tokens are read-only and can only list sessions".into(),
+        ));
+    }
 
     if jwt_secret.is_empty() {
         return Err(ServiceError::Unauthorized(
@@ -174,6 +191,178 @@ pub fn prepare_token_bundle(
     })
 }
 
+// ─── Body ETags ─────────────────────────────────────────────────────────────
+
+/// Strong ETag for a session body: the quoted SHA-256 of its bytes.
+pub fn body_etag(body: &[u8]) -> String {
+    format!("\"{}\"", body_sha256(body))
+}
+
+/// Lowercase hex SHA-256 of a session body, recorded at upload as its
+/// integrity hash.
+pub fn body_sha256(body: &[u8]) -> String {
+    use sha2::Digest;
+    hex::encode(sha2::Sha256::digest(body))
+}
+
+/// Bytes a server signs to attest that `body_sha256` is what it stored for
+/// `session_id` at upload time.
+pub fn body_signing_message(session_id: &str, body_sha256: &str) -> String {
+    format!("opensession-body-v1\n{session_id}\n{body_sha256}")
+}
+
+/// Whether an `If-None-Match` header value matches `etag`.
+///
+/// Accepts `*`, comma-separated lists, and weak (`W/`) validators, per the
+/// weak comparison RFC 9110 prescribes for `If-None-Match`.
+pub fn if_none_match_hits(if_none_match: &str, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+// ─── Content fingerprints ───────────────────────────────────────────────────
+
+/// Fingerprint of a session's underlying content, used to detect re-uploads.
+///
+/// Hashes the agent tool and each event's timestamp, type and content, so the
+/// same recording uploaded under another session id or with different
+/// metadata (title, tags, attributes) maps to the same value. Returns `None`
+/// for sessions without events, which carry nothing to deduplicate on.
+pub fn session_content_fingerprint(session: &opensession_core::Session) -> Option<String> {
+    use sha2::Digest;
+    if session.events.is_empty() {
+        return None;
+    }
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(session.agent.tool.as_bytes());
+    for event in &session.events {
+        hasher.update([0u8]);
+        hasher.update(event.timestamp.to_rfc3339().as_bytes());
+        hasher.update([0u8]);
+        hasher.update(serde_json::to_vec(&event.event_type).unwrap_or_default());
+        hasher.update([0u8]);
+        hasher.update(serde_json::to_vec(&event.content).unwrap_or_default());
+    }
+    Some(hex::encode(hasher.finalize()))
+}
+
+// ─── Sessions ───────────────────────────────────────────────────────────────
+
+/// `Content-Disposition` for full raw-body downloads.
+pub const RAW_BODY_CONTENT_DISPOSITION: &str = "attachment; filename=\"session.hail.jsonl\"";
+
+/// Gate for the session list and repo list: public unless the deployment
+/// disabled the public feed, in which case a signed-in caller is required.
+pub fn check_session_list_access(
+    public_feed_enabled: bool,
+    is_authenticated: bool,
+) -> Result<(), ServiceError> {
+    if public_feed_enabled || is_authenticated {
+        return Ok(());
+    }
+    Err(ServiceError::Unauthorized(
+        "public session feed is disabled; authentication required".into(),
+    ))
+}
+
+/// Reject empty or oversized batch uploads before any item is processed.
+pub fn validate_batch_upload_size(count: usize) -> Result<(), ServiceError> {
+    if count == 0 {
+        return Err(ServiceError::BadRequest(
+            "batch must contain at least one session".into(),
+        ));
+    }
+    if count > crate::MAX_BATCH_UPLOAD_SESSIONS {
+        return Err(ServiceError::BadRequest(format!(
+            "batch exceeds the limit of {} sessions",
+            crate::MAX_BATCH_UPLOAD_SESSIONS
+        )));
+    }
+    Ok(())
+}
+
+/// Normalize one uploaded session before any backend-specific policy runs:
+/// migrate it to the current HAIL version and require a session id.
+pub fn prepare_upload_session(
+    session: &opensession_core::Session,
+) -> Result<opensession_core::Session, ServiceError> {
+    let mut session = session.clone();
+    opensession_core::migrate::migrate_session(&mut session)
+        .map_err(|err| ServiceError::BadRequest(err.to_string()))?;
+    if session.session_id.trim().is_empty() {
+        return Err(ServiceError::BadRequest("session_id is required".into()));
+    }
+    Ok(session)
+}
+
+/// The form a session is stored in, whatever the client kept: metrics-only
+/// sessions are reduced again so nothing but stats is written.
+pub fn storage_form(
+    session: opensession_core::Session,
+) -> (opensession_core::Session, crate::SessionKind) {
+    use opensession_core::session::{is_metrics_only_session, metrics_only_session};
+    if is_metrics_only_session(&session) {
+        (
+            metrics_only_session(&session),
+            crate::SessionKind::MetricsOnly,
+        )
+    } else {
+        (session, crate::SessionKind::Full)
+    }
+}
+
+/// Reject empty or oversized `POST /api/query` batches before any operation runs.
+pub fn validate_query_size(count: usize) -> Result<(), ServiceError> {
+    if count == 0 {
+        return Err(ServiceError::BadRequest(
+            "operations must not be empty".into(),
+        ));
+    }
+    if count > crate::MAX_QUERY_OPERATIONS {
+        return Err(ServiceError::BadRequest(format!(
+            "too many operations (max {} per request)",
+            crate::MAX_QUERY_OPERATIONS
+        )));
+    }
+    Ok(())
+}
+
+/// Where a session's raw HAIL body lives.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RawBodySource {
+    /// Key in the deployment's own body storage (disk or R2).
+    Stored(String),
+    /// External `body_url` the client should be redirected to.
+    Redirect(String),
+}
+
+/// Pick the raw-body source from a session's storage columns; a non-empty
+/// `body_url` wins over the storage key.
+pub fn resolve_raw_body_source(
+    body_storage_key: &str,
+    body_url: Option<&str>,
+) -> Result<RawBodySource, ServiceError> {
+    if let Some(url) = body_url.map(str::trim).filter(|url| !url.is_empty()) {
+        return Ok(RawBodySource::Redirect(url.to_string()));
+    }
+    let key = body_storage_key.trim();
+    if key.is_empty() {
+        return Err(ServiceError::NotFound("session body not found".into()));
+    }
+    Ok(RawBodySource::Stored(key.to_string()))
+}
+
+/// JSONL for the events after index `since_event` of a stored raw body.
+pub fn raw_body_delta(body: &[u8], since_event: usize) -> Result<String, ServiceError> {
+    std::str::from_utf8(body)
+        .ok()
+        .and_then(|text| opensession_core::jsonl::event_delta(text, since_event).ok())
+        .ok_or_else(|| ServiceError::Internal("failed to slice session body".into()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,6 +444,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_guest_tokens_never_authenticate_as_users() {
+        let token = generate_guest_token();
+        assert!(is_guest_token(&token));
+        assert!(!is_guest_token(&generate_api_key()));
+        let err = match resolve_auth_token(&token, "jwt-secret", 0) {
+            Ok(_) => panic!("guest token must not resolve to a user"),
+            Err(err) => err,
+        };
+        assert!(err.message().contains("read-only"));
+    }
+
     #[test]
     fn test_resolve_auth_token_error_paths() {
         let missing_secret = match resolve_auth_token("definitely-not-an-api-key", "", 0) {
@@ -295,4 +496,141 @@ mod tests {
         };
         assert!(matches!(err, ServiceError::Internal(_)));
     }
+
+    #[test]
+    fn body_etag_is_quoted_and_content_addressed() {
+        let etag = body_etag(b"hello");
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(etag, body_etag(b"hello"));
+        assert_ne!(etag, body_etag(b"hello!"));
+    }
+
+    #[test]
+    fn if_none_match_handles_lists_wildcards_and_weak_tags() {
+        let etag = body_etag(b"body");
+        assert!(if_none_match_hits(&etag, &etag));
+        assert!(if_none_match_hits(&format!("\"other\", W/{etag}"), &etag));
+        assert!(if_none_match_hits("*", &etag));
+        assert!(!if_none_match_hits("\"other\"", &etag));
+        assert!(!if_none_match_hits("", &etag));
+    }
+
+    fn fingerprint_event(
+        event_type: opensession_core::EventType,
+        text: &str,
+    ) -> opensession_core::Event {
+        opensession_core::Event {
+            event_id: format!("event-{text}"),
+            timestamp: chrono::DateTime::from_timestamp(1_700_000_000, 0).expect("valid timestamp"),
+            event_type,
+            task_id: None,
+            content: opensession_core::Content::text(text),
+            duration_ms: None,
+            attributes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn content_fingerprint_ignores_ids_and_metadata() {
+        let agent = opensession_core::Agent {
+            provider: "anthropic".to_string(),
+            model: "claude".to_string(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+        };
+        let mut original = opensession_core::Session::new("session-a".to_string(), agent);
+        original.events.push(fingerprint_event(
+            opensession_core::EventType::UserMessage,
+            "fix the flaky test",
+        ));
+        let mut reupload = original.clone();
+        reupload.session_id = "session-b".to_string();
+        reupload.context.title = Some("renamed".to_string());
+        reupload.events[0].event_id = "other-event-id".to_string();
+
+        let fingerprint = session_content_fingerprint(&original).expect("has events");
+        assert_eq!(
+            Some(fingerprint.clone()),
+            session_content_fingerprint(&reupload)
+        );
+
+        reupload.events.push(fingerprint_event(
+            opensession_core::EventType::AgentMessage,
+            "done",
+        ));
+        assert_ne!(Some(fingerprint), session_content_fingerprint(&reupload));
+
+        original.events.clear();
+        assert_eq!(session_content_fingerprint(&original), None);
+    }
+
+    #[test]
+    fn session_list_access_follows_public_feed_flag() {
+        assert!(check_session_list_access(true, false).is_ok());
+        assert!(check_session_list_access(false, true).is_ok());
+        assert!(matches!(
+            check_session_list_access(false, false),
+            Err(ServiceError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn batch_upload_size_must_be_within_limit() {
+        assert!(validate_batch_upload_size(0).is_err());
+        assert!(validate_batch_upload_size(1).is_ok());
+        assert!(validate_batch_upload_size(crate::MAX_BATCH_UPLOAD_SESSIONS).is_ok());
+        assert!(validate_batch_upload_size(crate::MAX_BATCH_UPLOAD_SESSIONS + 1).is_err());
+    }
+
+    #[test]
+    fn upload_sessions_need_an_id_and_keep_their_kind() {
+        let agent = opensession_core::Agent {
+            provider: "openai".to_string(),
+            model: "gpt-5".to_string(),
+            tool: "codex".to_string(),
+            tool_version: None,
+        };
+        let blank = opensession_core::Session::new("  ".to_string(), agent.clone());
+        assert!(prepare_upload_session(&blank).is_err());
+
+        let session = opensession_core::Session::new("s1".to_string(), agent);
+        let prepared = prepare_upload_session(&session).expect("prepare");
+        let (stored, kind) = storage_form(prepared.clone());
+        assert_eq!(kind, crate::SessionKind::Full);
+        assert_eq!(stored.session_id, "s1");
+
+        let (_, kind) = storage_form(opensession_core::session::metrics_only_session(&prepared));
+        assert_eq!(kind, crate::SessionKind::MetricsOnly);
+    }
+
+    #[test]
+    fn query_size_must_be_within_limit() {
+        assert!(validate_query_size(0).is_err());
+        assert!(validate_query_size(crate::MAX_QUERY_OPERATIONS).is_ok());
+        assert!(validate_query_size(crate::MAX_QUERY_OPERATIONS + 1).is_err());
+    }
+
+    #[test]
+    fn raw_body_source_prefers_body_url_and_rejects_missing_storage() {
+        assert_eq!(
+            resolve_raw_body_source("", Some("https://example.com/a")).unwrap(),
+            RawBodySource::Redirect("https://example.com/a".to_string())
+        );
+        assert_eq!(
+            resolve_raw_body_source("abc.hail.jsonl", Some("   ")).unwrap(),
+            RawBodySource::Stored("abc.hail.jsonl".to_string())
+        );
+        assert!(matches!(
+            resolve_raw_body_source(" ", None),
+            Err(ServiceError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn raw_body_delta_rejects_non_utf8_bodies() {
+        assert!(matches!(
+            raw_body_delta(&[0xff, 0xfe], 0),
+            Err(ServiceError::Internal(_))
+        ));
+    }
 }
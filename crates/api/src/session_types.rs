@@ -1,9 +1,12 @@
 use crate::shared_types::{LinkType, SortOrder, TimeRange};
+use crate::team_types::QuotaNotice;
 use opensession_core::trace::{Agent, Event, Session, SessionContext};
+use opensession_core::validate::{ValidationError, validate_jsonl};
 use serde::{Deserialize, Serialize};
 
 /// Request body for `POST /api/sessions` — upload a recorded session.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct UploadRequest {
     pub session: Session,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -24,11 +27,33 @@ pub struct UploadRequest {
     pub pr_url: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub score_plugin: Option<String>,
+    /// Store the session even when its content matches an existing upload.
+    /// The new session is linked to the earlier one instead of being rejected.
+    #[serde(default)]
+    pub force: bool,
+    /// Base64 Ed25519 signature over [`UploadRequest::signing_message`] by the
+    /// uploading device's registered key; verified uploads are marked
+    /// `verified_origin`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl UploadRequest {
+    /// Bytes a device signs for this upload: a version prefix followed by the
+    /// session as JSON with object keys sorted, so client and server agree
+    /// regardless of map iteration order.
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = b"opensession-upload-v1\n".to_vec();
+        let canonical = serde_json::to_value(&self.session).unwrap_or_default();
+        message.extend(serde_json::to_vec(&canonical).unwrap_or_default());
+        message
+    }
 }
 
 /// Returned on successful session upload — contains the new session ID and URL.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct UploadResponse {
     pub id: String,
@@ -37,11 +62,108 @@ pub struct UploadResponse {
     pub session_score: i64,
     #[serde(default = "default_score_plugin")]
     pub score_plugin: String,
+    /// Team soft quotas this upload reached; the session was still stored.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quota_warnings: Vec<QuotaNotice>,
+}
+
+/// Maximum number of sessions accepted by a single `POST /api/sessions/batch`.
+pub const MAX_BATCH_UPLOAD_SESSIONS: usize = 50;
+
+/// Request body for `POST /api/sessions/batch` — upload several sessions at once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BatchUploadRequest {
+    pub sessions: Vec<UploadRequest>,
+}
+
+/// Outcome of one item in a batch upload. Exactly one of `upload`/`error` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct BatchUploadItemResult {
+    pub session_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload: Option<UploadResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Set when the item was rejected because its content matches this
+    /// already-uploaded session. Retry with `force` to store it anyway.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<String>,
+    /// Events that matched the server's or the uploader's teams' upload
+    /// exclude patterns, when the item was rejected by a privacy policy.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policy_violations: Vec<SessionValidationError>,
+    /// Set when the item was rejected because it would push one of the
+    /// uploader's teams past a hard storage quota.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_exceeded: Option<QuotaNotice>,
+}
+
+/// Returned by `POST /api/sessions/batch` — one result per submitted session, in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct BatchUploadResponse {
+    pub results: Vec<BatchUploadItemResult>,
+}
+
+impl BatchUploadResponse {
+    pub fn uploaded_count(&self) -> usize {
+        self.results.iter().filter(|r| r.upload.is_some()).count()
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.results.iter().filter(|r| r.error.is_some()).count()
+    }
+}
+
+/// Request body for `POST /api/admin/sessions/recompute` — rebuild derived
+/// columns for one batch of sessions, in id order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RecomputeSessionsRequest {
+    /// Resume after this session id (the previous batch's `next_after`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Sessions per batch, 1-500 (default 100).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// A session whose stored body could not be re-derived.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct RecomputeSessionError {
+    pub session_id: String,
+    pub error: String,
+}
+
+/// Returned by `POST /api/admin/sessions/recompute`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct RecomputeSessionsResponse {
+    pub scanned: u32,
+    /// Sessions whose stored columns differed from the recomputed values.
+    pub updated: u32,
+    #[serde(default)]
+    pub errors: Vec<RecomputeSessionError>,
+    /// Pass as `after` to process the next batch; absent once every session was scanned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_after: Option<String>,
 }
 
 /// Flat session summary returned by list/detail endpoints.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct SessionSummary {
     pub id: String,
@@ -87,38 +209,112 @@ pub struct SessionSummary {
     pub session_score: i64,
     #[serde(default = "default_score_plugin")]
     pub score_plugin: String,
+    /// Machine that uploaded the session (see `GET /api/auth/devices`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+    /// The upload was signed by `device_id`'s registered signing key.
+    #[serde(default)]
+    pub verified_origin: bool,
+    #[serde(default)]
+    pub kind: SessionKind,
+}
+
+/// What an uploaded session carries.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum SessionKind {
+    /// The full transcript.
+    #[default]
+    Full,
+    /// Aggregate stats only (tokens, duration, tool mix): no events, title or
+    /// paths. Uploaded for repositories configured as metrics-only.
+    MetricsOnly,
+}
+
+impl SessionKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::MetricsOnly => "metrics_only",
+        }
+    }
+
+    /// Unknown values read as `Full`.
+    pub fn parse(raw: &str) -> Self {
+        match raw {
+            "metrics_only" => Self::MetricsOnly,
+            _ => Self::Full,
+        }
+    }
 }
 
 /// Paginated session listing returned by `GET /api/sessions`.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct SessionListResponse {
     pub sessions: Vec<SessionSummary>,
     pub total: i64,
     pub page: u32,
     pub per_page: u32,
+    /// Opaque cursor for the next page; absent on the last page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Query parameters for `GET /api/sessions` — pagination, filtering, sorting.
-#[derive(Debug, Deserialize)]
+///
+/// Pagination is keyset-based: pass the previous response's `next_cursor` as
+/// `cursor`. `page`/`per_page` are deprecated and only honored when no cursor
+/// is given.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct SessionListQuery {
     #[serde(default = "default_page")]
     pub page: u32,
     #[serde(default = "default_per_page")]
     pub per_page: u32,
+    /// Opaque cursor from a previous `SessionListResponse::next_cursor`.
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Page size; takes precedence over `per_page`.
+    #[serde(default)]
+    pub limit: Option<u32>,
     pub search: Option<String>,
     pub tool: Option<String>,
     pub git_repo_name: Option<String>,
     pub sort: Option<SortOrder>,
     pub time_range: Option<TimeRange>,
+    /// Filter by model (glob-like, `*` matches any run of characters).
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Only sessions created at or after this ISO8601 timestamp.
+    #[serde(default)]
+    pub since: Option<String>,
+    /// Only sessions created before this ISO8601 timestamp.
+    #[serde(default)]
+    pub before: Option<String>,
+    /// Only sessions that modified this file path.
+    #[serde(default)]
+    pub touches: Option<String>,
+    /// Only sessions with (`true`) or without (`false`) errors.
+    #[serde(default)]
+    pub has_errors: Option<bool>,
+    /// Filter by git commit (prefix match, so short SHAs work).
+    #[serde(default)]
+    pub git_commit: Option<String>,
 }
 
 /// Desktop session list query payload passed through Tauri invoke.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct DesktopSessionListQuery {
     pub page: Option<String>,
@@ -134,6 +330,7 @@ pub struct DesktopSessionListQuery {
 /// Repo list response used by server/worker/desktop adapters.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct SessionRepoListResponse {
     pub repos: Vec<String>,
@@ -142,6 +339,7 @@ pub struct SessionRepoListResponse {
 /// Single session detail returned by `GET /api/sessions/:id`.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct SessionDetail {
     #[serde(flatten)]
@@ -149,11 +347,75 @@ pub struct SessionDetail {
     pub summary: SessionSummary,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub linked_sessions: Vec<SessionLink>,
+    /// Hex SHA-256 of the body as stored at upload; the `/raw` download must
+    /// hash to the same value. Absent for bodies hosted elsewhere.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_sha256: Option<String>,
+    /// Base64 Ed25519 signature by the server's body signing key over
+    /// `opensession-body-v1\n{id}\n{body_sha256}`, when one is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_signature: Option<String>,
+    /// Whether the body is in hot storage or archived to the cold store.
+    /// Cold bodies are brought back on the next `/raw` download.
+    #[serde(default)]
+    pub storage_class: StorageClass,
 }
 
+/// Storage tier of a session body on the server.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum StorageClass {
+    #[default]
+    Hot,
+    /// Moved to the cold store after going unread for the configured number of days.
+    Cold,
+}
+
+impl StorageClass {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hot => "hot",
+            Self::Cold => "cold",
+        }
+    }
+
+    /// Unknown values read as `Hot`, the tier every body starts in.
+    pub fn parse(raw: &str) -> Self {
+        match raw {
+            "cold" => Self::Cold,
+            _ => Self::Hot,
+        }
+    }
+}
+
+/// Query parameters for `GET /api/sessions/:id/raw`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct SessionRawQuery {
+    /// Return only events after the first `since_event` (plus the stats line)
+    /// instead of the full body. The response then carries
+    /// [`SESSION_DELTA_HEADER`].
+    #[serde(default)]
+    pub since_event: Option<u32>,
+}
+
+/// Response header marking a `since_event` delta body; its value echoes the
+/// number of events skipped.
+pub const SESSION_DELTA_HEADER: &str = "x-opensession-since-event";
+
+/// Response header on full `/raw` bodies carrying the SHA-256 recorded at
+/// upload, so clients can verify the download without a second request.
+pub const SESSION_BODY_SHA256_HEADER: &str = "x-opensession-body-sha256";
+
 /// A link between two sessions (e.g., handoff chain).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct SessionLink {
     pub session_id: String,
@@ -162,15 +424,18 @@ pub struct SessionLink {
     pub created_at: String,
 }
 
-/// Request body for `POST /api/sessions/:id/events` — append live events.
-#[derive(Debug, Deserialize)]
+/// Request body for `POST /api/sessions/:id/live` (start sharing) and
+/// `POST /api/sessions/:id/events` — append live events.
+#[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct StreamEventsRequest {
     #[cfg_attr(feature = "ts", ts(type = "any"))]
     pub agent: Option<Agent>,
     #[cfg_attr(feature = "ts", ts(type = "any"))]
     pub context: Option<SessionContext>,
+    #[serde(default)]
     #[cfg_attr(feature = "ts", ts(type = "any[]"))]
     pub events: Vec<Event>,
 }
@@ -178,14 +443,64 @@ pub struct StreamEventsRequest {
 /// Returned by `POST /api/sessions/:id/events` — number of events accepted.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct StreamEventsResponse {
     pub accepted: usize,
 }
 
+/// One problem reported by `POST /api/validate`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct SessionValidationError {
+    /// Location of the problem, e.g. `agent.tool`, `events[3]` or `line 2`.
+    pub path: String,
+    /// Stable machine-readable code, e.g. `missing_field`.
+    pub code: String,
+    pub message: String,
+}
+
+impl From<&ValidationError> for SessionValidationError {
+    fn from(err: &ValidationError) -> Self {
+        Self {
+            path: err.path(),
+            code: err.code().to_string(),
+            message: err.to_string(),
+        }
+    }
+}
+
+/// Returned by `POST /api/validate` — strict HAIL validation result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct ValidateSessionResponse {
+    pub valid: bool,
+    #[serde(default)]
+    pub errors: Vec<SessionValidationError>,
+}
+
+impl ValidateSessionResponse {
+    /// Parse and validate a HAIL JSONL body.
+    pub fn from_jsonl(body: &str) -> Self {
+        let errors: Vec<SessionValidationError> = match validate_jsonl(body) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors.iter().map(SessionValidationError::from).collect(),
+        };
+        Self {
+            valid: errors.is_empty(),
+            errors,
+        }
+    }
+}
+
 /// Returned by `GET /api/health` — server liveness check.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct HealthResponse {
     pub status: String,
@@ -195,12 +510,16 @@ pub struct HealthResponse {
 /// Returned by `GET /api/capabilities` — runtime feature availability.
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub struct CapabilitiesResponse {
     pub auth_enabled: bool,
     pub parse_preview_enabled: bool,
     pub register_targets: Vec<String>,
     pub share_modes: Vec<String>,
+    /// Base64 Ed25519 public key that verifies `SessionDetail.body_signature`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_signing_key: Option<String>,
 }
 
 pub const DEFAULT_REGISTER_TARGETS: &[&str] = &["local", "git"];
@@ -220,6 +539,7 @@ impl CapabilitiesResponse {
                 .iter()
                 .map(|mode| (*mode).to_string())
                 .collect(),
+            body_signing_key: None,
         }
     }
 }
@@ -238,8 +558,46 @@ impl SessionListQuery {
                 .git_repo_name
                 .as_deref()
                 .is_none_or(|repo| repo.trim().is_empty())
+            && [
+                &self.model,
+                &self.since,
+                &self.before,
+                &self.touches,
+                &self.git_commit,
+            ]
+            .iter()
+            .all(|value| value.as_deref().is_none_or(|v| v.trim().is_empty()))
+            && self.cursor.is_none()
             && self.page <= 10
-            && self.per_page <= 50
+            && self.page_size() <= 50
+    }
+
+    /// Effective page size, preferring `limit` over the deprecated `per_page`.
+    pub fn page_size(&self) -> u32 {
+        self.limit.unwrap_or(self.per_page).clamp(1, 100)
+    }
+}
+
+impl Default for SessionListQuery {
+    /// First page with the server's default page size and no filters.
+    fn default() -> Self {
+        Self {
+            page: default_page(),
+            per_page: default_per_page(),
+            cursor: None,
+            limit: None,
+            search: None,
+            tool: None,
+            git_repo_name: None,
+            sort: None,
+            time_range: None,
+            model: None,
+            since: None,
+            before: None,
+            touches: None,
+            has_errors: None,
+            git_commit: None,
+        }
     }
 }
 
@@ -267,11 +625,19 @@ mod tests {
         SessionListQuery {
             page: 1,
             per_page: 20,
+            cursor: None,
+            limit: None,
             search: None,
             tool: None,
             git_repo_name: None,
             sort: None,
             time_range: None,
+            model: None,
+            since: None,
+            before: None,
+            touches: None,
+            has_errors: None,
+            git_commit: None,
         }
     }
 
@@ -298,6 +664,10 @@ mod tests {
         q.git_repo_name = Some("org/repo".into());
         assert!(!q.is_public_feed_cacheable(false, false));
 
+        let mut q = base_query();
+        q.touches = Some("src/lib.rs".into());
+        assert!(!q.is_public_feed_cacheable(false, false));
+
         let mut q = base_query();
         q.page = 11;
         assert!(!q.is_public_feed_cacheable(false, false));
@@ -305,5 +675,45 @@ mod tests {
         let mut q = base_query();
         q.per_page = 100;
         assert!(!q.is_public_feed_cacheable(false, false));
+
+        let mut q = base_query();
+        q.cursor = Some("opaque".into());
+        assert!(!q.is_public_feed_cacheable(false, false));
+    }
+
+    #[test]
+    fn validate_response_reports_structured_errors() {
+        let result = ValidateSessionResponse::from_jsonl("not json");
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].code, "unreadable");
+        assert_eq!(result.errors[0].path, "line 1");
+    }
+
+    #[test]
+    fn upload_signing_message_survives_a_json_round_trip() {
+        let mut session = Session::new(
+            "s1".to_string(),
+            Agent {
+                provider: "anthropic".to_string(),
+                model: "claude".to_string(),
+                tool: "claude-code".to_string(),
+                tool_version: None,
+            },
+        );
+        for key in ["zeta", "alpha", "mid", "beta", "omega"] {
+            session
+                .context
+                .attributes
+                .insert(key.to_string(), serde_json::json!({ "b": 1, "a": [key] }));
+        }
+        let req: UploadRequest = serde_json::from_value(serde_json::json!({ "session": session }))
+            .expect("upload request");
+        let message = req.signing_message();
+        assert!(message.starts_with(b"opensession-upload-v1\n"));
+
+        let received: UploadRequest =
+            serde_json::from_slice(&serde_json::to_vec(&req).expect("encode")).expect("decode");
+        assert_eq!(received.signing_message(), message);
     }
 }
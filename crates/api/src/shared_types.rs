@@ -4,12 +4,15 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub enum SortOrder {
     #[default]
     Recent,
     Popular,
     Longest,
+    /// Highest session score first.
+    Best,
 }
 
 impl SortOrder {
@@ -18,6 +21,7 @@ impl SortOrder {
             Self::Recent => "recent",
             Self::Popular => "popular",
             Self::Longest => "longest",
+            Self::Best => "best",
         }
     }
 }
@@ -31,6 +35,7 @@ impl std::fmt::Display for SortOrder {
 /// Time range filter for queries.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub enum TimeRange {
     #[serde(rename = "24h")]
@@ -65,6 +70,7 @@ impl std::fmt::Display for TimeRange {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "ts", ts(export))]
 pub enum LinkType {
     Handoff,
@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+
+/// What a team storage quota limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum QuotaResource {
+    /// Bytes of stored session bodies.
+    BodyBytes,
+    /// Number of uploaded sessions.
+    Sessions,
+}
+
+impl QuotaResource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BodyBytes => "body_bytes",
+            Self::Sessions => "sessions",
+        }
+    }
+}
+
+/// Soft and hard limit for one resource; `None` is unlimited.
+///
+/// Crossing the soft limit only warns; an upload that would cross the hard
+/// limit is rejected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct QuotaLimit {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hard: Option<u64>,
+}
+
+impl QuotaLimit {
+    pub fn is_unlimited(&self) -> bool {
+        self.soft.is_none() && self.hard.is_none()
+    }
+}
+
+/// A team at or over one of its quota limits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct QuotaNotice {
+    pub team_id: String,
+    pub resource: QuotaResource,
+    /// Usage including the upload being checked, when there is one.
+    pub used: u64,
+    pub limit: u64,
+}
+
+impl std::fmt::Display for QuotaNotice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "team `{}` at {} of {} {}",
+            self.team_id,
+            self.used,
+            self.limit,
+            match self.resource {
+                QuotaResource::BodyBytes => "body bytes",
+                QuotaResource::Sessions => "sessions",
+            }
+        )
+    }
+}
+
+/// Response for `GET /api/teams/:team_id/usage` — storage used by sessions
+/// that the team's members uploaded, and the server's per-team quotas.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct TeamUsageResponse {
+    pub team_id: String,
+    pub session_count: u64,
+    pub body_bytes: u64,
+    #[serde(default)]
+    pub sessions_quota: QuotaLimit,
+    #[serde(default)]
+    pub body_bytes_quota: QuotaLimit,
+    /// Limits the team has reached, soft or hard.
+    #[serde(default)]
+    pub warnings: Vec<QuotaNotice>,
+}
+
+/// What the server does with an upload that references an excluded pattern.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub enum UploadPolicyAction {
+    /// Refuse the session and list the offending events.
+    #[default]
+    Reject,
+    /// Drop the offending events and their tool results, then store the rest.
+    Scrub,
+}
+
+impl UploadPolicyAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Reject => "reject",
+            Self::Scrub => "scrub",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "reject" => Some(Self::Reject),
+            "scrub" => Some(Self::Scrub),
+            _ => None,
+        }
+    }
+}
+
+/// Globs a team excludes from uploads by its members, on top of the
+/// server-wide upload policy.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct TeamUploadPolicy {
+    /// Matched against file paths and shell command words, like
+    /// `OPENSESSION_UPLOAD_EXCLUDE_PATTERNS`.
+    pub exclude_patterns: Vec<String>,
+    #[serde(default)]
+    pub action: UploadPolicyAction,
+}
+
+impl TeamUploadPolicy {
+    /// Whether switching from `current` to this policy would let through
+    /// uploads `current` stops: a dropped pattern, or `scrub` instead of
+    /// `reject`.
+    pub fn is_looser_than(&self, current: &TeamUploadPolicy) -> bool {
+        current
+            .exclude_patterns
+            .iter()
+            .any(|pattern| !self.exclude_patterns.contains(pattern))
+            || (self.action == UploadPolicyAction::Scrub
+                && current.action == UploadPolicyAction::Reject
+                && !current.exclude_patterns.is_empty())
+    }
+}
+
+/// Response for `GET`/`PUT`/`DELETE /api/teams/:team_id/upload-policy`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct TeamUploadPolicyResponse {
+    pub team_id: String,
+    /// Empty when the team has no policy of its own.
+    pub policy: TeamUploadPolicy,
+}
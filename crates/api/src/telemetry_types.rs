@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+/// Request body for `POST /api/telemetry` — anonymous usage counters a daemon
+/// collected over one reporting period. Carries no session content, user or
+/// device identity.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct TelemetryReport {
+    pub daemon_version: String,
+    /// RFC 3339 start of the period the counters cover.
+    pub period_start: String,
+    /// RFC 3339 end of the period the counters cover.
+    pub period_end: String,
+    pub sessions_indexed: u64,
+    pub parse_failures: u64,
+    pub upload_errors: u64,
+}
+
+/// Query for `GET /api/admin/telemetry`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TelemetrySummaryQuery {
+    /// Days to look back, 1-90 (default 7).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days: Option<u32>,
+}
+
+/// Counters summed over every report from one daemon version.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct TelemetryTotals {
+    pub daemon_version: String,
+    pub reports: u64,
+    pub sessions_indexed: u64,
+    pub parse_failures: u64,
+    pub upload_errors: u64,
+}
+
+/// Response for `GET /api/admin/telemetry`: fleet-wide totals for the
+/// window, plus a breakdown by daemon version (most reports first).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "ts", ts(export))]
+pub struct TelemetrySummaryResponse {
+    pub days: u32,
+    pub reports: u64,
+    pub sessions_indexed: u64,
+    pub parse_failures: u64,
+    pub upload_errors: u64,
+    #[serde(default)]
+    pub versions: Vec<TelemetryTotals>,
+}
+
+impl TelemetrySummaryResponse {
+    /// Fleet totals for `days`, summed from per-version rows.
+    pub fn from_versions(days: u32, versions: Vec<TelemetryTotals>) -> Self {
+        let mut summary = Self {
+            days,
+            ..Self::default()
+        };
+        for version in &versions {
+            summary.reports += version.reports;
+            summary.sessions_indexed += version.sessions_indexed;
+            summary.parse_failures += version.parse_failures;
+            summary.upload_errors += version.upload_errors;
+        }
+        summary.versions = versions;
+        summary
+    }
+}
@@ -0,0 +1,179 @@
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+
+use opensession_api_client::device::DeviceSigningKey;
+use opensession_api_client::opensession_api::{
+    QuotaLimit, RegisterDeviceKeyRequest, TeamUsageResponse,
+};
+
+use crate::user_guidance::guided_error;
+
+#[derive(Debug, Clone, Args)]
+pub struct AccountArgs {
+    #[command(subcommand)]
+    pub action: AccountAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum AccountAction {
+    /// Download everything the server stores for your account (JSON lines).
+    Export {
+        /// Output file.
+        #[arg(long, default_value = "opensession-export.jsonl")]
+        out: PathBuf,
+    },
+    /// Create this machine's upload signing key and register it, so its
+    /// uploads show as verified origin.
+    SignUploads,
+    /// Show storage used by your teams and how close they are to quota.
+    Usage {
+        /// Team to report (default: every team you belong to).
+        #[arg(long)]
+        team: Option<String>,
+    },
+    /// Permanently delete your server account and every session you uploaded.
+    Delete {
+        /// Skip typing the account nickname to confirm.
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+pub async fn run(args: AccountArgs) -> Result<()> {
+    let runtime = crate::runtime_settings::load_runtime_config()?;
+    let client = crate::handoff_v1::server_client(&runtime)?;
+    match args.action {
+        AccountAction::Export { out } => {
+            let mut stream = client.export_account().await.context("export account")?;
+            let mut file =
+                std::fs::File::create(&out).with_context(|| format!("create {}", out.display()))?;
+            let mut written = 0usize;
+            let mut records = 0usize;
+            while let Some(chunk) = stream.next_chunk().await.context("download export")? {
+                records += chunk.iter().filter(|byte| **byte == b'\n').count();
+                written += chunk.len();
+                file.write_all(&chunk)
+                    .with_context(|| format!("write {}", out.display()))?;
+            }
+            file.flush()?;
+            println!(
+                "exported {records} record(s), {written} bytes -> {}",
+                out.display()
+            );
+            Ok(())
+        }
+        AccountAction::SignUploads => {
+            let Some(device_id) = client.device_id().map(str::to_string) else {
+                bail!("this machine has no device id to register a key for");
+            };
+            let path = opensession_paths::device_key_path()?;
+            let key = DeviceSigningKey::load_or_create(&path)
+                .with_context(|| format!("load device key {}", path.display()))?;
+            let public_key = key.public_key();
+            client
+                .register_device_key(
+                    &device_id,
+                    &RegisterDeviceKeyRequest {
+                        public_key: public_key.clone(),
+                    },
+                )
+                .await
+                .map_err(|err| {
+                    guided_error(
+                        format!("register device key: {err}"),
+                        [
+                            "a device keeps its first key; revoke it with DELETE /api/auth/devices/{id} and delete the key file to start over".to_string(),
+                            format!("key file: {}", path.display()),
+                        ],
+                    )
+                })?;
+            println!("uploads from device {device_id} are now signed with {public_key}");
+            Ok(())
+        }
+        AccountAction::Usage { team } => {
+            let teams = match team {
+                Some(team) => vec![team],
+                None => client.me().await.context("look up account")?.teams,
+            };
+            if teams.is_empty() {
+                return Err(guided_error(
+                    "your account belongs to no team",
+                    ["teams come from your sign-in provider's group claims"],
+                ));
+            }
+            for team_id in teams {
+                let usage = client
+                    .team_usage(&team_id)
+                    .await
+                    .with_context(|| format!("team {team_id} usage"))?;
+                print_usage(&usage);
+            }
+            Ok(())
+        }
+        AccountAction::Delete { yes } => {
+            let me = client.me().await.context("look up account")?;
+            if !yes {
+                if !(io::stdin().is_terminal() && io::stderr().is_terminal()) {
+                    return Err(guided_error(
+                        "account delete needs confirmation",
+                        [
+                            "run `opensession account export` first to keep a copy",
+                            "re-run with --yes to delete without prompting",
+                        ],
+                    ));
+                }
+                eprint!(
+                    "This deletes @{} and every session it uploaded. Type the nickname to confirm: ",
+                    me.nickname
+                );
+                io::stderr().flush().ok();
+                let mut line = String::new();
+                io::stdin()
+                    .lock()
+                    .read_line(&mut line)
+                    .context("read delete confirmation")?;
+                if line.trim() != me.nickname {
+                    bail!("account delete cancelled");
+                }
+            }
+            let deleted = client.delete_account().await.context("delete account")?;
+            println!(
+                "deleted @{}: {} session(s), {} attachment(s)",
+                me.nickname, deleted.sessions_deleted, deleted.attachments_deleted
+            );
+            println!("remove `api_key` from opensession.toml; it no longer works");
+            Ok(())
+        }
+    }
+}
+
+fn print_usage(usage: &TeamUsageResponse) {
+    println!(
+        "{}: {} session(s), {} of session bodies",
+        usage.team_id,
+        usage.session_count,
+        crate::cache_cmd::format_size(usage.body_bytes)
+    );
+    println!(
+        "  sessions quota: {}",
+        describe_limit(&usage.sessions_quota, |count| count.to_string())
+    );
+    println!(
+        "  body quota: {}",
+        describe_limit(&usage.body_bytes_quota, crate::cache_cmd::format_size)
+    );
+    for warning in &usage.warnings {
+        println!("  warning: {warning}");
+    }
+}
+
+fn describe_limit(limit: &QuotaLimit, format: impl Fn(u64) -> String) -> String {
+    match (limit.soft, limit.hard) {
+        (None, None) => "unlimited".to_string(),
+        (Some(soft), None) => format!("soft {}", format(soft)),
+        (None, Some(hard)) => format!("hard {}", format(hard)),
+        (Some(soft), Some(hard)) => format!("soft {}, hard {}", format(soft), format(hard)),
+    }
+}
@@ -0,0 +1,138 @@
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use opensession_local_db::LocalDb;
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Args)]
+pub struct ArchiveArgs {
+    #[command(subcommand)]
+    pub action: ArchiveAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum ArchiveAction {
+    /// Move old session source files into monthly `.tar.zst` archives.
+    Run {
+        /// Archive files not modified for this many days.
+        #[arg(long, default_value_t = 30)]
+        older_than_days: u64,
+        /// Archive directory (default: `<data dir>/source-archive`).
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        /// List what would be archived without moving anything.
+        #[arg(long)]
+        dry_run: bool,
+        /// Print JSON instead of text.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print a session's raw source file, reading from its archive if needed.
+    Cat {
+        /// Session id.
+        session_id: String,
+    },
+    /// Extract an archived source file back to its original path.
+    Restore {
+        /// Session id.
+        session_id: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct ArchiveRunPayload {
+    dry_run: bool,
+    files: usize,
+    sessions: usize,
+    bytes: u64,
+    archives: Vec<String>,
+}
+
+pub fn run(args: ArchiveArgs) -> Result<()> {
+    let db = LocalDb::open().context("open local db")?;
+    match args.action {
+        ArchiveAction::Run {
+            older_than_days,
+            dir,
+            dry_run,
+            json,
+        } => {
+            let dir = match dir {
+                Some(dir) => dir,
+                None => opensession_paths::source_archive_root()
+                    .context("resolve source archive directory")?,
+            };
+            let older_than = Duration::from_secs(older_than_days.saturating_mul(86_400));
+            let plan = db
+                .plan_source_archive(&dir, older_than)
+                .context("select source files to archive")?;
+
+            let payload = if dry_run {
+                let mut archives = plan
+                    .iter()
+                    .map(|candidate| candidate.archive_path.display().to_string())
+                    .collect::<Vec<_>>();
+                archives.sort();
+                archives.dedup();
+                ArchiveRunPayload {
+                    dry_run,
+                    files: plan.len(),
+                    sessions: plan.iter().map(|c| c.session_ids.len()).sum(),
+                    bytes: plan.iter().map(|c| c.bytes).sum(),
+                    archives,
+                }
+            } else {
+                let report = db.archive_sources(&plan).context("archive source files")?;
+                ArchiveRunPayload {
+                    dry_run,
+                    files: report.files,
+                    sessions: report.sessions,
+                    bytes: report.bytes,
+                    archives: report
+                        .archives
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect(),
+                }
+            };
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&payload)?);
+            } else {
+                let verb = if dry_run { "would archive" } else { "archived" };
+                println!(
+                    "{verb} {} file(s) ({} session(s), {})",
+                    payload.files,
+                    payload.sessions,
+                    crate::cache_cmd::format_size(payload.bytes)
+                );
+                for archive in &payload.archives {
+                    println!("  {archive}");
+                }
+            }
+            Ok(())
+        }
+        ArchiveAction::Cat { session_id } => {
+            let Some(bytes) = db
+                .read_session_source(&session_id)
+                .with_context(|| format!("read source for {session_id}"))?
+            else {
+                bail!("no source file or archive recorded for session {session_id}");
+            };
+            std::io::stdout().write_all(&bytes)?;
+            Ok(())
+        }
+        ArchiveAction::Restore { session_id } => {
+            match db
+                .restore_session_source(&session_id)
+                .with_context(|| format!("restore source for {session_id}"))?
+            {
+                Some(path) => println!("restored {}", path.display()),
+                None => println!("session {session_id} is not archived"),
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use opensession_api::{
+    ASCIICAST_CONTENT_TYPE, AttachmentKind, AttachmentUploadQuery, MAX_ATTACHMENT_BYTES,
+    SessionAttachment, asciicast_recorded_at, is_asciicast,
+};
+use opensession_local_db::LocalDb;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Args)]
+pub struct AttachArgs {
+    #[command(subcommand)]
+    pub action: AttachAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum AttachAction {
+    /// Attach files (screenshots, logs, test reports, terminal recordings) to a session.
+    Add {
+        session_id: String,
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Media type for every file; guessed from the extension by default.
+        #[arg(long)]
+        content_type: Option<String>,
+        /// Mark the files as terminal recordings (tmux captures, ...).
+        /// Asciinema `.cast` files are recognized without it.
+        #[arg(long)]
+        terminal_recording: bool,
+        /// RFC 3339 time a terminal recording started; read from the
+        /// asciicast header by default.
+        #[arg(long, value_parser = parse_recorded_at)]
+        recorded_at: Option<String>,
+        /// Also upload to the configured server (the session must be yours there).
+        #[arg(long)]
+        upload: bool,
+    },
+    /// List a session's attachments.
+    List {
+        session_id: String,
+        /// List the server's attachments instead of local ones.
+        #[arg(long)]
+        remote: bool,
+        /// Print JSON instead of `id<TAB>name<TAB>type<TAB>bytes<TAB>kind` lines.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print where a local attachment is stored.
+    Path { attachment_id: String },
+}
+
+pub async fn run(args: AttachArgs) -> Result<()> {
+    match args.action {
+        AttachAction::Add {
+            session_id,
+            files,
+            content_type,
+            terminal_recording,
+            recorded_at,
+            upload,
+        } => {
+            let options = AddOptions {
+                content_type: content_type.as_deref(),
+                terminal_recording,
+                recorded_at: recorded_at.as_deref(),
+                upload,
+            };
+            add(&session_id, &files, options).await
+        }
+        AttachAction::List {
+            session_id,
+            remote,
+            json,
+        } => {
+            let attachments = if remote {
+                let runtime = crate::runtime_settings::load_runtime_config()?;
+                crate::handoff_v1::server_client(&runtime)?
+                    .list_attachments(&session_id)
+                    .await
+                    .context("list server attachments")?
+                    .attachments
+            } else {
+                LocalDb::open()
+                    .context("open local db")?
+                    .list_attachments(&session_id)
+                    .context("list attachments")?
+            };
+            print_attachments(&attachments, json)
+        }
+        AttachAction::Path { attachment_id } => {
+            let db = LocalDb::open().context("open local db")?;
+            let root = opensession_paths::attachments_root()?;
+            match db
+                .attachment_path(&root, &attachment_id)
+                .context("read attachment")?
+            {
+                Some(path) => {
+                    println!("{}", path.display());
+                    Ok(())
+                }
+                None => bail!("attachment not found: {attachment_id}"),
+            }
+        }
+    }
+}
+
+struct AddOptions<'a> {
+    content_type: Option<&'a str>,
+    terminal_recording: bool,
+    recorded_at: Option<&'a str>,
+    upload: bool,
+}
+
+async fn add(session_id: &str, files: &[PathBuf], options: AddOptions<'_>) -> Result<()> {
+    let db = LocalDb::open().context("open local db")?;
+    let root = opensession_paths::attachments_root()?;
+    let client = if options.upload {
+        let runtime = crate::runtime_settings::load_runtime_config()?;
+        Some(crate::handoff_v1::server_client(&runtime)?)
+    } else {
+        None
+    };
+
+    for file in files {
+        let data = std::fs::read(file).with_context(|| format!("read {}", file.display()))?;
+        if data.len() > MAX_ATTACHMENT_BYTES {
+            bail!(
+                "{} is larger than the {} MiB attachment limit",
+                file.display(),
+                MAX_ATTACHMENT_BYTES / (1024 * 1024)
+            );
+        }
+        let file_name = AttachmentUploadQuery {
+            name: file.to_string_lossy().into_owned(),
+            kind: None,
+            recorded_at: None,
+        }
+        .file_name()
+        .with_context(|| format!("{} has no file name", file.display()))?;
+        let content_type = options
+            .content_type
+            .unwrap_or_else(|| guess_content_type(file));
+        let kind = if options.terminal_recording
+            || content_type == ASCIICAST_CONTENT_TYPE
+            || is_asciicast(&data)
+        {
+            AttachmentKind::TerminalRecording
+        } else {
+            AttachmentKind::File
+        };
+        let recorded_at = match kind {
+            AttachmentKind::TerminalRecording => options
+                .recorded_at
+                .map(str::to_string)
+                .or_else(|| asciicast_recorded_at(&data)),
+            AttachmentKind::File => None,
+        };
+
+        let attachment = db
+            .add_attachment(
+                &root,
+                session_id,
+                &file_name,
+                content_type,
+                kind,
+                recorded_at.as_deref(),
+                &data,
+            )
+            .context("store attachment")?;
+        println!("{}\t{}", attachment.id, attachment.file_name);
+        if let Some(client) = &client {
+            let query = AttachmentUploadQuery {
+                name: file_name,
+                kind: Some(kind),
+                recorded_at,
+            };
+            let remote = client
+                .upload_attachment_with(session_id, &query, content_type, data)
+                .await
+                .with_context(|| format!("upload {}", file.display()))?;
+            println!("{}\t{}\t(uploaded)", remote.id, remote.file_name);
+        }
+    }
+    Ok(())
+}
+
+fn print_attachments(attachments: &[SessionAttachment], json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(attachments)?);
+        return Ok(());
+    }
+    for attachment in attachments {
+        println!(
+            "{}\t{}\t{}\t{}\t{}",
+            attachment.id,
+            attachment.file_name,
+            attachment.content_type,
+            attachment.size_bytes,
+            attachment.kind.as_str()
+        );
+    }
+    Ok(())
+}
+
+fn guess_content_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "json" => "application/json",
+        "jsonl" => "application/jsonl",
+        "xml" => "application/xml",
+        "html" | "htm" => "text/html",
+        "md" => "text/markdown",
+        "txt" | "log" => "text/plain",
+        "csv" => "text/csv",
+        "cast" => ASCIICAST_CONTENT_TYPE,
+        _ => "application/octet-stream",
+    }
+}
+
+/// Normalize `--recorded-at` to RFC 3339 UTC.
+fn parse_recorded_at(raw: &str) -> Result<String, String> {
+    chrono::DateTime::parse_from_rfc3339(raw)
+        .map(|at| {
+            at.with_timezone(&chrono::Utc)
+                .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+        })
+        .map_err(|err| format!("expected an RFC 3339 timestamp: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::guess_content_type;
+    use std::path::Path;
+
+    #[test]
+    fn content_type_follows_the_extension() {
+        assert_eq!(guess_content_type(Path::new("shot.PNG")), "image/png");
+        assert_eq!(
+            guess_content_type(Path::new("junit.xml")),
+            "application/xml"
+        );
+        assert_eq!(guess_content_type(Path::new("build.log")), "text/plain");
+        assert_eq!(
+            guess_content_type(Path::new("run.cast")),
+            "application/x-asciicast"
+        );
+        assert_eq!(
+            guess_content_type(Path::new("core")),
+            "application/octet-stream"
+        );
+    }
+}
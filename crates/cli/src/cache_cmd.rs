@@ -0,0 +1,120 @@
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use opensession_local_db::{LocalCacheStats, LocalDb};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Args)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub action: CacheAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum CacheAction {
+    /// Show local cache entry counts and sizes.
+    Stats {
+        /// Print JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Clear local caches (all of them unless a kind is selected).
+    Clear {
+        /// Clear cached semantic summaries only.
+        #[arg(long)]
+        summaries: bool,
+        /// Clear cached session bodies only.
+        #[arg(long)]
+        bodies: bool,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct CacheStatsPayload {
+    summaries: CacheKindStats,
+    bodies: CacheKindStats,
+}
+
+#[derive(Debug, Serialize)]
+struct CacheKindStats {
+    entries: u64,
+    bytes: u64,
+}
+
+impl From<LocalCacheStats> for CacheKindStats {
+    fn from(stats: LocalCacheStats) -> Self {
+        Self {
+            entries: stats.entries,
+            bytes: stats.bytes,
+        }
+    }
+}
+
+pub fn run(args: CacheArgs) -> Result<()> {
+    let db = LocalDb::open().context("open local db")?;
+    match args.action {
+        CacheAction::Stats { json } => {
+            let payload = CacheStatsPayload {
+                summaries: db
+                    .session_semantic_summary_cache_stats()
+                    .context("read summary cache stats")?
+                    .into(),
+                bodies: db
+                    .body_cache_stats()
+                    .context("read body cache stats")?
+                    .into(),
+            };
+            if json {
+                println!("{}", serde_json::to_string_pretty(&payload)?);
+            } else {
+                println!("{:<10} {:>8} {:>12}", "cache", "entries", "size");
+                for (name, stats) in [
+                    ("summaries", &payload.summaries),
+                    ("bodies", &payload.bodies),
+                ] {
+                    println!(
+                        "{name:<10} {:>8} {:>12}",
+                        stats.entries,
+                        format_size(stats.bytes)
+                    );
+                }
+            }
+            Ok(())
+        }
+        CacheAction::Clear { summaries, bodies } => {
+            let all = !summaries && !bodies;
+            if all || summaries {
+                let removed = db
+                    .clear_session_semantic_summaries()
+                    .context("clear summary cache")?;
+                println!("cleared {removed} cached summaries");
+            }
+            if all || bodies {
+                let removed = db.clear_body_cache().context("clear body cache")?;
+                println!("cleared {removed} cached bodies");
+            }
+            Ok(())
+        }
+    }
+}
+
+pub(crate) fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_size;
+
+    #[test]
+    fn format_size_picks_readable_unit() {
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+}
@@ -0,0 +1,266 @@
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration, Utc};
+use clap::Args;
+use opensession_local_db::{LocalDb, LocalSessionRow, LogFilter};
+use serde::Serialize;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+
+use crate::user_guidance::guided_error;
+
+/// Rows shown in the preview before asking for confirmation.
+const PREVIEW_ROWS: usize = 10;
+
+#[derive(Debug, Clone, Args)]
+pub struct CleanArgs {
+    /// Only sessions created more than this long ago (`90d`, `12h`, `2w`).
+    #[arg(long, value_name = "AGE")]
+    pub older_than: Option<String>,
+    /// Only sessions from this tool (exact match, e.g. `codex`).
+    #[arg(long)]
+    pub tool: Option<String>,
+    /// Only sessions whose model matches this pattern (`*` wildcards).
+    #[arg(long)]
+    pub model: Option<String>,
+    /// Only sessions from this git repo name.
+    #[arg(long)]
+    pub repo: Option<String>,
+    /// Select auxiliary sessions (sub-agents, summary workers) instead of regular ones.
+    #[arg(long)]
+    pub auxiliary: bool,
+    /// Also delete the source files the sessions were indexed from.
+    #[arg(long)]
+    pub purge_files: bool,
+    /// Show what would be deleted and exit.
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Skip the confirmation prompt.
+    #[arg(long)]
+    pub yes: bool,
+    /// Print JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct CleanReport {
+    dry_run: bool,
+    matched: usize,
+    deleted: usize,
+    files_removed: usize,
+    file_errors: Vec<String>,
+    session_ids: Vec<String>,
+}
+
+pub fn run(args: CleanArgs) -> Result<()> {
+    if args.older_than.is_none()
+        && args.tool.is_none()
+        && args.model.is_none()
+        && args.repo.is_none()
+        && !args.auxiliary
+    {
+        return Err(guided_error(
+            "refusing to clean without a selector",
+            [
+                "pass at least one of --older-than, --tool, --model, --repo, --auxiliary",
+                "preview first: opensession clean --older-than 90d --dry-run",
+            ],
+        ));
+    }
+
+    let before = args
+        .older_than
+        .as_deref()
+        .map(|raw| cutoff_before(Utc::now(), raw))
+        .transpose()?
+        .map(|cutoff| cutoff.to_rfc3339());
+    let filter = LogFilter {
+        tool: args.tool.clone(),
+        model: args.model.clone(),
+        before,
+        git_repo_name: args.repo.clone(),
+        auxiliary_only: args.auxiliary,
+        ..Default::default()
+    };
+
+    let db = LocalDb::open().context("open local db")?;
+    let rows = db
+        .list_sessions_log(&filter)
+        .context("select sessions to clean")?;
+
+    let mut report = CleanReport {
+        dry_run: args.dry_run,
+        matched: rows.len(),
+        deleted: 0,
+        files_removed: 0,
+        file_errors: Vec::new(),
+        session_ids: rows.iter().map(|row| row.id.clone()).collect(),
+    };
+    if rows.is_empty() || args.dry_run {
+        return finish(&args, &rows, &report);
+    }
+
+    if !args.json {
+        print_preview(&rows, args.purge_files);
+    }
+    if !args.yes {
+        if !is_interactive_terminal() {
+            return Err(guided_error(
+                "clean needs confirmation",
+                ["re-run with --yes to delete without prompting"],
+            ));
+        }
+        prompt_confirmation(&format!("Delete {} session(s)? [y/N] ", rows.len()))?;
+    }
+
+    for row in &rows {
+        db.delete_session(&row.id)
+            .with_context(|| format!("delete session {}", row.id))?;
+        report.deleted += 1;
+        if !args.purge_files {
+            continue;
+        }
+        let Some(path) = row.source_path.as_deref() else {
+            continue;
+        };
+        match remove_source_file(Path::new(path)) {
+            Ok(true) => report.files_removed += 1,
+            Ok(false) => {}
+            Err(err) => report.file_errors.push(format!("{path}: {err}")),
+        }
+    }
+
+    finish(&args, &rows, &report)
+}
+
+fn finish(args: &CleanArgs, rows: &[LocalSessionRow], report: &CleanReport) -> Result<()> {
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+        return Ok(());
+    }
+    if rows.is_empty() {
+        println!("no sessions matched");
+    } else if report.dry_run {
+        print_preview(rows, args.purge_files);
+        println!("dry run: nothing deleted");
+    } else {
+        println!("deleted {} session(s)", report.deleted);
+        if args.purge_files {
+            println!("removed {} source file(s)", report.files_removed);
+        }
+        for error in &report.file_errors {
+            eprintln!("could not remove {error}");
+        }
+    }
+    Ok(())
+}
+
+fn print_preview(rows: &[LocalSessionRow], purge_files: bool) {
+    println!("{} session(s) match:", rows.len());
+    for row in rows.iter().take(PREVIEW_ROWS) {
+        let created = row.created_at.get(..10).unwrap_or(&row.created_at);
+        let title = row.title.as_deref().unwrap_or("(untitled)");
+        println!("  {created}  {:<12} {}  {title}", row.tool, row.id);
+    }
+    if rows.len() > PREVIEW_ROWS {
+        println!("  ... and {} more", rows.len() - PREVIEW_ROWS);
+    }
+    if purge_files {
+        let files = rows.iter().filter(|row| row.source_path.is_some()).count();
+        println!("{files} source file(s) will also be deleted");
+    } else {
+        println!("source files are kept; re-indexing will bring these sessions back");
+    }
+}
+
+/// Remove `path` if it still exists. Returns whether a file was removed.
+fn remove_source_file(path: &Path) -> io::Result<bool> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// The instant `raw` (see [`parse_age`]) before `now`.
+fn cutoff_before(now: DateTime<Utc>, raw: &str) -> Result<DateTime<Utc>> {
+    now.checked_sub_signed(parse_age(raw)?)
+        .with_context(|| format!("age '{}' reaches too far into the past", raw.trim()))
+}
+
+/// Parse a compact age like `90d`, `12h`, `2w` or `30m`.
+fn parse_age(raw: &str) -> Result<Duration> {
+    let raw = raw.trim();
+    let split = raw.find(|c: char| !c.is_ascii_digit()).unwrap_or(raw.len());
+    let (amount, unit) = raw.split_at(split);
+    let Ok(amount) = amount.parse::<i64>() else {
+        bail!("invalid age '{raw}': expected a number followed by m, h, d or w (e.g. 90d)");
+    };
+    let age = match unit {
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => bail!("invalid age '{raw}': unit must be one of m, h, d, w"),
+    };
+    age.with_context(|| format!("invalid age '{raw}': out of range"))
+}
+
+fn prompt_confirmation(prompt: &str) -> Result<()> {
+    eprint!("{prompt}");
+    io::stderr().flush().ok();
+
+    let mut line = String::new();
+    io::stdin()
+        .read_line(&mut line)
+        .context("read clean confirmation")?;
+    let answer = line.trim();
+    if answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes") {
+        return Ok(());
+    }
+
+    bail!("clean cancelled by user")
+}
+
+fn is_interactive_terminal() -> bool {
+    io::stdin().is_terminal() && io::stderr().is_terminal()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_age_accepts_compact_units() {
+        assert_eq!(parse_age("90d").unwrap(), Duration::days(90));
+        assert_eq!(parse_age(" 12h ").unwrap(), Duration::hours(12));
+        assert_eq!(parse_age("2w").unwrap(), Duration::weeks(2));
+        assert_eq!(parse_age("30m").unwrap(), Duration::minutes(30));
+    }
+
+    #[test]
+    fn parse_age_rejects_unknown_input() {
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("90").is_err());
+        assert!(parse_age("3 days").is_err());
+        assert!(parse_age("1y").is_err());
+    }
+
+    #[test]
+    fn out_of_range_ages_are_errors() {
+        assert!(parse_age("999999999999d").is_err());
+        assert!(parse_age("99999999999999999999m").is_err());
+        let now = Utc::now();
+        assert!(cutoff_before(now, "99999999999d").is_err());
+        assert_eq!(cutoff_before(now, "2w").unwrap(), now - Duration::weeks(2));
+    }
+
+    #[test]
+    fn remove_source_file_tolerates_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(&path, "{}").unwrap();
+        assert!(remove_source_file(&path).unwrap());
+        assert!(!remove_source_file(&path).unwrap());
+    }
+}
@@ -18,12 +18,17 @@ Common next steps:
   opensession share os://src/local/<sha256> --quick"
 )]
 pub(crate) struct Cli {
+    /// Server profile from `[server.profiles.<name>]` (also `OPENSESSION_PROFILE`).
+    #[arg(long = "profile", value_name = "NAME")]
+    pub(crate) server_profile: Option<String>,
     #[command(subcommand)]
     pub(crate) command: Commands,
 }
 
 #[derive(Subcommand)]
 pub(crate) enum Commands {
+    /// Interactive first-run setup that writes runtime `opensession.toml`.
+    Init(crate::init_cmd::InitArgs),
     /// Register canonical HAIL JSONL into local object store.
     Register(crate::register::RegisterArgs),
     /// Print canonical JSONL for a local source URI.
@@ -38,12 +43,38 @@ pub(crate) enum Commands {
     Review(crate::review::ReviewArgs),
     /// Build and manage immutable handoff artifacts.
     Handoff(crate::handoff_v1::HandoffArgs),
+    /// Resume this repo's most recent session in an agent, via a handoff.
+    Continue(crate::continue_cmd::ContinueArgs),
     /// Parse agent-native logs into canonical HAIL JSONL.
     Parse(crate::parse_cmd::ParseArgs),
+    /// Inspect and validate HAIL session files, and request session reviews.
+    Session(crate::session_cmd::SessionArgs),
+    /// List indexed local sessions with filters (tool, time, file, issue).
+    Log(crate::log_cmd::LogArgs),
+    /// List external plugins and run plugin exporters.
+    Plugins(crate::plugins_cmd::PluginsArgs),
     /// Generate/show local semantic summaries.
     Summary(crate::summary_cmd::SummaryArgs),
+    /// Archive, read and restore old session source files.
+    Archive(crate::archive_cmd::ArchiveArgs),
+    /// Show or clear local summary/body caches.
+    Cache(crate::cache_cmd::CacheArgs),
+    /// Bookmarks, notes and saved filters synced across machines.
+    Meta(crate::meta_cmd::MetaArgs),
+    /// Attach screenshots, logs and reports to sessions.
+    Attach(crate::attach_cmd::AttachArgs),
+    /// Shared starter prompts and handoff templates (playbooks).
+    Playbook(crate::playbook_cmd::PlaybookArgs),
+    /// Export or delete your server account data, or check team storage usage.
+    Account(crate::account_cmd::AccountArgs),
+    /// One-line daemon status for tmux/starship prompts.
+    Statusline(crate::statusline_cmd::StatuslineArgs),
+    /// Record manual shell commands and show them in session timelines (opt-in hook).
+    ShellHistory(crate::shell_history_cmd::ShellHistoryArgs),
     /// Manage explicit repo config (`.opensession/config.toml`).
     Config(crate::config_cmd::ConfigArgs),
+    /// Bulk-delete indexed local sessions matching filters.
+    Clean(crate::clean_cmd::CleanArgs),
     /// Configure and run hidden-ref cleanup automation.
     Cleanup(crate::cleanup_cmd::CleanupArgs),
     /// Install/update OpenSession git hooks and diagnostics.
@@ -51,6 +82,10 @@ pub(crate) enum Commands {
     Setup(crate::setup_cmd::SetupArgs),
     /// Diagnose and optionally fix local OpenSession setup.
     Doctor(crate::doctor_cmd::DoctorArgs),
+    /// Load-test a server with synthetic sessions.
+    #[cfg(feature = "e2e")]
+    #[command(hide = true)]
+    Test(crate::load_test::TestArgs),
     /// Generate shell completion scripts.
     Docs {
         #[command(subcommand)]
@@ -123,6 +158,15 @@ fn localize_command(command: &mut Command) {
                 ),
             );
         }
+        "init" => {
+            set_about(
+                command,
+                localize(
+                    "Interactive first-run setup that writes runtime `opensession.toml`.",
+                    "런타임 `opensession.toml`을 작성하는 대화형 초기 설정을 실행합니다.",
+                ),
+            );
+        }
         "register" => {
             set_about(
                 command,
@@ -193,6 +237,15 @@ fn localize_command(command: &mut Command) {
                 ),
             );
         }
+        "continue" => {
+            set_about(
+                command,
+                localize(
+                    "Resume this repo's most recent session in an agent, via a handoff.",
+                    "이 저장소의 가장 최근 세션을 핸드오프와 함께 에이전트에서 이어갑니다.",
+                ),
+            );
+        }
         "parse" => {
             set_about(
                 command,
@@ -218,6 +271,87 @@ fn localize_command(command: &mut Command) {
                 ),
             );
         }
+        "archive" => {
+            set_about(
+                command,
+                localize(
+                    "Archive, read and restore old session source files.",
+                    "오래된 세션 원본 파일을 보관, 조회, 복원합니다.",
+                ),
+            );
+        }
+        "cache" => {
+            set_about(
+                command,
+                localize(
+                    "Show or clear local summary/body caches.",
+                    "로컬 summary/본문 캐시를 표시하거나 비웁니다.",
+                ),
+            );
+        }
+        "meta" => {
+            set_about(
+                command,
+                localize(
+                    "Bookmarks, notes and saved filters synced across machines.",
+                    "여러 머신 간에 동기화되는 북마크, 메모, 저장된 필터입니다.",
+                ),
+            );
+        }
+        "attach" => {
+            set_about(
+                command,
+                localize(
+                    "Attach screenshots, logs and reports to sessions.",
+                    "세션에 스크린샷, 로그, 리포트를 첨부합니다.",
+                ),
+            );
+        }
+        "playbook" => {
+            set_about(
+                command,
+                localize(
+                    "Shared starter prompts and handoff templates (playbooks).",
+                    "공유 시작 프롬프트와 핸드오프 템플릿(플레이북)입니다.",
+                ),
+            );
+        }
+        "account" => {
+            set_about(
+                command,
+                localize(
+                    "Export or delete your server account data, or check team storage usage.",
+                    "서버 계정 데이터를 내보내거나 삭제하고, 팀 저장 공간 사용량을 확인합니다.",
+                ),
+            );
+        }
+        "statusline" => {
+            set_about(
+                command,
+                localize(
+                    "One-line daemon status for tmux/starship prompts.",
+                    "tmux/starship 프롬프트용 한 줄 데몬 상태입니다.",
+                ),
+            );
+        }
+        "shell-history" => {
+            set_about(
+                command,
+                localize(
+                    "Record manual shell commands and show them in session timelines (opt-in hook).",
+                    "직접 실행한 셸 명령을 기록해 세션 타임라인에 표시합니다(선택형 훅).",
+                ),
+            );
+        }
+        "log" => {
+            set_about(
+                command,
+                localize(
+                    "List indexed local sessions with filters (tool, time, file, issue).",
+                    "필터(도구, 시간, 파일, 이슈)로 로컬 인덱스 세션을 나열합니다.",
+                ),
+            );
+        }
         "config" => {
             set_about(
                 command,
@@ -227,6 +361,15 @@ fn localize_command(command: &mut Command) {
                 ),
             );
         }
+        "clean" => {
+            set_about(
+                command,
+                localize(
+                    "Bulk-delete indexed local sessions matching filters.",
+                    "필터에 맞는 로컬 인덱스 세션을 일괄 삭제합니다.",
+                ),
+            );
+        }
         "cleanup" => {
             set_about(
                 command,
@@ -263,6 +406,24 @@ fn localize_command(command: &mut Command) {
                 ),
             );
         }
+        "test" => {
+            set_about(
+                command,
+                localize(
+                    "Load-test a server with synthetic sessions.",
+                    "합성 세션으로 서버 부하 테스트를 실행합니다.",
+                ),
+            );
+        }
+        "load" => {
+            set_about(
+                command,
+                localize(
+                    "Upload synthetic sessions and query the list concurrently, reporting latency percentiles.",
+                    "합성 세션 업로드와 목록 조회를 동시에 실행하고 지연 시간 백분위를 보고합니다.",
+                ),
+            );
+        }
         "completion" => {
             set_about(
                 command,
@@ -306,6 +467,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn server_profile_is_separate_from_subcommand_profiles() {
+        let cli = Cli::parse_from([
+            "opensession",
+            "--profile",
+            "work",
+            "docs",
+            "quickstart",
+            "--profile",
+            "claude",
+        ]);
+        assert_eq!(cli.server_profile.as_deref(), Some("work"));
+        match cli.command {
+            Commands::Docs {
+                action: DocsAction::Quickstart { profile, .. },
+            } => assert_eq!(profile, "claude"),
+            _ => panic!("expected docs quickstart command"),
+        }
+    }
+
     #[test]
     fn quickstart_defaults_profile_and_remote() {
         let cli = Cli::parse_from(["opensession", "docs", "quickstart"]);
@@ -1,12 +1,13 @@
 use crate::runtime_settings::{
-    apply_summary_profile, detect_local_summary_profile, load_runtime_config, save_runtime_config,
+    apply_summary_profile, detect_local_summary_profile, load_runtime_config, runtime_config_path,
+    save_runtime_config,
 };
 use crate::user_guidance::{guided_error, guided_error_with_doc};
 use anyhow::{Context, Result};
 use clap::{Args, Subcommand, ValueEnum};
 use opensession_runtime_config::{
     SessionDefaultView, SummaryOutputShape, SummaryProvider, SummaryResponseStyle,
-    SummarySourceMode, SummaryStorageBackend, SummaryTriggerMode,
+    SummarySourceMode, SummaryStorageBackend, SummaryTriggerMode, lint_config,
 };
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
@@ -37,6 +38,8 @@ pub enum ConfigAction {
         #[command(subcommand)]
         action: SummaryConfigAction,
     },
+    /// Check runtime `opensession.toml` for unknown keys and invalid values.
+    Lint,
 }
 
 #[derive(Debug, Clone, Subcommand)]
@@ -233,6 +236,7 @@ pub fn run(args: ConfigArgs) -> Result<()> {
         ConfigAction::Show => run_show(),
         ConfigAction::Runtime { action } => run_runtime(action),
         ConfigAction::Summary { action } => run_summary(action),
+        ConfigAction::Lint => run_lint(),
     }
 }
 
@@ -328,6 +332,34 @@ fn run_runtime(action: RuntimeConfigAction) -> Result<()> {
     }
 }
 
+fn run_lint() -> Result<()> {
+    let path = runtime_config_path()?;
+    if !path.exists() {
+        println!("{}: not found, defaults apply", path.display());
+        return Ok(());
+    }
+    let source = std::fs::read_to_string(&path)
+        .with_context(|| format!("read runtime config {}", path.display()))?;
+    let issues = lint_config(&source);
+    for issue in &issues {
+        println!("{}: {issue}", path.display());
+    }
+    let errors = issues.iter().filter(|issue| issue.is_error()).count();
+    if errors > 0 {
+        return Err(guided_error(
+            format!("{} has {errors} error(s)", path.display()),
+            [
+                "fix the reported lines, then rerun `opensession config lint`",
+                "or rewrite it with `opensession init`",
+            ],
+        ));
+    }
+    if issues.is_empty() {
+        println!("{}: ok", path.display());
+    }
+    Ok(())
+}
+
 fn run_summary(action: SummaryConfigAction) -> Result<()> {
     match action {
         SummaryConfigAction::Show => {
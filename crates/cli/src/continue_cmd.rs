@@ -0,0 +1,146 @@
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use opensession_core::trace::Session;
+use opensession_local_db::{LocalDb, LogFilter};
+use std::path::{Path, PathBuf};
+
+use crate::tool_launcher::ToolLauncher;
+use crate::user_guidance::guided_error;
+
+#[derive(Debug, Clone, Args)]
+pub struct ContinueArgs {
+    /// Agent to launch: a built-in (`claude-code`, `codex`, `gemini`,
+    /// `opencode`) or a `[tools]` entry. Defaults to the session's tool.
+    #[arg(long)]
+    pub tool: Option<String>,
+    /// Reopen the agent's own session by id instead of starting a new one
+    /// from the handoff. Only works when `--tool` matches the session's tool.
+    #[arg(long)]
+    pub resume: bool,
+    /// Write the handoff and print the launch command without running it.
+    #[arg(long)]
+    pub print: bool,
+}
+
+/// Find the most recent session for the current repo, write a handoff for
+/// it and start an agent with that handoff as its first prompt.
+pub fn run(args: ContinueArgs) -> Result<()> {
+    let filter = repo_filter().ok_or_else(|| {
+        guided_error(
+            "not inside a git repository",
+            ["run `opensession continue` from the repo you were working in"],
+        )
+    })?;
+    let db = LocalDb::open()?;
+    let Some(row) = db.list_sessions_log(&filter)?.into_iter().next() else {
+        return Err(guided_error(
+            "no indexed session for this repository yet",
+            [
+                "start the daemon so sessions get indexed: opensession setup",
+                "list what is indexed: opensession log",
+            ],
+        ));
+    };
+    let tool = args.tool.clone().unwrap_or_else(|| row.tool.clone());
+    let config = crate::runtime_settings::load_runtime_config()?;
+    let launcher = ToolLauncher::resolve(&tool, &config.tools).ok_or_else(|| {
+        guided_error(
+            format!("don't know how to start `{tool}`"),
+            [
+                format!("add `[tools.{tool}]` with a `binary` to opensession.toml"),
+                "or pick one of: claude-code, codex, gemini, opencode (--tool)".to_string(),
+            ],
+        )
+    })?;
+
+    let launch = if args.resume {
+        if tool != row.tool {
+            bail!(
+                "--resume reopens the {} session itself; drop --tool or pick {}",
+                row.tool,
+                row.tool
+            );
+        }
+        launcher.resume(&row.id).ok_or_else(|| {
+            guided_error(
+                format!("{tool} has no resume command"),
+                [
+                    format!("set `resume_args` under `[tools.{tool}]`"),
+                    "or drop --resume to start from a handoff".to_string(),
+                ],
+            )
+        })?
+    } else {
+        let source_path = row.source_path.as_deref().map(PathBuf::from);
+        let Some(source_path) = source_path.filter(|path| path.exists()) else {
+            bail!(
+                "source file for session {} is gone; restore it with `opensession archive restore`",
+                row.id
+            );
+        };
+        let session = crate::handoff_v1::parse_session_input(&source_path)?;
+        let handoff = write_handoff(&session)?;
+        eprintln!("handoff: {}", handoff.display());
+        launcher.with_handoff(&handoff, &continue_prompt(&row.tool, &handoff))
+    };
+
+    if args.print {
+        println!("{}", launch.shell_line());
+        return Ok(());
+    }
+    launch.run()
+}
+
+/// Sessions for the repo the current directory belongs to, newest first.
+fn repo_filter() -> Option<LogFilter> {
+    let mut filter = LogFilter {
+        limit: Some(1),
+        ..Default::default()
+    };
+    if let Some(repo) = crate::log_cmd::detect_git_repo_name() {
+        filter.git_repo_name = Some(repo);
+    } else {
+        filter.working_directory = Some(crate::log_cmd::detect_project_dir()?);
+    }
+    Some(filter)
+}
+
+fn write_handoff(session: &Session) -> Result<PathBuf> {
+    let markdown = crate::handoff_v1::render_artifact_markdown(std::slice::from_ref(session));
+    let dir = opensession_paths::handoffs_root()?;
+    std::fs::create_dir_all(&dir).with_context(|| format!("create {}", dir.display()))?;
+    let file_name: String = session
+        .session_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let path = dir.join(format!("continue-{file_name}.md"));
+    std::fs::write(&path, markdown).with_context(|| format!("write {}", path.display()))?;
+    Ok(path)
+}
+
+fn continue_prompt(source_tool: &str, handoff: &Path) -> String {
+    format!(
+        "Continue the work from my previous {source_tool} session. Its handoff is in {}: \
+         read it first, then pick up the open next steps.",
+        handoff.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn continue_prompt_points_at_the_handoff() {
+        let prompt = continue_prompt("codex", Path::new("/tmp/h.md"));
+        assert!(prompt.contains("previous codex session"));
+        assert!(prompt.contains("/tmp/h.md"));
+    }
+}
@@ -6,6 +6,8 @@ use crate::setup_cmd::{self, SetupArgs, SetupFanoutMode, SetupProfile};
 use crate::user_guidance::guided_error;
 use anyhow::Result;
 use clap::{Args, ValueEnum};
+use opensession_local_db::LocalDb;
+use std::path::Path;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum DoctorFanoutMode {
@@ -32,7 +34,7 @@ impl DoctorFanoutMode {
   opensession doctor --fix --yes --profile app --fanout-mode hidden_ref --open-target app
   opensession docs quickstart")]
 pub struct DoctorArgs {
-    /// Apply recommended setup fixes (hooks/shims/fanout defaults).
+    /// Apply recommended setup fixes (hooks/shims/fanout defaults, stale source paths).
     #[arg(long)]
     pub fix: bool,
     /// Apply setup changes without interactive confirmation.
@@ -86,6 +88,9 @@ pub fn run(args: DoctorArgs) -> Result<()> {
         }
     }
 
+    report_source_reconciliation(args.fix);
+    report_parse_errors(args.fix);
+
     if !args.fix {
         println!("hint: run `opensession doctor --fix` to apply recommended setup values.");
         println!("hint: run `opensession docs quickstart` for a 5-minute first-user flow.");
@@ -94,6 +99,82 @@ pub fn run(args: DoctorArgs) -> Result<()> {
     Ok(())
 }
 
+/// Check indexed session source paths; with `--fix`, re-point moved files and
+/// mark the rest as missing.
+fn report_source_reconciliation(fix: bool) {
+    let discovered = || {
+        opensession_parser_discovery::discover_sessions()
+            .into_iter()
+            .flat_map(|location| location.paths)
+            .collect()
+    };
+    let report = LocalDb::open().and_then(|db| db.reconcile_source_paths(discovered, !fix));
+    match report {
+        Ok(report) if report.stale == 0 => println!("session sources: ok"),
+        Ok(report) if fix => println!(
+            "session sources: relocated {}, marked {} missing",
+            report.relocated.len(),
+            report.missing.len()
+        ),
+        Ok(report) => println!(
+            "session sources: {} stale ({} relocatable, {} missing)",
+            report.stale,
+            report.relocated.len(),
+            report.missing.len()
+        ),
+        Err(error) => println!("session sources: check failed ({error:#})"),
+    }
+}
+
+/// Show session files the daemon failed to parse; with `--fix`, forget
+/// failures for files that no longer exist.
+fn report_parse_errors(fix: bool) {
+    let errors = LocalDb::open().and_then(|db| {
+        let mut errors = db.list_parse_errors()?;
+        if fix {
+            for gone in errors
+                .iter()
+                .filter(|row| !Path::new(&row.source_path).exists())
+            {
+                db.clear_parse_error(&gone.source_path)?;
+            }
+            errors.retain(|row| Path::new(&row.source_path).exists());
+        }
+        Ok(errors)
+    });
+    let errors = match errors {
+        Ok(errors) => errors,
+        Err(error) => {
+            println!("parse errors: check failed ({error:#})");
+            return;
+        }
+    };
+    if errors.is_empty() {
+        println!("parse errors: none");
+        return;
+    }
+    println!("parse errors: {} file(s) failed to parse", errors.len());
+    for row in errors.iter().take(PARSE_ERRORS_SHOWN) {
+        println!(
+            "  {} [{}] {}: {}",
+            row.failed_at.get(..19).unwrap_or(&row.failed_at),
+            row.parser.as_deref().unwrap_or("?"),
+            row.source_path,
+            first_line(&row.error)
+        );
+    }
+    if errors.len() > PARSE_ERRORS_SHOWN {
+        println!("  ... {} more", errors.len() - PARSE_ERRORS_SHOWN);
+    }
+    println!("hint: run `opensession parse --profile <parser> <file> --preview` to reproduce one.");
+}
+
+const PARSE_ERRORS_SHOWN: usize = 5;
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default()
+}
+
 fn validate_args(args: &DoctorArgs) -> Result<()> {
     if args.fanout_mode.is_some() && !args.fix {
         return Err(guided_error(
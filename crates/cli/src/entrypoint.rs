@@ -1,28 +1,52 @@
 use crate::{
-    cat_cmd, cleanup_cmd,
+    account_cmd, archive_cmd, attach_cmd, cache_cmd, cat_cmd, clean_cmd, cleanup_cmd,
     cli_args::{Commands, parse_cli},
-    config_cmd, docs_cmd, doctor_cmd, handoff_v1, inspect,
+    config_cmd, continue_cmd, docs_cmd, doctor_cmd, handoff_v1, init_cmd, inspect,
     locale::localize,
-    parse_cmd, register, review, setup_cmd, share, summary_cmd, view,
+    log_cmd, meta_cmd, parse_cmd, playbook_cmd, plugins_cmd, register, review, session_cmd,
+    setup_cmd, share, shell_history_cmd, statusline_cmd, summary_cmd, view,
 };
 
 pub(crate) async fn run_process() {
+    // Help text is localized while parsing, so `[ui] language` is read first.
+    // A broken config is reported by the command that needs it, not here.
+    if let Ok(config) = crate::runtime_settings::load_runtime_config() {
+        crate::locale::set_configured_language(config.ui.language);
+    }
     let cli = parse_cli();
+    crate::runtime_settings::set_server_profile(cli.server_profile);
+    migrate_legacy_dirs();
 
     let result = match cli.command {
+        Commands::Init(args) => init_cmd::run(args).await,
         Commands::Register(args) => register::run(args),
         Commands::Cat(args) => cat_cmd::run(args),
         Commands::Inspect(args) => inspect::run(args),
         Commands::Share(args) => share::run(args),
         Commands::View(args) => view::run(args).await,
         Commands::Review(args) => review::run(args).await,
-        Commands::Handoff(args) => handoff_v1::run(args),
+        Commands::Handoff(args) => handoff_v1::run(args).await,
+        Commands::Continue(args) => continue_cmd::run(args),
         Commands::Parse(args) => parse_cmd::run(args),
+        Commands::Plugins(args) => plugins_cmd::run(args),
         Commands::Summary(args) => summary_cmd::run(args).await,
+        Commands::Session(args) => session_cmd::run(args).await,
+        Commands::Log(args) => log_cmd::run(args),
+        Commands::Archive(args) => archive_cmd::run(args),
+        Commands::Cache(args) => cache_cmd::run(args),
+        Commands::Meta(args) => meta_cmd::run(args).await,
+        Commands::Attach(args) => attach_cmd::run(args).await,
+        Commands::Playbook(args) => playbook_cmd::run(args).await,
+        Commands::Account(args) => account_cmd::run(args).await,
+        Commands::Statusline(args) => statusline_cmd::run(args),
+        Commands::ShellHistory(args) => shell_history_cmd::run(args),
         Commands::Config(args) => config_cmd::run(args),
+        Commands::Clean(args) => clean_cmd::run(args),
         Commands::Cleanup(args) => cleanup_cmd::run(args),
         Commands::Setup(args) => setup_cmd::run(args),
         Commands::Doctor(args) => doctor_cmd::run(args),
+        #[cfg(feature = "e2e")]
+        Commands::Test(args) => crate::load_test::run(args).await,
         Commands::Docs { action } => docs_cmd::run_docs(action),
     };
 
@@ -36,6 +60,24 @@ pub(crate) async fn run_process() {
     }
 }
 
+/// Relocate data left at the fixed pre-XDG locations (see `opensession_paths`).
+/// Failures are reported but never block the command.
+fn migrate_legacy_dirs() {
+    match opensession_paths::migrate_legacy_dirs() {
+        Ok(moved) => {
+            for migration in moved {
+                eprintln!(
+                    "{} {} -> {}",
+                    localize("Moved OpenSession data:", "OpenSession 데이터 이동:"),
+                    migration.from.display(),
+                    migration.to.display()
+                );
+            }
+        }
+        Err(error) => eprintln!("{} {error}", localize("Warning:", "경고:")),
+    }
+}
+
 fn debug_errors_enabled() -> bool {
     matches!(
         std::env::var("OPENSESSION_DEBUG"),
@@ -1,7 +1,16 @@
 use anyhow::{Context, Result, bail};
 use clap::{Args, Subcommand, ValueEnum};
+use opensession_api::{
+    CompleteHandoffRequest, HandoffInboxItem, HandoffStatus, PublishHandoffRequest,
+};
+use opensession_api_client::device::DeviceSigningKey;
+use opensession_api_client::{ApiClient, UploadRateLimit};
 use opensession_core::Session;
-use opensession_core::handoff::{HandoffSummary, validate_handoff_summaries};
+use opensession_core::handoff::{
+    HandoffSummary, generate_handoff_markdown_v2, generate_merged_handoff_markdown_v2,
+    merge_sessions, validate_handoff_summaries,
+};
+use opensession_core::handoff_artifact::sort_sessions_time_asc;
 use opensession_core::source_uri::SourceUri;
 use opensession_core::validate::validate_session;
 use opensession_local_db::{LocalDb, LocalSessionFilter};
@@ -11,6 +20,9 @@ use opensession_local_store::{
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::user_guidance::guided_error;
 
 #[derive(Debug, Clone, Args)]
 pub struct HandoffArgs {
@@ -27,6 +39,44 @@ pub enum HandoffCommand {
         #[command(subcommand)]
         action: HandoffArtifactsCommand,
     },
+    /// List handoffs other users published to you.
+    Inbox {
+        /// Only show handoffs in this state.
+        #[arg(long, value_enum)]
+        status: Option<HandoffStatusArg>,
+        /// Maximum number of handoffs to show.
+        #[arg(long)]
+        limit: Option<u32>,
+        /// Print JSON instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Claim a pending handoff from your inbox.
+    Claim { id: String },
+    /// Mark a claimed handoff as completed.
+    Complete {
+        id: String,
+        /// Uploaded session that carried out the handed-off work.
+        #[arg(long = "session", value_name = "SESSION_ID")]
+        session_id: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum HandoffStatusArg {
+    Pending,
+    Claimed,
+    Completed,
+}
+
+impl From<HandoffStatusArg> for HandoffStatus {
+    fn from(value: HandoffStatusArg) -> Self {
+        match value {
+            HandoffStatusArg::Pending => Self::Pending,
+            HandoffStatusArg::Claimed => Self::Claimed,
+            HandoffStatusArg::Completed => Self::Completed,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Args)]
@@ -45,6 +95,15 @@ pub struct HandoffBuildArgs {
     /// Pin alias to move after build.
     #[arg(long)]
     pub pin: Option<String>,
+    /// Publish the built artifact to a user's handoff inbox on the server.
+    #[arg(long, requires = "to")]
+    pub publish: bool,
+    /// Recipient for `--publish` (`@nickname`).
+    #[arg(long, value_name = "@NICKNAME", requires = "publish")]
+    pub to: Option<String>,
+    /// Short note shown with the published handoff.
+    #[arg(long, requires = "publish")]
+    pub note: Option<String>,
 }
 
 #[derive(Debug, Clone, Subcommand)]
@@ -76,6 +135,8 @@ pub enum HandoffArtifactsCommand {
 pub enum ArtifactFormatArg {
     Canonical,
     Raw,
+    /// Time-ordered handoff markdown, including cross-session conflicts.
+    Markdown,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
@@ -105,14 +166,21 @@ pub struct HandoffSummaryMeta {
     pub summary_provider: String,
 }
 
-pub fn run(args: HandoffArgs) -> Result<()> {
+pub async fn run(args: HandoffArgs) -> Result<()> {
     match args.action {
-        HandoffCommand::Build(build) => run_build(build),
+        HandoffCommand::Build(build) => run_build(build).await,
         HandoffCommand::Artifacts { action } => run_artifacts(action),
+        HandoffCommand::Inbox {
+            status,
+            limit,
+            json,
+        } => run_inbox(status.map(Into::into), limit, json).await,
+        HandoffCommand::Claim { id } => run_claim(&id).await,
+        HandoffCommand::Complete { id, session_id } => run_complete(&id, session_id).await,
     }
 }
 
-fn run_build(args: HandoffBuildArgs) -> Result<()> {
+async fn run_build(args: HandoffBuildArgs) -> Result<()> {
     let cwd = std::env::current_dir().context("read current directory")?;
     let runtime = crate::runtime_settings::load_runtime_config().unwrap_or_default();
     let mut sessions = Vec::<Session>::new();
@@ -154,6 +222,7 @@ fn run_build(args: HandoffBuildArgs) -> Result<()> {
     if sessions.is_empty() {
         bail!("no sessions provided (use inputs, --from, or --last)");
     }
+    sort_sessions_time_asc(&mut sessions);
 
     let summaries = sessions
         .iter()
@@ -210,14 +279,147 @@ fn run_build(args: HandoffBuildArgs) -> Result<()> {
     };
     store_artifact_record(&record, &cwd)?;
 
+    if record.raw_sessions.len() > 1 {
+        for conflict in merge_sessions(&record.raw_sessions).conflicts {
+            eprintln!(
+                "warning: `{}` modified by multiple sessions ({}): {}",
+                conflict.path,
+                conflict.kind,
+                conflict.session_ids.join(", ")
+            );
+        }
+    }
+
     if let Some(alias) = args.pin {
         set_pin(&alias, &sha256, &cwd)?;
     }
 
     println!("{artifact_uri}");
+
+    if let Some(recipient) = args.to.filter(|_| args.publish) {
+        let client = server_client(&runtime)?;
+        let published = client
+            .publish_handoff(&PublishHandoffRequest {
+                recipient,
+                artifact_uri: artifact_uri.to_string(),
+                canonical_jsonl: record.canonical_jsonl.clone(),
+                markdown: Some(render_artifact_markdown(&record.raw_sessions))
+                    .filter(|markdown| !markdown.is_empty()),
+                note: args.note,
+            })
+            .await
+            .context("publish handoff")?;
+        eprintln!(
+            "published handoff {} to @{}",
+            published.id, published.recipient_nickname
+        );
+    }
     Ok(())
 }
 
+async fn run_inbox(status: Option<HandoffStatus>, limit: Option<u32>, json: bool) -> Result<()> {
+    let runtime = crate::runtime_settings::load_runtime_config()?;
+    let client = server_client(&runtime)?;
+    let inbox = client
+        .handoff_inbox(status, limit)
+        .await
+        .context("fetch handoff inbox")?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&inbox.items)?);
+        return Ok(());
+    }
+    if inbox.items.is_empty() {
+        println!("handoff inbox is empty");
+        return Ok(());
+    }
+    for item in &inbox.items {
+        println!("{}", inbox_line(item));
+        if let Some(note) = item.note.as_deref() {
+            println!("    {note}");
+        }
+    }
+    Ok(())
+}
+
+async fn run_claim(id: &str) -> Result<()> {
+    let runtime = crate::runtime_settings::load_runtime_config()?;
+    let client = server_client(&runtime)?;
+    let item = client.claim_handoff(id).await.context("claim handoff")?;
+    println!("{}", inbox_line(&item));
+    Ok(())
+}
+
+async fn run_complete(id: &str, session_id: Option<String>) -> Result<()> {
+    let runtime = crate::runtime_settings::load_runtime_config()?;
+    let client = server_client(&runtime)?;
+    let item = client
+        .complete_handoff(id, &CompleteHandoffRequest { session_id })
+        .await
+        .context("complete handoff")?;
+    println!("{}", inbox_line(&item));
+    Ok(())
+}
+
+fn inbox_line(item: &HandoffInboxItem) -> String {
+    let mut line = format!(
+        "{}  [{}]  {}  from @{}  {}",
+        item.id, item.status, item.created_at, item.sender_nickname, item.artifact_uri
+    );
+    if let Some(session_id) = item.result_session_id.as_deref() {
+        line.push_str(&format!("  -> session {session_id}"));
+    }
+    line
+}
+
+pub(crate) fn server_client(
+    runtime: &opensession_runtime_config::DaemonConfig,
+) -> Result<ApiClient> {
+    let server = crate::runtime_settings::active_server_settings(&runtime.server)?;
+    let api_key = server.api_key.trim();
+    if api_key.is_empty() {
+        return Err(guided_error(
+            "server.api_key is not configured",
+            [
+                "issue a key from the web account page (POST /api/auth/api-keys/issue)",
+                "set `api_key` under `[server]` in opensession.toml",
+            ],
+        ));
+    }
+    let mut client =
+        ApiClient::new(&server.url, Duration::from_secs(30)).context("create server client")?;
+    client.set_auth(api_key.to_string());
+    identify_device(&mut client);
+    client.set_upload_rate_limit(upload_rate_limit_now(&server.upload_limit));
+    Ok(client)
+}
+
+/// Tag server requests with this machine's device id, and sign uploads once
+/// `opensession account sign-uploads` created a device key. Best effort: a
+/// missing id only hides the machine from `GET /api/auth/devices`.
+pub(crate) fn identify_device(client: &mut ApiClient) {
+    if let Ok(path) = opensession_paths::device_id_path() {
+        let _ = client.identify_device(&path);
+    }
+    if let Ok(path) = opensession_paths::device_key_path() {
+        match DeviceSigningKey::load(&path) {
+            Ok(key) => client.set_signing_key(key),
+            Err(err) => eprintln!("warning: ignoring device key {}: {err}", path.display()),
+        }
+    }
+}
+
+/// Resolve `[server.upload_limit]` for the current local time of day.
+fn upload_rate_limit_now(
+    settings: &opensession_runtime_config::UploadLimitSettings,
+) -> Option<UploadRateLimit> {
+    use chrono::Timelike;
+    let now = chrono::Local::now();
+    settings
+        .kbps_at(now.hour() * 60 + now.minute())
+        .and_then(UploadRateLimit::from_kbps)
+}
+
 fn enum_label<T: serde::Serialize>(value: &T) -> String {
     serde_json::to_string(value)
         .ok()
@@ -275,6 +477,9 @@ fn run_artifacts_get(
     let (_path, record) = load_artifact_by_hash(&hash, &cwd)?;
 
     match (format, encode) {
+        (ArtifactFormatArg::Markdown, _) => {
+            print!("{}", render_artifact_markdown(&record.raw_sessions));
+        }
         (ArtifactFormatArg::Canonical, ArtifactEncodeArg::Jsonl) => {
             print!("{}", record.canonical_jsonl);
         }
@@ -300,6 +505,14 @@ fn run_artifacts_get(
     Ok(())
 }
 
+pub(crate) fn render_artifact_markdown(sessions: &[Session]) -> String {
+    match sessions {
+        [] => String::new(),
+        [session] => generate_handoff_markdown_v2(&HandoffSummary::from_session(session)),
+        _ => generate_merged_handoff_markdown_v2(&merge_sessions(sessions)),
+    }
+}
+
 fn run_artifacts_verify(id_or_uri: &str) -> Result<()> {
     let cwd = std::env::current_dir().context("read current directory")?;
     let hash = resolve_artifact_hash(id_or_uri, &cwd)?;
@@ -384,7 +597,7 @@ fn canonicalize_summaries(summaries: &[HandoffSummary]) -> Result<String> {
     Ok(out)
 }
 
-fn parse_session_input(path: &Path) -> Result<Session> {
+pub(crate) fn parse_session_input(path: &Path) -> Result<Session> {
     let raw = std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
     if let Ok(session) = Session::from_jsonl(&raw) {
         return Ok(session);
@@ -644,7 +857,7 @@ fn is_hash(value: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::{canonicalize_summaries, is_hash, validate_alias};
+    use super::{canonicalize_summaries, is_hash, render_artifact_markdown, validate_alias};
     use opensession_core::Session;
     use opensession_core::testing;
 
@@ -674,4 +887,34 @@ mod tests {
         let first = canonical.lines().next().expect("line");
         assert!(first.contains("\"source_session_id\":\"a\""));
     }
+
+    #[test]
+    fn markdown_render_merges_sessions_with_conflicts_section() {
+        let now = chrono::Utc::now();
+        let mut first = Session::new("first".to_string(), testing::agent());
+        first.context.created_at = now;
+        first.events.push(testing::event(
+            opensession_core::EventType::FileEdit {
+                path: "src/main.rs".to_string(),
+                diff: None,
+            },
+            "",
+        ));
+        let mut second = Session::new("second".to_string(), testing::agent());
+        second.context.created_at = now + chrono::Duration::seconds(30);
+        second.events.push(testing::event(
+            opensession_core::EventType::FileEdit {
+                path: "src/main.rs".to_string(),
+                diff: None,
+            },
+            "",
+        ));
+
+        let md = render_artifact_markdown(&[second, first]);
+        assert!(md.starts_with("# Merged Session Handoff"));
+        assert!(md.contains("- `src/main.rs` [multi_edit]: first edited → second edited"));
+        assert!(
+            md.find("## Session 1 — first").unwrap() < md.find("## Session 2 — second").unwrap()
+        );
+    }
 }
@@ -0,0 +1,533 @@
+use crate::runtime_settings::{load_runtime_config, runtime_config_path, save_runtime_config};
+use crate::user_guidance::guided_error;
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use opensession_api::LoginRequest;
+use opensession_api_client::ApiClient;
+use opensession_runtime_config::{DaemonConfig, PrivacySettings, TeamRoute, default_watch_paths};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::time::Duration;
+
+mod repo;
+
+#[derive(Debug, Clone, Args)]
+#[command(after_long_help = r"Examples:
+  opensession init
+  opensession init --yes --server-url https://opensession.io --api-key <key>
+  opensession init --yes --privacy strict --watch-path ~/.codex/sessions
+  opensession init --repo")]
+pub struct InitArgs {
+    /// Server base URL.
+    #[arg(long)]
+    pub server_url: Option<String>,
+    /// API key to store under `[server]` (skips the login step).
+    #[arg(long)]
+    pub api_key: Option<String>,
+    /// Privacy preset applied to `[privacy]`.
+    #[arg(long, value_enum)]
+    pub privacy: Option<PrivacyPreset>,
+    /// Session source path to watch; repeat to list several (replaces the defaults).
+    #[arg(long = "watch-path")]
+    pub watch_paths: Vec<String>,
+    /// Accept flags and current values without prompting.
+    #[arg(long)]
+    pub yes: bool,
+    /// Set up the current repository instead: commit hooks, privacy patterns
+    /// for detected secrets files, and daemon registration.
+    #[arg(long, conflicts_with_all = ["server_url", "api_key", "privacy", "watch_paths"])]
+    pub repo: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PrivacyPreset {
+    /// Strip paths and env vars, and skip key/token-like files.
+    Strict,
+    /// Strip paths and env vars with the default exclude patterns.
+    Standard,
+    /// Keep file paths; still strip env vars.
+    Open,
+}
+
+impl PrivacyPreset {
+    const STRICT_EXTRA_PATTERNS: [&'static str; 3] = ["*.pem", "*.key", "*token*"];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Strict => "strict",
+            Self::Standard => "standard",
+            Self::Open => "open",
+        }
+    }
+
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "strict" | "1" => Some(Self::Strict),
+            "standard" | "2" => Some(Self::Standard),
+            "open" | "3" => Some(Self::Open),
+            _ => None,
+        }
+    }
+
+    /// Preset matching the current settings, if they were written by one.
+    fn detect(privacy: &PrivacySettings) -> Option<Self> {
+        [Self::Strict, Self::Standard, Self::Open]
+            .into_iter()
+            .find(|preset| {
+                let mut expected = privacy.clone();
+                preset.apply(&mut expected);
+                expected.strip_paths == privacy.strip_paths
+                    && expected.strip_env_vars == privacy.strip_env_vars
+                    && expected.exclude_patterns == privacy.exclude_patterns
+            })
+    }
+
+    /// Overwrite stripping and exclude patterns; `exclude_tools` is left alone.
+    fn apply(self, privacy: &mut PrivacySettings) {
+        let defaults = PrivacySettings::default();
+        privacy.strip_env_vars = true;
+        privacy.strip_paths = self != Self::Open;
+        privacy.exclude_patterns = defaults.exclude_patterns;
+        if self == Self::Strict {
+            privacy.exclude_patterns.extend(
+                Self::STRICT_EXTRA_PATTERNS
+                    .iter()
+                    .map(|pattern| pattern.to_string()),
+            );
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Credential {
+    Keep,
+    ApiKey(String),
+    Login { email: String, password: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InitAnswers {
+    server_url: String,
+    credential: Credential,
+    routes: Vec<TeamRoute>,
+    watch_paths: Vec<String>,
+    privacy: PrivacyPreset,
+}
+
+impl InitAnswers {
+    fn from_args(args: &InitArgs, config: &DaemonConfig) -> Result<Self> {
+        Ok(Self {
+            server_url: normalize_server_url(
+                args.server_url.as_deref().unwrap_or(&config.server.url),
+            )?,
+            credential: args
+                .api_key
+                .as_deref()
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map_or(Credential::Keep, |key| Credential::ApiKey(key.to_string())),
+            routes: config.routes.clone(),
+            watch_paths: if args.watch_paths.is_empty() {
+                config.watchers.custom_paths.clone()
+            } else {
+                args.watch_paths.clone()
+            },
+            privacy: args
+                .privacy
+                .or_else(|| PrivacyPreset::detect(&config.privacy))
+                .unwrap_or(PrivacyPreset::Standard),
+        })
+    }
+
+    fn apply(self, config: &mut DaemonConfig) {
+        config.server.url = self.server_url;
+        if let Credential::ApiKey(key) = self.credential {
+            config.server.api_key = key;
+        }
+        config.routes = self.routes;
+        config.watchers.custom_paths = self.watch_paths;
+        self.privacy.apply(&mut config.privacy);
+    }
+}
+
+/// Line-based prompts, so the wizard works over SSH and in scripted input.
+struct Prompter<R, W> {
+    input: R,
+    output: W,
+    /// Read secrets without echo (real terminals only).
+    hide_secrets: bool,
+}
+
+impl<R: BufRead, W: Write> Prompter<R, W> {
+    /// Print `label [default]: ` and return the trimmed answer or the default.
+    fn ask(&mut self, label: &str, default: &str) -> Result<String> {
+        if default.is_empty() {
+            write!(self.output, "{label}: ")?;
+        } else {
+            write!(self.output, "{label} [{default}]: ")?;
+        }
+        self.output.flush().context("flush prompt")?;
+        let mut line = String::new();
+        self.input.read_line(&mut line).context("read answer")?;
+        let answer = line.trim();
+        Ok(if answer.is_empty() {
+            default.to_string()
+        } else {
+            answer.to_string()
+        })
+    }
+
+    fn secret(&mut self, label: &str) -> Result<String> {
+        if self.hide_secrets {
+            return dialoguer::Password::new()
+                .with_prompt(label)
+                .interact()
+                .context("read secret");
+        }
+        self.ask(label, "")
+    }
+
+    fn collect(&mut self, args: &InitArgs, config: &DaemonConfig) -> Result<InitAnswers> {
+        let mut answers = InitAnswers::from_args(args, config)?;
+        writeln!(
+            self.output,
+            "OpenSession setup — press Enter to keep the value in brackets."
+        )?;
+
+        loop {
+            let raw = self.ask("Server URL", &answers.server_url)?;
+            match normalize_server_url(&raw) {
+                Ok(url) => {
+                    answers.server_url = url;
+                    break;
+                }
+                Err(err) => writeln!(self.output, "  {err}")?,
+            }
+        }
+
+        if answers.credential == Credential::Keep {
+            answers.credential = self.ask_credential(!config.server.api_key.is_empty())?;
+        }
+
+        let current_routes = format_routes(&answers.routes);
+        loop {
+            let raw = self.ask(
+                "Team routes (`path=team`, comma-separated, `-` for none)",
+                &current_routes,
+            )?;
+            match parse_routes(&raw) {
+                Ok(routes) => {
+                    answers.routes = routes;
+                    break;
+                }
+                Err(err) => writeln!(self.output, "  {err}")?,
+            }
+        }
+
+        if args.watch_paths.is_empty() {
+            let raw = self.ask(
+                "Watch paths (comma-separated, `default` for built-ins)",
+                &answers.watch_paths.join(", "),
+            )?;
+            answers.watch_paths = parse_watch_paths(&raw);
+        }
+
+        if args.privacy.is_none() {
+            writeln!(
+                self.output,
+                "Privacy presets: 1) strict  2) standard  3) open (keeps file paths)"
+            )?;
+            loop {
+                let raw = self.ask("Privacy preset", answers.privacy.as_str())?;
+                match PrivacyPreset::parse(&raw) {
+                    Some(preset) => {
+                        answers.privacy = preset;
+                        break;
+                    }
+                    None => writeln!(self.output, "  choose strict, standard or open")?,
+                }
+            }
+        }
+        Ok(answers)
+    }
+
+    fn ask_credential(&mut self, has_key: bool) -> Result<Credential> {
+        let (options, default) = if has_key {
+            (
+                "1) keep the stored API key  2) paste a new key  3) log in",
+                "1",
+            )
+        } else {
+            ("1) skip for now  2) paste an API key  3) log in", "1")
+        };
+        writeln!(self.output, "Server credentials: {options}")?;
+        loop {
+            match self.ask("Credentials", default)?.as_str() {
+                "1" => return Ok(Credential::Keep),
+                "2" => {
+                    let key = self.secret("API key")?;
+                    if !key.trim().is_empty() {
+                        return Ok(Credential::ApiKey(key.trim().to_string()));
+                    }
+                    writeln!(self.output, "  API key cannot be empty")?;
+                }
+                "3" => {
+                    let email = self.ask("Email", "")?;
+                    let password = self.secret("Password")?;
+                    return Ok(Credential::Login { email, password });
+                }
+                _ => writeln!(self.output, "  choose 1, 2 or 3")?,
+            }
+        }
+    }
+}
+
+pub async fn run(args: InitArgs) -> Result<()> {
+    if args.repo {
+        return repo::run();
+    }
+    let mut config = load_runtime_config()?;
+    let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+    if !interactive && !args.yes {
+        return Err(guided_error(
+            "init cannot prompt without a terminal",
+            [
+                "pass values as flags and add `--yes`",
+                "example: `opensession init --yes --server-url https://opensession.io --api-key <key>`",
+            ],
+        ));
+    }
+
+    let mut answers = if args.yes {
+        InitAnswers::from_args(&args, &config)?
+    } else {
+        Prompter {
+            input: io::stdin().lock(),
+            output: io::stdout(),
+            hide_secrets: true,
+        }
+        .collect(&args, &config)?
+    };
+
+    if let Credential::Login { email, password } = &answers.credential {
+        let api_key = login_for_api_key(&answers.server_url, email, password).await?;
+        answers.credential = Credential::ApiKey(api_key);
+    }
+    answers.apply(&mut config);
+
+    let path = save_runtime_config(&config)?;
+    println!("wrote {}", path.display());
+    println!("  server: {}", config.server.url);
+    println!(
+        "  api key: {}",
+        if config.server.api_key.is_empty() {
+            "not set"
+        } else {
+            "stored"
+        }
+    );
+    println!("  routes: {}", config.routes.len());
+    println!("  watch paths: {}", config.watchers.custom_paths.join(", "));
+    if let Some(preset) = PrivacyPreset::detect(&config.privacy) {
+        println!("  privacy: {}", preset.as_str());
+    }
+    Ok(())
+}
+
+/// Exchange email/password for a long-lived API key stored in `opensession.toml`.
+async fn login_for_api_key(server_url: &str, email: &str, password: &str) -> Result<String> {
+    let mut client =
+        ApiClient::new(server_url, Duration::from_secs(30)).context("create server client")?;
+    crate::handoff_v1::identify_device(&mut client);
+    let tokens = client
+        .login(&LoginRequest {
+            email: email.trim().to_string(),
+            password: password.to_string(),
+        })
+        .await
+        .map_err(|err| {
+            guided_error(
+                format!("login to {server_url} failed: {err}"),
+                [
+                    "check the email/password, or paste an API key instead".to_string(),
+                    format!(
+                        "runtime config: {}",
+                        runtime_config_path()
+                            .map(|path| path.display().to_string())
+                            .unwrap_or_else(|_| "opensession.toml".to_string())
+                    ),
+                ],
+            )
+        })?;
+    client.set_auth(tokens.access_token);
+    let issued = client.issue_api_key().await.context("issue API key")?;
+    Ok(issued.api_key)
+}
+
+fn normalize_server_url(value: &str) -> Result<String> {
+    let trimmed = value.trim().trim_end_matches('/');
+    if !(trimmed.starts_with("http://") || trimmed.starts_with("https://")) {
+        return Err(guided_error(
+            "server URL must start with http:// or https://",
+            ["example: `https://opensession.io`"],
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+fn format_routes(routes: &[TeamRoute]) -> String {
+    if routes.is_empty() {
+        return "-".to_string();
+    }
+    routes
+        .iter()
+        .map(|route| format!("{}={}", route.path, route.team))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parse_routes(raw: &str) -> Result<Vec<TeamRoute>> {
+    if raw.trim() == "-" {
+        return Ok(Vec::new());
+    }
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (path, team) = entry.rsplit_once('=').unwrap_or((entry, ""));
+            let (path, team) = (path.trim(), team.trim());
+            if path.is_empty() || team.is_empty() {
+                return Err(guided_error(
+                    format!("invalid team route `{entry}`"),
+                    ["use `path=team`, e.g. `~/work/**=acme`"],
+                ));
+            }
+            Ok(TeamRoute {
+                path: path.to_string(),
+                team: team.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn parse_watch_paths(raw: &str) -> Vec<String> {
+    if raw.trim().eq_ignore_ascii_case("default") {
+        return default_watch_paths();
+    }
+    let paths: Vec<String> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .collect();
+    if paths.is_empty() {
+        default_watch_paths()
+    } else {
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Credential, InitArgs, PrivacyPreset, Prompter, parse_routes, parse_watch_paths};
+    use opensession_runtime_config::{DaemonConfig, PrivacySettings, default_watch_paths};
+    use std::io::Cursor;
+
+    fn args() -> InitArgs {
+        InitArgs {
+            server_url: None,
+            api_key: None,
+            privacy: None,
+            watch_paths: Vec::new(),
+            yes: false,
+            repo: false,
+        }
+    }
+
+    fn scripted(input: &str, args: &InitArgs, config: &DaemonConfig) -> super::InitAnswers {
+        let mut output = Vec::new();
+        Prompter {
+            input: Cursor::new(input.as_bytes()),
+            output: &mut output,
+            hide_secrets: false,
+        }
+        .collect(args, config)
+        .expect("collect answers")
+    }
+
+    #[test]
+    fn blank_answers_keep_current_config() {
+        let config = DaemonConfig::default();
+        let answers = scripted("\n\n\n\n\n", &args(), &config);
+        assert_eq!(answers.server_url, config.server.url);
+        assert_eq!(answers.credential, Credential::Keep);
+        assert!(answers.routes.is_empty());
+        assert_eq!(answers.watch_paths, config.watchers.custom_paths);
+        assert_eq!(answers.privacy, PrivacyPreset::Standard);
+    }
+
+    #[test]
+    fn scripted_answers_fill_every_step() {
+        let config = DaemonConfig::default();
+        let input = "ftp://bad\nhttps://sessions.example.com/\n2\nosk_123\n~/work/**=acme\n~/.codex/sessions\nstrict\n";
+        let answers = scripted(input, &args(), &config);
+        assert_eq!(answers.server_url, "https://sessions.example.com");
+        assert_eq!(
+            answers.credential,
+            Credential::ApiKey("osk_123".to_string())
+        );
+        assert_eq!(answers.routes.len(), 1);
+        assert_eq!(answers.routes[0].team, "acme");
+        assert_eq!(answers.watch_paths, vec!["~/.codex/sessions".to_string()]);
+        assert_eq!(answers.privacy, PrivacyPreset::Strict);
+
+        let mut config = config;
+        answers.apply(&mut config);
+        assert_eq!(config.server.api_key, "osk_123");
+        assert!(
+            config
+                .privacy
+                .exclude_patterns
+                .contains(&"*.pem".to_string())
+        );
+    }
+
+    #[test]
+    fn flags_skip_their_prompts() {
+        let mut flags = args();
+        flags.api_key = Some("osk_flag".to_string());
+        flags.privacy = Some(PrivacyPreset::Open);
+        flags.watch_paths = vec!["~/sessions".to_string()];
+        let answers = scripted("\n-\n", &flags, &DaemonConfig::default());
+        assert_eq!(
+            answers.credential,
+            Credential::ApiKey("osk_flag".to_string())
+        );
+        assert_eq!(answers.watch_paths, vec!["~/sessions".to_string()]);
+        assert_eq!(answers.privacy, PrivacyPreset::Open);
+    }
+
+    #[test]
+    fn privacy_presets_round_trip_through_detect() {
+        for preset in [
+            PrivacyPreset::Strict,
+            PrivacyPreset::Standard,
+            PrivacyPreset::Open,
+        ] {
+            let mut privacy = PrivacySettings::default();
+            preset.apply(&mut privacy);
+            assert_eq!(PrivacyPreset::detect(&privacy), Some(preset));
+        }
+        let mut custom = PrivacySettings::default();
+        custom.exclude_patterns.push("*.sqlite".to_string());
+        assert_eq!(PrivacyPreset::detect(&custom), None);
+    }
+
+    #[test]
+    fn route_and_watch_path_answers_parse() {
+        assert!(parse_routes("-").expect("none").is_empty());
+        assert_eq!(parse_routes("~/a=x, ~/b/**=y").expect("routes").len(), 2);
+        assert!(parse_routes("~/a").is_err());
+        assert_eq!(parse_watch_paths("default"), default_watch_paths());
+        assert_eq!(parse_watch_paths(" a , b "), vec!["a", "b"]);
+    }
+}
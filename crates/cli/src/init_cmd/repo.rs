@@ -0,0 +1,277 @@
+//! `opensession init --repo`: per-repository onboarding.
+
+use crate::hooks::{HookInstallAction, HookType, install_hooks_with_report};
+use anyhow::{Context, Result};
+use opensession_runtime_config::RepoRegistryFile;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml_edit::{Array, DocumentMut, Item, Table, value};
+
+/// File names that usually hold secrets; each glob is also the exclude
+/// pattern suggested when a matching file is found.
+const SECRET_FILE_PATTERNS: &[&str] = &[
+    ".env",
+    ".env.*",
+    "*.pem",
+    "*.key",
+    "*.p12",
+    "*.pfx",
+    "*.jks",
+    "*.keystore",
+    "id_rsa*",
+    "id_ed25519*",
+    "*.tfvars",
+    ".npmrc",
+    ".pypirc",
+    ".netrc",
+    "*credentials*.json",
+    "service-account*.json",
+];
+
+/// Committed example files that only document the real ones.
+const EXAMPLE_SUFFIXES: &[&str] = &[".example", ".sample", ".template", ".dist"];
+
+const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    ".opensession",
+    "node_modules",
+    "target",
+    "vendor",
+    "dist",
+    "build",
+    ".venv",
+];
+
+const SCAN_DEPTH: usize = 4;
+
+pub(super) fn run() -> Result<()> {
+    let cwd = std::env::current_dir().context("read current directory")?;
+    let repo_root = opensession_git_native::ops::find_repo_root(&cwd)
+        .ok_or_else(|| anyhow::anyhow!("current directory is not inside a git repository"))?;
+
+    let hook_reports = install_hooks_with_report(&repo_root, HookType::all())?;
+    let detected = detect_secret_files(&repo_root);
+    let config_path = repo_root.join(".opensession").join("config.toml");
+    let added = add_exclude_patterns(&config_path, detected.keys().cloned())?;
+    let registry_path = opensession_paths::repo_registry_path()?;
+    let registered = register_repo(&registry_path, &repo_root)?;
+
+    println!("Initialized {}:", repo_root.display());
+    for report in &hook_reports {
+        let action = match report.action {
+            HookInstallAction::InstallNew => "installed",
+            HookInstallAction::ReplaceManaged => "refreshed",
+            HookInstallAction::BackupAndReplace => "installed (original kept)",
+        };
+        println!("  - hook {}: {action}", report.hook_type.filename());
+        if let Some(backup_path) = report
+            .backup_path
+            .as_ref()
+            .filter(|_| report.backup_created)
+        {
+            println!("    original hook saved as: {}", backup_path.display());
+        }
+    }
+    if detected.is_empty() {
+        println!("  - privacy: no secrets files found");
+    }
+    for (pattern, files) in &detected {
+        let state = if added.contains(pattern) {
+            "added"
+        } else {
+            "already excluded"
+        };
+        println!("  - privacy: {pattern} {state} ({})", files.join(", "));
+    }
+    if !added.is_empty() {
+        println!("    written to {}", config_path.display());
+    }
+    println!(
+        "  - daemon: {}",
+        if registered {
+            "repo registered"
+        } else {
+            "repo already registered"
+        }
+    );
+    Ok(())
+}
+
+/// Secrets files under `repo_root`, grouped by the pattern that matched.
+fn detect_secret_files(repo_root: &Path) -> BTreeMap<String, Vec<String>> {
+    let patterns: Vec<(&str, glob::Pattern)> = SECRET_FILE_PATTERNS
+        .iter()
+        .filter_map(|raw| Some((*raw, glob::Pattern::new(raw).ok()?)))
+        .collect();
+    let mut found: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut pending = vec![(repo_root.to_path_buf(), 0usize)];
+    while let Some((dir, depth)) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if depth + 1 < SCAN_DEPTH && !SKIPPED_DIRS.contains(&name.as_str()) {
+                    pending.push((entry.path(), depth + 1));
+                }
+                continue;
+            }
+            if EXAMPLE_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)) {
+                continue;
+            }
+            if let Some((raw, _)) = patterns.iter().find(|(_, pattern)| pattern.matches(&name)) {
+                let relative = entry
+                    .path()
+                    .strip_prefix(repo_root)
+                    .map(Path::to_path_buf)
+                    .unwrap_or_else(|_| PathBuf::from(&name));
+                found
+                    .entry(raw.to_string())
+                    .or_default()
+                    .push(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    for files in found.values_mut() {
+        files.sort();
+    }
+    found
+}
+
+/// Add `patterns` to `[privacy].exclude_patterns` in the project config,
+/// keeping the rest of the file as written. Returns the patterns added.
+fn add_exclude_patterns(
+    config_path: &Path,
+    patterns: impl IntoIterator<Item = String>,
+) -> Result<Vec<String>> {
+    let source = match std::fs::read_to_string(config_path) {
+        Ok(source) => source,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => {
+            return Err(err).with_context(|| format!("read {}", config_path.display()));
+        }
+    };
+    let mut document: DocumentMut = source
+        .parse()
+        .with_context(|| format!("parse {}", config_path.display()))?;
+    let privacy = document
+        .entry("privacy")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_mut()
+        .with_context(|| format!("`privacy` in {} is not a table", config_path.display()))?;
+    let existing = privacy
+        .entry("exclude_patterns")
+        .or_insert_with(|| value(Array::new()))
+        .as_array_mut()
+        .with_context(|| {
+            format!(
+                "`privacy.exclude_patterns` in {} is not an array",
+                config_path.display()
+            )
+        })?;
+
+    let mut added = Vec::new();
+    for pattern in patterns {
+        if existing.iter().any(|item| item.as_str() == Some(&pattern)) {
+            continue;
+        }
+        existing.push(pattern.as_str());
+        added.push(pattern);
+    }
+    if added.is_empty() {
+        return Ok(added);
+    }
+    if let Some(dir) = config_path.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+    }
+    std::fs::write(config_path, document.to_string())
+        .with_context(|| format!("write {}", config_path.display()))?;
+    Ok(added)
+}
+
+/// Add `repo_root` to the daemon's repo registry; `false` when already there.
+fn register_repo(registry_path: &Path, repo_root: &Path) -> Result<bool> {
+    let mut registry = RepoRegistryFile::read(registry_path)
+        .with_context(|| format!("read repo registry {}", registry_path.display()))?;
+    if !registry
+        .repos
+        .insert(repo_root.to_string_lossy().to_string())
+    {
+        return Ok(false);
+    }
+    registry
+        .write(registry_path)
+        .with_context(|| format!("write {}", registry_path.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_exclude_patterns, detect_secret_files, register_repo};
+
+    #[test]
+    fn secrets_files_map_to_exclude_patterns() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        for file in [
+            ".env",
+            ".env.example",
+            "deploy/certs/server.pem",
+            "node_modules/pkg/test.pem",
+            "src/main.rs",
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+            std::fs::write(path, "x").expect("write");
+        }
+
+        let found = detect_secret_files(root);
+        assert_eq!(
+            found.keys().map(String::as_str).collect::<Vec<_>>(),
+            ["*.pem", ".env"]
+        );
+        assert_eq!(found["*.pem"], ["deploy/certs/server.pem"]);
+    }
+
+    #[test]
+    fn exclude_patterns_merge_into_existing_project_config() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".opensession").join("config.toml");
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        std::fs::write(
+            &path,
+            "# team settings\nallow_public = false\n\n[privacy]\nexclude_patterns = [\"*.pem\"]\n",
+        )
+        .expect("write config");
+
+        let added = add_exclude_patterns(&path, ["*.pem".to_string(), ".env".to_string()])
+            .expect("merge patterns");
+        assert_eq!(added, [".env"]);
+        let written = std::fs::read_to_string(&path).expect("read config");
+        assert!(written.starts_with("# team settings\nallow_public = false\n"));
+        let parsed: toml::Value = toml::from_str(&written).expect("parse");
+        assert_eq!(
+            parsed["privacy"]["exclude_patterns"],
+            toml::Value::Array(vec!["*.pem".into(), ".env".into()])
+        );
+
+        assert!(
+            add_exclude_patterns(&path, [".env".to_string()])
+                .expect("no-op")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn repos_register_once() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let registry = dir.path().join("repo-registry.json");
+        let repo = dir.path().join("app");
+        assert!(register_repo(&registry, &repo).expect("first"));
+        assert!(!register_repo(&registry, &repo).expect("second"));
+    }
+}
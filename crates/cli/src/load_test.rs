@@ -0,0 +1,408 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use opensession_api::{
+    BatchUploadRequest, MAX_BATCH_UPLOAD_SESSIONS, SessionListQuery, UploadRequest,
+};
+use opensession_api_client::ApiClient;
+use opensession_core::testing::SessionGenerator;
+use opensession_core::{Agent, Session};
+use serde::Serialize;
+
+use crate::user_guidance::guided_error;
+
+#[derive(Debug, Clone, Args)]
+pub struct TestArgs {
+    #[command(subcommand)]
+    pub action: TestAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum TestAction {
+    /// Upload synthetic sessions and query the list concurrently, reporting latency percentiles.
+    Load(LoadArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct LoadArgs {
+    /// Target server base URL. Required so a load run never hits a server by accident.
+    #[arg(long)]
+    pub url: String,
+    /// API key for uploads (defaults to `[server].api_key` of the active profile).
+    #[arg(long)]
+    pub api_key: Option<String>,
+    /// Number of synthetic sessions to upload.
+    #[arg(long, default_value_t = 100)]
+    pub sessions: usize,
+    /// Events per synthetic session.
+    #[arg(long, default_value_t = 50)]
+    pub events: usize,
+    /// Approximate text bytes per event.
+    #[arg(long, default_value_t = 512)]
+    pub event_bytes: usize,
+    /// Sessions per `POST /api/sessions/batch` request.
+    #[arg(long, default_value_t = 1)]
+    pub batch_size: usize,
+    /// Number of `GET /api/sessions` requests to issue.
+    #[arg(long, default_value_t = 200)]
+    pub list_queries: usize,
+    /// Requests in flight at once, per phase.
+    #[arg(long, default_value_t = 8)]
+    pub concurrency: usize,
+    /// Print JSON instead of a table.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct LoadReport {
+    target: String,
+    sessions: usize,
+    events_per_session: usize,
+    event_bytes: usize,
+    concurrency: usize,
+    upload: PhaseReport,
+    list: PhaseReport,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct PhaseReport {
+    requests: usize,
+    errors: usize,
+    elapsed_ms: u64,
+    requests_per_sec: f64,
+    p50_ms: f64,
+    p90_ms: f64,
+    p99_ms: f64,
+    max_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_error: Option<String>,
+}
+
+struct Sample {
+    latency: Duration,
+    error: Option<String>,
+}
+
+pub async fn run(args: TestArgs) -> Result<()> {
+    match args.action {
+        TestAction::Load(args) => run_load(args).await,
+    }
+}
+
+async fn run_load(args: LoadArgs) -> Result<()> {
+    if args.batch_size == 0 || args.batch_size > MAX_BATCH_UPLOAD_SESSIONS {
+        bail!("--batch-size must be between 1 and {MAX_BATCH_UPLOAD_SESSIONS}");
+    }
+    let concurrency = args.concurrency.max(1);
+    let client = Arc::new(load_client(&args)?);
+    client
+        .health()
+        .await
+        .with_context(|| format!("target {} is not healthy", args.url))?;
+
+    let run_id = uuid::Uuid::new_v4().simple().to_string();
+    let batches: Vec<usize> = (0..args.sessions)
+        .step_by(args.batch_size)
+        .map(|start| args.batch_size.min(args.sessions - start))
+        .collect();
+    let batches = Arc::new(batches);
+    let upload = run_phase(batches.len(), concurrency, {
+        let client = client.clone();
+        let run_id = run_id.clone();
+        let (events, event_bytes) = (args.events, args.event_bytes);
+        move |idx| {
+            let client = client.clone();
+            let batches = batches.clone();
+            let run_id = run_id.clone();
+            async move {
+                let sessions = (0..batches[idx])
+                    .map(|n| synthetic_session(&run_id, idx * 1000 + n, events, event_bytes))
+                    .map(upload_request)
+                    .collect();
+                let response = client
+                    .upload_sessions_batch(&BatchUploadRequest { sessions })
+                    .await
+                    .map_err(|err| err.to_string())?;
+                match response.results.iter().find_map(|item| item.error.clone()) {
+                    Some(error) => Err(error),
+                    None => Ok(()),
+                }
+            }
+        }
+    })
+    .await;
+
+    let list = run_phase(args.list_queries, concurrency, {
+        let client = client.clone();
+        move |idx| {
+            let client = client.clone();
+            async move {
+                client
+                    .list_sessions(&list_query(idx))
+                    .await
+                    .map(|_| ())
+                    .map_err(|err| err.to_string())
+            }
+        }
+    })
+    .await;
+
+    let report = LoadReport {
+        target: args.url.clone(),
+        sessions: args.sessions,
+        events_per_session: args.events,
+        event_bytes: args.event_bytes,
+        concurrency,
+        upload,
+        list,
+    };
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(())
+}
+
+fn load_client(args: &LoadArgs) -> Result<ApiClient> {
+    let api_key = match args.api_key.as_deref() {
+        Some(key) => key.trim().to_string(),
+        None => {
+            let runtime = crate::runtime_settings::load_runtime_config()?;
+            crate::runtime_settings::active_server_settings(&runtime.server)?
+                .api_key
+                .trim()
+                .to_string()
+        }
+    };
+    if api_key.is_empty() {
+        return Err(guided_error(
+            "no API key for the load test",
+            [
+                "pass --api-key osk_... for an account on the target server",
+                "or set `api_key` under `[server]` in opensession.toml",
+            ],
+        ));
+    }
+    let mut client =
+        ApiClient::new(&args.url, Duration::from_secs(60)).context("create server client")?;
+    client.set_auth(api_key);
+    Ok(client)
+}
+
+/// Run `total` requests with at most `concurrency` in flight, timing each one.
+async fn run_phase<F, Fut>(total: usize, concurrency: usize, request: F) -> PhaseReport
+where
+    F: Fn(usize) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = std::result::Result<(), String>> + Send,
+{
+    let request = Arc::new(request);
+    let next = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+    let workers: Vec<_> = (0..concurrency.min(total))
+        .map(|_| {
+            let request = request.clone();
+            let next = next.clone();
+            tokio::spawn(async move {
+                let mut samples = Vec::new();
+                loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    if idx >= total {
+                        break samples;
+                    }
+                    let begin = Instant::now();
+                    let error = request(idx).await.err();
+                    samples.push(Sample {
+                        latency: begin.elapsed(),
+                        error,
+                    });
+                }
+            })
+        })
+        .collect();
+
+    let mut samples = Vec::with_capacity(total);
+    for worker in workers {
+        match worker.await {
+            Ok(batch) => samples.extend(batch),
+            Err(err) => samples.push(Sample {
+                latency: Duration::ZERO,
+                error: Some(format!("worker task failed: {err}")),
+            }),
+        }
+    }
+    summarize(&samples, started.elapsed())
+}
+
+fn summarize(samples: &[Sample], elapsed: Duration) -> PhaseReport {
+    let mut latencies: Vec<f64> = samples
+        .iter()
+        .map(|sample| sample.latency.as_secs_f64() * 1000.0)
+        .collect();
+    latencies.sort_by(f64::total_cmp);
+    let elapsed_secs = elapsed.as_secs_f64();
+    PhaseReport {
+        requests: samples.len(),
+        errors: samples
+            .iter()
+            .filter(|sample| sample.error.is_some())
+            .count(),
+        elapsed_ms: elapsed.as_millis() as u64,
+        requests_per_sec: if elapsed_secs > 0.0 {
+            samples.len() as f64 / elapsed_secs
+        } else {
+            0.0
+        },
+        p50_ms: percentile(&latencies, 50.0),
+        p90_ms: percentile(&latencies, 90.0),
+        p99_ms: percentile(&latencies, 99.0),
+        max_ms: latencies.last().copied().unwrap_or(0.0),
+        first_error: samples.iter().find_map(|sample| sample.error.clone()),
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn print_report(report: &LoadReport) {
+    println!(
+        "target {}  sessions {} x {} events (~{} B/event)  concurrency {}",
+        report.target,
+        report.sessions,
+        report.events_per_session,
+        report.event_bytes,
+        report.concurrency
+    );
+    println!(
+        "{:<8} {:>8} {:>7} {:>9} {:>9} {:>9} {:>9} {:>9}",
+        "phase", "requests", "errors", "req/s", "p50 ms", "p90 ms", "p99 ms", "max ms"
+    );
+    for (name, phase) in [("upload", &report.upload), ("list", &report.list)] {
+        println!(
+            "{name:<8} {:>8} {:>7} {:>9.1} {:>9.1} {:>9.1} {:>9.1} {:>9.1}",
+            phase.requests,
+            phase.errors,
+            phase.requests_per_sec,
+            phase.p50_ms,
+            phase.p90_ms,
+            phase.p99_ms,
+            phase.max_ms
+        );
+        if let Some(error) = &phase.first_error {
+            println!("  first {name} error: {error}");
+        }
+    }
+}
+
+/// Alternate plain, searched and filtered list queries so the index paths all get traffic.
+fn list_query(idx: usize) -> SessionListQuery {
+    SessionListQuery {
+        page: 1,
+        per_page: 20,
+        cursor: None,
+        limit: Some(20),
+        search: (idx % 3 == 1).then(|| "load test".to_string()),
+        tool: (idx % 3 == 2).then(|| "opensession-load".to_string()),
+        git_repo_name: None,
+        sort: None,
+        time_range: None,
+        model: None,
+        since: None,
+        before: None,
+        touches: None,
+        has_errors: None,
+        git_commit: None,
+    }
+}
+
+fn upload_request(session: Session) -> UploadRequest {
+    UploadRequest {
+        session,
+        body_url: None,
+        linked_session_ids: None,
+        git_remote: None,
+        git_branch: None,
+        git_commit: None,
+        git_repo_name: Some("opensession-load/synthetic".to_string()),
+        pr_number: None,
+        pr_url: None,
+        score_plugin: None,
+        force: true,
+        signature: None,
+    }
+}
+
+/// Realistic session (tool calls, results, some errors) unique to this run and index.
+fn synthetic_session(run_id: &str, seq: usize, events: usize, event_bytes: usize) -> Session {
+    let seed = run_id
+        .bytes()
+        .fold(seq as u64, |acc, byte| acc.rotate_left(5) ^ u64::from(byte));
+    let mut session = SessionGenerator::new(seed)
+        .events(events)
+        .text_bytes(event_bytes)
+        .error_rate(0.05)
+        .agent(Agent {
+            provider: "synthetic".to_string(),
+            model: "load-test".to_string(),
+            tool: "opensession-load".to_string(),
+            tool_version: None,
+        })
+        .start(chrono::Utc::now())
+        .generate();
+    // Seeds may collide across runs; ids and titles must not.
+    session.session_id = uuid::Uuid::new_v4().to_string();
+    session.context.title = Some(format!("load test {run_id} #{seq}"));
+    session
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Sample, percentile, summarize, synthetic_session};
+    use std::time::Duration;
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let sorted: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(percentile(&sorted, 50.0), 50.0);
+        assert_eq!(percentile(&sorted, 99.0), 99.0);
+        assert_eq!(percentile(&sorted, 100.0), 100.0);
+        assert_eq!(percentile(&[7.0], 90.0), 7.0);
+        assert_eq!(percentile(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn summarize_counts_errors_and_orders_latencies() {
+        let samples = [30, 10, 20]
+            .into_iter()
+            .map(|ms| Sample {
+                latency: Duration::from_millis(ms),
+                error: (ms == 20).then(|| "boom".to_string()),
+            })
+            .collect::<Vec<_>>();
+        let report = summarize(&samples, Duration::from_secs(1));
+        assert_eq!(report.requests, 3);
+        assert_eq!(report.errors, 1);
+        assert_eq!(report.p50_ms, 20.0);
+        assert_eq!(report.max_ms, 30.0);
+        assert_eq!(report.first_error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn synthetic_sessions_have_requested_shape() {
+        let session = synthetic_session("run", 3, 4, 100);
+        assert_eq!(session.events.len(), 4);
+        assert_eq!(session.stats.event_count, 4);
+        let other = synthetic_session("run", 4, 4, 100);
+        assert_ne!(session.context.title, other.context.title);
+    }
+}
@@ -1,9 +1,34 @@
+use opensession_runtime_config::UiLanguage;
+use std::sync::OnceLock;
+
+/// Overrides `[ui] language` and the system locale, e.g. `OPENSESSION_LANG=ko`.
+pub(crate) const LANG_ENV: &str = "OPENSESSION_LANG";
+
+static CONFIGURED_LANGUAGE: OnceLock<UiLanguage> = OnceLock::new();
+
+/// Record `[ui] language` from `opensession.toml`.
+pub(crate) fn set_configured_language(language: Option<UiLanguage>) {
+    if let Some(language) = language {
+        let _ = CONFIGURED_LANGUAGE.set(language);
+    }
+}
+
+/// `OPENSESSION_LANG`, then `[ui] language`, then `LC_ALL` / `LC_MESSAGES` / `LANG`.
 pub(crate) fn is_korean() -> bool {
+    let language = std::env::var(LANG_ENV)
+        .ok()
+        .and_then(|value| UiLanguage::from_tag(&value))
+        .or_else(|| CONFIGURED_LANGUAGE.get().copied())
+        .or_else(system_language);
+    language == Some(UiLanguage::Ko)
+}
+
+fn system_language() -> Option<UiLanguage> {
     ["LC_ALL", "LC_MESSAGES", "LANG"]
         .into_iter()
         .filter_map(|key| std::env::var(key).ok())
-        .map(|value| value.trim().to_ascii_lowercase())
-        .any(|value| value == "ko" || value.starts_with("ko_") || value.starts_with("ko-"))
+        .find(|value| !value.trim().is_empty())
+        .and_then(|value| UiLanguage::from_tag(&value))
 }
 
 pub(crate) fn localize<'a>(en: &'a str, ko: &'a str) -> &'a str {
@@ -12,7 +37,7 @@ pub(crate) fn localize<'a>(en: &'a str, ko: &'a str) -> &'a str {
 
 #[cfg(test)]
 mod tests {
-    use super::is_korean;
+    use super::{LANG_ENV, is_korean};
     use std::sync::{Mutex, OnceLock};
 
     fn env_lock() -> &'static Mutex<()> {
@@ -24,14 +49,26 @@ mod tests {
     fn detects_korean_lang_prefixes() {
         let _guard = env_lock().lock().expect("lock env");
         let original = std::env::var("LANG").ok();
+        // SAFETY: tests serialize environment mutation with `env_lock`.
         unsafe {
             std::env::set_var("LANG", "ko_KR.UTF-8");
         }
         assert!(is_korean());
+        // SAFETY: tests serialize environment mutation with `env_lock`.
+        unsafe {
+            std::env::set_var(LANG_ENV, "en");
+        }
+        assert!(!is_korean());
+        // SAFETY: tests serialize environment mutation with `env_lock`.
+        unsafe {
+            std::env::remove_var(LANG_ENV);
+        }
         match original {
+            // SAFETY: tests serialize environment mutation with `env_lock`.
             Some(value) => unsafe {
                 std::env::set_var("LANG", value);
             },
+            // SAFETY: tests serialize environment mutation with `env_lock`.
             None => unsafe {
                 std::env::remove_var("LANG");
             },
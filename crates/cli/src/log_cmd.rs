@@ -1,6 +1,14 @@
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use chrono::{Duration, Utc};
+use clap::{Args, ValueEnum};
+use opensession_core::issue_refs::normalize_issue_ref;
+use opensession_local_db::environment::SessionEnvironment;
 use opensession_local_db::{LocalDb, LocalSessionRow, LogFilter};
+use opensession_runtime_config::IssueSettings;
+
+use crate::user_guidance::guided_error;
 
 /// All available JSON fields for --json selection.
 const AVAILABLE_JSON_FIELDS: &[&str] = &[
@@ -24,26 +32,64 @@ const AVAILABLE_JSON_FIELDS: &[&str] = &[
     "git_branch",
     "git_commit",
     "tags",
+    "issue_refs",
+    "issue_urls",
+    "environment",
 ];
 
+#[derive(Debug, Clone, Args)]
+pub struct LogArgs {
+    /// Only sessions created after this time (`3 hours ago`, `2 days`, `yesterday`, ISO8601).
+    #[arg(long)]
+    pub since: Option<String>,
+    /// Only sessions created before this time.
+    #[arg(long)]
+    pub before: Option<String>,
+    /// Only sessions from this tool (exact match, e.g. `codex`).
+    #[arg(long)]
+    pub tool: Option<String>,
+    /// Only sessions whose model matches this pattern (`*` wildcards).
+    #[arg(long)]
+    pub model: Option<String>,
+    /// Only sessions that modified this file.
+    #[arg(long)]
+    pub touches: Option<String>,
+    /// Search titles, descriptions and tags.
+    #[arg(long)]
+    pub grep: Option<String>,
+    /// Only sessions with errors.
+    #[arg(long)]
+    pub has_errors: bool,
+    /// Only sessions under this working directory (defaults to the current repo).
+    #[arg(long)]
+    pub project: Option<String>,
+    /// Only sessions mentioning this issue (`PROJ-123`, `#456`).
+    #[arg(long)]
+    pub issue: Option<String>,
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    pub format: LogFormat,
+    #[arg(long, default_value_t = 20)]
+    pub limit: u32,
+    /// Print selected JSON fields (comma-separated); without a value, list the fields.
+    #[arg(long, num_args = 0..=1, default_missing_value = "")]
+    pub json: Option<String>,
+    /// Filter JSON output with a jq expression.
+    #[arg(long)]
+    pub jq: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    Text,
+    Json,
+    /// NDJSON stream: each line is an independent JSON envelope
+    Stream,
+}
+
 /// Run the `log` command.
-#[allow(clippy::too_many_arguments)]
-pub fn run_log(
-    since: Option<&str>,
-    before: Option<&str>,
-    tool: Option<&str>,
-    model: Option<&str>,
-    touches: Option<&str>,
-    grep: Option<&str>,
-    has_errors: bool,
-    project: Option<&str>,
-    format: &crate::output::OutputFormat,
-    limit: u32,
-    json_fields: Option<&str>,
-    jq_filter: Option<&str>,
-) -> Result<()> {
+pub fn run(args: LogArgs) -> Result<()> {
     // Handle --json with no value: list available fields
-    if let Some(fields) = json_fields {
+    if let Some(fields) = args.json.as_deref() {
         if fields.is_empty() {
             println!("Available fields for --json:");
             for field in AVAILABLE_JSON_FIELDS {
@@ -53,15 +99,33 @@ pub fn run_log(
         }
     }
 
+    let issue = args
+        .issue
+        .as_deref()
+        .map(|raw| {
+            normalize_issue_ref(raw).ok_or_else(|| {
+                guided_error(
+                    format!("not an issue reference: {raw}"),
+                    ["use a Jira key like PROJ-123 or a GitHub number like #456"],
+                )
+            })
+        })
+        .transpose()?;
+    let issues = crate::runtime_settings::load_runtime_config()?.issues;
+
     let db = LocalDb::open()?;
 
-    let since_iso = since.map(parse_relative_time).transpose()?;
-    let before_iso = before.map(parse_relative_time).transpose()?;
+    let since_iso = args.since.as_deref().map(parse_relative_time).transpose()?;
+    let before_iso = args
+        .before
+        .as_deref()
+        .map(parse_relative_time)
+        .transpose()?;
 
     // Auto-detect project from CWD if no explicit project filter.
     // Prefer git_repo_name (more robust), fall back to working_directory.
-    let (working_dir, repo_name) = if project.is_some() {
-        (project.map(String::from), None)
+    let (working_dir, repo_name) = if args.project.is_some() {
+        (args.project.clone(), None)
     } else {
         let repo = detect_git_repo_name();
         if repo.is_some() {
@@ -72,16 +136,17 @@ pub fn run_log(
     };
 
     let filter = LogFilter {
-        tool: tool.map(String::from),
-        model: model.map(String::from),
+        tool: args.tool.clone(),
+        model: args.model.clone(),
         since: since_iso,
         before: before_iso,
-        touches: touches.map(String::from),
-        grep: grep.map(String::from),
-        has_errors: if has_errors { Some(true) } else { None },
+        touches: args.touches.clone(),
+        grep: args.grep.clone(),
+        has_errors: if args.has_errors { Some(true) } else { None },
         working_directory: working_dir,
         git_repo_name: repo_name,
-        limit: Some(limit),
+        issue,
+        limit: Some(args.limit),
         ..Default::default()
     };
 
@@ -92,13 +157,15 @@ pub fn run_log(
         return Ok(());
     }
 
+    let jq_filter = args.jq.as_deref();
+
     // --json overrides --format
-    if let Some(fields) = json_fields {
+    if let Some(fields) = args.json.as_deref() {
         let selected: Vec<&str> = fields.split(',').map(str::trim).collect();
         let entries: Vec<serde_json::Value> = sessions
             .iter()
             .map(|s| {
-                let full = session_to_full_json(s);
+                let full = session_to_full_json(s, &issues);
                 select_json_fields(&full, &selected)
             })
             .collect();
@@ -106,17 +173,19 @@ pub fn run_log(
         return apply_jq_filter(&json_str, jq_filter);
     }
 
-    match format {
-        crate::output::OutputFormat::Json => {
-            let entries: Vec<serde_json::Value> =
-                sessions.iter().map(session_to_full_json).collect();
+    match args.format {
+        LogFormat::Json => {
+            let entries: Vec<serde_json::Value> = sessions
+                .iter()
+                .map(|s| session_to_full_json(s, &issues))
+                .collect();
             let json_str = serde_json::to_string_pretty(&entries)?;
             apply_jq_filter(&json_str, jq_filter)?;
         }
-        crate::output::OutputFormat::Stream => {
+        LogFormat::Stream => {
             // Enveloped NDJSON — each line is a self-contained envelope
             for s in &sessions {
-                let data = session_to_full_json(s);
+                let data = session_to_full_json(s, &issues);
                 let title = s.title.as_deref().unwrap_or("(untitled)");
                 let envelope = crate::output::OutputEnvelope::new(
                     "session_log",
@@ -127,18 +196,18 @@ pub fn run_log(
                 println!("{json}");
             }
         }
-        _ => {
+        LogFormat::Text => {
             if jq_filter.is_some() {
                 anyhow::bail!("--jq requires --format json, --format stream, or --json");
             }
-            print_text(&sessions);
+            print_text(&sessions, &issues);
         }
     }
 
     Ok(())
 }
 
-fn print_text(sessions: &[LocalSessionRow]) {
+fn print_text(sessions: &[LocalSessionRow], issues: &IssueSettings) {
     for s in sessions {
         let title = s
             .title
@@ -177,36 +246,107 @@ fn print_text(sessions: &[LocalSessionRow]) {
             }
         }
 
+        let refs = issue_refs(s);
+        if !refs.is_empty() {
+            let display: Vec<String> = refs
+                .iter()
+                .map(|issue_ref| match issues.url_for(issue_ref) {
+                    Some(url) => format!("{issue_ref} <{url}>"),
+                    None => issue_ref.to_string(),
+                })
+                .collect();
+            println!("    issues: {}", display.join(", "));
+        }
+
+        if let Some(environment) = session_environment(s) {
+            println!("    env: {}", environment.summary());
+        }
+
         println!();
     }
 
     println!("\x1b[2mShowing {} session(s)\x1b[0m", sessions.len());
 }
 
-fn session_to_full_json(s: &LocalSessionRow) -> serde_json::Value {
-    serde_json::json!({
-        "id": s.id,
-        "tool": s.tool,
-        "model": s.agent_model,
-        "title": s.title,
-        "description": s.description,
-        "created_at": s.created_at,
-        "duration_seconds": s.duration_seconds,
-        "message_count": s.message_count,
-        "event_count": s.event_count,
-        "total_input_tokens": s.total_input_tokens,
-        "total_output_tokens": s.total_output_tokens,
-        "has_errors": s.has_errors,
-        "files_modified": s.files_modified.as_deref()
-            .and_then(|f| serde_json::from_str::<Vec<String>>(f).ok()),
-        "working_directory": s.working_directory,
-        "git_repo_name": s.git_repo_name,
-        "source_path": s.source_path,
-        "git_remote": s.git_remote,
-        "git_branch": s.git_branch,
-        "git_commit": s.git_commit,
-        "tags": s.tags,
-    })
+fn issue_refs(s: &LocalSessionRow) -> Vec<&str> {
+    s.issue_refs
+        .as_deref()
+        .map(|refs| refs.split(',').filter(|r| !r.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+fn session_environment(s: &LocalSessionRow) -> Option<SessionEnvironment> {
+    serde_json::from_str(s.environment.as_deref()?).ok()
+}
+
+/// One session row as emitted by `log --format json` and `--json`.
+#[derive(Debug, serde::Serialize)]
+struct SessionLogEntry<'a> {
+    id: &'a str,
+    tool: &'a str,
+    model: Option<&'a str>,
+    title: Option<&'a str>,
+    description: Option<&'a str>,
+    created_at: &'a str,
+    duration_seconds: i64,
+    message_count: i64,
+    event_count: i64,
+    total_input_tokens: i64,
+    total_output_tokens: i64,
+    has_errors: bool,
+    files_modified: Option<Vec<String>>,
+    working_directory: Option<&'a str>,
+    git_repo_name: Option<&'a str>,
+    source_path: Option<&'a str>,
+    git_remote: Option<&'a str>,
+    git_branch: Option<&'a str>,
+    git_commit: Option<&'a str>,
+    tags: Option<&'a str>,
+    issue_refs: Vec<&'a str>,
+    issue_urls: BTreeMap<&'a str, String>,
+    environment: Option<SessionEnvironment>,
+}
+
+impl<'a> SessionLogEntry<'a> {
+    fn new(s: &'a LocalSessionRow, issues: &IssueSettings) -> Self {
+        let issue_refs = issue_refs(s);
+        let issue_urls = issue_refs
+            .iter()
+            .filter_map(|issue_ref| Some((*issue_ref, issues.url_for(issue_ref)?)))
+            .collect();
+        Self {
+            id: &s.id,
+            tool: &s.tool,
+            model: s.agent_model.as_deref(),
+            title: s.title.as_deref(),
+            description: s.description.as_deref(),
+            created_at: &s.created_at,
+            duration_seconds: s.duration_seconds,
+            message_count: s.message_count,
+            event_count: s.event_count,
+            total_input_tokens: s.total_input_tokens,
+            total_output_tokens: s.total_output_tokens,
+            has_errors: s.has_errors,
+            files_modified: s
+                .files_modified
+                .as_deref()
+                .and_then(|f| serde_json::from_str(f).ok()),
+            working_directory: s.working_directory.as_deref(),
+            git_repo_name: s.git_repo_name.as_deref(),
+            source_path: s.source_path.as_deref(),
+            git_remote: s.git_remote.as_deref(),
+            git_branch: s.git_branch.as_deref(),
+            git_commit: s.git_commit.as_deref(),
+            tags: s.tags.as_deref(),
+            issue_refs,
+            issue_urls,
+            environment: session_environment(s),
+        }
+    }
+}
+
+fn session_to_full_json(s: &LocalSessionRow, issues: &IssueSettings) -> serde_json::Value {
+    serde_json::to_value(SessionLogEntry::new(s, issues)).unwrap_or_default()
 }
 
 /// Apply a jq filter to JSON output.
@@ -450,7 +590,7 @@ fn format_tokens(input: i64, output: i64) -> String {
 }
 
 /// Detect project directory from CWD by finding the git root.
-fn detect_project_dir() -> Option<String> {
+pub(crate) fn detect_project_dir() -> Option<String> {
     let cwd = std::env::current_dir().ok()?;
     // Walk up to find .git directory
     let mut dir = cwd.as_path();
@@ -463,7 +603,7 @@ fn detect_project_dir() -> Option<String> {
 }
 
 /// Detect git repo name from CWD's git remote.
-fn detect_git_repo_name() -> Option<String> {
+pub(crate) fn detect_git_repo_name() -> Option<String> {
     let output = std::process::Command::new("git")
         .args(["remote", "get-url", "origin"])
         .output()
@@ -690,13 +830,16 @@ mod tests {
             has_errors: false,
             max_active_agents: 1,
             is_auxiliary: false,
+            session_score: 0,
+            issue_refs: Some("PROJ-1,#2".to_string()),
+            environment: Some(r#"{"os":"linux","arch":"x86_64","git_dirty":true}"#.to_string()),
         }
     }
 
     #[test]
     fn test_session_to_full_json_all_fields() {
         let row = make_test_row();
-        let json = session_to_full_json(&row);
+        let json = session_to_full_json(&row, &IssueSettings::default());
         assert_eq!(json["id"], "abc-123");
         assert_eq!(json["tool"], "claude-code");
         assert_eq!(json["model"], "opus");
@@ -706,6 +849,26 @@ mod tests {
         assert_eq!(json["total_input_tokens"], 5000);
         assert_eq!(json["has_errors"], false);
         assert_eq!(json["git_repo_name"], "user/repo");
+        assert_eq!(
+            json["environment"],
+            serde_json::json!({"os": "linux", "arch": "x86_64", "git_dirty": true})
+        );
+    }
+
+    #[test]
+    fn test_session_to_full_json_links_configured_issues() {
+        let row = make_test_row();
+        let mut issues = IssueSettings::default();
+        issues.url_templates.insert(
+            "PROJ".to_string(),
+            "https://jira.example/browse/{ref}".to_string(),
+        );
+        let json = session_to_full_json(&row, &issues);
+        assert_eq!(json["issue_refs"], serde_json::json!(["PROJ-1", "#2"]));
+        assert_eq!(
+            json["issue_urls"],
+            serde_json::json!({"PROJ-1": "https://jira.example/browse/PROJ-1"})
+        );
     }
 
     #[test]
@@ -714,7 +877,7 @@ mod tests {
         row.title = None;
         row.agent_model = None;
         row.git_remote = None;
-        let json = session_to_full_json(&row);
+        let json = session_to_full_json(&row, &IssueSettings::default());
         assert!(json["title"].is_null());
         assert!(json["model"].is_null());
         assert!(json["git_remote"].is_null());
@@ -723,7 +886,7 @@ mod tests {
     #[test]
     fn test_session_to_full_json_files_modified_parsed() {
         let row = make_test_row();
-        let json = session_to_full_json(&row);
+        let json = session_to_full_json(&row, &IssueSettings::default());
         let files = json["files_modified"].as_array().unwrap();
         assert_eq!(files.len(), 2);
         assert_eq!(files[0], "src/main.rs");
@@ -734,7 +897,7 @@ mod tests {
     fn test_session_to_full_json_files_modified_invalid() {
         let mut row = make_test_row();
         row.files_modified = Some("not valid json".to_string());
-        let json = session_to_full_json(&row);
+        let json = session_to_full_json(&row, &IssueSettings::default());
         // Invalid JSON → null (from_str fails, and_then returns None)
         assert!(json["files_modified"].is_null());
     }
@@ -1,22 +1,44 @@
+mod account_cmd;
+mod archive_cmd;
+mod attach_cmd;
+mod cache_cmd;
 mod cat_cmd;
+mod clean_cmd;
 mod cleanup_cmd;
 mod cli_args;
 mod config_cmd;
+mod continue_cmd;
 mod docs_cmd;
 mod doctor_cmd;
 mod entrypoint;
 mod handoff_v1;
 mod hooks;
+mod init_cmd;
 mod inspect;
+#[cfg(feature = "e2e")]
+mod load_test;
 mod locale;
+mod log_cmd;
+mod meta_cmd;
 mod open_target;
+mod output;
 mod parse_cmd;
+mod playbook_cmd;
+mod plugins_cmd;
 mod register;
 mod review;
 mod runtime_settings;
+mod session_bookmarks;
+mod session_changes;
+mod session_cmd;
+mod session_commands;
+mod session_patch;
 mod setup_cmd;
 mod share;
+mod shell_history_cmd;
+mod statusline_cmd;
 mod summary_cmd;
+mod tool_launcher;
 mod url_opener;
 mod user_guidance;
 mod view;
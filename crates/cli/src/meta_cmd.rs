@@ -0,0 +1,128 @@
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use opensession_api::{MAX_METADATA_SYNC_ENTRIES, SyncMetadataRequest};
+use opensession_local_db::LocalDb;
+
+#[derive(Debug, Clone, Args)]
+pub struct MetaArgs {
+    #[command(subcommand)]
+    pub action: MetaAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum MetaAction {
+    /// Set a value, e.g. `bookmark:<session-id>` or `filter:<name>`.
+    Set { key: String, value: String },
+    /// Print a value.
+    Get { key: String },
+    /// List entries, optionally only keys starting with a prefix.
+    List {
+        #[arg(long, default_value = "")]
+        prefix: String,
+        /// Print JSON instead of `key<TAB>value` lines.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Delete an entry (the deletion syncs like any other write).
+    Rm { key: String },
+    /// Push local changes to the server and pull changes from other machines.
+    Sync,
+}
+
+pub async fn run(args: MetaArgs) -> Result<()> {
+    let db = LocalDb::open().context("open local db")?;
+    match args.action {
+        MetaAction::Set { key, value } => {
+            let key = normalize_key(&key)?;
+            db.set_metadata(key, Some(&value), now_ms())
+                .context("write metadata")?;
+            Ok(())
+        }
+        MetaAction::Get { key } => {
+            let key = normalize_key(&key)?;
+            match db.get_metadata(key).context("read metadata")? {
+                Some(value) => {
+                    println!("{value}");
+                    Ok(())
+                }
+                None => anyhow::bail!("metadata not found: {key}"),
+            }
+        }
+        MetaAction::List { prefix, json } => {
+            let entries = db.list_metadata(&prefix).context("list metadata")?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            } else {
+                for entry in entries {
+                    println!("{}\t{}", entry.key, entry.value.unwrap_or_default());
+                }
+            }
+            Ok(())
+        }
+        MetaAction::Rm { key } => {
+            let key = normalize_key(&key)?;
+            if db.get_metadata(key).context("read metadata")?.is_none() {
+                anyhow::bail!("metadata not found: {key}");
+            }
+            db.set_metadata(key, None, now_ms())
+                .context("delete metadata")?;
+            Ok(())
+        }
+        MetaAction::Sync => run_sync(&db).await,
+    }
+}
+
+async fn run_sync(db: &LocalDb) -> Result<()> {
+    let runtime = crate::runtime_settings::load_runtime_config()?;
+    let client = crate::handoff_v1::server_client(&runtime)?;
+
+    let dirty = db.list_dirty_metadata().context("list pending metadata")?;
+    let mut remote = None;
+    for chunk in dirty.chunks(MAX_METADATA_SYNC_ENTRIES) {
+        let merged = client
+            .sync_metadata(&SyncMetadataRequest {
+                entries: chunk.to_vec(),
+            })
+            .await
+            .context("push metadata")?;
+        remote = Some(merged.entries);
+    }
+    let remote = match remote {
+        Some(entries) => entries,
+        None => {
+            client
+                .list_metadata()
+                .await
+                .context("fetch metadata")?
+                .entries
+        }
+    };
+    let updated = db
+        .merge_remote_metadata(&remote)
+        .context("merge server metadata")?;
+    println!("metadata synced: pushed {}, pulled {updated}", dirty.len());
+    Ok(())
+}
+
+fn normalize_key(raw: &str) -> Result<&str> {
+    let key = raw.trim();
+    if key.is_empty() {
+        anyhow::bail!("metadata key must not be empty");
+    }
+    Ok(key)
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_key;
+
+    #[test]
+    fn keys_are_trimmed_and_must_not_be_blank() {
+        assert_eq!(normalize_key("  bookmark:s1 ").ok(), Some("bookmark:s1"));
+        assert!(normalize_key("   ").is_err());
+    }
+}
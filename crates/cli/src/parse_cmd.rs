@@ -1,8 +1,12 @@
 use crate::user_guidance::guided_error;
 use anyhow::{Context, Result};
 use clap::Args;
+use opensession_core::jsonl::{JsonlError, write_jsonl};
+use opensession_core::truncate::{side_file_path, to_side_jsonl, truncate_session};
 use opensession_core::validate::validate_session;
-use opensession_parsers::{ParseError, ParserRegistry};
+use opensession_parsers::plugin::find_plugin;
+use opensession_parsers::{ParseError, ParsePreview, ParserRegistry};
+use std::io::BufWriter;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Args)]
@@ -10,7 +14,7 @@ use std::path::PathBuf;
   opensession parse --profile codex ./raw-session.jsonl --preview
   opensession parse --profile codex ./raw-session.jsonl --out ./session.hail.jsonl")]
 pub struct ParseArgs {
-    /// Parser profile id (`hail`, `codex`, `claude-code`, `gemini`, ...).
+    /// Parser profile id (`hail`, `codex`, `claude-code`, `gemini`, ...) or a plugin name.
     #[arg(long)]
     pub profile: String,
     /// Input file path.
@@ -24,6 +28,10 @@ pub struct ParseArgs {
     /// Optional output file path (default stdout).
     #[arg(long)]
     pub out: Option<PathBuf>,
+    /// Truncate content blocks larger than this many bytes; full content is
+    /// written to a `.blocks.jsonl` side file next to `--out`.
+    #[arg(long, requires = "out")]
+    pub max_block_bytes: Option<usize>,
 }
 
 pub fn run(args: ParseArgs) -> Result<()> {
@@ -44,6 +52,22 @@ pub fn run(args: ParseArgs) -> Result<()> {
 
     let preview = ParserRegistry::default()
         .preview_bytes(filename, &bytes, Some(args.profile.as_str()))
+        .or_else(|err| match (&err, find_plugin(&args.profile)) {
+            (ParseError::InvalidParserHint { .. }, Some(plugin)) => {
+                let session = plugin.parse(&args.file).map_err(|error| ParseError::ParseFailed {
+                    message: format!("{error:#}"),
+                    parser_candidates: Vec::new(),
+                })?;
+                Ok(ParsePreview {
+                    parser_used: format!("plugin:{}", plugin.name),
+                    parser_candidates: Vec::new(),
+                    session,
+                    warnings: Vec::new(),
+                    native_adapter: None,
+                })
+            }
+            _ => Err(err),
+        })
         .map_err(|err| {
             match err {
                 ParseError::InvalidParserHint { .. }
@@ -90,21 +114,39 @@ pub fn run(args: ParseArgs) -> Result<()> {
         }
     }
 
-    let canonical = session
-        .to_jsonl()
-        .context("serialize canonical HAIL JSONL")?;
+    let truncated = args
+        .max_block_bytes
+        .map(|max_bytes| truncate_session(&mut session, max_bytes))
+        .unwrap_or_default();
+
     if let Some(path) = args.out {
-        std::fs::write(&path, canonical).map_err(|err| {
-            guided_error(
-                format!("failed to write parsed output `{}`: {err}", path.display()),
-                [
-                    format!("check output path permissions: {}", path.display()),
-                    "retry without `--out` to print JSONL to stdout".to_string(),
-                ],
-            )
-        })?;
+        if !truncated.is_empty() {
+            let side_path = side_file_path(&path);
+            let side = to_side_jsonl(&truncated).context("serialize truncated blocks")?;
+            std::fs::write(&side_path, side)
+                .with_context(|| format!("write {}", side_path.display()))?;
+            eprintln!(
+                "truncated {} oversized block(s); full content in {}",
+                truncated.len(),
+                side_path.display()
+            );
+        }
+        // Stream events straight to the file instead of building the body in memory.
+        std::fs::File::create(&path)
+            .map_err(JsonlError::from)
+            .and_then(|file| write_jsonl(&session, BufWriter::new(file)))
+            .map_err(|err| {
+                guided_error(
+                    format!("failed to write parsed output `{}`: {err}", path.display()),
+                    [
+                        format!("check output path permissions: {}", path.display()),
+                        "retry without `--out` to print JSONL to stdout".to_string(),
+                    ],
+                )
+            })?;
     } else {
-        print!("{canonical}");
+        write_jsonl(&session, std::io::stdout().lock())
+            .context("serialize canonical HAIL JSONL")?;
     }
 
     Ok(())
@@ -0,0 +1,299 @@
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand, ValueEnum};
+use opensession_api::{
+    MAX_PLAYBOOK_BODY_BYTES, Playbook, PlaybookKind, PutPlaybookRequest, is_valid_playbook_name,
+};
+use opensession_local_db::LocalDb;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Args)]
+pub struct PlaybookArgs {
+    #[command(subcommand)]
+    pub action: PlaybookAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum PlaybookAction {
+    /// List the server's playbooks (falls back to the local cache when offline).
+    List {
+        /// Only read the local cache.
+        #[arg(long)]
+        offline: bool,
+        /// Print JSON instead of `name<TAB>kind<TAB>title` lines.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print a playbook's body as stored.
+    Show {
+        name: String,
+        /// Only read the local cache.
+        #[arg(long)]
+        offline: bool,
+    },
+    /// Print a playbook with `{{placeholder}}` variables filled in.
+    Apply {
+        name: String,
+        /// Placeholder value, e.g. `--var issue=PROJ-12` (repeatable).
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+        vars: Vec<(String, String)>,
+        /// Only read the local cache.
+        #[arg(long)]
+        offline: bool,
+    },
+    /// Create a playbook, or replace one you wrote.
+    Push {
+        name: String,
+        /// Body file, or `-` for stdin.
+        #[arg(long)]
+        file: PathBuf,
+        #[arg(long, value_enum, default_value_t = KindArg::Prompt)]
+        kind: KindArg,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Delete a playbook you wrote.
+    Delete { name: String },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum KindArg {
+    Prompt,
+    HandoffTemplate,
+}
+
+impl From<KindArg> for PlaybookKind {
+    fn from(kind: KindArg) -> Self {
+        match kind {
+            KindArg::Prompt => Self::Prompt,
+            KindArg::HandoffTemplate => Self::HandoffTemplate,
+        }
+    }
+}
+
+pub async fn run(args: PlaybookArgs) -> Result<()> {
+    let db = LocalDb::open().context("open local db")?;
+    match args.action {
+        PlaybookAction::List { offline, json } => {
+            let playbooks = if offline {
+                db.cached_playbooks().context("read playbook cache")?
+            } else {
+                list_remote_or_cached(&db).await?
+            };
+            if json {
+                println!("{}", serde_json::to_string_pretty(&playbooks)?);
+            } else {
+                for playbook in &playbooks {
+                    println!(
+                        "{}\t{}\t{}",
+                        playbook.name,
+                        playbook.kind,
+                        playbook.title.as_deref().unwrap_or_default()
+                    );
+                }
+            }
+            Ok(())
+        }
+        PlaybookAction::Show { name, offline } => {
+            let playbook = fetch(&db, &name, offline).await?;
+            print!("{}", with_trailing_newline(&playbook.body));
+            Ok(())
+        }
+        PlaybookAction::Apply {
+            name,
+            vars,
+            offline,
+        } => {
+            let playbook = fetch(&db, &name, offline).await?;
+            let (rendered, unresolved) = render(&playbook.body, &vars);
+            if !unresolved.is_empty() {
+                eprintln!(
+                    "warning: unresolved placeholders: {} (pass --var KEY=VALUE)",
+                    unresolved.join(", ")
+                );
+            }
+            print!("{}", with_trailing_newline(&rendered));
+            Ok(())
+        }
+        PlaybookAction::Push {
+            name,
+            file,
+            kind,
+            title,
+            description,
+        } => {
+            if !is_valid_playbook_name(&name) {
+                bail!("playbook names use lowercase letters, digits, '-' and '_': {name}");
+            }
+            let body = read_body(&file)?;
+            if body.len() > MAX_PLAYBOOK_BODY_BYTES {
+                bail!("playbook body is larger than 64 KiB");
+            }
+            let runtime = crate::runtime_settings::load_runtime_config()?;
+            let playbook = crate::handoff_v1::server_client(&runtime)?
+                .put_playbook(
+                    &name,
+                    &PutPlaybookRequest {
+                        kind: kind.into(),
+                        title,
+                        description,
+                        body,
+                    },
+                )
+                .await
+                .context("push playbook")?;
+            db.cache_playbook(&playbook)
+                .context("update playbook cache")?;
+            println!("pushed playbook: {}", playbook.name);
+            Ok(())
+        }
+        PlaybookAction::Delete { name } => {
+            let runtime = crate::runtime_settings::load_runtime_config()?;
+            crate::handoff_v1::server_client(&runtime)?
+                .delete_playbook(&name)
+                .await
+                .context("delete playbook")?;
+            db.remove_cached_playbook(&name)
+                .context("update playbook cache")?;
+            println!("deleted playbook: {name}");
+            Ok(())
+        }
+    }
+}
+
+/// Refresh the cache from the server; when the server can't be reached,
+/// warn and serve the cached copy instead.
+async fn list_remote_or_cached(db: &LocalDb) -> Result<Vec<Playbook>> {
+    match list_remote().await {
+        Ok(playbooks) => {
+            db.replace_cached_playbooks(&playbooks)
+                .context("update playbook cache")?;
+            Ok(playbooks)
+        }
+        Err(error) => {
+            eprintln!("warning: {error:#}; showing cached playbooks");
+            db.cached_playbooks().context("read playbook cache")
+        }
+    }
+}
+
+async fn list_remote() -> Result<Vec<Playbook>> {
+    let runtime = crate::runtime_settings::load_runtime_config()?;
+    let response = crate::handoff_v1::server_client(&runtime)?
+        .list_playbooks()
+        .await
+        .context("list playbooks")?;
+    Ok(response.playbooks)
+}
+
+async fn fetch(db: &LocalDb, name: &str, offline: bool) -> Result<Playbook> {
+    if !offline {
+        match fetch_remote(name).await {
+            Ok(playbook) => {
+                db.cache_playbook(&playbook)
+                    .context("update playbook cache")?;
+                return Ok(playbook);
+            }
+            Err(error) => eprintln!("warning: {error:#}; using the cached copy"),
+        }
+    }
+    db.cached_playbook(name)
+        .context("read playbook cache")?
+        .ok_or_else(|| {
+            crate::user_guidance::guided_error(
+                format!("playbook not found: {name}"),
+                ["opensession playbook list"],
+            )
+        })
+}
+
+async fn fetch_remote(name: &str) -> Result<Playbook> {
+    let runtime = crate::runtime_settings::load_runtime_config()?;
+    crate::handoff_v1::server_client(&runtime)?
+        .get_playbook(name)
+        .await
+        .context("fetch playbook")
+}
+
+fn read_body(file: &Path) -> Result<String> {
+    if file == Path::new("-") {
+        let mut body = String::new();
+        std::io::stdin()
+            .read_to_string(&mut body)
+            .context("read playbook body from stdin")?;
+        return Ok(body);
+    }
+    std::fs::read_to_string(file).with_context(|| format!("read {}", file.display()))
+}
+
+fn parse_var(raw: &str) -> Result<(String, String), String> {
+    let (key, value) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got `{raw}`"))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(format!("expected KEY=VALUE, got `{raw}`"));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Substitute `{{ key }}` placeholders. Returns the rendered text and the
+/// names of placeholders without a value, which are left in place.
+fn render(body: &str, vars: &[(String, String)]) -> (String, Vec<String>) {
+    let mut rendered = String::with_capacity(body.len());
+    let mut unresolved: Vec<String> = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let placeholder = &rest[start..start + 2 + len + 2];
+        let key = rest[start + 2..start + 2 + len].trim();
+        rendered.push_str(&rest[..start]);
+        match vars.iter().rev().find(|(name, _)| name == key) {
+            Some((_, value)) => rendered.push_str(value),
+            None => {
+                rendered.push_str(placeholder);
+                if !unresolved.iter().any(|name| name == key) {
+                    unresolved.push(key.to_string());
+                }
+            }
+        }
+        rest = &rest[start + placeholder.len()..];
+    }
+    rendered.push_str(rest);
+    (rendered, unresolved)
+}
+
+fn with_trailing_newline(text: &str) -> String {
+    if text.ends_with('\n') {
+        text.to_string()
+    } else {
+        format!("{text}\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_var, render};
+
+    #[test]
+    fn render_fills_known_placeholders_and_reports_the_rest() {
+        let vars = vec![
+            parse_var("issue=PROJ-12").unwrap(),
+            parse_var("module=src/auth").unwrap(),
+        ];
+        let (rendered, unresolved) = render(
+            "Fix {{issue}} in {{ module }}; ping {{owner}} and {{owner}}. {{unterminated",
+            &vars,
+        );
+        assert_eq!(
+            rendered,
+            "Fix PROJ-12 in src/auth; ping {{owner}} and {{owner}}. {{unterminated"
+        );
+        assert_eq!(unresolved, vec!["owner"]);
+        assert!(parse_var("novalue").is_err());
+    }
+}
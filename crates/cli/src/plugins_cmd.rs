@@ -0,0 +1,80 @@
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use opensession_core::Session;
+use opensession_parsers::plugin::{PLUGIN_PREFIX, discover_plugins, find_plugin};
+use std::path::PathBuf;
+
+use crate::user_guidance::guided_error;
+
+#[derive(Debug, Clone, Args)]
+pub struct PluginsArgs {
+    #[command(subcommand)]
+    pub action: PluginsAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum PluginsAction {
+    /// List `opensession-plugin-*` executables found on PATH.
+    List,
+    /// Export canonical HAIL JSONL through a plugin exporter.
+    Export {
+        /// Plugin name (the part after `opensession-plugin-`).
+        plugin: String,
+        /// Export format advertised by the plugin.
+        #[arg(long)]
+        format: String,
+        /// Canonical HAIL JSONL input file.
+        file: PathBuf,
+        /// Optional output file path (default stdout).
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+pub fn run(args: PluginsArgs) -> Result<()> {
+    match args.action {
+        PluginsAction::List => {
+            let plugins = discover_plugins();
+            if plugins.is_empty() {
+                println!("no plugins found (looked for `{PLUGIN_PREFIX}*` on PATH)");
+            }
+            for plugin in plugins {
+                println!("{}\t{}", plugin.name, plugin.path.display());
+                if !plugin.manifest.parser_globs.is_empty() {
+                    println!("  parses:  {}", plugin.manifest.parser_globs.join(", "));
+                }
+                if !plugin.manifest.export_formats.is_empty() {
+                    println!("  exports: {}", plugin.manifest.export_formats.join(", "));
+                }
+            }
+            Ok(())
+        }
+        PluginsAction::Export {
+            plugin,
+            format,
+            file,
+            out,
+        } => {
+            let plugin = find_plugin(&plugin).ok_or_else(|| {
+                guided_error(
+                    format!("plugin `{plugin}` not found"),
+                    [
+                        format!("install an executable named `{PLUGIN_PREFIX}{plugin}` on PATH"),
+                        "run `opensession plugins list`".to_string(),
+                    ],
+                )
+            })?;
+            let text = std::fs::read_to_string(&file)
+                .with_context(|| format!("read {}", file.display()))?;
+            let session = Session::from_jsonl(&text)
+                .with_context(|| format!("parse HAIL JSONL {}", file.display()))?;
+            let output = plugin.export(&format, &session)?;
+            match out {
+                Some(path) => std::fs::write(&path, output)
+                    .with_context(|| format!("write {}", path.display()))?,
+                None => print!("{output}"),
+            }
+            Ok(())
+        }
+    }
+}
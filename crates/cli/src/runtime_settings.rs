@@ -1,7 +1,12 @@
 use anyhow::{Context, Result};
-use opensession_runtime_config::DaemonConfig;
+use opensession_runtime_config::{DaemonConfig, SERVER_PROFILE_ENV, ServerSettings};
 use opensession_summary_runtime::LocalSummaryProfile;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::user_guidance::guided_error;
+
+static SERVER_PROFILE: OnceLock<String> = OnceLock::new();
 
 pub fn runtime_config_path() -> Result<PathBuf> {
     opensession_paths::runtime_config_path().context("Could not determine home directory")
@@ -18,6 +23,52 @@ pub fn load_runtime_config() -> Result<DaemonConfig> {
         .with_context(|| format!("Failed to parse runtime config at {}", path.display()))
 }
 
+/// Record the global `--profile` flag; it takes precedence over `OPENSESSION_PROFILE`.
+pub fn set_server_profile(name: Option<String>) {
+    if let Some(name) = name {
+        let _ = SERVER_PROFILE.set(name);
+    }
+}
+
+fn selected_server_profile() -> Option<String> {
+    SERVER_PROFILE
+        .get()
+        .cloned()
+        .or_else(|| std::env::var(SERVER_PROFILE_ENV).ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Server settings to connect with: the selected `[server.profiles.<name>]`
+/// applied over `[server]`. Never write the result back to `opensession.toml`.
+pub fn active_server_settings(server: &ServerSettings) -> Result<ServerSettings> {
+    let mut server = server.clone();
+    apply_server_profile(&mut server, selected_server_profile().as_deref())?;
+    Ok(server)
+}
+
+fn apply_server_profile(server: &mut ServerSettings, name: Option<&str>) -> Result<()> {
+    let Some(name) = name else {
+        return Ok(());
+    };
+    if server.select_profile(name) {
+        return Ok(());
+    }
+    let known = server.profile_names();
+    Err(guided_error(
+        format!("server profile `{name}` is not defined"),
+        [
+            if known.is_empty() {
+                "add a `[server.profiles.<name>]` table with `url` and `api_key` to opensession.toml"
+                    .to_string()
+            } else {
+                format!("use one of: {}", known.join(", "))
+            },
+            format!("or unset `--profile` / `{SERVER_PROFILE_ENV}` to use `[server]`"),
+        ],
+    ))
+}
+
 pub fn save_runtime_config(config: &DaemonConfig) -> Result<PathBuf> {
     let path = runtime_config_path()?;
     if let Some(parent) = path.parent() {
@@ -39,3 +90,35 @@ pub fn apply_summary_profile(config: &mut DaemonConfig, profile: &LocalSummaryPr
     config.summary.provider.endpoint = profile.endpoint.clone();
     config.summary.provider.model = profile.model.clone();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::apply_server_profile;
+    use opensession_runtime_config::{ServerProfile, ServerSettings};
+
+    #[test]
+    fn apply_server_profile_selects_or_lists_known_profiles() {
+        let mut server = ServerSettings::default();
+        server.profiles.insert(
+            "oss".to_string(),
+            ServerProfile {
+                url: "https://opensession.io".to_string(),
+                api_key: "osk_oss".to_string(),
+                team: String::new(),
+            },
+        );
+
+        let mut unchanged = server.clone();
+        apply_server_profile(&mut unchanged, None).expect("no profile selected");
+        assert_eq!(unchanged.api_key, "");
+
+        let error = apply_server_profile(&mut server.clone(), Some("work"))
+            .expect_err("unknown profile")
+            .to_string();
+        assert!(error.contains("`work` is not defined"), "{error}");
+        assert!(error.contains("use one of: oss"), "{error}");
+
+        apply_server_profile(&mut server, Some("oss")).expect("known profile");
+        assert_eq!(server.api_key, "osk_oss");
+    }
+}
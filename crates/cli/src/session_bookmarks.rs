@@ -0,0 +1,71 @@
+use anyhow::{Context, Result};
+use opensession_api::{EventBookmark, event_bookmark_key, event_bookmark_prefix, event_permalink};
+use opensession_local_db::LocalDb;
+
+/// Bookmark an event (or, with `remove`, drop the bookmark) in the local
+/// synced metadata and print its permalink; `opensession meta sync` pushes
+/// the change to the server.
+pub fn bookmark_event(
+    session_id: &str,
+    event_id: &str,
+    note: Option<&str>,
+    remove: bool,
+) -> Result<()> {
+    let event_id = event_id.trim();
+    if event_id.is_empty() {
+        anyhow::bail!("event id must not be empty");
+    }
+    let db = LocalDb::open().context("open local db")?;
+    let key = event_bookmark_key(session_id, event_id);
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    if remove {
+        if db.get_metadata(&key).context("read bookmark")?.is_none() {
+            anyhow::bail!("event {event_id} of {session_id} is not bookmarked");
+        }
+        db.set_metadata(&key, None, now_ms)
+            .context("delete bookmark")?;
+        println!("removed bookmark on {event_id}");
+        return Ok(());
+    }
+    let note = note.map(str::trim).unwrap_or_default();
+    db.set_metadata(&key, Some(note), now_ms)
+        .context("write bookmark")?;
+    println!(
+        "{}",
+        event_permalink(&permalink_base(), session_id, event_id)
+    );
+    Ok(())
+}
+
+/// List a session's bookmarked events as `event<TAB>note<TAB>permalink` lines.
+pub fn list_bookmarks(session_id: &str, json: bool) -> Result<()> {
+    let db = LocalDb::open().context("open local db")?;
+    let bookmarks = db
+        .list_metadata(&event_bookmark_prefix(session_id))
+        .context("list bookmarks")?
+        .iter()
+        .filter_map(EventBookmark::from_entry)
+        .collect::<Vec<_>>();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&bookmarks)?);
+        return Ok(());
+    }
+    let base = permalink_base();
+    for bookmark in bookmarks {
+        println!(
+            "{}\t{}\t{}",
+            bookmark.event_id,
+            bookmark.note.as_deref().unwrap_or(""),
+            event_permalink(&base, &bookmark.session_id, &bookmark.event_id)
+        );
+    }
+    Ok(())
+}
+
+/// The configured server's URL, or empty for a site-relative link.
+fn permalink_base() -> String {
+    crate::runtime_settings::load_runtime_config()
+        .and_then(|runtime| crate::runtime_settings::active_server_settings(&runtime.server))
+        .map(|server| server.url)
+        .unwrap_or_default()
+}
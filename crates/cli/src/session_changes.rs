@@ -0,0 +1,249 @@
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, SecondsFormat, Utc};
+use opensession_core::Session;
+use opensession_core::extract::extract_changed_paths;
+use opensession_core::session::working_directory;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::user_guidance::guided_error;
+
+/// Print the net effect of a session's file edits: `git diff` from the commit
+/// checked out when the session started (or `base`) to the working tree,
+/// limited to the files the session touched. Edits the agent made and later
+/// reverted drop out of the diff.
+pub fn print_changes(session: &Session, base: Option<&str>, stat: bool) -> Result<()> {
+    let cwd = session_cwd(session)?;
+    let repo = repo_root(&cwd)?;
+    let (modified, deleted) = extract_changed_paths(&session.events);
+    let touched = modified.len() + deleted.len();
+    let paths = repo_relative_paths(modified.iter().chain(&deleted), &cwd, &repo);
+    if paths.is_empty() {
+        eprintln!("session made no file edits inside {}", repo.display());
+        return Ok(());
+    }
+    if paths.len() < touched {
+        eprintln!(
+            "skipping {} touched path(s) outside {}",
+            touched - paths.len(),
+            repo.display()
+        );
+    }
+
+    let base = match base {
+        Some(rev) => rev.to_string(),
+        None => commit_before(&repo, session_start(session))?,
+    };
+
+    let mut diff_args = vec!["diff", "--no-ext-diff"];
+    if stat {
+        diff_args.push("--stat");
+    }
+    diff_args.extend([base.as_str(), "--"]);
+    let mut diff = git(&repo, with_paths(&diff_args, &paths))?;
+    let changed = git(
+        &repo,
+        with_paths(&["diff", "--name-only", base.as_str(), "--"], &paths),
+    )?
+    .lines()
+    .count();
+
+    // `git diff <rev>` leaves out files that were never added to the index.
+    let untracked = git(
+        &repo,
+        with_paths(
+            &["ls-files", "--others", "--exclude-standard", "--"],
+            &paths,
+        ),
+    )?;
+    let untracked = untracked.lines().collect::<Vec<_>>();
+    for path in &untracked {
+        diff.push_str(&new_file_diff(&repo, path, stat)?);
+    }
+
+    print!("{diff}");
+    eprintln!(
+        "{} of {} touched file(s) differ from {}",
+        changed + untracked.len(),
+        paths.len(),
+        short_rev(&base)
+    );
+    Ok(())
+}
+
+fn session_start(session: &Session) -> DateTime<Utc> {
+    let created_at = session.context.created_at;
+    session
+        .events
+        .first()
+        .map_or(created_at, |event| event.timestamp.min(created_at))
+}
+
+/// The directory the session ran in, else the current directory.
+pub(crate) fn session_cwd(session: &Session) -> Result<PathBuf> {
+    match working_directory(session) {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => std::env::current_dir().context("read current directory"),
+    }
+}
+
+pub(crate) fn repo_root(cwd: &Path) -> Result<PathBuf> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(cwd)
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .context("run git")?;
+    if !output.status.success() {
+        return Err(guided_error(
+            format!("{} is not inside a git repository", cwd.display()),
+            ["run this from the repository the session worked in"],
+        ));
+    }
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim(),
+    ))
+}
+
+/// Newest commit on HEAD made before the session started.
+fn commit_before(repo: &Path, start: DateTime<Utc>) -> Result<String> {
+    let before = format!(
+        "--before={}",
+        start.to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+    let commit = git(repo, ["rev-list", "-1", before.as_str(), "HEAD"])?;
+    let commit = commit.trim();
+    if commit.is_empty() {
+        return Err(guided_error(
+            format!(
+                "no commit in {} predates the session start ({start})",
+                repo.display()
+            ),
+            ["pass `--base <rev>` to pick the baseline explicitly"],
+        ));
+    }
+    Ok(commit.to_string())
+}
+
+/// Map session paths (absolute, or relative to the session cwd) to
+/// repo-relative paths, dropping anything outside the repository.
+fn repo_relative_paths<'a>(
+    paths: impl IntoIterator<Item = &'a String>,
+    cwd: &Path,
+    repo: &Path,
+) -> Vec<String> {
+    paths
+        .into_iter()
+        .filter_map(|raw| repo_relative_path(raw, cwd, repo))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// `raw` (absolute, or relative to `cwd`) relative to `repo`, with `/`
+/// separators; `None` when it lies outside the repository.
+pub(crate) fn repo_relative_path(raw: &str, cwd: &Path, repo: &Path) -> Option<String> {
+    let path = cwd.join(raw);
+    let inside = path
+        .strip_prefix(repo)
+        .map(Path::to_path_buf)
+        .or_else(|_| {
+            canonical_lossy(&path)
+                .strip_prefix(repo)
+                .map(Path::to_path_buf)
+        })
+        .ok()?;
+    (!inside.as_os_str().is_empty()).then(|| inside.to_string_lossy().replace('\\', "/"))
+}
+
+/// Canonicalize a path whose file may no longer exist by resolving its parent.
+fn canonical_lossy(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => parent
+            .canonicalize()
+            .map(|parent| parent.join(name))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn with_paths<'a>(args: &[&'a str], paths: &'a [String]) -> Vec<&'a str> {
+    args.iter()
+        .copied()
+        .chain(paths.iter().map(String::as_str))
+        .collect()
+}
+
+fn new_file_diff(repo: &Path, path: &str, stat: bool) -> Result<String> {
+    let mut args = vec!["diff", "--no-ext-diff", "--no-index"];
+    if stat {
+        args.push("--stat");
+    }
+    args.extend(["--", "/dev/null", path]);
+    let output = git_command(repo, &args).output().context("run git diff")?;
+    // `--no-index` exits 1 when the files differ.
+    if output.status.code().is_some_and(|code| code > 1) {
+        bail!(
+            "git diff --no-index {path} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn git<'a>(repo: &Path, args: impl IntoIterator<Item = &'a str>) -> Result<String> {
+    let args = args.into_iter().collect::<Vec<_>>();
+    let output = git_command(repo, &args)
+        .output()
+        .with_context(|| format!("run git {}", args.first().copied().unwrap_or_default()))?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn git_command(repo: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(repo)
+        .arg("--literal-pathspecs")
+        .args(args);
+    command
+}
+
+fn short_rev(rev: &str) -> &str {
+    if rev.len() == 40 && rev.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        &rev[..12]
+    } else {
+        rev
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::repo_relative_paths;
+    use std::path::Path;
+
+    #[test]
+    fn paths_are_made_repo_relative_and_outside_paths_dropped() {
+        let paths = [
+            "/repo/src/main.rs".to_string(),
+            "lib.rs".to_string(),
+            "/elsewhere/notes.md".to_string(),
+            "/repo/src/main.rs".to_string(),
+        ];
+        assert_eq!(
+            repo_relative_paths(&paths, Path::new("/repo/crates/app"), Path::new("/repo")),
+            vec!["crates/app/lib.rs", "src/main.rs"]
+        );
+    }
+}
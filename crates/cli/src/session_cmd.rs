@@ -0,0 +1,643 @@
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand, ValueEnum};
+use opensession_api::{
+    CreateReviewRequest, ResolveReviewRequest, ReviewRequestItem, ReviewRequestListQuery,
+    ReviewRequestRole, ReviewRequestStatus, StreamEventsRequest, ValidateSessionResponse,
+};
+use opensession_core::Session;
+use opensession_core::anonymize::anonymize_session;
+use opensession_core::jsonl::{from_jsonl_str, salvage_jsonl, to_jsonl_string};
+use opensession_core::truncate::{find_full_block, side_file_path};
+use opensession_local_db::LocalDb;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::user_guidance::guided_error;
+
+#[derive(Debug, Clone, Args)]
+pub struct SessionArgs {
+    #[command(subcommand)]
+    pub action: SessionAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum SessionAction {
+    /// Strictly validate a HAIL JSONL file before uploading it.
+    Validate {
+        /// HAIL JSONL file (`-` reads stdin).
+        file: PathBuf,
+        /// Print the structured result as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Replace paths, identifiers and content with placeholders for bug reports.
+    Anonymize {
+        /// HAIL JSONL file (`-` reads stdin).
+        file: PathBuf,
+        /// Write to this file instead of stdout.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Salvage the readable lines of a truncated or corrupted JSONL session log.
+    Repair {
+        /// HAIL or agent-native JSONL file.
+        file: PathBuf,
+        /// Write the cleaned log here instead of `<name>.repaired.<ext>`.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Print the full content of a block cut by `parse --max-block-bytes`.
+    Block {
+        /// HAIL JSONL file produced with `--max-block-bytes`.
+        file: PathBuf,
+        /// Event id owning the block.
+        event_id: String,
+        /// Block index within the event content.
+        #[arg(long, default_value_t = 0)]
+        block: usize,
+    },
+    /// Show the net diff of a session's file edits against the repo state at
+    /// session start.
+    Changes {
+        /// Session file, session id, or `HEAD` / `HEAD^N`.
+        session: String,
+        /// Compare against this revision instead of the commit checked out
+        /// when the session started.
+        #[arg(long, value_name = "REV")]
+        base: Option<String>,
+        /// Print a diffstat instead of the full diff.
+        #[arg(long)]
+        stat: bool,
+    },
+    /// Export a session's recorded edits as a unified diff series.
+    Patch {
+        /// Session file, session id, or `HEAD` / `HEAD^N`.
+        session: String,
+        /// Write the patch to this file instead of stdout.
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Apply the patch to the repository in the current directory.
+        #[arg(long)]
+        apply: bool,
+    },
+    /// List the shell commands a session ran, with exit codes and durations.
+    Commands {
+        /// Session file, session id, or `HEAD` / `HEAD^N`.
+        session: String,
+        /// Write a runnable bash script instead of listing.
+        #[arg(long, value_name = "FILE", conflicts_with = "json")]
+        script: Option<PathBuf>,
+        /// Print JSON instead of `status<TAB>duration<TAB>command` lines.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Share a running session with your teams until Ctrl-C; teammates watch
+    /// it at `/session/<id>/live`.
+    Live {
+        /// Session file the agent is still writing (HAIL or agent-native).
+        file: PathBuf,
+        /// Seconds between checks for new events.
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+    },
+    /// Bookmark an event and print its permalink
+    /// (`<server>/session/<id>#event-<event-id>`). Sync with `opensession meta sync`.
+    Bookmark {
+        /// Session id, or `HEAD` / `HEAD^N` for recent local sessions.
+        session: String,
+        event_id: String,
+        /// Why the event matters.
+        #[arg(long)]
+        note: Option<String>,
+        /// Remove the bookmark instead.
+        #[arg(long, conflicts_with = "note")]
+        rm: bool,
+    },
+    /// List a session's bookmarked events with their permalinks.
+    Bookmarks {
+        /// Session id, or `HEAD` / `HEAD^N` for recent local sessions.
+        session: String,
+        /// Print JSON instead of `event<TAB>note<TAB>permalink` lines.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Ask another user to review an uploaded session.
+    RequestReview {
+        /// Session id, or `HEAD` / `HEAD^N` for recent local sessions.
+        session: String,
+        /// Reviewer nickname (`@nick` or `nick`).
+        #[arg(long = "from", value_name = "NICK")]
+        reviewer: String,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// List review requests assigned to you (or sent by you).
+    Reviews {
+        /// List requests you sent instead of ones assigned to you.
+        #[arg(long)]
+        requested: bool,
+        #[arg(long, value_enum)]
+        status: Option<ReviewStatusArg>,
+        /// Print JSON instead of one line per request.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Approve a review request assigned to you, or ask for changes.
+    #[command(group(
+        clap::ArgGroup::new("verdict")
+            .required(true)
+            .args(["approve", "request_changes"])
+    ))]
+    ResolveReview {
+        id: String,
+        #[arg(long)]
+        approve: bool,
+        #[arg(long)]
+        request_changes: bool,
+        #[arg(long)]
+        comment: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ReviewStatusArg {
+    Pending,
+    Approved,
+    ChangesRequested,
+}
+
+impl From<ReviewStatusArg> for ReviewRequestStatus {
+    fn from(status: ReviewStatusArg) -> Self {
+        match status {
+            ReviewStatusArg::Pending => Self::Pending,
+            ReviewStatusArg::Approved => Self::Approved,
+            ReviewStatusArg::ChangesRequested => Self::ChangesRequested,
+        }
+    }
+}
+
+pub async fn run(args: SessionArgs) -> Result<()> {
+    match args.action {
+        SessionAction::Validate { file, json } => validate(&file, json),
+        SessionAction::Anonymize { file, out } => anonymize(&file, out.as_deref()),
+        SessionAction::Repair { file, out } => repair(&file, out.as_deref()),
+        SessionAction::Block {
+            file,
+            event_id,
+            block,
+        } => print_full_block(&file, &event_id, block),
+        SessionAction::Changes {
+            session,
+            base,
+            stat,
+        } => crate::session_changes::print_changes(&load_session(&session)?, base.as_deref(), stat),
+        SessionAction::Patch {
+            session,
+            out,
+            apply,
+        } => crate::session_patch::export_patch(&load_session(&session)?, out.as_deref(), apply),
+        SessionAction::Commands {
+            session,
+            script,
+            json,
+        } => crate::session_commands::list_commands(
+            &load_session(&session)?,
+            script.as_deref(),
+            json,
+        ),
+        SessionAction::Live { file, interval } => {
+            share_live(&file, Duration::from_secs(interval.max(1))).await
+        }
+        SessionAction::Bookmark {
+            session,
+            event_id,
+            note,
+            rm,
+        } => crate::session_bookmarks::bookmark_event(
+            &resolve_session_id(&session)?,
+            &event_id,
+            note.as_deref(),
+            rm,
+        ),
+        SessionAction::Bookmarks { session, json } => {
+            crate::session_bookmarks::list_bookmarks(&resolve_session_id(&session)?, json)
+        }
+        SessionAction::RequestReview {
+            session,
+            reviewer,
+            note,
+        } => request_review(&session, reviewer, note).await,
+        SessionAction::Reviews {
+            requested,
+            status,
+            json,
+        } => list_reviews(requested, status, json).await,
+        SessionAction::ResolveReview {
+            id,
+            approve,
+            request_changes: _,
+            comment,
+        } => {
+            let status = if approve {
+                ReviewRequestStatus::Approved
+            } else {
+                ReviewRequestStatus::ChangesRequested
+            };
+            let runtime = crate::runtime_settings::load_runtime_config()?;
+            let item = crate::handoff_v1::server_client(&runtime)?
+                .resolve_review_request(&id, &ResolveReviewRequest { status, comment })
+                .await
+                .context("resolve review request")?;
+            println!("review {}: {}", item.id, item.status);
+            Ok(())
+        }
+    }
+}
+
+/// Post the events written so far, then relay new ones each time the file
+/// changes. The share ends on Ctrl-C, or on its own once the session is
+/// uploaded.
+async fn share_live(file: &Path, interval: Duration) -> Result<()> {
+    let session = crate::handoff_v1::parse_session_input(file)?;
+    let session_id = session.session_id.clone();
+    let runtime = crate::runtime_settings::load_runtime_config()?;
+    let client = crate::handoff_v1::server_client(&runtime)?;
+    let mut sent = session.events.len();
+    client
+        .start_live(
+            &session_id,
+            &StreamEventsRequest {
+                agent: Some(session.agent),
+                context: Some(session.context),
+                events: session.events,
+            },
+        )
+        .await
+        .map_err(|err| {
+            guided_error(
+                format!("live share of {session_id} failed: {err}"),
+                ["a session that is already uploaded cannot be shared live"],
+            )
+        })?;
+    println!(
+        "sharing {session_id} live: {}/session/{session_id}/live",
+        client.base_url().trim_end_matches('/')
+    );
+    println!("press Ctrl-C to stop");
+
+    let mut modified = modified_at(file);
+    let stop = tokio::signal::ctrl_c();
+    tokio::pin!(stop);
+    loop {
+        tokio::select! {
+            _ = &mut stop => break,
+            () = tokio::time::sleep(interval) => {}
+        }
+        let now = modified_at(file);
+        if now == modified {
+            continue;
+        }
+        modified = now;
+        let events = match crate::handoff_v1::parse_session_input(file) {
+            Ok(session) => session.events,
+            Err(err) => {
+                eprintln!("skipping unreadable update: {err:#}");
+                continue;
+            }
+        };
+        if events.len() <= sent {
+            continue;
+        }
+        let total = events.len();
+        client
+            .append_live_events(
+                &session_id,
+                &StreamEventsRequest {
+                    agent: None,
+                    context: None,
+                    events: events.into_iter().skip(sent).collect(),
+                },
+            )
+            .await
+            .context("relay live events")?;
+        sent = total;
+    }
+
+    client
+        .stop_live(&session_id)
+        .await
+        .context("stop live share")?;
+    println!("stopped sharing {session_id}");
+    Ok(())
+}
+
+fn modified_at(file: &Path) -> Option<SystemTime> {
+    std::fs::metadata(file)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+async fn request_review(session: &str, reviewer: String, note: Option<String>) -> Result<()> {
+    let session_id = resolve_session_id(session)?;
+    let runtime = crate::runtime_settings::load_runtime_config()?;
+    let item = crate::handoff_v1::server_client(&runtime)?
+        .request_review(&session_id, &CreateReviewRequest { reviewer, note })
+        .await
+        .map_err(|err| {
+            guided_error(
+                format!("review request for {session_id} failed: {err}"),
+                [
+                    "the session must be uploaded first: `opensession share <target>`",
+                    "check the reviewer's nickname",
+                ],
+            )
+        })?;
+    println!(
+        "requested review {} from @{} for {}",
+        item.id, item.reviewer_nickname, item.session_id
+    );
+    Ok(())
+}
+
+async fn list_reviews(requested: bool, status: Option<ReviewStatusArg>, json: bool) -> Result<()> {
+    let runtime = crate::runtime_settings::load_runtime_config()?;
+    let query = ReviewRequestListQuery {
+        role: if requested {
+            ReviewRequestRole::Requested
+        } else {
+            ReviewRequestRole::Assigned
+        },
+        status: status.map(Into::into),
+        limit: None,
+    };
+    let items = crate::handoff_v1::server_client(&runtime)?
+        .list_review_requests(&query)
+        .await
+        .context("list review requests")?
+        .items;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&items)?);
+    } else {
+        for item in &items {
+            println!("{}", review_line(item, requested));
+        }
+    }
+    Ok(())
+}
+
+/// `id  status  @other  session-id  title`, where `@other` is the requester
+/// for assigned reviews and the reviewer for requested ones.
+fn review_line(item: &ReviewRequestItem, requested: bool) -> String {
+    let other = if requested {
+        &item.reviewer_nickname
+    } else {
+        &item.requester_nickname
+    };
+    format!(
+        "{}\t{}\t@{}\t{}\t{}",
+        item.id,
+        item.status,
+        other,
+        item.session_id,
+        item.session_title.as_deref().unwrap_or_default()
+    )
+}
+
+/// `HEAD` and `HEAD^N` name recent local sessions; anything else is a session id.
+fn resolve_session_id(raw: &str) -> Result<String> {
+    let raw = raw.trim();
+    let offset = if raw.eq_ignore_ascii_case("HEAD") {
+        Some(0)
+    } else {
+        raw.get(..5)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("HEAD^"))
+            .and_then(|_| raw[5..].parse::<u32>().ok())
+    };
+    let Some(offset) = offset else {
+        return Ok(raw.to_string());
+    };
+    let db = LocalDb::open().context("open local db")?;
+    db.get_session_by_offset(offset)?
+        .map(|row| row.id)
+        .ok_or_else(|| {
+            guided_error(
+                format!("no local session at {raw}"),
+                ["run `opensession log` to see indexed sessions"],
+            )
+        })
+}
+
+/// Load a session from a file, or from the local index by id or `HEAD^N`.
+fn load_session(reference: &str) -> Result<Session> {
+    let path = Path::new(reference);
+    if path.is_file() {
+        return crate::handoff_v1::parse_session_input(path);
+    }
+    let session_id = resolve_session_id(reference)?;
+    let source = LocalDb::open()
+        .context("open local db")?
+        .get_session_source_path(&session_id)?
+        .ok_or_else(|| {
+            guided_error(
+                format!("session {session_id} is not indexed locally"),
+                [
+                    "run `opensession log` to see indexed sessions",
+                    "or pass the session file path instead",
+                ],
+            )
+        })?;
+    crate::handoff_v1::parse_session_input(Path::new(&source))
+}
+
+fn read_input(file: &Path) -> Result<String> {
+    if file.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin()).context("read stdin")
+    } else {
+        std::fs::read_to_string(file).with_context(|| format!("read {}", file.display()))
+    }
+}
+
+fn validate(file: &Path, json: bool) -> Result<()> {
+    let body = read_input(file)?;
+    let result = ValidateSessionResponse::from_jsonl(&body);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if result.valid {
+        println!("{}: valid HAIL session", file.display());
+    } else {
+        for error in &result.errors {
+            eprintln!("{}: [{}] {}", error.path, error.code, error.message);
+        }
+    }
+
+    if result.valid {
+        Ok(())
+    } else {
+        Err(guided_error(
+            format!(
+                "{} failed validation with {} error(s)",
+                file.display(),
+                result.errors.len()
+            ),
+            [
+                "fix the reported paths and run `opensession session validate <file>` again",
+                "use `opensession parse --validate` to regenerate HAIL from agent-native logs",
+            ],
+        ))
+    }
+}
+
+fn anonymize(file: &Path, out: Option<&Path>) -> Result<()> {
+    let body = read_input(file)?;
+    let session = from_jsonl_str(&body).map_err(|err| {
+        guided_error(
+            format!("{} is not a readable HAIL session: {err}", file.display()),
+            ["run `opensession session validate <file>` to see what is wrong"],
+        )
+    })?;
+    let anonymized = to_jsonl_string(&anonymize_session(&session))?;
+    match out {
+        Some(path) => {
+            std::fs::write(path, anonymized)
+                .with_context(|| format!("write {}", path.display()))?;
+            eprintln!(
+                "wrote {} (paths, ids and content replaced; review before sharing)",
+                path.display()
+            );
+        }
+        None => print!("{anonymized}"),
+    }
+    Ok(())
+}
+
+fn repair(file: &Path, out: Option<&Path>) -> Result<()> {
+    let bytes = std::fs::read(file).with_context(|| format!("read {}", file.display()))?;
+    // Crashes can leave NUL padding or cut a multi-byte character in half.
+    let salvage = salvage_jsonl(&String::from_utf8_lossy(&bytes));
+    if salvage.dropped.is_empty() {
+        println!("{}: no damaged lines", file.display());
+        return Ok(());
+    }
+    if salvage.kept == 0 {
+        return Err(guided_error(
+            format!("{} has no readable JSON lines", file.display()),
+            ["check that the file is a JSONL session log, not a single JSON document"],
+        ));
+    }
+
+    let out = out.map_or_else(|| repaired_path(file), Path::to_path_buf);
+    std::fs::write(&out, &salvage.body).with_context(|| format!("write {}", out.display()))?;
+    println!(
+        "wrote {} ({} line(s) kept, {} dropped: {})",
+        out.display(),
+        salvage.kept,
+        salvage.dropped.len(),
+        line_list(&salvage.dropped)
+    );
+    println!(
+        "hint: run `opensession parse --profile <parser> {} --preview` to check the result.",
+        out.display()
+    );
+    Ok(())
+}
+
+/// `session.jsonl` → `session.repaired.jsonl`.
+fn repaired_path(file: &Path) -> PathBuf {
+    match file.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => file.with_extension(format!("repaired.{ext}")),
+        None => file.with_extension("repaired"),
+    }
+}
+
+/// `line 3`, `lines 3, 9, 12`, or the first few followed by `...`.
+fn line_list(lines: &[usize]) -> String {
+    const SHOWN: usize = 8;
+    let mut list = lines
+        .iter()
+        .take(SHOWN)
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if lines.len() > SHOWN {
+        list.push_str(", ...");
+    }
+    if lines.len() == 1 {
+        format!("line {list}")
+    } else {
+        format!("lines {list}")
+    }
+}
+
+fn print_full_block(file: &Path, event_id: &str, block: usize) -> Result<()> {
+    let side_path = side_file_path(file);
+    let side = std::fs::read_to_string(&side_path).map_err(|err| {
+        guided_error(
+            format!("failed to read side file `{}`: {err}", side_path.display()),
+            [format!(
+                "re-run `opensession parse --out {} --max-block-bytes <n>` to produce it",
+                file.display()
+            )],
+        )
+    })?;
+    match find_full_block(&side, event_id, block)
+        .with_context(|| format!("parse {}", side_path.display()))?
+    {
+        Some(content) => {
+            print!("{content}");
+            Ok(())
+        }
+        None => Err(guided_error(
+            format!("block {block} of event `{event_id}` was not truncated"),
+            ["the block content in the session file is already complete"],
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{line_list, repaired_path, resolve_session_id, review_line};
+    use opensession_api::{ReviewRequestItem, ReviewRequestStatus};
+    use std::path::Path;
+
+    #[test]
+    fn review_line_names_the_other_party() {
+        let item = ReviewRequestItem {
+            id: "r1".to_string(),
+            session_id: "s1".to_string(),
+            session_title: Some("Fix login".to_string()),
+            requester_nickname: "alice".to_string(),
+            reviewer_nickname: "bob".to_string(),
+            note: None,
+            status: ReviewRequestStatus::Pending,
+            comment: None,
+            resolved_at: None,
+            created_at: "2026-01-01 00:00:00".to_string(),
+        };
+        assert_eq!(
+            review_line(&item, false),
+            "r1\tpending\t@alice\ts1\tFix login"
+        );
+        assert_eq!(review_line(&item, true), "r1\tpending\t@bob\ts1\tFix login");
+    }
+
+    #[test]
+    fn plain_session_ids_pass_through() {
+        assert_eq!(resolve_session_id(" abc-123 ").unwrap(), "abc-123");
+        assert_eq!(resolve_session_id("HEADLINE").unwrap(), "HEADLINE");
+    }
+
+    #[test]
+    fn repair_output_names() {
+        assert_eq!(
+            repaired_path(Path::new("logs/s.hail.jsonl")),
+            Path::new("logs/s.hail.repaired.jsonl")
+        );
+        assert_eq!(repaired_path(Path::new("s")), Path::new("s.repaired"));
+        assert_eq!(line_list(&[7]), "line 7");
+        assert_eq!(
+            line_list(&(1..=10).collect::<Vec<_>>()),
+            "lines 1, 2, 3, 4, 5, 6, 7, 8, ..."
+        );
+    }
+}
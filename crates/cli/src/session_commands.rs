@@ -0,0 +1,164 @@
+use anyhow::{Context, Result};
+use opensession_core::Session;
+use opensession_core::extract::{CommandRun, extract_command_runs};
+use opensession_core::session::working_directory;
+use std::path::Path;
+
+use crate::user_guidance::guided_error;
+
+/// List the shell commands a session ran as `status<TAB>duration<TAB>command`,
+/// or write them to `script` as a runnable bash script.
+pub fn list_commands(session: &Session, script: Option<&Path>, json: bool) -> Result<()> {
+    let runs = extract_command_runs(&session.events);
+    if runs.is_empty() {
+        return Err(guided_error(
+            "session ran no shell commands",
+            ["pick another session with `opensession log`"],
+        ));
+    }
+
+    if let Some(path) = script {
+        std::fs::write(path, render_script(session, &runs))
+            .with_context(|| format!("write {}", path.display()))?;
+        make_executable(path)?;
+        let failed = runs.iter().filter(|run| run.failed == Some(true)).count();
+        eprintln!(
+            "wrote {} command(s) to {} ({failed} failed, commented out)",
+            runs.len(),
+            path.display()
+        );
+    } else if json {
+        let payload = runs
+            .iter()
+            .map(|run| {
+                serde_json::json!({
+                    "event_id": run.event_id,
+                    "timestamp": run.timestamp,
+                    "command": run.command,
+                    "exit_code": run.exit_code,
+                    "failed": run.failed,
+                    "duration_ms": run.duration_ms,
+                })
+            })
+            .collect::<Vec<_>>();
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+    } else {
+        for run in &runs {
+            println!(
+                "{}\t{}\t{}",
+                status_label(run),
+                duration_label(run.duration_ms),
+                run.command.replace('\n', " ⏎ ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Exit code when known, else `ok`/`error` from the tool result, else `-`.
+fn status_label(run: &CommandRun) -> String {
+    match (run.exit_code, run.failed) {
+        (Some(code), _) => code.to_string(),
+        (None, Some(true)) => "error".to_string(),
+        (None, Some(false)) => "ok".to_string(),
+        (None, None) => "-".to_string(),
+    }
+}
+
+fn duration_label(duration_ms: Option<u64>) -> String {
+    match duration_ms {
+        None => "-".to_string(),
+        Some(ms) if ms < 1000 => format!("{ms}ms"),
+        Some(ms) if ms < 60_000 => format!("{:.1}s", ms as f64 / 1000.0),
+        Some(ms) => opensession_core::handoff::format_duration(ms / 1000),
+    }
+}
+
+/// Commands in session order, run from the session's working directory.
+/// Failed commands stay in the script as comments so it runs to completion.
+fn render_script(session: &Session, runs: &[CommandRun]) -> String {
+    let mut script = String::from("#!/usr/bin/env bash\nset -euo pipefail\n\n");
+    script.push_str(&format!(
+        "# Shell commands from session {} ({}), in order.\n",
+        session.session_id, session.agent.tool
+    ));
+    if let Some(cwd) = working_directory(session) {
+        script.push_str(&format!("cd {}\n", shell_quote(cwd)));
+    }
+    for run in runs {
+        script.push('\n');
+        if run.failed == Some(true) {
+            script.push_str(&format!("# failed ({}):\n", status_label(run)));
+            for line in run.command.lines() {
+                script.push_str(&format!("# {line}\n"));
+            }
+        } else {
+            script.push_str(run.command.trim_end());
+            script.push('\n');
+        }
+    }
+    script
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("chmod {}", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{duration_label, render_script};
+    use opensession_core::extract::CommandRun;
+    use opensession_core::{Agent, Session};
+
+    fn run(command: &str, exit_code: Option<i32>, failed: Option<bool>) -> CommandRun {
+        CommandRun {
+            event_id: "e".to_string(),
+            timestamp: chrono::Utc::now(),
+            command: command.to_string(),
+            exit_code,
+            failed,
+            duration_ms: None,
+        }
+    }
+
+    #[test]
+    fn script_comments_out_failed_commands() {
+        let mut session = Session::new(
+            "s1".to_string(),
+            Agent {
+                provider: "anthropic".to_string(),
+                model: "claude".to_string(),
+                tool: "claude-code".to_string(),
+                tool_version: None,
+            },
+        );
+        session
+            .context
+            .attributes
+            .insert("cwd".to_string(), "/work/it's".into());
+        let script = render_script(
+            &session,
+            &[
+                run("npm ci", Some(0), Some(false)),
+                run("npm test\nnpm run lint", None, Some(true)),
+            ],
+        );
+        assert!(script.contains("cd '/work/it'\\''s'\n"));
+        assert!(script.contains("\nnpm ci\n"));
+        assert!(script.contains("# failed (error):\n# npm test\n# npm run lint\n"));
+        assert_eq!(duration_label(Some(1500)), "1.5s");
+        assert_eq!(duration_label(Some(125_000)), "2m 5s");
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use opensession_core::Session;
+use opensession_core::patch::{edit_diffs, file_patch};
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::session_changes::{repo_relative_path, repo_root, session_cwd};
+use crate::user_guidance::guided_error;
+
+/// Render a session's recorded edits as a unified diff series, one
+/// `diff --git` section per edit in session order. Prints to stdout unless
+/// `out` is given; `apply` runs `git apply` in the current directory.
+pub fn export_patch(session: &Session, out: Option<&Path>, apply: bool) -> Result<()> {
+    let cwd = session_cwd(session)?;
+    // The session's checkout may be gone; paths then resolve against its cwd.
+    let root = repo_root(&cwd).unwrap_or_else(|_| cwd.clone());
+    let (diffs, missing) = edit_diffs(&session.events);
+
+    let mut patch = String::new();
+    let mut exported = 0;
+    let mut outside = BTreeSet::new();
+    for (path, hunks) in diffs {
+        match repo_relative_path(path, &cwd, &root) {
+            Some(relative) => {
+                patch.push_str(&file_patch(&relative, hunks));
+                exported += 1;
+            }
+            None => {
+                outside.insert(path);
+            }
+        }
+    }
+    let missing = missing.into_iter().collect::<BTreeSet<_>>();
+    if !missing.is_empty() {
+        eprintln!(
+            "skipped {} file(s) whose edits carry no recorded content: {}",
+            missing.len(),
+            missing.into_iter().collect::<Vec<_>>().join(", ")
+        );
+    }
+    if !outside.is_empty() {
+        eprintln!(
+            "skipped {} file(s) outside {}",
+            outside.len(),
+            root.display()
+        );
+    }
+    if exported == 0 {
+        return Err(guided_error(
+            "session has no file edits with recorded diffs",
+            [
+                "edit diffs are recorded for Claude Code Edit/MultiEdit and Codex apply_patch calls",
+                "use `opensession session changes <ref>` to diff the working tree instead",
+            ],
+        ));
+    }
+
+    match out {
+        Some(path) => {
+            std::fs::write(path, &patch).with_context(|| format!("write {}", path.display()))?;
+            eprintln!("wrote {exported} edit(s) to {}", path.display());
+        }
+        None if !apply => print!("{patch}"),
+        None => {}
+    }
+    if apply {
+        apply_to_worktree(&patch)?;
+        eprintln!("applied {exported} edit(s)");
+    }
+    Ok(())
+}
+
+/// `git apply` the series to the current directory. Hunks claim line 1, so
+/// `--unidiff-zero` lets git place context-free hunks wherever they match.
+fn apply_to_worktree(patch: &str) -> Result<()> {
+    let mut child = Command::new("git")
+        .args(["apply", "--unidiff-zero", "--whitespace=nowarn", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("run git apply")?;
+    child
+        .stdin
+        .take()
+        .context("open git apply stdin")?
+        .write_all(patch.as_bytes())
+        .context("write patch to git apply")?;
+    let output = child.wait_with_output().context("wait for git apply")?;
+    if !output.status.success() {
+        return Err(guided_error(
+            format!(
+                "git apply failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            [
+                "check out the commit the session started from",
+                "write the series with `--out fix.patch` and try `git apply --reject fix.patch`",
+            ],
+        ));
+    }
+    Ok(())
+}
@@ -600,17 +600,8 @@ fn push_failure_hints(reason: &str, remote: &str, target_ref: &str) -> [String;
 }
 
 fn share_metric_path() -> Option<PathBuf> {
-    let home = std::env::var("HOME")
-        .or_else(|_| std::env::var("USERPROFILE"))
-        .ok()?;
-    Some(
-        PathBuf::from(home)
-            .join(".local")
-            .join("share")
-            .join("opensession")
-            .join("metrics")
-            .join("share-funnel.jsonl"),
-    )
+    let data_dir = opensession_paths::data_dir().ok()?;
+    Some(data_dir.join("metrics").join("share-funnel.jsonl"))
 }
 
 fn record_share_metric(event: &str, mode: Option<&str>, reason: Option<&str>) {
@@ -0,0 +1,172 @@
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand, ValueEnum};
+use opensession_local_db::{LocalDb, ShellCommandRecord};
+
+const ZSH_HOOK: &str = r#"# OpenSession shell history hook (zsh)
+zmodload zsh/datetime 2>/dev/null
+_opensession_history_preexec() {
+  _opensession_history_cmd=$1
+  _opensession_history_cwd=$PWD
+  _opensession_history_started=$EPOCHSECONDS
+}
+_opensession_history_precmd() {
+  local exit_code=$?
+  [[ -n $_opensession_history_cmd ]] || return 0
+  command opensession shell-history record --shell zsh --exit-code "$exit_code" \
+    --cwd "$_opensession_history_cwd" --started-at "$_opensession_history_started" \
+    -- "$_opensession_history_cmd" >/dev/null 2>&1 &!
+  unset _opensession_history_cmd
+}
+autoload -Uz add-zsh-hook
+add-zsh-hook preexec _opensession_history_preexec
+add-zsh-hook precmd _opensession_history_precmd
+"#;
+
+const BASH_HOOK: &str = r#"# OpenSession shell history hook (bash)
+_opensession_history_last=$(HISTTIMEFORMAT= builtin history 1)
+_opensession_history_prompt() {
+  local exit_code=$?
+  local entry
+  entry=$(HISTTIMEFORMAT= builtin history 1)
+  if [[ -n $entry && $entry != "$_opensession_history_last" ]]; then
+    _opensession_history_last=$entry
+    entry=$(printf '%s' "$entry" | sed 's/^ *[0-9]* *//')
+    (command opensession shell-history record --shell bash --exit-code "$exit_code" \
+      --cwd "$PWD" -- "$entry" >/dev/null 2>&1 &)
+  fi
+  return $exit_code
+}
+PROMPT_COMMAND="_opensession_history_prompt${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+"#;
+
+#[derive(Debug, Clone, Args)]
+pub struct ShellHistoryArgs {
+    #[command(subcommand)]
+    pub action: ShellHistoryAction,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum ShellHistoryAction {
+    /// Print the hook to source from your shell rc, e.g. `eval "$(opensession shell-history hook zsh)"`.
+    Hook {
+        #[arg(value_enum)]
+        shell: HookShell,
+    },
+    /// Record one command (called by the hook).
+    #[command(hide = true)]
+    Record {
+        #[arg(long)]
+        cwd: String,
+        #[arg(long)]
+        exit_code: Option<i32>,
+        /// Unix seconds; defaults to now.
+        #[arg(long)]
+        started_at: Option<i64>,
+        #[arg(long)]
+        shell: Option<String>,
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+    /// Show recently recorded commands.
+    List {
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Delete recorded commands older than the given number of days.
+    Prune {
+        #[arg(long, default_value_t = 30)]
+        days: i64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum HookShell {
+    Zsh,
+    Bash,
+}
+
+pub fn run(args: ShellHistoryArgs) -> Result<()> {
+    match args.action {
+        ShellHistoryAction::Hook { shell } => {
+            print!(
+                "{}",
+                match shell {
+                    HookShell::Zsh => ZSH_HOOK,
+                    HookShell::Bash => BASH_HOOK,
+                }
+            );
+            Ok(())
+        }
+        ShellHistoryAction::Record {
+            cwd,
+            exit_code,
+            started_at,
+            shell,
+            command,
+        } => {
+            let command = command.join(" ");
+            if !should_record(&command) {
+                return Ok(());
+            }
+            let db = LocalDb::open().context("open local db")?;
+            db.record_shell_command(&ShellCommandRecord {
+                command: command.trim_end().to_string(),
+                cwd,
+                started_at: started_at.unwrap_or_else(|| chrono::Utc::now().timestamp()),
+                exit_code,
+                shell,
+            })
+            .context("record shell command")?;
+            Ok(())
+        }
+        ShellHistoryAction::List { limit } => {
+            let db = LocalDb::open().context("open local db")?;
+            let rows = db
+                .list_shell_commands(limit)
+                .context("list shell commands")?;
+            for row in rows.into_iter().rev() {
+                let record = row.record;
+                let started_at = chrono::DateTime::from_timestamp(record.started_at, 0)
+                    .map(|at| at.to_rfc3339())
+                    .unwrap_or_default();
+                let exit_code = record
+                    .exit_code
+                    .map_or_else(|| "-".to_string(), |code| code.to_string());
+                println!(
+                    "{started_at}\t{exit_code}\t{}\t{}",
+                    record.cwd, record.command
+                );
+            }
+            Ok(())
+        }
+        ShellHistoryAction::Prune { days } => {
+            let db = LocalDb::open().context("open local db")?;
+            let before = chrono::Utc::now().timestamp() - days.max(0) * 86_400;
+            let removed = db
+                .prune_shell_commands(before)
+                .context("prune shell commands")?;
+            println!("removed {removed} shell command(s)");
+            Ok(())
+        }
+    }
+}
+
+/// Commands typed with a leading space stay private, like `HISTCONTROL=ignorespace`.
+fn should_record(command: &str) -> bool {
+    !command.trim().is_empty()
+        && !command.starts_with(char::is_whitespace)
+        && !command.starts_with("opensession shell-history")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::should_record;
+
+    #[test]
+    fn skips_blank_private_and_own_commands() {
+        assert!(should_record("cargo test -p opensession"));
+        assert!(!should_record("   "));
+        assert!(!should_record(" export TOKEN=secret"));
+        assert!(!should_record("opensession shell-history list"));
+    }
+}
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use clap::Args;
+use opensession_runtime_config::{DaemonStatus, SyncState};
+
+/// A session counts as active while its last event is this recent.
+const ACTIVE_WINDOW_SECS: i64 = 10 * 60;
+
+#[derive(Debug, Clone, Args)]
+pub struct StatuslineArgs {
+    /// Print the daemon status snapshot as JSON instead of one line.
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Reads only the daemon's cached status file so prompts stay fast; never
+/// opens the local DB or the network.
+pub fn run(args: StatuslineArgs) -> Result<()> {
+    let status = opensession_paths::daemon_status_path()
+        .ok()
+        .and_then(|path| DaemonStatus::read(&path));
+    let now = chrono::Utc::now().timestamp();
+    if args.json {
+        let value = match &status {
+            Some(status) => serde_json::to_value(status)?,
+            None => serde_json::Value::Null,
+        };
+        println!("{value}");
+        return Ok(());
+    }
+    println!("{}", render(status.as_ref(), now));
+    Ok(())
+}
+
+fn render(status: Option<&DaemonStatus>, now: i64) -> String {
+    let Some(status) = status.filter(|status| status.is_fresh(now)) else {
+        return "os: off".to_string();
+    };
+
+    let mut parts = Vec::new();
+    match status
+        .active_session
+        .as_ref()
+        .filter(|active| now - active.last_event_at <= ACTIVE_WINDOW_SECS)
+    {
+        Some(active) => {
+            parts.push(active.tool.clone());
+            parts.push(format_elapsed(now - active.started_at));
+            parts.push(format!(
+                "{} tok",
+                format_tokens(active.input_tokens + active.output_tokens)
+            ));
+        }
+        None => parts.push("idle".to_string()),
+    }
+
+    let sync = if status.pending_files > 0 {
+        Some(format!("↑{}", status.pending_files))
+    } else {
+        match status.sync {
+            SyncState::Idle => None,
+            SyncState::Synced => Some("synced".to_string()),
+            SyncState::LocalOnly => Some("local".to_string()),
+            SyncState::Error => Some("sync error".to_string()),
+        }
+    };
+    parts.extend(sync);
+    format!("os: {}", parts.join(" · "))
+}
+
+fn format_elapsed(secs: i64) -> String {
+    let secs = secs.max(0);
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        _ => format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60),
+    }
+}
+
+fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        0..1_000 => tokens.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", tokens as f64 / 1_000.0),
+        _ => format!("{:.1}M", tokens as f64 / 1_000_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opensession_runtime_config::ActiveSessionStatus;
+
+    fn status(now: i64) -> DaemonStatus {
+        DaemonStatus {
+            pid: 1,
+            updated_at: now - 5,
+            active_session: Some(ActiveSessionStatus {
+                session_id: "s1".into(),
+                tool: "codex".into(),
+                model: None,
+                started_at: now - 754,
+                last_event_at: now - 20,
+                input_tokens: 40_000,
+                output_tokens: 5_200,
+            }),
+            pending_files: 0,
+            sync: SyncState::Synced,
+            last_synced_at: Some(now - 30),
+            last_error: None,
+            body_cache: None,
+        }
+    }
+
+    #[test]
+    fn renders_active_session_and_sync_state() {
+        let now = 10_000;
+        assert_eq!(
+            render(Some(&status(now)), now),
+            "os: codex · 12m · 45.2k tok · synced"
+        );
+
+        let mut pending = status(now);
+        pending.pending_files = 2;
+        assert_eq!(
+            render(Some(&pending), now),
+            "os: codex · 12m · 45.2k tok · ↑2"
+        );
+    }
+
+    #[test]
+    fn stale_or_missing_status_means_daemon_off() {
+        let now = 10_000;
+        assert_eq!(render(None, now), "os: off");
+        let mut stale = status(now);
+        stale.updated_at = now - 3_600;
+        assert_eq!(render(Some(&stale), now), "os: off");
+    }
+
+    #[test]
+    fn quiet_sessions_render_as_idle() {
+        let now = 10_000;
+        let mut idle = status(now);
+        idle.active_session.as_mut().expect("active").last_event_at = now - 3_600;
+        idle.sync = SyncState::LocalOnly;
+        assert_eq!(render(Some(&idle), now), "os: idle · local");
+    }
+
+    #[test]
+    fn formats_durations_and_token_counts() {
+        assert_eq!(format_elapsed(42), "42s");
+        assert_eq!(format_elapsed(3_900), "1h05m");
+        assert_eq!(format_tokens(999), "999");
+        assert_eq!(format_tokens(1_260_000), "1.3M");
+    }
+}
@@ -2,13 +2,14 @@ use crate::runtime_settings::load_runtime_config;
 use anyhow::{Context, Result, anyhow};
 use clap::{Args, Subcommand};
 use opensession_core::session::working_directory;
+use opensession_core::trace::Session;
 use opensession_git_native::extract_git_context;
 use opensession_local_db::{LocalDb, SessionSemanticSummaryUpsert};
 use opensession_local_store::find_repo_root;
 use opensession_parsers::ParserRegistry;
-use opensession_summary::{GitSummaryRequest, SemanticSummaryArtifact};
+use opensession_summary::{CachedSemanticSummary, GitSummaryRequest, SemanticSummaryArtifact};
 use opensession_summary_runtime::{
-    summarize_git_commit, summarize_git_working_tree, summarize_session,
+    summarize_git_commit, summarize_git_working_tree, summarize_session_cached,
 };
 use serde::Serialize;
 use std::path::{Path, PathBuf};
@@ -47,6 +48,9 @@ pub struct SummaryRunArgs {
     /// Skip local DB persistence.
     #[arg(long)]
     pub no_store: bool,
+    /// Ignore a cached summary and call the provider again.
+    #[arg(long)]
+    pub refresh: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -113,16 +117,25 @@ async fn run_generate(args: SummaryRunArgs) -> Result<()> {
     let parser_registry = ParserRegistry::default();
 
     if let Some(file) = args.file.as_deref() {
-        let artifact = run_from_file(file, settings).await?;
+        let session = parser_registry
+            .parse_path(file)
+            .with_context(|| format!("parse session file {}", file.display()))?
+            .ok_or_else(|| anyhow!("unsupported session source format"))?;
+        let db = if settings.persists_to_local_db() {
+            Some(LocalDb::open().context("open local db")?)
+        } else {
+            None
+        };
+        let cached = match db.as_ref() {
+            Some(db) if !args.refresh => cached_summary(db, &session.session_id)?,
+            _ => None,
+        };
+
+        let artifact = run_from_session(&session, settings, cached.as_ref()).await?;
         println!("{}", serde_json::to_string_pretty(&artifact)?);
 
-        if !args.no_store && settings.persists_to_local_db() {
-            let session = parser_registry
-                .parse_path(file)
-                .with_context(|| format!("parse session file {}", file.display()))?
-                .ok_or_else(|| anyhow!("unsupported session source format"))?;
-            let db = LocalDb::open().context("open local db")?;
-            store_artifact(&db, &session.session_id, &artifact)
+        if let Some(db) = db.as_ref().filter(|_| !args.no_store) {
+            store_artifact(db, &session.session_id, &artifact)
                 .context("persist summary artifact")?;
         }
         return Ok(());
@@ -147,31 +160,42 @@ async fn run_generate(args: SummaryRunArgs) -> Result<()> {
     Ok(())
 }
 
-async fn run_from_file(
-    path: &Path,
+async fn run_from_session(
+    session: &Session,
     settings: &opensession_runtime_config::SummarySettings,
+    cached: Option<&CachedSemanticSummary>,
 ) -> Result<SemanticSummaryArtifact> {
-    let session = ParserRegistry::default()
-        .parse_path(path)
-        .with_context(|| format!("parse session file {}", path.display()))?
-        .ok_or_else(|| anyhow!("unsupported session source format"))?;
-
     let git_request = if settings.allows_git_changes_fallback() {
-        working_directory(&session)
+        working_directory(session)
             .and_then(|cwd| find_repo_root(Path::new(cwd)))
             .map(|repo_root| GitSummaryRequest {
                 repo_root,
-                commit: working_directory(&session).and_then(|cwd| extract_git_context(cwd).commit),
+                commit: working_directory(session).and_then(|cwd| extract_git_context(cwd).commit),
             })
     } else {
         None
     };
 
-    summarize_session(&session, settings, git_request.as_ref())
+    summarize_session_cached(session, settings, git_request.as_ref(), cached)
         .await
         .map_err(anyhow::Error::msg)
 }
 
+fn cached_summary(db: &LocalDb, session_id: &str) -> Result<Option<CachedSemanticSummary>> {
+    let row = db
+        .get_session_semantic_summary(session_id)
+        .context("query cached summary")?;
+    Ok(row.and_then(|row| {
+        CachedSemanticSummary::from_stored(
+            &row.provider,
+            row.model.as_deref(),
+            &row.generation_kind,
+            row.prompt_fingerprint.as_deref(),
+            &row.summary_json,
+        )
+    }))
+}
+
 fn resolve_repo_root(repo: Option<&Path>) -> Result<PathBuf> {
     if let Some(path) = repo {
         let candidate = if path.is_absolute() {
@@ -0,0 +1,210 @@
+//! Starting agent CLIs with a handoff pre-loaded.
+//!
+//! Built-in defaults cover the tools OpenSession parses; `[tools.<name>]` in
+//! `opensession.toml` overrides them field by field or adds new tools.
+
+use anyhow::{Context, Result, bail};
+use opensession_runtime_config::{PromptInjection, ToolSettings};
+use std::path::Path;
+use std::process::Command;
+
+/// How to start one agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ToolLauncher {
+    binary: String,
+    resume_args: Option<Vec<String>>,
+    prompt: PromptInjection,
+    prompt_flag: Option<String>,
+}
+
+/// A resolved program and argument list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Launch {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl ToolLauncher {
+    /// Launcher for `tool`: the built-in default with `settings` applied on
+    /// top. `None` when the tool is neither built in nor given a `binary`.
+    pub(crate) fn resolve(tool: &str, settings: &ToolSettings) -> Option<Self> {
+        let configured = settings.get(tool);
+        let mut launcher = match (builtin(tool), configured) {
+            (Some(builtin), _) => builtin,
+            (None, Some(entry)) => Self {
+                binary: entry.binary.clone()?,
+                resume_args: None,
+                prompt: PromptInjection::Argument,
+                prompt_flag: None,
+            },
+            (None, None) => return None,
+        };
+        if let Some(entry) = configured {
+            if let Some(binary) = &entry.binary {
+                launcher.binary = binary.clone();
+            }
+            if let Some(resume_args) = &entry.resume_args {
+                launcher.resume_args = (!resume_args.is_empty()).then(|| resume_args.clone());
+            }
+            if let Some(prompt) = entry.prompt {
+                launcher.prompt = prompt;
+            }
+            if let Some(flag) = &entry.prompt_flag {
+                launcher.prompt_flag = Some(flag.clone());
+            }
+        }
+        Some(launcher)
+    }
+
+    /// Start a new session that begins from `handoff`; `prompt` is the first
+    /// message for tools that take the handoff as a prompt.
+    pub(crate) fn with_handoff(&self, handoff: &Path, prompt: &str) -> Launch {
+        let value = match self.prompt {
+            PromptInjection::Argument => prompt.to_string(),
+            PromptInjection::File => handoff.display().to_string(),
+        };
+        let mut args: Vec<String> = self.prompt_flag.iter().cloned().collect();
+        args.push(value);
+        Launch {
+            program: self.binary.clone(),
+            args,
+        }
+    }
+
+    /// Reopen the tool's own session, when it supports that.
+    pub(crate) fn resume(&self, session_id: &str) -> Option<Launch> {
+        let args = self
+            .resume_args
+            .as_ref()?
+            .iter()
+            .map(|arg| arg.replace("{session_id}", session_id))
+            .collect();
+        Some(Launch {
+            program: self.binary.clone(),
+            args,
+        })
+    }
+}
+
+fn builtin(tool: &str) -> Option<ToolLauncher> {
+    let (binary, resume_args, prompt_flag): (&str, &[&str], Option<&str>) = match tool {
+        "claude-code" => ("claude", &["--resume", "{session_id}"], None),
+        "codex" => ("codex", &["resume", "{session_id}"], None),
+        "gemini" => ("gemini", &[], Some("-i")),
+        "opencode" => ("opencode", &[], Some("--prompt")),
+        _ => return None,
+    };
+    Some(ToolLauncher {
+        binary: binary.to_string(),
+        resume_args: (!resume_args.is_empty())
+            .then(|| resume_args.iter().map(|arg| arg.to_string()).collect()),
+        prompt: PromptInjection::Argument,
+        prompt_flag: prompt_flag.map(str::to_string),
+    })
+}
+
+impl Launch {
+    /// Run in the foreground with inherited stdio.
+    pub(crate) fn run(&self) -> Result<()> {
+        let status = Command::new(&self.program)
+            .args(&self.args)
+            .status()
+            .with_context(|| format!("start `{}`", self.program))?;
+        if !status.success() {
+            bail!("`{}` exited with {status}", self.program);
+        }
+        Ok(())
+    }
+
+    /// The command as a shell line, for printing.
+    pub(crate) fn shell_line(&self) -> String {
+        std::iter::once(&self.program)
+            .chain(&self.args)
+            .map(|arg| {
+                if !arg.is_empty()
+                    && arg
+                        .chars()
+                        .all(|ch| ch.is_ascii_alphanumeric() || "-_./=:".contains(ch))
+                {
+                    arg.clone()
+                } else {
+                    format!("'{}'", arg.replace('\'', r"'\''"))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(source: &str) -> ToolSettings {
+        toml::from_str(source).expect("parse tool settings")
+    }
+
+    #[test]
+    fn builtins_resolve_and_config_overrides_fields() {
+        let handoff = Path::new("/tmp/h.md");
+        let none = ToolSettings::default();
+
+        let gemini = ToolLauncher::resolve("gemini", &none).expect("gemini");
+        assert_eq!(gemini.with_handoff(handoff, "go").args, ["-i", "go"]);
+        assert!(gemini.resume("abc").is_none());
+        assert_eq!(
+            ToolLauncher::resolve("codex", &none)
+                .and_then(|codex| codex.resume("abc"))
+                .map(|launch| launch.args),
+            Some(vec!["resume".to_string(), "abc".to_string()])
+        );
+        assert!(ToolLauncher::resolve("cursor", &none).is_none());
+
+        let configured = settings(
+            r#"
+[claude-code]
+binary = "/opt/claude"
+
+[codex]
+resume_args = []
+
+[aider]
+binary = "aider"
+prompt = "file"
+prompt_flag = "--read"
+
+[broken]
+prompt = "file"
+"#,
+        );
+        let claude = ToolLauncher::resolve("claude-code", &configured).expect("claude");
+        assert_eq!(
+            claude.resume("abc"),
+            Some(Launch {
+                program: "/opt/claude".to_string(),
+                args: vec!["--resume".to_string(), "abc".to_string()],
+            })
+        );
+        assert!(
+            ToolLauncher::resolve("codex", &configured)
+                .expect("codex")
+                .resume("abc")
+                .is_none()
+        );
+        let aider = ToolLauncher::resolve("aider", &configured).expect("aider");
+        assert_eq!(
+            aider.with_handoff(handoff, "go").args,
+            ["--read", "/tmp/h.md"]
+        );
+        assert!(ToolLauncher::resolve("broken", &configured).is_none());
+    }
+
+    #[test]
+    fn shell_line_quotes_arguments_with_spaces() {
+        let launch = Launch {
+            program: "claude".to_string(),
+            args: vec!["--resume".to_string(), "it's done".to_string()],
+        };
+        assert_eq!(launch.shell_line(), r"claude --resume 'it'\''s done'");
+    }
+}
@@ -13,6 +13,7 @@ use opensession_core::{
     Session,
     source_uri::{SourceSpec, SourceUri},
 };
+use opensession_local_db::LocalDb;
 use opensession_local_store::read_local_object_from_uri;
 use reqwest::Url;
 use serde::Deserialize;
@@ -292,7 +293,7 @@ async fn view_source_uri(uri: SourceUri, args: &ViewArgs) -> Result<()> {
         SourceUri::Src(SourceSpec::Local { .. }) => {
             let cwd = resolve_working_dir(args.repo.as_deref())?;
             let (_path, bytes) = read_local_object_from_uri(&uri, &cwd)?;
-            let session = decode_session_bytes(&bytes)?;
+            let session = with_shell_history(decode_session_bytes(&bytes)?);
             let bundle = build_bundle_from_sessions("local source uri", vec![session]);
             let review_id = bundle.review_id.clone();
             let repo_root = resolve_runtime_root(args.repo.as_deref())?;
@@ -334,7 +335,7 @@ async fn view_source_uri(uri: SourceUri, args: &ViewArgs) -> Result<()> {
 
 async fn view_jsonl_file(path: &Path, args: &ViewArgs) -> Result<()> {
     let bytes = fs::read(path).with_context(|| format!("read {}", path.display()))?;
-    let session = decode_session_bytes(&bytes)?;
+    let session = with_shell_history(decode_session_bytes(&bytes)?);
     let label = format!("file:{}", path.display());
     let bundle = build_bundle_from_sessions(&label, vec![session]);
     let review_id = bundle.review_id.clone();
@@ -445,6 +446,15 @@ fn decode_session_bytes(bytes: &[u8]) -> Result<Session> {
         .or_else(|_| serde_json::from_str(&text).context("parse session JSON"))
 }
 
+/// Interleave commands recorded by the opt-in shell hook; viewing still works
+/// when the local DB is unavailable.
+fn with_shell_history(mut session: Session) -> Session {
+    if let Ok(db) = LocalDb::open() {
+        let _ = db.interleave_shell_history(&mut session);
+    }
+    session
+}
+
 fn build_bundle_from_sessions(label: &str, sessions: Vec<Session>) -> LocalReviewBundle {
     let review_id = format!(
         "local-{}",
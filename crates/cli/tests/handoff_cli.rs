@@ -332,6 +332,8 @@ mod inspect_cli;
 mod parse_cli;
 #[path = "handoff_cli/review_cli.rs"]
 mod review_cli;
+#[path = "handoff_cli/session_cli.rs"]
+mod session_cli;
 #[path = "handoff_cli/setup_cli.rs"]
 mod setup_cli;
 #[path = "handoff_cli/share_cli.rs"]
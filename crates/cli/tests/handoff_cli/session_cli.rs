@@ -0,0 +1,230 @@
+use super::*;
+
+fn make_editing_session(repo: &Path, edits: &[EventType]) -> String {
+    let mut session = Session::new(
+        "s-changes".to_string(),
+        Agent {
+            provider: "anthropic".to_string(),
+            model: "claude-opus-4-6".to_string(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+        },
+    );
+    // Start after the baseline commit so it is the one picked as the base.
+    let start = chrono::Utc::now() + chrono::Duration::seconds(2);
+    session.context.created_at = start;
+    session
+        .context
+        .attributes
+        .insert("cwd".to_string(), Value::String(repo.display().to_string()));
+    for (index, event_type) in edits.iter().enumerate() {
+        session.events.push(Event {
+            event_id: format!("e{index}"),
+            timestamp: start + chrono::Duration::seconds(index as i64),
+            event_type: event_type.clone(),
+            task_id: None,
+            content: Content::empty(),
+            duration_ms: None,
+            attributes: Default::default(),
+        });
+    }
+    session.recompute_stats();
+    session.to_jsonl().expect("to jsonl")
+}
+
+#[test]
+fn session_changes_shows_net_diff_of_touched_files() {
+    let tmp = make_home();
+    let repo = tmp.path().join("repo");
+    init_git_repo(&repo);
+    write_file(&repo.join("reverted.txt"), "keep\n");
+    write_file(&repo.join("untouched.txt"), "before\n");
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "baseline"]);
+
+    let session = make_editing_session(
+        &repo,
+        &[
+            EventType::FileEdit {
+                path: repo.join("README.md").display().to_string(),
+                diff: None,
+            },
+            EventType::FileEdit {
+                path: "reverted.txt".to_string(),
+                diff: None,
+            },
+            EventType::FileCreate {
+                path: "notes/new.txt".to_string(),
+            },
+        ],
+    );
+    let input = tmp.path().join("session.hail.jsonl");
+    write_file(&input, &session);
+
+    // The agent's net effect: README changed, a new file, reverted.txt back
+    // to its original content. untouched.txt changes outside the session.
+    write_file(&repo.join("README.md"), "repo\nagent line\n");
+    write_file(&repo.join("notes/new.txt"), "fresh\n");
+    write_file(&repo.join("untouched.txt"), "after\n");
+
+    let out = run(
+        tmp.path(),
+        &repo,
+        &["session", "changes", input.to_str().expect("path")],
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "stderr: {stderr}");
+    assert!(stdout.contains("+agent line"), "stdout: {stdout}");
+    assert!(stdout.contains("notes/new.txt"), "stdout: {stdout}");
+    assert!(!stdout.contains("reverted.txt"), "stdout: {stdout}");
+    assert!(!stdout.contains("untouched.txt"), "stdout: {stdout}");
+    assert!(
+        stderr.contains("2 of 3 touched file(s) differ"),
+        "stderr: {stderr}"
+    );
+
+    let stat = run(
+        tmp.path(),
+        &repo,
+        &[
+            "session",
+            "changes",
+            input.to_str().expect("path"),
+            "--stat",
+            "--base",
+            "HEAD",
+        ],
+    );
+    assert!(stat.status.success());
+    let stdout = String::from_utf8_lossy(&stat.stdout);
+    assert!(stdout.contains("README.md | 1 +"), "stdout: {stdout}");
+}
+
+#[test]
+fn session_patch_exports_and_applies_recorded_edits() {
+    use opensession_core::patch::replacement_hunk;
+
+    let tmp = make_home();
+    let repo = tmp.path().join("repo");
+    init_git_repo(&repo);
+    write_file(
+        &repo.join("src/lib.rs"),
+        "fn a() {}\nfn b() {}\nfn c() {}\n",
+    );
+    run_git(&repo, &["add", "."]);
+    run_git(&repo, &["commit", "-m", "baseline"]);
+
+    let session = make_editing_session(
+        &repo,
+        &[
+            EventType::FileEdit {
+                path: repo.join("src/lib.rs").display().to_string(),
+                diff: Some(replacement_hunk("fn b() {}\n", "fn b() { todo!() }\n")),
+            },
+            EventType::FileEdit {
+                path: "notes/new.md".to_string(),
+                diff: Some("@@ -0,0 +1,1 @@\n+hello\n".to_string()),
+            },
+            EventType::FileEdit {
+                path: "src/lib.rs".to_string(),
+                diff: Some(replacement_hunk("fn c() {}", "fn c() {}\nfn d() {}")),
+            },
+            EventType::FileCreate {
+                path: "written.txt".to_string(),
+            },
+        ],
+    );
+    let input = tmp.path().join("session.hail.jsonl");
+    write_file(&input, &session);
+    let input = input.to_str().expect("path");
+
+    let patch_path = tmp.path().join("fix.patch");
+    let out = run(
+        tmp.path(),
+        &repo,
+        &[
+            "session",
+            "patch",
+            input,
+            "--out",
+            patch_path.to_str().expect("path"),
+        ],
+    );
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "stderr: {stderr}");
+    assert!(stderr.contains("wrote 3 edit(s)"), "stderr: {stderr}");
+    assert!(stderr.contains("written.txt"), "stderr: {stderr}");
+    let patch = fs::read_to_string(&patch_path).expect("read patch");
+    assert_eq!(
+        patch
+            .matches("diff --git a/src/lib.rs b/src/lib.rs")
+            .count(),
+        2
+    );
+    assert!(patch.contains("new file mode 100644\n--- /dev/null\n+++ b/notes/new.md\n"));
+
+    let applied = run(tmp.path(), &repo, &["session", "patch", input, "--apply"]);
+    assert!(
+        applied.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&applied.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(repo.join("src/lib.rs")).expect("read lib.rs"),
+        "fn a() {}\nfn b() { todo!() }\nfn c() {}\nfn d() {}\n"
+    );
+    assert_eq!(
+        fs::read_to_string(repo.join("notes/new.md")).expect("read new.md"),
+        "hello\n"
+    );
+}
+
+#[test]
+fn session_commands_lists_runs_and_writes_a_script() {
+    let tmp = make_home();
+    let repo = tmp.path().join("repo");
+    init_git_repo(&repo);
+    let session = make_editing_session(
+        &repo,
+        &[
+            EventType::ShellCommand {
+                command: "cargo build".to_string(),
+                exit_code: Some(0),
+            },
+            EventType::ShellCommand {
+                command: "cargo test".to_string(),
+                exit_code: Some(101),
+            },
+        ],
+    );
+    let input = tmp.path().join("session.hail.jsonl");
+    write_file(&input, &session);
+    let input = input.to_str().expect("path");
+
+    let out = run(tmp.path(), &repo, &["session", "commands", input]);
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        vec!["0\t-\tcargo build", "101\t-\tcargo test"]
+    );
+
+    let script = tmp.path().join("setup.sh");
+    let out = run(
+        tmp.path(),
+        &repo,
+        &[
+            "session",
+            "commands",
+            input,
+            "--script",
+            script.to_str().expect("path"),
+        ],
+    );
+    assert!(out.status.success());
+    let body = fs::read_to_string(&script).expect("read script");
+    assert!(body.starts_with("#!/usr/bin/env bash\n"));
+    assert!(body.contains("\ncargo build\n"));
+    assert!(body.contains("# failed (101):\n# cargo test\n"));
+}
@@ -0,0 +1,270 @@
+//! Structure-preserving anonymization for sharing sessions in bug reports.
+//!
+//! The output has the same events, event types, tasks, tool names, timing and
+//! text lengths as the input, but paths, identifiers and content are replaced
+//! with deterministic placeholders. The agent (provider/model/tool) and
+//! `source.*` attributes are kept because parser and UI bugs depend on them.
+
+use crate::trace::{
+    ATTR_SEMANTIC_CALL_ID, ATTR_SEMANTIC_GROUP_ID, ATTR_SEMANTIC_TOOL_KIND, ContentBlock, Event,
+    EventType, Session,
+};
+use std::collections::HashMap;
+
+/// Return an anonymized copy of `session`.
+///
+/// The same input always produces the same output, and a value that repeats
+/// in the input (a path, a task id, a tool call id) maps to the same
+/// placeholder everywhere, so correlations survive.
+pub fn anonymize_session(session: &Session) -> Session {
+    let mut anon = Anonymizer::default();
+    let mut out = session.clone();
+
+    out.session_id = anon.id("session", &session.session_id);
+    let context = &mut out.context;
+    context.title = context.title.as_deref().map(mask_text);
+    context.description = context.description.as_deref().map(mask_text);
+    context.tags = context.tags.iter().map(|tag| anon.id("tag", tag)).collect();
+    context.related_session_ids = context
+        .related_session_ids
+        .iter()
+        .map(|id| anon.id("session", id))
+        .collect();
+    for value in context.attributes.values_mut() {
+        mask_json(value);
+    }
+
+    for event in &mut out.events {
+        anonymize_event(event, &mut anon);
+    }
+    out
+}
+
+fn anonymize_event(event: &mut Event, anon: &mut Anonymizer) {
+    event.event_id = anon.id("event", &event.event_id);
+    event.task_id = event.task_id.as_deref().map(|id| anon.id("task", id));
+
+    match &mut event.event_type {
+        EventType::UserMessage
+        | EventType::AgentMessage
+        | EventType::SystemMessage
+        | EventType::Thinking
+        | EventType::ToolCall { .. }
+        | EventType::Custom { .. } => {}
+        EventType::ToolResult { call_id, .. } => {
+            *call_id = call_id.as_deref().map(|id| anon.id("call", id));
+        }
+        EventType::FileRead { path }
+        | EventType::FileCreate { path }
+        | EventType::FileDelete { path } => *path = anon.path(path),
+        EventType::FileEdit { path, diff } => {
+            *path = anon.path(path);
+            *diff = diff.as_deref().map(mask_text);
+        }
+        EventType::CodeSearch { query } | EventType::WebSearch { query } => {
+            *query = mask_text(query)
+        }
+        EventType::FileSearch { pattern } => *pattern = mask_text(pattern),
+        EventType::ShellCommand { command, .. } => *command = mask_text(command),
+        EventType::ImageGenerate { prompt }
+        | EventType::VideoGenerate { prompt }
+        | EventType::AudioGenerate { prompt } => *prompt = mask_text(prompt),
+        EventType::WebFetch { url } => *url = anon.url(url),
+        EventType::TaskStart { title } => *title = title.as_deref().map(mask_text),
+        EventType::TaskEnd { summary } => *summary = summary.as_deref().map(mask_text),
+    }
+
+    for block in &mut event.content.blocks {
+        match block {
+            ContentBlock::Text { text } => *text = mask_text(text),
+            ContentBlock::Code { code, .. } => *code = mask_text(code),
+            ContentBlock::Image { url, alt, .. } => {
+                *url = anon.url(url);
+                *alt = alt.as_deref().map(mask_text);
+            }
+            ContentBlock::Video { url, .. } | ContentBlock::Audio { url, .. } => {
+                *url = anon.url(url)
+            }
+            ContentBlock::File { path, content } => {
+                *path = anon.path(path);
+                *content = content.as_deref().map(mask_text);
+            }
+            ContentBlock::Json { data } => mask_json(data),
+            ContentBlock::Reference { uri, .. } => *uri = anon.url(uri),
+        }
+    }
+
+    for (key, value) in &mut event.attributes {
+        if key.starts_with("source.") || key == ATTR_SEMANTIC_TOOL_KIND {
+            continue;
+        }
+        match (key.as_str(), value.as_str()) {
+            (ATTR_SEMANTIC_CALL_ID, Some(id)) => *value = anon.id("call", id).into(),
+            (ATTR_SEMANTIC_GROUP_ID, Some(id)) => *value = anon.id("group", id).into(),
+            _ => mask_json(value),
+        }
+    }
+}
+
+/// Assigns `kind-N` placeholders in first-seen order.
+#[derive(Default)]
+struct Anonymizer {
+    seen: HashMap<(&'static str, String), String>,
+    counters: HashMap<&'static str, usize>,
+}
+
+impl Anonymizer {
+    fn id(&mut self, kind: &'static str, original: &str) -> String {
+        self.placeholder(kind, original, |n| format!("{kind}-{n}"))
+    }
+
+    /// `path/N.ext`, keeping the extension so language detection still works.
+    fn path(&mut self, original: &str) -> String {
+        let ext = std::path::Path::new(original)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .filter(|ext| ext.len() <= 8 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+            .map(str::to_string);
+        self.placeholder("path", original, |n| match &ext {
+            Some(ext) => format!("path/{n}.{ext}"),
+            None => format!("path/{n}"),
+        })
+    }
+
+    fn url(&mut self, original: &str) -> String {
+        self.placeholder("url", original, |n| format!("https://example.invalid/{n}"))
+    }
+
+    fn placeholder(
+        &mut self,
+        kind: &'static str,
+        original: &str,
+        render: impl FnOnce(usize) -> String,
+    ) -> String {
+        if let Some(existing) = self.seen.get(&(kind, original.to_string())) {
+            return existing.clone();
+        }
+        let counter = self.counters.entry(kind).or_insert(0);
+        *counter += 1;
+        let value = render(*counter);
+        self.seen
+            .insert((kind, original.to_string()), value.clone());
+        value
+    }
+}
+
+/// Replace letters with `x` and digits with `0`, keeping whitespace and ASCII
+/// punctuation so line structure, code shape and length are preserved.
+pub fn mask_text(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii_digit() {
+                '0'
+            } else if c.is_whitespace() || c.is_ascii_punctuation() {
+                c
+            } else {
+                'x'
+            }
+        })
+        .collect()
+}
+
+/// Mask every string in a JSON value, keeping keys, numbers and shape.
+fn mask_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(text) => *text = mask_text(text),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(mask_json),
+        serde_json::Value::Object(map) => map.values_mut().for_each(mask_json),
+        serde_json::Value::Null | serde_json::Value::Bool(_) | serde_json::Value::Number(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{self, SessionGenerator};
+    use crate::{ATTR_SOURCE_RAW_TYPE, Content};
+
+    #[test]
+    fn mask_text_preserves_shape() {
+        assert_eq!(
+            mask_text("fn main() { let a = 42; }"),
+            "xx xxxx() { xxx x = 00; }"
+        );
+        assert_eq!(mask_text("한글\nok"), "xx\nxx");
+    }
+
+    #[test]
+    fn anonymize_keeps_structure_and_correlations() {
+        let mut session = SessionGenerator::new(9)
+            .events(60)
+            .error_rate(0.3)
+            .generate();
+        session.context.tags = vec!["secret-project".to_string()];
+        session.events[0]
+            .attributes
+            .insert(ATTR_SOURCE_RAW_TYPE.to_string(), "user".into());
+        session.events[0]
+            .attributes
+            .insert("cwd".to_string(), "/home/alice/acme".into());
+
+        let anon = anonymize_session(&session);
+        assert_eq!(anon.events.len(), session.events.len());
+        assert_eq!(anon.session_id, "session-1");
+        assert_eq!(anon.context.tags, vec!["tag-1"]);
+        assert_eq!(anon.events[0].attributes[ATTR_SOURCE_RAW_TYPE], "user");
+        assert_eq!(anon.events[0].attributes["cwd"], "/xxxx/xxxxx/xxxx");
+
+        let mut paths = HashMap::new();
+        for (before, after) in session.events.iter().zip(&anon.events) {
+            assert_eq!(before.timestamp, after.timestamp);
+            assert_eq!(
+                serde_json::to_value(&before.event_type).unwrap()["type"],
+                serde_json::to_value(&after.event_type).unwrap()["type"]
+            );
+            if let (EventType::FileRead { path: a }, EventType::FileRead { path: b }) =
+                (&before.event_type, &after.event_type)
+            {
+                assert!(b.starts_with("path/") && b.ends_with(".rs"));
+                assert_eq!(paths.entry(a.clone()).or_insert_with(|| b.clone()), b);
+            }
+        }
+        assert!(
+            !serde_json::to_string(&anon)
+                .unwrap()
+                .contains("src/module_")
+        );
+        assert_eq!(
+            serde_json::to_string(&anonymize_session(&session)).unwrap(),
+            serde_json::to_string(&anon).unwrap(),
+            "anonymization must be deterministic"
+        );
+    }
+
+    #[test]
+    fn anonymize_replaces_urls_and_ids() {
+        let mut session = Session::new("real-id".to_string(), testing::agent());
+        let mut event = testing::event(
+            EventType::WebFetch {
+                url: "https://intranet.acme.test/doc".to_string(),
+            },
+            "",
+        );
+        event.content = Content::text("see ticket ACME-1234");
+        event.task_id = Some("t-real".to_string());
+        session.events.push(event);
+
+        let anon = anonymize_session(&session);
+        let event = &anon.events[0];
+        assert!(matches!(
+            &event.event_type,
+            EventType::WebFetch { url } if url == "https://example.invalid/1"
+        ));
+        assert_eq!(event.task_id.as_deref(), Some("task-1"));
+        assert_eq!(event.event_id, "event-1");
+        assert!(matches!(
+            &event.content.blocks[0],
+            ContentBlock::Text { text } if text == "xxx xxxxxx xxxx-0000"
+        ));
+    }
+}
@@ -1,4 +1,6 @@
 use crate::{ContentBlock, Event, EventType, Session};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 
 /// Metadata extracted from a session for DB storage at upload time.
 #[derive(Debug, Clone)]
@@ -173,6 +175,76 @@ pub fn extract_changed_paths(events: &[Event]) -> (Vec<String>, Vec<String>) {
     (modified, deleted)
 }
 
+/// A shell command the agent ran, paired with its tool result when there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandRun {
+    pub event_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub command: String,
+    pub exit_code: Option<i32>,
+    /// Whether the command failed, from the exit code or the result's error flag.
+    pub failed: Option<bool>,
+    pub duration_ms: Option<u64>,
+}
+
+/// Shell commands in session order. Exit codes and durations come from the
+/// command event, else from the `ToolResult` whose `call_id` names it (an
+/// `Exit code N` first line, and the gap between the two timestamps).
+pub fn extract_command_runs(events: &[Event]) -> Vec<CommandRun> {
+    let mut runs: Vec<CommandRun> = Vec::new();
+    let mut by_event_id = HashMap::new();
+    for event in events {
+        match &event.event_type {
+            EventType::ShellCommand { command, exit_code } => {
+                by_event_id.insert(event.event_id.as_str(), runs.len());
+                runs.push(CommandRun {
+                    event_id: event.event_id.clone(),
+                    timestamp: event.timestamp,
+                    command: command.clone(),
+                    exit_code: *exit_code,
+                    failed: exit_code.map(|code| code != 0),
+                    duration_ms: event.duration_ms,
+                });
+            }
+            EventType::ToolResult {
+                is_error,
+                call_id: Some(call_id),
+                ..
+            } => {
+                let Some(run) = by_event_id
+                    .get(call_id.as_str())
+                    .and_then(|index| runs.get_mut(*index))
+                else {
+                    continue;
+                };
+                if run.exit_code.is_none() {
+                    run.exit_code = extract_text_from_blocks(&event.content.blocks)
+                        .as_deref()
+                        .and_then(leading_exit_code);
+                }
+                run.failed = run.exit_code.map(|code| code != 0).or(Some(*is_error));
+                run.duration_ms = run.duration_ms.or(event.duration_ms).or_else(|| {
+                    u64::try_from((event.timestamp - run.timestamp).num_milliseconds()).ok()
+                });
+            }
+            _ => {}
+        }
+    }
+    runs
+}
+
+/// `Exit code 2` / `Exit code: 2` / `Process exited with code 2` on the first
+/// lines of a command result.
+fn leading_exit_code(text: &str) -> Option<i32> {
+    text.lines().take(4).find_map(|line| {
+        let line = line.trim();
+        line.strip_prefix("Exit code")
+            .map(|rest| rest.trim_start_matches(':'))
+            .or_else(|| line.strip_prefix("Process exited with code"))
+            .and_then(|code| code.trim().parse().ok())
+    })
+}
+
 /// Truncate a string to `max_len` characters, appending "..." if truncated.
 pub fn truncate_str(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -218,6 +290,66 @@ mod tests {
         session
     }
 
+    #[test]
+    fn test_extract_command_runs_pairs_results() {
+        let mut session = make_session(vec![
+            (
+                "cargo test",
+                EventType::ShellCommand {
+                    command: "cargo test".to_string(),
+                    exit_code: None,
+                },
+            ),
+            (
+                "Exit code 101\ntest failed",
+                EventType::ToolResult {
+                    name: "Bash".to_string(),
+                    is_error: true,
+                    call_id: Some("e0".to_string()),
+                },
+            ),
+            (
+                "ls",
+                EventType::ShellCommand {
+                    command: "ls".to_string(),
+                    exit_code: None,
+                },
+            ),
+            (
+                "Cargo.toml",
+                EventType::ToolResult {
+                    name: "Bash".to_string(),
+                    is_error: false,
+                    call_id: Some("e2".to_string()),
+                },
+            ),
+            (
+                "make",
+                EventType::ShellCommand {
+                    command: "make".to_string(),
+                    exit_code: Some(0),
+                },
+            ),
+        ]);
+        session.events[1].timestamp = session.events[0].timestamp + chrono::Duration::seconds(3);
+        session.events[3].duration_ms = Some(40);
+
+        let runs = extract_command_runs(&session.events);
+        assert_eq!(runs.len(), 3);
+        assert_eq!(
+            (runs[0].exit_code, runs[0].failed, runs[0].duration_ms),
+            (Some(101), Some(true), Some(3000))
+        );
+        assert_eq!(
+            (runs[1].exit_code, runs[1].failed, runs[1].duration_ms),
+            (None, Some(false), Some(40))
+        );
+        assert_eq!(
+            (runs[2].exit_code, runs[2].failed, runs[2].duration_ms),
+            (Some(0), Some(false), None)
+        );
+    }
+
     #[test]
     fn test_extract_first_user_text() {
         let session = make_session(vec![
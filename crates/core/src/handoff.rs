@@ -11,6 +11,8 @@ mod hail_export;
 mod markdown;
 #[path = "handoff/merge.rs"]
 mod merge;
+#[path = "handoff/open_threads.rs"]
+mod open_threads;
 #[cfg(test)]
 #[path = "handoff/tests.rs"]
 mod tests;
@@ -26,13 +28,14 @@ use execution::{
     build_execution_contract, build_work_packages, collect_evidence, collect_open_questions,
     collect_undefined_fields, dedupe_keep_order,
 };
+use open_threads::collect_open_threads;
 
 pub use hail_export::generate_handoff_hail;
 pub use markdown::{
     generate_handoff_markdown, generate_handoff_markdown_v2, generate_merged_handoff_markdown,
     generate_merged_handoff_markdown_v2,
 };
-pub use merge::merge_summaries;
+pub use merge::{merge_sessions, merge_summaries};
 pub use validation::{
     HandoffValidationReport, ValidationFinding, validate_handoff_summaries,
     validate_handoff_summary,
@@ -111,6 +114,25 @@ pub struct Verification {
     pub required_checks_missing: Vec<String>,
 }
 
+/// Work left unresolved at the end of a session, rendered as a checklist.
+#[derive(Debug, Clone, serde::Serialize, Default)]
+pub struct OpenThreads {
+    /// Tests whose last reported outcome was a failure.
+    pub failing_tests: Vec<String>,
+    /// TODO/FIXME comments added by file edits.
+    pub todos: Vec<String>,
+    /// Tool calls that never received a result.
+    pub unfinished_tool_calls: Vec<String>,
+}
+
+impl OpenThreads {
+    pub fn is_empty(&self) -> bool {
+        self.failing_tests.is_empty()
+            && self.todos.is_empty()
+            && self.unfinished_tool_calls.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct EvidenceRef {
     pub id: String,
@@ -167,6 +189,7 @@ pub struct HandoffSummary {
     pub verification: Verification,
     pub evidence: Vec<EvidenceRef>,
     pub work_packages: Vec<WorkPackage>,
+    pub open_threads: OpenThreads,
     pub undefined_fields: Vec<UndefinedField>,
 }
 
@@ -181,6 +204,20 @@ pub struct MergedHandoff {
     pub all_files_read: Vec<String>,
     pub total_duration_seconds: u64,
     pub total_errors: Vec<String>,
+    /// Files modified by more than one session.
+    pub conflicts: Vec<HandoffConflict>,
+}
+
+/// A file touched by several merged sessions.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HandoffConflict {
+    pub path: String,
+    /// "delete_edit" | "multi_create" | "multi_edit"
+    pub kind: &'static str,
+    /// Sessions that modified the file, in merge order.
+    pub session_ids: Vec<String>,
+    /// Action taken by each session, aligned with `session_ids`.
+    pub actions: Vec<&'static str>,
 }
 
 const MAX_KEY_CONVERSATIONS: usize = 12;
@@ -241,6 +278,7 @@ impl HandoffSummary {
             verification,
             evidence,
             work_packages,
+            open_threads: collect_open_threads(&session.events),
             undefined_fields,
         }
     }
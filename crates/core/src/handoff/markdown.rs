@@ -1,6 +1,6 @@
 use crate::extract::truncate_str;
 
-use super::{HandoffSummary, MergedHandoff, format_duration};
+use super::{HandoffConflict, HandoffSummary, MergedHandoff, OpenThreads, format_duration};
 
 /// Generate a v2 Markdown handoff document from a single session summary.
 pub fn generate_handoff_markdown_v2(summary: &HandoffSummary) -> String {
@@ -20,6 +20,14 @@ pub fn generate_merged_handoff_markdown_v2(merged: &MergedHandoff) -> String {
         format_duration(merged.total_duration_seconds)
     ));
 
+    md.push_str("## Conflicts\n");
+    if merged.conflicts.is_empty() {
+        md.push_str("_(none)_\n");
+    } else {
+        append_conflict_items(&mut md, &merged.conflicts);
+    }
+    md.push('\n');
+
     for (idx, summary) in merged.summaries.iter().enumerate() {
         md.push_str(&format!(
             "---\n\n## Session {} — {}\n\n",
@@ -142,6 +150,14 @@ fn append_v2_markdown_sections(md: &mut String, summary: &HandoffSummary) {
     }
     md.push('\n');
 
+    md.push_str("## Open Threads\n");
+    if summary.open_threads.is_empty() {
+        md.push_str("_(none)_\n");
+    } else {
+        append_open_thread_items(md, &summary.open_threads);
+    }
+    md.push('\n');
+
     md.push_str("## Evidence Index\n");
     if summary.evidence.is_empty() {
         md.push_str("_(none)_\n");
@@ -184,6 +200,34 @@ fn append_v2_markdown_sections(md: &mut String, summary: &HandoffSummary) {
     }
 }
 
+fn append_open_thread_items(md: &mut String, threads: &OpenThreads) {
+    for test in &threads.failing_tests {
+        md.push_str(&format!("- [ ] Fix failing test `{test}`\n"));
+    }
+    for todo in &threads.todos {
+        md.push_str(&format!("- [ ] Resolve {todo}\n"));
+    }
+    for call in &threads.unfinished_tool_calls {
+        md.push_str(&format!("- [ ] Finish tool call {call}\n"));
+    }
+}
+
+fn append_conflict_items(md: &mut String, conflicts: &[HandoffConflict]) {
+    for conflict in conflicts {
+        let edits = conflict
+            .session_ids
+            .iter()
+            .zip(&conflict.actions)
+            .map(|(session_id, action)| format!("{session_id} {action}"))
+            .collect::<Vec<_>>()
+            .join(" → ");
+        md.push_str(&format!(
+            "- `{}` [{}]: {}\n",
+            conflict.path, conflict.kind, edits
+        ));
+    }
+}
+
 /// Generate a Markdown handoff document from a single session summary.
 pub fn generate_handoff_markdown(summary: &HandoffSummary) -> String {
     const MAX_TASK_SUMMARIES_DISPLAY: usize = 5;
@@ -272,6 +316,12 @@ pub fn generate_handoff_markdown(summary: &HandoffSummary) -> String {
         md.push('\n');
     }
 
+    if !summary.open_threads.is_empty() {
+        md.push_str("## Open Threads\n");
+        append_open_thread_items(&mut md, &summary.open_threads);
+        md.push('\n');
+    }
+
     if !summary.key_conversations.is_empty() {
         md.push_str("## Key Conversations\n");
         for (idx, conversation) in summary.key_conversations.iter().enumerate() {
@@ -308,6 +358,12 @@ pub fn generate_merged_handoff_markdown(merged: &MergedHandoff) -> String {
         format_duration(merged.total_duration_seconds)
     ));
 
+    if !merged.conflicts.is_empty() {
+        md.push_str("## Conflicts\n");
+        append_conflict_items(&mut md, &merged.conflicts);
+        md.push('\n');
+    }
+
     for (idx, summary) in merged.summaries.iter().enumerate() {
         md.push_str(&format!(
             "---\n\n## Session {} — {}\n\n",
@@ -1,6 +1,17 @@
 use std::collections::{HashMap, HashSet};
 
-use super::{FileChange, HandoffSummary, MergedHandoff};
+use crate::Session;
+use crate::handoff_artifact::sort_sessions_time_asc;
+
+use super::{FileChange, HandoffConflict, HandoffSummary, MergedHandoff};
+
+/// Order sessions by start time and merge their summaries.
+pub fn merge_sessions(sessions: &[Session]) -> MergedHandoff {
+    let mut ordered = sessions.to_vec();
+    sort_sessions_time_asc(&mut ordered);
+    let summaries: Vec<HandoffSummary> = ordered.iter().map(HandoffSummary::from_session).collect();
+    merge_summaries(&summaries)
+}
 
 /// Merge multiple session summaries into a single handoff context.
 pub fn merge_summaries(summaries: &[HandoffSummary]) -> MergedHandoff {
@@ -54,5 +65,46 @@ pub fn merge_summaries(summaries: &[HandoffSummary]) -> MergedHandoff {
         all_files_read: sorted_read,
         total_duration_seconds: total_duration,
         total_errors,
+        conflicts: detect_conflicts(summaries),
+    }
+}
+
+fn detect_conflicts(summaries: &[HandoffSummary]) -> Vec<HandoffConflict> {
+    let mut touched: HashMap<&str, Vec<(&str, &'static str)>> = HashMap::new();
+    for summary in summaries {
+        for file_change in &summary.files_modified {
+            touched
+                .entry(file_change.path.as_str())
+                .or_default()
+                .push((summary.source_session_id.as_str(), file_change.action));
+        }
     }
+
+    let mut conflicts: Vec<HandoffConflict> = touched
+        .into_iter()
+        .filter(|(_, edits)| edits.len() > 1)
+        .map(|(path, edits)| {
+            let deleted = edits.iter().any(|(_, action)| *action == "deleted");
+            let kind = if deleted && edits.iter().any(|(_, action)| *action != "deleted") {
+                "delete_edit"
+            } else if edits
+                .iter()
+                .filter(|(_, action)| *action == "created")
+                .count()
+                > 1
+            {
+                "multi_create"
+            } else {
+                "multi_edit"
+            };
+            HandoffConflict {
+                path: path.to_string(),
+                kind,
+                session_ids: edits.iter().map(|(id, _)| id.to_string()).collect(),
+                actions: edits.iter().map(|(_, action)| *action).collect(),
+            }
+        })
+        .collect();
+    conflicts.sort_by(|left, right| left.path.cmp(&right.path));
+    conflicts
 }
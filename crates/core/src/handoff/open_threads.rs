@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use crate::extract::truncate_str;
+use crate::{ContentBlock, Event, EventType};
+
+use super::{OpenThreads, collapse_whitespace, execution::dedupe_keep_order};
+
+const MAX_OPEN_THREAD_ITEMS: usize = 20;
+const TODO_MARKERS: [&str; 2] = ["TODO", "FIXME"];
+
+pub(super) fn collect_open_threads(events: &[Event]) -> OpenThreads {
+    let mut todos = collect_added_todos(events);
+    dedupe_keep_order(&mut todos);
+    todos.truncate(MAX_OPEN_THREAD_ITEMS);
+
+    let mut failing_tests = collect_failing_tests(events);
+    failing_tests.truncate(MAX_OPEN_THREAD_ITEMS);
+
+    let mut unfinished_tool_calls = collect_unfinished_tool_calls(events);
+    unfinished_tool_calls.truncate(MAX_OPEN_THREAD_ITEMS);
+
+    OpenThreads {
+        failing_tests,
+        todos,
+        unfinished_tool_calls,
+    }
+}
+
+/// Test names whose most recent reported outcome was a failure.
+///
+/// Recognizes `cargo test`, pytest and `go test` output in shell/tool results,
+/// so a test that failed and later passed is not reported.
+fn collect_failing_tests(events: &[Event]) -> Vec<String> {
+    let mut failing: Vec<String> = Vec::new();
+    for event in events {
+        if !matches!(
+            event.event_type,
+            EventType::ShellCommand { .. } | EventType::ToolResult { .. }
+        ) {
+            continue;
+        }
+        for text in event_texts(event) {
+            for line in text.lines() {
+                let Some((name, passed)) = parse_test_outcome(line.trim()) else {
+                    continue;
+                };
+                failing.retain(|existing| existing != &name);
+                if !passed {
+                    failing.push(name);
+                }
+            }
+        }
+    }
+    failing
+}
+
+fn parse_test_outcome(line: &str) -> Option<(String, bool)> {
+    // cargo test: `test module::name ... FAILED`
+    if let Some(rest) = line.strip_prefix("test ")
+        && let Some((name, outcome)) = rest.rsplit_once(" ... ")
+    {
+        let passed = match outcome.trim() {
+            "ok" => true,
+            "FAILED" => false,
+            _ => return None,
+        };
+        return Some((name.trim().to_string(), passed));
+    }
+    // pytest short summary: `FAILED tests/test_x.py::test_y - AssertionError`
+    if let Some(rest) = line.strip_prefix("FAILED ") {
+        let name = rest.split(" - ").next().unwrap_or(rest).trim();
+        return (!name.is_empty()).then(|| (name.to_string(), false));
+    }
+    if let Some(rest) = line.strip_prefix("PASSED ") {
+        let name = rest.trim();
+        return (!name.is_empty()).then(|| (name.to_string(), true));
+    }
+    // go test: `--- FAIL: TestName (0.01s)`
+    for (prefix, passed) in [("--- FAIL: ", false), ("--- PASS: ", true)] {
+        if let Some(rest) = line.strip_prefix(prefix) {
+            let name = rest.split_whitespace().next()?;
+            return Some((name.to_string(), passed));
+        }
+    }
+    None
+}
+
+/// TODO/FIXME lines introduced by file edits (`+` diff lines) or new files.
+fn collect_added_todos(events: &[Event]) -> Vec<String> {
+    let mut todos = Vec::new();
+    for event in events {
+        let (path, added_lines): (&str, Vec<&str>) = match &event.event_type {
+            EventType::FileEdit {
+                path,
+                diff: Some(diff),
+            } => (
+                path,
+                diff.lines()
+                    .filter(|line| !line.starts_with("+++"))
+                    .filter_map(|line| line.strip_prefix('+'))
+                    .collect(),
+            ),
+            EventType::FileCreate { path } => (
+                path,
+                event
+                    .content
+                    .blocks
+                    .iter()
+                    .flat_map(|block| match block {
+                        ContentBlock::Code { code, .. } => code.lines().collect(),
+                        ContentBlock::File {
+                            content: Some(content),
+                            ..
+                        } => content.lines().collect(),
+                        _ => Vec::new(),
+                    })
+                    .collect(),
+            ),
+            _ => continue,
+        };
+
+        for line in added_lines {
+            if let Some(note) = todo_note(line) {
+                todos.push(format!("`{path}`: {note}"));
+            }
+        }
+    }
+    todos
+}
+
+fn todo_note(line: &str) -> Option<String> {
+    let start = TODO_MARKERS
+        .iter()
+        .filter_map(|marker| {
+            line.match_indices(marker).find_map(|(idx, _)| {
+                let after = line[idx + marker.len()..].chars().next();
+                let before = line[..idx].chars().next_back();
+                let bounded = !before.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+                    && !after.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+                bounded.then_some(idx)
+            })
+        })
+        .min()?;
+    Some(truncate_str(&collapse_whitespace(&line[start..]), 160))
+}
+
+/// Tool calls with a call id that never received a matching result.
+fn collect_unfinished_tool_calls(events: &[Event]) -> Vec<String> {
+    let answered: HashSet<&str> = events
+        .iter()
+        .filter(|event| matches!(event.event_type, EventType::ToolResult { .. }))
+        .filter_map(Event::semantic_call_id)
+        .collect();
+
+    events
+        .iter()
+        .filter_map(|event| match &event.event_type {
+            EventType::ToolCall { name } => {
+                let call_id = event.semantic_call_id()?;
+                (!answered.contains(call_id))
+                    .then(|| format!("{name} (call {call_id}, event {})", event.event_id))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn event_texts(event: &Event) -> impl Iterator<Item = &str> {
+    event.content.blocks.iter().filter_map(|block| match block {
+        ContentBlock::Text { text } => Some(text.as_str()),
+        ContentBlock::Code { code, .. } => Some(code.as_str()),
+        _ => None,
+    })
+}
@@ -291,6 +291,77 @@ fn test_merge_summaries() {
     assert_eq!(merged.source_session_ids.len(), 2);
     assert_eq!(merged.total_duration_seconds, 300);
     assert_eq!(merged.all_files_modified.len(), 2);
+    assert!(merged.conflicts.is_empty());
+}
+
+#[test]
+fn test_merge_sessions_orders_by_time_and_detects_conflicts() {
+    let now = chrono::Utc::now();
+    let mut later = Session::new("session-later".to_string(), make_agent());
+    later.context.created_at = now + chrono::Duration::minutes(5);
+    later
+        .events
+        .push(make_event(EventType::UserMessage, "cleanup"));
+    later.events.push(make_event(
+        EventType::FileDelete {
+            path: "src/legacy.rs".to_string(),
+        },
+        "",
+    ));
+    later.events.push(make_event(
+        EventType::FileEdit {
+            path: "src/lib.rs".to_string(),
+            diff: None,
+        },
+        "",
+    ));
+
+    let mut earlier = Session::new("session-earlier".to_string(), make_agent());
+    earlier.context.created_at = now;
+    earlier
+        .events
+        .push(make_event(EventType::UserMessage, "refactor"));
+    earlier.events.push(make_event(
+        EventType::FileEdit {
+            path: "src/legacy.rs".to_string(),
+            diff: None,
+        },
+        "",
+    ));
+    earlier.events.push(make_event(
+        EventType::FileEdit {
+            path: "src/lib.rs".to_string(),
+            diff: None,
+        },
+        "",
+    ));
+    earlier.events.push(make_event(
+        EventType::FileEdit {
+            path: "src/only_earlier.rs".to_string(),
+            diff: None,
+        },
+        "",
+    ));
+
+    let merged = merge_sessions(&[later, earlier]);
+    assert_eq!(
+        merged.source_session_ids,
+        vec!["session-earlier".to_string(), "session-later".to_string()]
+    );
+    assert_eq!(merged.conflicts.len(), 2);
+    assert_eq!(merged.conflicts[0].path, "src/legacy.rs");
+    assert_eq!(merged.conflicts[0].kind, "delete_edit");
+    assert_eq!(merged.conflicts[0].actions, vec!["edited", "deleted"]);
+    assert_eq!(merged.conflicts[1].path, "src/lib.rs");
+    assert_eq!(merged.conflicts[1].kind, "multi_edit");
+
+    let md = generate_merged_handoff_markdown_v2(&merged);
+    let conflicts_idx = md.find("## Conflicts").unwrap();
+    assert!(conflicts_idx < md.find("## Session 1").unwrap());
+    assert!(md.contains(
+        "- `src/legacy.rs` [delete_edit]: session-earlier edited → session-later deleted"
+    ));
+    assert!(!md.contains("`src/only_earlier.rs` ["));
 }
 
 #[test]
@@ -364,6 +435,7 @@ fn test_generate_handoff_markdown_v2_section_order() {
         "## Next Actions (ordered)",
         "## Verification",
         "## Blockers / Decisions",
+        "## Open Threads",
         "## Evidence Index",
         "## Conversations",
         "## User Messages",
@@ -380,6 +452,109 @@ fn test_generate_handoff_markdown_v2_section_order() {
     }
 }
 
+#[test]
+fn test_open_threads_collects_failing_tests_todos_and_unfinished_calls() {
+    let mut session = Session::new("open-threads".to_string(), make_agent());
+    session
+        .events
+        .push(make_event(EventType::UserMessage, "Fix auth tests"));
+    session.events.push(make_event(
+        EventType::ShellCommand {
+            command: "cargo test".to_string(),
+            exit_code: Some(101),
+        },
+        "test auth::tests::rejects_expired ... FAILED\ntest auth::tests::accepts_valid ... FAILED",
+    ));
+    session.events.push(make_event(
+        EventType::ShellCommand {
+            command: "cargo test accepts_valid".to_string(),
+            exit_code: Some(0),
+        },
+        "test auth::tests::accepts_valid ... ok",
+    ));
+    session.events.push(make_event(
+        EventType::FileEdit {
+            path: "src/auth.rs".to_string(),
+            diff: Some(
+                "--- a/src/auth.rs\n+++ b/src/auth.rs\n-    // TODO: old note\n+    // TODO: handle   clock skew\n+    let todos = TODOS;\n"
+                    .to_string(),
+            ),
+        },
+        "",
+    ));
+    let mut pending = make_event(
+        EventType::ToolCall {
+            name: "Bash".to_string(),
+        },
+        "",
+    );
+    pending.attributes.insert(
+        crate::trace::ATTR_SEMANTIC_CALL_ID.to_string(),
+        serde_json::Value::String("call-pending".to_string()),
+    );
+    session.events.push(pending);
+    let mut answered = make_event(
+        EventType::ToolCall {
+            name: "Read".to_string(),
+        },
+        "",
+    );
+    answered.attributes.insert(
+        crate::trace::ATTR_SEMANTIC_CALL_ID.to_string(),
+        serde_json::Value::String("call-done".to_string()),
+    );
+    session.events.push(answered);
+    session.events.push(make_event(
+        EventType::ToolResult {
+            name: "Read".to_string(),
+            is_error: false,
+            call_id: Some("call-done".to_string()),
+        },
+        "FAILED tests/test_api.py::test_login - AssertionError",
+    ));
+
+    let summary = HandoffSummary::from_session(&session);
+    assert_eq!(
+        summary.open_threads.failing_tests,
+        vec![
+            "auth::tests::rejects_expired".to_string(),
+            "tests/test_api.py::test_login".to_string(),
+        ]
+    );
+    assert_eq!(
+        summary.open_threads.todos,
+        vec!["`src/auth.rs`: TODO: handle clock skew".to_string()]
+    );
+    assert_eq!(summary.open_threads.unfinished_tool_calls.len(), 1);
+    assert!(summary.open_threads.unfinished_tool_calls[0].starts_with("Bash (call call-pending"));
+
+    let md = generate_handoff_markdown_v2(&summary);
+    assert!(md.contains("## Open Threads\n"));
+    assert!(md.contains("- [ ] Fix failing test `auth::tests::rejects_expired`"));
+    assert!(md.contains("- [ ] Resolve `src/auth.rs`: TODO: handle clock skew"));
+    assert!(md.contains("- [ ] Finish tool call Bash (call call-pending"));
+    assert!(!md.contains("Fix failing test `auth::tests::accepts_valid`"));
+}
+
+#[test]
+fn test_open_threads_parses_go_test_failures() {
+    let mut session = Session::new("open-threads-go".to_string(), make_agent());
+    session.events.push(make_event(
+        EventType::ShellCommand {
+            command: "go test ./...".to_string(),
+            exit_code: Some(1),
+        },
+        "--- FAIL: TestParse (0.00s)\n--- PASS: TestFormat (0.00s)",
+    ));
+
+    let summary = HandoffSummary::from_session(&session);
+    assert_eq!(
+        summary.open_threads.failing_tests,
+        vec!["TestParse".to_string()]
+    );
+    assert!(summary.open_threads.todos.is_empty());
+}
+
 #[test]
 fn test_execution_contract_and_verification_from_failed_command() {
     let mut session = Session::new("failed-check".to_string(), make_agent());
@@ -0,0 +1,168 @@
+//! Issue-tracker references (Jira `PROJ-123`, GitHub `#456`) mentioned by a session.
+
+use crate::{ContentBlock, EventType, Session};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Uppercase prefixes that look like Jira keys but name standards, hashes or models.
+const NON_ISSUE_KEYS: &[&str] = &[
+    "AES", "CVE", "ECMA", "GMT", "GPT", "HTTP", "ISO", "PEP", "RFC", "RSA", "SHA", "TLS", "UTC",
+    "UTF",
+];
+
+static JIRA_REF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b([A-Z][A-Z0-9]{1,9})-([1-9][0-9]{0,6})\b").unwrap());
+static GITHUB_REF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|[\s(\[{,;:])#([1-9][0-9]{0,6})\b").unwrap());
+static BRANCH_NUMBER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|/)([1-9][0-9]{0,6})(?:[-_]|$)").unwrap());
+
+/// Issue references in free text, in order of first appearance.
+///
+/// `GH-456` is reported as `#456`.
+pub fn parse_issue_refs(text: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    collect_refs(text, &mut refs);
+    refs
+}
+
+/// Issue references for a session, from its git branch, title, user prompts
+/// and `git commit` commands, deduplicated in that order.
+///
+/// A branch such as `feature/123-fix-login` also yields `#123`.
+pub fn session_issue_refs(session: &Session, branch: Option<&str>) -> Vec<String> {
+    let mut refs = Vec::new();
+    if let Some(branch) = branch {
+        collect_refs(branch, &mut refs);
+        for cap in BRANCH_NUMBER.captures_iter(branch) {
+            push_unique(&mut refs, format!("#{}", &cap[1]));
+        }
+    }
+    if let Some(title) = session.context.title.as_deref() {
+        collect_refs(title, &mut refs);
+    }
+    for event in &session.events {
+        match &event.event_type {
+            EventType::UserMessage => {
+                for block in &event.content.blocks {
+                    if let ContentBlock::Text { text } = block {
+                        collect_refs(text, &mut refs);
+                    }
+                }
+            }
+            EventType::ShellCommand { command, .. } if command.contains("git commit") => {
+                collect_refs(command, &mut refs);
+            }
+            _ => {}
+        }
+    }
+    refs
+}
+
+/// Canonical form of a user-supplied reference: `proj-123` → `PROJ-123`,
+/// `456` / `gh-456` → `#456`. `None` when it is not an issue reference.
+pub fn normalize_issue_ref(input: &str) -> Option<String> {
+    let trimmed = input.trim();
+    let number = trimmed
+        .strip_prefix('#')
+        .or_else(|| {
+            trimmed
+                .get(..3)
+                .filter(|prefix| prefix.eq_ignore_ascii_case("gh-"))
+                .map(|_| &trimmed[3..])
+        })
+        .unwrap_or(trimmed);
+    if !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()) {
+        let number = number.trim_start_matches('0');
+        return (!number.is_empty()).then(|| format!("#{number}"));
+    }
+    let upper = trimmed.to_ascii_uppercase();
+    let whole = JIRA_REF
+        .find(&upper)
+        .is_some_and(|m| m.start() == 0 && m.end() == upper.len());
+    whole.then_some(upper)
+}
+
+fn collect_refs(text: &str, refs: &mut Vec<String>) {
+    let mut found: Vec<(usize, String)> = Vec::new();
+    for cap in JIRA_REF.captures_iter(text) {
+        let key = &cap[1];
+        let start = cap.get(0).map_or(0, |m| m.start());
+        if key == "GH" {
+            found.push((start, format!("#{}", &cap[2])));
+        } else if !NON_ISSUE_KEYS.contains(&key) {
+            found.push((start, cap[0].to_string()));
+        }
+    }
+    for cap in GITHUB_REF.captures_iter(text) {
+        let start = cap.get(1).map_or(0, |m| m.start());
+        found.push((start, format!("#{}", &cap[1])));
+    }
+    found.sort_by_key(|(start, _)| *start);
+    for (_, issue_ref) in found {
+        push_unique(refs, issue_ref);
+    }
+}
+
+fn push_unique(refs: &mut Vec<String>, issue_ref: String) {
+    if !refs.contains(&issue_ref) {
+        refs.push(issue_ref);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn parses_jira_and_github_refs_in_order() {
+        assert_eq!(
+            parse_issue_refs("Fix PROJ-123 (see #456, GH-789); PROJ-123 again"),
+            vec!["PROJ-123", "#456", "#789"]
+        );
+    }
+
+    #[test]
+    fn ignores_look_alikes() {
+        assert!(parse_issue_refs("UTF-8 SHA-256 GPT-4 color#123 issue#0 a-1").is_empty());
+    }
+
+    #[test]
+    fn session_refs_cover_branch_title_prompts_and_commits() {
+        let mut session = Session::new("s1".to_string(), testing::agent());
+        session.context.title = Some("Work on OPS-7".to_string());
+        session
+            .events
+            .push(testing::event(EventType::UserMessage, "please close #12"));
+        session.events.push(testing::event(
+            EventType::ShellCommand {
+                command: "git commit -m 'Fix login (WEB-3)'".to_string(),
+                exit_code: Some(0),
+            },
+            "",
+        ));
+        session.events.push(testing::event(
+            EventType::ShellCommand {
+                command: "echo NOPE-1".to_string(),
+                exit_code: Some(0),
+            },
+            "",
+        ));
+
+        assert_eq!(
+            session_issue_refs(&session, Some("feature/42-login-PROJ-9")),
+            vec!["PROJ-9", "#42", "OPS-7", "#12", "WEB-3"]
+        );
+    }
+
+    #[test]
+    fn normalizes_user_supplied_refs() {
+        assert_eq!(normalize_issue_ref("proj-123").as_deref(), Some("PROJ-123"));
+        assert_eq!(normalize_issue_ref("#456").as_deref(), Some("#456"));
+        assert_eq!(normalize_issue_ref("456").as_deref(), Some("#456"));
+        assert_eq!(normalize_issue_ref("GH-456").as_deref(), Some("#456"));
+        assert_eq!(normalize_issue_ref("#0"), None);
+        assert_eq!(normalize_issue_ref("not an issue"), None);
+    }
+}
@@ -11,6 +11,9 @@
 //! The header line contains session metadata (no events).
 //! Each event is one line.
 //! The last line is aggregate stats (optional on write, recomputed on read if missing).
+//!
+//! [`JsonlWriter`] and [`JsonlEventReader`] stream a body line by line so
+//! large sessions never need a full in-memory copy of the JSONL text.
 
 use crate::trace::{Agent, Event, Session, SessionContext, Stats};
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
@@ -66,30 +69,73 @@ fn json_from_str_line<T: DeserializeOwned>(input: &str, line: usize) -> Result<T
     serde_json::from_str(input).map_err(|source| JsonlError::Json { line, source })
 }
 
-/// Write a Session as HAIL JSONL to a writer
-pub fn write_jsonl<W: Write>(session: &Session, mut writer: W) -> Result<(), JsonlError> {
-    // Line 1: header
-    let header = HailLine::Header {
-        version: session.version.clone(),
-        session_id: session.session_id.clone(),
-        agent: session.agent.clone(),
-        context: session.context.clone(),
-    };
-    json_to_writer_line(&mut writer, &header, 1)?;
-    writer.write_all(b"\n")?;
+/// Borrowed view of a [`HailLine`], so writers serialize without cloning events.
+/// Field order must stay identical to [`HailLine`] to keep output canonical.
+#[derive(Serialize)]
+#[serde(tag = "type")]
+enum HailLineRef<'a> {
+    #[serde(rename = "header")]
+    Header {
+        version: &'a str,
+        session_id: &'a str,
+        agent: &'a Agent,
+        context: &'a SessionContext,
+    },
+    #[serde(rename = "event")]
+    Event(&'a Event),
+    #[serde(rename = "stats")]
+    Stats(&'a Stats),
+}
 
-    // Lines 2..N: events
-    for (i, event) in session.events.iter().enumerate() {
-        let line = HailLine::Event(event.clone());
-        json_to_writer_line(&mut writer, &line, i + 2)?;
-        writer.write_all(b"\n")?;
+/// Incremental HAIL JSONL writer.
+///
+/// Writes the header on creation, then one line per [`JsonlWriter::write_event`],
+/// and the stats line on [`JsonlWriter::finish`] — events never need to be
+/// held in memory at once.
+pub struct JsonlWriter<W: Write> {
+    writer: W,
+    line: usize,
+}
+
+impl<W: Write> JsonlWriter<W> {
+    /// Start a body with the header taken from `session` (its events are ignored).
+    pub fn new(writer: W, session: &Session) -> Result<Self, JsonlError> {
+        let mut this = Self { writer, line: 0 };
+        this.write_line(&HailLineRef::Header {
+            version: &session.version,
+            session_id: &session.session_id,
+            agent: &session.agent,
+            context: &session.context,
+        })?;
+        Ok(this)
     }
 
-    // Last line: stats
-    let stats_line = HailLine::Stats(session.stats.clone());
-    json_to_writer_line(&mut writer, &stats_line, session.events.len() + 2)?;
-    writer.write_all(b"\n")?;
+    pub fn write_event(&mut self, event: &Event) -> Result<(), JsonlError> {
+        self.write_line(&HailLineRef::Event(event))
+    }
+
+    /// Write the trailing stats line, flush, and return the inner writer.
+    pub fn finish(mut self, stats: &Stats) -> Result<W, JsonlError> {
+        self.write_line(&HailLineRef::Stats(stats))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_line(&mut self, line: &HailLineRef<'_>) -> Result<(), JsonlError> {
+        self.line += 1;
+        json_to_writer_line(&mut self.writer, line, self.line)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
 
+/// Write a Session as HAIL JSONL to a writer
+pub fn write_jsonl<W: Write>(session: &Session, writer: W) -> Result<(), JsonlError> {
+    let mut out = JsonlWriter::new(writer, session)?;
+    for event in &session.events {
+        out.write_event(event)?;
+    }
+    out.finish(&session.stats)?;
     Ok(())
 }
 
@@ -101,52 +147,116 @@ pub fn to_jsonl_string(session: &Session) -> Result<String, JsonlError> {
     Ok(String::from_utf8(buf).unwrap())
 }
 
-/// Read a Session from HAIL JSONL reader
-pub fn read_jsonl<R: BufRead>(reader: R) -> Result<Session, JsonlError> {
-    let mut lines = reader.lines();
-
-    // Line 1: header
-    let header_str = lines.next().ok_or(JsonlError::MissingHeader)??;
-    let header: HailLine = json_from_str_line(&header_str, 1)?;
+/// Session metadata from the header line.
+#[derive(Debug, Clone)]
+pub struct JsonlHeader {
+    pub version: String,
+    pub session_id: String,
+    pub agent: Agent,
+    pub context: SessionContext,
+}
 
-    let (version, session_id, agent, context) = match header {
-        HailLine::Header {
-            version,
-            session_id,
-            agent,
-            context,
-        } => (version, session_id, agent, context),
-        _ => return Err(JsonlError::UnexpectedLineType(1)),
-    };
+/// Streaming HAIL JSONL reader: yields events one line at a time.
+///
+/// The stats line, if present, is captured and available from
+/// [`JsonlEventReader::stats`] once iteration reaches it. Duplicate header
+/// lines are ignored, and so is an unparseable final line with no trailing
+/// newline — what a writer that crashed mid-line leaves behind.
+pub struct JsonlEventReader<R: BufRead> {
+    reader: R,
+    line: usize,
+    stats: Option<Stats>,
+}
 
-    let mut events = Vec::new();
-    let mut stats = None;
-    let mut line_num = 1usize;
+impl<R: BufRead> JsonlEventReader<R> {
+    /// Read the header line and return it with a reader positioned at the events.
+    pub fn open(mut reader: R) -> Result<(JsonlHeader, Self), JsonlError> {
+        let (header_str, _) = read_raw_line(&mut reader)?.ok_or(JsonlError::MissingHeader)?;
+        let header = match json_from_str_line(&header_str, 1)? {
+            HailLine::Header {
+                version,
+                session_id,
+                agent,
+                context,
+            } => JsonlHeader {
+                version,
+                session_id,
+                agent,
+                context,
+            },
+            _ => return Err(JsonlError::UnexpectedLineType(1)),
+        };
+        Ok((
+            header,
+            Self {
+                reader,
+                line: 1,
+                stats: None,
+            },
+        ))
+    }
 
-    for line_result in lines {
-        line_num += 1;
-        let line_str = line_result?;
-        if line_str.is_empty() {
-            continue;
-        }
+    /// Stats line seen so far (normally available after the last event).
+    pub fn stats(&self) -> Option<&Stats> {
+        self.stats.as_ref()
+    }
 
-        let hail_line: HailLine = json_from_str_line(&line_str, line_num)?;
+    pub fn into_stats(self) -> Option<Stats> {
+        self.stats
+    }
+}
 
-        match hail_line {
-            HailLine::Event(event) => events.push(event),
-            HailLine::Stats(s) => stats = Some(s),
-            HailLine::Header { .. } => {
-                // Ignore duplicate headers
+impl<R: BufRead> Iterator for JsonlEventReader<R> {
+    type Item = Result<Event, JsonlError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (line_str, terminated) = match read_raw_line(&mut self.reader) {
+                Ok(line) => line?,
+                Err(err) => return Some(Err(err.into())),
+            };
+            self.line += 1;
+            if line_str.is_empty() {
+                continue;
+            }
+            match json_from_str_line(&line_str, self.line) {
+                Ok(HailLine::Event(event)) => return Some(Ok(event)),
+                Ok(HailLine::Stats(stats)) => self.stats = Some(stats),
+                Ok(HailLine::Header { .. }) => {
+                    // Ignore duplicate headers
+                }
+                // Partial last line from an interrupted write
+                Err(_) if !terminated => return None,
+                Err(err) => return Some(Err(err)),
             }
         }
     }
+}
+
+/// Next line without its line ending, and whether it had one.
+fn read_raw_line<R: BufRead>(reader: &mut R) -> io::Result<Option<(String, bool)>> {
+    let mut buf = String::new();
+    if reader.read_line(&mut buf)? == 0 {
+        return Ok(None);
+    }
+    let terminated = buf.ends_with('\n');
+    let trimmed = buf.trim_end_matches(['\n', '\r']).len();
+    buf.truncate(trimmed);
+    Ok(Some((buf, terminated)))
+}
+
+/// Read a Session from HAIL JSONL reader
+pub fn read_jsonl<R: BufRead>(reader: R) -> Result<Session, JsonlError> {
+    let (header, mut events_reader) = JsonlEventReader::open(reader)?;
+    let events = events_reader.by_ref().collect::<Result<Vec<_>, _>>()?;
+    let stats = events_reader.into_stats();
 
     let has_stats = stats.is_some();
     let mut session = Session {
-        version,
-        session_id,
-        agent,
-        context,
+        version: header.version,
+        session_id: header.session_id,
+        agent: header.agent,
+        context: header.context,
         events,
         stats: stats.unwrap_or_default(),
     };
@@ -155,6 +265,8 @@ pub fn read_jsonl<R: BufRead>(reader: R) -> Result<Session, JsonlError> {
     if !has_stats {
         session.recompute_stats();
     }
+    // Bodies stored by older builds are upgraded to the current shape
+    crate::migrate::upgrade_in_place(&mut session);
 
     Ok(session)
 }
@@ -228,6 +340,134 @@ pub fn read_header_and_stats(
     Ok((version, session_id, agent, context, stats))
 }
 
+/// Just the `type` tag of a HAIL line, so delta helpers can classify lines
+/// without deserializing whole events.
+#[derive(Deserialize)]
+struct LineTag {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+fn line_kind(line: &str, line_num: usize) -> Result<String, JsonlError> {
+    json_from_str_line::<LineTag>(line, line_num).map(|tag| tag.kind)
+}
+
+/// Count the event lines in a HAIL JSONL body.
+pub fn count_events(data: &str) -> Result<usize, JsonlError> {
+    let mut count = 0;
+    for (idx, line) in data.lines().enumerate() {
+        if !line.is_empty() && line_kind(line, idx + 1)? == "event" {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Extract the events after the first `since_event` from a HAIL JSONL body.
+///
+/// The delta holds the remaining event lines verbatim followed by the stats
+/// line (if any); the header is dropped. Apply it with [`apply_event_delta`].
+pub fn event_delta(data: &str, since_event: usize) -> Result<String, JsonlError> {
+    let mut out = String::new();
+    let mut seen = 0usize;
+    for (idx, line) in data.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        match line_kind(line, idx + 1)?.as_str() {
+            "event" => {
+                if seen >= since_event {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+                seen += 1;
+            }
+            "stats" => {
+                out.push_str(line);
+                out.push('\n');
+            }
+            _ => {}
+        }
+    }
+    Ok(out)
+}
+
+/// Append an [`event_delta`] to a full HAIL JSONL body.
+///
+/// The body's stats line is replaced by the delta's, so the merged body is
+/// identical to re-downloading the whole session.
+pub fn apply_event_delta(data: &str, delta: &str) -> Result<String, JsonlError> {
+    let mut out = String::with_capacity(data.len() + delta.len());
+    let mut delta_stats = None;
+    let mut body_stats = None;
+
+    for (idx, line) in data.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        if idx > 0 && line_kind(line, idx + 1)? == "stats" {
+            body_stats = Some(line);
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    if out.is_empty() {
+        return Err(JsonlError::MissingHeader);
+    }
+
+    for (idx, line) in delta.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        match line_kind(line, idx + 1)?.as_str() {
+            "event" => {
+                out.push_str(line);
+                out.push('\n');
+            }
+            "stats" => delta_stats = Some(line),
+            _ => return Err(JsonlError::UnexpectedLineType(idx + 1)),
+        }
+    }
+
+    if let Some(stats) = delta_stats.or(body_stats) {
+        out.push_str(stats);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Result of [`salvage_jsonl`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonlSalvage {
+    /// Every line that parsed as a JSON object, newline-terminated.
+    pub body: String,
+    pub kept: usize,
+    /// 1-based numbers of the lines that were dropped.
+    pub dropped: Vec<usize>,
+}
+
+/// Keep the lines of any JSONL log (HAIL or agent-native) that parse as JSON
+/// objects and drop the rest — truncated writes, interleaved garbage, or
+/// binary junk. Blank lines are neither kept nor counted as dropped.
+pub fn salvage_jsonl(data: &str) -> JsonlSalvage {
+    let mut salvage = JsonlSalvage::default();
+    for (idx, line) in data.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(value) if value.is_object() => {
+                salvage.body.push_str(line);
+                salvage.body.push('\n');
+                salvage.kept += 1;
+            }
+            _ => salvage.dropped.push(idx + 1),
+        }
+    }
+    salvage
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -282,6 +522,89 @@ mod tests {
         session
     }
 
+    #[test]
+    fn test_streaming_writer_matches_owned_line_encoding() {
+        let session = make_test_session();
+        let jsonl = to_jsonl_string(&session).unwrap();
+        let lines: Vec<&str> = jsonl.lines().collect();
+
+        let header = HailLine::Header {
+            version: session.version.clone(),
+            session_id: session.session_id.clone(),
+            agent: session.agent.clone(),
+            context: session.context.clone(),
+        };
+        assert_eq!(lines[0], serde_json::to_string(&header).unwrap());
+        for (i, event) in session.events.iter().enumerate() {
+            let owned = HailLine::Event(event.clone());
+            assert_eq!(lines[i + 1], serde_json::to_string(&owned).unwrap());
+        }
+        let stats = HailLine::Stats(session.stats.clone());
+        assert_eq!(
+            *lines.last().unwrap(),
+            serde_json::to_string(&stats).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_streaming_reader_yields_events_then_stats() {
+        let session = make_test_session();
+        let jsonl = to_jsonl_string(&session).unwrap();
+
+        let (header, mut reader) = JsonlEventReader::open(jsonl.as_bytes()).unwrap();
+        assert_eq!(header.session_id, session.session_id);
+        assert!(reader.stats().is_none());
+
+        let ids: Vec<String> = reader
+            .by_ref()
+            .map(|event| event.unwrap().event_id)
+            .collect();
+        assert_eq!(ids, vec!["e1", "e2", "e3"]);
+        assert_eq!(
+            reader.stats().map(|stats| stats.event_count),
+            Some(session.stats.event_count)
+        );
+    }
+
+    #[test]
+    fn test_streaming_reader_reports_bad_line_number() {
+        let session = make_test_session();
+        let mut jsonl = to_jsonl_string(&session).unwrap();
+        jsonl.push_str("{not json}\n");
+
+        let (_, reader) = JsonlEventReader::open(jsonl.as_bytes()).unwrap();
+        let err = reader.filter_map(Result::err).next().expect("error");
+        assert!(matches!(err, JsonlError::Json { line: 6, .. }));
+    }
+
+    #[test]
+    fn test_reader_ignores_partial_last_line() {
+        let session = make_test_session();
+        let mut jsonl = to_jsonl_string(&session).unwrap();
+        let stats_start = jsonl[..jsonl.len() - 1].rfind('\n').unwrap() + 1;
+        jsonl.truncate(stats_start);
+        jsonl.push_str(r#"{"type":"event","event_id":"e4","times"#);
+
+        let parsed = from_jsonl_str(&jsonl).unwrap();
+        assert_eq!(parsed.events.len(), 3);
+        assert_eq!(parsed.stats.event_count, 3);
+
+        jsonl.push('\n');
+        assert!(matches!(
+            from_jsonl_str(&jsonl),
+            Err(JsonlError::Json { line: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_salvage_keeps_json_object_lines() {
+        let data = "{\"a\":1}\n\n[1,2]\n{\"b\":{\"c\"\n{\"d\":2}\r\n{\"e\":";
+        let salvage = salvage_jsonl(data);
+        assert_eq!(salvage.body, "{\"a\":1}\n{\"d\":2}\n");
+        assert_eq!(salvage.kept, 2);
+        assert_eq!(salvage.dropped, vec![3, 4, 6]);
+    }
+
     #[test]
     fn test_jsonl_roundtrip() {
         let session = make_test_session();
@@ -413,4 +736,38 @@ mod tests {
         // Events e1, e2 have no task_id
         assert_eq!(parsed.events[0].task_id, None);
     }
+
+    #[test]
+    fn test_event_delta_round_trips_through_apply() {
+        let full = make_test_session();
+        let full_jsonl = to_jsonl_string(&full).unwrap();
+
+        let mut prefix = full.clone();
+        prefix.events.truncate(1);
+        prefix.recompute_stats();
+        let prefix_jsonl = to_jsonl_string(&prefix).unwrap();
+
+        assert_eq!(count_events(&prefix_jsonl).unwrap(), 1);
+        let delta = event_delta(&full_jsonl, count_events(&prefix_jsonl).unwrap()).unwrap();
+        assert!(!delta.contains("\"type\":\"header\""));
+        assert_eq!(count_events(&delta).unwrap(), full.events.len() - 1);
+
+        let merged = apply_event_delta(&prefix_jsonl, &delta).unwrap();
+        assert_eq!(merged, full_jsonl);
+    }
+
+    #[test]
+    fn test_event_delta_past_end_keeps_only_stats() {
+        let jsonl = to_jsonl_string(&make_test_session()).unwrap();
+        let delta = event_delta(&jsonl, 100).unwrap();
+        assert_eq!(count_events(&delta).unwrap(), 0);
+        assert!(delta.starts_with("{\"type\":\"stats\""));
+    }
+
+    #[test]
+    fn test_apply_event_delta_rejects_header_in_delta() {
+        let jsonl = to_jsonl_string(&make_test_session()).unwrap();
+        let err = apply_event_delta(&jsonl, &jsonl).unwrap_err();
+        assert!(matches!(err, JsonlError::UnexpectedLineType(1)));
+    }
 }
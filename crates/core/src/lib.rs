@@ -1,13 +1,19 @@
 pub mod agent_metrics;
+pub mod anonymize;
 pub mod extract;
 pub mod handoff;
 pub mod handoff_artifact;
+pub mod issue_refs;
 pub mod jsonl;
+pub mod migrate;
+pub mod patch;
 pub mod sanitize;
+pub mod schema;
 pub mod scoring;
 pub mod session;
 pub mod source_uri;
 pub mod trace;
+pub mod truncate;
 pub mod validate;
 
 pub use trace::*;
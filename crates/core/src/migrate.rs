@@ -0,0 +1,182 @@
+//! HAIL format versioning and upgrade shims.
+//!
+//! A session records its format in [`Session::version`] as
+//! `hail-<major>.<minor>.<patch>`. Readers accept any version sharing the
+//! current major, upgrade older majors through the shims below, and refuse
+//! newer majors they cannot interpret instead of mis-reading them.
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::trace::Session;
+
+/// Parsed `hail-<major>.<minor>.<patch>` format version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HailVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl HailVersion {
+    /// Version written by this build.
+    pub const CURRENT: Self = Self::new(1, 0, 0);
+
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parse `hail-1`, `hail-1.2` or `hail-1.2.3`; missing components are zero.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let rest = raw.trim().strip_prefix("hail-")?;
+        let mut parts = rest.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+        let patch = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for HailVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hail-{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum MigrateError {
+    #[error("invalid HAIL version: {0}")]
+    InvalidVersion(String),
+    #[error("unsupported HAIL version {found}: this build reads up to hail-{supported_major}.x")]
+    Unsupported {
+        found: HailVersion,
+        supported_major: u32,
+    },
+}
+
+/// Check that this build can read a session written with version `raw`.
+pub fn negotiate(raw: &str) -> Result<HailVersion, MigrateError> {
+    let version =
+        HailVersion::parse(raw).ok_or_else(|| MigrateError::InvalidVersion(raw.to_string()))?;
+    if version.major > HailVersion::CURRENT.major {
+        return Err(MigrateError::Unsupported {
+            found: version,
+            supported_major: HailVersion::CURRENT.major,
+        });
+    }
+    Ok(version)
+}
+
+/// An upgrade step applied to sessions older than `before`.
+struct Shim {
+    before: HailVersion,
+    upgrade: fn(&mut Session),
+}
+
+/// Ordered oldest first; each shim brings a session up to its `before` version.
+const SHIMS: &[Shim] = &[Shim {
+    before: HailVersion::new(1, 0, 0),
+    upgrade: upgrade_to_1_0,
+}];
+
+/// Pre-1.0 drafts had no stats contract, so stored stats are not trusted.
+fn upgrade_to_1_0(session: &mut Session) {
+    session.recompute_stats();
+}
+
+/// Negotiate the session's version and upgrade it to the current shape.
+///
+/// Returns whether any shim changed the session. Sessions from a newer major
+/// version are rejected rather than silently mis-read.
+pub fn migrate_session(session: &mut Session) -> Result<bool, MigrateError> {
+    negotiate(&session.version)?;
+    Ok(upgrade_in_place(session))
+}
+
+/// Best-effort upgrade used when reading stored bodies: applies pending shims
+/// to older sessions and leaves unparseable or newer versions untouched.
+pub fn upgrade_in_place(session: &mut Session) -> bool {
+    let Some(version) = HailVersion::parse(&session.version) else {
+        return false;
+    };
+    let mut upgraded = false;
+    for shim in SHIMS.iter().filter(|shim| version < shim.before) {
+        (shim.upgrade)(session);
+        upgraded = true;
+    }
+    if upgraded {
+        session.version = HailVersion::CURRENT.to_string();
+    }
+    upgraded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+    use crate::trace::EventType;
+
+    #[test]
+    fn parses_partial_and_full_versions() {
+        assert_eq!(HailVersion::parse("hail-1.0.0"), Some(HailVersion::CURRENT));
+        assert_eq!(
+            HailVersion::parse("hail-0.9"),
+            Some(HailVersion::new(0, 9, 0))
+        );
+        assert_eq!(
+            HailVersion::parse("hail-2"),
+            Some(HailVersion::new(2, 0, 0))
+        );
+        assert_eq!(HailVersion::parse("1.0.0"), None);
+        assert_eq!(HailVersion::parse("hail-1.x"), None);
+        assert_eq!(HailVersion::parse("hail-1.0.0.1"), None);
+        assert_eq!(HailVersion::CURRENT.to_string(), Session::CURRENT_VERSION);
+    }
+
+    #[test]
+    fn negotiate_rejects_newer_major_only() {
+        assert!(negotiate("hail-1.4.0").is_ok());
+        assert!(negotiate("hail-0.3.0").is_ok());
+        assert!(matches!(
+            negotiate("hail-2.0.0"),
+            Err(MigrateError::Unsupported { .. })
+        ));
+        assert!(matches!(
+            negotiate("trace-1"),
+            Err(MigrateError::InvalidVersion(_))
+        ));
+    }
+
+    #[test]
+    fn migrate_upgrades_pre_1_0_sessions() {
+        let mut session = Session::new("s1".to_string(), testing::agent());
+        session.version = "hail-0.9.0".to_string();
+        session
+            .events
+            .push(testing::event(EventType::UserMessage, "hello"));
+
+        assert!(migrate_session(&mut session).expect("migrate"));
+        assert_eq!(session.version, Session::CURRENT_VERSION);
+        assert_eq!(session.stats.event_count, 1);
+
+        assert!(!migrate_session(&mut session).expect("already current"));
+    }
+
+    #[test]
+    fn upgrade_in_place_leaves_newer_versions_alone() {
+        let mut session = Session::new("s1".to_string(), testing::agent());
+        session.version = "hail-2.0.0".to_string();
+        assert!(!upgrade_in_place(&mut session));
+        assert_eq!(session.version, "hail-2.0.0");
+        assert!(migrate_session(&mut session).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+//! Unified diffs for file edit events.
+//!
+//! Parsers store `FileEdit.diff` as unified-diff hunks (`@@ ... @@` headers
+//! followed by ` `/`-`/`+` lines) without file headers. Tool calls rarely
+//! record where in the file an edit landed, so hunks claim line 1 and rely on
+//! `git apply` locating them by content.
+
+use crate::{Event, EventType};
+
+/// Hunk for a tool call that replaced `old` with `new` (Claude Code `Edit`).
+pub fn replacement_hunk(old: &str, new: &str) -> String {
+    let old_lines = split_lines(old);
+    let new_lines = split_lines(new);
+    let mut hunk = hunk_header(old_lines.len(), new_lines.len());
+    for line in old_lines {
+        push_line(&mut hunk, '-', line);
+    }
+    for line in new_lines {
+        push_line(&mut hunk, '+', line);
+    }
+    hunk
+}
+
+/// Hunks for `path` in a Codex `apply_patch` body (`*** Begin Patch` format).
+///
+/// `*** Add File` sections become a single hunk against an empty file;
+/// deletions and moves return `None` because the patch lacks the content.
+pub fn apply_patch_hunks(patch: &str, path: &str) -> Option<String> {
+    let mut lines = patch.lines();
+    let added = loop {
+        let line = lines.next()?.trim_end();
+        if line.strip_prefix("*** Update File:").map(str::trim) == Some(path) {
+            break false;
+        }
+        if line.strip_prefix("*** Add File:").map(str::trim) == Some(path) {
+            break true;
+        }
+    };
+
+    let mut chunks: Vec<Vec<&str>> = vec![Vec::new()];
+    for line in lines {
+        if line.starts_with("*** End of File") {
+            continue;
+        }
+        if line.starts_with("*** Move to:") {
+            return None;
+        }
+        if line.starts_with("*** ") {
+            break;
+        }
+        if line.starts_with("@@") {
+            chunks.push(Vec::new());
+            continue;
+        }
+        if let Some(chunk) = chunks.last_mut() {
+            chunk.push(line);
+        }
+    }
+
+    let mut hunks = String::new();
+    for mut chunk in chunks {
+        // Blank separator lines before the next section are not context.
+        while chunk.last().is_some_and(|line| line.is_empty()) {
+            chunk.pop();
+        }
+        if chunk.is_empty() {
+            continue;
+        }
+        let mut old_count = 0;
+        let mut new_count = 0;
+        let mut body = String::new();
+        for line in chunk {
+            let (marker, text) = match line.chars().next() {
+                Some(marker @ ('+' | '-' | ' ')) => (marker, &line[1..]),
+                // Codex writes empty context lines without the leading space.
+                _ => (' ', line),
+            };
+            if added && marker != '+' {
+                return None;
+            }
+            if marker != '+' {
+                old_count += 1;
+            }
+            if marker != '-' {
+                new_count += 1;
+            }
+            push_line(&mut body, marker, text);
+        }
+        let header = if added {
+            format!("@@ -0,0 +1,{new_count} @@\n")
+        } else {
+            hunk_header(old_count, new_count)
+        };
+        hunks.push_str(&header);
+        hunks.push_str(&body);
+    }
+    (!hunks.is_empty()).then_some(hunks)
+}
+
+/// Whether hunks create the file from nothing (`@@ -0,0 ...`).
+pub fn creates_file(hunks: &str) -> bool {
+    hunks.starts_with("@@ -0,0 ")
+}
+
+/// A full `diff --git` section for one edit of `path` (repo-relative).
+pub fn file_patch(path: &str, hunks: &str) -> String {
+    let mut patch = format!("diff --git a/{path} b/{path}\n");
+    if creates_file(hunks) {
+        patch.push_str(&format!(
+            "new file mode 100644\n--- /dev/null\n+++ b/{path}\n"
+        ));
+    } else {
+        patch.push_str(&format!("--- a/{path}\n+++ b/{path}\n"));
+    }
+    patch.push_str(hunks);
+    if !hunks.ends_with('\n') {
+        patch.push('\n');
+    }
+    patch
+}
+
+/// File edit events in session order, split into `(path, hunks)` pairs for
+/// edits with a recorded diff and paths of edits without one. Created and
+/// deleted files carry no content and are always in the second list.
+pub fn edit_diffs(events: &[Event]) -> (Vec<(&str, &str)>, Vec<&str>) {
+    let mut diffs = Vec::new();
+    let mut missing = Vec::new();
+    for event in events {
+        match &event.event_type {
+            EventType::FileEdit {
+                path,
+                diff: Some(diff),
+            } if !diff.trim().is_empty() => diffs.push((path.as_str(), diff.as_str())),
+            EventType::FileEdit { path, .. }
+            | EventType::FileCreate { path }
+            | EventType::FileDelete { path } => missing.push(path.as_str()),
+            _ => {}
+        }
+    }
+    (diffs, missing)
+}
+
+fn split_lines(text: &str) -> Vec<&str> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+    text.strip_suffix('\n')
+        .unwrap_or(text)
+        .split('\n')
+        .collect()
+}
+
+fn hunk_header(old_count: usize, new_count: usize) -> String {
+    let old_start = usize::from(old_count > 0);
+    let new_start = usize::from(new_count > 0);
+    format!("@@ -{old_start},{old_count} +{new_start},{new_count} @@\n")
+}
+
+fn push_line(out: &mut String, marker: char, line: &str) {
+    out.push(marker);
+    out.push_str(line.strip_suffix('\r').unwrap_or(line));
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replacement_hunk_lists_removed_then_added_lines() {
+        assert_eq!(
+            replacement_hunk("let a = 1;\nlet b = 2;\n", "let a = 10;\n"),
+            "@@ -1,2 +1,1 @@\n-let a = 1;\n-let b = 2;\n+let a = 10;\n"
+        );
+        assert_eq!(replacement_hunk("", "x"), "@@ -0,0 +1,1 @@\n+x\n");
+    }
+
+    #[test]
+    fn apply_patch_hunks_picks_the_target_file() {
+        let patch = "*** Begin Patch\n\
+                     *** Update File: src/a.rs\n\
+                     @@ fn main\n\
+                     \x20fn main() {\n\
+                     -    old();\n\
+                     +    new();\n\
+                     \n\
+                     *** Add File: src/b.rs\n\
+                     +pub fn b() {}\n\
+                     *** End Patch\n";
+        assert_eq!(
+            apply_patch_hunks(patch, "src/a.rs").as_deref(),
+            Some("@@ -1,2 +1,2 @@\n fn main() {\n-    old();\n+    new();\n")
+        );
+        let added = apply_patch_hunks(patch, "src/b.rs").unwrap();
+        assert_eq!(added, "@@ -0,0 +1,1 @@\n+pub fn b() {}\n");
+        assert!(file_patch("src/b.rs", &added).contains("--- /dev/null\n+++ b/src/b.rs\n"));
+        assert_eq!(apply_patch_hunks(patch, "src/missing.rs"), None);
+    }
+}
@@ -0,0 +1,41 @@
+//! JSON Schema for the HAIL session format.
+//!
+//! The schema is generated from the trace types with the `schema` feature and
+//! committed, so consumers get it without depending on `schemars`.
+//! Regenerate with: cargo test -p opensession-core --features schema -- session_schema
+
+/// Canonical JSON Schema (draft 2020-12) describing a serialized [`crate::Session`].
+pub const SESSION_JSON_SCHEMA: &str = include_str!("../schema/session.schema.json");
+
+#[cfg(test)]
+mod tests {
+    use super::SESSION_JSON_SCHEMA;
+
+    #[test]
+    fn committed_session_schema_is_valid_json() {
+        let schema: serde_json::Value =
+            serde_json::from_str(SESSION_JSON_SCHEMA).expect("schema should parse");
+        assert_eq!(schema["title"], "Session");
+        assert!(schema["properties"]["events"].is_object());
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn session_schema_matches_trace_types() {
+        let schema = schemars::schema_for!(crate::Session);
+        let generated = format!(
+            "{}\n",
+            serde_json::to_string_pretty(&schema).expect("schema should serialize")
+        );
+        if generated != SESSION_JSON_SCHEMA {
+            let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("schema/session.schema.json");
+            std::fs::write(&path, &generated)
+                .unwrap_or_else(|e| panic!("Failed to write {}: {}", path.display(), e));
+            panic!(
+                "{} was stale and has been regenerated; re-run the tests and commit it",
+                path.display()
+            );
+        }
+    }
+}
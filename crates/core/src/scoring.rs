@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 pub const DEFAULT_SCORE_PLUGIN: &str = "heuristic_v1";
+/// Plugin behind the user-facing "best" sort order.
+pub const USEFULNESS_SCORE_PLUGIN: &str = "usefulness_v1";
 
 /// A scoring plugin maps one session to one numeric score.
 pub trait SessionScorePlugin: Send + Sync {
@@ -53,6 +55,7 @@ impl Default for SessionScoreRegistry {
     fn default() -> Self {
         let mut registry = Self::new(DEFAULT_SCORE_PLUGIN);
         registry.register(HeuristicV1ScorePlugin);
+        registry.register(UsefulnessV1ScorePlugin);
         registry.register(ZeroV1ScorePlugin);
         registry
     }
@@ -136,6 +139,67 @@ impl SessionScorePlugin for HeuristicV1ScorePlugin {
     }
 }
 
+/// User-facing "usefulness" scorer used for the `best` sort order.
+///
+/// Formula:
+/// - user engagement: +8 per user message (cap +32)
+/// - agent replies: +2 per agent message (cap +16)
+/// - completion: +12 if a task ended, +12 if an agent message follows the last user message
+/// - error-free finish: +16 if the last shell/tool outcome succeeded (or none ran)
+/// - `has_errors` => -12
+/// - clamp to 0..100
+pub struct UsefulnessV1ScorePlugin;
+
+impl SessionScorePlugin for UsefulnessV1ScorePlugin {
+    fn id(&self) -> &'static str {
+        USEFULNESS_SCORE_PLUGIN
+    }
+
+    fn score(&self, session: &Session) -> i64 {
+        let (_, _, has_errors) = extract_file_metadata(session);
+        let mut user_messages = 0i64;
+        let mut agent_messages = 0i64;
+        let mut task_ended = false;
+        let mut answered_last_user = false;
+        let mut last_outcome_ok = true;
+
+        for event in &session.events {
+            match &event.event_type {
+                EventType::UserMessage => {
+                    user_messages += 1;
+                    answered_last_user = false;
+                }
+                EventType::AgentMessage => {
+                    agent_messages += 1;
+                    answered_last_user = true;
+                }
+                EventType::TaskEnd { .. } => task_ended = true,
+                EventType::ShellCommand {
+                    exit_code: Some(code),
+                    ..
+                } => last_outcome_ok = *code == 0,
+                EventType::ToolResult { is_error, .. } => last_outcome_ok = !is_error,
+                _ => {}
+            }
+        }
+
+        let mut score = (user_messages * 8).min(32) + (agent_messages * 2).min(16);
+        if task_ended {
+            score += 12;
+        }
+        if answered_last_user && user_messages > 0 {
+            score += 12;
+        }
+        if last_outcome_ok {
+            score += 16;
+        }
+        if has_errors {
+            score -= 12;
+        }
+        score.clamp(0, 100)
+    }
+}
+
 /// A deterministic scorer useful for testing and compatibility checks.
 pub struct ZeroV1ScorePlugin;
 
@@ -247,6 +311,7 @@ mod tests {
         let registry = SessionScoreRegistry::default();
         let names = registry.available_plugins();
         assert!(names.contains(&"heuristic_v1".to_string()));
+        assert!(names.contains(&"usefulness_v1".to_string()));
         assert!(names.contains(&"zero_v1".to_string()));
     }
 
@@ -280,6 +345,48 @@ mod tests {
         assert_eq!(result.score, 85);
     }
 
+    #[test]
+    fn usefulness_v1_rewards_engagement_and_clean_finish() {
+        let registry = SessionScoreRegistry::default();
+        let engaged = build_session(vec![
+            testing::event(EventType::UserMessage, "fix the build"),
+            testing::event(
+                EventType::ShellCommand {
+                    command: "cargo test".to_string(),
+                    exit_code: Some(0),
+                },
+                "",
+            ),
+            testing::event(EventType::AgentMessage, "done"),
+            testing::event(EventType::UserMessage, "thanks, now docs"),
+            testing::event(EventType::AgentMessage, "updated"),
+        ]);
+        let abandoned = build_session(vec![
+            testing::event(EventType::UserMessage, "fix the build"),
+            testing::event(
+                EventType::ShellCommand {
+                    command: "cargo test".to_string(),
+                    exit_code: Some(101),
+                },
+                "",
+            ),
+        ]);
+
+        let engaged_score = registry
+            .score_with(USEFULNESS_SCORE_PLUGIN, &engaged)
+            .expect("usefulness scorer must exist")
+            .score;
+        let abandoned_score = registry
+            .score_with(USEFULNESS_SCORE_PLUGIN, &abandoned)
+            .expect("usefulness scorer must exist")
+            .score;
+
+        // 2 users (+16), 2 agents (+4), answered (+12), clean finish (+16)
+        assert_eq!(engaged_score, 48);
+        // 1 user (+8), failed last command (+0), has_errors (-12) => clamped
+        assert_eq!(abandoned_score, 0);
+    }
+
     #[test]
     fn zero_plugin_returns_zero() {
         let session = build_session(vec![testing::event(EventType::UserMessage, "hello")]);
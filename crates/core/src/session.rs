@@ -6,6 +6,10 @@ pub const ATTR_WORKING_DIRECTORY: &str = "working_directory";
 pub const ATTR_SOURCE_PATH: &str = "source_path";
 pub const ATTR_SESSION_ROLE: &str = "session_role";
 pub const ATTR_PARENT_SESSION_ID: &str = "parent_session_id";
+pub const ATTR_SESSION_KIND: &str = "session_kind";
+pub const ATTR_TOOL_MIX: &str = "tool_mix";
+/// [`ATTR_SESSION_KIND`] of a session reduced by [`metrics_only_session`].
+pub const METRICS_ONLY_KIND: &str = "metrics_only";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SessionRole {
@@ -75,6 +79,56 @@ pub fn interaction_compressed_stats(session: &Session) -> Stats {
     interaction_compressed_session(session).stats
 }
 
+/// Whether `session` was reduced by [`metrics_only_session`].
+pub fn is_metrics_only_session(session: &Session) -> bool {
+    attr_non_empty_str(session, ATTR_SESSION_KIND) == Some(METRICS_ONLY_KIND)
+}
+
+/// Reduce a session to aggregate metrics, for repositories too sensitive to
+/// share transcripts from.
+///
+/// Keeps the id, agent, timestamps and stats, plus tool call counts by tool
+/// name under [`ATTR_TOOL_MIX`]. Every event, the title, description, tags,
+/// links and all other attributes are dropped. Reducing an already reduced
+/// session returns it unchanged.
+pub fn metrics_only_session(session: &Session) -> Session {
+    let tool_mix = if is_metrics_only_session(session) {
+        session
+            .context
+            .attributes
+            .get(ATTR_TOOL_MIX)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+            .unwrap_or_default()
+    } else {
+        tool_mix(session)
+    };
+
+    let mut reduced = Session::new(session.session_id.clone(), session.agent.clone());
+    reduced.version = session.version.clone();
+    reduced.context.created_at = session.context.created_at;
+    reduced.context.updated_at = session.context.updated_at;
+    reduced.context.attributes.insert(
+        ATTR_SESSION_KIND.to_string(),
+        serde_json::Value::String(METRICS_ONLY_KIND.to_string()),
+    );
+    reduced.context.attributes.insert(
+        ATTR_TOOL_MIX.to_string(),
+        serde_json::to_value(tool_mix).unwrap_or_default(),
+    );
+    reduced.stats = session.stats.clone();
+    reduced
+}
+
+fn tool_mix(session: &Session) -> std::collections::BTreeMap<String, u64> {
+    let mut mix = std::collections::BTreeMap::new();
+    for event in &session.events {
+        if let EventType::ToolCall { name } = &event.event_type {
+            *mix.entry(name.clone()).or_insert(0) += 1;
+        }
+    }
+    mix
+}
+
 fn is_interaction_flow_event(event: &Event) -> bool {
     if is_interrupt_like_event(event) {
         return true;
@@ -173,7 +227,8 @@ mod tests {
         ATTR_PARENT_SESSION_ID, ATTR_SESSION_ROLE, GitMeta, SessionRole,
         build_git_storage_meta_json, build_git_storage_meta_json_with_git,
         interaction_compressed_session, interaction_compressed_stats, is_auxiliary_session,
-        session_role, source_path, working_directory,
+        is_metrics_only_session, metrics_only_session, session_role, source_path,
+        working_directory,
     };
     use crate::trace::{Agent, Content, Event, EventType, Session};
     use serde_json::Value;
@@ -399,4 +454,48 @@ mod tests {
         ));
         assert_eq!(compressed.stats.event_count, 1);
     }
+
+    #[test]
+    fn metrics_only_session_keeps_stats_and_tool_mix_but_no_content() {
+        let mut session = make_session();
+        session.context.title = Some("rotate prod keys".to_string());
+        session.context.tags = vec!["secret".to_string()];
+        session
+            .context
+            .attributes
+            .insert("cwd".to_string(), Value::String("/repo/vault".to_string()));
+        for (id, name) in [("e1", "bash"), ("e2", "bash"), ("e3", "read_file")] {
+            session.events.push(Event {
+                event_id: id.to_string(),
+                timestamp: chrono::Utc::now(),
+                event_type: EventType::ToolCall {
+                    name: name.to_string(),
+                },
+                task_id: None,
+                content: Content::text("cat .env"),
+                duration_ms: None,
+                attributes: HashMap::new(),
+            });
+        }
+        session.recompute_stats();
+        session.stats.total_input_tokens = 1_200;
+
+        let reduced = metrics_only_session(&session);
+        assert!(is_metrics_only_session(&reduced));
+        assert!(!is_metrics_only_session(&session));
+        assert!(reduced.events.is_empty());
+        assert_eq!(reduced.context.title, None);
+        assert!(reduced.context.tags.is_empty());
+        assert_eq!(working_directory(&reduced), None);
+        assert_eq!(reduced.stats.tool_call_count, 3);
+        assert_eq!(reduced.stats.total_input_tokens, 1_200);
+        assert_eq!(
+            reduced.context.attributes["tool_mix"],
+            serde_json::json!({ "bash": 2, "read_file": 1 })
+        );
+
+        let again = metrics_only_session(&reduced);
+        assert_eq!(again.context.attributes, reduced.context.attributes);
+        assert_eq!(again.stats.tool_call_count, 3);
+    }
 }
@@ -52,3 +52,340 @@ fn next_id() -> u32 {
     static COUNTER: AtomicU32 = AtomicU32::new(0);
     COUNTER.fetch_add(1, Ordering::Relaxed)
 }
+
+/// Deterministic generator for realistic sessions, for benchmarks, e2e
+/// fixtures, parser tests and load testing.
+///
+/// The same seed and settings always produce the same session: ids,
+/// timestamps, tool mix, injected errors and text are all derived from the
+/// seed.
+///
+/// ```ignore
+/// let session = SessionGenerator::new(7).events(500).error_rate(0.1).generate();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SessionGenerator {
+    seed: u64,
+    events: usize,
+    tool_call_ratio: f64,
+    error_rate: f64,
+    text_bytes: usize,
+    agent: Agent,
+    start: chrono::DateTime<chrono::Utc>,
+}
+
+/// Tools the generator draws from, with the action event each one records.
+const GENERATED_TOOLS: [&str; 5] = ["Read", "Edit", "Bash", "Grep", "WebFetch"];
+
+const WORDS: [&str; 24] = [
+    "refactor",
+    "the",
+    "parser",
+    "so",
+    "that",
+    "session",
+    "events",
+    "keep",
+    "their",
+    "order",
+    "when",
+    "tool",
+    "results",
+    "arrive",
+    "late",
+    "and",
+    "add",
+    "a",
+    "regression",
+    "test",
+    "for",
+    "timeout",
+    "handling",
+    "cleanly",
+];
+
+impl SessionGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            events: 20,
+            tool_call_ratio: 0.5,
+            error_rate: 0.0,
+            text_bytes: 200,
+            agent: agent(),
+            start: chrono::DateTime::from_timestamp(1_735_689_600, 0)
+                .expect("fixed start timestamp is valid"),
+        }
+    }
+
+    /// Exact number of events in the generated session.
+    pub fn events(mut self, events: usize) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Share of agent steps that are tool interactions (0.0..=1.0).
+    pub fn tool_call_ratio(mut self, ratio: f64) -> Self {
+        self.tool_call_ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Share of tool results reported as errors (0.0..=1.0).
+    pub fn error_rate(mut self, rate: f64) -> Self {
+        self.error_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Approximate size of each message and tool output, in bytes.
+    pub fn text_bytes(mut self, bytes: usize) -> Self {
+        self.text_bytes = bytes;
+        self
+    }
+
+    pub fn agent(mut self, agent: Agent) -> Self {
+        self.agent = agent;
+        self
+    }
+
+    pub fn start(mut self, start: chrono::DateTime<chrono::Utc>) -> Self {
+        self.start = start;
+        self
+    }
+
+    pub fn generate(&self) -> crate::Session {
+        let mut rng = SplitMix64(self.seed);
+        let mut id_bytes = [0u8; 16];
+        id_bytes[..8].copy_from_slice(&rng.next_u64().to_le_bytes());
+        id_bytes[8..].copy_from_slice(&rng.next_u64().to_le_bytes());
+        let session_id = uuid::Builder::from_random_bytes(id_bytes)
+            .into_uuid()
+            .to_string();
+
+        let mut session = crate::Session::new(session_id, self.agent.clone());
+        session.context.title = Some(format!("synthetic session {}", self.seed));
+        session.context.created_at = self.start;
+
+        let mut builder = EventSink {
+            events: Vec::with_capacity(self.events + 2),
+            clock: self.start,
+            seed: self.seed,
+        };
+        let mut turn = 0usize;
+        while builder.events.len() < self.events {
+            turn += 1;
+            let task_id = format!("task-{turn}");
+            let text = self.text(&mut rng);
+            builder.push(
+                &mut rng,
+                EventType::UserMessage,
+                &task_id,
+                Content::text(text),
+                None,
+            );
+
+            let steps = 1 + rng.below(4);
+            for _ in 0..steps {
+                if builder.events.len() >= self.events {
+                    break;
+                }
+                if rng.chance(self.tool_call_ratio) {
+                    self.push_tool_use(&mut rng, &mut builder, &task_id);
+                } else {
+                    let text = self.text(&mut rng);
+                    builder.push(
+                        &mut rng,
+                        EventType::AgentMessage,
+                        &task_id,
+                        Content::text(text),
+                        None,
+                    );
+                }
+            }
+        }
+        builder.events.truncate(self.events);
+
+        session.context.updated_at = builder
+            .events
+            .last()
+            .map_or(self.start, |event| event.timestamp);
+        session.events = builder.events;
+        session.recompute_stats();
+        session
+    }
+
+    fn push_tool_use(&self, rng: &mut SplitMix64, sink: &mut EventSink, task_id: &str) {
+        let name = GENERATED_TOOLS[rng.below(GENERATED_TOOLS.len())];
+        let file = format!("src/module_{}.rs", rng.below(40));
+        let is_error = rng.chance(self.error_rate);
+        let call_id = format!("call-{}", sink.events.len());
+
+        sink.push(
+            rng,
+            EventType::ToolCall {
+                name: name.to_string(),
+            },
+            task_id,
+            Content::empty(),
+            None,
+        );
+        let action = match name {
+            "Read" => EventType::FileRead { path: file.clone() },
+            "Edit" => EventType::FileEdit {
+                path: file.clone(),
+                diff: Some(format!(
+                    "--- a/{file}\n+++ b/{file}\n@@ -1 +1 @@\n-old\n+new\n"
+                )),
+            },
+            "Bash" => EventType::ShellCommand {
+                command: "cargo test --quiet".to_string(),
+                exit_code: Some(i32::from(is_error)),
+            },
+            "Grep" => EventType::CodeSearch {
+                query: WORDS[rng.below(WORDS.len())].to_string(),
+            },
+            _ => EventType::WebFetch {
+                url: format!("https://docs.example.com/page/{}", rng.below(100)),
+            },
+        };
+        sink.push(rng, action, task_id, Content::empty(), None);
+
+        let output = if is_error {
+            format!("error: {name} failed: {}", self.text(rng))
+        } else {
+            self.text(rng)
+        };
+        let duration_ms = 20 + rng.below(2_000) as u64;
+        sink.push(
+            rng,
+            EventType::ToolResult {
+                name: name.to_string(),
+                is_error,
+                call_id: Some(call_id),
+            },
+            task_id,
+            Content::code(output, None),
+            Some(duration_ms),
+        );
+    }
+
+    fn text(&self, rng: &mut SplitMix64) -> String {
+        let mut out = String::with_capacity(self.text_bytes + 16);
+        while out.len() < self.text_bytes.max(1) {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            out.push_str(WORDS[rng.below(WORDS.len())]);
+        }
+        out
+    }
+}
+
+struct EventSink {
+    events: Vec<Event>,
+    clock: chrono::DateTime<chrono::Utc>,
+    seed: u64,
+}
+
+impl EventSink {
+    fn push(
+        &mut self,
+        rng: &mut SplitMix64,
+        event_type: EventType,
+        task_id: &str,
+        content: Content,
+        duration_ms: Option<u64>,
+    ) {
+        self.clock += chrono::Duration::milliseconds(200 + rng.below(5_000) as i64);
+        self.events.push(Event {
+            event_id: format!("evt-{:x}-{}", self.seed, self.events.len()),
+            timestamp: self.clock,
+            event_type,
+            task_id: Some(task_id.to_string()),
+            content,
+            duration_ms,
+            attributes: HashMap::new(),
+        });
+    }
+}
+
+/// SplitMix64: tiny, fast and good enough for fixture data.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound.max(1) as u64) as usize
+    }
+
+    /// True with `probability`; the uniform draw is in `[0, 1)`, so 0 never and 1 always hits.
+    fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SessionGenerator;
+    use crate::EventType;
+
+    #[test]
+    fn same_seed_generates_identical_sessions() {
+        let a = SessionGenerator::new(42)
+            .events(200)
+            .error_rate(0.2)
+            .generate();
+        let b = SessionGenerator::new(42)
+            .events(200)
+            .error_rate(0.2)
+            .generate();
+        assert_eq!(
+            serde_json::to_string(&a).unwrap(),
+            serde_json::to_string(&b).unwrap()
+        );
+        let c = SessionGenerator::new(43).events(200).generate();
+        assert_ne!(a.session_id, c.session_id);
+    }
+
+    #[test]
+    fn generator_honours_event_count_tool_mix_and_errors() {
+        let session = SessionGenerator::new(1)
+            .events(500)
+            .tool_call_ratio(1.0)
+            .error_rate(1.0)
+            .generate();
+        assert_eq!(session.events.len(), 500);
+        assert_eq!(session.stats.event_count, 500);
+        assert!(
+            !session
+                .events
+                .iter()
+                .any(|event| matches!(event.event_type, EventType::AgentMessage))
+        );
+        assert!(session.events.iter().all(|event| match &event.event_type {
+            EventType::ToolResult { is_error, .. } => *is_error,
+            _ => true,
+        }));
+
+        let chatty = SessionGenerator::new(1)
+            .events(100)
+            .tool_call_ratio(0.0)
+            .generate();
+        assert!(chatty.events.iter().all(|event| matches!(
+            event.event_type,
+            EventType::UserMessage | EventType::AgentMessage
+        )));
+        assert!(
+            chatty
+                .events
+                .windows(2)
+                .all(|w| w[0].timestamp < w[1].timestamp)
+        );
+    }
+}
@@ -12,9 +12,12 @@ pub const ATTR_SEMANTIC_GROUP_ID: &str = "semantic.group_id";
 pub const ATTR_SEMANTIC_CALL_ID: &str = "semantic.call_id";
 /// Canonical event attribute key for semantic tool kind classification.
 pub const ATTR_SEMANTIC_TOOL_KIND: &str = "semantic.tool_kind";
+/// Canonical event attribute key listing content blocks cut by [`crate::truncate`].
+pub const ATTR_CONTENT_TRUNCATED_BLOCKS: &str = "content.truncated_blocks";
 
 /// Top-level session - the root of a HAIL (Human AI Interaction Log) trace
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Session {
     /// Format version, e.g. "hail-1.0.0"
     pub version: String,
@@ -150,6 +153,11 @@ impl Session {
         }
     }
 
+    /// Parsed format version, or `None` when `version` is not `hail-<semver>`.
+    pub fn hail_version(&self) -> Option<crate::migrate::HailVersion> {
+        crate::migrate::HailVersion::parse(&self.version)
+    }
+
     /// Serialize to HAIL JSONL string
     pub fn to_jsonl(&self) -> Result<String, crate::jsonl::JsonlError> {
         crate::jsonl::to_jsonl_string(self)
@@ -172,6 +180,7 @@ impl Session {
 
 /// AI agent information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Agent {
     /// Provider: "anthropic", "openai", "local"
     pub provider: String,
@@ -186,6 +195,7 @@ pub struct Agent {
 
 /// Session context metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SessionContext {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -218,6 +228,7 @@ impl Default for SessionContext {
 
 /// A single event in the flat timeline
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Event {
     /// Unique event identifier
     pub event_id: String,
@@ -294,6 +305,7 @@ impl Event {
 
 /// Event type - the core abstraction
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", content = "data")]
 #[non_exhaustive]
 pub enum EventType {
@@ -378,6 +390,7 @@ pub enum EventType {
 
 /// Multimodal content container
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Content {
     pub blocks: Vec<ContentBlock>,
 }
@@ -406,6 +419,7 @@ impl Content {
 
 /// Individual content block
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 #[non_exhaustive]
 pub enum ContentBlock {
@@ -449,6 +463,7 @@ pub enum ContentBlock {
 
 /// Aggregate session statistics
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Stats {
     pub event_count: u64,
     pub message_count: u64,
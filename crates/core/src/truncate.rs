@@ -0,0 +1,256 @@
+//! Event-level content truncation with a side store for full content.
+//!
+//! Giant tool outputs make every consumer of a session slow. [`truncate_session`]
+//! cuts oversized text/code/file blocks down to a preview, records the cut in
+//! the event's [`ATTR_CONTENT_TRUNCATED_BLOCKS`] attribute, and hands back the
+//! full content as [`TruncatedBlock`]s. Callers persist those next to the
+//! session (see [`to_side_jsonl`]) and load them on demand with
+//! [`find_full_block`].
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::trace::{ATTR_CONTENT_TRUNCATED_BLOCKS, ContentBlock, Event, Session};
+
+/// Default per-block size limit (64 KiB).
+pub const DEFAULT_MAX_BLOCK_BYTES: usize = 64 * 1024;
+
+/// Full content of one block removed from a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TruncatedBlock {
+    pub event_id: String,
+    /// Index into the event's `content.blocks`.
+    pub block: usize,
+    pub original_bytes: usize,
+    pub content: String,
+}
+
+/// Marker stored in [`ATTR_CONTENT_TRUNCATED_BLOCKS`] for each cut block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TruncationMarker {
+    pub block: usize,
+    pub original_bytes: usize,
+}
+
+/// Truncate every block larger than `max_bytes`, returning the removed content.
+///
+/// Blocks already listed in the event's truncation attribute are left alone,
+/// so running the transform twice is a no-op.
+pub fn truncate_session(session: &mut Session, max_bytes: usize) -> Vec<TruncatedBlock> {
+    session
+        .events
+        .iter_mut()
+        .flat_map(|event| truncate_event(event, max_bytes))
+        .collect()
+}
+
+/// Truncate oversized blocks of a single event.
+pub fn truncate_event(event: &mut Event, max_bytes: usize) -> Vec<TruncatedBlock> {
+    let mut markers = truncation_markers(event);
+    let mut removed = Vec::new();
+    for (index, block) in event.content.blocks.iter_mut().enumerate() {
+        if markers.iter().any(|marker| marker.block == index) {
+            continue;
+        }
+        let Some(text) = block_text_mut(block) else {
+            continue;
+        };
+        if text.len() <= max_bytes {
+            continue;
+        }
+        let original_bytes = text.len();
+        let preview = preview_of(text, max_bytes, original_bytes);
+        let content = std::mem::replace(text, preview);
+        markers.push(TruncationMarker {
+            block: index,
+            original_bytes,
+        });
+        removed.push(TruncatedBlock {
+            event_id: event.event_id.clone(),
+            block: index,
+            original_bytes,
+            content,
+        });
+    }
+    if !removed.is_empty() {
+        event.attributes.insert(
+            ATTR_CONTENT_TRUNCATED_BLOCKS.to_string(),
+            serde_json::to_value(&markers).unwrap_or_default(),
+        );
+    }
+    removed
+}
+
+/// Blocks of `event` that were cut by [`truncate_event`].
+pub fn truncation_markers(event: &Event) -> Vec<TruncationMarker> {
+    event
+        .attributes
+        .get(ATTR_CONTENT_TRUNCATED_BLOCKS)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Serialize removed blocks as a side file: one JSON object per line.
+pub fn to_side_jsonl(blocks: &[TruncatedBlock]) -> Result<String, serde_json::Error> {
+    let mut out = String::new();
+    for block in blocks {
+        out.push_str(&serde_json::to_string(block)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Conventional side file location next to a session file:
+/// `x.hail.jsonl` becomes `x.blocks.jsonl`, other names get `.blocks.jsonl` appended.
+pub fn side_file_path(session_path: &Path) -> PathBuf {
+    let name = session_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let side_name = match name.strip_suffix(".hail.jsonl") {
+        Some(stem) => format!("{stem}.blocks.jsonl"),
+        None => format!("{name}.blocks.jsonl"),
+    };
+    session_path.with_file_name(side_name)
+}
+
+/// Look up the full content of one block in a side file.
+///
+/// Only lines that contain the JSON-encoded `event_id` are deserialized;
+/// the rest are skipped with a substring check.
+pub fn find_full_block(
+    side_jsonl: &str,
+    event_id: &str,
+    block: usize,
+) -> Result<Option<String>, serde_json::Error> {
+    let needle = serde_json::to_string(event_id)?;
+    for line in side_jsonl.lines().filter(|line| line.contains(&needle)) {
+        let entry: TruncatedBlock = serde_json::from_str(line)?;
+        if entry.event_id == event_id && entry.block == block {
+            return Ok(Some(entry.content));
+        }
+    }
+    Ok(None)
+}
+
+fn block_text_mut(block: &mut ContentBlock) -> Option<&mut String> {
+    match block {
+        ContentBlock::Text { text } => Some(text),
+        ContentBlock::Code { code, .. } => Some(code),
+        ContentBlock::File {
+            content: Some(content),
+            ..
+        } => Some(content),
+        _ => None,
+    }
+}
+
+fn preview_of(text: &str, max_bytes: usize, original_bytes: usize) -> String {
+    let mut end = max_bytes.min(text.len());
+    while end > 0 && !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}\n[truncated: {} of {original_bytes} bytes omitted]",
+        &text[..end],
+        original_bytes - end
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+    use crate::trace::{Content, EventType};
+
+    fn session_with(text: &str) -> Session {
+        let mut session = Session::new("s1".to_string(), testing::agent());
+        session.events.push(testing::event_with_content(
+            EventType::ToolResult {
+                name: "bash".to_string(),
+                is_error: false,
+                call_id: None,
+            },
+            Content {
+                blocks: vec![
+                    ContentBlock::Text {
+                        text: "short".to_string(),
+                    },
+                    ContentBlock::Code {
+                        code: text.to_string(),
+                        language: None,
+                        start_line: None,
+                    },
+                ],
+            },
+        ));
+        session
+    }
+
+    #[test]
+    fn truncates_only_oversized_blocks_and_marks_event() {
+        let big = "é".repeat(100);
+        let mut session = session_with(&big);
+        let removed = truncate_session(&mut session, 51);
+
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].block, 1);
+        assert_eq!(removed[0].original_bytes, 200);
+        assert_eq!(removed[0].content, big);
+
+        let event = &session.events[0];
+        let ContentBlock::Code { code, .. } = &event.content.blocks[1] else {
+            panic!("expected code block");
+        };
+        assert!(code.starts_with(&"é".repeat(25)));
+        assert!(code.ends_with("[truncated: 150 of 200 bytes omitted]"));
+        assert_eq!(
+            truncation_markers(event),
+            vec![TruncationMarker {
+                block: 1,
+                original_bytes: 200
+            }]
+        );
+    }
+
+    #[test]
+    fn truncation_is_idempotent() {
+        let mut session = session_with(&"x".repeat(500));
+        assert_eq!(truncate_session(&mut session, 100).len(), 1);
+        assert!(truncate_session(&mut session, 100).is_empty());
+    }
+
+    #[test]
+    fn side_file_round_trip_finds_full_content() {
+        let mut session = session_with(&"y".repeat(300));
+        let removed = truncate_session(&mut session, 10);
+        let side = to_side_jsonl(&removed).expect("side file");
+        let event_id = &session.events[0].event_id;
+
+        assert_eq!(
+            find_full_block(&side, event_id, 1).expect("lookup"),
+            Some("y".repeat(300))
+        );
+        assert_eq!(find_full_block(&side, event_id, 0).expect("lookup"), None);
+
+        // Lines for other events are skipped without being parsed.
+        let side = format!("{{\"event_id\":\"other\",\"block\":\n{side}");
+        assert_eq!(
+            find_full_block(&side, event_id, 1).expect("lookup"),
+            Some("y".repeat(300))
+        );
+    }
+
+    #[test]
+    fn side_file_path_replaces_hail_suffix() {
+        assert_eq!(
+            side_file_path(Path::new("/tmp/run.hail.jsonl")),
+            PathBuf::from("/tmp/run.blocks.jsonl")
+        );
+        assert_eq!(
+            side_file_path(Path::new("run.json")),
+            PathBuf::from("run.json.blocks.jsonl")
+        );
+    }
+}
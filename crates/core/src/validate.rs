@@ -1,3 +1,5 @@
+use crate::jsonl::{JsonlError, from_jsonl_str};
+use crate::migrate::{MigrateError, negotiate};
 use crate::trace::{Event, EventType, Session};
 use thiserror::Error;
 
@@ -8,6 +10,11 @@ pub enum ValidationError {
     MissingField { field: String },
     #[error("invalid version: {version}, expected prefix 'hail-'")]
     InvalidVersion { version: String },
+    #[error("unsupported version: {version}, this build reads up to hail-{supported_major}.x")]
+    UnsupportedVersion {
+        version: String,
+        supported_major: u32,
+    },
     #[error("empty session: no events")]
     EmptySession,
     #[error("invalid event at index {index}: {reason}")]
@@ -16,6 +23,56 @@ pub enum ValidationError {
     EventsOutOfOrder { index: usize },
     #[error("duplicate event_id: {event_id}")]
     DuplicateEventId { event_id: String },
+    #[error("unreadable HAIL JSONL: {reason}")]
+    Unreadable { line: usize, reason: String },
+}
+
+impl ValidationError {
+    /// Stable machine-readable error code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MissingField { .. } => "missing_field",
+            Self::InvalidVersion { .. } => "invalid_version",
+            Self::UnsupportedVersion { .. } => "unsupported_version",
+            Self::EmptySession => "empty_session",
+            Self::InvalidEvent { .. } => "invalid_event",
+            Self::EventsOutOfOrder { .. } => "events_out_of_order",
+            Self::DuplicateEventId { .. } => "duplicate_event_id",
+            Self::Unreadable { .. } => "unreadable",
+        }
+    }
+
+    /// Location of the problem, e.g. `agent.tool`, `events[3]` or `line 2`.
+    pub fn path(&self) -> String {
+        match self {
+            Self::MissingField { field } => field.clone(),
+            Self::InvalidVersion { .. } | Self::UnsupportedVersion { .. } => "version".to_string(),
+            Self::EmptySession => "events".to_string(),
+            Self::InvalidEvent { index, .. } | Self::EventsOutOfOrder { index } => {
+                format!("events[{index}]")
+            }
+            Self::DuplicateEventId { .. } => "events[].event_id".to_string(),
+            Self::Unreadable { line, .. } => format!("line {line}"),
+        }
+    }
+}
+
+/// Parse HAIL JSONL and validate the resulting session.
+///
+/// Parse failures are reported as a single [`ValidationError::Unreadable`].
+pub fn validate_jsonl(body: &str) -> Result<Session, Vec<ValidationError>> {
+    let session = from_jsonl_str(body).map_err(|err| {
+        let line = match &err {
+            JsonlError::Json { line, .. } | JsonlError::UnexpectedLineType(line) => *line,
+            _ => 1,
+        };
+        vec![ValidationError::Unreadable {
+            line,
+            reason: err.to_string(),
+        }]
+    })?;
+    validate_session(&session)?;
+    Ok(session)
 }
 
 /// Validate a complete session by composing independent validators.
@@ -37,12 +94,17 @@ pub fn validate_session(session: &Session) -> Result<(), Vec<ValidationError>> {
 }
 
 fn validate_version(session: &Session) -> Vec<ValidationError> {
-    if session.version.starts_with("hail-") {
-        vec![]
-    } else {
-        vec![ValidationError::InvalidVersion {
+    match negotiate(&session.version) {
+        Ok(_) => vec![],
+        Err(MigrateError::Unsupported {
+            supported_major, ..
+        }) => vec![ValidationError::UnsupportedVersion {
             version: session.version.clone(),
-        }]
+            supported_major,
+        }],
+        Err(_) => vec![ValidationError::InvalidVersion {
+            version: session.version.clone(),
+        }],
     }
 }
 
@@ -213,6 +275,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_newer_major_version_is_unsupported() {
+        let mut session = make_session_with_events(vec![Event {
+            event_id: "e1".to_string(),
+            timestamp: Utc::now(),
+            event_type: EventType::UserMessage,
+            task_id: None,
+            content: Content::text("hello"),
+            duration_ms: None,
+            attributes: HashMap::new(),
+        }]);
+        session.version = "hail-2.0.0".to_string();
+        let errs = validate_session(&session).unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].code(), "unsupported_version");
+        assert_eq!(errs[0].path(), "version");
+    }
+
     #[test]
     fn test_duplicate_event_id() {
         let now = Utc::now();
@@ -420,4 +500,20 @@ mod tests {
         let session = make_session_with_events(events);
         assert!(validate_session(&session).is_ok());
     }
+
+    #[test]
+    fn test_validate_jsonl_reports_codes_and_paths() {
+        let mut session = make_session_with_events(vec![]);
+        session.agent.tool.clear();
+        let body = crate::jsonl::to_jsonl_string(&session).unwrap();
+        let errs = validate_jsonl(&body).unwrap_err();
+        let found: Vec<_> = errs.iter().map(|e| (e.code(), e.path())).collect();
+        assert!(found.contains(&("missing_field", "agent.tool".to_string())));
+        assert!(found.contains(&("empty_session", "events".to_string())));
+
+        let errs = validate_jsonl("{\"type\":\"header\"").unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].code(), "unreadable");
+        assert_eq!(errs[0].path(), "line 1");
+    }
 }
@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 #[command(
@@ -14,6 +15,34 @@ pub(crate) struct Cli {
 pub(crate) enum DaemonCommand {
     /// Start the daemon event loop.
     Run,
+    /// Inspect the auto-publish policy.
+    Policy {
+        #[command(subcommand)]
+        action: PolicyAction,
+    },
+    /// Install the daemon as a user service that restarts on failure
+    /// (systemd on Linux, launchd on macOS).
+    Install {
+        /// Print the service definition and commands without applying them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Stop the user service and remove its definition.
+    Uninstall,
+    /// Show what the running daemon last reported: sync state and body cache.
+    Status,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum PolicyAction {
+    /// Evaluate the publish policy against a session file.
+    Test {
+        /// Agent-native session log or HAIL JSONL file.
+        session: PathBuf,
+        /// Rule to evaluate instead of the configured `daemon.publish_policy`.
+        #[arg(long)]
+        rule: Option<String>,
+    },
 }
 
 #[cfg(test)]
@@ -31,4 +60,42 @@ mod tests {
         let cli = Cli::try_parse_from(["opensession-daemon", "run"]).expect("parse cli");
         assert!(matches!(cli.command, Some(DaemonCommand::Run)));
     }
+
+    #[test]
+    fn cli_accepts_status_subcommand() {
+        let cli = Cli::try_parse_from(["opensession-daemon", "status"]).expect("parse cli");
+        assert!(matches!(cli.command, Some(DaemonCommand::Status)));
+    }
+
+    #[test]
+    fn cli_accepts_install_dry_run() {
+        let cli =
+            Cli::try_parse_from(["opensession-daemon", "install", "--dry-run"]).expect("parse cli");
+        assert!(matches!(
+            cli.command,
+            Some(DaemonCommand::Install { dry_run: true })
+        ));
+    }
+
+    #[test]
+    fn cli_accepts_policy_test_with_rule_override() {
+        let cli = Cli::try_parse_from([
+            "opensession-daemon",
+            "policy",
+            "test",
+            "session.jsonl",
+            "--rule",
+            "!has_errors",
+        ])
+        .expect("parse cli");
+        match cli.command {
+            Some(DaemonCommand::Policy {
+                action: PolicyAction::Test { session, rule },
+            }) => {
+                assert_eq!(session, PathBuf::from("session.jsonl"));
+                assert_eq!(rule.as_deref(), Some("!has_errors"));
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
 }
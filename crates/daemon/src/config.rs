@@ -1,12 +1,13 @@
 use anyhow::{Context, Result};
-use opensession_paths::home_dir;
+use opensession_paths::{expand_home, home_dir, portable_path_text};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 // Re-export shared runtime config types
 pub use opensession_runtime_config::{
-    DaemonConfig, DaemonSettings, GitStorageMethod, PublishMode, SessionDefaultView,
+    DaemonConfig, DaemonSettings, GitStorageMethod, PublishMode, SERVER_PROFILE_ENV,
+    SessionDefaultView,
 };
 
 /// Get the config directory path
@@ -24,6 +25,11 @@ pub fn pid_file_path() -> Result<PathBuf> {
     Ok(config_dir()?.join("daemon.pid"))
 }
 
+/// Get the file recording recent unclean daemon starts
+pub fn start_history_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("daemon.starts"))
+}
+
 /// Load daemon config from disk
 pub fn load_config() -> Result<DaemonConfig> {
     let path = config_path()?;
@@ -38,9 +44,167 @@ pub fn load_config() -> Result<DaemonConfig> {
     let mut config: DaemonConfig = toml::from_str(&content)
         .with_context(|| format!("Failed to parse daemon config at {}", path.display()))?;
     normalize_fixed_runtime_tuning(&mut config);
+    let profile = std::env::var(SERVER_PROFILE_ENV).unwrap_or_default();
+    select_server_profile(&mut config, &profile)?;
     Ok(config)
 }
 
+/// Apply the `[server.profiles.<name>]` chosen via `OPENSESSION_PROFILE`; blank keeps `[server]`.
+fn select_server_profile(config: &mut DaemonConfig, name: &str) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() || config.server.select_profile(name) {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "{SERVER_PROFILE_ENV}={name} does not match any server profile (known: {})",
+        config.server.profile_names().join(", ")
+    )
+}
+
+/// Log `opensession.toml` lint findings so typos and bad values are not silently
+/// replaced by serde defaults.
+pub fn log_config_issues() {
+    let Ok(path) = config_path() else {
+        return;
+    };
+    let Ok(source) = std::fs::read_to_string(&path) else {
+        return;
+    };
+    for issue in opensession_runtime_config::lint_config(&source) {
+        if issue.is_error() {
+            tracing::error!("{}: {issue}", path.display());
+        } else {
+            tracing::warn!("{}: {issue}", path.display());
+        }
+    }
+}
+
+/// Settings that differ between two configs, one `key: old -> new` entry each,
+/// for the live-reload log line. Secrets are reported without their values.
+pub fn describe_config_changes(old: &DaemonConfig, new: &DaemonConfig) -> Vec<String> {
+    fn field<T: PartialEq + std::fmt::Debug>(
+        changes: &mut Vec<String>,
+        key: &str,
+        old: &T,
+        new: &T,
+    ) {
+        if old != new {
+            changes.push(format!("{key}: {old:?} -> {new:?}"));
+        }
+    }
+
+    let mut changes = Vec::new();
+    field(
+        &mut changes,
+        "watchers.custom_paths",
+        &old.watchers.custom_paths,
+        &new.watchers.custom_paths,
+    );
+    field(
+        &mut changes,
+        "daemon.publish_on",
+        &old.daemon.publish_on,
+        &new.daemon.publish_on,
+    );
+    field(
+        &mut changes,
+        "daemon.auto_publish",
+        &old.daemon.auto_publish,
+        &new.daemon.auto_publish,
+    );
+    field(
+        &mut changes,
+        "daemon.debounce_secs",
+        &old.daemon.debounce_secs,
+        &new.daemon.debounce_secs,
+    );
+    field(
+        &mut changes,
+        "daemon.publish_policy",
+        &old.daemon.publish_policy,
+        &new.daemon.publish_policy,
+    );
+    field(
+        &mut changes,
+        "daemon.pre_upload_hooks",
+        &old.daemon.pre_upload_hooks,
+        &new.daemon.pre_upload_hooks,
+    );
+    field(
+        &mut changes,
+        "privacy.strip_paths",
+        &old.privacy.strip_paths,
+        &new.privacy.strip_paths,
+    );
+    field(
+        &mut changes,
+        "privacy.strip_env_vars",
+        &old.privacy.strip_env_vars,
+        &new.privacy.strip_env_vars,
+    );
+    field(
+        &mut changes,
+        "privacy.exclude_patterns",
+        &old.privacy.exclude_patterns,
+        &new.privacy.exclude_patterns,
+    );
+    field(
+        &mut changes,
+        "privacy.exclude_tools",
+        &old.privacy.exclude_tools,
+        &new.privacy.exclude_tools,
+    );
+    field(
+        &mut changes,
+        "privacy.capture_environment",
+        &old.privacy.capture_environment,
+        &new.privacy.capture_environment,
+    );
+    field(
+        &mut changes,
+        "privacy.telemetry",
+        &old.privacy.telemetry,
+        &new.privacy.telemetry,
+    );
+    field(&mut changes, "server.url", &old.server.url, &new.server.url);
+    field(
+        &mut changes,
+        "server.team",
+        &old.server.team,
+        &new.server.team,
+    );
+    if old.server.api_key != new.server.api_key {
+        changes.push("server.api_key: changed".to_string());
+    }
+
+    let sections = |config: &DaemonConfig| {
+        [
+            ("identity", toml::Value::try_from(&config.identity).ok()),
+            (
+                "git_storage",
+                toml::Value::try_from(&config.git_storage).ok(),
+            ),
+            ("summary", toml::Value::try_from(&config.summary).ok()),
+            (
+                "vector_search",
+                toml::Value::try_from(&config.vector_search).ok(),
+            ),
+            (
+                "change_reader",
+                toml::Value::try_from(&config.change_reader).ok(),
+            ),
+            ("lifecycle", toml::Value::try_from(&config.lifecycle).ok()),
+            ("routes", toml::Value::try_from(&config.routes).ok()),
+        ]
+    };
+    for ((key, old), (_, new)) in sections(old).into_iter().zip(sections(new)) {
+        if old != new {
+            changes.push(format!("[{key}] changed"));
+        }
+    }
+    changes
+}
+
 fn normalize_fixed_runtime_tuning(config: &mut DaemonConfig) {
     let defaults = DaemonConfig::default();
     config.daemon.realtime_debounce_ms = defaults.daemon.realtime_debounce_ms;
@@ -57,7 +221,7 @@ pub fn resolve_watch_paths(config: &DaemonConfig) -> Vec<PathBuf> {
     let mut paths = Vec::new();
     let mut seen = HashSet::new();
     for raw in raw_paths {
-        let p = PathBuf::from(shellexpand(&raw, &home));
+        let p = expand_home(&raw, &home);
         if p.exists() && seen.insert(p.clone()) {
             paths.push(p);
         }
@@ -66,13 +230,67 @@ pub fn resolve_watch_paths(config: &DaemonConfig) -> Vec<PathBuf> {
     paths
 }
 
-/// Simple ~ expansion
-fn shellexpand(path: &str, home: &Path) -> String {
-    if let Some(rest) = path.strip_prefix("~/") {
-        format!("{}/{}", home.display(), rest)
-    } else {
-        path.to_string()
+/// Resolve the team for a session from `[[routes]]`, checking each candidate path
+/// (source file, working directory) in order. The first matching route wins;
+/// otherwise the active server's default `team` applies.
+pub fn resolve_route_team(config: &DaemonConfig, candidates: &[&Path]) -> Option<String> {
+    let home = home_dir().unwrap_or_else(|_| PathBuf::from("."));
+    config
+        .routes
+        .iter()
+        .find(|route| {
+            let pattern = portable_path_text(&expand_home(route.path.trim(), &home));
+            candidates
+                .iter()
+                .any(|path| route_pattern_matches(&pattern, &portable_path_text(path)))
+        })
+        .map(|route| route.team.trim().to_string())
+        .or_else(|| Some(config.server.team.trim().to_string()))
+        .filter(|team| !team.is_empty())
+}
+
+/// Whether sessions at `candidates` (source file, working directory) publish
+/// only aggregate stats, per `privacy.metrics_only` or `privacy.metrics_only_paths`.
+pub fn is_metrics_only(config: &DaemonConfig, candidates: &[&Path]) -> bool {
+    if config.privacy.metrics_only {
+        return true;
     }
+    let home = home_dir().unwrap_or_else(|_| PathBuf::from("."));
+    config.privacy.metrics_only_paths.iter().any(|pattern| {
+        let pattern = portable_path_text(&expand_home(pattern.trim(), &home));
+        candidates
+            .iter()
+            .any(|path| route_pattern_matches(&pattern, &portable_path_text(path)))
+    })
+}
+
+fn route_pattern_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/');
+    if pattern.is_empty() {
+        return false;
+    }
+    if !pattern.contains(['*', '?']) {
+        return path == pattern
+            || path
+                .strip_prefix(pattern)
+                .is_some_and(|rest| rest.starts_with('/'));
+    }
+
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            other => regex.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    regex.push('$');
+    regex::Regex::new(&regex).is_ok_and(|re| re.is_match(path))
 }
 
 // ── Per-repo project configuration ─────────────────────────────────────
@@ -99,6 +317,9 @@ pub struct ProjectPrivacy {
     pub strip_env_vars: Option<bool>,
     pub exclude_patterns: Option<Vec<String>>,
     pub exclude_tools: Option<Vec<String>>,
+    /// Publish only aggregate stats for sessions in this repository.
+    #[serde(default)]
+    pub metrics_only: Option<bool>,
 }
 
 /// Project-level identity overrides.
@@ -171,6 +392,9 @@ pub fn merge_project_config(global: &DaemonConfig, project: &ProjectConfig) -> D
                 }
             }
         }
+        if let Some(metrics_only) = privacy.metrics_only {
+            merged.privacy.metrics_only = metrics_only;
+        }
     }
 
     if let Some(ref identity) = project.identity {
@@ -193,6 +417,7 @@ pub fn merge_project_configs(shared: &ProjectConfig, local: &ProjectConfig) -> P
             strip_env_vars: None,
             exclude_patterns: None,
             exclude_tools: None,
+            metrics_only: None,
         });
         if local_privacy.strip_paths.is_some() {
             base.strip_paths = local_privacy.strip_paths;
@@ -200,6 +425,9 @@ pub fn merge_project_configs(shared: &ProjectConfig, local: &ProjectConfig) -> P
         if local_privacy.strip_env_vars.is_some() {
             base.strip_env_vars = local_privacy.strip_env_vars;
         }
+        if local_privacy.metrics_only.is_some() {
+            base.metrics_only = local_privacy.metrics_only;
+        }
         if let Some(ref patterns) = local_privacy.exclude_patterns {
             let existing = base.exclude_patterns.get_or_insert_with(Vec::new);
             for p in patterns {
@@ -263,6 +491,7 @@ pub fn generate_default_project_config() -> String {
 # strip_env_vars = true
 # exclude_patterns = ["*.env", "*secret*"]
 # exclude_tools = []
+# metrics_only = false       # Publish only aggregate stats, never transcripts
 
 # [identity]
 # nickname = ""          # Optional per-repo display handle override
@@ -278,6 +507,31 @@ mod tests {
     use super::*;
     use opensession_runtime_config::PrivacySettings;
 
+    #[test]
+    fn describe_config_changes_lists_changed_settings() {
+        let old = DaemonConfig::default();
+        assert!(describe_config_changes(&old, &old).is_empty());
+
+        let mut new = old.clone();
+        new.daemon.publish_on = PublishMode::Realtime;
+        new.watchers.custom_paths = vec!["~/sessions".to_string()];
+        new.server.api_key = "osk_secret".to_string();
+        new.lifecycle.session_ttl_days += 1;
+        let changes = describe_config_changes(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                format!(
+                    "watchers.custom_paths: {:?} -> [\"~/sessions\"]",
+                    old.watchers.custom_paths
+                ),
+                "daemon.publish_on: Manual -> Realtime".to_string(),
+                "server.api_key: changed".to_string(),
+                "[lifecycle] changed".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn test_default_config_serializes() {
         let config = DaemonConfig::default();
@@ -397,6 +651,7 @@ nickname = "repo-user"
                 strip_env_vars: None,
                 exclude_patterns: None,
                 exclude_tools: None,
+                metrics_only: None,
             }),
             ..Default::default()
         };
@@ -421,6 +676,110 @@ nickname = "repo-user"
         assert_eq!(merged.identity.nickname, "project-nick");
     }
 
+    #[test]
+    fn test_route_pattern_matches_globs_and_directory_prefixes() {
+        assert!(route_pattern_matches(
+            "/home/u/work/**",
+            "/home/u/work/acme/app"
+        ));
+        assert!(route_pattern_matches("/home/u/work", "/home/u/work/acme"));
+        assert!(route_pattern_matches("/home/u/work/", "/home/u/work"));
+        assert!(!route_pattern_matches("/home/u/work", "/home/u/workshop"));
+        assert!(route_pattern_matches("/srv/*/repo", "/srv/team/repo"));
+        assert!(!route_pattern_matches("/srv/*/repo", "/srv/a/b/repo"));
+        assert!(!route_pattern_matches("", "/anything"));
+    }
+
+    #[test]
+    fn test_resolve_route_team_uses_first_matching_route() {
+        let config: DaemonConfig = toml::from_str(
+            r#"
+[[routes]]
+path = "/work/acme/**"
+team = "acme"
+
+[[routes]]
+path = "/work"
+team = "work"
+"#,
+        )
+        .expect("parse routes");
+
+        let source = Path::new("/tmp/sessions/a.jsonl");
+        assert_eq!(
+            resolve_route_team(&config, &[source, Path::new("/work/acme/app")]).as_deref(),
+            Some("acme")
+        );
+        assert_eq!(
+            resolve_route_team(&config, &[source, Path::new("/work/other")]).as_deref(),
+            Some("work")
+        );
+        assert_eq!(resolve_route_team(&config, &[source]), None);
+    }
+
+    #[test]
+    fn test_metrics_only_matches_paths_or_project_flag() {
+        let mut config: DaemonConfig = toml::from_str(
+            r#"
+[privacy]
+metrics_only_paths = ["/work/vault"]
+"#,
+        )
+        .expect("parse privacy");
+
+        let source = Path::new("/tmp/sessions/a.jsonl");
+        assert!(is_metrics_only(
+            &config,
+            &[source, Path::new("/work/vault/api")]
+        ));
+        assert!(!is_metrics_only(&config, &[source, Path::new("/work/app")]));
+
+        config.privacy.metrics_only_paths.clear();
+        let project = ProjectConfig {
+            privacy: Some(ProjectPrivacy {
+                strip_paths: None,
+                strip_env_vars: None,
+                exclude_patterns: None,
+                exclude_tools: None,
+                metrics_only: Some(true),
+            }),
+            ..Default::default()
+        };
+        let merged = merge_project_config(&config, &project);
+        assert!(is_metrics_only(&merged, &[source]));
+    }
+
+    #[test]
+    fn test_server_profile_selects_server_and_default_team() {
+        let mut config: DaemonConfig = toml::from_str(
+            r#"
+[server]
+url = "https://opensession.io"
+
+[server.profiles.work]
+url = "https://sessions.corp.example"
+api_key = "osk_work"
+team = "platform"
+"#,
+        )
+        .expect("parse profiles");
+        let source = Path::new("/tmp/sessions/a.jsonl");
+
+        select_server_profile(&mut config, " ").expect("blank keeps [server]");
+        assert_eq!(config.server.url, "https://opensession.io");
+        assert_eq!(resolve_route_team(&config, &[source]), None);
+
+        let error = select_server_profile(&mut config.clone(), "oss").expect_err("unknown");
+        assert!(error.to_string().contains("known: work"), "{error}");
+
+        select_server_profile(&mut config, "work").expect("select work");
+        assert_eq!(config.server.url, "https://sessions.corp.example");
+        assert_eq!(
+            resolve_route_team(&config, &[source]).as_deref(),
+            Some("platform")
+        );
+    }
+
     #[test]
     fn test_merge_project_config_patterns_union() {
         let global = DaemonConfig {
@@ -438,6 +797,7 @@ nickname = "repo-user"
                 strip_env_vars: None,
                 exclude_patterns: Some(vec!["*secret*".to_string(), "*.log".to_string()]),
                 exclude_tools: Some(vec!["codex".to_string(), "cursor".to_string()]),
+                metrics_only: None,
             }),
             ..Default::default()
         };
@@ -459,6 +819,7 @@ nickname = "repo-user"
                 strip_env_vars: None,
                 exclude_patterns: Some(vec!["*.env".to_string()]),
                 exclude_tools: None,
+                metrics_only: None,
             }),
             identity: Some(ProjectIdentity {
                 nickname: Some("shared-nick".to_string()),
@@ -473,6 +834,7 @@ nickname = "repo-user"
                 strip_env_vars: None,
                 exclude_patterns: Some(vec!["*.log".to_string()]), // union
                 exclude_tools: None,
+                metrics_only: None,
             }),
             identity: Some(ProjectIdentity {
                 nickname: Some("me".to_string()), // add
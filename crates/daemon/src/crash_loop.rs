@@ -0,0 +1,123 @@
+//! Crash-loop detection.
+//!
+//! Each start appends a timestamp to `daemon.starts`; a clean shutdown clears
+//! it. When a supervisor keeps restarting a crashing daemon, the history grows
+//! and startup is delayed with exponential backoff instead of hammering the
+//! server and the local DB.
+
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+use crate::config;
+
+/// Starts older than this no longer count towards a crash loop.
+const CRASH_WINDOW_SECS: u64 = 600;
+/// Unclean starts tolerated inside the window before backing off.
+const CRASH_THRESHOLD: usize = 3;
+const BASE_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Delay before starting, given how many unclean starts fall inside the window.
+fn backoff_for(recent_starts: usize) -> Duration {
+    if recent_starts < CRASH_THRESHOLD {
+        return Duration::ZERO;
+    }
+    let exponent = (recent_starts - CRASH_THRESHOLD).min(16) as u32;
+    BASE_BACKOFF
+        .saturating_mul(2u32.pow(exponent))
+        .min(MAX_BACKOFF)
+}
+
+/// Prune history older than the window, append `now`, and return the backoff.
+fn record_start(path: &Path, now: u64) -> (usize, Duration) {
+    let mut starts: Vec<u64> = std::fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .filter(|&started| now.saturating_sub(started) < CRASH_WINDOW_SECS)
+        .collect();
+    let recent = starts.len();
+    starts.push(now);
+
+    let body: String = starts
+        .iter()
+        .map(|started| format!("{started}\n"))
+        .collect();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(error) = std::fs::write(path, body) {
+        warn!(
+            "Failed to record daemon start in {}: {error}",
+            path.display()
+        );
+    }
+    (recent, backoff_for(recent))
+}
+
+/// Record this start and sleep if the daemon appears to be crash-looping.
+pub(crate) async fn backoff_on_start() {
+    let Ok(path) = config::start_history_path() else {
+        return;
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let (recent, backoff) = record_start(&path, now);
+    if !backoff.is_zero() {
+        warn!(
+            "Daemon restarted {recent} times in the last {} minutes without a clean shutdown; \
+             waiting {}s before starting",
+            CRASH_WINDOW_SECS / 60,
+            backoff.as_secs()
+        );
+        tokio::time::sleep(backoff).await;
+    }
+}
+
+/// Forget the start history after a clean shutdown.
+pub(crate) fn clear() {
+    if let Ok(path) = config::start_history_path() {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_exponentially_after_threshold_and_caps() {
+        assert_eq!(backoff_for(0), Duration::ZERO);
+        assert_eq!(backoff_for(CRASH_THRESHOLD - 1), Duration::ZERO);
+        assert_eq!(backoff_for(CRASH_THRESHOLD), BASE_BACKOFF);
+        assert_eq!(backoff_for(CRASH_THRESHOLD + 2), BASE_BACKOFF * 4);
+        assert_eq!(backoff_for(CRASH_THRESHOLD + 40), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn record_start_prunes_expired_history() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("daemon.starts");
+        let now = 10_000;
+        std::fs::write(
+            &path,
+            format!("{}\n{}\n{}\n", now - 5_000, now - 30, now - 10),
+        )
+        .expect("seed history");
+
+        let (recent, backoff) = record_start(&path, now);
+        assert_eq!(recent, 2);
+        assert_eq!(backoff, Duration::ZERO);
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read history"),
+            format!("{}\n{}\n{now}\n", now - 30, now - 10)
+        );
+
+        let (recent, backoff) = record_start(&path, now + 1);
+        assert_eq!(recent, CRASH_THRESHOLD);
+        assert_eq!(backoff, BASE_BACKOFF);
+    }
+}
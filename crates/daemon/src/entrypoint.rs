@@ -1,14 +1,21 @@
 use clap::Parser;
-use tracing::error;
+use tracing::{error, info, warn};
 
-use crate::cli::{Cli, DaemonCommand};
+use crate::cli::{Cli, DaemonCommand, PolicyAction};
 
 pub(crate) async fn run_process() {
     let cli = Cli::parse();
     initialize_tracing();
+    migrate_legacy_dirs();
 
     let result = match cli.command.unwrap_or(DaemonCommand::Run) {
         DaemonCommand::Run => crate::runtime::run().await,
+        DaemonCommand::Policy {
+            action: PolicyAction::Test { session, rule },
+        } => crate::policy_cmd::run_test(&session, rule.as_deref()),
+        DaemonCommand::Install { dry_run } => crate::service::install(dry_run),
+        DaemonCommand::Uninstall => crate::service::uninstall(),
+        DaemonCommand::Status => crate::status::print_status(),
     };
 
     if let Err(error) = result {
@@ -17,6 +24,21 @@ pub(crate) async fn run_process() {
     }
 }
 
+fn migrate_legacy_dirs() {
+    match opensession_paths::migrate_legacy_dirs() {
+        Ok(moved) => {
+            for migration in moved {
+                info!(
+                    "Moved {} to {}",
+                    migration.from.display(),
+                    migration.to.display()
+                );
+            }
+        }
+        Err(error) => warn!("Legacy data directory migration failed: {error}"),
+    }
+}
+
 fn initialize_tracing() {
     tracing_subscriber::fmt()
         .with_env_filter(
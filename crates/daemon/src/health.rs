@@ -30,6 +30,14 @@ pub async fn run_health_check(
     if !api_key.is_empty() {
         api.set_auth(api_key);
     }
+    match opensession_paths::device_id_path() {
+        Ok(path) => {
+            if let Err(e) = api.identify_device(&path) {
+                warn!("Failed to load device id: {e}");
+            }
+        }
+        Err(e) => warn!("Failed to resolve device id path: {e}"),
+    }
 
     loop {
         tokio::select! {
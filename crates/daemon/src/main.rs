@@ -1,11 +1,20 @@
 mod cli;
 mod config;
+mod crash_loop;
 mod entrypoint;
 mod health;
 pub mod hooks;
+mod policy_cmd;
+mod pre_upload;
+mod prefetch;
+mod publish_policy;
+mod reload;
 mod repo_registry;
 mod runtime;
 mod scheduler;
+mod service;
+mod status;
+mod telemetry;
 mod watcher;
 
 #[tokio::main]
@@ -0,0 +1,61 @@
+use anyhow::{Context, Result, bail};
+use opensession_parsers::ParserRegistry;
+use std::path::Path;
+
+use crate::publish_policy::{PolicySubject, PublishPolicy};
+
+/// `opensession-daemon policy test` — show how the publish policy treats a session.
+pub(crate) fn run_test(path: &Path, rule_override: Option<&str>) -> Result<()> {
+    let session = ParserRegistry::with_plugins()
+        .parse_path(path)?
+        .with_context(|| format!("no parser recognized {}", path.display()))?;
+
+    let rule = match rule_override {
+        Some(rule) => rule.to_string(),
+        None => {
+            let config = crate::config::load_config()?;
+            crate::scheduler::resolve_effective_config(&session, &config)
+                .daemon
+                .publish_policy
+        }
+    };
+    let policy = match PublishPolicy::parse(&rule) {
+        Ok(policy) => policy,
+        Err(error) => bail!("invalid publish policy: {error}"),
+    };
+
+    let subject = PolicySubject::for_session(&session);
+    let rule_label = if rule.trim().is_empty() {
+        "(empty: publish everything)"
+    } else {
+        rule.trim()
+    };
+    println!("session:  {}", session.session_id);
+    println!("rule:     {rule_label}");
+    println!(
+        "tool={} provider={} model={}",
+        subject.tool, subject.provider, subject.model
+    );
+    println!(
+        "repo={} branch={}",
+        subject.repo.as_deref().unwrap_or("-"),
+        subject.branch.as_deref().unwrap_or("-")
+    );
+    println!(
+        "has_errors={} duration={}s events={} messages={} user_messages={} tasks={} tokens={} files_changed={}",
+        subject.has_errors,
+        subject.duration_secs,
+        subject.events,
+        subject.messages,
+        subject.user_messages,
+        subject.tasks,
+        subject.tokens,
+        subject.files_changed
+    );
+    if policy.allows(&subject) {
+        println!("decision: publish");
+    } else {
+        println!("decision: keep local");
+    }
+    Ok(())
+}
@@ -0,0 +1,193 @@
+//! Pre-upload hook scripts run before the daemon publishes a session.
+//!
+//! Each hook is a shell command that receives the session as JSON on stdin:
+//! - exit 0 with empty stdout keeps the session unchanged,
+//! - exit 0 with a session JSON on stdout replaces it (e.g. scrubbed content),
+//! - a non-zero exit vetoes the upload; stderr is reported as the reason.
+//!
+//! Hooks run in configuration order and each sees the previous hook's output.
+
+use anyhow::{Context, Result};
+use opensession_core::Session;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+const HOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum PreUploadOutcome {
+    Publish(Box<Session>),
+    Vetoed { hook: String, reason: String },
+}
+
+pub async fn run_pre_upload_hooks(session: &Session, hooks: &[String]) -> Result<PreUploadOutcome> {
+    let mut current = session.clone();
+    for hook in hooks
+        .iter()
+        .map(|hook| hook.trim())
+        .filter(|h| !h.is_empty())
+    {
+        let input = serde_json::to_vec(&current).context("serialize session for hook")?;
+        let output = run_hook(hook, input, HOOK_TIMEOUT).await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            let reason = if stderr.is_empty() {
+                format!("exited with {}", output.status)
+            } else {
+                stderr
+            };
+            return Ok(PreUploadOutcome::Vetoed {
+                hook: hook.to_string(),
+                reason,
+            });
+        }
+
+        if !output.stdout.iter().all(u8::is_ascii_whitespace) {
+            let mut replaced: Session = serde_json::from_slice(&output.stdout)
+                .with_context(|| format!("pre-upload hook printed invalid session JSON: {hook}"))?;
+            if replaced.session_id != current.session_id {
+                anyhow::bail!("pre-upload hook changed the session id: {hook}");
+            }
+            replaced.recompute_stats();
+            current = replaced;
+        }
+    }
+    Ok(PreUploadOutcome::Publish(Box::new(current)))
+}
+
+async fn run_hook(hook: &str, input: Vec<u8>, timeout: Duration) -> Result<std::process::Output> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(hook)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("spawn pre-upload hook: {hook}"))?;
+
+    // Feed stdin while the output is drained, so a hook that writes before it
+    // has read everything cannot fill its stdout pipe and stall.
+    let stdin = child.stdin.take();
+    let writer = tokio::spawn(async move {
+        let Some(mut stdin) = stdin else {
+            return Ok(());
+        };
+        match stdin.write_all(&input).await {
+            // A hook may exit without reading stdin; a broken pipe is not an error.
+            Err(error) if error.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+            other => other,
+        }
+    });
+
+    // Dropping the `wait_with_output` future on timeout kills the child.
+    let output = match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(output) => output.with_context(|| format!("wait for pre-upload hook: {hook}"))?,
+        Err(_) => {
+            writer.abort();
+            anyhow::bail!("pre-upload hook timed out after {timeout:?}: {hook}");
+        }
+    };
+    writer
+        .await
+        .context("join pre-upload hook stdin writer")?
+        .with_context(|| format!("write session to pre-upload hook: {hook}"))?;
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opensession_core::Agent;
+
+    fn session() -> Session {
+        let agent = Agent {
+            provider: "openai".to_string(),
+            model: "gpt-5".to_string(),
+            tool: "codex".to_string(),
+            tool_version: None,
+        };
+        let mut session = Session::new("hook-session".to_string(), agent);
+        session.context.title = Some("original".to_string());
+        session
+    }
+
+    #[tokio::test]
+    async fn no_hooks_publishes_unchanged() {
+        let outcome = run_pre_upload_hooks(&session(), &[])
+            .await
+            .expect("run hooks");
+        match outcome {
+            PreUploadOutcome::Publish(s) => {
+                assert_eq!(s.context.title.as_deref(), Some("original"))
+            }
+            other => panic!("unexpected outcome: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn silent_success_keeps_session_and_nonzero_exit_vetoes() {
+        let hooks = vec!["cat >/dev/null".to_string()];
+        assert!(matches!(
+            run_pre_upload_hooks(&session(), &hooks)
+                .await
+                .expect("run hooks"),
+            PreUploadOutcome::Publish(_)
+        ));
+
+        let hooks = vec!["echo 'contains customer data' >&2; exit 3".to_string()];
+        match run_pre_upload_hooks(&session(), &hooks)
+            .await
+            .expect("run hooks")
+        {
+            PreUploadOutcome::Vetoed { reason, .. } => {
+                assert_eq!(reason, "contains customer data");
+            }
+            other => panic!("unexpected outcome: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn hook_output_replaces_the_session() {
+        let hooks = vec!["sed 's/original/scrubbed/'".to_string()];
+        match run_pre_upload_hooks(&session(), &hooks)
+            .await
+            .expect("run hooks")
+        {
+            PreUploadOutcome::Publish(s) => {
+                assert_eq!(s.context.title.as_deref(), Some("scrubbed"))
+            }
+            other => panic!("unexpected outcome: {other:?}"),
+        }
+
+        let hooks = vec!["echo not-json".to_string()];
+        assert!(run_pre_upload_hooks(&session(), &hooks).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn large_sessions_stream_through_without_stalling() {
+        let mut large = session();
+        large.context.description = Some("x".repeat(1 << 20));
+        let hooks = vec!["cat".to_string()];
+        match run_pre_upload_hooks(&large, &hooks)
+            .await
+            .expect("run hooks")
+        {
+            PreUploadOutcome::Publish(s) => {
+                assert_eq!(s.context.description.map(|d| d.len()), Some(1 << 20))
+            }
+            other => panic!("unexpected outcome: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn hooks_that_hang_time_out() {
+        let err = run_hook("sleep 5", Vec::new(), Duration::from_millis(100))
+            .await
+            .expect_err("hook should time out");
+        assert!(err.to_string().contains("timed out"), "{err}");
+    }
+}
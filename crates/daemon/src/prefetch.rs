@@ -0,0 +1,236 @@
+use anyhow::{Result, bail};
+use chrono::Utc;
+use opensession_api::{SessionListQuery, SortOrder};
+use opensession_api_client::{ApiClient, RawSessionBody};
+use opensession_local_db::AsyncLocalDb;
+use opensession_runtime_config::{BodyCacheStatus, PrefetchSettings};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::config::DaemonConfig;
+
+/// Sessions requested per list page.
+const PAGE_SIZE: u32 = 100;
+
+/// What one prefetch pass did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct PrefetchRun {
+    hits: u64,
+    misses: u64,
+    evicted: u32,
+}
+
+/// Keep bodies of the server's recent sessions in the local body cache while
+/// `[prefetch]` is enabled, publishing cache stats on `status_tx` for the
+/// status file. Sessions indexed from local files are skipped.
+pub async fn run_body_prefetch(
+    mut config_rx: watch::Receiver<DaemonConfig>,
+    db: AsyncLocalDb,
+    status_tx: watch::Sender<Option<BodyCacheStatus>>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut config = config_rx.borrow_and_update().clone();
+    let mut next_run = config.prefetch.enabled.then(Instant::now);
+    let mut totals = BodyCacheStatus::default();
+
+    loop {
+        let wake_at = next_run.unwrap_or_else(|| Instant::now() + Duration::from_secs(3_600));
+        tokio::select! {
+            _ = tokio::time::sleep_until(wake_at) => {
+                if next_run.is_none() {
+                    continue;
+                }
+                let settings = config.prefetch.clone();
+                match prefetch_once(&config, &settings, &db).await {
+                    Ok(run) => {
+                        totals.hits += run.hits;
+                        totals.misses += run.misses;
+                        totals.last_prefetch_at = Some(Utc::now().timestamp());
+                        if run.misses > 0 || run.evicted > 0 {
+                            info!(
+                                "Prefetched {} session bodies ({} already cached, {} evicted)",
+                                run.misses, run.hits, run.evicted
+                            );
+                        }
+                    }
+                    Err(error) => warn!("Body prefetch failed: {error:#}"),
+                }
+                match db.call(|db| db.body_cache_stats()).await {
+                    Ok(stats) => {
+                        totals.entries = stats.entries;
+                        totals.bytes = stats.bytes;
+                        totals.max_bytes = settings.max_cache_bytes();
+                        status_tx.send_replace(Some(totals.clone()));
+                    }
+                    Err(error) => debug!("Body cache stats unavailable: {error}"),
+                }
+                next_run = Some(Instant::now() + interval(&settings));
+            }
+            Ok(()) = config_rx.changed() => {
+                let previous = std::mem::replace(&mut config, config_rx.borrow_and_update().clone());
+                if config.prefetch != previous.prefetch {
+                    next_run = config.prefetch.enabled.then(Instant::now);
+                    if !config.prefetch.enabled {
+                        status_tx.send_replace(None);
+                    }
+                }
+            }
+            _ = shutdown.changed() => {
+                if *shutdown.borrow() {
+                    debug!("Body prefetch shutting down");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Event count of a cached JSONL body, used as the `since_event` of a delta fetch.
+fn cached_event_count(body: &[u8]) -> Option<usize> {
+    let body = std::str::from_utf8(body).ok()?;
+    opensession_core::jsonl::count_events(body).ok()
+}
+
+fn interval(settings: &PrefetchSettings) -> Duration {
+    Duration::from_secs(settings.interval_secs.max(60))
+}
+
+/// Fetch bodies of sessions from the last `recent_days`, newest first, until
+/// this pass has downloaded `max_cache_mb`, then evict down to the bound.
+async fn prefetch_once(
+    config: &DaemonConfig,
+    settings: &PrefetchSettings,
+    db: &AsyncLocalDb,
+) -> Result<PrefetchRun> {
+    if config.server.url.trim().is_empty() || config.server.api_key.trim().is_empty() {
+        bail!("server.url and server.api_key are required");
+    }
+    let mut api = ApiClient::new(&config.server.url, Duration::from_secs(30))?;
+    api.set_auth(config.server.api_key.clone());
+
+    let max_bytes = settings.max_cache_bytes();
+    let since = (Utc::now() - chrono::Duration::days(i64::from(settings.recent_days))).to_rfc3339();
+    let mut run = PrefetchRun::default();
+    let mut downloaded = 0u64;
+    let mut cursor = None;
+    'pages: loop {
+        let page = api
+            .list_sessions(&SessionListQuery {
+                cursor: cursor.take(),
+                limit: Some(PAGE_SIZE),
+                sort: Some(SortOrder::Recent),
+                since: Some(since.clone()),
+                ..SessionListQuery::default()
+            })
+            .await?;
+        for summary in page.sessions {
+            let id = summary.id.clone();
+            let cached = db
+                .call(move |db| {
+                    if db.get_session_source_path(&id)?.is_some() {
+                        return Ok(None);
+                    }
+                    Ok(Some(db.get_cached_body_row(&id)?))
+                })
+                .await?;
+            let Some(cached) = cached else {
+                continue;
+            };
+            // Ask for only the events past what is cached; the server answers
+            // 304 first when the ETag still matches.
+            let etag = cached.as_ref().and_then(|row| row.etag.clone());
+            let since_event = cached
+                .as_ref()
+                .and_then(|row| cached_event_count(&row.body));
+            let id = summary.id;
+            match api
+                .fetch_session_body(&id, etag.as_deref(), since_event)
+                .await
+            {
+                Ok(RawSessionBody::NotModified) => {
+                    run.hits += 1;
+                    db.call(move |db| db.touch_cached_body(&id)).await?;
+                }
+                Ok(RawSessionBody::Fetched { body, etag }) => {
+                    run.misses += 1;
+                    downloaded += body.len() as u64;
+                    db.call(move |db| db.cache_body_with_etag(&id, &body, etag.as_deref()))
+                        .await?;
+                }
+                Ok(RawSessionBody::Delta {
+                    since_event,
+                    delta,
+                    etag,
+                }) => {
+                    run.misses += 1;
+                    downloaded += delta.len() as u64;
+                    let appended = {
+                        let id = id.clone();
+                        db.call(move |db| {
+                            db.append_cached_body_delta(&id, since_event, &delta, etag.as_deref())
+                        })
+                        .await?
+                    };
+                    if !appended {
+                        // The cache moved under us, or the server rewrote the
+                        // body; replace it with the full body.
+                        match api.fetch_session_body(&id, None, None).await {
+                            Ok(RawSessionBody::Fetched { body, etag }) => {
+                                downloaded += body.len() as u64;
+                                db.call(move |db| {
+                                    db.cache_body_with_etag(&id, &body, etag.as_deref())
+                                })
+                                .await?;
+                            }
+                            Ok(_) => {}
+                            Err(error) => debug!("Prefetch of {id} failed: {error}"),
+                        }
+                    }
+                }
+                Err(error) => debug!("Prefetch of {id} failed: {error}"),
+            }
+            if downloaded >= max_bytes {
+                break 'pages;
+            }
+        }
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    run.evicted = db.call(move |db| db.evict_body_cache(max_bytes)).await?;
+    Ok(run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opensession_core::{Agent, Content, Event, EventType, Session};
+
+    #[test]
+    fn cached_event_count_reads_jsonl_bodies_only() {
+        let agent = Agent {
+            provider: "openai".to_string(),
+            model: "gpt-5".to_string(),
+            tool: "codex".to_string(),
+            tool_version: None,
+        };
+        let mut session = Session::new("prefetch".to_string(), agent);
+        session.events.push(Event {
+            event_id: "e1".to_string(),
+            timestamp: Utc::now(),
+            event_type: EventType::UserMessage,
+            task_id: None,
+            content: Content::text("hello"),
+            duration_ms: None,
+            attributes: Default::default(),
+        });
+        let body = opensession_core::jsonl::to_jsonl_string(&session).expect("jsonl");
+
+        assert_eq!(cached_event_count(body.as_bytes()), Some(1));
+        assert_eq!(cached_event_count(b"not jsonl"), None);
+        assert_eq!(cached_event_count(&[0xff, 0xfe]), None);
+    }
+}
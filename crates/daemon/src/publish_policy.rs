@@ -0,0 +1,639 @@
+//! Publish policy rules evaluated before auto-publish.
+//!
+//! A policy is a boolean expression over session facts, optionally prefixed
+//! with `publish if`:
+//!
+//! ```text
+//! publish if repo in [opensession, "infra-tools"] and !has_errors and duration > 60s
+//! ```
+//!
+//! Operators: `and`, `or`, `!`/`not`, parentheses, `== != > >= < <=`, `in [...]`.
+//! Durations accept `s`, `m`, and `h` suffixes.
+
+use opensession_core::Session;
+use opensession_core::extract::extract_upload_metadata;
+use opensession_core::session::working_directory;
+use opensession_git_native::extract_git_context;
+use std::fmt;
+
+/// Facts a policy can reference, resolved once per session.
+#[derive(Debug, Clone, Default)]
+pub struct PolicySubject {
+    pub tool: String,
+    pub provider: String,
+    pub model: String,
+    pub repo: Option<String>,
+    pub branch: Option<String>,
+    pub has_errors: bool,
+    pub duration_secs: u64,
+    pub events: u64,
+    pub messages: u64,
+    pub user_messages: u64,
+    pub tasks: u64,
+    pub tokens: u64,
+    pub files_changed: u64,
+}
+
+impl PolicySubject {
+    /// Resolve facts for a session, reading repo/branch from its working directory.
+    pub fn for_session(session: &Session) -> Self {
+        let git = working_directory(session)
+            .map(extract_git_context)
+            .unwrap_or_default();
+        Self::from_session(session, git.repo_name, git.branch)
+    }
+
+    pub fn from_session(session: &Session, repo: Option<String>, branch: Option<String>) -> Self {
+        let stats = &session.stats;
+        Self {
+            tool: session.agent.tool.clone(),
+            provider: session.agent.provider.clone(),
+            model: session.agent.model.clone(),
+            repo,
+            branch,
+            has_errors: extract_upload_metadata(session).has_errors,
+            duration_secs: stats.duration_seconds,
+            events: stats.event_count,
+            messages: stats.message_count,
+            user_messages: stats.user_message_count,
+            tasks: stats.task_count,
+            tokens: stats
+                .total_input_tokens
+                .saturating_add(stats.total_output_tokens),
+            files_changed: stats.files_changed,
+        }
+    }
+
+    fn text(&self, field: TextField) -> Option<&str> {
+        match field {
+            TextField::Tool => Some(&self.tool),
+            TextField::Provider => Some(&self.provider),
+            TextField::Model => Some(&self.model),
+            TextField::Repo => self.repo.as_deref(),
+            TextField::Branch => self.branch.as_deref(),
+        }
+    }
+
+    fn number(&self, field: NumberField) -> u64 {
+        match field {
+            NumberField::Duration => self.duration_secs,
+            NumberField::Events => self.events,
+            NumberField::Messages => self.messages,
+            NumberField::UserMessages => self.user_messages,
+            NumberField::Tasks => self.tasks,
+            NumberField::Tokens => self.tokens,
+            NumberField::FilesChanged => self.files_changed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyParseError {
+    pub offset: usize,
+    pub message: String,
+}
+
+impl fmt::Display for PolicyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at offset {})", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for PolicyParseError {}
+
+/// A parsed publish policy. An empty rule allows every session.
+#[derive(Debug, Clone)]
+pub struct PublishPolicy {
+    expr: Option<Expr>,
+}
+
+impl PublishPolicy {
+    pub fn parse(source: &str) -> Result<Self, PolicyParseError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        if parser.peek_word("publish") {
+            parser.pos += 1;
+            parser.expect_word("if")?;
+        } else if parser.tokens.is_empty() {
+            return Ok(Self { expr: None });
+        }
+        let expr = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(token.error("unexpected trailing input"));
+        }
+        Ok(Self { expr: Some(expr) })
+    }
+
+    pub fn allows(&self, subject: &PolicySubject) -> bool {
+        self.expr.as_ref().is_none_or(|expr| expr.eval(subject))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextField {
+    Tool,
+    Provider,
+    Model,
+    Repo,
+    Branch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumberField {
+    Duration,
+    Events,
+    Messages,
+    UserMessages,
+    Tasks,
+    Tokens,
+    FilesChanged,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Text(TextField),
+    Number(NumberField),
+    HasErrors,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "tool" => Self::Text(TextField::Tool),
+            "provider" => Self::Text(TextField::Provider),
+            "model" => Self::Text(TextField::Model),
+            "repo" => Self::Text(TextField::Repo),
+            "branch" => Self::Text(TextField::Branch),
+            "duration" => Self::Number(NumberField::Duration),
+            "events" => Self::Number(NumberField::Events),
+            "messages" => Self::Number(NumberField::Messages),
+            "user_messages" => Self::Number(NumberField::UserMessages),
+            "tasks" => Self::Number(NumberField::Tasks),
+            "tokens" => Self::Number(NumberField::Tokens),
+            "files_changed" => Self::Number(NumberField::FilesChanged),
+            "has_errors" => Self::HasErrors,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    HasErrors,
+    BoolCmp(CmpOp, bool),
+    TextCmp(TextField, CmpOp, String),
+    TextIn(TextField, Vec<String>),
+    NumberCmp(NumberField, CmpOp, u64),
+    NumberIn(NumberField, Vec<u64>),
+}
+
+impl Expr {
+    fn eval(&self, subject: &PolicySubject) -> bool {
+        match self {
+            Self::And(lhs, rhs) => lhs.eval(subject) && rhs.eval(subject),
+            Self::Or(lhs, rhs) => lhs.eval(subject) || rhs.eval(subject),
+            Self::Not(inner) => !inner.eval(subject),
+            Self::HasErrors => subject.has_errors,
+            Self::BoolCmp(op, value) => match op {
+                CmpOp::Eq => subject.has_errors == *value,
+                _ => subject.has_errors != *value,
+            },
+            Self::TextCmp(field, op, value) => {
+                let actual = subject.text(*field);
+                let equal = actual.is_some_and(|actual| actual.eq_ignore_ascii_case(value));
+                match op {
+                    CmpOp::Eq => equal,
+                    _ => !equal,
+                }
+            }
+            Self::TextIn(field, values) => subject
+                .text(*field)
+                .is_some_and(|actual| values.iter().any(|v| actual.eq_ignore_ascii_case(v))),
+            Self::NumberCmp(field, op, value) => {
+                let actual = subject.number(*field);
+                match op {
+                    CmpOp::Eq => actual == *value,
+                    CmpOp::Ne => actual != *value,
+                    CmpOp::Gt => actual > *value,
+                    CmpOp::Ge => actual >= *value,
+                    CmpOp::Lt => actual < *value,
+                    CmpOp::Le => actual <= *value,
+                }
+            }
+            Self::NumberIn(field, values) => values.contains(&subject.number(*field)),
+        }
+    }
+}
+
+// ── Tokenizer ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Word(String),
+    Str(String),
+    Op(&'static str),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    offset: usize,
+}
+
+impl Token {
+    fn error(&self, message: impl Into<String>) -> PolicyParseError {
+        PolicyParseError {
+            offset: self.offset,
+            message: message.into(),
+        }
+    }
+}
+
+const OPERATORS: &[&str] = &[
+    "==", "!=", ">=", "<=", "&&", "||", ">", "<", "!", "(", ")", "[", "]", ",",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>, PolicyParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(offset, ch)) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if ch == '"' || ch == '\'' {
+            chars.next();
+            let mut value = String::new();
+            let mut closed = false;
+            for (_, c) in chars.by_ref() {
+                if c == ch {
+                    closed = true;
+                    break;
+                }
+                value.push(c);
+            }
+            if !closed {
+                return Err(PolicyParseError {
+                    offset,
+                    message: "unterminated string".to_string(),
+                });
+            }
+            tokens.push(Token {
+                kind: TokenKind::Str(value),
+                offset,
+            });
+            continue;
+        }
+        if let Some(op) = OPERATORS
+            .iter()
+            .find(|op| source[offset..].starts_with(**op))
+        {
+            for _ in 0..op.len() {
+                chars.next();
+            }
+            let op = match *op {
+                "&&" => "and",
+                "||" => "or",
+                other => other,
+            };
+            let kind = if op == "and" || op == "or" {
+                TokenKind::Word(op.to_string())
+            } else {
+                TokenKind::Op(op)
+            };
+            tokens.push(Token { kind, offset });
+            continue;
+        }
+        if ch.is_alphanumeric() || "_-./".contains(ch) {
+            let mut word = String::new();
+            while let Some(&(_, c)) = chars.peek() {
+                if c.is_alphanumeric() || "_-./".contains(c) {
+                    word.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token {
+                kind: TokenKind::Word(word),
+                offset,
+            });
+            continue;
+        }
+        return Err(PolicyParseError {
+            offset,
+            message: format!("unexpected character `{ch}`"),
+        });
+    }
+    Ok(tokens)
+}
+
+// ── Parser ────────────────────────────────────────────────────────────────
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn end_error(&self, message: &str) -> PolicyParseError {
+        let offset = self.tokens.last().map(|t| t.offset).unwrap_or_default();
+        PolicyParseError {
+            offset,
+            message: format!("{message}, found end of rule"),
+        }
+    }
+
+    fn next(&mut self, expected: &str) -> Result<Token, PolicyParseError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| self.end_error(&format!("expected {expected}")))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn peek_word(&self, word: &str) -> bool {
+        matches!(
+            self.tokens.get(self.pos).map(|t| &t.kind),
+            Some(TokenKind::Word(w)) if w.eq_ignore_ascii_case(word)
+        )
+    }
+
+    fn peek_op(&self, op: &str) -> bool {
+        matches!(
+            self.tokens.get(self.pos).map(|t| &t.kind),
+            Some(TokenKind::Op(o)) if *o == op
+        )
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<(), PolicyParseError> {
+        let token = self.next(&format!("`{word}`"))?;
+        match &token.kind {
+            TokenKind::Word(w) if w.eq_ignore_ascii_case(word) => Ok(()),
+            _ => Err(token.error(format!("expected `{word}`"))),
+        }
+    }
+
+    fn expect_op(&mut self, op: &str) -> Result<(), PolicyParseError> {
+        let token = self.next(&format!("`{op}`"))?;
+        match token.kind {
+            TokenKind::Op(o) if o == op => Ok(()),
+            _ => Err(token.error(format!("expected `{op}`"))),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, PolicyParseError> {
+        let mut lhs = self.parse_and()?;
+        while self.peek_word("or") {
+            self.pos += 1;
+            let rhs = self.parse_and()?;
+            lhs = Expr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, PolicyParseError> {
+        let mut lhs = self.parse_unary()?;
+        while self.peek_word("and") {
+            self.pos += 1;
+            let rhs = self.parse_unary()?;
+            lhs = Expr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, PolicyParseError> {
+        if self.peek_op("!") || self.peek_word("not") {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.peek_op("(") {
+            self.pos += 1;
+            let inner = self.parse_or()?;
+            self.expect_op(")")?;
+            return Ok(inner);
+        }
+        self.parse_condition()
+    }
+
+    fn parse_condition(&mut self) -> Result<Expr, PolicyParseError> {
+        let token = self.next("a field name")?;
+        let TokenKind::Word(name) = &token.kind else {
+            return Err(token.error("expected a field name"));
+        };
+        let field = Field::from_name(&name.to_ascii_lowercase())
+            .ok_or_else(|| token.error(format!("unknown field `{name}`")))?;
+
+        if self.peek_word("in") {
+            self.pos += 1;
+            return self.parse_in(field, &token);
+        }
+        let op = match self.tokens.get(self.pos).map(|t| &t.kind) {
+            Some(TokenKind::Op(op)) => match *op {
+                "==" => Some(CmpOp::Eq),
+                "!=" => Some(CmpOp::Ne),
+                ">" => Some(CmpOp::Gt),
+                ">=" => Some(CmpOp::Ge),
+                "<" => Some(CmpOp::Lt),
+                "<=" => Some(CmpOp::Le),
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(op) = op else {
+            return match field {
+                Field::HasErrors => Ok(Expr::HasErrors),
+                _ => Err(token.error(format!("`{name}` needs a comparison"))),
+            };
+        };
+        self.pos += 1;
+
+        match field {
+            Field::HasErrors => {
+                if !matches!(op, CmpOp::Eq | CmpOp::Ne) {
+                    return Err(token.error("`has_errors` only supports == and !="));
+                }
+                Ok(Expr::BoolCmp(op, self.parse_bool()?))
+            }
+            Field::Text(text) => {
+                if !matches!(op, CmpOp::Eq | CmpOp::Ne) {
+                    return Err(token.error(format!("`{name}` only supports ==, != and in")));
+                }
+                Ok(Expr::TextCmp(text, op, self.parse_text()?))
+            }
+            Field::Number(number) => Ok(Expr::NumberCmp(number, op, self.parse_number(number)?)),
+        }
+    }
+
+    fn parse_in(&mut self, field: Field, field_token: &Token) -> Result<Expr, PolicyParseError> {
+        self.expect_op("[")?;
+        let mut texts = Vec::new();
+        let mut numbers = Vec::new();
+        loop {
+            match field {
+                Field::Text(_) => texts.push(self.parse_text()?),
+                Field::Number(number) => numbers.push(self.parse_number(number)?),
+                Field::HasErrors => {
+                    return Err(field_token.error("`has_errors` does not support `in`"));
+                }
+            }
+            if self.peek_op(",") {
+                self.pos += 1;
+                if self.peek_op("]") {
+                    break;
+                }
+                continue;
+            }
+            break;
+        }
+        self.expect_op("]")?;
+        Ok(match field {
+            Field::Text(text) => Expr::TextIn(text, texts),
+            Field::Number(number) => Expr::NumberIn(number, numbers),
+            Field::HasErrors => unreachable!("rejected above"),
+        })
+    }
+
+    fn parse_text(&mut self) -> Result<String, PolicyParseError> {
+        let token = self.next("a value")?;
+        match token.kind {
+            TokenKind::Word(word) | TokenKind::Str(word) => Ok(word),
+            TokenKind::Op(_) => Err(token.error("expected a value")),
+        }
+    }
+
+    fn parse_bool(&mut self) -> Result<bool, PolicyParseError> {
+        let token = self.next("true or false")?;
+        match &token.kind {
+            TokenKind::Word(w) if w.eq_ignore_ascii_case("true") => Ok(true),
+            TokenKind::Word(w) if w.eq_ignore_ascii_case("false") => Ok(false),
+            _ => Err(token.error("expected true or false")),
+        }
+    }
+
+    fn parse_number(&mut self, field: NumberField) -> Result<u64, PolicyParseError> {
+        let token = self.next("a number")?;
+        let TokenKind::Word(word) = &token.kind else {
+            return Err(token.error("expected a number"));
+        };
+        let (digits, multiplier) = match field {
+            NumberField::Duration => match word.char_indices().last() {
+                Some((idx, 's')) => (&word[..idx], 1),
+                Some((idx, 'm')) => (&word[..idx], 60),
+                Some((idx, 'h')) => (&word[..idx], 3600),
+                _ => (word.as_str(), 1),
+            },
+            _ => (word.as_str(), 1),
+        };
+        digits
+            .parse::<u64>()
+            .map(|value| value.saturating_mul(multiplier))
+            .map_err(|_| token.error(format!("invalid number `{word}`")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subject() -> PolicySubject {
+        PolicySubject {
+            tool: "codex".to_string(),
+            provider: "openai".to_string(),
+            model: "gpt-5".to_string(),
+            repo: Some("opensession".to_string()),
+            branch: Some("main".to_string()),
+            has_errors: false,
+            duration_secs: 120,
+            events: 40,
+            messages: 8,
+            user_messages: 3,
+            tasks: 1,
+            tokens: 9000,
+            files_changed: 2,
+        }
+    }
+
+    fn allows(rule: &str, subject: &PolicySubject) -> bool {
+        PublishPolicy::parse(rule)
+            .expect("parse policy")
+            .allows(subject)
+    }
+
+    #[test]
+    fn empty_policy_allows_everything() {
+        assert!(allows("", &subject()));
+        assert!(allows("   ", &subject()));
+    }
+
+    #[test]
+    fn evaluates_the_documented_example() {
+        let rule = "publish if repo in [opensession, 'infra'] and !has_errors and duration > 60s";
+        let mut s = subject();
+        assert!(allows(rule, &s));
+
+        s.has_errors = true;
+        assert!(!allows(rule, &s));
+
+        s = subject();
+        s.repo = Some("personal".to_string());
+        assert!(!allows(rule, &s));
+
+        s = subject();
+        s.duration_secs = 30;
+        assert!(!allows(rule, &s));
+    }
+
+    #[test]
+    fn supports_precedence_parentheses_and_units() {
+        let s = subject();
+        assert!(allows(
+            "tool == claude-code or tool == codex and tokens >= 9000",
+            &s
+        ));
+        assert!(!allows(
+            "(tool == claude-code or tool == codex) and tokens > 9000",
+            &s
+        ));
+        assert!(allows("duration >= 2m and duration < 1h", &s));
+        assert!(allows("not has_errors == true && branch != release", &s));
+    }
+
+    #[test]
+    fn missing_repo_never_matches_repo_conditions() {
+        let mut s = subject();
+        s.repo = None;
+        assert!(!allows("repo in [opensession]", &s));
+        assert!(!allows("repo == opensession", &s));
+        assert!(allows("repo != opensession", &s));
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_bad_operators() {
+        let err = PublishPolicy::parse("publish if team == acme").expect_err("unknown field");
+        assert!(err.message.contains("unknown field `team`"));
+        assert_eq!(err.offset, 11);
+
+        assert!(PublishPolicy::parse("repo > 3").is_err());
+        assert!(PublishPolicy::parse("duration > soon").is_err());
+        assert!(PublishPolicy::parse("publish repo == a").is_err());
+        assert!(PublishPolicy::parse("repo in [a, b").is_err());
+        assert!(PublishPolicy::parse("has_errors and").is_err());
+        assert!(PublishPolicy::parse("tool == 'codex").is_err());
+    }
+}
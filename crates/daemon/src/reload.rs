@@ -0,0 +1,76 @@
+//! Live reload of `opensession.toml`.
+//!
+//! The file is polled rather than watched with notify: editors and the CLI
+//! replace it via rename, which drops inode-based watches on some platforms.
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+use crate::config::{self, DaemonConfig};
+
+const RELOAD_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Modification time and size, enough to notice saves without reading the file.
+type FileStamp = Option<(SystemTime, u64)>;
+
+fn file_stamp(path: &Path) -> FileStamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Poll the runtime config and publish each valid change on `config_tx`.
+///
+/// Unparseable edits are logged and skipped so the daemon keeps running on the
+/// last good config.
+pub(crate) async fn run_config_reload(
+    config_tx: watch::Sender<DaemonConfig>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let Ok(path) = config::config_path() else {
+        return;
+    };
+    let mut last_stamp = file_stamp(&path);
+    let mut tick = tokio::time::interval(RELOAD_POLL_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = tick.tick() => {
+                let stamp = file_stamp(&path);
+                if stamp == last_stamp {
+                    continue;
+                }
+                last_stamp = stamp;
+                reload_once(&path, &config_tx);
+            }
+            _ = shutdown.changed() => {
+                if *shutdown.borrow() {
+                    debug!("Config reload shutting down");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn reload_once(path: &Path, config_tx: &watch::Sender<DaemonConfig>) {
+    config::log_config_issues();
+    let next = match config::load_config() {
+        Ok(next) => next,
+        Err(error) => {
+            warn!(
+                "Ignoring change to {}; keeping previous config: {error:#}",
+                path.display()
+            );
+            return;
+        }
+    };
+    let changes = config::describe_config_changes(&config_tx.borrow(), &next);
+    if changes.is_empty() {
+        debug!("{} saved without effective changes", path.display());
+        return;
+    }
+    info!("Reloaded {}: {}", path.display(), changes.join("; "));
+    config_tx.send_replace(next);
+}
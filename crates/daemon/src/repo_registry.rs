@@ -1,15 +1,10 @@
 use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
+use opensession_runtime_config::RepoRegistryFile;
 use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Serialize, Deserialize, Default)]
-struct RegistryPayload {
-    repos: Vec<String>,
-}
-
 /// Tracks repository roots that successfully stored sessions in git-native mode.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct RepoRegistry {
     path: Option<PathBuf>,
     repos: BTreeSet<PathBuf>,
@@ -17,26 +12,13 @@ pub struct RepoRegistry {
 
 impl RepoRegistry {
     pub fn load_default() -> Result<Self> {
-        let path = crate::config::config_dir()?.join("repo-registry.json");
-        if !path.exists() {
-            return Ok(Self {
-                path: Some(path),
-                repos: BTreeSet::new(),
-            });
-        }
-
-        let raw = std::fs::read_to_string(&path)
+        let path = opensession_paths::repo_registry_path()
+            .context("Could not determine repo registry path")?;
+        let file = RepoRegistryFile::read(&path)
             .with_context(|| format!("read repo registry {}", path.display()))?;
-        let payload: RegistryPayload = serde_json::from_str(&raw)
-            .with_context(|| format!("parse repo registry {}", path.display()))?;
-        let repos = payload
-            .repos
-            .iter()
-            .map(PathBuf::from)
-            .collect::<BTreeSet<_>>();
         Ok(Self {
             path: Some(path),
-            repos,
+            repos: file.repos.iter().map(PathBuf::from).collect(),
         })
     }
 
@@ -52,23 +34,21 @@ impl RepoRegistry {
         self.repos.iter().cloned().collect()
     }
 
-    fn persist(&self) -> Result<()> {
+    /// Merge with the file on disk first, so repos registered by
+    /// `opensession init --repo` while the daemon runs are kept.
+    fn persist(&mut self) -> Result<()> {
         let Some(path) = &self.path else {
             return Ok(());
         };
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)
-                .with_context(|| format!("create {}", parent.display()))?;
-        }
-        let payload = RegistryPayload {
-            repos: self
-                .repos
-                .iter()
-                .map(|p| p.to_string_lossy().to_string())
-                .collect(),
-        };
-        let bytes = serde_json::to_vec_pretty(&payload)?;
-        std::fs::write(path, bytes).with_context(|| format!("write {}", path.display()))?;
+        let mut file = RepoRegistryFile::read(path).unwrap_or_default();
+        self.repos.extend(file.repos.iter().map(PathBuf::from));
+        file.repos = self
+            .repos
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        file.write(path)
+            .with_context(|| format!("write {}", path.display()))?;
         Ok(())
     }
 }
@@ -92,4 +72,21 @@ mod tests {
         assert!(!registry.add(&repo).expect("duplicate add"));
         assert_eq!(registry.repo_roots().len(), 1);
     }
+
+    #[test]
+    fn add_keeps_repos_registered_by_others() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("repo-registry.json");
+        let mut registry = RepoRegistry {
+            path: Some(path.clone()),
+            repos: BTreeSet::new(),
+        };
+        let mut file = RepoRegistryFile::default();
+        file.repos.insert("/work/registered-by-cli".to_string());
+        file.write(&path).expect("write registry");
+
+        assert!(registry.add(&dir.path().join("repo")).expect("add"));
+        assert_eq!(registry.repo_roots().len(), 2);
+        assert_eq!(RepoRegistryFile::read(&path).expect("read").repos.len(), 2);
+    }
 }
@@ -1,14 +1,17 @@
 use anyhow::Result;
-use opensession_local_db::LocalDb;
+use opensession_local_db::AsyncLocalDb;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::{mpsc, watch};
-use tracing::info;
+use tracing::{info, warn};
 
-use crate::{config, health, scheduler, watcher};
+use crate::{config, crash_loop, health, prefetch, reload, scheduler, telemetry, watcher};
 
 pub(crate) async fn run() -> Result<()> {
     info!("opensession-daemon starting");
+    crash_loop::backoff_on_start().await;
 
+    config::log_config_issues();
     let cfg = config::load_config()?;
     let watch_paths = config::resolve_watch_paths(&cfg);
 
@@ -18,22 +21,54 @@ pub(crate) async fn run() -> Result<()> {
         info!("Watching {} directories", watch_paths.len());
     }
 
-    let db = Arc::new(LocalDb::open()?);
+    let db = AsyncLocalDb::open().await?;
     info!("Local DB opened");
 
     write_pid_file()?;
 
     let (tx, rx) = mpsc::unbounded_channel();
     let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let (config_tx, config_rx) = watch::channel(cfg.clone());
 
-    let _watcher = start_watcher_pipeline(&watch_paths, &tx)?;
+    let watcher = start_watcher_pipeline(&watch_paths, &tx)?;
+    let watcher_handle = tokio::spawn(follow_watch_path_changes(
+        watcher,
+        watch_paths.clone(),
+        config_rx.clone(),
+        tx,
+        shutdown_rx.clone(),
+    ));
+    let reload_handle = tokio::spawn(reload::run_config_reload(config_tx, shutdown_rx.clone()));
+
+    let (body_cache_tx, body_cache_rx) = watch::channel(None);
+    let prefetch_handle = tokio::spawn(prefetch::run_body_prefetch(
+        config_rx.clone(),
+        db.clone(),
+        body_cache_tx,
+        shutdown_rx.clone(),
+    ));
 
-    let scheduler_cfg = cfg.clone();
+    let scheduler_config = config_rx.clone();
     let scheduler_shutdown = shutdown_rx.clone();
-    let scheduler_db = Arc::clone(&db);
+    let scheduler_db = db.clone();
+    let counters = Arc::new(telemetry::TelemetryCounters::default());
+    let scheduler_counters = Arc::clone(&counters);
     let scheduler_handle = tokio::spawn(async move {
-        scheduler::run_scheduler(scheduler_cfg, rx, scheduler_shutdown, scheduler_db).await;
+        scheduler::run_scheduler(
+            scheduler_config,
+            rx,
+            scheduler_shutdown,
+            scheduler_db,
+            scheduler_counters,
+            body_cache_rx,
+        )
+        .await;
     });
+    let telemetry_handle = tokio::spawn(telemetry::run_telemetry_reporter(
+        config_rx.clone(),
+        counters,
+        shutdown_rx.clone(),
+    ));
 
     let health_shutdown = shutdown_rx.clone();
     let health_handle = tokio::spawn(health::run_health_check(
@@ -51,8 +86,13 @@ pub(crate) async fn run() -> Result<()> {
 
     let _ = scheduler_handle.await;
     let _ = health_handle.await;
+    let _ = telemetry_handle.await;
+    let _ = prefetch_handle.await;
+    let _ = reload_handle.await;
+    let _ = watcher_handle.await;
 
     cleanup_pid_file();
+    crash_loop::clear();
 
     info!("opensession-daemon stopped");
     Ok(())
@@ -77,6 +117,86 @@ fn start_watcher_pipeline(
     Ok(Some(watcher_handle))
 }
 
+/// Keep the notify watcher in sync with reloaded `watchers.custom_paths` and
+/// with roots that appear after startup. Newly added roots are backfilled like
+/// on startup, and all roots are rescanned periodically for missed events.
+async fn follow_watch_path_changes(
+    mut watcher: Option<notify::RecommendedWatcher>,
+    mut watch_paths: Vec<PathBuf>,
+    mut config_rx: watch::Receiver<opensession_runtime_config::DaemonConfig>,
+    tx: mpsc::UnboundedSender<watcher::FileChangeEvent>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut snapshot = watcher::RescanSnapshot::capture(&watch_paths);
+    let mut rescan = tokio::time::interval(watcher::RESCAN_INTERVAL);
+    rescan.tick().await;
+
+    loop {
+        tokio::select! {
+            Ok(()) = config_rx.changed() => {
+                let next_paths = config::resolve_watch_paths(&config_rx.borrow_and_update());
+                if next_paths != watch_paths {
+                    restart_watcher(&mut watcher, &watch_paths, &next_paths, &tx, "config reload");
+                    watch_paths = next_paths;
+                    // Baseline the new roots now; their files were just seeded.
+                    snapshot.rescan(&watch_paths, &tx);
+                }
+            }
+            _ = rescan.tick() => {
+                // Roots that did not exist yet are dropped by `resolve_watch_paths`;
+                // pick them up once the agent creates them.
+                let next_paths = config::resolve_watch_paths(&config_rx.borrow());
+                if next_paths != watch_paths {
+                    restart_watcher(&mut watcher, &watch_paths, &next_paths, &tx, "watch roots changed");
+                    watch_paths = next_paths;
+                }
+                let queued = snapshot.rescan(&watch_paths, &tx);
+                if queued > 0 {
+                    info!("Rescan queued {queued} session files the watcher did not report");
+                }
+            }
+            _ = shutdown.changed() => {
+                if *shutdown.borrow() {
+                    break;
+                }
+            }
+        }
+    }
+    drop(watcher);
+}
+
+fn restart_watcher(
+    watcher: &mut Option<notify::RecommendedWatcher>,
+    watch_paths: &[PathBuf],
+    next_paths: &[PathBuf],
+    tx: &mpsc::UnboundedSender<watcher::FileChangeEvent>,
+    reason: &str,
+) {
+    let added: Vec<PathBuf> = next_paths
+        .iter()
+        .filter(|path| !watch_paths.contains(path))
+        .cloned()
+        .collect();
+    drop(watcher.take());
+    *watcher = if next_paths.is_empty() {
+        None
+    } else {
+        match watcher::start_watcher(next_paths, tx.clone()) {
+            Ok(handle) => Some(handle),
+            Err(error) => {
+                warn!("Failed to restart watcher after {reason}: {error:#}");
+                None
+            }
+        }
+    };
+    let seeded = watcher::seed_existing_session_files(&added, tx);
+    info!(
+        "Watching {} directories after {reason} ({} existing session files queued)",
+        next_paths.len(),
+        seeded
+    );
+}
+
 fn write_pid_file() -> Result<()> {
     let path = config::pid_file_path()?;
     let dir = path.parent().expect("pid file path should have parent");
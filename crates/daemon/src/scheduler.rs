@@ -3,9 +3,11 @@ mod git_retention;
 mod helpers;
 mod lifecycle;
 mod pipeline;
+mod reconcile;
 mod runtime;
 
 #[cfg(test)]
 mod tests;
 
+pub(crate) use config_resolution::resolve_effective_config;
 pub use runtime::run_scheduler;
@@ -35,7 +35,7 @@ pub(super) fn resolve_lifecycle_schedule(config: &DaemonConfig) -> Option<Durati
     ))
 }
 
-pub(super) fn resolve_effective_config(session: &Session, config: &DaemonConfig) -> DaemonConfig {
+pub(crate) fn resolve_effective_config(session: &Session, config: &DaemonConfig) -> DaemonConfig {
     if let Some(cwd) = session_cwd(session) {
         if let Some(repo_root) = crate::config::find_repo_root(cwd) {
             if let Some(project) = crate::config::load_effective_project_config(&repo_root) {
@@ -1,5 +1,6 @@
 use crate::config::DaemonConfig;
 use opensession_core::Session;
+use opensession_core::jsonl::write_jsonl;
 use opensession_core::sanitize::{SanitizeConfig, sanitize_session};
 use opensession_core::session::{GitMeta, build_git_storage_meta_json_with_git, working_directory};
 
@@ -12,8 +13,9 @@ pub(super) fn build_session_meta_json(session: &Session, git: Option<&GitMeta>)
 }
 
 pub(super) fn session_to_hail_jsonl_bytes(session: &Session) -> Option<Vec<u8>> {
-    match session.to_jsonl() {
-        Ok(jsonl) => Some(jsonl.into_bytes()),
+    let mut buf = Vec::new();
+    match write_jsonl(session, &mut buf) {
+        Ok(()) => Some(buf),
         Err(error) => {
             tracing::warn!(
                 "Failed to serialize session {} to HAIL JSONL: {}",
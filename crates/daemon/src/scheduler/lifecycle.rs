@@ -83,6 +83,9 @@ pub(super) fn run_lifecycle_cleanup_once(
         return Ok(());
     }
 
+    // Re-locate moved source files first so they are not swept as orphans.
+    super::reconcile::reconcile_source_paths(db);
+
     let storage = opensession_git_native::NativeGitStorage;
     let expired_sessions = db.list_expired_session_ids(config.lifecycle.session_ttl_days)?;
     let orphaned_sessions = list_sessions_with_missing_source_parent_dirs(db)?;
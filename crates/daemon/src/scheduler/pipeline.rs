@@ -1,21 +1,28 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use opensession_core::Session;
-use opensession_core::session::{GitMeta, interaction_compressed_session, is_auxiliary_session};
+use opensession_core::session::{
+    GitMeta, interaction_compressed_session, is_auxiliary_session, metrics_only_session,
+};
 use opensession_git_native::{
     SUMMARY_LEDGER_REF, SessionSummaryLedgerRecord, branch_ledger_ref, extract_git_context,
     resolve_ledger_branch,
 };
-use opensession_local_db::LocalDb;
+use opensession_local_db::environment::SessionEnvironment;
+use opensession_local_db::{AsyncLocalDb, LocalDb};
 use opensession_parsers::ParserRegistry;
 use opensession_runtime_config::SummaryStorageBackend;
-use opensession_summary::GitSummaryRequest;
-use opensession_summary_runtime::summarize_session;
+use opensession_summary::{CachedSemanticSummary, GitSummaryRequest};
+use opensession_summary_runtime::summarize_session_cached;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
 use crate::config::{DaemonConfig, GitStorageMethod, SessionDefaultView};
+use crate::pre_upload::{PreUploadOutcome, run_pre_upload_hooks};
+use crate::publish_policy::{PolicySubject, PublishPolicy};
 use crate::repo_registry::RepoRegistry;
+use crate::status::{ProcessOutcome, active_session_status};
+use crate::telemetry::FailedStep;
 
 use super::config_resolution::resolve_effective_config;
 use super::git_retention::collect_commit_shas_for_session;
@@ -24,28 +31,42 @@ use super::helpers::{
 };
 
 pub(super) async fn process_file(
-    path: &PathBuf,
+    path: &Path,
     config: &DaemonConfig,
-    db: &LocalDb,
+    db: &AsyncLocalDb,
     repo_registry: &mut RepoRegistry,
     auto_upload: bool,
-) -> Result<()> {
-    if was_already_uploaded(path, db)? {
-        return Ok(());
-    }
-
-    let mut session = match parse_session(path)? {
-        Some(session) => session,
-        None => return Ok(()),
+) -> Result<ProcessOutcome> {
+    let parsed = {
+        let path = path.to_path_buf();
+        db.call(move |db| {
+            if was_already_uploaded(&path, db)? {
+                return Ok(None);
+            }
+            parse_session(&path, db).context(FailedStep::Parse)
+        })
+        .await?
+    };
+    let Some(session) = parsed else {
+        return Ok(ProcessOutcome::Skipped);
     };
 
     let effective_config = resolve_effective_config(&session, config);
 
     if is_tool_excluded(&session, &effective_config) {
-        return Ok(());
+        return Ok(ProcessOutcome::Skipped);
     }
 
-    store_locally(&session, path, db, &effective_config)?;
+    let mut session = {
+        let path = path.to_path_buf();
+        let config = effective_config.clone();
+        db.call(move |db| {
+            store_locally(&session, &path, db, &config)?;
+            Ok(session)
+        })
+        .await?
+    };
+    let active = active_session_status(&session);
     if let Err(error) = maybe_generate_semantic_summary(&session, db, &effective_config).await {
         warn!(
             session_id = %session.session_id,
@@ -53,13 +74,43 @@ pub(super) async fn process_file(
         );
     }
 
-    if !auto_upload {
-        return Ok(());
+    if !auto_upload || !publish_policy_allows(&session, &effective_config) {
+        return Ok(ProcessOutcome::LocalOnly(active));
     }
 
+    let metrics_only = {
+        let candidates: Vec<&Path> = std::iter::once(path)
+            .chain(session_cwd(&session).map(Path::new))
+            .collect();
+        crate::config::is_metrics_only(&effective_config, &candidates)
+    };
+
     sanitize(&mut session, &effective_config);
 
-    let git_store = maybe_git_store(&session, &effective_config);
+    match run_pre_upload_hooks(&session, &effective_config.daemon.pre_upload_hooks)
+        .await
+        .context(FailedStep::Publish)?
+    {
+        PreUploadOutcome::Publish(hooked) => session = *hooked,
+        PreUploadOutcome::Vetoed { hook, reason } => {
+            info!(
+                "Pre-upload hook `{hook}` vetoed session {}: {reason}",
+                session.session_id
+            );
+            return Ok(ProcessOutcome::LocalOnly(active));
+        }
+    }
+
+    let cwd = session_cwd(&session).map(str::to_string);
+    if metrics_only {
+        info!(
+            "Publishing only aggregate stats for session {}",
+            session.session_id
+        );
+        session = metrics_only_session(&session);
+    }
+
+    let git_store = maybe_git_store(&session, cwd.as_deref(), &effective_config);
     if let Some(ref stored) = git_store {
         if let Err(error) = repo_registry.add(&stored.repo_root) {
             warn!(
@@ -69,13 +120,29 @@ pub(super) async fn process_file(
         }
     }
 
-    mark_session_share_ready(
-        &session,
-        db,
-        git_store
-            .as_ref()
-            .and_then(|stored| stored.body_url.as_deref()),
-    )
+    let body_url = git_store.and_then(|stored| stored.body_url);
+    db.call(move |db| mark_session_share_ready(&session, db, body_url.as_deref()))
+        .await
+        .context(FailedStep::Publish)?;
+    Ok(ProcessOutcome::Synced(active))
+}
+
+pub(super) fn publish_policy_allows(session: &Session, config: &DaemonConfig) -> bool {
+    let policy = match PublishPolicy::parse(&config.daemon.publish_policy) {
+        Ok(policy) => policy,
+        Err(error) => {
+            warn!(
+                session_id = %session.session_id,
+                "invalid publish_policy, keeping session local: {error}"
+            );
+            return false;
+        }
+    };
+    let allowed = policy.allows(&PolicySubject::for_session(session));
+    if !allowed {
+        info!("Publish policy kept session {} local", session.session_id);
+    }
+    allowed
 }
 
 pub(super) fn was_already_uploaded(path: &PathBuf, db: &LocalDb) -> Result<bool> {
@@ -88,12 +155,30 @@ pub(super) fn was_already_uploaded(path: &PathBuf, db: &LocalDb) -> Result<bool>
     Ok(false)
 }
 
-pub(super) fn parse_session(path: &Path) -> Result<Option<Session>> {
-    let session = match ParserRegistry::default().parse_path(path)? {
-        Some(session) => session,
-        None => {
-            warn!("No parser for: {}", path.display());
-            return Ok(None);
+/// Parse a session file, keeping the outcome in the local DB's parse error
+/// list so `opensession doctor` can report files that fail.
+pub(super) fn parse_session(path: &Path, db: &LocalDb) -> Result<Option<Session>> {
+    let registry = ParserRegistry::with_plugins();
+    let Some(parser) = registry.parser_for_path(path) else {
+        warn!("No parser for: {}", path.display());
+        return Ok(None);
+    };
+    let source_path = path.to_string_lossy();
+    let session = match parser.parse(path) {
+        Ok(session) => {
+            db.clear_parse_error(&source_path)?;
+            session
+        }
+        Err(error) => {
+            if let Err(db_error) =
+                db.record_parse_error(&source_path, Some(parser.name()), &format!("{error:#}"))
+            {
+                warn!(
+                    "Failed to record parse error for {}: {db_error}",
+                    path.display()
+                );
+            }
+            return Err(error);
         }
     };
     if is_auxiliary_session(&session) {
@@ -148,6 +233,15 @@ pub(super) fn store_locally(
     };
 
     db.upsert_local_session(&local_session, &path_str, &local_git)?;
+    let route_candidates: Vec<&Path> = std::iter::once(path)
+        .chain(session_cwd(&local_session).map(Path::new))
+        .collect();
+    if let Some(team) = crate::config::resolve_route_team(config, &route_candidates) {
+        db.set_session_team(&session.session_id, &team)?;
+    }
+    if config.privacy.capture_environment {
+        record_environment(&local_session, db)?;
+    }
     match std::fs::read(path) {
         Ok(body) => {
             if let Err(error) = db.cache_body(&session.session_id, &body) {
@@ -167,9 +261,29 @@ pub(super) fn store_locally(
     Ok(())
 }
 
+/// Snapshot the machine a session runs on the first time it is indexed.
+/// Sessions idle for longer than `ENVIRONMENT_CAPTURE_WINDOW` are skipped:
+/// the current machine state would no longer describe them.
+fn record_environment(session: &Session, db: &LocalDb) -> Result<()> {
+    const ENVIRONMENT_CAPTURE_WINDOW: chrono::Duration = chrono::Duration::hours(1);
+
+    if Utc::now() - session.context.updated_at > ENVIRONMENT_CAPTURE_WINDOW
+        || db.has_session_environment(&session.session_id)?
+    {
+        return Ok(());
+    }
+    let environment = SessionEnvironment::capture(session_cwd(session));
+    debug!(
+        "Recorded environment for session {}: {}",
+        session.session_id,
+        environment.summary()
+    );
+    db.set_session_environment(&session.session_id, &environment)
+}
+
 pub(super) async fn maybe_generate_semantic_summary(
     session: &Session,
-    db: &LocalDb,
+    db: &AsyncLocalDb,
     config: &DaemonConfig,
 ) -> Result<()> {
     let settings = &config.summary;
@@ -194,9 +308,27 @@ pub(super) async fn maybe_generate_semantic_summary(
         None
     };
 
-    let artifact = summarize_session(session, settings, git_request.as_ref())
-        .await
-        .map_err(anyhow::Error::msg)?;
+    let cached = if settings.persists_to_local_db() {
+        let session_id = session.session_id.clone();
+        db.call(move |db| db.get_session_semantic_summary(&session_id))
+            .await?
+            .and_then(|row| {
+                CachedSemanticSummary::from_stored(
+                    &row.provider,
+                    row.model.as_deref(),
+                    &row.generation_kind,
+                    row.prompt_fingerprint.as_deref(),
+                    &row.summary_json,
+                )
+            })
+    } else {
+        None
+    };
+
+    let artifact =
+        summarize_session_cached(session, settings, git_request.as_ref(), cached.as_ref())
+            .await
+            .map_err(anyhow::Error::msg)?;
 
     match settings.storage.backend {
         SummaryStorageBackend::LocalDb => {
@@ -226,21 +358,26 @@ pub(super) async fn maybe_generate_semantic_summary(
                 Some(artifact.prompt_fingerprint)
             };
 
-            db.upsert_session_semantic_summary(
-                &opensession_local_db::SessionSemanticSummaryUpsert {
-                    session_id: &session.session_id,
-                    summary_json: &summary_json,
-                    generated_at: &generated_at,
-                    provider: &provider,
-                    model: model.as_deref(),
-                    source_kind: &source_kind,
-                    generation_kind: &generation_kind,
-                    prompt_fingerprint: prompt_fingerprint.as_deref(),
-                    source_details_json: source_details_json.as_deref(),
-                    diff_tree_json: diff_tree_json.as_deref(),
-                    error: artifact.error.as_deref(),
-                },
-            )?;
+            let session_id = session.session_id.clone();
+            let error = artifact.error;
+            db.call(move |db| {
+                db.upsert_session_semantic_summary(
+                    &opensession_local_db::SessionSemanticSummaryUpsert {
+                        session_id: &session_id,
+                        summary_json: &summary_json,
+                        generated_at: &generated_at,
+                        provider: &provider,
+                        model: model.as_deref(),
+                        source_kind: &source_kind,
+                        generation_kind: &generation_kind,
+                        prompt_fingerprint: prompt_fingerprint.as_deref(),
+                        source_details_json: source_details_json.as_deref(),
+                        diff_tree_json: diff_tree_json.as_deref(),
+                        error: error.as_deref(),
+                    },
+                )
+            })
+            .await?;
         }
         SummaryStorageBackend::HiddenRef => {
             let cwd = session_cwd(session)
@@ -280,12 +417,18 @@ pub(super) struct GitStoreOutcome {
     pub(super) repo_root: PathBuf,
 }
 
-pub(super) fn maybe_git_store(session: &Session, config: &DaemonConfig) -> Option<GitStoreOutcome> {
+/// Store `session` in the git-native ledger of the repository at `cwd`, the
+/// session's working directory (taken before a metrics-only session lost it).
+pub(super) fn maybe_git_store(
+    session: &Session,
+    cwd: Option<&str>,
+    config: &DaemonConfig,
+) -> Option<GitStoreOutcome> {
     if config.git_storage.method == GitStorageMethod::Sqlite {
         return None;
     }
 
-    let cwd = session_cwd(session)?;
+    let cwd = cwd?;
     let repo_root = crate::config::find_repo_root(cwd)?;
     let git_ctx = extract_git_context(cwd);
     let branch = resolve_ledger_branch(git_ctx.branch.as_deref(), git_ctx.commit.as_deref());
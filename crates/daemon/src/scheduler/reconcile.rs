@@ -0,0 +1,24 @@
+use opensession_local_db::LocalDb;
+use opensession_parser_discovery::discover_sessions;
+use tracing::{info, warn};
+
+/// Re-point source paths of files moved outside opensession, and mark the
+/// ones that cannot be found so hydration treats them as body-less.
+pub(super) fn reconcile_source_paths(db: &LocalDb) {
+    let discovered = || {
+        discover_sessions()
+            .into_iter()
+            .flat_map(|location| location.paths)
+            .collect()
+    };
+    match db.reconcile_source_paths(discovered, false) {
+        Ok(report) if report.stale > 0 => info!(
+            stale = report.stale,
+            relocated = report.relocated.len(),
+            missing = report.missing.len(),
+            "Source reconciliation: updated stale source paths"
+        ),
+        Ok(_) => {}
+        Err(error) => warn!("Source reconciliation failed: {error}"),
+    }
+}
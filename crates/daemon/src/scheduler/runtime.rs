@@ -1,13 +1,16 @@
-use opensession_local_db::LocalDb;
+use opensession_local_db::AsyncLocalDb;
+use opensession_runtime_config::BodyCacheStatus;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 use tokio::time::Instant;
 use tracing::{debug, error, info, warn};
 
 use crate::config::{DaemonConfig, PublishMode};
 use crate::repo_registry::RepoRegistry;
+use crate::status::StatusFile;
+use crate::telemetry::TelemetryCounters;
 use crate::watcher::FileChangeEvent;
 
 use super::config_resolution::{
@@ -17,16 +20,23 @@ use super::config_resolution::{
 use super::git_retention::run_git_retention_once;
 use super::lifecycle::{run_lifecycle_cleanup_on_start, run_lifecycle_cleanup_once};
 use super::pipeline::process_file;
+use super::reconcile::reconcile_source_paths;
 
+/// Debounce and upload sessions as they change. `config_rx` carries live
+/// reloads of `opensession.toml`; schedules derived from it are recomputed.
+/// `body_cache` is the prefetch task's latest stats for the status file.
 pub async fn run_scheduler(
-    config: DaemonConfig,
+    mut config_rx: watch::Receiver<DaemonConfig>,
     mut rx: mpsc::UnboundedReceiver<FileChangeEvent>,
-    mut shutdown: tokio::sync::watch::Receiver<bool>,
-    db: std::sync::Arc<LocalDb>,
+    mut shutdown: watch::Receiver<bool>,
+    db: AsyncLocalDb,
+    telemetry: std::sync::Arc<TelemetryCounters>,
+    body_cache: watch::Receiver<Option<BodyCacheStatus>>,
 ) {
-    let debounce_duration = Duration::from_secs(config.daemon.debounce_secs);
+    let mut config = config_rx.borrow_and_update().clone();
+    let mut debounce_duration = Duration::from_secs(config.daemon.debounce_secs);
 
-    let effective_mode = resolve_publish_mode(&config.daemon);
+    let mut effective_mode = resolve_publish_mode(&config.daemon);
     let mut repo_registry = match RepoRegistry::load_default() {
         Ok(registry) => registry,
         Err(error) => {
@@ -35,14 +45,27 @@ pub async fn run_scheduler(
         }
     };
 
-    run_lifecycle_cleanup_on_start(&config, &db, &repo_registry);
+    let startup = {
+        let config = config.clone();
+        let repo_registry = repo_registry.clone();
+        db.call(move |db| {
+            reconcile_source_paths(db);
+            run_lifecycle_cleanup_on_start(&config, db, &repo_registry);
+            Ok(())
+        })
+        .await
+    };
+    if let Err(error) = startup {
+        warn!("Scheduler startup maintenance failed: {error:#}");
+    }
 
     let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+    let mut status = StatusFile::open();
 
     let mut tick = tokio::time::interval(Duration::from_secs(1));
-    let retention_schedule = resolve_git_retention_schedule(&config);
+    let mut retention_schedule = resolve_git_retention_schedule(&config);
     let mut next_retention_run = retention_schedule.map(|(_, interval)| Instant::now() + interval);
-    let lifecycle_interval = resolve_lifecycle_schedule(&config);
+    let mut lifecycle_interval = resolve_lifecycle_schedule(&config);
     let mut next_lifecycle_run = lifecycle_interval.map(|interval| Instant::now() + interval);
 
     loop {
@@ -72,25 +95,50 @@ pub async fn run_scheduler(
                             path.display()
                         );
                     }
-                    if let Err(error) = process_file(
+                    let result = process_file(
                         &path,
                         &config,
                         &db,
                         &mut repo_registry,
                         should_auto_upload(&effective_mode),
                     )
-                    .await
-                    {
-                        error!("Failed to process {}: {:#}", path.display(), error);
+                    .await;
+                    telemetry.record(&result);
+                    match result {
+                        Ok(outcome) => status.record(outcome, chrono::Utc::now().timestamp()),
+                        Err(error) => {
+                            error!("Failed to process {}: {:#}", path.display(), error);
+                            status.record_error(&error);
+                        }
                     }
                 }
+                status.set_pending(pending.len());
+                status.set_body_cache(body_cache.borrow().clone());
+                status.flush(chrono::Utc::now().timestamp());
 
                 maybe_run_retention_cycle(now, retention_schedule, &mut next_retention_run, &repo_registry);
-                maybe_run_lifecycle_cycle(now, lifecycle_interval, &mut next_lifecycle_run, &config, &db, &repo_registry);
+                maybe_run_lifecycle_cycle(now, lifecycle_interval, &mut next_lifecycle_run, &config, &db, &repo_registry).await;
+            }
+            Ok(()) = config_rx.changed() => {
+                config = config_rx.borrow_and_update().clone();
+                debounce_duration = Duration::from_secs(config.daemon.debounce_secs);
+                effective_mode = resolve_publish_mode(&config.daemon);
+                let next_retention = resolve_git_retention_schedule(&config);
+                if next_retention != retention_schedule {
+                    retention_schedule = next_retention;
+                    next_retention_run = retention_schedule.map(|(_, interval)| Instant::now() + interval);
+                }
+                let next_lifecycle = resolve_lifecycle_schedule(&config);
+                if next_lifecycle != lifecycle_interval {
+                    lifecycle_interval = next_lifecycle;
+                    next_lifecycle_run = lifecycle_interval.map(|interval| Instant::now() + interval);
+                }
+                debug!("Scheduler applied reloaded config ({:?} mode)", effective_mode);
             }
             _ = shutdown.changed() => {
                 if *shutdown.borrow() {
                     info!("Scheduler shutting down");
+                    status.remove();
                     break;
                 }
             }
@@ -115,17 +163,22 @@ fn maybe_run_retention_cycle(
     }
 }
 
-fn maybe_run_lifecycle_cycle(
+async fn maybe_run_lifecycle_cycle(
     now: Instant,
     lifecycle_interval: Option<Duration>,
     next_lifecycle_run: &mut Option<Instant>,
     config: &DaemonConfig,
-    db: &LocalDb,
+    db: &AsyncLocalDb,
     repo_registry: &RepoRegistry,
 ) {
     if let (Some(interval), Some(next_at)) = (lifecycle_interval, *next_lifecycle_run) {
         if now >= next_at {
-            if let Err(error) = run_lifecycle_cleanup_once(config, db, repo_registry) {
+            let config = config.clone();
+            let repo_registry = repo_registry.clone();
+            let result = db
+                .call(move |db| run_lifecycle_cleanup_once(&config, db, &repo_registry))
+                .await;
+            if let Err(error) = result {
                 warn!("Lifecycle cleanup failed: {error}");
             }
             *next_lifecycle_run = Some(now + interval);
@@ -4,7 +4,7 @@ use super::config_resolution::{
 };
 use super::helpers::{build_session_meta_json, session_cwd, session_to_hail_jsonl_bytes};
 use super::lifecycle::{run_lifecycle_cleanup_on_start, run_lifecycle_cleanup_once};
-use super::pipeline::{maybe_generate_semantic_summary, store_locally};
+use super::pipeline::{maybe_generate_semantic_summary, parse_session, store_locally};
 use crate::config::{
     DaemonConfig, DaemonSettings, GitStorageMethod, PublishMode, SessionDefaultView,
 };
@@ -12,8 +12,10 @@ use crate::repo_registry::RepoRegistry;
 use chrono::Utc;
 use opensession_core::{Agent, Content, Event, EventType, Session};
 use opensession_git_native::{NativeGitStorage, SUMMARY_LEDGER_REF, SessionSummaryLedgerRecord};
-use opensession_local_db::LocalDb;
-use opensession_runtime_config::{SummaryProvider, SummaryStorageBackend, SummaryTriggerMode};
+use opensession_local_db::{AsyncLocalDb, LocalDb};
+use opensession_runtime_config::{
+    SummaryProvider, SummaryStorageBackend, SummaryTriggerMode, TeamRoute,
+};
 use serde_json::json;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -642,11 +644,96 @@ fn test_store_locally_caches_source_body() {
     assert_eq!(cached, source_body);
 }
 
+#[test]
+fn test_store_locally_applies_team_route_for_source_path() {
+    let tmp = tempdir().expect("tempdir");
+    let db_path = PathBuf::from(tmp.path()).join("local.db");
+    let db = LocalDb::open_path(&db_path).expect("open local db");
+    let mut config = DaemonConfig::default();
+    config.routes = vec![TeamRoute {
+        path: "/tmp/work/**".to_string(),
+        team: "acme".to_string(),
+    }];
+
+    let routed = make_interaction_fixture_session("store-routed");
+    store_locally(&routed, Path::new("/tmp/work/a.jsonl"), &db, &config)
+        .expect("store routed session");
+    let unrouted = make_interaction_fixture_session("store-personal");
+    store_locally(&unrouted, Path::new("/tmp/home/b.jsonl"), &db, &config)
+        .expect("store personal session");
+
+    let team_of = |id: &str| {
+        db.get_session_by_id(id)
+            .expect("query session")
+            .expect("session exists")
+            .team_id
+    };
+    assert_eq!(team_of("store-routed").as_deref(), Some("acme"));
+    assert_eq!(team_of("store-personal").as_deref(), Some("personal"));
+}
+
+#[test]
+fn test_store_locally_records_environment_only_when_enabled() {
+    let tmp = tempdir().expect("tempdir");
+    let db_path = PathBuf::from(tmp.path()).join("local.db");
+    let db = LocalDb::open_path(&db_path).expect("open local db");
+    let environment_of = |id: &str| {
+        db.get_session_by_id(id)
+            .expect("query session")
+            .expect("session exists")
+            .environment
+    };
+
+    let mut config = DaemonConfig::default();
+    let session = make_interaction_fixture_session("env-default");
+    store_locally(&session, Path::new("/tmp/env-default.jsonl"), &db, &config)
+        .expect("store session");
+    assert_eq!(environment_of("env-default"), None);
+
+    config.privacy.capture_environment = true;
+    let session = make_interaction_fixture_session("env-live");
+    store_locally(&session, Path::new("/tmp/env-live.jsonl"), &db, &config).expect("store session");
+    let recorded: serde_json::Value =
+        serde_json::from_str(&environment_of("env-live").expect("environment recorded"))
+            .expect("environment json");
+    assert_eq!(recorded["os"], std::env::consts::OS);
+
+    let mut stale = make_interaction_fixture_session("env-stale");
+    stale.context.updated_at = Utc::now() - chrono::Duration::days(2);
+    store_locally(&stale, Path::new("/tmp/env-stale.jsonl"), &db, &config).expect("store session");
+    assert_eq!(environment_of("env-stale"), None);
+}
+
+#[test]
+fn test_parse_session_records_and_clears_parse_errors() {
+    let tmp = tempdir().expect("tempdir");
+    let db = LocalDb::open_path(&tmp.path().join("local.db")).expect("open local db");
+    let chats = tmp.path().join(".gemini/tmp/abc123/chats");
+    std::fs::create_dir_all(&chats).expect("create chats dir");
+    let source = chats.join("session-broken.json");
+    std::fs::write(&source, r#"{"sessionId":"g1","messages":["#).expect("write broken log");
+
+    assert!(parse_session(&source, &db).is_err());
+    let errors = db.list_parse_errors().expect("list parse errors");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].source_path, source.to_string_lossy());
+    assert_eq!(errors[0].parser.as_deref(), Some("gemini"));
+    assert!(errors[0].error.contains("Failed to parse Gemini session"));
+
+    std::fs::write(&source, r#"{"sessionId":"g1","messages":[]}"#).expect("write fixed log");
+    parse_session(&source, &db).expect("fixed log parses");
+    assert!(
+        db.list_parse_errors()
+            .expect("list parse errors")
+            .is_empty()
+    );
+}
+
 #[tokio::test]
 async fn test_auto_summary_runs_on_session_save_and_persists_row() {
     let tmp = tempdir().expect("tempdir");
     let db_path = PathBuf::from(tmp.path()).join("local.db");
-    let db = LocalDb::open_path(&db_path).expect("open local db");
+    let db = AsyncLocalDb::from(LocalDb::open_path(&db_path).expect("open local db"));
 
     let session = make_interaction_fixture_session("summary-auto");
     let mut config = DaemonConfig::default();
@@ -659,6 +746,7 @@ async fn test_auto_summary_runs_on_session_save_and_persists_row() {
         .expect("summary generation should not fail hard");
 
     let row = db
+        .blocking()
         .get_session_semantic_summary("summary-auto")
         .expect("query summary")
         .expect("summary row should exist");
@@ -671,7 +759,7 @@ async fn test_auto_summary_runs_on_session_save_and_persists_row() {
 async fn test_auto_summary_skips_when_trigger_mode_is_manual() {
     let tmp = tempdir().expect("tempdir");
     let db_path = PathBuf::from(tmp.path()).join("local.db");
-    let db = LocalDb::open_path(&db_path).expect("open local db");
+    let db = AsyncLocalDb::from(LocalDb::open_path(&db_path).expect("open local db"));
 
     let session = make_interaction_fixture_session("summary-manual");
     let mut config = DaemonConfig::default();
@@ -684,6 +772,7 @@ async fn test_auto_summary_skips_when_trigger_mode_is_manual() {
         .expect("manual trigger should no-op");
 
     let row = db
+        .blocking()
         .get_session_semantic_summary("summary-manual")
         .expect("query summary");
     assert!(row.is_none());
@@ -693,7 +782,7 @@ async fn test_auto_summary_skips_when_trigger_mode_is_manual() {
 async fn test_auto_summary_skips_when_storage_backend_is_none() {
     let tmp = tempdir().expect("tempdir");
     let db_path = PathBuf::from(tmp.path()).join("local.db");
-    let db = LocalDb::open_path(&db_path).expect("open local db");
+    let db = AsyncLocalDb::from(LocalDb::open_path(&db_path).expect("open local db"));
 
     let session = make_interaction_fixture_session("summary-no-persist");
     let mut config = DaemonConfig::default();
@@ -706,6 +795,7 @@ async fn test_auto_summary_skips_when_storage_backend_is_none() {
         .expect("none persist should no-op");
 
     let row = db
+        .blocking()
         .get_session_semantic_summary("summary-no-persist")
         .expect("query summary");
     assert!(row.is_none());
@@ -0,0 +1,262 @@
+//! Install the daemon as a per-user service (systemd on Linux, launchd on macOS).
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const SYSTEMD_UNIT_NAME: &str = "opensession-daemon.service";
+const LAUNCHD_LABEL: &str = "io.opensession.daemon";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ServiceManager {
+    Systemd,
+    Launchd,
+}
+
+impl ServiceManager {
+    fn detect() -> Result<Self> {
+        if cfg!(target_os = "macos") {
+            Ok(Self::Launchd)
+        } else if cfg!(target_os = "linux") {
+            Ok(Self::Systemd)
+        } else {
+            bail!("service installation supports systemd (Linux) and launchd (macOS) only")
+        }
+    }
+
+    /// systemd reads user units from `$XDG_CONFIG_HOME/systemd/user`, which
+    /// defaults to `~/.config`; relative values are ignored per the XDG spec.
+    fn unit_path(self, home: &Path, xdg_config_home: Option<&Path>) -> PathBuf {
+        match self {
+            Self::Systemd => xdg_config_home
+                .filter(|dir| dir.is_absolute())
+                .map_or_else(|| home.join(".config"), Path::to_path_buf)
+                .join("systemd/user")
+                .join(SYSTEMD_UNIT_NAME),
+            Self::Launchd => home
+                .join("Library/LaunchAgents")
+                .join(format!("{LAUNCHD_LABEL}.plist")),
+        }
+    }
+
+    fn resolve_unit_path(self) -> Result<PathBuf> {
+        let home = opensession_paths::home_dir().context("resolve home directory")?;
+        let xdg_config_home = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from);
+        Ok(self.unit_path(&home, xdg_config_home.as_deref()))
+    }
+
+    fn render(self, exe: &Path, log_dir: &Path) -> String {
+        match self {
+            Self::Systemd => systemd_unit(exe),
+            Self::Launchd => launchd_plist(exe, log_dir),
+        }
+    }
+
+    fn enable_commands(self, unit: &Path) -> Vec<Vec<String>> {
+        match self {
+            Self::Systemd => vec![
+                args(&["systemctl", "--user", "daemon-reload"]),
+                args(&["systemctl", "--user", "enable", "--now", SYSTEMD_UNIT_NAME]),
+            ],
+            Self::Launchd => vec![args(&[
+                "launchctl",
+                "load",
+                "-w",
+                &unit.display().to_string(),
+            ])],
+        }
+    }
+
+    fn disable_commands(self, unit: &Path) -> Vec<Vec<String>> {
+        match self {
+            Self::Systemd => vec![args(&[
+                "systemctl",
+                "--user",
+                "disable",
+                "--now",
+                SYSTEMD_UNIT_NAME,
+            ])],
+            Self::Launchd => vec![args(&[
+                "launchctl",
+                "unload",
+                "-w",
+                &unit.display().to_string(),
+            ])],
+        }
+    }
+}
+
+fn args(parts: &[&str]) -> Vec<String> {
+    parts.iter().map(|part| part.to_string()).collect()
+}
+
+/// systemd restarts on failure but gives up after 5 crashes in 10 minutes;
+/// the daemon's own crash-loop backoff spaces out the attempts in between.
+fn systemd_unit(exe: &Path) -> String {
+    format!(
+        "[Unit]
+Description=OpenSession daemon (session capture and upload)
+After=network-online.target
+StartLimitIntervalSec=600
+StartLimitBurst=5
+
+[Service]
+Type=simple
+ExecStart=\"{}\" run
+Restart=on-failure
+RestartSec=5
+
+[Install]
+WantedBy=default.target
+",
+        exe.display().to_string().replace('"', "\\\"")
+    )
+}
+
+/// launchd relaunches only after a non-zero exit and at most every 30 seconds.
+fn launchd_plist(exe: &Path, log_dir: &Path) -> String {
+    let log = |name: &str| xml_escape(&log_dir.join(name).display().to_string());
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key>
+  <string>{LAUNCHD_LABEL}</string>
+  <key>ProgramArguments</key>
+  <array>
+    <string>{}</string>
+    <string>run</string>
+  </array>
+  <key>RunAtLoad</key>
+  <true/>
+  <key>KeepAlive</key>
+  <dict>
+    <key>SuccessfulExit</key>
+    <false/>
+  </dict>
+  <key>ThrottleInterval</key>
+  <integer>30</integer>
+  <key>StandardOutPath</key>
+  <string>{}</string>
+  <key>StandardErrorPath</key>
+  <string>{}</string>
+</dict>
+</plist>
+"#,
+        xml_escape(&exe.display().to_string()),
+        log("daemon.out.log"),
+        log("daemon.err.log"),
+    )
+}
+
+fn xml_escape(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn run_commands(commands: &[Vec<String>]) -> Result<()> {
+    for command in commands {
+        let status = Command::new(&command[0])
+            .args(&command[1..])
+            .status()
+            .with_context(|| format!("run `{}`", command.join(" ")))?;
+        if !status.success() {
+            bail!("`{}` exited with {status}", command.join(" "));
+        }
+    }
+    Ok(())
+}
+
+/// Write the service definition for this executable and enable it.
+pub(crate) fn install(dry_run: bool) -> Result<()> {
+    let manager = ServiceManager::detect()?;
+    let exe = std::env::current_exe().context("resolve daemon executable path")?;
+    let unit_path = manager.resolve_unit_path()?;
+    let body = manager.render(&exe, &crate::config::config_dir()?);
+    let commands = manager.enable_commands(&unit_path);
+
+    if dry_run {
+        println!("# {}", unit_path.display());
+        print!("{body}");
+        for command in &commands {
+            println!("$ {}", command.join(" "));
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = unit_path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+    }
+    std::fs::write(&unit_path, body).with_context(|| format!("write {}", unit_path.display()))?;
+    println!("wrote {}", unit_path.display());
+    run_commands(&commands)?;
+    println!("opensession-daemon is installed and running as a user service");
+    Ok(())
+}
+
+/// Stop and disable the service, then remove its definition.
+pub(crate) fn uninstall() -> Result<()> {
+    let manager = ServiceManager::detect()?;
+    let unit_path = manager.resolve_unit_path()?;
+    if !unit_path.exists() {
+        println!("no service installed at {}", unit_path.display());
+        return Ok(());
+    }
+
+    run_commands(&manager.disable_commands(&unit_path))?;
+    std::fs::remove_file(&unit_path).with_context(|| format!("remove {}", unit_path.display()))?;
+    if manager == ServiceManager::Systemd {
+        run_commands(&[args(&["systemctl", "--user", "daemon-reload"])])?;
+    }
+    println!("removed {}", unit_path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn systemd_unit_restarts_on_failure_with_start_limit() {
+        let unit = systemd_unit(Path::new("/opt/open session/opensession-daemon"));
+        assert!(unit.contains("ExecStart=\"/opt/open session/opensession-daemon\" run"));
+        assert!(unit.contains("Restart=on-failure"));
+        assert!(unit.contains("StartLimitBurst=5"));
+        assert!(unit.contains("WantedBy=default.target"));
+    }
+
+    #[test]
+    fn launchd_plist_escapes_paths_and_keeps_alive_on_crash() {
+        let plist = launchd_plist(
+            Path::new("/Users/a&b/bin/opensession-daemon"),
+            Path::new("/Users/a&b/.config/opensession"),
+        );
+        assert!(plist.contains("<string>/Users/a&amp;b/bin/opensession-daemon</string>"));
+        assert!(plist.contains("<key>SuccessfulExit</key>\n    <false/>"));
+        assert!(plist.contains("/Users/a&amp;b/.config/opensession/daemon.err.log"));
+    }
+
+    #[test]
+    fn unit_paths_follow_platform_conventions() {
+        let home = Path::new("/home/u");
+        assert_eq!(
+            ServiceManager::Systemd.unit_path(home, None),
+            PathBuf::from("/home/u/.config/systemd/user/opensession-daemon.service")
+        );
+        assert_eq!(
+            ServiceManager::Systemd.unit_path(home, Some(Path::new("/xdg/config"))),
+            PathBuf::from("/xdg/config/systemd/user/opensession-daemon.service")
+        );
+        assert_eq!(
+            ServiceManager::Systemd.unit_path(home, Some(Path::new("relative"))),
+            PathBuf::from("/home/u/.config/systemd/user/opensession-daemon.service")
+        );
+        assert_eq!(
+            ServiceManager::Launchd.unit_path(home, Some(Path::new("/xdg/config"))),
+            PathBuf::from("/home/u/Library/LaunchAgents/io.opensession.daemon.plist")
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use opensession_core::Session;
+use opensession_runtime_config::{
+    ActiveSessionStatus, BodyCacheStatus, DAEMON_STATUS_HEARTBEAT_SECS, DaemonStatus, SyncState,
+};
+use std::path::PathBuf;
+use tracing::{debug, warn};
+
+/// What happened to a processed session file.
+pub(crate) enum ProcessOutcome {
+    /// Nothing was indexed (already uploaded, no parser, excluded tool).
+    Skipped,
+    /// Indexed but kept local by publish mode, policy or a hook veto.
+    LocalOnly(ActiveSessionStatus),
+    /// Indexed and marked share-ready.
+    Synced(ActiveSessionStatus),
+}
+
+pub(crate) fn active_session_status(session: &Session) -> ActiveSessionStatus {
+    let last_event_at = session
+        .events
+        .iter()
+        .map(|event| event.timestamp)
+        .max()
+        .unwrap_or(session.context.updated_at);
+    ActiveSessionStatus {
+        session_id: session.session_id.clone(),
+        tool: session.agent.tool.clone(),
+        model: Some(session.agent.model.clone()).filter(|model| !model.is_empty()),
+        started_at: session.context.created_at.timestamp(),
+        last_event_at: last_event_at.timestamp(),
+        input_tokens: session.stats.total_input_tokens,
+        output_tokens: session.stats.total_output_tokens,
+    }
+}
+
+/// Keeps `daemon.status.json` current: rewritten on change and at least every
+/// heartbeat so readers can tell a running daemon from a stale file.
+pub(crate) struct StatusFile {
+    path: Option<PathBuf>,
+    status: DaemonStatus,
+    dirty: bool,
+}
+
+impl StatusFile {
+    pub(crate) fn open() -> Self {
+        let path = match opensession_paths::daemon_status_path() {
+            Ok(path) => Some(path),
+            Err(error) => {
+                warn!("daemon status file disabled: {error}");
+                None
+            }
+        };
+        Self {
+            path,
+            status: DaemonStatus {
+                pid: std::process::id(),
+                ..DaemonStatus::default()
+            },
+            dirty: true,
+        }
+    }
+
+    pub(crate) fn set_pending(&mut self, pending: usize) {
+        let pending = u32::try_from(pending).unwrap_or(u32::MAX);
+        if self.status.pending_files != pending {
+            self.status.pending_files = pending;
+            self.dirty = true;
+        }
+    }
+
+    pub(crate) fn set_body_cache(&mut self, body_cache: Option<BodyCacheStatus>) {
+        if self.status.body_cache != body_cache {
+            self.status.body_cache = body_cache;
+            self.dirty = true;
+        }
+    }
+
+    pub(crate) fn record(&mut self, outcome: ProcessOutcome, now: i64) {
+        let (active, sync) = match outcome {
+            ProcessOutcome::Skipped => return,
+            ProcessOutcome::LocalOnly(active) => (active, SyncState::LocalOnly),
+            ProcessOutcome::Synced(active) => {
+                self.status.last_synced_at = Some(now);
+                (active, SyncState::Synced)
+            }
+        };
+        self.status.active_session = Some(active);
+        self.status.sync = sync;
+        self.status.last_error = None;
+        self.dirty = true;
+    }
+
+    pub(crate) fn record_error(&mut self, error: &anyhow::Error) {
+        self.status.sync = SyncState::Error;
+        self.status.last_error = Some(format!("{error:#}"));
+        self.dirty = true;
+    }
+
+    pub(crate) fn flush(&mut self, now: i64) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if !self.dirty && now - self.status.updated_at < DAEMON_STATUS_HEARTBEAT_SECS {
+            return;
+        }
+        self.status.updated_at = now;
+        match self.status.write(path) {
+            Ok(()) => self.dirty = false,
+            Err(error) => debug!("failed to write {}: {error}", path.display()),
+        }
+    }
+
+    pub(crate) fn remove(&self) {
+        if let Some(path) = &self.path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Print the status snapshot for `opensession-daemon status`.
+pub(crate) fn print_status() -> anyhow::Result<()> {
+    let path = opensession_paths::daemon_status_path()?;
+    let status = DaemonStatus::read(&path);
+    print!(
+        "{}",
+        render_status(status.as_ref(), chrono::Utc::now().timestamp())
+    );
+    Ok(())
+}
+
+fn render_status(status: Option<&DaemonStatus>, now: i64) -> String {
+    let Some(status) = status.filter(|status| status.is_fresh(now)) else {
+        return "daemon: not running\n".to_string();
+    };
+    let mut out = format!(
+        "daemon: running (pid {}, updated {}s ago)\n",
+        status.pid,
+        (now - status.updated_at).max(0)
+    );
+    let sync = match status.sync {
+        SyncState::Idle => "idle",
+        SyncState::Synced => "synced",
+        SyncState::LocalOnly => "local only",
+        SyncState::Error => "error",
+    };
+    out.push_str(&format!(
+        "sync: {sync}, {} file(s) pending\n",
+        status.pending_files
+    ));
+    if let Some(error) = &status.last_error {
+        out.push_str(&format!("last error: {error}\n"));
+    }
+    match &status.body_cache {
+        Some(cache) => {
+            let hit_rate = (cache.hits * 100)
+                .checked_div(cache.hits + cache.misses)
+                .map(|percent| format!(" ({percent}% hit rate)"))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "body cache: {} bodies, {} of {}; prefetch {} hit(s), {} miss(es){hit_rate}\n",
+                cache.entries,
+                format_mb(cache.bytes),
+                format_mb(cache.max_bytes),
+                cache.hits,
+                cache.misses,
+            ));
+        }
+        None => out.push_str("body cache: prefetch off\n"),
+    }
+    out
+}
+
+fn format_mb(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active(id: &str) -> ActiveSessionStatus {
+        ActiveSessionStatus {
+            session_id: id.into(),
+            tool: "codex".into(),
+            model: None,
+            started_at: 0,
+            last_event_at: 10,
+            input_tokens: 1,
+            output_tokens: 2,
+        }
+    }
+
+    #[test]
+    fn status_tracks_the_latest_outcome() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("daemon.status.json");
+        let mut file = StatusFile {
+            path: Some(path.clone()),
+            status: DaemonStatus::default(),
+            dirty: false,
+        };
+
+        file.record(ProcessOutcome::Synced(active("s1")), 100);
+        file.set_pending(2);
+        file.flush(100);
+        let written = DaemonStatus::read(&path).expect("status written");
+        assert_eq!(written.sync, SyncState::Synced);
+        assert_eq!(written.last_synced_at, Some(100));
+        assert_eq!(written.pending_files, 2);
+        assert_eq!(written.active_session, Some(active("s1")));
+
+        file.record(ProcessOutcome::Skipped, 101);
+        file.record_error(&anyhow::anyhow!("disk full"));
+        file.flush(101);
+        let written = DaemonStatus::read(&path).expect("status written");
+        assert_eq!(written.sync, SyncState::Error);
+        assert_eq!(written.last_error.as_deref(), Some("disk full"));
+        assert_eq!(written.active_session, Some(active("s1")));
+
+        file.remove();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn status_reports_body_cache_hit_rate() {
+        let mut status = DaemonStatus {
+            pid: 7,
+            updated_at: 100,
+            sync: SyncState::Synced,
+            ..DaemonStatus::default()
+        };
+        assert_eq!(render_status(Some(&status), 1_000), "daemon: not running\n");
+        assert!(render_status(Some(&status), 110).ends_with("body cache: prefetch off\n"));
+
+        status.body_cache = Some(BodyCacheStatus {
+            entries: 4,
+            bytes: 3 * 1024 * 1024,
+            max_bytes: 256 * 1024 * 1024,
+            hits: 3,
+            misses: 1,
+            last_prefetch_at: Some(90),
+        });
+        assert_eq!(
+            render_status(Some(&status), 110),
+            "daemon: running (pid 7, updated 10s ago)\n\
+             sync: synced, 0 file(s) pending\n\
+             body cache: 4 bodies, 3.0 MB of 256.0 MB; prefetch 3 hit(s), 1 miss(es) (75% hit rate)\n"
+        );
+    }
+}
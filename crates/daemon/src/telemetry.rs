@@ -0,0 +1,195 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use opensession_api::TelemetryReport;
+use opensession_api_client::ApiClient;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::{debug, info};
+
+use crate::config::DaemonConfig;
+use crate::status::ProcessOutcome;
+
+/// How often counters are sent when `privacy.telemetry` is on.
+const REPORT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Pipeline step a session failed in, attached to the error as context so
+/// failures can be counted without inspecting messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FailedStep {
+    Parse,
+    Publish,
+}
+
+impl std::fmt::Display for FailedStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Parse => "failed to parse session",
+            Self::Publish => "failed to publish session",
+        })
+    }
+}
+
+/// Anonymous usage counters, filled by the scheduler and drained by
+/// [`run_telemetry_reporter`].
+#[derive(Debug, Default)]
+pub(crate) struct TelemetryCounters {
+    sessions_indexed: AtomicU64,
+    parse_failures: AtomicU64,
+    upload_errors: AtomicU64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Counts {
+    sessions_indexed: u64,
+    parse_failures: u64,
+    upload_errors: u64,
+}
+
+impl TelemetryCounters {
+    pub(crate) fn record(&self, result: &anyhow::Result<ProcessOutcome>) {
+        let counter = match result {
+            Ok(ProcessOutcome::Skipped) => return,
+            Ok(ProcessOutcome::LocalOnly(_) | ProcessOutcome::Synced(_)) => &self.sessions_indexed,
+            Err(error) => match error.downcast_ref::<FailedStep>() {
+                Some(FailedStep::Parse) => &self.parse_failures,
+                Some(FailedStep::Publish) => &self.upload_errors,
+                None => return,
+            },
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn take(&self) -> Counts {
+        Counts {
+            sessions_indexed: self.sessions_indexed.swap(0, Ordering::Relaxed),
+            parse_failures: self.parse_failures.swap(0, Ordering::Relaxed),
+            upload_errors: self.upload_errors.swap(0, Ordering::Relaxed),
+        }
+    }
+
+    /// Put back counts whose report could not be delivered.
+    fn restore(&self, counts: Counts) {
+        self.sessions_indexed
+            .fetch_add(counts.sessions_indexed, Ordering::Relaxed);
+        self.parse_failures
+            .fetch_add(counts.parse_failures, Ordering::Relaxed);
+        self.upload_errors
+            .fetch_add(counts.upload_errors, Ordering::Relaxed);
+    }
+}
+
+/// Send counters to the configured server's `/api/telemetry` every
+/// [`REPORT_INTERVAL`] while `privacy.telemetry` is enabled. Counts gathered
+/// while it is off are discarded rather than sent later.
+pub async fn run_telemetry_reporter(
+    mut config_rx: watch::Receiver<DaemonConfig>,
+    counters: Arc<TelemetryCounters>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut interval = tokio::time::interval(REPORT_INTERVAL);
+    // Skip the first immediate tick
+    interval.tick().await;
+    let mut period_start = Utc::now();
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let config = config_rx.borrow_and_update().clone();
+                let counts = counters.take();
+                let period_end = Utc::now();
+                if !config.privacy.telemetry {
+                    period_start = period_end;
+                    continue;
+                }
+                match send_report(&config, counts, period_start, period_end).await {
+                    Ok(()) => period_start = period_end,
+                    Err(error) => {
+                        debug!("Telemetry report not sent: {error}");
+                        counters.restore(counts);
+                    }
+                }
+            }
+            _ = shutdown.changed() => {
+                if *shutdown.borrow() {
+                    debug!("Telemetry reporter shutting down");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+async fn send_report(
+    config: &DaemonConfig,
+    counts: Counts,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    if config.server.url.trim().is_empty() || config.server.api_key.trim().is_empty() {
+        anyhow::bail!("server.url and server.api_key are required");
+    }
+    let mut api = ApiClient::new(&config.server.url, Duration::from_secs(10))?;
+    api.set_auth(config.server.api_key.clone());
+    api.report_telemetry(&build_report(counts, period_start, period_end))
+        .await?;
+    info!(
+        "Reported telemetry: {} indexed, {} parse failures, {} upload errors",
+        counts.sessions_indexed, counts.parse_failures, counts.upload_errors
+    );
+    Ok(())
+}
+
+fn build_report(
+    counts: Counts,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+) -> TelemetryReport {
+    TelemetryReport {
+        daemon_version: env!("CARGO_PKG_VERSION").to_string(),
+        period_start: period_start.to_rfc3339_opts(SecondsFormat::Secs, true),
+        period_end: period_end.to_rfc3339_opts(SecondsFormat::Secs, true),
+        sessions_indexed: counts.sessions_indexed,
+        parse_failures: counts.parse_failures,
+        upload_errors: counts.upload_errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn counters_classify_outcomes_and_failed_steps() {
+        let counters = TelemetryCounters::default();
+        counters.record(&Ok(ProcessOutcome::Skipped));
+        counters.record(
+            &Err::<ProcessOutcome, _>(anyhow::anyhow!("bad json")).context(FailedStep::Parse),
+        );
+        counters.record(
+            &Err::<ProcessOutcome, _>(anyhow::anyhow!("hook crashed")).context(FailedStep::Publish),
+        );
+        counters.record(&Err(anyhow::anyhow!("disk full")));
+
+        let counts = counters.take();
+        assert_eq!(
+            counts,
+            Counts {
+                sessions_indexed: 0,
+                parse_failures: 1,
+                upload_errors: 1,
+            }
+        );
+        assert_eq!(counters.take(), Counts::default());
+
+        counters.restore(counts);
+        let report = build_report(
+            counters.take(),
+            "2026-10-18T00:00:00Z".parse().expect("start"),
+            "2026-10-18T01:00:00Z".parse().expect("end"),
+        );
+        assert_eq!(report.parse_failures, 1);
+        assert_eq!(report.period_end, "2026-10-18T01:00:00Z");
+    }
+}
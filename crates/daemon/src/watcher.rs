@@ -1,11 +1,19 @@
 use anyhow::{Context, Result};
-use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use opensession_parser_discovery::discover_sessions;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
+/// How often watch roots are rescanned for changes notify did not report.
+pub const RESCAN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Directory depth walked when a new directory appears or during rescans.
+const MAX_WALK_DEPTH: usize = 12;
+
 /// A file change event emitted by the watcher
 #[derive(Debug, Clone)]
 pub struct FileChangeEvent {
@@ -36,30 +44,19 @@ pub fn start_watcher(
 ) -> Result<RecommendedWatcher> {
     let tx_clone = tx.clone();
 
-    let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
-        match res {
+    let mut watcher =
+        notify::recommended_watcher(move |res: Result<Event, notify::Error>| match res {
             Ok(event) => {
-                let should_emit = matches!(
-                    event.kind,
-                    notify::EventKind::Create(_) | notify::EventKind::Modify(_)
-                );
-
-                if should_emit {
-                    for path in event.paths {
-                        // Only care about session-like files
-                        if is_session_file(&path) {
-                            debug!("File change detected: {}", path.display());
-                            let _ = tx_clone.send(FileChangeEvent { path });
-                        }
-                    }
+                for path in paths_to_enqueue(&event) {
+                    debug!("File change detected: {}", path.display());
+                    let _ = tx_clone.send(FileChangeEvent { path });
                 }
             }
             Err(e) => {
                 error!("Watcher error: {}", e);
             }
-        }
-    })
-    .context("Failed to create file watcher")?;
+        })
+        .context("Failed to create file watcher")?;
 
     for path in paths {
         info!("Watching directory: {}", path.display());
@@ -71,6 +68,110 @@ pub fn start_watcher(
     Ok(watcher)
 }
 
+/// Session files affected by a notify event.
+///
+/// Renames are how atomic writers publish a finished file, so the rename
+/// target counts as a change while the vanished source is skipped. A new or
+/// renamed-in directory is walked, because files written into it before notify
+/// attached a watch produce no events of their own.
+fn paths_to_enqueue(event: &Event) -> Vec<PathBuf> {
+    let renamed = matches!(event.kind, EventKind::Modify(ModifyKind::Name(_)));
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+        return Vec::new();
+    }
+
+    let mut paths = Vec::new();
+    for path in &event.paths {
+        if path.is_dir() {
+            if renamed || matches!(event.kind, EventKind::Create(_)) {
+                collect_session_files(path, 0, &mut paths);
+            }
+        } else if is_session_file(path) && (!renamed || path.is_file()) {
+            paths.push(path.clone());
+        }
+    }
+    paths
+}
+
+/// Recursively collect session files below `dir`, without following symlinks.
+fn collect_session_files(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    if depth > MAX_WALK_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_session_files(&path, depth + 1, out);
+        } else if file_type.is_file() && is_session_file(&path) {
+            out.push(path);
+        }
+    }
+}
+
+/// Size and modification time of every session file under the watch roots.
+///
+/// The periodic rescan diffs against this to catch writes notify dropped, e.g.
+/// on network filesystems or after the kernel event queue overflowed.
+#[derive(Debug, Default)]
+pub struct RescanSnapshot {
+    files: HashMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl RescanSnapshot {
+    /// Record the current state of `roots` without reporting anything.
+    pub fn capture(roots: &[PathBuf]) -> Self {
+        let mut snapshot = Self::default();
+        snapshot.diff(roots);
+        snapshot
+    }
+
+    /// Rescan `roots` and enqueue files that are new or changed since the last scan.
+    pub fn rescan(
+        &mut self,
+        roots: &[PathBuf],
+        tx: &mpsc::UnboundedSender<FileChangeEvent>,
+    ) -> usize {
+        let changed = self.diff(roots);
+        let queued = changed.len();
+        for path in changed {
+            debug!("Rescan found change: {}", path.display());
+            let _ = tx.send(FileChangeEvent { path });
+        }
+        queued
+    }
+
+    fn diff(&mut self, roots: &[PathBuf]) -> Vec<PathBuf> {
+        let mut found = Vec::new();
+        for root in roots {
+            collect_session_files(root, 0, &mut found);
+        }
+
+        let mut next = HashMap::with_capacity(found.len());
+        let mut changed = Vec::new();
+        for path in found {
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            let stamp = (
+                metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                metadata.len(),
+            );
+            if self.files.get(&path) != Some(&stamp) {
+                changed.push(path.clone());
+            }
+            next.insert(path, stamp);
+        }
+        self.files = next;
+        changed
+    }
+}
+
 /// Check if a file looks like a session file we care about
 fn is_session_file(path: &Path) -> bool {
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
@@ -146,6 +247,67 @@ mod tests {
         assert!(observed.contains(&inside_b));
     }
 
+    #[test]
+    fn rename_events_enqueue_only_the_target() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let target = temp.path().join("session.jsonl");
+        std::fs::write(&target, "{}\n").expect("write target");
+        let source = temp.path().join("session.jsonl.tmp");
+
+        let event = Event::new(EventKind::Modify(ModifyKind::Name(
+            notify::event::RenameMode::Both,
+        )))
+        .add_path(source)
+        .add_path(target.clone());
+        assert_eq!(paths_to_enqueue(&event), vec![target.clone()]);
+
+        let vanished = Event::new(EventKind::Modify(ModifyKind::Name(
+            notify::event::RenameMode::From,
+        )))
+        .add_path(temp.path().join("gone.jsonl"));
+        assert!(paths_to_enqueue(&vanished).is_empty());
+    }
+
+    #[test]
+    fn new_directories_are_walked_for_existing_sessions() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let project = temp.path().join("project");
+        let nested = project.join("nested").join("chat.json");
+        std::fs::create_dir_all(nested.parent().expect("parent")).expect("mkdir");
+        std::fs::write(&nested, "{}").expect("write nested");
+        std::fs::write(project.join("notes.txt"), "").expect("write notes");
+
+        let event =
+            Event::new(EventKind::Create(notify::event::CreateKind::Folder)).add_path(project);
+        assert_eq!(paths_to_enqueue(&event), vec![nested]);
+    }
+
+    #[test]
+    fn rescan_reports_only_new_or_changed_files() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let root = temp.path().to_path_buf();
+        let existing = root.join("a.jsonl");
+        std::fs::write(&existing, "{}\n").expect("write existing");
+
+        let mut snapshot = RescanSnapshot::capture(std::slice::from_ref(&root));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        assert_eq!(snapshot.rescan(std::slice::from_ref(&root), &tx), 0);
+
+        let added = root.join("later").join("b.jsonl");
+        std::fs::create_dir_all(added.parent().expect("parent")).expect("mkdir");
+        std::fs::write(&added, "{}\n").expect("write added");
+        std::fs::write(&existing, "{}\n{}\n").expect("grow existing");
+
+        assert_eq!(snapshot.rescan(std::slice::from_ref(&root), &tx), 2);
+        let mut observed = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            observed.push(event.path);
+        }
+        observed.sort();
+        assert_eq!(observed, vec![existing, added]);
+        assert_eq!(snapshot.rescan(&[root], &tx), 0);
+    }
+
     #[test]
     fn is_path_under_any_watch_root_returns_false_when_unrelated() {
         let root = PathBuf::from("/tmp/watch");
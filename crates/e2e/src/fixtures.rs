@@ -65,3 +65,17 @@ pub fn minimal_session_with_title(title: Option<&str>) -> Session {
     session.recompute_stats();
     session
 }
+
+/// Create a realistic session of `event_count` events (messages, tool calls
+/// and results) with roughly `payload_bytes` of text each, for exercising
+/// large raw bodies.
+pub fn large_session(event_count: usize, payload_bytes: usize) -> Session {
+    let mut session = opensession_core::testing::SessionGenerator::new(event_count as u64)
+        .events(event_count)
+        .text_bytes(payload_bytes)
+        .start(Utc::now())
+        .generate();
+    // Fresh id per call so repeated runs against one server do not collide.
+    session.session_id = Uuid::new_v4().to_string();
+    session
+}
@@ -14,9 +14,10 @@ macro_rules! for_each_spec {
         // health (1)
         $mac!(health::health_check);
 
-        // public sessions (3)
+        // public sessions (4)
         $mac!(sessions::list_sessions_public);
         $mac!(sessions::get_session_not_found_public);
         $mac!(sessions::get_session_raw_not_found_public);
+        $mac!(sessions::get_session_raw_delta_not_found_public);
     };
 }
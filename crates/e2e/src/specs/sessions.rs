@@ -31,3 +31,13 @@ pub async fn get_session_raw_not_found_public(ctx: &TestContext) -> Result<()> {
     ensure!(resp.status() == 404, "expected 404, got {}", resp.status());
     Ok(())
 }
+
+/// `since_event` must not bypass the missing-session check on the raw endpoint.
+pub async fn get_session_raw_delta_not_found_public(ctx: &TestContext) -> Result<()> {
+    let id = Uuid::new_v4().to_string();
+    let resp = ctx
+        .get(&format!("/sessions/{id}/raw?since_event=1"))
+        .await?;
+    ensure!(resp.status() == 404, "expected 404, got {}", resp.status());
+    Ok(())
+}
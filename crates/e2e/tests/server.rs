@@ -1,9 +1,17 @@
 mod common;
 
 use common::{register_user, test_context_from_env};
-use opensession_api::{ChangePasswordRequest, CreateGitCredentialRequest, OkResponse};
+use opensession_api::{
+    AccountExportRecord, BatchUploadRequest, BatchUploadResponse, ChangePasswordRequest,
+    CreateGitCredentialRequest, CreateReviewRequest, OkResponse, PlaybookKind, PutPlaybookRequest,
+    QueryOperation, QueryRequest, QueryResponse, QueryResult, ResolveReviewRequest,
+    ReviewRequestListQuery, ReviewRequestStatus, SESSION_DELTA_HEADER, TelemetryReport,
+    UploadRequest,
+};
+use opensession_api_client::{ApiClient, ApiClientError, SessionQuery};
 use opensession_e2e::client::TestContext;
 use serde_json::json;
+use std::time::Duration;
 
 fn get_ctx() -> Option<TestContext> {
     test_context_from_env("OPENSESSION_E2E_SERVER_BASE_URL")
@@ -44,6 +52,509 @@ async fn server_sessions_repos_list() {
     );
 }
 
+#[tokio::test]
+async fn server_query_batches_reads_with_per_operation_errors() {
+    let Some(ctx) = get_ctx() else {
+        return;
+    };
+    let user = register_user(&ctx, "server-query", "test-pass-123").await;
+
+    let response = reqwest::Client::new()
+        .post(ctx.url("/query"))
+        .bearer_auth(&user.tokens.access_token)
+        .json(&QueryRequest {
+            operations: vec![
+                QueryOperation::SessionRepos,
+                QueryOperation::Session {
+                    id: "missing-session".to_string(),
+                },
+            ],
+        })
+        .send()
+        .await
+        .expect("query request failed");
+    assert_eq!(response.status().as_u16(), 200, "query must succeed");
+
+    let body: QueryResponse = response.json().await.expect("invalid query response");
+    assert_eq!(body.results.len(), 2, "one result per operation");
+    assert!(matches!(body.results[0], QueryResult::SessionRepos(_)));
+    match &body.results[1] {
+        QueryResult::Error(error) => assert_eq!(error.code, "not_found"),
+        other => panic!("missing session must report an error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn server_api_client_covers_documented_endpoints() {
+    let Some(ctx) = get_ctx() else {
+        return;
+    };
+    let user = register_user(&ctx, "server-sdk", "test-pass-123").await;
+
+    let mut client = ApiClient::new(ctx.base_url(), Duration::from_secs(30))
+        .expect("api client should construct");
+    client.health().await.expect("health");
+    client.capabilities().await.expect("capabilities");
+    client.auth_providers().await.expect("auth providers");
+    let openapi = client.openapi_document().await.expect("openapi document");
+    assert_eq!(openapi["openapi"], "3.1.0");
+    let schema = client.session_schema().await.expect("session schema");
+    assert!(schema.is_object(), "session schema must be a JSON object");
+    let validation = client
+        .validate_session("not json\n")
+        .await
+        .expect("validate");
+    assert!(!validation.valid, "garbage must not validate");
+
+    let query = SessionQuery::new().tool("codex").limit(5).build();
+    let page = client
+        .list_sessions(&query)
+        .await
+        .expect("anonymous list sessions");
+    assert!(page.sessions.len() <= 5);
+    client
+        .list_session_repos()
+        .await
+        .expect("anonymous list repos");
+    match client.stream_session_body("missing-session").await {
+        Err(ApiClientError::UnexpectedStatus { status, .. }) => assert_eq!(status.as_u16(), 404),
+        Err(other) => panic!("missing body must be 404, got {other}"),
+        Ok(_) => panic!("missing body must be 404"),
+    }
+
+    client.set_auth(user.tokens.access_token.clone());
+    client.me().await.expect("me");
+    client.verify().await.expect("verify");
+    client.list_devices().await.expect("list devices");
+    client
+        .list_git_credentials()
+        .await
+        .expect("list git credentials");
+    client.list_metadata().await.expect("list metadata");
+    client
+        .handoff_inbox(None, Some(5))
+        .await
+        .expect("handoff inbox");
+    client
+        .change_password(&ChangePasswordRequest {
+            current_password: user.password.clone(),
+            new_password: "test-pass-456".to_string(),
+        })
+        .await
+        .expect("change password");
+}
+
+#[tokio::test]
+async fn server_raw_body_large_round_trip() {
+    let Some(ctx) = get_ctx() else {
+        return;
+    };
+    let user = register_user(&ctx, "server-raw-large", "test-pass-123").await;
+    // ~2 MiB body: large enough to cross typical buffer and chunk sizes.
+    let session = opensession_e2e::fixtures::large_session(512, 4096);
+    let session_id = session.session_id.clone();
+    let event_count = session.events.len();
+
+    let client = reqwest::Client::new();
+    let upload_response = client
+        .post(ctx.url("/sessions/batch"))
+        .bearer_auth(&user.tokens.access_token)
+        .json(&BatchUploadRequest {
+            sessions: vec![UploadRequest {
+                session,
+                body_url: None,
+                linked_session_ids: None,
+                git_remote: None,
+                git_branch: None,
+                git_commit: None,
+                git_repo_name: None,
+                pr_number: None,
+                pr_url: None,
+                score_plugin: None,
+                force: true,
+                signature: None,
+            }],
+        })
+        .send()
+        .await
+        .expect("batch upload request failed");
+    assert_eq!(
+        upload_response.status().as_u16(),
+        200,
+        "batch upload must succeed"
+    );
+    let uploaded: BatchUploadResponse = upload_response
+        .json()
+        .await
+        .expect("invalid batch upload response");
+    assert!(
+        uploaded.results[0].upload.is_some(),
+        "large session must be stored: {:?}",
+        uploaded.results[0].error
+    );
+
+    let raw_response = ctx
+        .get(&format!("/sessions/{session_id}/raw"))
+        .await
+        .expect("raw request failed");
+    assert_eq!(
+        raw_response.status().as_u16(),
+        200,
+        "raw body must be served"
+    );
+    let etag = raw_response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .expect("raw body must carry an ETag")
+        .to_string();
+    let body = raw_response.text().await.expect("read raw body");
+    let downloaded =
+        opensession_core::jsonl::from_jsonl_str(&body).expect("raw body must be valid HAIL JSONL");
+    assert_eq!(downloaded.session_id, session_id);
+    assert_eq!(
+        downloaded.events.len(),
+        event_count,
+        "every event must survive the round trip"
+    );
+
+    let cached_response = client
+        .get(ctx.url(&format!("/sessions/{session_id}/raw")))
+        .header(reqwest::header::IF_NONE_MATCH, &etag)
+        .send()
+        .await
+        .expect("conditional raw request failed");
+    assert_eq!(
+        cached_response.status().as_u16(),
+        304,
+        "matching If-None-Match must short-circuit"
+    );
+
+    let since_event = event_count - 2;
+    let delta_response = ctx
+        .get(&format!(
+            "/sessions/{session_id}/raw?since_event={since_event}"
+        ))
+        .await
+        .expect("delta request failed");
+    assert_eq!(
+        delta_response.status().as_u16(),
+        200,
+        "delta must be served"
+    );
+    assert_eq!(
+        delta_response
+            .headers()
+            .get(SESSION_DELTA_HEADER)
+            .and_then(|value| value.to_str().ok()),
+        Some(since_event.to_string().as_str())
+    );
+    let delta = delta_response.text().await.expect("read delta body");
+    assert_eq!(
+        opensession_core::jsonl::count_events(&delta).expect("delta must be valid JSONL"),
+        2,
+        "delta must contain only the events after since_event"
+    );
+}
+
+#[tokio::test]
+async fn server_attachments_round_trip_for_session_owner() {
+    let Some(ctx) = get_ctx() else {
+        return;
+    };
+    let owner = register_user(&ctx, "server-attach-owner", "test-pass-123").await;
+    let other = register_user(&ctx, "server-attach-other", "test-pass-123").await;
+    let session = opensession_e2e::fixtures::minimal_session();
+    let session_id = session.session_id.clone();
+
+    let mut client = ApiClient::new(ctx.base_url(), Duration::from_secs(30))
+        .expect("api client should construct");
+    client.set_auth(owner.tokens.access_token.clone());
+    client
+        .upload_sessions_batch(&BatchUploadRequest {
+            sessions: vec![UploadRequest {
+                session,
+                body_url: None,
+                linked_session_ids: None,
+                git_remote: None,
+                git_branch: None,
+                git_commit: None,
+                git_repo_name: None,
+                pr_number: None,
+                pr_url: None,
+                score_plugin: None,
+                force: true,
+                signature: None,
+            }],
+        })
+        .await
+        .expect("batch upload");
+
+    let attachment = client
+        .upload_attachment(
+            &session_id,
+            "../shot.png",
+            "image/png",
+            b"png-bytes".to_vec(),
+        )
+        .await
+        .expect("owner upload");
+    assert_eq!(attachment.file_name, "shot.png");
+    assert_eq!(attachment.size_bytes, 9);
+
+    let listed = client
+        .list_attachments(&session_id)
+        .await
+        .expect("list attachments");
+    assert_eq!(listed.attachments, vec![attachment.clone()]);
+    let data = client
+        .download_attachment(&session_id, &attachment.id)
+        .await
+        .expect("download attachment");
+    assert_eq!(data, b"png-bytes");
+
+    client.set_auth(other.tokens.access_token.clone());
+    match client
+        .upload_attachment(&session_id, "x.txt", "text/plain", b"x".to_vec())
+        .await
+    {
+        Err(ApiClientError::UnexpectedStatus { status, .. }) => assert_eq!(status.as_u16(), 403),
+        Err(other) => panic!("non-owner upload must be 403, got {other}"),
+        Ok(_) => panic!("non-owner upload must be 403"),
+    }
+}
+
+#[tokio::test]
+async fn server_account_export_then_delete() {
+    let Some(ctx) = get_ctx() else {
+        return;
+    };
+    let user = register_user(&ctx, "server-gdpr", "test-pass-123").await;
+    let session = opensession_e2e::fixtures::minimal_session();
+    let session_id = session.session_id.clone();
+
+    let mut client = ApiClient::new(ctx.base_url(), Duration::from_secs(30))
+        .expect("api client should construct");
+    client.set_auth(user.tokens.access_token.clone());
+    client
+        .upload_sessions_batch(&BatchUploadRequest {
+            sessions: vec![UploadRequest {
+                session,
+                body_url: None,
+                linked_session_ids: None,
+                git_remote: None,
+                git_branch: None,
+                git_commit: None,
+                git_repo_name: None,
+                pr_number: None,
+                pr_url: None,
+                score_plugin: None,
+                force: true,
+                signature: None,
+            }],
+        })
+        .await
+        .expect("batch upload");
+    client
+        .upload_attachment(&session_id, "notes.txt", "text/plain", b"notes".to_vec())
+        .await
+        .expect("upload attachment");
+
+    let mut stream = client.export_account().await.expect("export account");
+    let mut export = Vec::new();
+    while let Some(chunk) = stream.next_chunk().await.expect("export chunk") {
+        export.extend(chunk);
+    }
+    let records = String::from_utf8(export)
+        .expect("utf-8 export")
+        .lines()
+        .map(|line| serde_json::from_str::<AccountExportRecord>(line).expect("export record"))
+        .collect::<Vec<_>>();
+    assert_eq!(records.len(), 3);
+    match &records[0] {
+        AccountExportRecord::Account { account, .. } => {
+            assert_eq!(account.user_id, user.tokens.user_id)
+        }
+        other => panic!("export must start with the account, got {other:?}"),
+    }
+    match &records[1] {
+        AccountExportRecord::Session { session, body } => {
+            assert_eq!(session.id, session_id);
+            assert!(!body.is_empty());
+        }
+        other => panic!("expected the session, got {other:?}"),
+    }
+    match &records[2] {
+        AccountExportRecord::Attachment {
+            attachment,
+            content_base64,
+        } => {
+            assert_eq!(attachment.file_name, "notes.txt");
+            assert_eq!(content_base64, "bm90ZXM=");
+        }
+        other => panic!("expected the attachment, got {other:?}"),
+    }
+
+    let deleted = client.delete_account().await.expect("delete account");
+    assert_eq!(deleted.sessions_deleted, 1);
+    assert_eq!(deleted.attachments_deleted, 1);
+
+    match client.get_session(&session_id).await {
+        Err(ApiClientError::UnexpectedStatus { status, .. }) => assert_eq!(status.as_u16(), 404),
+        other => panic!("deleted session must be gone, got {other:?}"),
+    }
+    assert!(
+        client.me().await.is_err(),
+        "deleted account must not authenticate"
+    );
+}
+
+#[tokio::test]
+async fn server_review_requests_flow_from_requester_to_reviewer() {
+    let Some(ctx) = get_ctx() else {
+        return;
+    };
+    let requester = register_user(&ctx, "server-review-requester", "test-pass-123").await;
+    let reviewer = register_user(&ctx, "server-review-reviewer", "test-pass-123").await;
+    let session = opensession_e2e::fixtures::minimal_session();
+    let session_id = session.session_id.clone();
+
+    let mut client = ApiClient::new(ctx.base_url(), Duration::from_secs(30))
+        .expect("api client should construct");
+    client.set_auth(reviewer.tokens.access_token.clone());
+    let reviewer_nickname = client.verify().await.expect("verify reviewer").nickname;
+
+    client.set_auth(requester.tokens.access_token.clone());
+    client
+        .upload_sessions_batch(&BatchUploadRequest {
+            sessions: vec![UploadRequest {
+                session,
+                body_url: None,
+                linked_session_ids: None,
+                git_remote: None,
+                git_branch: None,
+                git_commit: None,
+                git_repo_name: None,
+                pr_number: None,
+                pr_url: None,
+                score_plugin: None,
+                force: true,
+                signature: None,
+            }],
+        })
+        .await
+        .expect("batch upload");
+    let created = client
+        .request_review(
+            &session_id,
+            &CreateReviewRequest {
+                reviewer: format!("@{reviewer_nickname}"),
+                note: Some("is the retry loop safe?".to_string()),
+            },
+        )
+        .await
+        .expect("request review");
+    assert_eq!(created.status, ReviewRequestStatus::Pending);
+    assert_eq!(created.reviewer_nickname, reviewer_nickname);
+    match client
+        .resolve_review_request(
+            &created.id,
+            &ResolveReviewRequest {
+                status: ReviewRequestStatus::Approved,
+                comment: None,
+            },
+        )
+        .await
+    {
+        Err(ApiClientError::UnexpectedStatus { status, .. }) => assert_eq!(status.as_u16(), 404),
+        Err(other) => panic!("requester resolve must be 404, got {other}"),
+        Ok(_) => panic!("requester resolve must be 404"),
+    }
+
+    client.set_auth(reviewer.tokens.access_token.clone());
+    let assigned = client
+        .list_review_requests(&ReviewRequestListQuery {
+            status: Some(ReviewRequestStatus::Pending),
+            ..Default::default()
+        })
+        .await
+        .expect("assigned reviews");
+    assert_eq!(assigned.items.len(), 1);
+    assert_eq!(assigned.items[0].id, created.id);
+    let resolved = client
+        .resolve_review_request(
+            &created.id,
+            &ResolveReviewRequest {
+                status: ReviewRequestStatus::ChangesRequested,
+                comment: Some("add a backoff cap".to_string()),
+            },
+        )
+        .await
+        .expect("resolve review");
+    assert_eq!(resolved.status, ReviewRequestStatus::ChangesRequested);
+    assert_eq!(resolved.comment.as_deref(), Some("add a backoff cap"));
+}
+
+#[tokio::test]
+async fn server_playbooks_are_shared_but_author_owned() {
+    let Some(ctx) = get_ctx() else {
+        return;
+    };
+    let author = register_user(&ctx, "server-playbook-author", "test-pass-123").await;
+    let other = register_user(&ctx, "server-playbook-other", "test-pass-123").await;
+    let name = format!("triage-{}", uuid::Uuid::new_v4().simple());
+
+    let mut client = ApiClient::new(ctx.base_url(), Duration::from_secs(30))
+        .expect("api client should construct");
+    client.set_auth(author.tokens.access_token.clone());
+    let request = PutPlaybookRequest {
+        kind: PlaybookKind::Prompt,
+        title: Some("  Bug triage ".to_string()),
+        description: None,
+        body: "Triage {{issue}}".to_string(),
+    };
+    let created = client
+        .put_playbook(&name, &request)
+        .await
+        .expect("create playbook");
+    assert_eq!(created.title.as_deref(), Some("Bug triage"));
+    assert!(
+        created
+            .author_nickname
+            .as_deref()
+            .is_some_and(|nickname| nickname.starts_with("server-playbook-author"))
+    );
+
+    client.set_auth(other.tokens.access_token.clone());
+    let fetched = client.get_playbook(&name).await.expect("get playbook");
+    assert_eq!(fetched.body, "Triage {{issue}}");
+    assert!(
+        client
+            .list_playbooks()
+            .await
+            .expect("list playbooks")
+            .playbooks
+            .iter()
+            .any(|playbook| playbook.name == name)
+    );
+    match client.put_playbook(&name, &request).await {
+        Err(ApiClientError::UnexpectedStatus { status, .. }) => assert_eq!(status.as_u16(), 403),
+        Err(other) => panic!("non-author replace must be 403, got {other}"),
+        Ok(_) => panic!("non-author replace must be 403"),
+    }
+
+    client.set_auth(author.tokens.access_token.clone());
+    client
+        .delete_playbook(&name)
+        .await
+        .expect("delete playbook");
+    match client.get_playbook(&name).await {
+        Err(ApiClientError::UnexpectedStatus { status, .. }) => assert_eq!(status.as_u16(), 404),
+        Err(other) => panic!("deleted playbook must be 404, got {other}"),
+        Ok(_) => panic!("deleted playbook must be 404"),
+    }
+}
+
 #[tokio::test]
 async fn server_auth_password_change_success() {
     let Some(ctx) = get_ctx() else {
@@ -206,6 +717,67 @@ async fn server_admin_delete_session_authz() {
     );
 }
 
+#[tokio::test]
+async fn server_admin_recompute_sessions_authz() {
+    let Some(ctx) = get_ctx() else {
+        return;
+    };
+    let response = reqwest::Client::new()
+        .post(ctx.url("/admin/sessions/recompute"))
+        .header("X-OpenSession-Admin-Key", "not-the-admin-key")
+        .json(&json!({ "limit": 10 }))
+        .send()
+        .await
+        .expect("recompute request failed");
+
+    assert_eq!(
+        response.status().as_u16(),
+        401,
+        "recompute endpoint must require the admin key"
+    );
+}
+
+#[tokio::test]
+async fn server_telemetry_accepts_member_reports_and_hides_totals_from_non_admins() {
+    let Some(ctx) = get_ctx() else {
+        return;
+    };
+    let user = register_user(&ctx, "server-telemetry", "test-pass-123").await;
+    let mut client = ApiClient::new(ctx.base_url(), Duration::from_secs(30))
+        .expect("api client should construct");
+    let report = TelemetryReport {
+        daemon_version: "0.0.0-e2e".to_string(),
+        period_start: "2026-10-18T00:00:00Z".to_string(),
+        period_end: "2026-10-18T01:00:00Z".to_string(),
+        sessions_indexed: 3,
+        parse_failures: 1,
+        upload_errors: 0,
+    };
+    match client.report_telemetry(&report).await {
+        Err(ApiClientError::AuthTokenMissing) => {}
+        other => panic!("anonymous report must need a token, got {other:?}"),
+    }
+
+    client.set_auth(user.tokens.access_token.clone());
+    let accepted = client
+        .report_telemetry(&report)
+        .await
+        .expect("report telemetry");
+    assert!(accepted.ok);
+
+    let response = reqwest::Client::new()
+        .get(ctx.url("/admin/telemetry"))
+        .bearer_auth(&user.tokens.access_token)
+        .send()
+        .await
+        .expect("telemetry summary request failed");
+    assert_eq!(
+        response.status().as_u16(),
+        401,
+        "telemetry totals must require the admin key"
+    );
+}
+
 #[tokio::test]
 async fn removed_team_and_sync_endpoints_are_unavailable() {
     let Some(ctx) = get_ctx() else {
@@ -0,0 +1,63 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::connection::LocalDb;
+
+/// Async facade over [`LocalDb`] for the daemon scheduler and other tokio
+/// event loops: each call runs on the blocking pool, so SQLite I/O and
+/// waiting on the connection mutex never stall the runtime's worker threads.
+///
+/// Cheap to clone; every clone shares one connection.
+#[derive(Clone)]
+pub struct AsyncLocalDb {
+    db: Arc<LocalDb>,
+}
+
+impl AsyncLocalDb {
+    /// Open the local database at the default path on the blocking pool.
+    pub async fn open() -> Result<Self> {
+        tokio::task::spawn_blocking(LocalDb::open)
+            .await
+            .context("open local db task")?
+            .map(Self::from)
+    }
+
+    /// Open the local database at `path` on the blocking pool.
+    pub async fn open_path(path: PathBuf) -> Result<Self> {
+        tokio::task::spawn_blocking(move || LocalDb::open_path(&path))
+            .await
+            .context("open local db task")?
+            .map(Self::from)
+    }
+
+    /// Run `op` against the database on the blocking pool.
+    pub async fn call<T, F>(&self, op: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&LocalDb) -> Result<T> + Send + 'static,
+    {
+        let db = Arc::clone(&self.db);
+        tokio::task::spawn_blocking(move || op(&db))
+            .await
+            .context("local db task")?
+    }
+
+    /// The wrapped database, for synchronous callers that are already off
+    /// the async runtime (blocking threads, tests).
+    pub fn blocking(&self) -> &LocalDb {
+        &self.db
+    }
+}
+
+impl From<LocalDb> for AsyncLocalDb {
+    fn from(db: LocalDb) -> Self {
+        Self { db: Arc::new(db) }
+    }
+}
+
+impl From<Arc<LocalDb>> for AsyncLocalDb {
+    fn from(db: Arc<LocalDb>) -> Self {
+        Self { db }
+    }
+}
@@ -0,0 +1,158 @@
+//! Files attached to local sessions.
+//!
+//! Rows live in `session_attachments`; content is copied into an attachments
+//! directory (see `opensession_paths::attachments_root`) under `storage_key`.
+
+use anyhow::{Context, Result};
+use opensession_api::{AttachmentKind, SessionAttachment};
+use rusqlite::{OptionalExtension, params};
+use std::path::{Path, PathBuf};
+
+use crate::connection::LocalDb;
+
+impl LocalDb {
+    /// Copy `data` into `root` and record it as an attachment of `session_id`.
+    ///
+    /// `recorded_at` is the RFC 3339 start of a terminal recording.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_attachment(
+        &self,
+        root: &Path,
+        session_id: &str,
+        file_name: &str,
+        content_type: &str,
+        kind: AttachmentKind,
+        recorded_at: Option<&str>,
+        data: &[u8],
+    ) -> Result<SessionAttachment> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let storage_key = format!("{id}.attachment");
+        std::fs::create_dir_all(root).with_context(|| format!("create {}", root.display()))?;
+        let path = root.join(&storage_key);
+        std::fs::write(&path, data).with_context(|| format!("write {}", path.display()))?;
+
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO session_attachments \
+             (id, session_id, file_name, content_type, size_bytes, storage_key, kind, recorded_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                id,
+                session_id,
+                file_name,
+                content_type,
+                i64::try_from(data.len()).unwrap_or(i64::MAX),
+                storage_key,
+                kind.as_str(),
+                recorded_at
+            ],
+        )?;
+        conn.query_row(
+            &format!("SELECT {ATTACHMENT_COLUMNS} FROM session_attachments WHERE id = ?1"),
+            params![id],
+            attachment_from_row,
+        )
+        .map_err(Into::into)
+    }
+
+    /// A session's attachments, oldest first.
+    pub fn list_attachments(&self, session_id: &str) -> Result<Vec<SessionAttachment>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {ATTACHMENT_COLUMNS} FROM session_attachments \
+             WHERE session_id = ?1 ORDER BY created_at, id"
+        ))?;
+        let rows = stmt.query_map(params![session_id], attachment_from_row)?;
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+
+    /// Where an attachment's content is stored under `root`.
+    pub fn attachment_path(&self, root: &Path, id: &str) -> Result<Option<PathBuf>> {
+        let storage_key = self
+            .conn()
+            .query_row(
+                "SELECT storage_key FROM session_attachments WHERE id = ?1",
+                params![id],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?;
+        Ok(storage_key.map(|key| root.join(key)))
+    }
+}
+
+const ATTACHMENT_COLUMNS: &str =
+    "id, session_id, file_name, content_type, size_bytes, created_at, kind, recorded_at";
+
+fn attachment_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionAttachment> {
+    Ok(SessionAttachment {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        file_name: row.get(2)?,
+        content_type: row.get(3)?,
+        size_bytes: row.get(4)?,
+        created_at: row.get(5)?,
+        kind: AttachmentKind::parse(&row.get::<_, String>(6)?),
+        recorded_at: row.get(7)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::LocalDb;
+    use opensession_api::AttachmentKind;
+    use tempfile::tempdir;
+
+    #[test]
+    fn attachments_are_stored_and_listed_per_session() {
+        let dir = tempdir().unwrap();
+        let db = LocalDb::open_path(&dir.path().join("local.db")).unwrap();
+        let root = dir.path().join("attachments");
+
+        let shot = db
+            .add_attachment(
+                &root,
+                "s1",
+                "shot.png",
+                "image/png",
+                AttachmentKind::File,
+                None,
+                b"png",
+            )
+            .unwrap();
+        db.add_attachment(
+            &root,
+            "s2",
+            "other.log",
+            "text/plain",
+            AttachmentKind::File,
+            None,
+            b"log",
+        )
+        .unwrap();
+
+        let listed = db.list_attachments("s1").unwrap();
+        assert_eq!(listed, vec![shot.clone()]);
+        assert_eq!(shot.size_bytes, 3);
+        let path = db.attachment_path(&root, &shot.id).unwrap().unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), b"png");
+        assert!(db.attachment_path(&root, "missing").unwrap().is_none());
+
+        let cast = db
+            .add_attachment(
+                &root,
+                "s1",
+                "run.cast",
+                "application/x-asciicast",
+                AttachmentKind::TerminalRecording,
+                Some("2023-11-14T22:13:20Z"),
+                b"{}",
+            )
+            .unwrap();
+        let listed = db.list_attachments("s1").unwrap();
+        assert_eq!(listed.len(), 2);
+        assert!(listed.contains(&cast));
+        assert_eq!(cast.kind, AttachmentKind::TerminalRecording);
+        assert_eq!(cast.recorded_at.as_deref(), Some("2023-11-14T22:13:20Z"));
+    }
+}
@@ -18,7 +18,7 @@ pub struct LocalDb {
 
 impl LocalDb {
     /// Open (or create) the local database at the default path.
-    /// `~/.local/share/opensession/local.db` or `OPENSESSION_LOCAL_DB_PATH` when set.
+    /// `<data dir>/local.db` (see `opensession_paths::data_dir`) or `OPENSESSION_LOCAL_DB_PATH` when set.
     pub fn open() -> Result<Self> {
         let path = default_db_path()?;
         Self::open_path(&path)
@@ -105,7 +105,12 @@ mod tests {
         let _lock = env_test_lock().lock().expect("env lock");
         let _guard = EnvVarGuard::set("OPENSESSION_LOCAL_DB_PATH", "");
         let path = default_db_path().expect("default db path");
-        assert!(path.ends_with(PathBuf::from(".local/share/opensession/local.db")));
+        assert_eq!(
+            path,
+            opensession_paths::data_dir()
+                .expect("data dir")
+                .join("local.db")
+        );
     }
 
     #[test]
@@ -0,0 +1,277 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+/// Longest a single probe command may run before it is killed.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Where a session ran, as far as can be told when it is indexed.
+/// Collected only when `privacy.capture_environment` is enabled.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionEnvironment {
+    pub os: String,
+    pub arch: String,
+    /// When the probe ran. This is index time, which can be well after the
+    /// session itself for backfilled or re-indexed sessions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captured_at: Option<DateTime<Utc>>,
+    /// `rustc --version`, for Rust projects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rustc: Option<String>,
+    /// `node --version`, for Node projects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+    /// Whether the git working tree had uncommitted changes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_dirty: Option<bool>,
+    /// Container runtime (`docker`, `podman`, `kubernetes`, ...), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+}
+
+impl SessionEnvironment {
+    /// Probe the current machine and, when given, the session's working directory.
+    pub fn capture(cwd: Option<&str>) -> Self {
+        let cwd = cwd.map(Path::new).filter(|dir| dir.is_dir());
+        let rustc = cwd
+            .filter(|dir| {
+                has_marker(
+                    dir,
+                    &["Cargo.toml", "rust-toolchain", "rust-toolchain.toml"],
+                )
+            })
+            .and_then(|_| tool_version("rustc"));
+        let node = cwd
+            .filter(|dir| has_marker(dir, &["package.json"]))
+            .and_then(|_| tool_version("node"));
+        let git_dirty = cwd.and_then(|dir| {
+            // No fsmonitor: it would run a hook the repository chose.
+            let mut command = Command::new("git");
+            command
+                .arg("-C")
+                .arg(dir)
+                .args(["-c", "core.fsmonitor=false"])
+                .args(["status", "--porcelain", "--untracked-files=no"]);
+            let output = run_bounded(command, PROBE_TIMEOUT)?;
+            output
+                .status
+                .success()
+                .then(|| !output.stdout.iter().all(u8::is_ascii_whitespace))
+        });
+
+        Self {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            captured_at: Some(Utc::now()),
+            rustc,
+            node,
+            git_dirty,
+            container: detect_container(),
+        }
+    }
+
+    /// One-line form, e.g. `linux/x86_64, rustc 1.85.0, node v20.11.1, dirty tree, docker`.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!("{}/{}", self.os, self.arch)];
+        if let Some(rustc) = &self.rustc {
+            parts.push(format!("rustc {rustc}"));
+        }
+        if let Some(node) = &self.node {
+            parts.push(format!("node {node}"));
+        }
+        match self.git_dirty {
+            Some(true) => parts.push("dirty tree".to_string()),
+            Some(false) => parts.push("clean tree".to_string()),
+            None => {}
+        }
+        if let Some(container) = &self.container {
+            parts.push(container.clone());
+        }
+        parts.join(", ")
+    }
+}
+
+/// Whether `dir` or one of its ancestors up to the repository root holds a marker file.
+fn has_marker(dir: &Path, markers: &[&str]) -> bool {
+    for ancestor in dir.ancestors() {
+        if markers.iter().any(|marker| ancestor.join(marker).is_file()) {
+            return true;
+        }
+        if ancestor.join(".git").exists() {
+            break;
+        }
+    }
+    false
+}
+
+/// Version token of `<tool> --version` for the tool found on `PATH`.
+///
+/// Toolchain shims (rustup, volta, asdf, mise, ...) are skipped: run inside
+/// a pinned project they may download a whole toolchain first.
+fn tool_version(tool: &str) -> Option<String> {
+    let path = find_on_path(tool)?;
+    if is_toolchain_shim(&path) {
+        return None;
+    }
+    let mut command = Command::new(path);
+    command.arg("--version");
+    let output = run_bounded(command, PROBE_TIMEOUT)?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_version(tool, &String::from_utf8_lossy(&output.stdout))
+}
+
+/// `tool` in an absolute `PATH` entry; relative entries such as `.` would
+/// resolve into the session's repository.
+fn find_on_path(tool: &str) -> Option<PathBuf> {
+    let file_name = format!("{tool}{}", std::env::consts::EXE_SUFFIX);
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .filter(|dir| dir.is_absolute())
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Whether `path` is a version manager's proxy rather than a real toolchain
+/// binary: it sits in a `shims` directory or next to the manager itself.
+fn is_toolchain_shim(path: &Path) -> bool {
+    const MANAGERS: &[&str] = &["rustup", "volta", "proto"];
+
+    let Some(dir) = path.parent() else {
+        return false;
+    };
+    dir.file_name().is_some_and(|name| name == "shims")
+        || MANAGERS.iter().any(|manager| {
+            dir.join(format!("{manager}{}", std::env::consts::EXE_SUFFIX))
+                .is_file()
+        })
+}
+
+/// Run `command` with its stdout captured, killing it once `timeout` passes.
+fn run_bounded(mut command: Command, timeout: Duration) -> Option<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    // Drain stdout on the side so a chatty command cannot fill the pipe and stall.
+    let mut stdout = child.stdout.take()?;
+    let reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        stdout.read_to_end(&mut buf).map(|_| buf)
+    });
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    };
+    let stdout = reader.join().ok()?.ok()?;
+    Some(Output {
+        status,
+        stdout,
+        stderr: Vec::new(),
+    })
+}
+
+/// `rustc 1.85.0 (4d91de4e4 2025-02-17)` → `1.85.0`; `v20.11.1` → `v20.11.1`.
+fn parse_version(tool: &str, output: &str) -> Option<String> {
+    let line = output.lines().next()?.trim();
+    let version = line
+        .strip_prefix(tool)
+        .unwrap_or(line)
+        .split_whitespace()
+        .next()?;
+    Some(version.to_string())
+}
+
+fn detect_container() -> Option<String> {
+    if Path::new("/.dockerenv").exists() {
+        return Some("docker".to_string());
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some("podman".to_string());
+    }
+    if std::env::var_os("KUBERNETES_SERVICE_HOST").is_some() {
+        return Some("kubernetes".to_string());
+    }
+    std::env::var("container")
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SessionEnvironment, is_toolchain_shim, parse_version};
+
+    #[test]
+    fn versions_and_summary() {
+        assert_eq!(
+            parse_version("rustc", "rustc 1.85.0 (4d91de4e4 2025-02-17)\n").as_deref(),
+            Some("1.85.0")
+        );
+        assert_eq!(
+            parse_version("node", "v20.11.1\n").as_deref(),
+            Some("v20.11.1")
+        );
+        let environment = SessionEnvironment {
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            captured_at: None,
+            rustc: Some("1.85.0".to_string()),
+            node: None,
+            git_dirty: Some(true),
+            container: Some("docker".to_string()),
+        };
+        assert_eq!(
+            environment.summary(),
+            "linux/x86_64, rustc 1.85.0, dirty tree, docker"
+        );
+    }
+
+    #[test]
+    fn toolchain_shims_are_recognized() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let exe = |name: &str| format!("{name}{}", std::env::consts::EXE_SUFFIX);
+        let cargo_bin = tmp.path().join("cargo-bin");
+        let shims = tmp.path().join("shims");
+        let plain = tmp.path().join("bin");
+        for dir in [&cargo_bin, &shims, &plain] {
+            std::fs::create_dir_all(dir).expect("create dir");
+        }
+        std::fs::write(cargo_bin.join(exe("rustup")), "").expect("write rustup");
+
+        assert!(is_toolchain_shim(&cargo_bin.join(exe("rustc"))));
+        assert!(is_toolchain_shim(&shims.join(exe("node"))));
+        assert!(!is_toolchain_shim(&plain.join(exe("rustc"))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn probes_are_killed_after_the_timeout() {
+        let started = std::time::Instant::now();
+        let mut command = std::process::Command::new("sleep");
+        command.arg("5");
+        assert!(super::run_bounded(command, std::time::Duration::from_millis(50)).is_none());
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+
+        let mut command = std::process::Command::new("echo");
+        command.arg("hi");
+        let output =
+            super::run_bounded(command, std::time::Duration::from_secs(2)).expect("echo finishes");
+        assert_eq!(output.stdout, b"hi\n");
+    }
+}
@@ -1,21 +1,37 @@
+pub mod environment;
 pub mod git;
 
+mod async_db;
+mod attachment_store;
 mod connection;
 mod job_store;
+mod metadata_store;
 mod migrations;
+mod parse_error_store;
+mod playbook_store;
+mod query;
 mod repo_store;
 mod session_store;
+mod shell_history_store;
+mod source_archive;
+mod source_reconcile;
 mod summary_store;
 mod sync_store;
 mod vector_store;
 
+pub use async_db::AsyncLocalDb;
 pub use connection::LocalDb;
 pub use job_store::{LifecycleCleanupJobRow, SummaryBatchJobRow, VectorIndexJobRow};
+pub use parse_error_store::ParseErrorRow;
 pub use session_store::{
     LocalSessionFilter, LocalSessionLink, LocalSessionRow, LocalSortOrder, LocalTimeRange,
     LogFilter, RemoteSessionSummary,
 };
+pub use shell_history_store::{ShellCommandRecord, ShellCommandRow};
+pub use source_archive::{SourceArchiveCandidate, SourceArchiveLocation, SourceArchiveReport};
+pub use source_reconcile::{SourceReconcileReport, SourceRelocation};
 pub use summary_store::{SessionSemanticSummaryRow, SessionSemanticSummaryUpsert};
+pub use sync_store::{CachedBodyRow, LocalCacheStats};
 pub use vector_store::{VectorChunkCandidateRow, VectorChunkUpsert};
 
 #[cfg(test)]
@@ -49,6 +65,43 @@ mod tests {
         let _db = test_db();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_async_facade_runs_calls_off_the_runtime() {
+        let dir = tempdir().unwrap();
+        let db = AsyncLocalDb::open_path(dir.path().join("async.db"))
+            .await
+            .unwrap();
+
+        let writes = (0..8).map(|i| {
+            let db = db.clone();
+            tokio::spawn(async move {
+                db.call(move |db| {
+                    db.upsert_remote_session(&make_summary(
+                        &format!("a{i}"),
+                        "codex",
+                        "Async write",
+                        "2024-01-01T00:00:00Z",
+                    ))
+                })
+                .await
+            })
+        });
+        for write in writes {
+            write.await.unwrap().unwrap();
+        }
+
+        let count = db
+            .call(|db| db.count_sessions_filtered(&LocalSessionFilter::default()))
+            .await
+            .unwrap();
+        assert_eq!(count, 8);
+        assert!(
+            db.call(|_| -> Result<()> { anyhow::bail!("boom") })
+                .await
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_open_repairs_codex_tool_hint_from_source_path() {
         let dir = tempfile::tempdir().unwrap();
@@ -81,6 +134,64 @@ mod tests {
         assert_eq!(row.tool, "codex");
     }
 
+    #[test]
+    fn test_local_session_columns_map_to_their_fields() {
+        use crate::session_store::{
+            FROM_CLAUSE, LOCAL_SESSION_COLUMN_COUNT, LOCAL_SESSION_COLUMNS,
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("columns.db");
+        {
+            let _ = LocalDb::open_path(&path).unwrap();
+        }
+        {
+            let conn = Connection::open(&path).unwrap();
+            let stmt = conn
+                .prepare(&format!("SELECT {LOCAL_SESSION_COLUMNS} {FROM_CLAUSE}"))
+                .unwrap();
+            assert_eq!(stmt.column_count(), LOCAL_SESSION_COLUMN_COUNT);
+            conn.execute(
+                "INSERT INTO sessions (id, team_id, tool, created_at, body_storage_key, title, \
+                 has_errors, session_score, issue_refs, environment) \
+                 VALUES ('cols', 'personal', 'claude-code', '2026-02-20T00:00:00Z', '', 'Columns', \
+                 1, 42, 'PROJ-1', '{}')",
+                [],
+            )
+            .unwrap();
+        }
+
+        let db = LocalDb::open_path(&path).unwrap();
+        let row = db.get_session_by_id("cols").unwrap().expect("row");
+        assert_eq!(row.title.as_deref(), Some("Columns"));
+        assert_eq!(row.sync_status, "unknown");
+        assert!(row.has_errors);
+        assert_eq!(row.max_active_agents, 1);
+        assert_eq!(row.session_score, 42);
+        assert_eq!(row.issue_refs.as_deref(), Some("PROJ-1"));
+        assert_eq!(row.environment.as_deref(), Some("{}"));
+    }
+
+    #[test]
+    fn test_infer_tool_from_windows_source_path() {
+        use crate::session_store::infer_tool_from_source_path;
+
+        assert_eq!(
+            infer_tool_from_source_path(Some(
+                r"C:\Users\Test\.codex\sessions\2026\02\20\rollout-1.jsonl"
+            )),
+            Some("codex")
+        );
+        assert_eq!(
+            infer_tool_from_source_path(Some(r"C:\Users\Test\.claude\projects\p\s.jsonl")),
+            Some("claude-code")
+        );
+        assert_eq!(
+            infer_tool_from_source_path(Some(r"C:\Users\Test\notes\s.jsonl")),
+            None
+        );
+    }
+
     #[test]
     fn test_open_repairs_codex_auxiliary_flag_from_source_path() {
         let dir = tempfile::tempdir().unwrap();
@@ -204,6 +315,60 @@ mod tests {
         assert_eq!(row.tool, "codex");
     }
 
+    #[test]
+    fn test_upsert_local_session_stores_usefulness_score_for_best_sort() {
+        let db = test_db();
+        let agent = opensession_core::trace::Agent {
+            provider: "anthropic".to_string(),
+            model: "claude".to_string(),
+            tool: "claude-code".to_string(),
+            tool_version: None,
+        };
+        let mut engaged = Session::new("engaged".to_string(), agent.clone());
+        engaged.context.created_at = chrono::Utc::now() - chrono::Duration::hours(1);
+        for (idx, event_type) in [
+            opensession_core::trace::EventType::UserMessage,
+            opensession_core::trace::EventType::AgentMessage,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            engaged.events.push(opensession_core::trace::Event {
+                event_id: format!("e{idx}"),
+                timestamp: chrono::Utc::now(),
+                event_type,
+                task_id: None,
+                content: opensession_core::trace::Content::text("hello"),
+                duration_ms: None,
+                attributes: Default::default(),
+            });
+        }
+        engaged.recompute_stats();
+        let mut bare = Session::new("bare".to_string(), agent);
+        bare.stats.event_count = 1;
+
+        for session in [&engaged, &bare] {
+            db.upsert_local_session(
+                session,
+                &format!("/tmp/{}.jsonl", session.session_id),
+                &crate::git::GitContext::default(),
+            )
+            .unwrap();
+        }
+
+        let recent = db.list_sessions(&LocalSessionFilter::default()).unwrap();
+        assert_eq!(recent[0].id, "bare");
+
+        let best = db
+            .list_sessions(&LocalSessionFilter {
+                sort: LocalSortOrder::Best,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(best[0].id, "engaged");
+        assert!(best[0].session_score > best[1].session_score);
+    }
+
     #[test]
     fn test_upsert_local_session_preserves_existing_git_when_session_has_no_git_metadata() {
         let db = test_db();
@@ -596,6 +761,106 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_body_cache_tracks_etag() {
+        let db = test_db();
+        db.cache_body_with_etag("s1", b"remote", Some("\"v1\""))
+            .unwrap();
+        let row = db.get_cached_body_row("s1").unwrap().expect("cached row");
+        assert_eq!(row.body, b"remote".to_vec());
+        assert_eq!(row.etag.as_deref(), Some("\"v1\""));
+
+        // Re-caching without a validator must not keep a stale ETag.
+        db.cache_body("s1", b"local").unwrap();
+        let row = db.get_cached_body_row("s1").unwrap().expect("cached row");
+        assert_eq!(row.etag, None);
+    }
+
+    #[test]
+    fn test_body_cache_evicts_least_recently_cached() {
+        let db = test_db();
+        for id in ["old", "touched", "new"] {
+            db.cache_body(id, b"0123456789").unwrap();
+        }
+        db.conn()
+            .execute(
+                "UPDATE body_cache SET cached_at = datetime('now', '-1 hour') \
+                 WHERE session_id IN ('old', 'touched')",
+                [],
+            )
+            .unwrap();
+        db.touch_cached_body("touched").unwrap();
+
+        assert_eq!(db.evict_body_cache(25).unwrap(), 1);
+        assert_eq!(db.get_cached_body("old").unwrap(), None);
+        assert!(db.get_cached_body("touched").unwrap().is_some());
+        assert_eq!(db.body_cache_stats().unwrap().bytes, 20);
+        assert_eq!(db.evict_body_cache(25).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_body_cache_appends_event_deltas() {
+        let db = test_db();
+        let mut full = Session::new(
+            "delta".to_string(),
+            opensession_core::trace::Agent {
+                provider: "anthropic".to_string(),
+                model: "claude".to_string(),
+                tool: "claude-code".to_string(),
+                tool_version: None,
+            },
+        );
+        for idx in 0..3 {
+            full.events.push(opensession_core::trace::Event {
+                event_id: format!("e{idx}"),
+                timestamp: chrono::Utc::now(),
+                event_type: opensession_core::trace::EventType::UserMessage,
+                task_id: None,
+                content: opensession_core::trace::Content::text(format!("message {idx}")),
+                duration_ms: None,
+                attributes: Default::default(),
+            });
+        }
+        full.recompute_stats();
+        let mut prefix = full.clone();
+        prefix.events.truncate(1);
+        prefix.recompute_stats();
+
+        let full_jsonl = opensession_core::jsonl::to_jsonl_string(&full).unwrap();
+        let prefix_jsonl = opensession_core::jsonl::to_jsonl_string(&prefix).unwrap();
+        let delta = opensession_core::jsonl::event_delta(&full_jsonl, 1).unwrap();
+
+        assert!(
+            !db.append_cached_body_delta("delta", 1, delta.as_bytes(), None)
+                .unwrap(),
+            "nothing cached yet"
+        );
+        db.cache_body_with_etag("delta", prefix_jsonl.as_bytes(), Some("\"v1\""))
+            .unwrap();
+        assert!(
+            !db.append_cached_body_delta("delta", 2, delta.as_bytes(), None)
+                .unwrap(),
+            "event count mismatch must not merge"
+        );
+        assert!(
+            !db.append_cached_body_delta("delta", 1, delta.as_bytes(), Some("\"rewritten\""))
+                .unwrap(),
+            "a merge that does not hash to the server ETag must not be cached"
+        );
+        let full_etag = opensession_api::service::body_etag(full_jsonl.as_bytes());
+        assert!(
+            db.append_cached_body_delta("delta", 1, delta.as_bytes(), Some(&full_etag))
+                .unwrap()
+        );
+
+        let row = db
+            .get_cached_body_row("delta")
+            .unwrap()
+            .expect("cached row");
+        assert_eq!(row.body, full_jsonl.into_bytes());
+        assert_eq!(row.etag, Some(full_etag));
+    }
+
     #[test]
     fn test_get_session_by_id_and_list_session_links() {
         let db = test_db();
@@ -635,6 +900,25 @@ mod tests {
         assert_eq!(links[0].link_type, "handoff");
     }
 
+    #[test]
+    fn test_attachment_kind_migration_still_runs_for_older_schemas() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE session_attachments (id TEXT PRIMARY KEY);")
+            .unwrap();
+        let satisfied = |conn: &Connection| {
+            crate::migrations::satisfied_by_shared_schema(conn, "local_0016_attachment_kind")
+                .unwrap()
+        };
+        assert!(!satisfied(&conn));
+        conn.execute_batch("ALTER TABLE session_attachments ADD COLUMN kind TEXT;")
+            .unwrap();
+        assert!(satisfied(&conn));
+        assert!(
+            !crate::migrations::satisfied_by_shared_schema(&conn, "local_0015_parse_errors")
+                .unwrap()
+        );
+    }
+
     #[test]
     fn test_local_migrations_are_loaded_from_api_crate() {
         let migration_names: Vec<&str> = super::LOCAL_MIGRATIONS
@@ -661,10 +945,54 @@ mod tests {
             migration_names.contains(&"local_0005_lifecycle_cleanup_status"),
             "expected local_0005_lifecycle_cleanup_status migration from opensession-api"
         );
+        assert!(
+            migration_names.contains(&"local_0006_body_cache_etag"),
+            "expected local_0006_body_cache_etag migration from opensession-api"
+        );
+        assert!(
+            migration_names.contains(&"local_0007_source_archive"),
+            "expected local_0007_source_archive migration from opensession-api"
+        );
+        assert!(
+            migration_names.contains(&"local_0008_source_missing"),
+            "expected local_0008_source_missing migration from opensession-api"
+        );
+        assert!(
+            migration_names.contains(&"local_0009_synced_metadata"),
+            "expected local_0009_synced_metadata migration from opensession-api"
+        );
+        assert!(
+            migration_names.contains(&"local_0010_shell_history"),
+            "expected local_0010_shell_history migration from opensession-api"
+        );
+        assert!(
+            migration_names.contains(&"local_0011_session_attachments"),
+            "expected local_0011_session_attachments migration from opensession-api"
+        );
+        assert!(
+            migration_names.contains(&"local_0012_session_issue_refs"),
+            "expected local_0012_session_issue_refs migration from opensession-api"
+        );
+        assert!(
+            migration_names.contains(&"local_0013_playbook_cache"),
+            "expected local_0013_playbook_cache migration from opensession-api"
+        );
+        assert!(
+            migration_names.contains(&"local_0014_session_environment"),
+            "expected local_0014_session_environment migration from opensession-api"
+        );
+        assert!(
+            migration_names.contains(&"local_0015_parse_errors"),
+            "expected local_0015_parse_errors migration from opensession-api"
+        );
+        assert!(
+            migration_names.contains(&"local_0016_attachment_kind"),
+            "expected local_0016_attachment_kind migration from opensession-api"
+        );
         assert_eq!(
             migration_names.len(),
-            5,
-            "local schema should include baseline + summary cache + vector index + summary batch status + lifecycle cleanup status steps"
+            16,
+            "local schema should include baseline + summary cache + vector index + summary batch status + lifecycle cleanup status + body cache etag + source archive + source missing + synced metadata + shell history + session attachments + issue refs + playbook cache + session environment + parse errors + attachment kind steps"
         );
 
         let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -947,6 +1275,44 @@ mod tests {
         assert_eq!(results.len(), 2); // s2, s3
     }
 
+    #[test]
+    fn test_combined_filters_bind_parameters_in_order() {
+        let db = test_db();
+        seed_sessions(&db);
+
+        let log = db
+            .list_sessions_log(&LogFilter {
+                tool: Some("claude-code".to_string()),
+                model: Some("claude*".to_string()),
+                since: Some("2024-01-02T00:00:00Z".to_string()),
+                grep: Some("o".to_string()),
+                limit: Some(1),
+                offset: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        // claude-code since 01-02 with an "o": s5, s4, s2 -> second page of one
+        assert_eq!(
+            log.iter().map(|row| row.id.as_str()).collect::<Vec<_>>(),
+            ["s4"]
+        );
+
+        let filter = LocalSessionFilter {
+            team_id: Some("t1".to_string()),
+            sync_status: Some("remote_only".to_string()),
+            search: Some("session".to_string()),
+            tool: Some("claude-code".to_string()),
+            ..Default::default()
+        };
+        let listed = db.list_sessions(&filter).unwrap();
+        assert_eq!(
+            listed.iter().map(|row| row.id.as_str()).collect::<Vec<_>>(),
+            ["s1"]
+        );
+        assert_eq!(db.count_sessions_filtered(&filter).unwrap(), 1);
+        assert_eq!(db.list_session_tools(&filter).unwrap(), ["claude-code"]);
+    }
+
     #[test]
     fn test_log_filter_grep() {
         let db = test_db();
@@ -960,6 +1326,94 @@ mod tests {
         assert_eq!(results[0].id, "s2");
     }
 
+    #[test]
+    fn test_log_filter_by_issue_ref_from_branch_and_prompt() {
+        let db = test_db();
+        let mut session = Session::new(
+            "session-issue".to_string(),
+            opensession_core::trace::Agent {
+                provider: "anthropic".to_string(),
+                model: "claude-opus-4-6".to_string(),
+                tool: "claude-code".to_string(),
+                tool_version: None,
+            },
+        );
+        session.stats.event_count = 1;
+        session.context.title = Some("Close #45".to_string());
+        let git = crate::git::GitContext {
+            branch: Some("feature/PROJ-123-login".to_string()),
+            ..Default::default()
+        };
+        db.upsert_local_session(
+            &session,
+            "/Users/test/.claude/projects/foo/session-issue.jsonl",
+            &git,
+        )
+        .unwrap();
+
+        let by_issue = |issue: &str| {
+            db.list_sessions_log(&LogFilter {
+                issue: Some(issue.to_string()),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let rows = by_issue("PROJ-123");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].issue_refs.as_deref(), Some("PROJ-123,#45"));
+        assert_eq!(by_issue("#45").len(), 1);
+        assert!(by_issue("PROJ-12").is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors_keep_the_latest_failure_per_file() {
+        let db = test_db();
+        db.record_parse_error("/logs/a.jsonl", Some("codex"), "expected value at line 3")
+            .unwrap();
+        db.record_parse_error("/logs/b.jsonl", None, "empty file")
+            .unwrap();
+        db.record_parse_error("/logs/a.jsonl", Some("codex"), "EOF while parsing")
+            .unwrap();
+
+        let errors = db.list_parse_errors().unwrap();
+        assert_eq!(errors.len(), 2);
+        let a = errors
+            .iter()
+            .find(|row| row.source_path == "/logs/a.jsonl")
+            .unwrap();
+        assert_eq!(a.parser.as_deref(), Some("codex"));
+        assert_eq!(a.error, "EOF while parsing");
+
+        db.clear_parse_error("/logs/a.jsonl").unwrap();
+        let errors = db.list_parse_errors().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].source_path, "/logs/b.jsonl");
+    }
+
+    #[test]
+    fn test_session_environment_round_trips_through_log_rows() {
+        let db = test_db();
+        seed_sessions(&db);
+        assert!(!db.has_session_environment("s1").unwrap());
+
+        let environment = crate::environment::SessionEnvironment {
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            captured_at: Some(chrono::Utc::now()),
+            rustc: Some("1.85.0".to_string()),
+            git_dirty: Some(false),
+            ..Default::default()
+        };
+        db.set_session_environment("s1", &environment).unwrap();
+        assert!(db.has_session_environment("s1").unwrap());
+        assert!(!db.has_session_environment("missing").unwrap());
+
+        let row = db.get_session_by_id("s1").unwrap().unwrap();
+        let stored: crate::environment::SessionEnvironment =
+            serde_json::from_str(row.environment.as_deref().unwrap()).unwrap();
+        assert_eq!(stored, environment);
+    }
+
     #[test]
     fn test_log_limit_and_offset() {
         let db = test_db();
@@ -987,6 +1441,27 @@ mod tests {
         assert_eq!(results.len(), 3);
     }
 
+    #[test]
+    fn test_log_auxiliary_only_selects_hidden_sessions() {
+        let db = test_db();
+        seed_sessions(&db);
+        db.conn()
+            .execute("UPDATE sessions SET is_auxiliary = 1 WHERE id = 's2'", [])
+            .unwrap();
+
+        let visible = db.list_sessions_log(&LogFilter::default()).unwrap();
+        assert_eq!(visible.len(), 4);
+        assert!(visible.iter().all(|row| row.id != "s2"));
+
+        let filter = LogFilter {
+            auxiliary_only: true,
+            ..Default::default()
+        };
+        let hidden = db.list_sessions_log(&filter).unwrap();
+        assert_eq!(hidden.len(), 1);
+        assert_eq!(hidden[0].id, "s2");
+    }
+
     #[test]
     fn test_list_sessions_limit_offset() {
         let db = test_db();
@@ -1342,6 +1817,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cache_stats_and_clear() {
+        let db = test_db();
+        db.cache_body("s1", b"hello world").unwrap();
+        db.upsert_session_semantic_summary(&SessionSemanticSummaryUpsert {
+            session_id: "s1",
+            summary_json: r#"{"changes":"x"}"#,
+            generated_at: "2024-01-01T00:00:00Z",
+            provider: "ollama",
+            model: Some("llama3.2:3b"),
+            source_kind: "session_signals",
+            generation_kind: "provider",
+            prompt_fingerprint: Some("abc"),
+            source_details_json: None,
+            diff_tree_json: None,
+            error: None,
+        })
+        .unwrap();
+
+        assert_eq!(
+            db.body_cache_stats().unwrap(),
+            LocalCacheStats {
+                entries: 1,
+                bytes: 11
+            }
+        );
+        assert_eq!(
+            db.session_semantic_summary_cache_stats().unwrap(),
+            LocalCacheStats {
+                entries: 1,
+                bytes: 15
+            }
+        );
+
+        assert_eq!(db.clear_session_semantic_summaries().unwrap(), 1);
+        assert_eq!(db.clear_body_cache().unwrap(), 1);
+        assert_eq!(db.body_cache_stats().unwrap(), LocalCacheStats::default());
+        assert_eq!(
+            db.session_semantic_summary_cache_stats().unwrap(),
+            LocalCacheStats::default()
+        );
+    }
+
     #[test]
     fn test_list_all_session_ids_returns_sorted_ids() {
         let db = test_db();
@@ -0,0 +1,157 @@
+//! Local side of the per-user metadata synced through `/api/sync/metadata`.
+//!
+//! Writes are stamped with the local clock and marked dirty until a sync
+//! pushes them; merging the server state keeps whichever write is newer.
+
+use anyhow::Result;
+use opensession_api::MetadataEntry;
+use rusqlite::{OptionalExtension, params};
+
+use crate::connection::LocalDb;
+
+impl LocalDb {
+    /// Set (or, with `None`, delete) a metadata entry and mark it for push.
+    pub fn set_metadata(&self, key: &str, value: Option<&str>, updated_at: i64) -> Result<()> {
+        self.conn().execute(
+            "INSERT INTO synced_metadata (key, value, updated_at, dirty) VALUES (?1, ?2, ?3, 1) \
+             ON CONFLICT(key) DO UPDATE SET \
+             value = excluded.value, updated_at = excluded.updated_at, dirty = 1",
+            params![key, value, updated_at],
+        )?;
+        Ok(())
+    }
+
+    /// Current value for `key`; deleted entries read as `None`.
+    pub fn get_metadata(&self, key: &str) -> Result<Option<String>> {
+        let value = self
+            .conn()
+            .query_row(
+                "SELECT value FROM synced_metadata WHERE key = ?1",
+                params![key],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()?;
+        Ok(value.flatten())
+    }
+
+    /// Live (non-deleted) entries whose key starts with `prefix`, by key.
+    pub fn list_metadata(&self, prefix: &str) -> Result<Vec<MetadataEntry>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT key, value, updated_at FROM synced_metadata \
+             WHERE value IS NOT NULL AND substr(key, 1, length(?1)) = ?1 \
+             ORDER BY key",
+        )?;
+        let rows = stmt.query_map(params![prefix], metadata_entry_from_row)?;
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+
+    /// Entries written locally since the last sync, deletions included.
+    pub fn list_dirty_metadata(&self) -> Result<Vec<MetadataEntry>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT key, value, updated_at FROM synced_metadata WHERE dirty = 1 ORDER BY key",
+        )?;
+        let rows = stmt.query_map([], metadata_entry_from_row)?;
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+
+    /// Merge the server's state: entries at least as new as the local copy
+    /// replace it and clear its dirty flag; newer local writes stay pending.
+    /// Returns how many local entries changed value.
+    pub fn merge_remote_metadata(&self, entries: &[MetadataEntry]) -> Result<usize> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let mut changed = 0;
+        for entry in entries {
+            let local = tx
+                .query_row(
+                    "SELECT value, updated_at FROM synced_metadata WHERE key = ?1",
+                    params![entry.key],
+                    |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, i64>(1)?)),
+                )
+                .optional()?;
+            if let Some((_, local_updated_at)) = &local
+                && *local_updated_at > entry.updated_at
+            {
+                continue;
+            }
+            if local.as_ref().map(|(value, _)| value) != Some(&entry.value) {
+                changed += 1;
+            }
+            tx.execute(
+                "INSERT INTO synced_metadata (key, value, updated_at, dirty) VALUES (?1, ?2, ?3, 0) \
+                 ON CONFLICT(key) DO UPDATE SET \
+                 value = excluded.value, updated_at = excluded.updated_at, dirty = 0",
+                params![entry.key, entry.value, entry.updated_at],
+            )?;
+        }
+        tx.commit()?;
+        Ok(changed)
+    }
+}
+
+fn metadata_entry_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<MetadataEntry> {
+    Ok(MetadataEntry {
+        key: row.get(0)?,
+        value: row.get(1)?,
+        updated_at: row.get(2)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::LocalDb;
+    use opensession_api::MetadataEntry;
+    use tempfile::tempdir;
+
+    fn entry(key: &str, value: Option<&str>, updated_at: i64) -> MetadataEntry {
+        MetadataEntry {
+            key: key.to_string(),
+            value: value.map(str::to_string),
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn merge_keeps_newer_local_writes_pending() {
+        let dir = tempdir().unwrap();
+        let db = LocalDb::open_path(&dir.path().join("local.db")).unwrap();
+        db.set_metadata("bookmark:s1", Some("1"), 100).unwrap();
+        db.set_metadata("note:s1", Some("local"), 300).unwrap();
+        db.set_metadata("note:s2", Some("gone"), 100).unwrap();
+        db.set_metadata("note:s2", None, 150).unwrap();
+
+        assert_eq!(db.list_dirty_metadata().unwrap().len(), 3);
+        assert_eq!(
+            db.list_metadata("note:").unwrap(),
+            vec![entry("note:s1", Some("local"), 300)]
+        );
+
+        let changed = db
+            .merge_remote_metadata(&[
+                entry("bookmark:s1", Some("1"), 100),
+                entry("note:s1", Some("remote"), 200),
+                entry("note:s2", None, 150),
+                entry("filter:mine", Some("tool=codex"), 50),
+            ])
+            .unwrap();
+
+        assert_eq!(changed, 1);
+        assert_eq!(
+            db.list_dirty_metadata().unwrap(),
+            vec![entry("note:s1", Some("local"), 300)]
+        );
+        assert_eq!(
+            db.get_metadata("note:s1").unwrap().as_deref(),
+            Some("local")
+        );
+        assert_eq!(db.get_metadata("note:s2").unwrap(), None);
+        assert_eq!(
+            db.get_metadata("filter:mine").unwrap().as_deref(),
+            Some("tool=codex")
+        );
+    }
+}
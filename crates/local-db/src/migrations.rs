@@ -1,11 +1,12 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use opensession_api::db::migrations::{LOCAL_MIGRATIONS, MIGRATIONS};
 use rusqlite::{Connection, params};
 use std::fs;
 use std::io::{BufRead, BufReader};
 
 use crate::session_store::{
-    FROM_CLAUSE, LOCAL_SESSION_COLUMNS, infer_tool_from_source_path, normalize_tool_for_source_path,
+    FROM_CLAUSE, LOCAL_SESSION_COLUMN_COUNT, LOCAL_SESSION_COLUMNS, infer_tool_from_source_path,
+    normalize_tool_for_source_path,
 };
 
 pub(crate) fn apply_local_migrations(conn: &Connection) -> Result<()> {
@@ -31,8 +32,10 @@ pub(crate) fn apply_local_migrations(conn: &Connection) -> Result<()> {
             continue;
         }
 
-        conn.execute_batch(sql)
-            .with_context(|| format!("apply local migration {name}"))?;
+        if !satisfied_by_shared_schema(conn, name)? {
+            conn.execute_batch(sql)
+                .with_context(|| format!("apply local migration {name}"))?;
+        }
 
         conn.execute(
             "INSERT OR IGNORE INTO _migrations (name) VALUES (?1)",
@@ -44,11 +47,34 @@ pub(crate) fn apply_local_migrations(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Local migrations whose columns `0001_schema` now creates itself. A fresh
+/// database already has them, so the migration is only recorded.
+pub(crate) fn satisfied_by_shared_schema(conn: &Connection, name: &str) -> Result<bool> {
+    let (table, column) = match name {
+        "local_0016_attachment_kind" => ("session_attachments", "kind"),
+        _ => return Ok(false),
+    };
+    conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+        params![table, column],
+        |row| row.get(0),
+    )
+    .with_context(|| format!("inspect {table}.{column} for local migration {name}"))
+}
+
 pub(crate) fn validate_local_schema(conn: &Connection) -> Result<()> {
     let sql = format!("SELECT {LOCAL_SESSION_COLUMNS} {FROM_CLAUSE} WHERE 1=0");
-    conn.prepare(&sql)
-        .map(|_| ())
-        .context("validate local session schema")
+    let stmt = conn
+        .prepare(&sql)
+        .context("validate local session schema")?;
+    if stmt.column_count() != LOCAL_SESSION_COLUMN_COUNT {
+        bail!(
+            "local session column list selects {} columns but maps {}",
+            stmt.column_count(),
+            LOCAL_SESSION_COLUMN_COUNT
+        );
+    }
+    Ok(())
 }
 
 pub(crate) fn repair_session_tools_from_source_path(conn: &Connection) -> Result<()> {
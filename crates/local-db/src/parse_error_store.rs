@@ -0,0 +1,65 @@
+//! Session files that failed to parse, kept so the failures are visible in
+//! `opensession doctor` instead of only in the daemon log.
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+
+use crate::connection::LocalDb;
+
+/// The latest parse failure for one session file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseErrorRow {
+    pub source_path: String,
+    /// Parser that claimed the file, when known.
+    pub parser: Option<String>,
+    pub error: String,
+    /// RFC 3339.
+    pub failed_at: String,
+}
+
+impl LocalDb {
+    /// Record that `source_path` failed to parse, replacing any earlier failure.
+    pub fn record_parse_error(
+        &self,
+        source_path: &str,
+        parser: Option<&str>,
+        error: &str,
+    ) -> Result<()> {
+        self.conn().execute(
+            "INSERT INTO parse_errors (source_path, parser, error, failed_at) \
+             VALUES (?1, ?2, ?3, ?4) \
+             ON CONFLICT(source_path) DO UPDATE SET \
+              parser=excluded.parser, error=excluded.error, failed_at=excluded.failed_at",
+            params![source_path, parser, error, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Forget the failure for `source_path` once it parses again.
+    pub fn clear_parse_error(&self, source_path: &str) -> Result<()> {
+        self.conn().execute(
+            "DELETE FROM parse_errors WHERE source_path = ?1",
+            params![source_path],
+        )?;
+        Ok(())
+    }
+
+    /// Failures, newest first.
+    pub fn list_parse_errors(&self) -> Result<Vec<ParseErrorRow>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT source_path, parser, error, failed_at FROM parse_errors \
+             ORDER BY failed_at DESC, source_path",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(ParseErrorRow {
+                source_path: row.get(0)?,
+                parser: row.get(1)?,
+                error: row.get(2)?,
+                failed_at: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+}
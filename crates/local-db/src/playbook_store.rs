@@ -0,0 +1,146 @@
+//! Local cache of the server's shared playbooks (`/api/playbooks`).
+//!
+//! A successful listing replaces the whole cache, so playbooks deleted on
+//! the server disappear locally too; single fetches and pushes update one row.
+
+use anyhow::Result;
+use opensession_api::{Playbook, PlaybookKind};
+use rusqlite::{OptionalExtension, params};
+
+use crate::connection::LocalDb;
+
+const PLAYBOOK_COLUMNS: &str =
+    "name, kind, title, description, body, author_nickname, created_at, updated_at";
+
+impl LocalDb {
+    /// Replace the cache with the server's current playbook list.
+    pub fn replace_cached_playbooks(&self, playbooks: &[Playbook]) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM playbook_cache", [])?;
+        for playbook in playbooks {
+            insert_playbook(&tx, playbook)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Insert or refresh one cached playbook.
+    pub fn cache_playbook(&self, playbook: &Playbook) -> Result<()> {
+        insert_playbook(&self.conn(), playbook)
+    }
+
+    /// Cached playbooks, by name.
+    pub fn cached_playbooks(&self) -> Result<Vec<Playbook>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {PLAYBOOK_COLUMNS} FROM playbook_cache ORDER BY name"
+        ))?;
+        let rows = stmt.query_map([], playbook_from_row)?;
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+
+    pub fn cached_playbook(&self, name: &str) -> Result<Option<Playbook>> {
+        self.conn()
+            .query_row(
+                &format!("SELECT {PLAYBOOK_COLUMNS} FROM playbook_cache WHERE name = ?1"),
+                params![name],
+                playbook_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    pub fn remove_cached_playbook(&self, name: &str) -> Result<()> {
+        self.conn()
+            .execute("DELETE FROM playbook_cache WHERE name = ?1", params![name])?;
+        Ok(())
+    }
+}
+
+fn insert_playbook(conn: &rusqlite::Connection, playbook: &Playbook) -> Result<()> {
+    conn.execute(
+        &format!(
+            "INSERT OR REPLACE INTO playbook_cache ({PLAYBOOK_COLUMNS}) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+        ),
+        params![
+            playbook.name,
+            playbook.kind.as_str(),
+            playbook.title,
+            playbook.description,
+            playbook.body,
+            playbook.author_nickname,
+            playbook.created_at,
+            playbook.updated_at,
+        ],
+    )?;
+    Ok(())
+}
+
+fn playbook_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Playbook> {
+    let kind: String = row.get(1)?;
+    Ok(Playbook {
+        name: row.get(0)?,
+        kind: PlaybookKind::parse(&kind).unwrap_or_default(),
+        title: row.get(2)?,
+        description: row.get(3)?,
+        body: row.get(4)?,
+        author_nickname: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::LocalDb;
+    use opensession_api::{Playbook, PlaybookKind};
+    use tempfile::tempdir;
+
+    fn playbook(name: &str, kind: PlaybookKind, body: &str) -> Playbook {
+        Playbook {
+            name: name.to_string(),
+            kind,
+            title: None,
+            description: None,
+            body: body.to_string(),
+            author_nickname: Some("alice".to_string()),
+            created_at: "2026-01-01 00:00:00".to_string(),
+            updated_at: "2026-01-01 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn listing_replaces_the_cache() {
+        let dir = tempdir().unwrap();
+        let db = LocalDb::open_path(&dir.path().join("local.db")).unwrap();
+        db.cache_playbook(&playbook("stale", PlaybookKind::Prompt, "old"))
+            .unwrap();
+        db.replace_cached_playbooks(&[
+            playbook("triage", PlaybookKind::Prompt, "Triage {{issue}}"),
+            playbook(
+                "handoff",
+                PlaybookKind::HandoffTemplate,
+                "Status: {{status}}",
+            ),
+        ])
+        .unwrap();
+
+        let names: Vec<_> = db
+            .cached_playbooks()
+            .unwrap()
+            .into_iter()
+            .map(|playbook| playbook.name)
+            .collect();
+        assert_eq!(names, vec!["handoff", "triage"]);
+        assert_eq!(
+            db.cached_playbook("handoff").unwrap().unwrap().kind,
+            PlaybookKind::HandoffTemplate
+        );
+
+        db.remove_cached_playbook("triage").unwrap();
+        assert!(db.cached_playbook("triage").unwrap().is_none());
+    }
+}
@@ -0,0 +1,88 @@
+//! sea-query glue: the local filters are built as typed predicates with the
+//! same `opensession_api::db` machinery the server uses, then bound here.
+
+use opensession_api::db::sessions::Built;
+use opensession_api::db::{Sessions, Users};
+use rusqlite::Connection;
+use rusqlite::types::ToSql;
+use sea_query::{Alias, Expr, IntoIden, JoinType, Query, SelectStatement, SimpleExpr};
+
+/// A column of the `sessions s` alias, including local-only columns that have
+/// no [`Sessions`] variant (pass `Alias::new("is_auxiliary")`).
+pub(crate) fn s(column: impl IntoIden + 'static) -> Expr {
+    Expr::col((Alias::new("s"), column))
+}
+
+/// `SELECT … FROM sessions s LEFT JOIN session_sync ss LEFT JOIN users u`,
+/// the shape of [`crate::session_store::FROM_CLAUSE`].
+pub(crate) fn select_sessions() -> SelectStatement {
+    Query::select()
+        .from_as(Sessions::Table, Alias::new("s"))
+        .join_as(
+            JoinType::LeftJoin,
+            Alias::new("session_sync"),
+            Alias::new("ss"),
+            Expr::col((Alias::new("ss"), Alias::new("session_id")))
+                .equals((Alias::new("s"), Sessions::Id)),
+        )
+        .join_as(
+            JoinType::LeftJoin,
+            Users::Table,
+            Alias::new("u"),
+            Expr::col((Alias::new("u"), Users::Id)).equals((Alias::new("s"), Sessions::UserId)),
+        )
+        .to_owned()
+}
+
+pub(crate) fn and_where_all(select: &mut SelectStatement, conds: Vec<SimpleExpr>) {
+    for cond in conds {
+        select.and_where(cond);
+    }
+}
+
+fn sq_params(values: &sea_query::Values) -> Vec<Box<dyn ToSql>> {
+    values
+        .0
+        .iter()
+        .map(|v| -> Box<dyn ToSql> {
+            match v {
+                sea_query::Value::Bool(Some(b)) => Box::new(*b),
+                sea_query::Value::TinyInt(Some(i)) => Box::new(*i as i32),
+                sea_query::Value::SmallInt(Some(i)) => Box::new(*i as i32),
+                sea_query::Value::Int(Some(i)) => Box::new(*i),
+                sea_query::Value::BigInt(Some(i)) => Box::new(*i),
+                sea_query::Value::TinyUnsigned(Some(u)) => Box::new(*u as i64),
+                sea_query::Value::SmallUnsigned(Some(u)) => Box::new(*u as i64),
+                sea_query::Value::Unsigned(Some(u)) => Box::new(*u as i64),
+                sea_query::Value::BigUnsigned(Some(u)) => Box::new(*u as i64),
+                sea_query::Value::Float(Some(f)) => Box::new(*f as f64),
+                sea_query::Value::Double(Some(f)) => Box::new(*f),
+                sea_query::Value::String(Some(s)) => Box::new(s.as_ref().clone()),
+                sea_query::Value::Bytes(Some(b)) => Box::new(b.as_ref().clone()),
+                _ => Box::new(rusqlite::types::Null),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn sq_query_row<T>(
+    conn: &Connection,
+    (sql, values): Built,
+    f: impl FnOnce(&rusqlite::Row<'_>) -> rusqlite::Result<T>,
+) -> rusqlite::Result<T> {
+    let params = sq_params(&values);
+    let refs: Vec<&dyn ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    conn.query_row(&sql, refs.as_slice(), f)
+}
+
+pub(crate) fn sq_query_map<T>(
+    conn: &Connection,
+    (sql, values): Built,
+    f: impl FnMut(&rusqlite::Row<'_>) -> rusqlite::Result<T>,
+) -> rusqlite::Result<Vec<T>> {
+    let params = sq_params(&values);
+    let refs: Vec<&dyn ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(refs.as_slice(), f)?;
+    rows.collect()
+}
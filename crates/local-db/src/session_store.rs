@@ -1,12 +1,19 @@
 use anyhow::Result;
+use opensession_api::db::Sessions;
+use opensession_core::scoring::SessionScorePlugin;
 use opensession_core::session::{is_auxiliary_session, working_directory};
 use opensession_core::trace::Session;
-use rusqlite::params;
+use rusqlite::{OptionalExtension, params};
+use sea_query::{
+    Alias, Asterisk, Expr, Func, Order, SelectStatement, SimpleExpr, SqliteQueryBuilder,
+};
 use serde_json::Value;
 use std::collections::HashSet;
 
 use crate::connection::LocalDb;
+use crate::environment::SessionEnvironment;
 use crate::git::{GitContext, normalize_repo_name};
+use crate::query::{and_where_all, s, select_sessions, sq_query_map, sq_query_row};
 
 pub(crate) const SUMMARY_WORKER_TITLE_PREFIX_LOWER: &str =
     "convert a real coding session into semantic compression.";
@@ -48,6 +55,12 @@ pub struct LocalSessionRow {
     pub has_errors: bool,
     pub max_active_agents: i64,
     pub is_auxiliary: bool,
+    /// `usefulness_v1` score computed at index time.
+    pub session_score: i64,
+    /// Comma-separated issue references (`PROJ-123`, `#456`) found at index time.
+    pub issue_refs: Option<String>,
+    /// JSON [`SessionEnvironment`](crate::environment::SessionEnvironment) recorded at index time.
+    pub environment: Option<String>,
 }
 
 /// A lightweight local link row for session-to-session relationships.
@@ -60,21 +73,13 @@ pub struct LocalSessionLink {
 }
 
 pub(crate) fn infer_tool_from_source_path(source_path: Option<&str>) -> Option<&'static str> {
-    let source_path = source_path.map(|path| path.to_ascii_lowercase())?;
+    let source_path = source_path.map(|path| path.to_ascii_lowercase().replace('\\', "/"))?;
 
-    if source_path.contains("/.codex/sessions/")
-        || source_path.contains("\\.codex\\sessions\\")
-        || source_path.contains("/codex/sessions/")
-        || source_path.contains("\\codex\\sessions\\")
-    {
+    if source_path.contains("/.codex/sessions/") || source_path.contains("/codex/sessions/") {
         return Some("codex");
     }
 
-    if source_path.contains("/.claude/projects/")
-        || source_path.contains("\\.claude\\projects\\")
-        || source_path.contains("/claude/projects/")
-        || source_path.contains("\\claude\\projects\\")
-    {
+    if source_path.contains("/.claude/projects/") || source_path.contains("/claude/projects/") {
         return Some("claude-code");
     }
 
@@ -209,6 +214,8 @@ pub enum LocalSortOrder {
     Recent,
     Popular,
     Longest,
+    /// Highest `usefulness_v1` score first.
+    Best,
 }
 
 /// Time range filter for local session listing.
@@ -276,10 +283,15 @@ pub struct LogFilter {
     pub working_directory: Option<String>,
     /// Filter by git repo name.
     pub git_repo_name: Option<String>,
+    /// Filter by issue reference (exact, e.g. `PROJ-123` or `#456`).
+    pub issue: Option<String>,
     /// Maximum number of results.
     pub limit: Option<u32>,
     /// Offset for pagination.
     pub offset: Option<u32>,
+    /// Select only auxiliary sessions (sub-agents, summary workers) instead of
+    /// hiding them.
+    pub auxiliary_only: bool,
 }
 
 /// Base FROM clause for session list queries.
@@ -288,138 +300,212 @@ FROM sessions s \
 LEFT JOIN session_sync ss ON ss.session_id = s.id \
 LEFT JOIN users u ON u.id = s.user_id";
 
-pub(crate) const LOCAL_SESSION_COLUMNS: &str = "\
-s.id, ss.source_path, COALESCE(ss.sync_status, 'unknown') AS sync_status, ss.last_synced_at, \
-s.user_id, u.nickname, s.team_id, s.tool, s.agent_provider, s.agent_model, \
-s.title, s.description, s.tags, s.created_at, s.uploaded_at, \
-s.message_count, COALESCE(s.user_message_count, 0), s.task_count, s.event_count, s.duration_seconds, \
-s.total_input_tokens, s.total_output_tokens, \
-s.git_remote, s.git_branch, s.git_commit, s.git_repo_name, \
-s.pr_number, s.pr_url, s.working_directory, \
-s.files_modified, s.files_read, s.has_errors, COALESCE(s.max_active_agents, 1), COALESCE(s.is_auxiliary, 0)";
+/// Declares the SELECT expression behind each [`LocalSessionRow`] field once,
+/// and derives from that list both [`LOCAL_SESSION_COLUMNS`] and the mapper
+/// that reads a result row back. Positions come from declaration order, and
+/// the generated struct literal fails to compile if a field is missing or
+/// listed twice, so the two can no longer drift apart.
+macro_rules! local_session_columns {
+    ($first_field:ident: $first_sql:literal $(, $field:ident: $sql:literal)* $(,)?) => {
+        pub(crate) const LOCAL_SESSION_COLUMNS: &str = concat!($first_sql $(, ", ", $sql)*);
+
+        /// Number of entries in [`LOCAL_SESSION_COLUMNS`].
+        pub(crate) const LOCAL_SESSION_COLUMN_COUNT: usize = [stringify!($first_field) $(, stringify!($field))*].len();
+
+        fn read_local_session_columns(row: &rusqlite::Row) -> rusqlite::Result<LocalSessionRow> {
+            #[allow(non_camel_case_types)]
+            enum Column {
+                $first_field,
+                $($field,)*
+            }
+
+            Ok(LocalSessionRow {
+                $first_field: row.get(Column::$first_field as usize)?,
+                $($field: row.get(Column::$field as usize)?,)*
+            })
+        }
+    };
+}
+
+local_session_columns! {
+    id: "s.id",
+    source_path: "ss.source_path",
+    sync_status: "COALESCE(ss.sync_status, 'unknown') AS sync_status",
+    last_synced_at: "ss.last_synced_at",
+    user_id: "s.user_id",
+    nickname: "u.nickname",
+    team_id: "s.team_id",
+    tool: "s.tool",
+    agent_provider: "s.agent_provider",
+    agent_model: "s.agent_model",
+    title: "s.title",
+    description: "s.description",
+    tags: "s.tags",
+    created_at: "s.created_at",
+    uploaded_at: "s.uploaded_at",
+    message_count: "s.message_count",
+    user_message_count: "COALESCE(s.user_message_count, 0)",
+    task_count: "s.task_count",
+    event_count: "s.event_count",
+    duration_seconds: "s.duration_seconds",
+    total_input_tokens: "s.total_input_tokens",
+    total_output_tokens: "s.total_output_tokens",
+    git_remote: "s.git_remote",
+    git_branch: "s.git_branch",
+    git_commit: "s.git_commit",
+    git_repo_name: "s.git_repo_name",
+    pr_number: "s.pr_number",
+    pr_url: "s.pr_url",
+    working_directory: "s.working_directory",
+    files_modified: "s.files_modified",
+    files_read: "s.files_read",
+    has_errors: "COALESCE(s.has_errors, 0)",
+    max_active_agents: "COALESCE(s.max_active_agents, 1)",
+    is_auxiliary: "COALESCE(s.is_auxiliary, 0)",
+    session_score: "COALESCE(s.session_score, 0)",
+    issue_refs: "s.issue_refs",
+    environment: "s.environment",
+}
 
 pub(crate) fn row_to_local_session(row: &rusqlite::Row) -> rusqlite::Result<LocalSessionRow> {
-    let source_path: Option<String> = row.get(1)?;
-    let tool: String = row.get(7)?;
-    let normalized_tool = normalize_tool_for_source_path(&tool, source_path.as_deref());
-
-    Ok(LocalSessionRow {
-        id: row.get(0)?,
-        source_path,
-        sync_status: row.get(2)?,
-        last_synced_at: row.get(3)?,
-        user_id: row.get(4)?,
-        nickname: row.get(5)?,
-        team_id: row.get(6)?,
-        tool: normalized_tool,
-        agent_provider: row.get(8)?,
-        agent_model: row.get(9)?,
-        title: row.get(10)?,
-        description: row.get(11)?,
-        tags: row.get(12)?,
-        created_at: row.get(13)?,
-        uploaded_at: row.get(14)?,
-        message_count: row.get(15)?,
-        user_message_count: row.get(16)?,
-        task_count: row.get(17)?,
-        event_count: row.get(18)?,
-        duration_seconds: row.get(19)?,
-        total_input_tokens: row.get(20)?,
-        total_output_tokens: row.get(21)?,
-        git_remote: row.get(22)?,
-        git_branch: row.get(23)?,
-        git_commit: row.get(24)?,
-        git_repo_name: row.get(25)?,
-        pr_number: row.get(26)?,
-        pr_url: row.get(27)?,
-        working_directory: row.get(28)?,
-        files_modified: row.get(29)?,
-        files_read: row.get(30)?,
-        has_errors: row.get::<_, i64>(31).unwrap_or(0) != 0,
-        max_active_agents: row.get(32).unwrap_or(1),
-        is_auxiliary: row.get::<_, i64>(33).unwrap_or(0) != 0,
-    })
+    let mut session = read_local_session_columns(row)?;
+    session.tool = normalize_tool_for_source_path(&session.tool, session.source_path.as_deref());
+    Ok(session)
 }
 
-impl LocalDb {
-    pub(crate) fn build_local_session_where_clause(
-        filter: &LocalSessionFilter,
-    ) -> (String, Vec<Box<dyn rusqlite::types::ToSql>>) {
-        let mut where_clauses = vec![
-            "1=1".to_string(),
-            "COALESCE(s.is_auxiliary, 0) = 0".to_string(),
-            format!(
-                "NOT (LOWER(COALESCE(s.tool, '')) = 'codex' \
-                 AND LOWER(COALESCE(s.title, '')) LIKE '{}%')",
-                SUMMARY_WORKER_TITLE_PREFIX_LOWER
-            ),
-        ];
-        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-        let mut idx = 1u32;
-
-        if let Some(ref team_id) = filter.team_id {
-            where_clauses.push(format!("s.team_id = ?{idx}"));
-            param_values.push(Box::new(team_id.clone()));
-            idx += 1;
-        }
+/// `sessions s` rows produced by the summary worker rather than a user.
+fn summary_worker_condition() -> SimpleExpr {
+    Expr::cust_with_values(
+        "(LOWER(COALESCE(s.tool, '')) = 'codex' AND LOWER(COALESCE(s.title, '')) LIKE ?)",
+        [format!("{SUMMARY_WORKER_TITLE_PREFIX_LOWER}%")],
+    )
+}
 
-        if let Some(ref sync_status) = filter.sync_status {
-            where_clauses.push(format!("COALESCE(ss.sync_status, 'unknown') = ?{idx}"));
-            param_values.push(Box::new(sync_status.clone()));
-            idx += 1;
-        }
+fn is_auxiliary_condition() -> SimpleExpr {
+    Expr::expr(Func::coalesce([
+        s(Alias::new("is_auxiliary")).into(),
+        Expr::val(0).into(),
+    ]))
+    .eq(1)
+}
 
-        if let Some(ref repo) = filter.git_repo_name {
-            where_clauses.push(format!("s.git_repo_name = ?{idx}"));
-            param_values.push(Box::new(repo.clone()));
-            idx += 1;
-        }
+fn time_range_condition(time_range: &LocalTimeRange) -> Option<SimpleExpr> {
+    let interval = match time_range {
+        LocalTimeRange::Hours24 => "-1 day",
+        LocalTimeRange::Days7 => "-7 days",
+        LocalTimeRange::Days30 => "-30 days",
+        LocalTimeRange::All => return None,
+    };
+    Some(Expr::cust_with_values(
+        "datetime(s.created_at) >= datetime('now', ?)",
+        [interval],
+    ))
+}
 
-        if let Some(ref tool) = filter.tool {
-            where_clauses.push(format!("s.tool = ?{idx}"));
-            param_values.push(Box::new(tool.clone()));
-            idx += 1;
-        }
+fn text_search_condition(needle: &str) -> SimpleExpr {
+    let like = format!("%{needle}%");
+    s(Sessions::Title)
+        .like(&like)
+        .or(s(Sessions::Description).like(&like))
+        .or(s(Sessions::Tags).like(&like))
+}
 
-        if let Some(ref search) = filter.search {
-            let like = format!("%{search}%");
-            where_clauses.push(format!(
-                "(s.title LIKE ?{i1} OR s.description LIKE ?{i2} OR s.tags LIKE ?{i3})",
-                i1 = idx,
-                i2 = idx + 1,
-                i3 = idx + 2,
-            ));
-            param_values.push(Box::new(like.clone()));
-            param_values.push(Box::new(like.clone()));
-            param_values.push(Box::new(like));
-            idx += 3;
-        }
+/// WHERE conditions for [`LocalDb::list_sessions`] and its count/tool queries.
+pub(crate) fn local_session_conditions(filter: &LocalSessionFilter) -> Vec<SimpleExpr> {
+    let mut conds = vec![
+        is_auxiliary_condition().not(),
+        summary_worker_condition().not(),
+    ];
 
-        if filter.exclude_low_signal {
-            where_clauses.push(
-                "NOT (COALESCE(s.message_count, 0) = 0 \
-                  AND COALESCE(s.user_message_count, 0) = 0 \
-                  AND COALESCE(s.task_count, 0) = 0 \
-                  AND COALESCE(s.event_count, 0) <= 2 \
-                  AND (s.title IS NULL OR TRIM(s.title) = ''))"
-                    .to_string(),
-            );
-        }
+    if let Some(ref team_id) = filter.team_id {
+        conds.push(s(Sessions::TeamId).eq(team_id.as_str()));
+    }
+    if let Some(ref sync_status) = filter.sync_status {
+        conds.push(
+            Expr::expr(Func::coalesce([
+                Expr::col((Alias::new("ss"), Alias::new("sync_status"))).into(),
+                Expr::val("unknown").into(),
+            ]))
+            .eq(sync_status.as_str()),
+        );
+    }
+    if let Some(ref repo) = filter.git_repo_name {
+        conds.push(s(Sessions::GitRepoName).eq(repo.as_str()));
+    }
+    if let Some(ref tool) = filter.tool {
+        conds.push(s(Sessions::Tool).eq(tool.as_str()));
+    }
+    if let Some(ref search) = filter.search {
+        conds.push(text_search_condition(search));
+    }
+    if filter.exclude_low_signal {
+        conds.push(Expr::cust(
+            "NOT (COALESCE(s.message_count, 0) = 0 \
+              AND COALESCE(s.user_message_count, 0) = 0 \
+              AND COALESCE(s.task_count, 0) = 0 \
+              AND COALESCE(s.event_count, 0) <= 2 \
+              AND (s.title IS NULL OR TRIM(s.title) = ''))",
+        ));
+    }
+    conds.extend(time_range_condition(&filter.time_range));
+    conds
+}
 
-        let interval = match filter.time_range {
-            LocalTimeRange::Hours24 => Some("-1 day"),
-            LocalTimeRange::Days7 => Some("-7 days"),
-            LocalTimeRange::Days30 => Some("-30 days"),
-            LocalTimeRange::All => None,
-        };
-        if let Some(interval) = interval {
-            where_clauses.push(format!("datetime(s.created_at) >= datetime('now', ?{idx})"));
-            param_values.push(Box::new(interval.to_string()));
-        }
+/// WHERE conditions for [`LocalDb::list_sessions_log`].
+pub(crate) fn log_conditions(filter: &LogFilter) -> Vec<SimpleExpr> {
+    let auxiliary = is_auxiliary_condition().or(summary_worker_condition());
+    let mut conds = vec![if filter.auxiliary_only {
+        auxiliary
+    } else {
+        auxiliary.not()
+    }];
+
+    if let Some(ref tool) = filter.tool {
+        conds.push(s(Sessions::Tool).eq(tool.as_str()));
+    }
+    if let Some(ref model) = filter.model {
+        conds.push(s(Sessions::AgentModel).like(model.replace('*', "%")));
+    }
+    if let Some(ref since) = filter.since {
+        conds.push(s(Sessions::CreatedAt).gte(since.as_str()));
+    }
+    if let Some(ref before) = filter.before {
+        conds.push(s(Sessions::CreatedAt).lt(before.as_str()));
+    }
+    if let Some(ref touches) = filter.touches {
+        conds.push(s(Sessions::FilesModified).like(format!("%\"{touches}\"%")));
+    }
+    if let Some(ref grep) = filter.grep {
+        conds.push(text_search_condition(grep));
+    }
+    if let Some(true) = filter.has_errors {
+        conds.push(s(Sessions::HasErrors).eq(1));
+    }
+    if let Some(ref wd) = filter.working_directory {
+        conds.push(s(Sessions::WorkingDirectory).like(format!("{wd}%")));
+    }
+    if let Some(ref repo) = filter.git_repo_name {
+        conds.push(s(Sessions::GitRepoName).eq(repo.as_str()));
+    }
+    if let Some(ref issue) = filter.issue {
+        conds.push(Expr::cust_with_values(
+            "(',' || COALESCE(s.issue_refs, '') || ',') LIKE ?",
+            [format!("%,{issue},%")],
+        ));
+    }
+    conds
+}
 
-        (where_clauses.join(" AND "), param_values)
+fn limit_offset(select: &mut SelectStatement, limit: Option<u32>, offset: Option<u32>) {
+    if let Some(limit) = limit {
+        select.limit(limit.into());
+        if let Some(offset) = offset {
+            select.offset(offset.into());
+        }
     }
+}
 
+impl LocalDb {
     pub fn upsert_local_session(
         &self,
         session: &Session,
@@ -449,11 +535,15 @@ impl LocalDb {
         let (files_modified, files_read, has_errors) =
             opensession_core::extract::extract_file_metadata(session);
         let max_active_agents = opensession_core::agent_metrics::max_active_agents(session) as i64;
+        let session_score = usefulness_score(session);
         let normalized_tool =
             normalize_tool_for_source_path(&session.agent.tool, Some(source_path));
         let git_from_session = git_context_from_session_attributes(session);
         let has_session_git = git_context_has_any_field(&git_from_session);
         let merged_git = merge_git_context(&git_from_session, git);
+        let issue_refs =
+            opensession_core::issue_refs::session_issue_refs(session, merged_git.branch.as_deref());
+        let issue_refs = (!issue_refs.is_empty()).then(|| issue_refs.join(","));
 
         let conn = self.conn();
         conn.execute(
@@ -463,8 +553,8 @@ impl LocalDb {
              message_count, user_message_count, task_count, event_count, duration_seconds, \
               total_input_tokens, total_output_tokens, body_storage_key, \
               git_remote, git_branch, git_commit, git_repo_name, working_directory, \
-              files_modified, files_read, has_errors, max_active_agents, is_auxiliary) \
-             VALUES (?1,'personal',?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,'',?16,?17,?18,?19,?20,?21,?22,?23,?24,?25) \
+              files_modified, files_read, has_errors, max_active_agents, is_auxiliary, session_score, score_plugin, issue_refs) \
+             VALUES (?1,'personal',?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,'',?16,?17,?18,?19,?20,?21,?22,?23,?24,?25,?27,'usefulness_v1',?28) \
              ON CONFLICT(id) DO UPDATE SET \
               tool=excluded.tool, agent_provider=excluded.agent_provider, \
               agent_model=excluded.agent_model, \
@@ -483,7 +573,9 @@ impl LocalDb {
               files_modified=excluded.files_modified, files_read=excluded.files_read, \
               has_errors=excluded.has_errors, \
               max_active_agents=excluded.max_active_agents, \
-              is_auxiliary=excluded.is_auxiliary",
+              is_auxiliary=excluded.is_auxiliary, \
+              session_score=excluded.session_score, score_plugin=excluded.score_plugin, \
+              issue_refs=excluded.issue_refs",
             params![
                 &session.session_id,
                 &normalized_tool,
@@ -511,18 +603,55 @@ impl LocalDb {
                 max_active_agents,
                 is_auxiliary as i64,
                 has_session_git as i64,
+                session_score,
+                &issue_refs,
             ],
         )?;
 
         conn.execute(
             "INSERT INTO session_sync (session_id, source_path, sync_status) \
              VALUES (?1, ?2, 'local_only') \
-             ON CONFLICT(session_id) DO UPDATE SET source_path=excluded.source_path",
+             ON CONFLICT(session_id) DO UPDATE SET \
+              source_path=excluded.source_path, source_missing_at=NULL",
             params![&session.session_id, source_path],
         )?;
         Ok(())
     }
 
+    /// Assign a locally indexed session to a team (see `[[routes]]` in the runtime config).
+    pub fn set_session_team(&self, session_id: &str, team_id: &str) -> Result<()> {
+        self.conn().execute(
+            "UPDATE sessions SET team_id = ?2 WHERE id = ?1",
+            params![session_id, team_id],
+        )?;
+        Ok(())
+    }
+
+    /// Record where a locally indexed session ran (see `privacy.capture_environment`).
+    pub fn set_session_environment(
+        &self,
+        session_id: &str,
+        environment: &SessionEnvironment,
+    ) -> Result<()> {
+        self.conn().execute(
+            "UPDATE sessions SET environment = ?2 WHERE id = ?1",
+            params![session_id, serde_json::to_string(environment)?],
+        )?;
+        Ok(())
+    }
+
+    pub fn has_session_environment(&self, session_id: &str) -> Result<bool> {
+        let found = self
+            .conn()
+            .query_row(
+                "SELECT environment IS NOT NULL FROM sessions WHERE id = ?1",
+                params![session_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(found.unwrap_or(false))
+    }
+
     pub fn upsert_remote_session(&self, summary: &RemoteSessionSummary) -> Result<()> {
         let conn = self.conn();
         conn.execute(
@@ -593,186 +722,77 @@ impl LocalDb {
     }
 
     pub fn list_sessions(&self, filter: &LocalSessionFilter) -> Result<Vec<LocalSessionRow>> {
-        let (where_str, mut param_values) = Self::build_local_session_where_clause(filter);
-        let order_clause = match filter.sort {
-            LocalSortOrder::Popular => "s.message_count DESC, s.created_at DESC",
-            LocalSortOrder::Longest => "s.duration_seconds DESC, s.created_at DESC",
-            LocalSortOrder::Recent => "s.created_at DESC",
-        };
-
-        let mut sql = format!(
-            "SELECT {LOCAL_SESSION_COLUMNS} \
-             {FROM_CLAUSE} WHERE {where_str} \
-             ORDER BY {order_clause}"
-        );
-
-        if let Some(limit) = filter.limit {
-            sql.push_str(" LIMIT ?");
-            param_values.push(Box::new(limit));
-            if let Some(offset) = filter.offset {
-                sql.push_str(" OFFSET ?");
-                param_values.push(Box::new(offset));
+        let mut select = select_sessions();
+        select.expr(Expr::cust(LOCAL_SESSION_COLUMNS));
+        and_where_all(&mut select, local_session_conditions(filter));
+        match filter.sort {
+            LocalSortOrder::Popular => {
+                select.order_by_expr(s(Sessions::MessageCount).into(), Order::Desc);
+            }
+            LocalSortOrder::Longest => {
+                select.order_by_expr(s(Sessions::DurationSeconds).into(), Order::Desc);
+            }
+            LocalSortOrder::Best => {
+                select.order_by_expr(s(Sessions::SessionScore).into(), Order::Desc);
             }
+            LocalSortOrder::Recent => {}
         }
+        select.order_by_expr(s(Sessions::CreatedAt).into(), Order::Desc);
+        limit_offset(&mut select, filter.limit, filter.offset);
 
-        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
-            param_values.iter().map(|p| p.as_ref()).collect();
         let conn = self.conn();
-        let mut stmt = conn.prepare(&sql)?;
-        let rows = stmt.query_map(param_refs.as_slice(), row_to_local_session)?;
-
-        let mut result = Vec::new();
-        for row in rows {
-            result.push(row?);
-        }
-
-        Ok(result)
+        Ok(sq_query_map(
+            &conn,
+            select.build(SqliteQueryBuilder),
+            row_to_local_session,
+        )?)
     }
 
     pub fn count_sessions_filtered(&self, filter: &LocalSessionFilter) -> Result<i64> {
-        let mut count_filter = filter.clone();
-        count_filter.limit = None;
-        count_filter.offset = None;
-        let (where_str, param_values) = Self::build_local_session_where_clause(&count_filter);
-        let sql = format!("SELECT COUNT(*) {FROM_CLAUSE} WHERE {where_str}");
-        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
-            param_values.iter().map(|p| p.as_ref()).collect();
+        let mut select = select_sessions();
+        select.expr(Func::count(Expr::col(Asterisk)));
+        and_where_all(&mut select, local_session_conditions(filter));
         let conn = self.conn();
-        let count = conn.query_row(&sql, param_refs.as_slice(), |row| row.get(0))?;
-        Ok(count)
+        Ok(sq_query_row(
+            &conn,
+            select.build(SqliteQueryBuilder),
+            |row| row.get(0),
+        )?)
     }
 
     pub fn list_session_tools(&self, filter: &LocalSessionFilter) -> Result<Vec<String>> {
         let mut tool_filter = filter.clone();
         tool_filter.tool = None;
-        tool_filter.limit = None;
-        tool_filter.offset = None;
-        let (where_str, param_values) = Self::build_local_session_where_clause(&tool_filter);
-        let sql = format!(
-            "SELECT DISTINCT s.tool \
-             {FROM_CLAUSE} WHERE {where_str} \
-             ORDER BY s.tool ASC"
-        );
-        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
-            param_values.iter().map(|p| p.as_ref()).collect();
+        let mut select = select_sessions();
+        select
+            .distinct()
+            .expr(s(Sessions::Tool))
+            .order_by_expr(s(Sessions::Tool).into(), Order::Asc);
+        and_where_all(&mut select, local_session_conditions(&tool_filter));
         let conn = self.conn();
-        let mut stmt = conn.prepare(&sql)?;
-        let rows = stmt.query_map(param_refs.as_slice(), |row| row.get::<_, String>(0))?;
-
-        let mut tools = Vec::new();
-        for row in rows {
-            let tool = row?;
-            if !tool.trim().is_empty() {
-                tools.push(tool);
-            }
-        }
-        Ok(tools)
+        let tools = sq_query_map(&conn, select.build(SqliteQueryBuilder), |row| {
+            row.get::<_, String>(0)
+        })?;
+        Ok(tools
+            .into_iter()
+            .filter(|tool| !tool.trim().is_empty())
+            .collect())
     }
 
     pub fn list_sessions_log(&self, filter: &LogFilter) -> Result<Vec<LocalSessionRow>> {
-        let mut where_clauses = vec![
-            "1=1".to_string(),
-            "COALESCE(s.is_auxiliary, 0) = 0".to_string(),
-            format!(
-                "NOT (LOWER(COALESCE(s.tool, '')) = 'codex' \
-                 AND LOWER(COALESCE(s.title, '')) LIKE '{}%')",
-                SUMMARY_WORKER_TITLE_PREFIX_LOWER
-            ),
-        ];
-        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-        let mut idx = 1u32;
-
-        if let Some(ref tool) = filter.tool {
-            where_clauses.push(format!("s.tool = ?{idx}"));
-            param_values.push(Box::new(tool.clone()));
-            idx += 1;
-        }
-
-        if let Some(ref model) = filter.model {
-            let like = model.replace('*', "%");
-            where_clauses.push(format!("s.agent_model LIKE ?{idx}"));
-            param_values.push(Box::new(like));
-            idx += 1;
-        }
-
-        if let Some(ref since) = filter.since {
-            where_clauses.push(format!("s.created_at >= ?{idx}"));
-            param_values.push(Box::new(since.clone()));
-            idx += 1;
-        }
+        let mut select = select_sessions();
+        select
+            .expr(Expr::cust(LOCAL_SESSION_COLUMNS))
+            .order_by_expr(s(Sessions::CreatedAt).into(), Order::Desc);
+        and_where_all(&mut select, log_conditions(filter));
+        limit_offset(&mut select, filter.limit, filter.offset);
 
-        if let Some(ref before) = filter.before {
-            where_clauses.push(format!("s.created_at < ?{idx}"));
-            param_values.push(Box::new(before.clone()));
-            idx += 1;
-        }
-
-        if let Some(ref touches) = filter.touches {
-            let like = format!("%\"{touches}\"%");
-            where_clauses.push(format!("s.files_modified LIKE ?{idx}"));
-            param_values.push(Box::new(like));
-            idx += 1;
-        }
-
-        if let Some(ref grep) = filter.grep {
-            let like = format!("%{grep}%");
-            where_clauses.push(format!(
-                "(s.title LIKE ?{i1} OR s.description LIKE ?{i2} OR s.tags LIKE ?{i3})",
-                i1 = idx,
-                i2 = idx + 1,
-                i3 = idx + 2,
-            ));
-            param_values.push(Box::new(like.clone()));
-            param_values.push(Box::new(like.clone()));
-            param_values.push(Box::new(like));
-            idx += 3;
-        }
-
-        if let Some(true) = filter.has_errors {
-            where_clauses.push("s.has_errors = 1".to_string());
-        }
-
-        if let Some(ref wd) = filter.working_directory {
-            where_clauses.push(format!("s.working_directory LIKE ?{idx}"));
-            param_values.push(Box::new(format!("{wd}%")));
-            idx += 1;
-        }
-
-        if let Some(ref repo) = filter.git_repo_name {
-            where_clauses.push(format!("s.git_repo_name = ?{idx}"));
-            param_values.push(Box::new(repo.clone()));
-            idx += 1;
-        }
-
-        let _ = idx;
-
-        let where_str = where_clauses.join(" AND ");
-        let mut sql = format!(
-            "SELECT {LOCAL_SESSION_COLUMNS} \
-             {FROM_CLAUSE} WHERE {where_str} \
-             ORDER BY s.created_at DESC"
-        );
-
-        if let Some(limit) = filter.limit {
-            sql.push_str(" LIMIT ?");
-            param_values.push(Box::new(limit));
-            if let Some(offset) = filter.offset {
-                sql.push_str(" OFFSET ?");
-                param_values.push(Box::new(offset));
-            }
-        }
-
-        let param_refs: Vec<&dyn rusqlite::types::ToSql> =
-            param_values.iter().map(|p| p.as_ref()).collect();
         let conn = self.conn();
-        let mut stmt = conn.prepare(&sql)?;
-        let rows = stmt.query_map(param_refs.as_slice(), row_to_local_session)?;
-
-        let mut result = Vec::new();
-        for row in rows {
-            result.push(row?);
-        }
-        Ok(result)
+        Ok(sq_query_map(
+            &conn,
+            select.build(SqliteQueryBuilder),
+            row_to_local_session,
+        )?)
     }
 
     pub fn get_sessions_by_tool_latest(
@@ -953,7 +973,7 @@ impl LocalDb {
              event_count=?7, duration_seconds=?8, \
              total_input_tokens=?9, total_output_tokens=?10, \
               files_modified=?11, files_read=?12, has_errors=?13, \
-             max_active_agents=?14, is_auxiliary=?15 \
+             max_active_agents=?14, is_auxiliary=?15, session_score=?16, score_plugin='usefulness_v1' \
              WHERE id=?1",
             params![
                 &session.session_id,
@@ -971,8 +991,13 @@ impl LocalDb {
                 has_errors,
                 max_active_agents,
                 is_auxiliary as i64,
+                usefulness_score(session),
             ],
         )?;
         Ok(())
     }
 }
+
+fn usefulness_score(session: &Session) -> i64 {
+    opensession_core::scoring::UsefulnessV1ScorePlugin.score(session)
+}
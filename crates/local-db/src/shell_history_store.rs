@@ -0,0 +1,275 @@
+//! Manually run shell commands recorded by the opt-in shell hook.
+//!
+//! Commands are kept in `shell_commands` independently of any session and
+//! matched at read time: a command belongs to a session when it started inside
+//! the session's time window and in its working directory (or below it).
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use opensession_core::session::working_directory;
+use opensession_core::trace::{ATTR_SOURCE_RAW_TYPE, Content, Event, EventType, Session};
+use rusqlite::params;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::connection::LocalDb;
+
+/// `source.raw_type` of events interleaved from shell history.
+pub const SHELL_HISTORY_RAW_TYPE: &str = "shell_history";
+
+/// A command reported by the shell hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellCommandRecord {
+    pub command: String,
+    pub cwd: String,
+    /// Unix seconds.
+    pub started_at: i64,
+    pub exit_code: Option<i32>,
+    pub shell: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellCommandRow {
+    pub id: i64,
+    pub record: ShellCommandRecord,
+}
+
+impl LocalDb {
+    pub fn record_shell_command(&self, record: &ShellCommandRecord) -> Result<i64> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO shell_commands (command, cwd, started_at, exit_code, shell) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                record.command,
+                record.cwd,
+                record.started_at,
+                record.exit_code,
+                record.shell
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Most recent commands first.
+    pub fn list_shell_commands(&self, limit: usize) -> Result<Vec<ShellCommandRow>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, command, cwd, started_at, exit_code, shell FROM shell_commands \
+             ORDER BY started_at DESC, id DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(
+            params![i64::try_from(limit).unwrap_or(i64::MAX)],
+            shell_command_from_row,
+        )?;
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+
+    /// Commands started within `[start, end]` in `cwd` or a directory below it,
+    /// oldest first.
+    pub fn shell_commands_in_window(
+        &self,
+        cwd: &str,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<ShellCommandRow>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, command, cwd, started_at, exit_code, shell FROM shell_commands \
+             WHERE started_at BETWEEN ?1 AND ?2 ORDER BY started_at, id",
+        )?;
+        let rows = stmt
+            .query_map(params![start, end], shell_command_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let root = Path::new(cwd);
+        Ok(rows
+            .into_iter()
+            .filter(|row| Path::new(&row.record.cwd).starts_with(root))
+            .collect())
+    }
+
+    /// Delete commands started before `before` (Unix seconds); returns how many.
+    pub fn prune_shell_commands(&self, before: i64) -> Result<usize> {
+        let removed = self.conn().execute(
+            "DELETE FROM shell_commands WHERE started_at < ?1",
+            params![before],
+        )?;
+        Ok(removed)
+    }
+
+    /// Insert matching shell commands into `session.events` as `ShellCommand`
+    /// events tagged `source.raw_type = shell_history`, each placed before the
+    /// first event that happened after it. Sessions without a working
+    /// directory are left unchanged; stats keep describing the agent's work.
+    /// Returns how many events were added.
+    pub fn interleave_shell_history(&self, session: &mut Session) -> Result<usize> {
+        let Some(cwd) = working_directory(session).map(str::to_string) else {
+            return Ok(0);
+        };
+        let (start, end) = session_window(session);
+        let commands = self.shell_commands_in_window(&cwd, start.timestamp(), end.timestamp())?;
+
+        let existing = session
+            .events
+            .iter()
+            .map(|event| event.event_id.clone())
+            .collect::<HashSet<_>>();
+        let mut added = 0;
+        for row in commands {
+            let event = shell_command_event(&row);
+            if existing.contains(&event.event_id) {
+                continue;
+            }
+            let index = session
+                .events
+                .iter()
+                .position(|other| other.timestamp > event.timestamp)
+                .unwrap_or(session.events.len());
+            session.events.insert(index, event);
+            added += 1;
+        }
+        Ok(added)
+    }
+}
+
+fn session_window(session: &Session) -> (DateTime<Utc>, DateTime<Utc>) {
+    let timestamps = session.events.iter().map(|event| event.timestamp);
+    let start = timestamps
+        .clone()
+        .min()
+        .map_or(session.context.created_at, |first| {
+            first.min(session.context.created_at)
+        });
+    let end = timestamps.max().map_or(session.context.updated_at, |last| {
+        last.max(session.context.updated_at)
+    });
+    (start, end)
+}
+
+fn shell_command_event(row: &ShellCommandRow) -> Event {
+    let record = &row.record;
+    let mut attributes = HashMap::from([
+        (
+            ATTR_SOURCE_RAW_TYPE.to_string(),
+            serde_json::Value::from(SHELL_HISTORY_RAW_TYPE),
+        ),
+        (
+            "cwd".to_string(),
+            serde_json::Value::from(record.cwd.clone()),
+        ),
+    ]);
+    if let Some(shell) = &record.shell {
+        attributes.insert("shell".to_string(), serde_json::Value::from(shell.clone()));
+    }
+    Event {
+        event_id: format!("shell-history-{}", row.id),
+        timestamp: DateTime::from_timestamp(record.started_at, 0).unwrap_or_default(),
+        event_type: EventType::ShellCommand {
+            command: record.command.clone(),
+            exit_code: record.exit_code,
+        },
+        task_id: None,
+        content: Content::code(record.command.clone(), record.shell.clone()),
+        duration_ms: None,
+        attributes,
+    }
+}
+
+fn shell_command_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ShellCommandRow> {
+    Ok(ShellCommandRow {
+        id: row.get(0)?,
+        record: ShellCommandRecord {
+            command: row.get(1)?,
+            cwd: row.get(2)?,
+            started_at: row.get(3)?,
+            exit_code: row.get(4)?,
+            shell: row.get(5)?,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opensession_core::trace::Agent;
+    use tempfile::tempdir;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    fn record(command: &str, cwd: &str, started_at: i64) -> ShellCommandRecord {
+        ShellCommandRecord {
+            command: command.to_string(),
+            cwd: cwd.to_string(),
+            started_at,
+            exit_code: Some(0),
+            shell: Some("zsh".to_string()),
+        }
+    }
+
+    fn session() -> Session {
+        let mut session = Session::new(
+            "s1".to_string(),
+            Agent {
+                provider: "openai".to_string(),
+                model: "gpt-5".to_string(),
+                tool: "codex".to_string(),
+                tool_version: None,
+            },
+        );
+        session.context.created_at = at(1_000);
+        session.context.updated_at = at(1_300);
+        session
+            .context
+            .attributes
+            .insert("cwd".to_string(), "/work/repo".into());
+        for (id, secs) in [("u1", 1_000), ("a1", 1_100), ("u2", 1_200)] {
+            session.events.push(Event {
+                event_id: id.to_string(),
+                timestamp: at(secs),
+                event_type: EventType::UserMessage,
+                task_id: None,
+                content: Content::text(id),
+                duration_ms: None,
+                attributes: HashMap::new(),
+            });
+        }
+        session
+    }
+
+    #[test]
+    fn interleaves_commands_in_window_and_cwd() {
+        let dir = tempdir().unwrap();
+        let db = LocalDb::open_path(&dir.path().join("local.db")).unwrap();
+        db.record_shell_command(&record("cargo test", "/work/repo", 1_150))
+            .unwrap();
+        db.record_shell_command(&record("git status", "/work/repo/crates", 1_250))
+            .unwrap();
+        db.record_shell_command(&record("ls", "/work/other", 1_150))
+            .unwrap();
+        db.record_shell_command(&record("make", "/work/repo", 2_000))
+            .unwrap();
+
+        let mut session = session();
+        assert_eq!(db.interleave_shell_history(&mut session).unwrap(), 2);
+        let order = session
+            .events
+            .iter()
+            .map(|event| match &event.event_type {
+                EventType::ShellCommand { command, .. } => command.as_str(),
+                _ => event.event_id.as_str(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(order, ["u1", "a1", "cargo test", "u2", "git status"]);
+        assert_eq!(
+            session.events[2].source_raw_type(),
+            Some(SHELL_HISTORY_RAW_TYPE)
+        );
+
+        assert_eq!(db.interleave_shell_history(&mut session).unwrap(), 0);
+        assert_eq!(db.list_shell_commands(1).unwrap()[0].record.command, "make");
+        assert_eq!(db.prune_shell_commands(1_200).unwrap(), 2);
+    }
+}
@@ -0,0 +1,371 @@
+//! Month-bucketed `.tar.zst` archives for old session source files.
+//!
+//! Archiving appends one zstd frame holding a tar of the new members to
+//! `<dir>/<YYYY-MM>.tar.zst` (bucketed by file mtime), records the archive
+//! path and member name in `session_sync`, then removes the source file. The
+//! index rows stay, so archived sessions remain listable and their bodies can
+//! be read or restored from the archive.
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use rusqlite::{OptionalExtension, params};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::connection::LocalDb;
+
+const ARCHIVE_EXTENSION: &str = "tar.zst";
+const ZSTD_LEVEL: i32 = 9;
+
+/// Where an archived source file lives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceArchiveLocation {
+    pub archive_path: String,
+    pub member: String,
+}
+
+/// One source file selected for archival, shared by every session indexed from it.
+#[derive(Debug, Clone)]
+pub struct SourceArchiveCandidate {
+    pub source_path: String,
+    pub session_ids: Vec<String>,
+    pub bytes: u64,
+    pub archive_path: PathBuf,
+    pub member: String,
+}
+
+/// Outcome of [`LocalDb::archive_sources`].
+#[derive(Debug, Clone, Default)]
+pub struct SourceArchiveReport {
+    pub files: usize,
+    pub sessions: usize,
+    pub bytes: u64,
+    pub archives: Vec<PathBuf>,
+}
+
+impl LocalDb {
+    /// Archive location recorded for a session, if its source was archived.
+    pub fn get_session_source_archive(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<SourceArchiveLocation>> {
+        let location = self
+            .conn()
+            .query_row(
+                "SELECT archive_path, archive_member FROM session_sync \
+                 WHERE session_id = ?1 AND archive_path IS NOT NULL AND archive_member IS NOT NULL",
+                params![session_id],
+                |row| {
+                    Ok(SourceArchiveLocation {
+                        archive_path: row.get(0)?,
+                        member: row.get(1)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(location)
+    }
+
+    fn set_session_source_archive(
+        &self,
+        session_id: &str,
+        location: &SourceArchiveLocation,
+    ) -> Result<()> {
+        self.conn().execute(
+            "UPDATE session_sync SET archive_path = ?2, archive_member = ?3 WHERE session_id = ?1",
+            params![session_id, location.archive_path, location.member],
+        )?;
+        Ok(())
+    }
+
+    fn clear_source_archive(&self, location: &SourceArchiveLocation) -> Result<()> {
+        self.conn().execute(
+            "UPDATE session_sync SET archive_path = NULL, archive_member = NULL \
+             WHERE archive_path = ?1 AND archive_member = ?2",
+            params![location.archive_path, location.member],
+        )?;
+        Ok(())
+    }
+
+    /// Source files not modified for at least `older_than`, grouped per file.
+    pub fn plan_source_archive(
+        &self,
+        archive_dir: &Path,
+        older_than: Duration,
+    ) -> Result<Vec<SourceArchiveCandidate>> {
+        let cutoff = SystemTime::now()
+            .checked_sub(older_than)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        let mut by_path: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (session_id, source_path) in self.list_session_source_paths()? {
+            by_path.entry(source_path).or_default().push(session_id);
+        }
+
+        let mut candidates = Vec::new();
+        for (source_path, mut session_ids) in by_path {
+            session_ids.sort();
+            let Ok(metadata) = fs::metadata(&source_path) else {
+                continue;
+            };
+            let Ok(modified) = metadata.modified() else {
+                continue;
+            };
+            if !metadata.is_file() || modified > cutoff {
+                continue;
+            }
+            let month = DateTime::<Utc>::from(modified).format("%Y-%m");
+            candidates.push(SourceArchiveCandidate {
+                member: archive_member_name(Path::new(&source_path)),
+                archive_path: archive_dir.join(format!("{month}.{ARCHIVE_EXTENSION}")),
+                source_path,
+                session_ids,
+                bytes: metadata.len(),
+            });
+        }
+        Ok(candidates)
+    }
+
+    /// Move planned source files into their month archives.
+    ///
+    /// Each archive gets one appended frame; if writing it fails the archive is
+    /// truncated back to its previous length and no rows or files are touched.
+    pub fn archive_sources(
+        &self,
+        candidates: &[SourceArchiveCandidate],
+    ) -> Result<SourceArchiveReport> {
+        let mut by_archive: BTreeMap<&Path, Vec<&SourceArchiveCandidate>> = BTreeMap::new();
+        for candidate in candidates {
+            by_archive
+                .entry(candidate.archive_path.as_path())
+                .or_default()
+                .push(candidate);
+        }
+
+        let mut report = SourceArchiveReport::default();
+        for (archive_path, members) in by_archive {
+            append_archive_frame(archive_path, &members)
+                .with_context(|| format!("write archive {}", archive_path.display()))?;
+            report.archives.push(archive_path.to_path_buf());
+
+            let archive_path = archive_path.to_string_lossy().into_owned();
+            for candidate in members {
+                let location = SourceArchiveLocation {
+                    archive_path: archive_path.clone(),
+                    member: candidate.member.clone(),
+                };
+                for session_id in &candidate.session_ids {
+                    self.set_session_source_archive(session_id, &location)?;
+                }
+                fs::remove_file(&candidate.source_path)
+                    .with_context(|| format!("remove archived {}", candidate.source_path))?;
+                report.files += 1;
+                report.sessions += candidate.session_ids.len();
+                report.bytes += candidate.bytes;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Raw source bytes for a session, from its source file or its archive.
+    pub fn read_session_source(&self, session_id: &str) -> Result<Option<Vec<u8>>> {
+        if let Some(location) = self.get_session_source_archive(session_id)? {
+            return read_archive_member(Path::new(&location.archive_path), &location.member)
+                .map(Some);
+        }
+        if self.is_source_missing(session_id)? {
+            return Ok(None);
+        }
+        let Some(source_path) = self.get_session_source_path(session_id)? else {
+            return Ok(None);
+        };
+        match fs::read(&source_path) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).with_context(|| format!("read {source_path}")),
+        }
+    }
+
+    /// Write an archived source file back to its original path and forget the
+    /// archive location. Returns the restored path, or `None` if the session
+    /// was not archived. An existing file at that path is left untouched.
+    pub fn restore_session_source(&self, session_id: &str) -> Result<Option<PathBuf>> {
+        let Some(location) = self.get_session_source_archive(session_id)? else {
+            return Ok(None);
+        };
+        let Some(source_path) = self.get_session_source_path(session_id)? else {
+            bail!("session {session_id} is archived but has no source path");
+        };
+        let source_path = PathBuf::from(source_path);
+        if !source_path.exists() {
+            let bytes = read_archive_member(Path::new(&location.archive_path), &location.member)?;
+            if let Some(parent) = source_path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("create {}", parent.display()))?;
+            }
+            fs::write(&source_path, bytes)
+                .with_context(|| format!("restore {}", source_path.display()))?;
+        }
+        self.clear_source_archive(&location)?;
+        Ok(Some(source_path))
+    }
+}
+
+/// Tar member name for a source path: its normal components joined by `/`,
+/// so the member mirrors where the file came from.
+fn archive_member_name(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().replace(':', "_")),
+            Component::Prefix(_)
+            | Component::RootDir
+            | Component::CurDir
+            | Component::ParentDir => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn append_archive_frame(archive_path: &Path, members: &[&SourceArchiveCandidate]) -> Result<()> {
+    if let Some(parent) = archive_path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(archive_path)?;
+    let previous_len = file.seek(SeekFrom::End(0))?;
+
+    let written = write_frame(&file, members);
+    if written.is_err() {
+        file.set_len(previous_len).ok();
+    }
+    written?;
+    file.flush()?;
+    file.sync_all()?;
+    Ok(())
+}
+
+fn write_frame(file: &File, members: &[&SourceArchiveCandidate]) -> Result<()> {
+    let encoder = zstd::Encoder::new(file, ZSTD_LEVEL)?;
+    let mut builder = tar::Builder::new(encoder);
+    for candidate in members {
+        builder
+            .append_path_with_name(&candidate.source_path, &candidate.member)
+            .with_context(|| format!("add {}", candidate.source_path))?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Read `member` from a multi-frame archive. A member archived more than once
+/// (restored, then archived again) resolves to its latest copy.
+fn read_archive_member(archive_path: &Path, member: &str) -> Result<Vec<u8>> {
+    let file = File::open(archive_path)
+        .with_context(|| format!("open archive {}", archive_path.display()))?;
+    let mut archive = tar::Archive::new(zstd::Decoder::new(file)?);
+    archive.set_ignore_zeros(true);
+
+    let mut found = None;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.to_string_lossy() != member {
+            continue;
+        }
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        found = Some(bytes);
+    }
+    found.with_context(|| format!("{member} not found in {}", archive_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn add_source(db: &LocalDb, dir: &Path, session_id: &str, file_name: &str, body: &str) {
+        let path = dir.join("sources").join(file_name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, body).unwrap();
+        db.set_session_sync_path(session_id, &path.to_string_lossy())
+            .unwrap();
+    }
+
+    #[test]
+    fn archive_member_name_drops_root_and_prefix() {
+        assert_eq!(
+            archive_member_name(Path::new("/home/u/.codex/sessions/a.jsonl")),
+            "home/u/.codex/sessions/a.jsonl"
+        );
+        assert_eq!(
+            archive_member_name(Path::new("./x/../y.jsonl")),
+            "x/y.jsonl"
+        );
+    }
+
+    #[test]
+    fn archive_round_trip_reads_and_restores_sources() {
+        let dir = tempdir().unwrap();
+        let db = LocalDb::open_path(&dir.path().join("local.db")).unwrap();
+        add_source(&db, dir.path(), "s1", "a.jsonl", "first\n");
+        add_source(&db, dir.path(), "s2", "b/c.jsonl", "second\n");
+        let archive_dir = dir.path().join("archive");
+
+        assert!(
+            db.plan_source_archive(&archive_dir, Duration::from_secs(86_400))
+                .unwrap()
+                .is_empty(),
+            "fresh files must not be archived"
+        );
+        let plan = db
+            .plan_source_archive(&archive_dir, Duration::ZERO)
+            .unwrap();
+        assert_eq!(plan.len(), 2);
+
+        let report = db.archive_sources(&plan).unwrap();
+        assert_eq!((report.files, report.sessions), (2, 2));
+        assert_eq!(report.archives.len(), 1);
+        assert!(!Path::new(&plan[0].source_path).exists());
+        assert!(db.list_session_source_paths().unwrap().is_empty());
+        assert_eq!(db.read_session_source("s2").unwrap().unwrap(), b"second\n");
+
+        // A second frame appended to the same month archive stays readable.
+        add_source(&db, dir.path(), "s3", "d.jsonl", "third\n");
+        let plan = db
+            .plan_source_archive(&archive_dir, Duration::ZERO)
+            .unwrap();
+        db.archive_sources(&plan).unwrap();
+        assert_eq!(db.read_session_source("s3").unwrap().unwrap(), b"third\n");
+        assert_eq!(db.read_session_source("s1").unwrap().unwrap(), b"first\n");
+
+        let restored = db.restore_session_source("s1").unwrap().unwrap();
+        assert_eq!(fs::read(&restored).unwrap(), b"first\n");
+        assert!(db.get_session_source_archive("s1").unwrap().is_none());
+        assert!(db.restore_session_source("s1").unwrap().is_none());
+    }
+
+    #[test]
+    fn archive_shares_one_member_between_sessions_of_the_same_file() {
+        let dir = tempdir().unwrap();
+        let db = LocalDb::open_path(&dir.path().join("local.db")).unwrap();
+        add_source(&db, dir.path(), "parent", "shared.jsonl", "body\n");
+        let shared = db.get_session_source_path("parent").unwrap().unwrap();
+        db.set_session_sync_path("child", &shared).unwrap();
+
+        let plan = db
+            .plan_source_archive(&dir.path().join("archive"), Duration::ZERO)
+            .unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].session_ids, vec!["child", "parent"]);
+        db.archive_sources(&plan).unwrap();
+
+        db.restore_session_source("child").unwrap();
+        assert!(db.get_session_source_archive("parent").unwrap().is_none());
+        assert_eq!(fs::read(&shared).unwrap(), b"body\n");
+    }
+}
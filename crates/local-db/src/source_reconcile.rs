@@ -0,0 +1,231 @@
+//! Reconcile `session_sync.source_path` with files moved or deleted outside
+//! opensession.
+//!
+//! A session whose source file no longer exists is re-pointed at a candidate
+//! file with the same file name, or else at the single candidate whose name
+//! contains the session id. Sessions with no unambiguous match are marked with
+//! `source_missing_at` so readers treat them as body-less instead of failing
+//! on the stale path; marked rows are skipped by later passes until the file
+//! is indexed again.
+
+use anyhow::Result;
+use rusqlite::{OptionalExtension, params};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::connection::LocalDb;
+
+/// A session whose source file was found at a new path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceRelocation {
+    pub session_id: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Outcome of [`LocalDb::reconcile_source_paths`].
+#[derive(Debug, Clone, Default)]
+pub struct SourceReconcileReport {
+    /// Sessions whose recorded source file does not exist.
+    pub stale: usize,
+    pub relocated: Vec<SourceRelocation>,
+    /// Session ids left without a source file.
+    pub missing: Vec<String>,
+}
+
+impl LocalDb {
+    /// Re-locate stale source paths among `candidates` (called only when at
+    /// least one recorded file is gone, since discovery walks the disk).
+    /// With `dry_run`, the report is computed but nothing is written.
+    pub fn reconcile_source_paths(
+        &self,
+        candidates: impl FnOnce() -> Vec<PathBuf>,
+        dry_run: bool,
+    ) -> Result<SourceReconcileReport> {
+        let stale = self
+            .list_unreconciled_source_paths()?
+            .into_iter()
+            .filter(|(_, source_path)| !Path::new(source_path).exists())
+            .collect::<Vec<_>>();
+        let mut report = SourceReconcileReport {
+            stale: stale.len(),
+            ..Default::default()
+        };
+        if stale.is_empty() {
+            return Ok(report);
+        }
+
+        let candidates = candidates();
+        let mut by_name: HashMap<&str, Vec<&Path>> = HashMap::new();
+        for path in &candidates {
+            if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+                by_name.entry(name).or_default().push(path);
+            }
+        }
+
+        for (session_id, source_path) in stale {
+            match relocate(&session_id, &source_path, &by_name, &candidates) {
+                Some(to) => {
+                    let to = to.to_string_lossy().into_owned();
+                    if !dry_run {
+                        self.set_session_sync_path(&session_id, &to)?;
+                    }
+                    report.relocated.push(SourceRelocation {
+                        session_id,
+                        from: source_path,
+                        to,
+                    });
+                }
+                None => {
+                    if !dry_run {
+                        self.mark_source_missing(&session_id)?;
+                    }
+                    report.missing.push(session_id);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Source paths that are neither archived nor already marked missing.
+    fn list_unreconciled_source_paths(&self) -> Result<Vec<(String, String)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT session_id, source_path FROM session_sync \
+             WHERE source_path IS NOT NULL AND TRIM(source_path) != '' \
+             AND archive_path IS NULL AND source_missing_at IS NULL",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into)
+    }
+
+    fn mark_source_missing(&self, session_id: &str) -> Result<()> {
+        self.conn().execute(
+            "UPDATE session_sync SET source_missing_at = COALESCE(source_missing_at, datetime('now')) \
+             WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        Ok(())
+    }
+
+    /// Whether reconciliation marked this session's source file as missing.
+    pub fn is_source_missing(&self, session_id: &str) -> Result<bool> {
+        let missing = self
+            .conn()
+            .query_row(
+                "SELECT source_missing_at IS NOT NULL FROM session_sync WHERE session_id = ?1",
+                params![session_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(missing.unwrap_or(false))
+    }
+}
+
+fn relocate<'a>(
+    session_id: &str,
+    source_path: &str,
+    by_name: &HashMap<&str, Vec<&'a Path>>,
+    candidates: &'a [PathBuf],
+) -> Option<&'a Path> {
+    let existing = |path: &&Path| path.is_file();
+
+    let same_name = Path::new(source_path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| by_name.get(name))
+        .map(|paths| paths.iter().copied().filter(existing).collect::<Vec<_>>())
+        .unwrap_or_default();
+    if let [only] = same_name.as_slice() {
+        return Some(only);
+    }
+
+    if session_id.trim().is_empty() {
+        return None;
+    }
+    let by_id = candidates
+        .iter()
+        .map(PathBuf::as_path)
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.contains(session_id))
+        })
+        .filter(existing)
+        .collect::<Vec<_>>();
+    match by_id.as_slice() {
+        [only] => Some(only),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reconcile_relocates_by_name_or_id_and_marks_the_rest() {
+        let dir = tempdir().unwrap();
+        let db = LocalDb::open_path(&dir.path().join("local.db")).unwrap();
+        let old = dir.path().join("old");
+        let new = dir.path().join("new");
+        fs::create_dir_all(&new).unwrap();
+
+        let renamed = new.join("rollout-2025-01-01-abc123.jsonl");
+        let moved = new.join("moved.jsonl");
+        let kept = dir.path().join("kept.jsonl");
+        for path in [&renamed, &moved, &kept] {
+            fs::write(path, "{}\n").unwrap();
+        }
+        let old_moved = old.join("moved.jsonl").to_string_lossy().into_owned();
+        db.set_session_sync_path("abc123", &old.join("abc123.jsonl").to_string_lossy())
+            .unwrap();
+        db.set_session_sync_path("moved", &old_moved).unwrap();
+        db.set_session_sync_path("gone", &old.join("gone.jsonl").to_string_lossy())
+            .unwrap();
+        db.set_session_sync_path("kept", &kept.to_string_lossy())
+            .unwrap();
+
+        let candidates = || vec![renamed.clone(), moved.clone(), kept.clone()];
+        let preview = db.reconcile_source_paths(candidates, true).unwrap();
+        assert_eq!(preview.stale, 3);
+        assert_eq!(
+            db.get_session_source_path("moved").unwrap().as_deref(),
+            Some(old_moved.as_str()),
+            "dry run must not write"
+        );
+
+        let report = db.reconcile_source_paths(candidates, false).unwrap();
+        let mut relocated = report
+            .relocated
+            .iter()
+            .map(|r| (r.session_id.as_str(), r.to.as_str()))
+            .collect::<Vec<_>>();
+        relocated.sort();
+        assert_eq!(
+            relocated,
+            vec![
+                ("abc123", renamed.to_str().unwrap()),
+                ("moved", moved.to_str().unwrap()),
+            ]
+        );
+        assert_eq!(report.missing, vec!["gone"]);
+        assert!(db.is_source_missing("gone").unwrap());
+        assert!(!db.is_source_missing("kept").unwrap());
+        assert!(db.read_session_source("gone").unwrap().is_none());
+
+        // Marked rows do not trigger discovery again.
+        let report = db
+            .reconcile_source_paths(|| panic!("no stale rows, no discovery"), false)
+            .unwrap();
+        assert_eq!(report.stale, 0);
+
+        // Recording a path again clears the marker.
+        db.set_session_sync_path("gone", &kept.to_string_lossy())
+            .unwrap();
+        assert!(!db.is_source_missing("gone").unwrap());
+    }
+}
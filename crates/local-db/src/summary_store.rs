@@ -2,6 +2,7 @@ use anyhow::Result;
 use rusqlite::{OptionalExtension, params};
 
 use crate::connection::LocalDb;
+use crate::sync_store::LocalCacheStats;
 
 /// Session-level semantic summary row persisted in local SQLite.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -144,6 +145,32 @@ impl LocalDb {
         Ok(row)
     }
 
+    pub fn session_semantic_summary_cache_stats(&self) -> Result<LocalCacheStats> {
+        let stats = self.conn().query_row(
+            "SELECT COUNT(*), \
+                    COALESCE(SUM(LENGTH(summary_json) \
+                        + COALESCE(LENGTH(source_details_json), 0) \
+                        + COALESCE(LENGTH(diff_tree_json), 0)), 0) \
+             FROM session_semantic_summaries",
+            [],
+            |row| {
+                Ok(LocalCacheStats {
+                    entries: row.get::<_, i64>(0)? as u64,
+                    bytes: row.get::<_, i64>(1)? as u64,
+                })
+            },
+        )?;
+        Ok(stats)
+    }
+
+    /// Drop every cached semantic summary so the next request regenerates it.
+    pub fn clear_session_semantic_summaries(&self) -> Result<u32> {
+        let deleted = self
+            .conn()
+            .execute("DELETE FROM session_semantic_summaries", [])?;
+        Ok(deleted as u32)
+    }
+
     pub fn delete_expired_session_summaries(&self, keep_days: u32) -> Result<u32> {
         let deleted = self.conn().execute(
             "DELETE FROM session_semantic_summaries \
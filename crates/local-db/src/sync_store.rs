@@ -1,9 +1,25 @@
 use anyhow::Result;
+use opensession_api::service::body_etag;
+use opensession_core::jsonl;
 use rusqlite::{OptionalExtension, params};
 
 use crate::connection::LocalDb;
 use crate::session_store::{LOCAL_SESSION_COLUMNS, LocalSessionRow, row_to_local_session};
 
+/// A cached session body and the server ETag it was served with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedBodyRow {
+    pub body: Vec<u8>,
+    pub etag: Option<String>,
+}
+
+/// Entry count and payload size of a local cache table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LocalCacheStats {
+    pub entries: u64,
+    pub bytes: u64,
+}
+
 impl LocalDb {
     /// Fetch the source path used when the session was last parsed/loaded.
     pub fn get_session_source_path(&self, session_id: &str) -> Result<Option<String>> {
@@ -20,12 +36,14 @@ impl LocalDb {
     }
 
     /// List every session id with a non-empty source path from session_sync.
+    /// Sessions whose source file was moved into an archive are left out.
     pub fn list_session_source_paths(&self) -> Result<Vec<(String, String)>> {
         let conn = self.conn();
         let mut stmt = conn.prepare(
             "SELECT session_id, source_path \
              FROM session_sync \
-             WHERE source_path IS NOT NULL AND TRIM(source_path) != ''",
+             WHERE source_path IS NOT NULL AND TRIM(source_path) != '' \
+             AND archive_path IS NULL",
         )?;
         let rows = stmt.query_map([], |row| {
             let session_id: String = row.get(0)?;
@@ -112,25 +130,140 @@ impl LocalDb {
     }
 
     pub fn cache_body(&self, session_id: &str, body: &[u8]) -> Result<()> {
+        self.cache_body_with_etag(session_id, body, None)
+    }
+
+    /// Cache a body together with the server ETag it was fetched with.
+    pub fn cache_body_with_etag(
+        &self,
+        session_id: &str,
+        body: &[u8],
+        etag: Option<&str>,
+    ) -> Result<()> {
         self.conn().execute(
-            "INSERT INTO body_cache (session_id, body, cached_at) \
-             VALUES (?1, ?2, datetime('now')) \
-             ON CONFLICT(session_id) DO UPDATE SET body=excluded.body, cached_at=datetime('now')",
-            params![session_id, body],
+            "INSERT INTO body_cache (session_id, body, etag, cached_at) \
+             VALUES (?1, ?2, ?3, datetime('now')) \
+             ON CONFLICT(session_id) DO UPDATE SET \
+             body=excluded.body, etag=excluded.etag, cached_at=datetime('now')",
+            params![session_id, body, etag],
         )?;
         Ok(())
     }
 
+    /// Append a `since_event` delta to the cached body instead of replacing it.
+    ///
+    /// Returns `false` (leaving the cache untouched) when nothing is cached,
+    /// the cached body no longer has exactly `since_event` events, or the
+    /// merged body does not hash to `etag` (the server rewrote the prefix);
+    /// callers should then re-fetch the full body.
+    pub fn append_cached_body_delta(
+        &self,
+        session_id: &str,
+        since_event: usize,
+        delta: &[u8],
+        etag: Option<&str>,
+    ) -> Result<bool> {
+        let Some(cached) = self.get_cached_body_row(session_id)? else {
+            return Ok(false);
+        };
+        let (Ok(body), Ok(delta)) = (
+            std::str::from_utf8(&cached.body),
+            std::str::from_utf8(delta),
+        ) else {
+            return Ok(false);
+        };
+        if jsonl::count_events(body).ok() != Some(since_event) {
+            return Ok(false);
+        }
+        let merged = jsonl::apply_event_delta(body, delta)?;
+        if etag.is_some_and(|etag| body_etag(merged.as_bytes()) != etag) {
+            return Ok(false);
+        }
+        self.cache_body_with_etag(session_id, merged.as_bytes(), etag)?;
+        Ok(true)
+    }
+
     pub fn get_cached_body(&self, session_id: &str) -> Result<Option<Vec<u8>>> {
-        let body = self
+        Ok(self.get_cached_body_row(session_id)?.map(|row| row.body))
+    }
+
+    /// Cached body plus its ETag, for conditional re-fetches.
+    pub fn get_cached_body_row(&self, session_id: &str) -> Result<Option<CachedBodyRow>> {
+        let row = self
             .conn()
             .query_row(
-                "SELECT body FROM body_cache WHERE session_id = ?1",
+                "SELECT body, etag FROM body_cache WHERE session_id = ?1",
                 params![session_id],
-                |row| row.get(0),
+                |row| {
+                    Ok(CachedBodyRow {
+                        body: row.get(0)?,
+                        etag: row.get(1)?,
+                    })
+                },
             )
             .optional()?;
-        Ok(body)
+        Ok(row)
+    }
+
+    pub fn body_cache_stats(&self) -> Result<LocalCacheStats> {
+        let stats = self.conn().query_row(
+            "SELECT COUNT(*), COALESCE(SUM(LENGTH(body)), 0) FROM body_cache",
+            [],
+            |row| {
+                Ok(LocalCacheStats {
+                    entries: row.get::<_, i64>(0)? as u64,
+                    bytes: row.get::<_, i64>(1)? as u64,
+                })
+            },
+        )?;
+        Ok(stats)
+    }
+
+    /// Mark a cached body as still wanted, so eviction keeps it longer.
+    pub fn touch_cached_body(&self, session_id: &str) -> Result<()> {
+        self.conn().execute(
+            "UPDATE body_cache SET cached_at = datetime('now') WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        Ok(())
+    }
+
+    /// Evict the least recently cached bodies until the cache holds at most
+    /// `max_bytes`. Returns how many were evicted.
+    pub fn evict_body_cache(&self, max_bytes: u64) -> Result<u32> {
+        let conn = self.conn();
+        let rows = conn
+            .prepare(
+                "SELECT session_id, LENGTH(body) FROM body_cache \
+                 ORDER BY cached_at DESC, rowid DESC",
+            )?
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?.max(0) as u64,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let mut kept = 0u64;
+        let mut evicted = 0;
+        for (session_id, size) in rows {
+            if kept + size <= max_bytes {
+                kept += size;
+                continue;
+            }
+            conn.execute(
+                "DELETE FROM body_cache WHERE session_id = ?1",
+                params![session_id],
+            )?;
+            evicted += 1;
+        }
+        Ok(evicted)
+    }
+
+    /// Drop every cached body; they are re-fetched or re-read on demand.
+    pub fn clear_body_cache(&self) -> Result<u32> {
+        let deleted = self.conn().execute("DELETE FROM body_cache", [])?;
+        Ok(deleted as u32)
     }
 
     /// Update only sync metadata path for an existing session.
@@ -138,7 +271,8 @@ impl LocalDb {
         self.conn().execute(
             "INSERT INTO session_sync (session_id, source_path) \
              VALUES (?1, ?2) \
-             ON CONFLICT(session_id) DO UPDATE SET source_path = excluded.source_path",
+             ON CONFLICT(session_id) DO UPDATE SET \
+             source_path = excluded.source_path, source_missing_at = NULL",
             params![session_id, source_path],
         )?;
         Ok(())
@@ -141,13 +141,23 @@ fn dirs_home() -> PathBuf {
     opensession_paths::home_dir().unwrap_or_else(|_| PathBuf::from("."))
 }
 
-fn find_files_with_ext(dir: &Path, ext: &str) -> Vec<PathBuf> {
-    let pattern = format!("{}/**/*.{}", dir.display(), ext);
+/// Glob `suffix` under `dir`. The directory part is escaped so home paths
+/// containing `[` or `*` (common on Windows profiles) match literally.
+fn glob_under(dir: &Path, suffix: &str) -> Vec<PathBuf> {
+    let pattern = format!(
+        "{}/{}",
+        glob::Pattern::escape(&dir.to_string_lossy()),
+        suffix
+    );
     glob::glob(&pattern)
         .map(|paths| paths.filter_map(Result::ok).collect())
         .unwrap_or_default()
 }
 
+fn find_files_with_ext(dir: &Path, ext: &str) -> Vec<PathBuf> {
+    glob_under(dir, &format!("**/*.{ext}"))
+}
+
 fn find_codex_sessions(home: &Path) -> Vec<PathBuf> {
     let mut roots = Vec::new();
     if let Ok(codex_home) = std::env::var("CODEX_HOME") {
@@ -196,10 +206,7 @@ fn find_opencode_sessions(home: &Path) -> Vec<PathBuf> {
     if !session_path.exists() {
         return Vec::new();
     }
-    let pattern = format!("{}/*/*.json", session_path.display());
-    glob::glob(&pattern)
-        .map(|paths| paths.filter_map(Result::ok).collect())
-        .unwrap_or_default()
+    glob_under(&session_path, "*/*.json")
 }
 
 fn find_cline_sessions(home: &Path) -> Vec<PathBuf> {
@@ -207,10 +214,7 @@ fn find_cline_sessions(home: &Path) -> Vec<PathBuf> {
     if !tasks_dir.exists() {
         return Vec::new();
     }
-    let pattern = format!("{}/*/api_conversation_history.json", tasks_dir.display());
-    glob::glob(&pattern)
-        .map(|paths| paths.filter_map(Result::ok).collect())
-        .unwrap_or_default()
+    glob_under(&tasks_dir, "*/api_conversation_history.json")
 }
 
 fn find_amp_threads(home: &Path) -> Vec<PathBuf> {
@@ -222,10 +226,7 @@ fn find_amp_threads(home: &Path) -> Vec<PathBuf> {
     if !threads_dir.exists() {
         return Vec::new();
     }
-    let pattern = format!("{}/*.json", threads_dir.display());
-    glob::glob(&pattern)
-        .map(|paths| paths.filter_map(Result::ok).collect())
-        .unwrap_or_default()
+    glob_under(&threads_dir, "*.json")
 }
 
 fn find_gemini_sessions(home: &Path) -> Vec<PathBuf> {
@@ -235,10 +236,10 @@ fn find_gemini_sessions(home: &Path) -> Vec<PathBuf> {
     }
     let mut results = Vec::new();
     for ext in &["json", "jsonl"] {
-        let pattern = format!("{}/*/chats/session-*.{}", gemini_path.display(), ext);
-        if let Ok(paths) = glob::glob(&pattern) {
-            results.extend(paths.filter_map(Result::ok));
-        }
+        results.extend(glob_under(
+            &gemini_path,
+            &format!("*/chats/session-*.{ext}"),
+        ));
     }
     results
 }
@@ -252,8 +253,13 @@ fn find_cursor_vscdb(home: &Path) -> Vec<PathBuf> {
         .join("Cursor")
         .join("User");
     let cursor_base_linux = home.join(".config").join("Cursor").join("User");
+    let cursor_base_windows = home
+        .join("AppData")
+        .join("Roaming")
+        .join("Cursor")
+        .join("User");
 
-    for base in &[&cursor_base, &cursor_base_linux] {
+    for base in &[&cursor_base, &cursor_base_linux, &cursor_base_windows] {
         if !base.exists() {
             continue;
         }
@@ -265,14 +271,11 @@ fn find_cursor_vscdb(home: &Path) -> Vec<PathBuf> {
 
         let workspace_dir = base.join("workspaceStorage");
         if workspace_dir.exists() {
-            let pattern = format!("{}/*/state.vscdb", workspace_dir.display());
-            if let Ok(paths) = glob::glob(&pattern) {
-                results.extend(
-                    paths
-                        .filter_map(Result::ok)
-                        .filter(|path| cursor_db_has_composer_data(path)),
-                );
-            }
+            results.extend(
+                glob_under(&workspace_dir, "*/state.vscdb")
+                    .into_iter()
+                    .filter(|path| cursor_db_has_composer_data(path)),
+            );
         }
     }
 
@@ -1,5 +1,5 @@
 use crate::SessionParser;
-use crate::common::set_first;
+use crate::common::{portable_path_text, set_first};
 use anyhow::{Context, Result};
 use chrono::{TimeZone, Utc};
 use opensession_core::trace::{
@@ -19,7 +19,7 @@ impl SessionParser for AmpParser {
     fn can_parse(&self, path: &Path) -> bool {
         // Entry point: ~/.local/share/amp/threads/T-{uuid}.json
         path.extension().is_some_and(|ext| ext == "json")
-            && path.to_str().is_some_and(|s| s.contains("amp/threads/"))
+            && portable_path_text(path).contains("amp/threads/")
     }
 
     fn parse(&self, path: &Path) -> Result<Session> {
@@ -7,23 +7,21 @@ use super::{
     subagent::{merge_subagent_sessions, read_subagent_meta},
 };
 use crate::common::{
-    ToolUseInfo, attach_semantic_attrs, attach_source_attrs, infer_tool_kind, set_first,
-    strip_system_reminders,
+    ToolUseInfo, attach_semantic_attrs, attach_source_attrs, infer_tool_kind, open_bounded_lines,
+    set_first, strip_system_reminders,
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use opensession_core::trace::{Agent, Content, Event, EventType, Session, SessionContext};
 use std::collections::HashMap;
-use std::io::BufRead;
 use std::path::Path;
 
 // ── Parsing logic ───────────────────────────────────────────────────────────
 
 pub(super) fn parse_claude_code_jsonl(path: &Path) -> Result<Session> {
     let own_meta = read_subagent_meta(path);
-    let file = std::fs::File::open(path)
+    let mut lines = open_bounded_lines(path)
         .with_context(|| format!("Failed to open JSONL file: {}", path.display()))?;
-    let reader = std::io::BufReader::new(file);
 
     let mut events: Vec<Event> = Vec::new();
     let mut model_name: Option<String> = None;
@@ -37,7 +35,7 @@ pub(super) fn parse_claude_code_jsonl(path: &Path) -> Result<Session> {
     // Map tool_use_id -> tool metadata (name + file_path for language detection)
     let mut tool_use_info: HashMap<String, ToolUseInfo> = HashMap::new();
 
-    for line_result in reader.lines() {
+    for line_result in lines.by_ref() {
         let line = match line_result {
             Ok(l) => l,
             Err(e) => {
@@ -59,7 +57,12 @@ pub(super) fn parse_claude_code_jsonl(path: &Path) -> Result<Session> {
         };
 
         match entry {
-            RawEntry::FileHistorySnapshot {} | RawEntry::Unknown => continue,
+            RawEntry::FileHistorySnapshot {} => continue,
+            // Lines from a newer layout still carry the release that wrote them
+            RawEntry::Unknown => {
+                set_first(&mut tool_version, crate::compat::line_version(&line));
+                continue;
+            }
             RawEntry::System(system) => {
                 set_first(&mut session_id, system.session_id.clone());
                 set_first(&mut tool_version, system.version.clone());
@@ -139,6 +142,14 @@ pub(super) fn parse_claude_code_jsonl(path: &Path) -> Result<Session> {
         }
     }
 
+    if lines.skipped() > 0 {
+        tracing::warn!(
+            "Skipped {} oversized line(s) in Claude Code JSONL: {}",
+            lines.skipped(),
+            path.display()
+        );
+    }
+
     let parent_session_id = own_meta
         .as_ref()
         .and_then(|value| value.parent_session_id.clone())
@@ -212,6 +223,12 @@ pub(super) fn parse_claude_code_jsonl(path: &Path) -> Result<Session> {
             ),
         );
     }
+    crate::compat::check_format(
+        "claude-code",
+        crate::compat::claude_code_format(agent.tool_version.as_deref()),
+        events.len(),
+        &mut attributes,
+    )?;
 
     let title = first_user_text.map(|t| {
         if t.chars().count() > 80 {
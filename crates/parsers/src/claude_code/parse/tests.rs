@@ -485,3 +485,36 @@ fn test_subagent_parse_sets_related_parent_session_id() {
         Some("parent-2")
     );
 }
+
+#[test]
+fn test_log_format_is_recorded_and_newer_formats_are_reported() {
+    let dir = test_temp_root();
+    let path = dir.join("format.jsonl");
+    let line = |version: &str, kind: &str| {
+        serde_json::json!({
+            "type": kind,
+            "uuid": "u1",
+            "sessionId": "s1",
+            "version": version,
+            "timestamp": "2026-01-01T00:00:00Z",
+            "message": {"role": "user", "content": "hello"}
+        })
+        .to_string()
+    };
+
+    write(&path, line("2.0.14", "user")).unwrap();
+    let parsed = parse_claude_code_jsonl(&path).unwrap();
+    assert_eq!(parsed.agent.tool_version.as_deref(), Some("2.0.14"));
+    assert_eq!(parsed.context.attributes["log_format"], "v2");
+
+    write(&path, line("3.0.0", "user")).unwrap();
+    let parsed = parse_claude_code_jsonl(&path).unwrap();
+    assert_eq!(
+        parsed.context.attributes["log_format_warning"],
+        "claude-code log format v3 detected, supported: v1–v2"
+    );
+
+    write(&path, line("3.0.0", "turn")).unwrap();
+    let err = parse_claude_code_jsonl(&path).unwrap_err();
+    assert!(err.to_string().contains("log format v3 detected"));
+}
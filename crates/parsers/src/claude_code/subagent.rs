@@ -1,13 +1,12 @@
 use super::parse::{parse_timestamp, process_assistant_entry, process_user_entry};
 use super::raw::RawEntry;
 use crate::common::ToolUseInfo;
-use crate::common::set_first;
+use crate::common::{open_bounded_lines, set_first};
 use anyhow::{Context, Result};
 use chrono::Utc;
 use opensession_core::trace::{Agent, Event, EventType, Session, SessionContext};
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
-use std::io::BufRead;
 use std::path::{Path, PathBuf};
 
 fn is_subagent_file_name(name: &str) -> bool {
@@ -230,10 +229,7 @@ pub(super) struct SubagentMeta {
 }
 
 pub(super) fn read_subagent_meta(path: &Path) -> Option<SubagentMeta> {
-    let file = std::fs::File::open(path).ok()?;
-    let mut reader = std::io::BufReader::new(file);
-    let mut first_line = String::new();
-    reader.read_line(&mut first_line).ok()?;
+    let first_line = open_bounded_lines(path).ok()?.next()?.ok()?;
 
     #[derive(Deserialize)]
     #[serde(rename_all = "camelCase")]
@@ -259,9 +255,8 @@ pub(super) fn read_subagent_meta(path: &Path) -> Option<SubagentMeta> {
 
 pub(super) fn parse_subagent_jsonl(path: &Path) -> Result<Session> {
     let meta = read_subagent_meta(path);
-    let file = std::fs::File::open(path)
+    let lines = open_bounded_lines(path)
         .with_context(|| format!("Failed to open subagent JSONL: {}", path.display()))?;
-    let reader = std::io::BufReader::new(file);
 
     let mut events: Vec<Event> = Vec::new();
     let mut model_name: Option<String> = None;
@@ -271,7 +266,7 @@ pub(super) fn parse_subagent_jsonl(path: &Path) -> Result<Session> {
     let mut git_branch: Option<String> = None;
     let mut tool_use_info: HashMap<String, ToolUseInfo> = HashMap::new();
 
-    for line_result in reader.lines() {
+    for line_result in lines {
         let line = match line_result {
             Ok(line) => line,
             Err(_) => continue,
@@ -286,7 +281,12 @@ pub(super) fn parse_subagent_jsonl(path: &Path) -> Result<Session> {
         };
 
         match entry {
-            RawEntry::FileHistorySnapshot {} | RawEntry::Unknown => continue,
+            RawEntry::FileHistorySnapshot {} => continue,
+            // Lines from a newer layout still carry the release that wrote them
+            RawEntry::Unknown => {
+                set_first(&mut tool_version, crate::compat::line_version(&line));
+                continue;
+            }
             RawEntry::System(system) => {
                 set_first(&mut session_id, system.session_id.clone());
                 set_first(&mut tool_version, system.version.clone());
@@ -383,6 +383,12 @@ pub(super) fn parse_subagent_jsonl(path: &Path) -> Result<Session> {
             serde_json::Value::String(branch.clone()),
         );
     }
+    crate::compat::check_format(
+        "claude-code",
+        crate::compat::claude_code_format(agent.tool_version.as_deref()),
+        events.len(),
+        &mut attributes,
+    )?;
 
     let context = SessionContext {
         title: None,
@@ -1,4 +1,5 @@
 use crate::common::{ToolUseInfo, build_tool_result_content};
+use opensession_core::patch::replacement_hunk;
 use opensession_core::trace::{Content, ContentBlock, EventType};
 
 // ── Content transformation helpers ──────────────────────────────────────────
@@ -68,14 +69,15 @@ pub(super) fn classify_tool_use(name: &str, input: &serde_json::Value) -> EventT
                 .to_string();
             EventType::FileCreate { path }
         }
-        "Edit" | "NotebookEdit" => {
+        "Edit" | "MultiEdit" | "NotebookEdit" => {
             let path = input
                 .get("file_path")
                 .or_else(|| input.get("notebook_path"))
                 .and_then(|v| v.as_str())
                 .unwrap_or("unknown")
                 .to_string();
-            EventType::FileEdit { path, diff: None }
+            let diff = edit_diff(input);
+            EventType::FileEdit { path, diff }
         }
         "Bash" => {
             let command = input
@@ -110,6 +112,24 @@ pub(super) fn classify_tool_use(name: &str, input: &serde_json::Value) -> EventT
     }
 }
 
+/// Hunks for `Edit` (`old_string`/`new_string`) and `MultiEdit` (`edits`)
+/// inputs, in the order the replacements were applied.
+fn edit_diff(input: &serde_json::Value) -> Option<String> {
+    let replacement = |edit: &serde_json::Value| {
+        let old = edit.get("old_string")?.as_str()?;
+        let new = edit.get("new_string")?.as_str()?;
+        Some(replacement_hunk(old, new))
+    };
+    match input.get("edits").and_then(|v| v.as_array()) {
+        Some(edits) => edits
+            .iter()
+            .map(replacement)
+            .collect::<Option<String>>()
+            .filter(|diff| !diff.is_empty()),
+        None => replacement(input),
+    }
+}
+
 /// Build content for a tool_use event.
 /// Extracts the most useful information from the tool input
 /// so the frontend can render without parsing raw JSON.
@@ -281,7 +301,25 @@ mod tests {
             serde_json::json!({"file_path": "/tmp/test.rs", "old_string": "a", "new_string": "b"});
         let event_type = classify_tool_use("Edit", &input);
         match event_type {
-            EventType::FileEdit { path, .. } => assert_eq!(path, "/tmp/test.rs"),
+            EventType::FileEdit { path, diff } => {
+                assert_eq!(path, "/tmp/test.rs");
+                assert_eq!(diff.as_deref(), Some("@@ -1,1 +1,1 @@\n-a\n+b\n"));
+            }
+            _ => panic!("Expected FileEdit"),
+        }
+
+        let input = serde_json::json!({
+            "file_path": "/tmp/test.rs",
+            "edits": [
+                {"old_string": "a", "new_string": "b"},
+                {"old_string": "c\n", "new_string": ""}
+            ]
+        });
+        match classify_tool_use("MultiEdit", &input) {
+            EventType::FileEdit { diff, .. } => assert_eq!(
+                diff.as_deref(),
+                Some("@@ -1,1 +1,1 @@\n-a\n+b\n@@ -1,1 +0,0 @@\n-c\n")
+            ),
             _ => panic!("Expected FileEdit"),
         }
     }
@@ -1,7 +1,7 @@
 use crate::SessionParser;
 use crate::common::{
     INTERACTIVE_USER_INPUT_TOOL, ToolUseInfo, build_tool_result_content, canonical_tool_name,
-    extract_tag_content, set_first, strip_system_reminders,
+    extract_tag_content, portable_path_text, set_first, strip_system_reminders,
 };
 use anyhow::{Context, Result};
 use chrono::{TimeZone, Utc};
@@ -24,9 +24,7 @@ impl SessionParser for ClineParser {
     fn can_parse(&self, path: &Path) -> bool {
         path.file_name()
             .is_some_and(|f| f == "api_conversation_history.json")
-            && path.to_str().is_some_and(|s| {
-                s.contains(".cline/data/tasks/") || s.contains("cline/data/tasks/")
-            })
+            && portable_path_text(path).contains("cline/data/tasks/")
     }
 
     fn parse(&self, path: &Path) -> Result<Session> {
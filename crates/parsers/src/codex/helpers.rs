@@ -50,6 +50,16 @@ pub(super) fn parse_function_output(raw: &str) -> (String, bool, Option<u64>) {
     }
 }
 
+/// `metadata.exit_code` from a JSON function output.
+pub(super) fn function_output_exit_code(raw: &str) -> Option<i32> {
+    serde_json::from_str::<serde_json::Value>(raw)
+        .ok()?
+        .get("metadata")?
+        .get("exit_code")?
+        .as_i64()
+        .and_then(|code| i32::try_from(code).ok())
+}
+
 pub(super) fn is_low_signal_output_marker(text: &str) -> bool {
     let trimmed = text.trim();
     !trimmed.is_empty()
@@ -405,7 +415,11 @@ pub(super) fn classify_codex_function(name: &str, args: &serde_json::Value) -> E
         },
         "apply_diff" | "apply_patch" => {
             let path = extract_patch_target_path(args).unwrap_or_else(|| "unknown".to_string());
-            EventType::FileEdit { path, diff: None }
+            let diff = ["input", "patch"]
+                .into_iter()
+                .find_map(|key| args.get(key).and_then(|v| v.as_str()))
+                .and_then(|patch| apply_patch_hunks(patch, &path));
+            EventType::FileEdit { path, diff }
         }
         "create_file" | "write_file" => {
             let path = args
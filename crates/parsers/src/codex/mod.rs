@@ -6,6 +6,7 @@ use crate::common::{
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 #[allow(unused_imports)]
+use opensession_core::patch::apply_patch_hunks;
 use opensession_core::session::{ATTR_PARENT_SESSION_ID, ATTR_SESSION_ROLE};
 #[allow(unused_imports)]
 use opensession_core::trace::{
@@ -1,9 +1,9 @@
 use super::*;
+use crate::common::open_bounded_lines;
 
 pub(crate) fn parse_codex_jsonl(path: &Path) -> Result<Session> {
-    let file = std::fs::File::open(path)
+    let mut lines = open_bounded_lines(path)
         .with_context(|| format!("Failed to open Codex JSONL: {}", path.display()))?;
-    let reader = std::io::BufReader::new(file);
 
     let mut events: Vec<Event> = Vec::new();
     let mut session_id: Option<String> = None;
@@ -20,10 +20,11 @@ pub(crate) fn parse_codex_jsonl(path: &Path) -> Result<Session> {
     let mut parent_session_id: Option<String> = None;
     let mut is_auxiliary_session = false;
     let mut is_desktop = false;
+    let mut is_legacy = false;
     let mut open_tasks: BTreeMap<String, Option<String>> = BTreeMap::new();
     let mut interactive_call_meta: HashMap<String, RequestUserInputCallMeta> = HashMap::new();
 
-    for line_result in reader.lines() {
+    for line_result in lines.by_ref() {
         let line = match line_result {
             Ok(l) => l,
             Err(_) => continue,
@@ -95,6 +96,7 @@ pub(crate) fn parse_codex_jsonl(path: &Path) -> Result<Session> {
 
         // Session header — no `type` field, has `id` + `timestamp` (legacy CLI format)
         if !obj.contains_key("type") {
+            is_legacy = true;
             set_first(
                 &mut session_id,
                 obj.get("id").and_then(|v| v.as_str()).map(String::from),
@@ -491,6 +493,14 @@ pub(crate) fn parse_codex_jsonl(path: &Path) -> Result<Session> {
         );
     }
 
+    if lines.skipped() > 0 {
+        tracing::warn!(
+            "Skipped {} oversized line(s) in Codex JSONL: {}",
+            lines.skipped(),
+            path.display()
+        );
+    }
+
     if !open_tasks.is_empty() {
         let synthetic_ts = events
             .last()
@@ -586,6 +596,13 @@ pub(crate) fn parse_codex_jsonl(path: &Path) -> Result<Session> {
         }
     }
 
+    let log_format = if is_desktop {
+        Some(2)
+    } else {
+        is_legacy.then_some(1)
+    };
+    crate::compat::check_format("codex", log_format, events.len(), &mut attributes)?;
+
     let title = first_user_text.map(|t| {
         if t.chars().count() > 80 {
             let truncated: String = t.chars().take(77).collect();
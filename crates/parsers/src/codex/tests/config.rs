@@ -203,6 +203,8 @@ fn test_parse_function_output_error() {
         r#"{"output":"command not found","metadata":{"exit_code":127,"duration_seconds":0.01}}"#;
     let (_, is_error, _) = parse_function_output(raw);
     assert!(is_error);
+    assert_eq!(function_output_exit_code(raw), Some(127));
+    assert_eq!(function_output_exit_code("command not found"), None);
 }
 
 #[test]
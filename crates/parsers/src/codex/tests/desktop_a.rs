@@ -44,6 +44,8 @@ fn test_desktop_format_response_item() {
             .and_then(|v| v.as_str()),
         Some("Codex Desktop")
     );
+    assert_eq!(session.agent.tool_version.as_deref(), Some("0.94.0"));
+    assert_eq!(session.context.attributes["log_format"], "v2");
 
     let _ = std::fs::remove_dir_all(&dir);
 }
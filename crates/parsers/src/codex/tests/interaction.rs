@@ -162,8 +162,11 @@ fn test_classify_apply_patch_uses_path_from_patch_input() {
         "input": "*** Begin Patch\n*** Update File: crates/tui/src/ui.rs\n@@\n- old\n+ new\n*** End Patch\n"
     });
     let et = classify_codex_function("functions.apply_patch", &args);
-    assert!(matches!(
-        et,
-        EventType::FileEdit { path, diff: None } if path == "crates/tui/src/ui.rs"
-    ));
+    match et {
+        EventType::FileEdit { path, diff } => {
+            assert_eq!(path, "crates/tui/src/ui.rs");
+            assert_eq!(diff.as_deref(), Some("@@ -1,1 +1,1 @@\n- old\n+ new\n"));
+        }
+        other => panic!("expected FileEdit, got {other:?}"),
+    }
 }
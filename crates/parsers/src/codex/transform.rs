@@ -214,6 +214,19 @@ pub(super) fn process_item_with_options(
                     (prev_id, last_function_name.clone())
                 };
 
+            // Keep the exit code on the command event, not only in the output JSON.
+            if let (Some(code), Some(call_event_id)) = (
+                function_output_exit_code(raw_output),
+                call_id_ref.as_deref(),
+            ) && let Some(EventType::ShellCommand { exit_code, .. }) = events
+                .iter_mut()
+                .rev()
+                .find(|event| event.event_id == call_event_id)
+                .map(|event| &mut event.event_type)
+            {
+                exit_code.get_or_insert(code);
+            }
+
             if call_name == INTERACTIVE_USER_INPUT_TOOL {
                 let call_meta = item
                     .get("call_id")
@@ -10,10 +10,19 @@ use opensession_core::trace::{
 use regex::Regex;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::LazyLock;
 
 pub const INTERACTIVE_USER_INPUT_TOOL: &str = "request_user_input";
 
+// ── Path matching ───────────────────────────────────────────────────────────
+
+/// Path text with `\` separators rewritten to `/`, so `can_parse` layout
+/// checks match Windows paths too.
+pub fn portable_path_text(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
 // ── First-wins metadata helper ──────────────────────────────────────────────
 
 /// Assign `source` to `target` if `target` is still `None` (first-wins semantics).
@@ -26,6 +35,116 @@ pub fn set_first<T>(target: &mut Option<T>, source: Option<T>) {
     }
 }
 
+// ── Bounded JSONL line reading ──────────────────────────────────────────────
+
+/// Hard cap on a single JSONL record. Longer lines are skipped without being
+/// buffered, so one runaway tool output cannot exhaust memory.
+pub const MAX_JSONL_LINE_BYTES: usize = 32 * 1024 * 1024;
+
+/// Line iterator over agent-native JSONL that never holds more than
+/// `max_line_bytes` of a single line in memory.
+///
+/// Behaves like [`std::io::BufRead::lines`] (strips `\n`/`\r\n`, yields
+/// `InvalidData` for non-UTF-8 lines) except that oversized lines are
+/// discarded chunk by chunk and counted in [`BoundedLines::skipped`].
+pub struct BoundedLines<R> {
+    reader: R,
+    max_line_bytes: usize,
+    skipped: usize,
+}
+
+impl<R: std::io::BufRead> BoundedLines<R> {
+    pub fn new(reader: R, max_line_bytes: usize) -> Self {
+        Self {
+            reader,
+            max_line_bytes,
+            skipped: 0,
+        }
+    }
+
+    /// Number of oversized lines skipped so far.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Read one raw line; `Ok(None)` at EOF, `Ok(Some(None))` for a skipped line.
+    fn read_line(&mut self) -> std::io::Result<Option<Option<Vec<u8>>>> {
+        let mut line = Vec::new();
+        let mut oversized = false;
+        let mut saw_bytes = false;
+        loop {
+            let available = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if available.is_empty() {
+                break;
+            }
+            saw_bytes = true;
+            let (chunk, done) = match available.iter().position(|b| *b == b'\n') {
+                Some(idx) => (&available[..idx], Some(idx + 1)),
+                None => (available, None),
+            };
+            if !oversized && line.len() + chunk.len() > self.max_line_bytes {
+                oversized = true;
+                line = Vec::new();
+            }
+            if !oversized {
+                line.extend_from_slice(chunk);
+            }
+            let consumed = done.unwrap_or(available.len());
+            self.reader.consume(consumed);
+            if done.is_some() {
+                break;
+            }
+        }
+        if !saw_bytes {
+            return Ok(None);
+        }
+        if oversized {
+            self.skipped += 1;
+            return Ok(Some(None));
+        }
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        Ok(Some(Some(line)))
+    }
+}
+
+impl<R: std::io::BufRead> Iterator for BoundedLines<R> {
+    type Item = std::io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.read_line() {
+                Ok(None) => return None,
+                Ok(Some(None)) => {}
+                Ok(Some(Some(bytes))) => {
+                    return Some(
+                        String::from_utf8(bytes).map_err(|err| {
+                            std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+                        }),
+                    );
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// Bounded line iterator for a JSONL file on disk.
+pub fn open_bounded_lines(
+    path: &std::path::Path,
+) -> std::io::Result<BoundedLines<std::io::BufReader<std::fs::File>>> {
+    let file = std::fs::File::open(path)?;
+    Ok(BoundedLines::new(
+        std::io::BufReader::new(file),
+        MAX_JSONL_LINE_BYTES,
+    ))
+}
+
 // ── Shared semantic metadata helpers ────────────────────────────────────────
 
 /// Normalize cross-tool role labels into a canonical role string.
@@ -230,7 +349,7 @@ pub fn parse_line_numbered_output(text: &str) -> (String, u32) {
 
 /// Detect programming language from file path extension
 pub fn detect_language(file_path: &str) -> Option<String> {
-    let basename = file_path.rsplit('/').next().unwrap_or(file_path);
+    let basename = file_path.rsplit(['/', '\\']).next().unwrap_or(file_path);
 
     // Special filenames
     match basename {
@@ -406,6 +525,10 @@ mod tests {
         assert_eq!(detect_language("Dockerfile"), Some("bash".to_string()));
         assert_eq!(detect_language("/foo/bar.kt"), Some("kotlin".to_string()));
         assert_eq!(detect_language("/foo/bar.xyz"), None);
+        assert_eq!(
+            detect_language(r"C:\src\Dockerfile"),
+            Some("bash".to_string())
+        );
     }
 
     #[test]
@@ -573,4 +696,23 @@ mod tests {
             Some("shell")
         );
     }
+
+    #[test]
+    fn bounded_lines_skip_oversized_records() {
+        let input = format!("short\r\n{}\nafter\n\nlast", "x".repeat(40));
+        let mut lines =
+            BoundedLines::new(std::io::BufReader::with_capacity(8, input.as_bytes()), 16);
+        let collected: Vec<String> = lines.by_ref().map(Result::unwrap).collect();
+        assert_eq!(collected, vec!["short", "after", "", "last"]);
+        assert_eq!(lines.skipped(), 1);
+    }
+
+    #[test]
+    fn bounded_lines_report_invalid_utf8() {
+        let input: &[u8] = b"ok\n\xff\xfe\nnext\n";
+        let results: Vec<_> = BoundedLines::new(input, 64).collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[1].is_err());
+        assert_eq!(results[2].as_deref().ok(), Some("next"));
+    }
 }
@@ -0,0 +1,176 @@
+//! Log format revisions each parser understands.
+//!
+//! Agent CLIs change their on-disk logs between releases. Parsers sniff the
+//! revision they are reading, record it as the `log_format` context attribute,
+//! and report revisions newer than [`COMPATIBILITY`] lists instead of quietly
+//! producing a half-empty session.
+
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// One on-disk log layout of an agent CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogFormat {
+    pub version: u32,
+    /// Tool releases known to write this layout.
+    pub tool_versions: &'static str,
+    pub description: &'static str,
+}
+
+/// Log formats a built-in parser supports, oldest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserCompat {
+    pub parser: &'static str,
+    pub formats: &'static [LogFormat],
+}
+
+pub const COMPATIBILITY: &[ParserCompat] = &[
+    ParserCompat {
+        parser: "claude-code",
+        formats: &[
+            LogFormat {
+                version: 1,
+                tool_versions: "0.x–1.x",
+                description: "user/assistant/summary lines under ~/.claude/projects",
+            },
+            LogFormat {
+                version: 2,
+                tool_versions: "2.x",
+                description: "adds progress, queue-operation and file-history-snapshot lines",
+            },
+        ],
+    },
+    ParserCompat {
+        parser: "codex",
+        formats: &[
+            LogFormat {
+                version: 1,
+                tool_versions: "legacy CLI",
+                description: "untyped session header followed by flat response items",
+            },
+            LogFormat {
+                version: 2,
+                tool_versions: "rollout CLI and Desktop",
+                description: "session_meta header with response_item/event_msg envelopes",
+            },
+        ],
+    },
+];
+
+impl ParserCompat {
+    pub fn min_version(&self) -> u32 {
+        self.formats.first().map_or(0, |format| format.version)
+    }
+
+    pub fn max_version(&self) -> u32 {
+        self.formats.last().map_or(0, |format| format.version)
+    }
+
+    pub fn supports(&self, version: u32) -> bool {
+        self.formats.iter().any(|format| format.version == version)
+    }
+
+    /// `codex log format v3 detected, supported: v1–v2`
+    pub fn unsupported_message(&self, version: u32) -> String {
+        format!(
+            "{} log format v{version} detected, supported: v{}–v{}",
+            self.parser,
+            self.min_version(),
+            self.max_version()
+        )
+    }
+}
+
+pub fn parser_compat(parser: &str) -> Option<&'static ParserCompat> {
+    COMPATIBILITY.iter().find(|compat| compat.parser == parser)
+}
+
+/// Claude Code stamps each line with its release; the major version tracks
+/// the log layout (0.x and 1.x share the first one).
+pub(crate) fn claude_code_format(tool_version: Option<&str>) -> Option<u32> {
+    let major = tool_version?
+        .trim()
+        .split('.')
+        .next()?
+        .parse::<u32>()
+        .ok()?;
+    Some(major.max(1))
+}
+
+/// Top-level `version` string of a raw JSONL line.
+pub(crate) fn line_version(line: &str) -> Option<String> {
+    #[derive(serde::Deserialize)]
+    struct Versioned {
+        version: Option<String>,
+    }
+    serde_json::from_str::<Versioned>(line).ok()?.version
+}
+
+/// Record the sniffed format in `attributes` and reject unsupported ones.
+///
+/// A newer format that still yielded events is kept with a
+/// `log_format_warning` attribute; one that yielded nothing is an error, since
+/// the empty session would be a misparse.
+pub(crate) fn check_format(
+    parser: &str,
+    detected: Option<u32>,
+    parsed_events: usize,
+    attributes: &mut HashMap<String, serde_json::Value>,
+) -> Result<()> {
+    let (Some(version), Some(compat)) = (detected, parser_compat(parser)) else {
+        return Ok(());
+    };
+    attributes.insert(
+        "log_format".to_string(),
+        serde_json::Value::String(format!("v{version}")),
+    );
+    if compat.supports(version) {
+        return Ok(());
+    }
+    let message = compat.unsupported_message(version);
+    if parsed_events == 0 {
+        anyhow::bail!("{message}; update opensession to read these logs");
+    }
+    tracing::warn!("{message}; some events may be missing");
+    attributes.insert(
+        "log_format_warning".to_string(),
+        serde_json::Value::String(message),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claude_code_format_follows_major_version() {
+        assert_eq!(claude_code_format(Some("0.2.9")), Some(1));
+        assert_eq!(claude_code_format(Some("1.0.35")), Some(1));
+        assert_eq!(claude_code_format(Some("2.0.14")), Some(2));
+        assert_eq!(claude_code_format(Some("3.1.0")), Some(3));
+        assert_eq!(claude_code_format(Some("dev")), None);
+        assert_eq!(claude_code_format(None), None);
+    }
+
+    #[test]
+    fn newer_formats_warn_or_fail() {
+        let mut attributes = HashMap::new();
+        check_format("claude-code", Some(2), 4, &mut attributes).unwrap();
+        assert_eq!(attributes["log_format"], "v2");
+        assert!(!attributes.contains_key("log_format_warning"));
+
+        check_format("claude-code", Some(3), 4, &mut attributes).unwrap();
+        assert_eq!(
+            attributes["log_format_warning"],
+            "claude-code log format v3 detected, supported: v1–v2"
+        );
+
+        let err = check_format("codex", Some(3), 0, &mut HashMap::new()).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("codex log format v3 detected, supported: v1–v2")
+        );
+        check_format("gemini", Some(9), 0, &mut HashMap::new()).unwrap();
+    }
+}
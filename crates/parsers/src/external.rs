@@ -1,5 +1,6 @@
 use crate::SessionParser;
 use anyhow::{Context, Result};
+use opensession_core::migrate::migrate_session;
 use opensession_core::trace::Session;
 use std::path::Path;
 use std::process::Command;
@@ -60,20 +61,16 @@ impl SessionParser for ExternalParser {
             );
         }
 
-        let session: Session = serde_json::from_slice(&output.stdout).with_context(|| {
+        let mut session: Session = serde_json::from_slice(&output.stdout).with_context(|| {
             format!(
                 "External parser '{}' returned invalid HAIL JSON",
                 self.config.name
             )
         })?;
 
-        // Validate version
-        if !session.version.starts_with("hail-") {
-            anyhow::bail!(
-                "External parser '{}' returned invalid version: {}",
-                self.config.name,
-                session.version
-            );
+        // Negotiate the format version and upgrade older output
+        if let Err(err) = migrate_session(&mut session) {
+            anyhow::bail!("External parser '{}' returned {err}", self.config.name);
         }
 
         Ok(session)
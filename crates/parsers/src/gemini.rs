@@ -1,6 +1,7 @@
 use crate::SessionParser;
 use crate::common::{
-    attach_semantic_attrs, attach_source_attrs, infer_tool_kind, normalize_role_label, set_first,
+    attach_semantic_attrs, attach_source_attrs, infer_tool_kind, normalize_role_label,
+    open_bounded_lines, portable_path_text, set_first,
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -21,9 +22,10 @@ impl SessionParser for GeminiParser {
     fn can_parse(&self, path: &Path) -> bool {
         path.extension()
             .is_some_and(|ext| ext == "json" || ext == "jsonl")
-            && path
-                .to_str()
-                .is_some_and(|s| s.contains(".gemini/tmp/") && s.contains("/chats/session-"))
+            && {
+                let s = portable_path_text(path);
+                s.contains(".gemini/tmp/") && s.contains("/chats/session-")
+            }
     }
 
     fn parse(&self, path: &Path) -> Result<Session> {
@@ -614,7 +616,7 @@ enum GeminiContentBlock {
 }
 
 fn parse_jsonl(path: &Path) -> Result<Session> {
-    let text = std::fs::read_to_string(path)
+    let lines = open_bounded_lines(path)
         .with_context(|| format!("Failed to read Gemini JSONL session: {}", path.display()))?;
 
     let mut session_id = None;
@@ -625,7 +627,10 @@ fn parse_jsonl(path: &Path) -> Result<Session> {
     let mut model_name: Option<String> = None;
     let mut token_map: HashMap<String, GeminiTokens> = HashMap::new();
 
-    for line in text.lines() {
+    for line in lines {
+        let Ok(line) = line else {
+            continue;
+        };
         let line = line.trim();
         if line.is_empty() {
             continue;
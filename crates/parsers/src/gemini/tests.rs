@@ -10,6 +10,9 @@ fn test_can_parse() {
     assert!(parser.can_parse(Path::new(
         "/Users/test/.gemini/tmp/abc123/chats/session-2026-02-09T15-11-8205f040.jsonl"
     )));
+    assert!(parser.can_parse(Path::new(
+        r"C:\Users\test\.gemini\tmp\abc123\chats\session-2026-02-09T15-11-8205f040.json"
+    )));
     assert!(!parser.can_parse(Path::new("/tmp/random.json")));
     assert!(!parser.can_parse(Path::new("/tmp/random.jsonl")));
     assert!(!parser.can_parse(Path::new("/Users/test/.gemini/settings.json")));
@@ -163,6 +163,7 @@ pub(crate) fn preview_parse_bytes(
 
         match parse_with_parser_id(hint, filename, content) {
             Ok(session) => {
+                warnings.extend(log_format_warning(&session));
                 return Ok(ParsePreview {
                     parser_used: hint.to_string(),
                     parser_candidates,
@@ -187,6 +188,7 @@ pub(crate) fn preview_parse_bytes(
         attempted.push(candidate_id.clone());
         match parse_with_parser_id(&candidate_id, filename, content) {
             Ok(session) => {
+                warnings.extend(log_format_warning(&session));
                 return Ok(ParsePreview {
                     parser_used: candidate_id.clone(),
                     parser_candidates,
@@ -222,6 +224,16 @@ pub(crate) fn preview_parse_bytes(
     })
 }
 
+/// Warning left by [`crate::compat::check_format`] for a newer log format.
+fn log_format_warning(session: &Session) -> Option<String> {
+    session
+        .context
+        .attributes
+        .get("log_format_warning")
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+}
+
 fn add_candidate(candidates: &mut Vec<ParseCandidate>, id: &str, confidence: u8, reason: &str) {
     if let Some(existing) = candidates.iter_mut().find(|c| c.id == id) {
         if confidence > existing.confidence {
@@ -5,11 +5,13 @@ mod amp;
 mod cline;
 mod codex;
 pub(crate) mod common;
+pub mod compat;
 mod cursor;
 pub mod external;
 mod gemini;
 mod ingest;
 mod opencode;
+pub mod plugin;
 
 use anyhow::Result;
 use opensession_core::trace::Session;
@@ -50,6 +52,20 @@ impl Default for ParserRegistry {
 }
 
 impl ParserRegistry {
+    /// Built-in parsers followed by parser plugins discovered on `PATH`.
+    pub fn with_plugins() -> Self {
+        let mut registry = Self::default();
+        registry.parsers.extend(
+            plugin::discover_plugins()
+                .iter()
+                .filter(|plugin| !plugin.manifest.parser_globs.is_empty())
+                .map(|plugin| {
+                    Box::new(plugin::PluginParser(plugin.clone())) as Box<dyn SessionParser>
+                }),
+        );
+        registry
+    }
+
     pub fn parser_for_path(&self, path: &Path) -> Option<&dyn SessionParser> {
         self.parsers
             .iter()
@@ -1,5 +1,7 @@
 use crate::SessionParser;
-use crate::common::{attach_semantic_attrs, attach_source_attrs, infer_tool_kind, set_first};
+use crate::common::{
+    attach_semantic_attrs, attach_source_attrs, infer_tool_kind, portable_path_text, set_first,
+};
 use anyhow::{Context, Result};
 use chrono::{DateTime, TimeZone, Utc};
 use opensession_core::trace::{Agent, Content, Event, EventType, Session, SessionContext};
@@ -16,10 +18,10 @@ impl SessionParser for OpenCodeParser {
 
     fn can_parse(&self, path: &Path) -> bool {
         // Actual layout: ~/.local/share/opencode/storage/session/<project_hash>/<session_id>.json
-        path.extension().is_some_and(|ext| ext == "json")
-            && path
-                .to_str()
-                .is_some_and(|s| s.contains("opencode") && s.contains("/storage/session/"))
+        path.extension().is_some_and(|ext| ext == "json") && {
+            let s = portable_path_text(path);
+            s.contains("opencode") && s.contains("/storage/session/")
+        }
     }
 
     fn parse(&self, path: &Path) -> Result<Session> {
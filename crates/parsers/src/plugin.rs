@@ -0,0 +1,429 @@
+//! External-process plugins (`opensession-plugin-*` executables on `PATH`).
+//!
+//! Protocol: each call spawns the plugin, writes one JSON request to stdin and
+//! reads one JSON response from stdout.
+//!
+//! | request                                              | response                                        |
+//! |------------------------------------------------------|-------------------------------------------------|
+//! | `{"method":"describe"}`                              | `{"parser_globs":[...],"export_formats":[...]}` |
+//! | `{"method":"parse","path":"/abs/file"}`              | `{"session":{...HAIL session...}}`              |
+//! | `{"method":"export","format":"md","session":{...}}`  | `{"output":"..."}`                              |
+//!
+//! Any response may instead carry `{"error":"..."}`.
+//!
+//! A plugin that has not exited after [`Plugin::timeout`] is killed; the
+//! default of 60 seconds can be changed with `OPENSESSION_PLUGIN_TIMEOUT_SECS`.
+
+use crate::SessionParser;
+use anyhow::{Context, Result, bail};
+use opensession_core::migrate::migrate_session;
+use opensession_core::trace::Session;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+pub const PLUGIN_PREFIX: &str = "opensession-plugin-";
+
+const DEFAULT_PLUGIN_TIMEOUT: Duration = Duration::from_secs(60);
+const PLUGIN_TIMEOUT_ENV: &str = "OPENSESSION_PLUGIN_TIMEOUT_SECS";
+
+/// Capabilities a plugin reports from `describe`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginManifest {
+    #[serde(default)]
+    pub parser_globs: Vec<String>,
+    #[serde(default)]
+    pub export_formats: Vec<String>,
+}
+
+/// A discovered plugin executable and its reported capabilities.
+#[derive(Debug, Clone)]
+pub struct Plugin {
+    pub name: String,
+    pub path: PathBuf,
+    pub manifest: PluginManifest,
+    /// How long one call may run before the plugin is killed.
+    pub timeout: Duration,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+enum PluginRequest<'a> {
+    Describe,
+    Parse {
+        path: &'a Path,
+    },
+    Export {
+        format: &'a str,
+        session: &'a Session,
+    },
+}
+
+#[derive(Deserialize)]
+struct PluginResponse<T> {
+    #[serde(flatten)]
+    value: Option<T>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ParseResponse {
+    session: Session,
+}
+
+#[derive(Deserialize)]
+struct ExportResponse {
+    output: String,
+}
+
+impl Plugin {
+    fn call<T: serde::de::DeserializeOwned>(&self, request: &PluginRequest<'_>) -> Result<T> {
+        let mut input = serde_json::to_vec(request)?;
+        input.push(b'\n');
+        let mut child = Command::new(&self.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run plugin '{}'", self.name))?;
+
+        // Write the request and drain both pipes on their own threads, so a
+        // plugin that answers before reading all of a large request (or
+        // fills stderr) cannot deadlock the call.
+        let mut stdin = child.stdin.take();
+        let writer = std::thread::spawn(move || match stdin.as_mut() {
+            Some(stdin) => match stdin.write_all(&input) {
+                // The plugin may answer without reading the whole request.
+                Err(error) if error.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+                other => other,
+            },
+            None => Ok(()),
+        });
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            if let Some(status) = child
+                .try_wait()
+                .with_context(|| format!("Failed to wait for plugin '{}'", self.name))?
+            {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!(
+                    "Plugin '{}' timed out after {}s",
+                    self.name,
+                    self.timeout.as_secs_f32()
+                );
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+
+        writer
+            .join()
+            .map_err(|_| anyhow::anyhow!("Plugin '{}' request writer panicked", self.name))?
+            .with_context(|| format!("Failed to send request to plugin '{}'", self.name))?;
+        let stdout = stdout
+            .join()
+            .map_err(|_| anyhow::anyhow!("Plugin '{}' output reader panicked", self.name))?
+            .with_context(|| format!("Failed to read output of plugin '{}'", self.name))?;
+        let stderr = stderr
+            .join()
+            .map_err(|_| anyhow::anyhow!("Plugin '{}' output reader panicked", self.name))?
+            .unwrap_or_default();
+        if !status.success() {
+            bail!(
+                "Plugin '{}' failed (exit {}): {}",
+                self.name,
+                status.code().unwrap_or(-1),
+                String::from_utf8_lossy(&stderr).trim()
+            );
+        }
+
+        let response: PluginResponse<T> = serde_json::from_slice(&stdout)
+            .with_context(|| format!("Plugin '{}' returned invalid JSON", self.name))?;
+        if let Some(error) = response.error {
+            bail!("Plugin '{}' reported an error: {error}", self.name);
+        }
+        response
+            .value
+            .with_context(|| format!("Plugin '{}' returned an empty response", self.name))
+    }
+
+    fn describe(name: String, path: PathBuf) -> Result<Self> {
+        let mut plugin = Self {
+            name,
+            path,
+            manifest: PluginManifest::default(),
+            timeout: plugin_timeout(),
+        };
+        plugin.manifest = plugin.call(&PluginRequest::Describe)?;
+        Ok(plugin)
+    }
+
+    pub fn can_parse(&self, path: &Path) -> bool {
+        self.manifest.parser_globs.iter().any(|glob| {
+            let pattern = shellexpand::tilde(glob).to_string();
+            glob::Pattern::new(&pattern).is_ok_and(|p| p.matches_path(path))
+        })
+    }
+
+    pub fn parse(&self, path: &Path) -> Result<Session> {
+        let response: ParseResponse = self.call(&PluginRequest::Parse { path })?;
+        let mut session = response.session;
+        if let Err(err) = migrate_session(&mut session) {
+            bail!("Plugin '{}' returned {err}", self.name);
+        }
+        session.recompute_stats();
+        Ok(session)
+    }
+
+    pub fn supports_export(&self, format: &str) -> bool {
+        self.manifest
+            .export_formats
+            .iter()
+            .any(|f| f.eq_ignore_ascii_case(format))
+    }
+
+    pub fn export(&self, format: &str, session: &Session) -> Result<String> {
+        if !self.supports_export(format) {
+            bail!(
+                "Plugin '{}' does not export `{format}` (supports: {})",
+                self.name,
+                self.manifest.export_formats.join(", ")
+            );
+        }
+        let response: ExportResponse = self.call(&PluginRequest::Export { format, session })?;
+        Ok(response.output)
+    }
+}
+
+/// Read a child pipe to its end on a background thread.
+fn drain<R: Read + Send + 'static>(
+    pipe: Option<R>,
+) -> std::thread::JoinHandle<std::io::Result<Vec<u8>>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut buf)?;
+        }
+        Ok(buf)
+    })
+}
+
+/// Per-call timeout from `OPENSESSION_PLUGIN_TIMEOUT_SECS`, else the default.
+fn plugin_timeout() -> Duration {
+    std::env::var(PLUGIN_TIMEOUT_ENV)
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .map_or(DEFAULT_PLUGIN_TIMEOUT, Duration::from_secs)
+}
+
+/// Parser adapter registered by [`crate::ParserRegistry::with_plugins`].
+pub struct PluginParser(pub Plugin);
+
+impl SessionParser for PluginParser {
+    fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    fn can_parse(&self, path: &Path) -> bool {
+        self.0.can_parse(path)
+    }
+
+    fn parse(&self, path: &Path) -> Result<Session> {
+        self.0.parse(path)
+    }
+}
+
+/// Plugins found on `PATH`, discovered once per process.
+///
+/// Executables that fail `describe` are skipped with a warning.
+pub fn discover_plugins() -> &'static [Plugin] {
+    static PLUGINS: OnceLock<Vec<Plugin>> = OnceLock::new();
+    PLUGINS.get_or_init(|| {
+        let dirs: Vec<PathBuf> = std::env::var_os("PATH")
+            .map(|raw| std::env::split_paths(&raw).collect())
+            .unwrap_or_default();
+        discover_plugins_in(&dirs)
+    })
+}
+
+/// Look up a discovered plugin by name (the part after `opensession-plugin-`).
+pub fn find_plugin(name: &str) -> Option<&'static Plugin> {
+    discover_plugins().iter().find(|plugin| plugin.name == name)
+}
+
+/// Discover plugins in the given directories. Earlier directories win on name clashes.
+pub fn discover_plugins_in(dirs: &[PathBuf]) -> Vec<Plugin> {
+    let mut plugins: Vec<Plugin> = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        let mut candidates: Vec<(String, PathBuf)> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                let file_name = path.file_name()?.to_str()?;
+                let name = file_name
+                    .strip_prefix(PLUGIN_PREFIX)?
+                    .trim_end_matches(".exe")
+                    .to_string();
+                (!name.is_empty() && is_executable(&path)).then_some((name, path))
+            })
+            .collect();
+        candidates.sort();
+
+        for (name, path) in candidates {
+            if plugins.iter().any(|plugin| plugin.name == name) {
+                continue;
+            }
+            match Plugin::describe(name, path.clone()) {
+                Ok(plugin) => plugins.push(plugin),
+                Err(error) => {
+                    tracing::warn!("skipping plugin {}: {error:#}", path.display());
+                }
+            }
+        }
+    }
+    plugins
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use opensession_core::trace::Agent;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn write_plugin(dir: &Path, name: &str, script: &str) {
+        let path = dir.join(format!("{PLUGIN_PREFIX}{name}"));
+        std::fs::write(&path, format!("#!/bin/sh\n{script}\n")).expect("write plugin");
+        let mut perms = std::fs::metadata(&path).expect("metadata").permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&path, perms).expect("chmod plugin");
+    }
+
+    fn fixture_session_json() -> String {
+        let session = Session::new(
+            "plugin-session".to_string(),
+            Agent {
+                provider: "acme".to_string(),
+                model: "internal-1".to_string(),
+                tool: "acme-agent".to_string(),
+                tool_version: None,
+            },
+        );
+        serde_json::to_string(&session).expect("serialize session")
+    }
+
+    #[test]
+    fn discovers_describes_parses_and_exports_through_plugins() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let session_json = fixture_session_json();
+        write_plugin(
+            dir.path(),
+            "acme",
+            &format!(
+                r#"read request
+case "$request" in
+  *describe*) echo '{{"parser_globs":["**/*.acme"],"export_formats":["md"]}}' ;;
+  *parse*) echo '{{"session":{session_json}}}' ;;
+  *export*) echo '{{"output":"exported markdown"}}' ;;
+esac"#
+            ),
+        );
+        write_plugin(dir.path(), "broken", "exit 1");
+        std::fs::write(dir.path().join("opensession-plugin-notexec"), "").expect("write file");
+
+        let plugins = discover_plugins_in(&[dir.path().to_path_buf()]);
+        assert_eq!(plugins.len(), 1);
+        let plugin = &plugins[0];
+        assert_eq!(plugin.name, "acme");
+        assert!(plugin.can_parse(Path::new("/tmp/logs/run.acme")));
+        assert!(!plugin.can_parse(Path::new("/tmp/logs/run.jsonl")));
+
+        let parser = PluginParser(plugin.clone());
+        let session = parser
+            .parse(Path::new("/tmp/logs/run.acme"))
+            .expect("parse via plugin");
+        assert_eq!(session.session_id, "plugin-session");
+        assert_eq!(session.agent.tool, "acme-agent");
+
+        assert_eq!(
+            plugin.export("md", &session).expect("export"),
+            "exported markdown"
+        );
+        assert!(plugin.export("pdf", &session).is_err());
+    }
+
+    #[test]
+    fn plugin_error_responses_surface_as_errors() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write_plugin(
+            dir.path(),
+            "picky",
+            r#"read request
+case "$request" in
+  *describe*) echo '{"parser_globs":["*.picky"]}' ;;
+  *) echo '{"error":"unsupported log version"}' ;;
+esac"#,
+        );
+
+        let plugins = discover_plugins_in(&[dir.path().to_path_buf()]);
+        let err = plugins[0]
+            .parse(Path::new("a.picky"))
+            .expect_err("plugin error");
+        assert!(err.to_string().contains("unsupported log version"));
+    }
+
+    #[test]
+    fn large_requests_do_not_deadlock_and_hung_plugins_are_killed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write_plugin(
+            dir.path(),
+            "echoer",
+            r#"head -c 200000 /dev/zero >&2
+read request
+case "$request" in
+  *describe*) echo '{"export_formats":["md","slow"]}' ;;
+  *'"format":"slow"'*) sleep 30 ;;
+  *) printf '{"output":"'; printf '%s' "$request" | wc -c | tr -d ' \n'; echo '"}' ;;
+esac"#,
+        );
+
+        let mut plugin = discover_plugins_in(&[dir.path().to_path_buf()])
+            .pop()
+            .expect("plugin");
+        let mut session: Session = serde_json::from_str(&fixture_session_json()).expect("session");
+        session.context.description = Some("x".repeat(1 << 20));
+        let output = plugin.export("md", &session).expect("export large session");
+        assert!(output.parse::<usize>().expect("byte count") > 1 << 20);
+
+        plugin.timeout = Duration::from_millis(200);
+        let started = Instant::now();
+        let err = plugin.export("slow", &session).expect_err("timeout");
+        assert!(err.to_string().contains("timed out"), "{err}");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,238 @@
+//! Property tests feeding malformed and truncated input to every file-based
+//! parser. Parsers may reject bad input, but must never panic on it.
+
+use opensession_core::trace::Session;
+use opensession_parsers::ParserRegistry;
+use proptest::prelude::*;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+const CLINE_SEED: &str = r#"[{"role":"user","content":[{"type":"text","text":"<task>fix the build</task>"}]},{"role":"assistant","content":[{"type":"text","text":"Looking."},{"type":"tool_use","id":"t1","name":"read_file","input":{"path":"src/lib.rs"}}],"modelInfo":{"modelId":"claude-sonnet","providerId":"anthropic"}},{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"fn main() {}"}]}]"#;
+
+const AMP_SEED: &str = r#"{"v":1,"id":"T-fixture","created":1735689600000,"title":"fixture","messages":[{"role":"user","messageId":0,"content":[{"type":"text","text":"hi"}],"meta":{"sentAt":1735689600000}},{"role":"assistant","messageId":1,"content":[{"type":"thinking","thinking":"hm"},{"type":"tool_use","id":"t1","name":"Bash","input":{"cmd":"ls"}},{"type":"text","text":"done"}],"state":{"type":"complete","stopReason":"end_turn"},"usage":{"model":"claude-sonnet","inputTokens":3,"outputTokens":5}},{"role":"user","messageId":2,"content":[{"type":"tool_result","toolUseID":"t1","run":{"status":"done","result":"ok"}}]}]}"#;
+
+const GEMINI_JSONL_SEED: &str = r#"{"type":"session_metadata","sessionId":"sess-1","startTime":"2026-02-09T15:00:00.000Z"}
+{"type":"user","id":"u1","timestamp":"2026-02-09T15:01:00.000Z","content":[{"type":"text","text":"hello gemini"}]}
+{"type":"gemini","id":"g1","timestamp":"2026-02-09T15:02:00.000Z","content":[{"type":"thinking","text":"analyzing..."},{"type":"text","text":"Here is my answer"},{"type":"functionCall","name":"readFile","args":{"path":"/tmp/x.rs"}}],"model":"gemini-2.5-pro"}
+{"type":"user","id":"u2","timestamp":"2026-02-09T15:03:00.000Z","content":[{"type":"functionResponse","name":"readFile","response":{"content":"fn main() {}"}}]}
+{"type":"message_update","id":"g1","tokens":{"input":100,"output":50,"total":150}}"#;
+
+/// Keys the parsers branch on, so generated objects reach past deserialization.
+const KEYS: &[&str] = &[
+    "type",
+    "role",
+    "content",
+    "text",
+    "message",
+    "payload",
+    "timestamp",
+    "id",
+    "name",
+    "input",
+    "output",
+    "call_id",
+    "tool_use_id",
+    "sessionId",
+    "messages",
+    "parts",
+    "toolCalls",
+    "args",
+    "result",
+    "uuid",
+    "parentUuid",
+    "model",
+    "tokens",
+];
+
+struct Target {
+    /// Path below the staging root that routes to the intended parser.
+    staged: &'static str,
+    seed: String,
+    jsonl: bool,
+}
+
+fn fixture(relative: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(relative);
+    std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("read {}", path.display()))
+}
+
+fn targets() -> Vec<Target> {
+    vec![
+        Target {
+            staged: ".codex/sessions/rollout-fuzz.jsonl",
+            seed: fixture("codex/rollout-desktop.jsonl"),
+            jsonl: true,
+        },
+        Target {
+            staged: ".codex/sessions/web-fuzz.jsonl",
+            seed: fixture("codex/web-search-actions.jsonl"),
+            jsonl: true,
+        },
+        Target {
+            staged: ".claude/projects/demo/session-fuzz.jsonl",
+            seed: fixture("claude/session-fallback.jsonl"),
+            jsonl: true,
+        },
+        Target {
+            staged: ".gemini/tmp/demo/chats/session-fuzz.json",
+            seed: fixture("gemini/session-toolcalls.json"),
+            jsonl: false,
+        },
+        Target {
+            staged: ".gemini/tmp/demo/chats/session-fuzz.jsonl",
+            seed: GEMINI_JSONL_SEED.to_string(),
+            jsonl: true,
+        },
+        Target {
+            staged: ".cline/data/tasks/1/api_conversation_history.json",
+            seed: CLINE_SEED.to_string(),
+            jsonl: false,
+        },
+        Target {
+            staged: ".local/share/amp/threads/T-fuzz.json",
+            seed: AMP_SEED.to_string(),
+            jsonl: false,
+        },
+    ]
+}
+
+/// Parse `bytes` as if they were found at `target.staged`; errors are fine.
+fn parse_staged(target: &Target, bytes: &[u8]) -> anyhow::Result<Option<Session>> {
+    let root = tempfile::tempdir().expect("create fuzz staging dir");
+    let path = root.path().join(target.staged);
+    std::fs::create_dir_all(path.parent().expect("staged parent")).expect("create staged dir");
+    std::fs::write(&path, bytes).expect("write staged input");
+    let registry = ParserRegistry::default();
+    assert!(
+        registry.parser_for_path(&path).is_some(),
+        "no parser for {}",
+        path.display()
+    );
+    registry.parse_path(Path::new(&path))
+}
+
+#[test]
+fn seeds_parse_cleanly() {
+    for target in targets() {
+        let session = parse_staged(&target, target.seed.as_bytes())
+            .unwrap_or_else(|err| panic!("seed for {} failed: {err:#}", target.staged))
+            .unwrap_or_else(|| panic!("seed for {} was skipped", target.staged));
+        assert!(
+            !session.events.is_empty(),
+            "seed for {} produced no events",
+            target.staged
+        );
+    }
+}
+
+fn arb_json() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        any::<i64>().prop_map(Value::from),
+        any::<f64>().prop_map(Value::from),
+        ".{0,12}".prop_map(Value::String),
+        proptest::sample::select(KEYS).prop_map(|key| Value::String(key.to_string())),
+    ];
+    leaf.prop_recursive(4, 48, 6, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..6).prop_map(Value::Array),
+            prop::collection::vec((proptest::sample::select(KEYS), inner), 0..6).prop_map(
+                |entries| Value::Object(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (key.to_string(), value))
+                        .collect()
+                )
+            ),
+        ]
+    })
+}
+
+/// Overwrite `key` on the `pick`-th object (pre-order) inside `value`.
+fn set_nested_field(value: &mut Value, pick: &mut usize, key: &str, replacement: &Value) -> bool {
+    match value {
+        Value::Object(map) => {
+            if *pick == 0 {
+                map.insert(key.to_string(), replacement.clone());
+                return true;
+            }
+            *pick -= 1;
+            map.values_mut()
+                .any(|child| set_nested_field(child, pick, key, replacement))
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .any(|child| set_nested_field(child, pick, key, replacement)),
+        _ => false,
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(48))]
+
+    #[test]
+    fn truncated_input_never_panics(target in 0..targets().len(), cut in any::<prop::sample::Index>()) {
+        let target = &targets()[target];
+        let bytes = target.seed.as_bytes();
+        let _ = parse_staged(target, &bytes[..cut.index(bytes.len() + 1)]);
+    }
+
+    #[test]
+    fn spliced_bytes_never_panic(
+        target in 0..targets().len(),
+        at in any::<prop::sample::Index>(),
+        noise in prop::collection::vec(any::<u8>(), 1..32),
+    ) {
+        let target = &targets()[target];
+        let mut bytes = target.seed.as_bytes().to_vec();
+        let at = at.index(bytes.len() + 1);
+        bytes.splice(at..at, noise);
+        let _ = parse_staged(target, &bytes);
+    }
+
+    #[test]
+    fn structurally_wrong_json_never_panics(
+        target in 0..targets().len(),
+        line in any::<prop::sample::Index>(),
+        value in arb_json(),
+    ) {
+        let target = &targets()[target];
+        let replacement = value.to_string();
+        let input = if target.jsonl {
+            let mut lines: Vec<&str> = target.seed.lines().collect();
+            let line = line.index(lines.len());
+            lines[line] = &replacement;
+            lines.join("\n")
+        } else {
+            replacement
+        };
+        let _ = parse_staged(target, input.as_bytes());
+    }
+
+    #[test]
+    fn mutated_fields_never_panic(
+        target in 0..targets().len(),
+        line in any::<prop::sample::Index>(),
+        pick in 0usize..64,
+        key in proptest::sample::select(KEYS),
+        value in arb_json(),
+    ) {
+        let target = &targets()[target];
+        let mut lines: Vec<String> = if target.jsonl {
+            target.seed.lines().map(str::to_string).collect()
+        } else {
+            vec![target.seed.clone()]
+        };
+        let line = line.index(lines.len());
+        let mut record: Value = serde_json::from_str(&lines[line]).expect("seed line is JSON");
+        let mut pick = pick;
+        if !set_nested_field(&mut record, &mut pick, key, &value) {
+            set_nested_field(&mut record, &mut 0, key, &value);
+        }
+        lines[line] = record.to_string();
+        let _ = parse_staged(target, lines.join("\n").as_bytes());
+    }
+}
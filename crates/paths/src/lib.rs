@@ -1,17 +1,40 @@
-use directories::BaseDirs;
-use opensession_runtime_config::CONFIG_FILE_NAME;
+use directories::{BaseDirs, ProjectDirs};
+use opensession_runtime_config::{
+    CONFIG_FILE_NAME, DAEMON_STATUS_FILE_NAME, REPO_REGISTRY_FILE_NAME,
+};
 use std::path::{Path, PathBuf};
 
+/// Root for both config (`config/`) and data (`data/`), overriding XDG and
+/// platform defaults.
+pub const OPENSESSION_HOME_ENV: &str = "OPENSESSION_HOME";
+
 #[derive(Debug, thiserror::Error)]
 pub enum PathError {
     #[error("could not determine home directory")]
     HomeUnavailable,
+    #[error("could not move {} to {}: {source}", .from.display(), .to.display())]
+    Migrate {
+        from: PathBuf,
+        to: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// A directory relocated by [`migrate_legacy_dirs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirMigration {
+    pub from: PathBuf,
+    pub to: PathBuf,
 }
 
 fn base_dirs() -> Result<BaseDirs, PathError> {
     BaseDirs::new().ok_or(PathError::HomeUnavailable)
 }
 
+fn project_dirs() -> Result<ProjectDirs, PathError> {
+    ProjectDirs::from("", "", "opensession").ok_or(PathError::HomeUnavailable)
+}
+
 fn join_segments(base: &Path, segments: &[&str]) -> PathBuf {
     segments
         .iter()
@@ -22,21 +45,172 @@ pub fn home_dir() -> Result<PathBuf, PathError> {
     Ok(base_dirs()?.home_dir().to_path_buf())
 }
 
+/// Absolute directory from `key`; relative values are ignored, as the XDG
+/// spec requires.
+fn env_dir(key: &str) -> Option<PathBuf> {
+    std::env::var_os(key)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+/// `$OPENSESSION_HOME/config`, else `$XDG_CONFIG_HOME/opensession`, else
+/// `~/.config/opensession` (`%APPDATA%\opensession\config` on Windows).
 pub fn config_dir() -> Result<PathBuf, PathError> {
-    Ok(join_segments(&home_dir()?, &[".config", "opensession"]))
+    if let Some(root) = env_dir(OPENSESSION_HOME_ENV) {
+        return Ok(root.join("config"));
+    }
+    if cfg!(windows) {
+        return Ok(project_dirs()?.config_dir().to_path_buf());
+    }
+    if let Some(xdg) = env_dir("XDG_CONFIG_HOME") {
+        return Ok(xdg.join("opensession"));
+    }
+    legacy_config_dir()
 }
 
+/// `$OPENSESSION_HOME/data`, else `$XDG_DATA_HOME/opensession`, else
+/// `~/.local/share/opensession` (`%APPDATA%\opensession\data` on Windows).
 pub fn data_dir() -> Result<PathBuf, PathError> {
+    if let Some(root) = env_dir(OPENSESSION_HOME_ENV) {
+        return Ok(root.join("data"));
+    }
+    if cfg!(windows) {
+        return Ok(project_dirs()?.data_dir().to_path_buf());
+    }
+    if let Some(xdg) = env_dir("XDG_DATA_HOME") {
+        return Ok(xdg.join("opensession"));
+    }
+    legacy_data_dir()
+}
+
+fn legacy_config_dir() -> Result<PathBuf, PathError> {
+    Ok(join_segments(&home_dir()?, &[".config", "opensession"]))
+}
+
+fn legacy_data_dir() -> Result<PathBuf, PathError> {
     Ok(join_segments(
         &home_dir()?,
         &[".local", "share", "opensession"],
     ))
 }
 
+/// Move the fixed `~/.config/opensession` and `~/.local/share/opensession`
+/// directories to the resolved [`config_dir`]/[`data_dir`] when those differ
+/// and do not exist yet. Once moved, later calls find nothing to do.
+///
+/// Nothing moves under an [`OPENSESSION_HOME_ENV`] override, which points at
+/// a separate root rather than a new home for the default one, or while a
+/// daemon started from the legacy config dir is still running.
+pub fn migrate_legacy_dirs() -> Result<Vec<DirMigration>, PathError> {
+    if env_dir(OPENSESSION_HOME_ENV).is_some() {
+        return Ok(Vec::new());
+    }
+    let legacy_config = legacy_config_dir()?;
+    if pid_file_is_live(&legacy_config.join("daemon.pid")) {
+        return Ok(Vec::new());
+    }
+    let mut moved = Vec::new();
+    for (from, to) in [
+        (legacy_config, config_dir()?),
+        (legacy_data_dir()?, data_dir()?),
+    ] {
+        if move_dir_once(&from, &to).map_err(|source| PathError::Migrate {
+            from: from.clone(),
+            to: to.clone(),
+            source,
+        })? {
+            moved.push(DirMigration { from, to });
+        }
+    }
+    Ok(moved)
+}
+
+/// Whether `path` names a process that is still alive.
+fn pid_file_is_live(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u32>().ok())
+        .is_some_and(|pid| pid > 0 && process_running(pid))
+}
+
+#[cfg(unix)]
+fn process_running(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: kill(pid, 0) delivers no signal; it only probes whether the
+    // process exists and whether we may signal it.
+    let rc = unsafe { libc::kill(pid, 0) };
+    rc == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn process_running(_pid: u32) -> bool {
+    false
+}
+
+/// Move `from` to `to` if `from` exists and `to` does not. Falls back to a
+/// recursive copy only when the rename crosses filesystems; any other rename
+/// error leaves `from` in place.
+fn move_dir_once(from: &Path, to: &Path) -> std::io::Result<bool> {
+    if from == to || to.starts_with(from) || from.starts_with(to) {
+        return Ok(false);
+    }
+    if !from.is_dir() || to.exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    match std::fs::rename(from, to) {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::CrossesDevices => {
+            copy_dir(from, to)?;
+            std::fs::remove_dir_all(from)?;
+        }
+        Err(err) => return Err(err),
+    }
+    Ok(true)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
 pub fn runtime_config_path() -> Result<PathBuf, PathError> {
     Ok(config_dir()?.join(CONFIG_FILE_NAME))
 }
 
+/// Status snapshot the daemon keeps current for `opensession statusline`.
+pub fn daemon_status_path() -> Result<PathBuf, PathError> {
+    Ok(config_dir()?.join(DAEMON_STATUS_FILE_NAME))
+}
+
+/// Repository roots the daemon maintains.
+pub fn repo_registry_path() -> Result<PathBuf, PathError> {
+    Ok(config_dir()?.join(REPO_REGISTRY_FILE_NAME))
+}
+
+/// Stable id identifying this machine to the server.
+pub fn device_id_path() -> Result<PathBuf, PathError> {
+    Ok(config_dir()?.join("device-id"))
+}
+
+/// Private key this machine signs its session uploads with.
+pub fn device_key_path() -> Result<PathBuf, PathError> {
+    Ok(config_dir()?.join("device-key"))
+}
+
 pub fn local_db_path() -> Result<PathBuf, PathError> {
     if let Some(path) = std::env::var_os("OPENSESSION_LOCAL_DB_PATH")
         .map(PathBuf::from)
@@ -51,13 +225,49 @@ pub fn local_store_root() -> Result<PathBuf, PathError> {
     Ok(data_dir()?.join("objects"))
 }
 
+pub fn attachments_root() -> Result<PathBuf, PathError> {
+    Ok(data_dir()?.join("attachments"))
+}
+
+pub fn source_archive_root() -> Result<PathBuf, PathError> {
+    Ok(data_dir()?.join("source-archive"))
+}
+
+/// Handoff prompt files written for `opensession continue`.
+pub fn handoffs_root() -> Result<PathBuf, PathError> {
+    Ok(data_dir()?.join("handoffs"))
+}
+
+/// Expand a leading `~` against `home`. Separators after it may be `/` or
+/// `\`, so `~/.codex/sessions` from a shared config resolves natively on
+/// every platform. Other values are returned unchanged.
+pub fn expand_home(raw: &str, home: &Path) -> PathBuf {
+    let rest = match raw.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => rest,
+        _ => return PathBuf::from(raw),
+    };
+    let segments = rest
+        .split(['/', '\\'])
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    join_segments(home, &segments)
+}
+
+/// Path text with `\` separators rewritten to `/`, for substring and glob
+/// checks that are written against Unix-style paths.
+pub fn portable_path_text(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        config_dir, data_dir, home_dir, local_db_path, local_store_root, runtime_config_path,
+        OPENSESSION_HOME_ENV, config_dir, data_dir, expand_home, home_dir, local_db_path,
+        local_store_root, migrate_legacy_dirs, move_dir_once, pid_file_is_live, portable_path_text,
+        runtime_config_path, source_archive_root,
     };
     use opensession_runtime_config::CONFIG_FILE_NAME;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::sync::{Mutex, OnceLock};
 
     fn env_test_lock() -> &'static Mutex<()> {
@@ -101,7 +311,11 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(windows))]
     fn config_path_uses_opensession_suffix() {
+        let _lock = env_test_lock().lock().expect("env lock");
+        let _home = EnvVarGuard::clear(OPENSESSION_HOME_ENV);
+        let _xdg = EnvVarGuard::clear("XDG_CONFIG_HOME");
         let path = config_dir().expect("config dir");
         assert_eq!(
             path,
@@ -121,9 +335,12 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(windows))]
     fn data_paths_use_opensession_suffix() {
         let _lock = env_test_lock().lock().expect("env lock");
         let _guard = EnvVarGuard::clear("OPENSESSION_LOCAL_DB_PATH");
+        let _home = EnvVarGuard::clear(OPENSESSION_HOME_ENV);
+        let _xdg = EnvVarGuard::clear("XDG_DATA_HOME");
         let path = data_dir().expect("data dir");
         assert_eq!(
             path,
@@ -147,6 +364,92 @@ mod tests {
                 .expect("local store parent"),
             path.as_path()
         );
+        assert_eq!(
+            source_archive_root()
+                .expect("source archive root")
+                .parent()
+                .expect("source archive parent"),
+            path.as_path()
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn xdg_dirs_are_honored_when_absolute() {
+        let _lock = env_test_lock().lock().expect("env lock");
+        let _home = EnvVarGuard::clear(OPENSESSION_HOME_ENV);
+        let _config = EnvVarGuard::set("XDG_CONFIG_HOME", "/tmp/xdg-config");
+        let _data = EnvVarGuard::set("XDG_DATA_HOME", "relative/data");
+        assert_eq!(
+            config_dir().expect("config dir"),
+            PathBuf::from("/tmp/xdg-config/opensession")
+        );
+        assert_eq!(
+            data_dir().expect("data dir"),
+            home_dir()
+                .expect("home dir")
+                .join(".local")
+                .join("share")
+                .join("opensession")
+        );
+    }
+
+    #[test]
+    fn opensession_home_overrides_config_and_data() {
+        let _lock = env_test_lock().lock().expect("env lock");
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tmp.path().to_path_buf();
+        let _home = EnvVarGuard::set(OPENSESSION_HOME_ENV, &root.to_string_lossy());
+        let _config = EnvVarGuard::set("XDG_CONFIG_HOME", "/tmp/xdg-config");
+        let _db = EnvVarGuard::clear("OPENSESSION_LOCAL_DB_PATH");
+        assert_eq!(config_dir().expect("config dir"), root.join("config"));
+        assert_eq!(data_dir().expect("data dir"), root.join("data"));
+        assert_eq!(
+            local_db_path().expect("local db path"),
+            root.join("data").join("local.db")
+        );
+    }
+
+    #[test]
+    fn move_dir_once_relocates_only_into_a_fresh_target() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let from = tmp.path().join("legacy");
+        let to = tmp.path().join("xdg").join("opensession");
+        std::fs::create_dir_all(from.join("objects")).expect("create legacy");
+        std::fs::write(from.join("objects").join("a"), "a").expect("write legacy");
+
+        assert!(move_dir_once(&from, &to).expect("move"));
+        assert!(!from.exists());
+        assert_eq!(
+            std::fs::read_to_string(to.join("objects").join("a")).expect("read moved"),
+            "a"
+        );
+
+        std::fs::create_dir_all(&from).expect("recreate legacy");
+        assert!(!move_dir_once(&from, &to).expect("second move"));
+        assert!(from.exists(), "existing target must not be replaced");
+        assert!(!move_dir_once(&from, &from.join("config")).expect("nested move"));
+    }
+
+    #[test]
+    fn opensession_home_override_never_receives_legacy_dirs() {
+        let _lock = env_test_lock().lock().expect("env lock");
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let _home = EnvVarGuard::set(OPENSESSION_HOME_ENV, &tmp.path().to_string_lossy());
+        assert!(migrate_legacy_dirs().expect("migrate").is_empty());
+        assert!(!tmp.path().join("config").exists());
+        assert!(!tmp.path().join("data").exists());
+    }
+
+    #[test]
+    fn pid_file_is_live_only_for_running_processes() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let pid_path = tmp.path().join("daemon.pid");
+        assert!(!pid_file_is_live(&pid_path));
+        std::fs::write(&pid_path, "not-a-pid").expect("write pid");
+        assert!(!pid_file_is_live(&pid_path));
+        std::fs::write(&pid_path, std::process::id().to_string()).expect("write pid");
+        assert_eq!(pid_file_is_live(&pid_path), cfg!(unix));
     }
 
     #[test]
@@ -158,4 +461,23 @@ mod tests {
             PathBuf::from("/tmp/opensession-test.db")
         );
     }
+
+    #[test]
+    fn expand_home_accepts_either_separator() {
+        let home = Path::new("home");
+        let expected = home.join(".codex").join("sessions");
+        assert_eq!(expand_home("~/.codex/sessions", home), expected);
+        assert_eq!(expand_home("~\\.codex\\sessions\\", home), expected);
+        assert_eq!(expand_home("~", home), home.to_path_buf());
+        assert_eq!(expand_home("~other/x", home), PathBuf::from("~other/x"));
+        assert_eq!(expand_home("/abs/path", home), PathBuf::from("/abs/path"));
+    }
+
+    #[test]
+    fn portable_path_text_uses_forward_slashes() {
+        assert_eq!(
+            portable_path_text(Path::new(r"C:\Users\me\.gemini\tmp\x")),
+            "C:/Users/me/.gemini/tmp/x"
+        );
+    }
 }
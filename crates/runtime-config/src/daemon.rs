@@ -5,8 +5,9 @@ use crate::defaults::{
     default_session_default_view,
 };
 use crate::{
-    ChangeReaderSettings, GitStorageSettings, IdentitySettings, LifecycleSettings, PrivacySettings,
-    ServerSettings, SummarySettings, VectorSearchSettings, WatcherSettings,
+    ChangeReaderSettings, GitStorageSettings, IdentitySettings, IssueSettings, LifecycleSettings,
+    PrefetchSettings, PrivacySettings, ServerSettings, SummarySettings, TeamRoute, ToolSettings,
+    UiSettings, VectorSearchSettings, WatcherSettings,
 };
 use serde::{Deserialize, Serialize};
 
@@ -32,6 +33,16 @@ pub struct DaemonConfig {
     pub change_reader: ChangeReaderSettings,
     #[serde(default)]
     pub lifecycle: LifecycleSettings,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<TeamRoute>,
+    #[serde(default, skip_serializing_if = "IssueSettings::is_empty")]
+    pub issues: IssueSettings,
+    #[serde(default, skip_serializing_if = "UiSettings::is_empty")]
+    pub ui: UiSettings,
+    #[serde(default, skip_serializing_if = "ToolSettings::is_empty")]
+    pub tools: ToolSettings,
+    #[serde(default, skip_serializing_if = "PrefetchSettings::is_default")]
+    pub prefetch: PrefetchSettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +53,14 @@ pub struct DaemonSettings {
     pub debounce_secs: u64,
     #[serde(default = "default_publish_on")]
     pub publish_on: PublishMode,
+    /// Rule gating auto-publish, e.g. `publish if repo in [a, b] and !has_errors`.
+    /// Empty publishes every session.
+    #[serde(default)]
+    pub publish_policy: String,
+    /// Shell commands run before publishing; each gets the session JSON on stdin
+    /// and may print a replacement session or exit non-zero to veto.
+    #[serde(default)]
+    pub pre_upload_hooks: Vec<String>,
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
     #[serde(default = "default_health_check_interval")]
@@ -62,6 +81,8 @@ impl Default for DaemonSettings {
             auto_publish: false,
             debounce_secs: 5,
             publish_on: PublishMode::Manual,
+            publish_policy: String::new(),
+            pre_upload_hooks: Vec::new(),
             max_retries: 3,
             health_check_interval_secs: 300,
             realtime_debounce_ms: 500,
@@ -131,6 +131,7 @@ pub const DEFAULT_WATCH_PATHS: &[&str] = &[
     "~/.gemini/tmp",
     "~/Library/Application Support/Cursor/User",
     "~/.config/Cursor/User",
+    "~/AppData/Roaming/Cursor/User",
 ];
 
 pub fn default_watch_paths() -> Vec<String> {
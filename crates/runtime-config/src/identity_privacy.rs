@@ -25,6 +25,24 @@ pub struct PrivacySettings {
     pub exclude_patterns: Vec<String>,
     #[serde(default)]
     pub exclude_tools: Vec<String>,
+    /// Record OS, toolchain versions, dirty-tree state and container runtime
+    /// for each session when the daemon indexes it. Off by default.
+    #[serde(default)]
+    pub capture_environment: bool,
+    /// Send anonymous usage counters (sessions indexed, parse failures,
+    /// upload errors) to the configured server. Off by default.
+    #[serde(default)]
+    pub telemetry: bool,
+    /// Publish only aggregate stats (tokens, duration, tool mix) for every
+    /// session, never the transcript. Usually set per repository in
+    /// `.opensession/config.toml`.
+    #[serde(default)]
+    pub metrics_only: bool,
+    /// Publish only aggregate stats for sessions under these paths. Globs are
+    /// matched like `[[routes]] path` against a session's source file and
+    /// working directory.
+    #[serde(default)]
+    pub metrics_only_paths: Vec<String>,
 }
 
 impl Default for PrivacySettings {
@@ -34,6 +52,10 @@ impl Default for PrivacySettings {
             strip_env_vars: true,
             exclude_patterns: default_exclude_patterns(),
             exclude_tools: Vec::new(),
+            capture_environment: false,
+            telemetry: false,
+            metrics_only: false,
+            metrics_only_paths: Vec::new(),
         }
     }
 }
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Links for issue references found in sessions.
+///
+/// ```toml
+/// [issues.url_templates]
+/// PROJ = "https://acme.atlassian.net/browse/{ref}"
+/// "#" = "https://github.com/acme/app/issues/{number}"
+/// ```
+///
+/// Keys are Jira project keys, or `#` for GitHub-style numbers. `{ref}` expands
+/// to the whole reference (`PROJ-123`) and `{number}` to its number (`123`).
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct IssueSettings {
+    #[serde(default)]
+    pub url_templates: BTreeMap<String, String>,
+}
+
+impl IssueSettings {
+    pub fn is_empty(&self) -> bool {
+        self.url_templates.is_empty()
+    }
+
+    /// The configured URL for `issue_ref` (`PROJ-123` or `#456`), if any.
+    pub fn url_for(&self, issue_ref: &str) -> Option<String> {
+        let (key, number) = match issue_ref.strip_prefix('#') {
+            Some(number) => ("#", number),
+            None => issue_ref.rsplit_once('-')?,
+        };
+        let template = self.url_templates.get(key)?;
+        Some(
+            template
+                .replace("{ref}", issue_ref)
+                .replace("{number}", number),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IssueSettings;
+
+    #[test]
+    fn url_templates_expand_ref_and_number() {
+        let settings: IssueSettings = toml::from_str(
+            r##"
+[url_templates]
+PROJ = "https://acme.atlassian.net/browse/{ref}"
+"#" = "https://github.com/acme/app/issues/{number}"
+"##,
+        )
+        .expect("parse issue settings");
+
+        assert_eq!(
+            settings.url_for("PROJ-123").as_deref(),
+            Some("https://acme.atlassian.net/browse/PROJ-123")
+        );
+        assert_eq!(
+            settings.url_for("#456").as_deref(),
+            Some("https://github.com/acme/app/issues/456")
+        );
+        assert_eq!(settings.url_for("OTHER-1"), None);
+    }
+}
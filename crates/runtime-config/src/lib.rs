@@ -9,9 +9,17 @@ mod daemon;
 mod defaults;
 mod git_storage;
 mod identity_privacy;
+mod issues;
 mod lifecycle;
+mod lint;
+mod prefetch;
+mod repos;
+mod routes;
 mod server;
+mod status;
 mod summary;
+mod tools;
+mod ui;
 mod vector;
 mod watcher;
 
@@ -24,14 +32,28 @@ pub use daemon::{
 pub use defaults::{CONFIG_FILE_NAME, DEFAULT_WATCH_PATHS, default_watch_paths};
 pub use git_storage::{GitRetentionSettings, GitStorageMethod, GitStorageSettings};
 pub use identity_privacy::{IdentitySettings, PrivacySettings};
+pub use issues::IssueSettings;
 pub use lifecycle::LifecycleSettings;
-pub use server::ServerSettings;
+pub use lint::{ConfigIssue, ConfigIssueSeverity, lint_config};
+pub use prefetch::PrefetchSettings;
+pub use repos::{REPO_REGISTRY_FILE_NAME, RepoRegistryFile};
+pub use routes::TeamRoute;
+pub use server::{
+    SERVER_PROFILE_ENV, ServerProfile, ServerSettings, UploadLimitSettings, UploadLimitWindow,
+    parse_clock,
+};
+pub use status::{
+    ActiveSessionStatus, BodyCacheStatus, DAEMON_STATUS_FILE_NAME, DAEMON_STATUS_HEARTBEAT_SECS,
+    DaemonStatus, SyncState,
+};
 pub use summary::{
     SummaryBatchExecutionMode, SummaryBatchScope, SummaryBatchSettings, SummaryOutputShape,
     SummaryPromptSettings, SummaryProvider, SummaryProviderSettings, SummaryProviderTransport,
     SummaryResponseSettings, SummaryResponseStyle, SummarySettings, SummarySourceMode,
     SummaryStorageBackend, SummaryStorageSettings, SummaryTriggerMode,
 };
+pub use tools::{PromptInjection, ToolLaunchSettings, ToolSettings};
+pub use ui::{UiLanguage, UiSettings};
 pub use vector::{
     VectorChunkingMode, VectorSearchGranularity, VectorSearchProvider, VectorSearchSettings,
 };
@@ -110,6 +132,29 @@ interval_secs = 43200
         assert_eq!(cfg.git_storage.retention.interval_secs, 43_200);
     }
 
+    #[test]
+    fn prefetch_is_off_by_default_and_only_serialized_when_set() {
+        let cfg = DaemonConfig::default();
+        assert!(!cfg.prefetch.enabled);
+        assert!(
+            !toml::to_string(&cfg)
+                .expect("serialize")
+                .contains("[prefetch]")
+        );
+
+        let cfg: DaemonConfig = toml::from_str(
+            r#"
+[prefetch]
+enabled = true
+max_cache_mb = 64
+"#,
+        )
+        .expect("parse config");
+        assert!(cfg.prefetch.enabled);
+        assert_eq!(cfg.prefetch.recent_days, 7);
+        assert_eq!(cfg.prefetch.max_cache_bytes(), 64 * 1024 * 1024);
+    }
+
     #[test]
     fn summary_provider_requires_canonical_values() {
         let parsed: Result<DaemonConfig, _> = toml::from_str(
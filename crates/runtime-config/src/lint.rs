@@ -0,0 +1,435 @@
+use crate::{DaemonConfig, SummaryProvider, parse_clock};
+use std::fmt;
+use std::ops::Range;
+use toml_edit::{ImDocument, Item};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigIssueSeverity {
+    Warning,
+    Error,
+}
+
+impl ConfigIssueSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// A problem found in `opensession.toml` that serde defaults would otherwise hide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub severity: ConfigIssueSeverity,
+    /// Dotted key path, e.g. `server.url` or `routes.0.team`; empty for the whole file.
+    pub key: String,
+    /// 1-based line in the source, when the key appears in the file.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    pub fn is_error(&self) -> bool {
+        self.severity == ConfigIssueSeverity::Error
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {line}: ")?;
+        }
+        write!(f, "{}: ", self.severity.as_str())?;
+        if !self.key.is_empty() {
+            write!(f, "`{}` ", self.key)?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+/// Check `opensession.toml` source for syntax errors, unknown keys, invalid URLs,
+/// out-of-range values and conflicting options. Issues are ordered by line.
+pub fn lint_config(source: &str) -> Vec<ConfigIssue> {
+    let document = match ImDocument::parse(source) {
+        Ok(document) => document,
+        Err(err) => {
+            return vec![ConfigIssue {
+                severity: ConfigIssueSeverity::Error,
+                key: String::new(),
+                line: err.span().map(|span| line_of(source, span.start)),
+                message: format!("invalid TOML: {}", err.message()),
+            }];
+        }
+    };
+
+    let mut unknown = Vec::new();
+    let parsed: Result<DaemonConfig, _> =
+        serde_ignored::deserialize(toml::Deserializer::new(source), |path| {
+            let mut segments = Vec::new();
+            path_segments(&path, &mut segments);
+            unknown.push(segments);
+        });
+    let config = match parsed {
+        Ok(config) => config,
+        Err(err) => {
+            return vec![ConfigIssue {
+                severity: ConfigIssueSeverity::Error,
+                key: String::new(),
+                line: err.span().map(|span| line_of(source, span.start)),
+                message: err.message().to_string(),
+            }];
+        }
+    };
+
+    let mut lint = Lint {
+        source,
+        document: &document,
+        issues: Vec::new(),
+    };
+    for segments in unknown {
+        lint.push(
+            ConfigIssueSeverity::Warning,
+            &segments,
+            "is not a recognized setting and is ignored",
+        );
+    }
+    lint.check_values(&config);
+
+    let mut issues = lint.issues;
+    issues.sort_by_key(|issue| (issue.line.unwrap_or(usize::MAX), issue.key.clone()));
+    issues
+}
+
+struct Lint<'a> {
+    source: &'a str,
+    document: &'a ImDocument<&'a str>,
+    issues: Vec<ConfigIssue>,
+}
+
+impl Lint<'_> {
+    fn push<S: AsRef<str>>(
+        &mut self,
+        severity: ConfigIssueSeverity,
+        segments: &[S],
+        message: impl Into<String>,
+    ) {
+        let line =
+            locate(self.document.as_item(), segments).map(|span| line_of(self.source, span.start));
+        self.issues.push(ConfigIssue {
+            severity,
+            key: segments
+                .iter()
+                .map(AsRef::as_ref)
+                .collect::<Vec<_>>()
+                .join("."),
+            line,
+            message: message.into(),
+        });
+    }
+
+    fn check_values(&mut self, config: &DaemonConfig) {
+        use ConfigIssueSeverity::{Error, Warning};
+
+        if !is_http_url(&config.server.url) {
+            self.push(
+                Error,
+                &["server", "url"],
+                "must be an http:// or https:// URL",
+            );
+        }
+        if config.daemon.auto_publish && config.server.api_key.trim().is_empty() {
+            self.push(
+                Error,
+                &["daemon", "auto_publish"],
+                "requires `server.api_key`; uploads would be rejected",
+            );
+        }
+        for (name, profile) in &config.server.profiles {
+            if !is_http_url(&profile.url) {
+                self.push(
+                    Error,
+                    &["server", "profiles", name.as_str(), "url"],
+                    "must be an http:// or https:// URL",
+                );
+            }
+        }
+        for (index, window) in config.server.upload_limit.windows.iter().enumerate() {
+            let index = index.to_string();
+            for (key, value) in [("start", &window.start), ("end", &window.end)] {
+                if parse_clock(value).is_none() {
+                    self.push(
+                        Error,
+                        &["server", "upload_limit", "windows", index.as_str(), key],
+                        "must be a 24-hour `HH:MM` time",
+                    );
+                }
+            }
+        }
+        if config.daemon.debounce_secs == 0 {
+            self.push(
+                Warning,
+                &["daemon", "debounce_secs"],
+                "is 0; sessions are re-read on every file write",
+            );
+        } else if config.daemon.debounce_secs > 3600 {
+            self.push(
+                Warning,
+                &["daemon", "debounce_secs"],
+                "is over an hour; changes will appear very late",
+            );
+        }
+
+        if config.summary.provider.id == SummaryProvider::Ollama {
+            if !is_http_url(&config.summary.provider.endpoint) {
+                self.push(
+                    Error,
+                    &["summary", "provider", "endpoint"],
+                    "must be an http:// or https:// URL for the ollama provider",
+                );
+            }
+            if config.summary.provider.model.trim().is_empty() {
+                self.push(
+                    Warning,
+                    &["summary", "provider", "model"],
+                    "is empty; summaries stay disabled until an ollama model is set",
+                );
+            }
+        }
+
+        if config.lifecycle.enabled {
+            for (key, value) in [
+                (
+                    "session_ttl_days",
+                    u64::from(config.lifecycle.session_ttl_days),
+                ),
+                (
+                    "summary_ttl_days",
+                    u64::from(config.lifecycle.summary_ttl_days),
+                ),
+                (
+                    "cleanup_interval_secs",
+                    config.lifecycle.cleanup_interval_secs,
+                ),
+            ] {
+                if value == 0 {
+                    self.push(
+                        Error,
+                        &["lifecycle", key],
+                        "must be greater than 0 while lifecycle cleanup is enabled",
+                    );
+                }
+            }
+        }
+
+        for (index, route) in config.routes.iter().enumerate() {
+            let index = index.to_string();
+            if route.path.trim().is_empty() {
+                self.push(
+                    Error,
+                    &["routes", index.as_str(), "path"],
+                    "cannot be empty",
+                );
+            }
+            if route.team.trim().is_empty() {
+                self.push(
+                    Error,
+                    &["routes", index.as_str(), "team"],
+                    "cannot be empty",
+                );
+            }
+        }
+
+        for (key, template) in &config.issues.url_templates {
+            if !is_http_url(template) {
+                self.push(
+                    Error,
+                    &["issues", "url_templates", key.as_str()],
+                    "must be an http:// or https:// URL",
+                );
+            }
+        }
+
+        for (tool, launch) in &config.tools.launchers {
+            if launch
+                .binary
+                .as_deref()
+                .is_some_and(|binary| binary.trim().is_empty())
+            {
+                self.push(
+                    Error,
+                    &["tools", tool.as_str(), "binary"],
+                    "cannot be empty",
+                );
+            }
+            if launch.resume_args.as_ref().is_some_and(|args| {
+                !args.is_empty() && !args.iter().any(|arg| arg.contains("{session_id}"))
+            }) {
+                self.push(
+                    Warning,
+                    &["tools", tool.as_str(), "resume_args"],
+                    "has no `{session_id}` placeholder, so every resume opens the same session",
+                );
+            }
+        }
+    }
+}
+
+fn path_segments(path: &serde_ignored::Path<'_>, out: &mut Vec<String>) {
+    match path {
+        serde_ignored::Path::Root => {}
+        serde_ignored::Path::Seq { parent, index } => {
+            path_segments(parent, out);
+            out.push(index.to_string());
+        }
+        serde_ignored::Path::Map { parent, key } => {
+            path_segments(parent, out);
+            out.push(key.clone());
+        }
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => path_segments(parent, out),
+    }
+}
+
+/// Span of the deepest item along `segments` that carries a location.
+fn locate<S: AsRef<str>>(root: &Item, segments: &[S]) -> Option<Range<usize>> {
+    let mut item = root;
+    let mut span = None;
+    for segment in segments {
+        let segment = segment.as_ref();
+        let next = match segment.parse::<usize>() {
+            Ok(index) if item.is_array() || item.is_array_of_tables() => item.get(index),
+            _ => item.get(segment),
+        };
+        let Some(next) = next else {
+            break;
+        };
+        item = next;
+        span = item.span().or(span);
+    }
+    span
+}
+
+/// 1-based line of `offset`; errors reported at end of input map to the last line.
+fn line_of(source: &str, offset: usize) -> usize {
+    let end = source.trim_end_matches('\n').len();
+    source[..offset.min(end)].matches('\n').count() + 1
+}
+
+fn is_http_url(raw: &str) -> bool {
+    let raw = raw.trim();
+    ["http://", "https://"].iter().any(|scheme| {
+        raw.strip_prefix(scheme).is_some_and(|rest| {
+            let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+            !host.is_empty() && !host.contains(char::is_whitespace)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfigIssueSeverity, lint_config};
+
+    #[test]
+    fn default_config_is_clean() {
+        assert!(lint_config("").is_empty());
+        let defaults = toml::to_string_pretty(&crate::DaemonConfig::default()).expect("serialize");
+        assert_eq!(lint_config(&defaults), Vec::new());
+    }
+
+    #[test]
+    fn unknown_keys_report_their_line() {
+        let issues = lint_config(
+            r#"
+[server]
+url = "https://opensession.io"
+api_kye = "typo"
+
+[watchers]
+claude_code = true
+"#,
+        );
+        let keys: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.key.as_str(), issue.line, issue.severity))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("server.api_kye", Some(4), ConfigIssueSeverity::Warning),
+                (
+                    "watchers.claude_code",
+                    Some(7),
+                    ConfigIssueSeverity::Warning
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_values_and_conflicts_are_errors() {
+        let issues = lint_config(
+            r#"
+[daemon]
+auto_publish = true
+
+[server]
+url = "opensession.io"
+
+[lifecycle]
+session_ttl_days = 0
+
+[[routes]]
+path = "~/work"
+team = ""
+
+[issues.url_templates]
+PROJ = "jira/browse/{ref}"
+
+[[server.upload_limit.windows]]
+start = "19:00"
+end = "8am"
+
+[tools.aider]
+binary = " "
+"#,
+        );
+        let errors: Vec<_> = issues
+            .iter()
+            .filter(|issue| issue.is_error())
+            .map(|issue| (issue.key.as_str(), issue.line))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                ("daemon.auto_publish", Some(3)),
+                ("server.url", Some(6)),
+                ("lifecycle.session_ttl_days", Some(9)),
+                ("routes.0.team", Some(13)),
+                ("issues.url_templates.PROJ", Some(16)),
+                ("server.upload_limit.windows.0.end", Some(20)),
+                ("tools.aider.binary", Some(23)),
+            ]
+        );
+    }
+
+    #[test]
+    fn syntax_and_type_errors_point_at_the_line() {
+        let syntax = lint_config("[server]\nurl = \"https://x\n");
+        assert_eq!(syntax.len(), 1);
+        assert_eq!(syntax[0].line, Some(2));
+        assert_eq!(lint_config("a = 1\nbroken = [\n")[0].line, Some(2));
+
+        let typed = lint_config("[daemon]\ndebounce_secs = \"soon\"\n");
+        assert_eq!(typed.len(), 1);
+        assert!(typed[0].is_error());
+        assert_eq!(typed[0].line, Some(2));
+        assert_eq!(
+            typed[0].to_string().split(':').next(),
+            Some("line 2"),
+            "{}",
+            typed[0]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// Background download of teammates' recent session bodies into the local
+/// body cache, so they can be opened without a connection.
+///
+/// ```toml
+/// [prefetch]
+/// enabled = true
+/// recent_days = 7
+/// max_cache_mb = 256
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PrefetchSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Prefetch sessions uploaded within this many days.
+    #[serde(default = "default_prefetch_recent_days")]
+    pub recent_days: u32,
+    /// Upper bound for the whole body cache; the oldest entries are evicted
+    /// past it.
+    #[serde(default = "default_prefetch_max_cache_mb")]
+    pub max_cache_mb: u64,
+    #[serde(default = "default_prefetch_interval_secs")]
+    pub interval_secs: u64,
+}
+
+impl Default for PrefetchSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            recent_days: default_prefetch_recent_days(),
+            max_cache_mb: default_prefetch_max_cache_mb(),
+            interval_secs: default_prefetch_interval_secs(),
+        }
+    }
+}
+
+impl PrefetchSettings {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn max_cache_bytes(&self) -> u64 {
+        self.max_cache_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+fn default_prefetch_recent_days() -> u32 {
+    7
+}
+
+fn default_prefetch_max_cache_mb() -> u64 {
+    256
+}
+
+fn default_prefetch_interval_secs() -> u64 {
+    900
+}
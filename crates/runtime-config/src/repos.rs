@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// File name of the repo registry inside the config directory.
+pub const REPO_REGISTRY_FILE_NAME: &str = "repo-registry.json";
+
+/// Repository roots the daemon maintains (git-native retention). The daemon
+/// adds repos it stores sessions in; `opensession init --repo` adds them up front.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoRegistryFile {
+    #[serde(default)]
+    pub repos: BTreeSet<String>,
+}
+
+impl RepoRegistryFile {
+    /// Empty when the file does not exist yet.
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        match std::fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Replace the file atomically so the daemon never reads a partial list.
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{REPO_REGISTRY_FILE_NAME, RepoRegistryFile};
+
+    #[test]
+    fn registry_round_trips_and_defaults_when_missing() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(REPO_REGISTRY_FILE_NAME);
+        assert_eq!(
+            RepoRegistryFile::read(&path).expect("missing file"),
+            RepoRegistryFile::default()
+        );
+
+        let mut registry = RepoRegistryFile::default();
+        registry.repos.insert("/work/app".to_string());
+        registry.write(&path).expect("write registry");
+        assert_eq!(RepoRegistryFile::read(&path).expect("read"), registry);
+
+        std::fs::write(&path, "not json").expect("corrupt");
+        assert!(RepoRegistryFile::read(&path).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Maps sessions under a path to a team (`[[routes]] path = "~/work/**" team = "acme"`).
+///
+/// `path` is a glob (`*`, `**`, `?`) matched against a session's source file and
+/// working directory; a pattern without glob characters matches that directory
+/// and everything below it. The first matching route wins.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TeamRoute {
+    pub path: String,
+    pub team: String,
+}
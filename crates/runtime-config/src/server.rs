@@ -1,5 +1,9 @@
 use crate::defaults::default_server_url;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Environment variable naming the server profile to use, like `--profile`.
+pub const SERVER_PROFILE_ENV: &str = "OPENSESSION_PROFILE";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerSettings {
@@ -7,6 +11,14 @@ pub struct ServerSettings {
     pub url: String,
     #[serde(default)]
     pub api_key: String,
+    /// Team for sessions that match no `[[routes]]` entry; empty for none.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub team: String,
+    #[serde(default, skip_serializing_if = "UploadLimitSettings::is_unlimited")]
+    pub upload_limit: UploadLimitSettings,
+    /// Named alternatives to the top-level server, e.g. `[server.profiles.work]`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, ServerProfile>,
 }
 
 impl Default for ServerSettings {
@@ -14,6 +26,172 @@ impl Default for ServerSettings {
         Self {
             url: default_server_url(),
             api_key: String::new(),
+            team: String::new(),
+            upload_limit: UploadLimitSettings::default(),
+            profiles: BTreeMap::new(),
         }
     }
 }
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerProfile {
+    pub url: String,
+    #[serde(default)]
+    pub api_key: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub team: String,
+}
+
+impl ServerSettings {
+    /// Make profile `name` the active server by copying its url, API key and
+    /// default team over the top-level values. Returns `false` if no such profile.
+    pub fn select_profile(&mut self, name: &str) -> bool {
+        let Some(profile) = self.profiles.get(name.trim()) else {
+            return false;
+        };
+        self.url = profile.url.clone();
+        self.api_key = profile.api_key.clone();
+        self.team = profile.team.clone();
+        true
+    }
+
+    pub fn profile_names(&self) -> Vec<&str> {
+        self.profiles.keys().map(String::as_str).collect()
+    }
+}
+
+/// Upload bandwidth cap, optionally varying by local time of day.
+///
+/// `kbps = 0` means unlimited. The first window containing the current time
+/// overrides `kbps`, so `{ start = "19:00", end = "08:00", kbps = 0 }` lifts
+/// the cap overnight.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadLimitSettings {
+    #[serde(default)]
+    pub kbps: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<UploadLimitWindow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadLimitWindow {
+    /// Local start time, `HH:MM`.
+    pub start: String,
+    /// Local end time, `HH:MM`; earlier than `start` wraps past midnight.
+    pub end: String,
+    #[serde(default)]
+    pub kbps: u32,
+}
+
+impl UploadLimitSettings {
+    pub fn is_unlimited(&self) -> bool {
+        self.kbps == 0 && self.windows.is_empty()
+    }
+
+    /// Effective limit in KB/s at `minute_of_day` (0..1440), `None` when unlimited.
+    /// Windows with unparseable times are skipped.
+    pub fn kbps_at(&self, minute_of_day: u32) -> Option<u32> {
+        let kbps = self
+            .windows
+            .iter()
+            .find(|window| window.contains(minute_of_day))
+            .map_or(self.kbps, |window| window.kbps);
+        (kbps > 0).then_some(kbps)
+    }
+}
+
+impl UploadLimitWindow {
+    fn contains(&self, minute_of_day: u32) -> bool {
+        let (Some(start), Some(end)) = (parse_clock(&self.start), parse_clock(&self.end)) else {
+            return false;
+        };
+        if start <= end {
+            (start..end).contains(&minute_of_day)
+        } else {
+            minute_of_day >= start || minute_of_day < end
+        }
+    }
+}
+
+/// Parse `HH:MM` (24-hour) into minutes since midnight.
+pub fn parse_clock(raw: &str) -> Option<u32> {
+    let (hours, minutes) = raw.trim().split_once(':')?;
+    if minutes.len() != 2 {
+        return None;
+    }
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ServerProfile, ServerSettings, UploadLimitSettings, UploadLimitWindow, parse_clock,
+    };
+
+    fn window(start: &str, end: &str, kbps: u32) -> UploadLimitWindow {
+        UploadLimitWindow {
+            start: start.to_string(),
+            end: end.to_string(),
+            kbps,
+        }
+    }
+
+    #[test]
+    fn parse_clock_accepts_24_hour_times_only() {
+        assert_eq!(parse_clock("00:00"), Some(0));
+        assert_eq!(parse_clock("9:05"), Some(545));
+        assert_eq!(parse_clock("23:59"), Some(1439));
+        assert_eq!(parse_clock("24:00"), None);
+        assert_eq!(parse_clock("12:60"), None);
+        assert_eq!(parse_clock("noon"), None);
+    }
+
+    #[test]
+    fn windows_override_the_default_and_wrap_past_midnight() {
+        let limit = UploadLimitSettings {
+            kbps: 256,
+            windows: vec![window("19:00", "08:00", 0), window("12:00", "13:00", 1024)],
+        };
+        assert_eq!(limit.kbps_at(10 * 60), Some(256));
+        assert_eq!(limit.kbps_at(12 * 60 + 30), Some(1024));
+        assert_eq!(limit.kbps_at(13 * 60), Some(256));
+        assert_eq!(limit.kbps_at(23 * 60), None);
+        assert_eq!(limit.kbps_at(7 * 60 + 59), None);
+        assert_eq!(UploadLimitSettings::default().kbps_at(0), None);
+    }
+
+    #[test]
+    fn select_profile_replaces_active_server() {
+        let mut server: ServerSettings = toml::from_str(
+            r#"
+url = "https://opensession.io"
+api_key = "osk_public"
+
+[profiles.work]
+url = "https://sessions.corp.example"
+api_key = "osk_work"
+team = "platform"
+"#,
+        )
+        .expect("parse server settings");
+        assert_eq!(server.profile_names(), vec!["work"]);
+        assert_eq!(
+            server.profiles["work"],
+            ServerProfile {
+                url: "https://sessions.corp.example".to_string(),
+                api_key: "osk_work".to_string(),
+                team: "platform".to_string(),
+            }
+        );
+
+        assert!(!server.select_profile("oss"));
+        assert_eq!(server.url, "https://opensession.io");
+
+        assert!(server.select_profile("work"));
+        assert_eq!(server.url, "https://sessions.corp.example");
+        assert_eq!(server.api_key, "osk_work");
+        assert_eq!(server.team, "platform");
+    }
+}
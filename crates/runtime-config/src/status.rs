@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// File name of the daemon status snapshot inside the config directory.
+pub const DAEMON_STATUS_FILE_NAME: &str = "daemon.status.json";
+
+/// Seconds between status writes while nothing changes; readers treat older
+/// snapshots as a stopped daemon.
+pub const DAEMON_STATUS_HEARTBEAT_SECS: i64 = 30;
+
+/// Snapshot the daemon writes for cheap readers such as `opensession statusline`.
+/// Timestamps are Unix seconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub pid: u32,
+    pub updated_at: i64,
+    /// Most recently changed session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_session: Option<ActiveSessionStatus>,
+    /// Session files waiting for the debounce window.
+    #[serde(default)]
+    pub pending_files: u32,
+    #[serde(default)]
+    pub sync: SyncState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_synced_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Remote body prefetch, when `[prefetch]` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_cache: Option<BodyCacheStatus>,
+}
+
+/// Body cache size and what prefetch found there since the daemon started.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BodyCacheStatus {
+    pub entries: u64,
+    pub bytes: u64,
+    pub max_bytes: u64,
+    /// Bodies already cached and still current on the server.
+    pub hits: u64,
+    /// Bodies that had to be downloaded.
+    pub misses: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_prefetch_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveSessionStatus {
+    pub session_id: String,
+    pub tool: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub started_at: i64,
+    pub last_event_at: i64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+/// Outcome of the last session the daemon processed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncState {
+    #[default]
+    Idle,
+    /// Indexed and share-ready.
+    Synced,
+    /// Indexed but kept local (manual mode, publish policy or hook veto).
+    LocalOnly,
+    Error,
+}
+
+impl DaemonStatus {
+    /// `None` when the file is missing or unreadable.
+    pub fn read(path: &Path) -> Option<Self> {
+        let bytes = std::fs::read(path).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// Replace the snapshot atomically so readers never see a partial file.
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(self)?)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Whether the daemon refreshed the snapshot recently enough to trust it.
+    pub fn is_fresh(&self, now: i64) -> bool {
+        now - self.updated_at <= DAEMON_STATUS_HEARTBEAT_SECS * 3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_round_trips_through_the_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(DAEMON_STATUS_FILE_NAME);
+        assert!(DaemonStatus::read(&path).is_none());
+
+        let status = DaemonStatus {
+            pid: 42,
+            updated_at: 1_000,
+            active_session: Some(ActiveSessionStatus {
+                session_id: "s1".into(),
+                tool: "codex".into(),
+                model: Some("gpt-5".into()),
+                started_at: 400,
+                last_event_at: 990,
+                input_tokens: 1_200,
+                output_tokens: 300,
+            }),
+            pending_files: 1,
+            sync: SyncState::Synced,
+            last_synced_at: Some(995),
+            last_error: None,
+            body_cache: Some(BodyCacheStatus {
+                entries: 3,
+                bytes: 4_096,
+                max_bytes: 1 << 20,
+                hits: 2,
+                misses: 1,
+                last_prefetch_at: Some(900),
+            }),
+        };
+        status.write(&path).expect("write status");
+        assert_eq!(DaemonStatus::read(&path), Some(status.clone()));
+        assert!(status.is_fresh(1_000 + DAEMON_STATUS_HEARTBEAT_SECS));
+        assert!(!status.is_fresh(1_000 + DAEMON_STATUS_HEARTBEAT_SECS * 4));
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How `opensession continue` starts each agent, keyed by tool name.
+///
+/// ```toml
+/// [tools.claude-code]
+/// binary = "/opt/claude/bin/claude"
+///
+/// [tools.aider]
+/// binary = "aider"
+/// prompt = "file"
+/// prompt_flag = "--read"
+/// ```
+///
+/// Built-in tools (`claude-code`, `codex`, `gemini`, `opencode`) work without
+/// an entry; set only the fields to override. Other tools need at least
+/// `binary`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct ToolSettings {
+    pub launchers: BTreeMap<String, ToolLaunchSettings>,
+}
+
+impl ToolSettings {
+    pub fn is_empty(&self) -> bool {
+        self.launchers.is_empty()
+    }
+
+    pub fn get(&self, tool: &str) -> Option<&ToolLaunchSettings> {
+        self.launchers.get(tool)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ToolLaunchSettings {
+    /// Executable name or path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary: Option<String>,
+    /// Arguments that reopen the tool's own session; `{session_id}` expands to
+    /// its id, e.g. `["--resume", "{session_id}"]`. Empty turns resume off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_args: Option<Vec<String>>,
+    /// What the handoff is passed as.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<PromptInjection>,
+    /// Flag placed before the prompt or file, e.g. `-i`; positional when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_flag: Option<String>,
+}
+
+/// How an agent receives the handoff.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptInjection {
+    /// A first prompt that points at the handoff file.
+    Argument,
+    /// The handoff file path itself.
+    File,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PromptInjection, ToolSettings};
+
+    #[test]
+    fn tool_entries_parse_by_name() {
+        let settings: ToolSettings = toml::from_str(
+            r#"
+[claude-code]
+binary = "/opt/claude/bin/claude"
+
+[aider]
+binary = "aider"
+prompt = "file"
+prompt_flag = "--read"
+"#,
+        )
+        .expect("parse tool settings");
+
+        let claude = settings.get("claude-code").expect("claude entry");
+        assert_eq!(claude.binary.as_deref(), Some("/opt/claude/bin/claude"));
+        assert_eq!(claude.prompt, None);
+        let aider = settings.get("aider").expect("aider entry");
+        assert_eq!(aider.prompt, Some(PromptInjection::File));
+        assert_eq!(aider.prompt_flag.as_deref(), Some("--read"));
+        assert!(toml::from_str::<ToolSettings>("[x]\nprompt = \"stdin\"").is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+/// Terminal-facing presentation settings.
+///
+/// ```toml
+/// [ui]
+/// language = "ko"
+/// ```
+///
+/// `OPENSESSION_LANG` overrides `language`; without either, the CLI follows
+/// `LC_ALL` / `LC_MESSAGES` / `LANG`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct UiSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<UiLanguage>,
+}
+
+impl UiSettings {
+    pub fn is_empty(&self) -> bool {
+        self.language.is_none()
+    }
+}
+
+/// Languages with translated CLI messages.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UiLanguage {
+    En,
+    Ko,
+}
+
+impl UiLanguage {
+    /// Parse a language tag such as `ko`, `ko-KR` or `en_US.UTF-8`.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let tag = tag.trim().to_ascii_lowercase();
+        let primary = tag.split(['_', '-', '.']).next().unwrap_or_default();
+        match primary {
+            "en" => Some(Self::En),
+            "ko" => Some(Self::Ko),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UiLanguage, UiSettings};
+
+    #[test]
+    fn language_parses_from_config_and_tags() {
+        let settings: UiSettings = toml::from_str("language = \"ko\"").expect("parse ui settings");
+        assert_eq!(settings.language, Some(UiLanguage::Ko));
+        assert!(toml::from_str::<UiSettings>("language = \"fr\"").is_err());
+
+        assert_eq!(UiLanguage::from_tag("ko_KR.UTF-8"), Some(UiLanguage::Ko));
+        assert_eq!(UiLanguage::from_tag("en-US"), Some(UiLanguage::En));
+        assert_eq!(UiLanguage::from_tag("C"), None);
+    }
+}
@@ -1,8 +1,18 @@
 use std::path::PathBuf;
 
+use anyhow::Context;
+
+use opensession_api::RetentionPolicy;
 use opensession_api::crypto::CredentialKeyring;
 use opensession_api::oauth::{self, OAuthProviderConfig};
 
+use crate::body_signing::BodySigner;
+use crate::github_app::GithubAppConfig;
+use crate::proxy_auth::ProxyAuthConfig;
+use crate::retention;
+use crate::team_quota::TeamQuotas;
+use crate::upload_policy::UploadPolicy;
+
 #[derive(Clone)]
 pub struct AppConfig {
     pub base_url: String,
@@ -14,6 +24,13 @@ pub struct AppConfig {
     pub public_feed_enabled: bool,
     pub local_review_root: Option<PathBuf>,
     pub credential_keyring: Option<CredentialKeyring>,
+    pub github_app: Option<GithubAppConfig>,
+    pub upload_policy: UploadPolicy,
+    pub proxy_auth: Option<ProxyAuthConfig>,
+    pub body_signer: Option<BodySigner>,
+    pub team_quotas: TeamQuotas,
+    /// Retention for teams without a policy; `None` keeps sessions forever.
+    pub retention_default: Option<RetentionPolicy>,
 }
 
 pub struct ServerBootstrap {
@@ -23,7 +40,9 @@ pub struct ServerBootstrap {
     pub config: AppConfig,
 }
 
-pub fn load_server_bootstrap() -> ServerBootstrap {
+/// Fails when a security-relevant setting is present but invalid, so a typo
+/// cannot silently start the server with that protection off.
+pub fn load_server_bootstrap() -> anyhow::Result<ServerBootstrap> {
     let data_dir = std::env::var("OPENSESSION_DATA_DIR")
         .map(PathBuf::from)
         .unwrap_or_else(|_| PathBuf::from("data"));
@@ -39,7 +58,7 @@ pub fn load_server_bootstrap() -> ServerBootstrap {
     let public_feed_enabled_raw =
         std::env::var(opensession_api::deploy::ENV_PUBLIC_FEED_ENABLED).ok();
 
-    ServerBootstrap {
+    Ok(ServerBootstrap {
         data_dir,
         web_dir,
         port,
@@ -58,8 +77,14 @@ pub fn load_server_bootstrap() -> ServerBootstrap {
                 .ok()
                 .map(PathBuf::from),
             credential_keyring: load_credential_keyring(),
+            github_app: load_github_app(),
+            upload_policy: UploadPolicy::from_env(),
+            proxy_auth: load_proxy_auth()?,
+            body_signer: load_body_signer(),
+            team_quotas: load_team_quotas(),
+            retention_default: load_retention_default(),
         },
-    }
+    })
 }
 
 fn origin_from_base_url(raw: &str) -> Option<String> {
@@ -98,7 +123,9 @@ fn load_oauth_providers() -> Vec<OAuthProviderConfig> {
         .collect()
 }
 
-fn env_trimmed(name: &str) -> Option<String> {
+/// A set, non-blank environment variable, trimmed and with surrounding quotes
+/// removed.
+pub(crate) fn env_trimmed(name: &str) -> Option<String> {
     std::env::var(name)
         .ok()
         .and_then(|value| oauth::normalize_oauth_config_value(&value))
@@ -120,6 +147,67 @@ fn try_load_gitlab() -> Option<OAuthProviderConfig> {
     Some(oauth::gitlab_preset(url, ext_url, id, secret))
 }
 
+/// Generic OpenID Connect provider (Okta, Keycloak, Azure AD) configured by
+/// issuer URL. Its endpoints come from the issuer's discovery document, so
+/// this runs once at startup, before the server accepts requests.
+pub async fn load_oidc_provider() -> Option<OAuthProviderConfig> {
+    let issuer = env_trimmed("OIDC_ISSUER_URL")?;
+    let (Some(id), Some(secret)) = (
+        env_trimmed("OIDC_CLIENT_ID"),
+        env_trimmed("OIDC_CLIENT_SECRET"),
+    ) else {
+        tracing::error!("OIDC_ISSUER_URL is set but OIDC_CLIENT_ID/OIDC_CLIENT_SECRET are missing");
+        return None;
+    };
+    let group_teams =
+        match oauth::parse_group_teams(&env_trimmed("OIDC_GROUP_TEAMS").unwrap_or_default()) {
+            Ok(map) => map,
+            Err(err) => {
+                tracing::error!("invalid OIDC_GROUP_TEAMS: {}", err.message());
+                return None;
+            }
+        };
+    let discovery = match fetch_oidc_discovery(&issuer).await {
+        Ok(discovery) => discovery,
+        Err(err) => {
+            tracing::error!("OIDC discovery for {issuer} failed: {err:#}");
+            return None;
+        }
+    };
+    let mut provider = oauth::oidc_preset(
+        env_trimmed("OIDC_PROVIDER_ID").unwrap_or_else(|| "oidc".to_string()),
+        env_trimmed("OIDC_DISPLAY_NAME").unwrap_or_else(|| "SSO".to_string()),
+        &discovery,
+        id,
+        secret,
+    );
+    if let Some(scopes) = env_trimmed("OIDC_SCOPES") {
+        provider.scopes = scopes;
+    }
+    provider.groups_claim = env_trimmed("OIDC_GROUPS_CLAIM")
+        .or_else(|| (!group_teams.is_empty()).then(|| "groups".to_string()));
+    provider.group_teams = group_teams;
+    tracing::info!(
+        "OAuth provider enabled: {} (OIDC {issuer}, {} group mapping(s))",
+        provider.display_name,
+        provider.group_teams.len()
+    );
+    Some(provider)
+}
+
+async fn fetch_oidc_discovery(issuer: &str) -> anyhow::Result<oauth::OidcDiscovery> {
+    let raw = reqwest::Client::new()
+        .get(oauth::oidc_discovery_url(issuer))
+        .header("Accept", "application/json")
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    oauth::parse_oidc_discovery(issuer, &raw)
+        .map_err(|err| anyhow::anyhow!(err.message().to_string()))
+}
+
 fn load_credential_keyring() -> Option<CredentialKeyring> {
     let active = env_trimmed("OPENSESSION_CREDENTIAL_ACTIVE_KID")?;
     let keyset = env_trimmed("OPENSESSION_CREDENTIAL_KEYS")?;
@@ -132,6 +220,73 @@ fn load_credential_keyring() -> Option<CredentialKeyring> {
     }
 }
 
+fn load_github_app() -> Option<GithubAppConfig> {
+    match GithubAppConfig::from_env() {
+        Ok(Some(app)) => {
+            tracing::info!("GitHub App check runs enabled");
+            Some(app)
+        }
+        Ok(None) => None,
+        Err(err) => {
+            tracing::error!("invalid GitHub App config: {err:#}");
+            None
+        }
+    }
+}
+
+/// Proxy auth turns password sign-in off, so a broken config must stop the
+/// server rather than fall back to passwords.
+fn load_proxy_auth() -> anyhow::Result<Option<ProxyAuthConfig>> {
+    let proxy = ProxyAuthConfig::from_env().context("invalid proxy auth config")?;
+    if proxy.is_some() {
+        tracing::info!("reverse-proxy authentication enabled; password sign-in is disabled");
+    }
+    Ok(proxy)
+}
+
+fn load_body_signer() -> Option<BodySigner> {
+    match BodySigner::from_env() {
+        Ok(Some(signer)) => {
+            tracing::info!("signing session bodies with key {}", signer.public_key());
+            Some(signer)
+        }
+        Ok(None) => None,
+        Err(err) => {
+            tracing::error!("invalid body signing key: {err:#}");
+            None
+        }
+    }
+}
+
+fn load_team_quotas() -> TeamQuotas {
+    match TeamQuotas::from_env() {
+        Ok(quotas) => {
+            if !quotas.is_unlimited() {
+                tracing::info!(
+                    "team quotas: sessions {:?}, body bytes {:?}",
+                    quotas.sessions,
+                    quotas.body_bytes
+                );
+            }
+            quotas
+        }
+        Err(err) => {
+            tracing::error!("invalid team quota config: {err:#}");
+            TeamQuotas::default()
+        }
+    }
+}
+
+fn load_retention_default() -> Option<RetentionPolicy> {
+    match retention::default_policy_from_env() {
+        Ok(policy) => policy,
+        Err(err) => {
+            tracing::error!("invalid retention config, keeping sessions forever: {err:#}");
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::load_server_bootstrap;
@@ -211,7 +366,7 @@ mod tests {
             EnvVarGuard::clear("GITLAB_EXTERNAL_URL"),
         ];
 
-        let bootstrap = load_server_bootstrap();
+        let bootstrap = load_server_bootstrap().expect("bootstrap");
 
         assert_eq!(bootstrap.config.base_url, "https://api.example.test");
         assert_eq!(
@@ -244,7 +399,7 @@ mod tests {
             EnvVarGuard::clear("GITLAB_EXTERNAL_URL"),
         ];
 
-        let bootstrap = load_server_bootstrap();
+        let bootstrap = load_server_bootstrap().expect("bootstrap");
 
         assert_eq!(bootstrap.config.base_url, "http://localhost:3000");
         assert_eq!(
@@ -271,8 +426,27 @@ mod tests {
             EnvVarGuard::clear("GITLAB_EXTERNAL_URL"),
         ];
 
-        let bootstrap = load_server_bootstrap();
+        let bootstrap = load_server_bootstrap().expect("bootstrap");
 
         assert!(bootstrap.config.credential_keyring.is_none());
     }
+
+    #[test]
+    fn bootstrap_fails_on_invalid_proxy_auth_config() {
+        let _lock = lock_env();
+        let _guards = [
+            EnvVarGuard::set("OPENSESSION_PROXY_AUTH_EMAIL_HEADER", "X-Forwarded-Email"),
+            EnvVarGuard::set("OPENSESSION_PROXY_AUTH_TRUSTED_IPS", "10.0.0.0/33"),
+            EnvVarGuard::clear("OPENSESSION_PROXY_AUTH_NAME_HEADER"),
+        ];
+
+        let err = load_server_bootstrap()
+            .err()
+            .expect("invalid proxy auth must fail startup");
+
+        assert!(
+            format!("{err:#}").contains("invalid proxy auth config"),
+            "{err:#}"
+        );
+    }
 }
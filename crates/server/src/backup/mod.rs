@@ -0,0 +1,538 @@
+//! Backup of the SQLite database and session bodies to S3-compatible storage.
+//!
+//! Layout under `OPENSESSION_BACKUP_S3_PREFIX`:
+//!
+//! - `snapshots/<id>/opensession.db` — consistent copy made with `VACUUM INTO`
+//! - `snapshots/<id>/manifest.json` — SHA-256 and size of every object in the snapshot
+//! - `bodies/<sha256>` — content-addressed session bodies, shared across snapshots
+//! - `latest.json` — id of the newest complete snapshot
+//!
+//! The manifest is written after all data, and `latest.json` last, so an
+//! interrupted backup never becomes the restore default.
+
+mod s3;
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::env_trimmed;
+
+pub use s3::{S3Client, S3Config, sha256_hex};
+
+const DB_FILE: &str = "opensession.db";
+const MANIFEST_VERSION: u32 = 1;
+
+/// Backup target read from `OPENSESSION_BACKUP_*` and `AWS_*` environment variables.
+#[derive(Clone)]
+pub struct BackupConfig {
+    pub s3: S3Config,
+    pub prefix: String,
+}
+
+impl BackupConfig {
+    /// `None` when no bucket is configured; an error when it is but credentials are missing.
+    pub fn from_env() -> Result<Option<Self>> {
+        let Some(bucket) = env_trimmed("OPENSESSION_BACKUP_S3_BUCKET") else {
+            return Ok(None);
+        };
+        let region =
+            env_trimmed("OPENSESSION_BACKUP_S3_REGION").unwrap_or_else(|| "us-east-1".into());
+        let endpoint = env_trimmed("OPENSESSION_BACKUP_S3_ENDPOINT")
+            .unwrap_or_else(|| format!("https://s3.{region}.amazonaws.com"));
+        let access_key_id = env_trimmed("AWS_ACCESS_KEY_ID")
+            .context("AWS_ACCESS_KEY_ID is required for S3 backups")?;
+        let secret_access_key = env_trimmed("AWS_SECRET_ACCESS_KEY")
+            .context("AWS_SECRET_ACCESS_KEY is required for S3 backups")?;
+        let prefix = env_trimmed("OPENSESSION_BACKUP_S3_PREFIX")
+            .unwrap_or_else(|| "opensession".into())
+            .trim_matches('/')
+            .to_string();
+        Ok(Some(Self {
+            s3: S3Config {
+                endpoint,
+                bucket,
+                region,
+                access_key_id,
+                secret_access_key,
+            },
+            prefix,
+        }))
+    }
+
+    pub fn require_from_env() -> Result<Self> {
+        Self::from_env()?
+            .context("OPENSESSION_BACKUP_S3_BUCKET is not set; configure the backup target first")
+    }
+
+    fn key(&self, rest: &str) -> String {
+        if self.prefix.is_empty() {
+            rest.to_string()
+        } else {
+            format!("{}/{rest}", self.prefix)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub sha256: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: u32,
+    pub snapshot: String,
+    pub created_at: String,
+    pub database: ManifestEntry,
+    /// Session bodies by storage key (file name under `bodies/`).
+    pub bodies: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Latest {
+    snapshot: String,
+}
+
+pub struct BackupReport {
+    pub snapshot: String,
+    pub bodies: usize,
+    pub bodies_uploaded: usize,
+}
+
+/// Snapshot `data_dir` and upload it. Bodies already stored by earlier
+/// snapshots are not uploaded again.
+pub async fn run_backup(config: &BackupConfig, data_dir: &Path) -> Result<BackupReport> {
+    let client = S3Client::new(config.s3.clone());
+    let snapshot = chrono::Utc::now().format("%Y%m%dT%H%M%S%3fZ").to_string();
+
+    let staging = tempdir_in(data_dir, "backup")?;
+    let db_copy = staging.path.join(DB_FILE);
+    snapshot_database(&data_dir.join(DB_FILE), &db_copy)?;
+    let db_bytes = std::fs::read(&db_copy).context("read database snapshot")?;
+
+    let known: HashSet<String> = match latest_manifest(config, &client).await? {
+        Some(previous) => previous
+            .bodies
+            .into_values()
+            .map(|entry| entry.sha256)
+            .collect(),
+        None => HashSet::new(),
+    };
+
+    let mut bodies = BTreeMap::new();
+    let mut uploaded = HashSet::new();
+    let bodies_dir = data_dir.join("bodies");
+    if bodies_dir.is_dir() {
+        for entry in std::fs::read_dir(&bodies_dir).context("list session bodies")? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let data = std::fs::read(entry.path())
+                .with_context(|| format!("read body {}", entry.path().display()))?;
+            let sha256 = sha256_hex(&data);
+            if !known.contains(&sha256) && uploaded.insert(sha256.clone()) {
+                client
+                    .put_object(&config.key(&format!("bodies/{sha256}")), data.clone())
+                    .await?;
+            }
+            bodies.insert(
+                name,
+                ManifestEntry {
+                    sha256,
+                    size: data.len() as u64,
+                },
+            );
+        }
+    }
+
+    let manifest = Manifest {
+        version: MANIFEST_VERSION,
+        snapshot: snapshot.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        database: ManifestEntry {
+            sha256: sha256_hex(&db_bytes),
+            size: db_bytes.len() as u64,
+        },
+        bodies,
+    };
+    client
+        .put_object(
+            &config.key(&format!("snapshots/{snapshot}/{DB_FILE}")),
+            db_bytes,
+        )
+        .await?;
+    client
+        .put_object(
+            &config.key(&format!("snapshots/{snapshot}/manifest.json")),
+            serde_json::to_vec_pretty(&manifest)?,
+        )
+        .await?;
+    client
+        .put_object(
+            &config.key("latest.json"),
+            serde_json::to_vec(&Latest {
+                snapshot: snapshot.clone(),
+            })?,
+        )
+        .await?;
+
+    Ok(BackupReport {
+        snapshot,
+        bodies: manifest.bodies.len(),
+        bodies_uploaded: uploaded.len(),
+    })
+}
+
+/// Copy the live database consistently, even while the server is writing to it.
+fn snapshot_database(source: &Path, target: &Path) -> Result<()> {
+    if !source.exists() {
+        bail!("database not found at {}", source.display());
+    }
+    let conn = rusqlite::Connection::open(source).context("open database for backup")?;
+    conn.execute("VACUUM INTO ?1", [target.to_string_lossy().as_ref()])
+        .context("snapshot database with VACUUM INTO")?;
+    Ok(())
+}
+
+async fn latest_manifest(config: &BackupConfig, client: &S3Client) -> Result<Option<Manifest>> {
+    let Some(raw) = client.get_object(&config.key("latest.json")).await? else {
+        return Ok(None);
+    };
+    let latest: Latest = serde_json::from_slice(&raw).context("parse latest.json")?;
+    fetch_manifest(config, client, &latest.snapshot)
+        .await
+        .map(Some)
+}
+
+async fn fetch_manifest(
+    config: &BackupConfig,
+    client: &S3Client,
+    snapshot: &str,
+) -> Result<Manifest> {
+    let raw = client
+        .get_object(&config.key(&format!("snapshots/{snapshot}/manifest.json")))
+        .await?
+        .with_context(|| format!("snapshot `{snapshot}` has no manifest (incomplete backup?)"))?;
+    let manifest: Manifest = serde_json::from_slice(&raw).context("parse backup manifest")?;
+    if manifest.version != MANIFEST_VERSION {
+        bail!("unsupported backup manifest version {}", manifest.version);
+    }
+    Ok(manifest)
+}
+
+async fn fetch_verified(client: &S3Client, key: &str, expected: &ManifestEntry) -> Result<Vec<u8>> {
+    let data = client
+        .get_object(key)
+        .await?
+        .with_context(|| format!("backup object `{key}` is missing"))?;
+    let actual = sha256_hex(&data);
+    if actual != expected.sha256 || data.len() as u64 != expected.size {
+        bail!(
+            "backup object `{key}` failed verification (sha256 {actual}, expected {})",
+            expected.sha256
+        );
+    }
+    Ok(data)
+}
+
+/// Storage keys become file names on restore; refuse anything that could escape `bodies/`.
+fn is_safe_body_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\'])
+        && !name.contains('\0')
+}
+
+/// Download `snapshot` (default: latest) into `target`, checking every object
+/// against the manifest and the database with `PRAGMA integrity_check`.
+async fn download_snapshot(
+    config: &BackupConfig,
+    client: &S3Client,
+    snapshot: Option<&str>,
+    target: &Path,
+) -> Result<Manifest> {
+    let snapshot = match snapshot {
+        Some(snapshot) => snapshot.to_string(),
+        None => {
+            let raw = client
+                .get_object(&config.key("latest.json"))
+                .await?
+                .context("no backups found (latest.json is missing)")?;
+            serde_json::from_slice::<Latest>(&raw)
+                .context("parse latest.json")?
+                .snapshot
+        }
+    };
+    let manifest = fetch_manifest(config, client, &snapshot).await?;
+
+    let db = fetch_verified(
+        client,
+        &config.key(&format!("snapshots/{snapshot}/{DB_FILE}")),
+        &manifest.database,
+    )
+    .await?;
+    std::fs::write(target.join(DB_FILE), db).context("write restored database")?;
+
+    let bodies_dir = target.join("bodies");
+    std::fs::create_dir_all(&bodies_dir)?;
+    for (name, entry) in &manifest.bodies {
+        if !is_safe_body_name(name) {
+            bail!("backup manifest contains an unsafe body name `{name}`");
+        }
+        let data = fetch_verified(
+            client,
+            &config.key(&format!("bodies/{}", entry.sha256)),
+            entry,
+        )
+        .await?;
+        std::fs::write(bodies_dir.join(name), data)
+            .with_context(|| format!("write restored body {name}"))?;
+    }
+
+    let conn = rusqlite::Connection::open(target.join(DB_FILE))?;
+    let integrity: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+    if integrity != "ok" {
+        bail!("restored database failed integrity_check: {integrity}");
+    }
+    Ok(manifest)
+}
+
+/// Download and verify a snapshot without touching `data_dir`.
+pub async fn verify_backup(
+    config: &BackupConfig,
+    data_dir: &Path,
+    snapshot: Option<&str>,
+) -> Result<Manifest> {
+    let client = S3Client::new(config.s3.clone());
+    std::fs::create_dir_all(data_dir)?;
+    let staging = tempdir_in(data_dir, "verify")?;
+    download_snapshot(config, &client, snapshot, &staging.path).await
+}
+
+/// Restore a snapshot into `data_dir`. The server must be stopped. Existing
+/// data is only replaced with `force`; replaced bodies are kept aside.
+pub async fn restore_backup(
+    config: &BackupConfig,
+    data_dir: &Path,
+    snapshot: Option<&str>,
+    force: bool,
+) -> Result<Manifest> {
+    let db_path = data_dir.join(DB_FILE);
+    if db_path.exists() && !force {
+        bail!(
+            "{} already exists; stop the server and pass --force to replace it",
+            db_path.display()
+        );
+    }
+
+    let client = S3Client::new(config.s3.clone());
+    std::fs::create_dir_all(data_dir)?;
+    let staging = tempdir_in(data_dir, "restore")?;
+    let manifest = download_snapshot(config, &client, snapshot, &staging.path).await?;
+
+    for suffix in ["", "-wal", "-shm"] {
+        let path = data_dir.join(format!("{DB_FILE}{suffix}"));
+        if path.exists() {
+            std::fs::remove_file(&path).with_context(|| format!("remove {}", path.display()))?;
+        }
+    }
+    std::fs::rename(staging.path.join(DB_FILE), &db_path).context("move restored database")?;
+
+    let bodies_dir = data_dir.join("bodies");
+    if bodies_dir.exists() {
+        let aside = data_dir.join(format!("bodies.before-restore-{}", manifest.snapshot));
+        std::fs::rename(&bodies_dir, &aside)
+            .with_context(|| format!("move existing bodies to {}", aside.display()))?;
+    }
+    std::fs::rename(staging.path.join("bodies"), &bodies_dir).context("move restored bodies")?;
+    Ok(manifest)
+}
+
+/// Back up every `interval` until the process exits; failures are logged.
+pub async fn run_scheduled(config: BackupConfig, data_dir: PathBuf, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await;
+    loop {
+        ticker.tick().await;
+        match run_backup(&config, &data_dir).await {
+            Ok(report) => tracing::info!(
+                "backup {} complete ({} bodies, {} uploaded)",
+                report.snapshot,
+                report.bodies,
+                report.bodies_uploaded
+            ),
+            Err(error) => tracing::error!("scheduled backup failed: {error:#}"),
+        }
+    }
+}
+
+/// Scratch directory inside `data_dir` (same filesystem, so restores can rename).
+struct ScratchDir {
+    path: PathBuf,
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+fn tempdir_in(data_dir: &Path, purpose: &str) -> Result<ScratchDir> {
+    let path = data_dir.join(format!(".{purpose}-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&path).with_context(|| format!("create {}", path.display()))?;
+    Ok(ScratchDir { path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Router,
+        body::Bytes,
+        extract::{Path as AxumPath, State},
+        http::StatusCode,
+        routing::get,
+    };
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+    /// In-memory stand-in for an S3 bucket (signatures are not checked).
+    async fn fake_s3() -> (String, Objects) {
+        let objects: Objects = Arc::default();
+        let app = Router::new()
+            .route(
+                "/{*key}",
+                get(
+                    |State(objects): State<Objects>, AxumPath(key): AxumPath<String>| async move {
+                        match objects.lock().unwrap().get(&key) {
+                            Some(data) => Ok(data.clone()),
+                            None => Err(StatusCode::NOT_FOUND),
+                        }
+                    },
+                )
+                .put(
+                    |State(objects): State<Objects>,
+                     AxumPath(key): AxumPath<String>,
+                     body: Bytes| async move {
+                        objects.lock().unwrap().insert(key, body.to_vec());
+                        StatusCode::OK
+                    },
+                ),
+            )
+            .with_state(Arc::clone(&objects));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (format!("http://{addr}"), objects)
+    }
+
+    fn test_config(endpoint: String) -> BackupConfig {
+        BackupConfig {
+            s3: S3Config {
+                endpoint,
+                bucket: "bucket".to_string(),
+                region: "us-east-1".to_string(),
+                access_key_id: "AKID".to_string(),
+                secret_access_key: "secret".to_string(),
+            },
+            prefix: "os".to_string(),
+        }
+    }
+
+    fn seed_data_dir(dir: &Path) {
+        let db = crate::storage::init_db(dir).expect("init db");
+        drop(db);
+        let bodies = dir.join("bodies");
+        std::fs::create_dir_all(&bodies).unwrap();
+        std::fs::write(bodies.join("a.hail.jsonl"), b"{\"a\":1}\n").unwrap();
+        std::fs::write(bodies.join("b.hail.jsonl"), b"{\"b\":2}\n").unwrap();
+    }
+
+    #[tokio::test]
+    async fn backup_and_restore_round_trip_with_incremental_bodies() {
+        let (endpoint, objects) = fake_s3().await;
+        let config = test_config(endpoint);
+        let source = tempfile_dir("backup-source");
+        seed_data_dir(&source);
+
+        let first = run_backup(&config, &source).await.expect("first backup");
+        assert_eq!((first.bodies, first.bodies_uploaded), (2, 2));
+        assert!(
+            objects
+                .lock()
+                .unwrap()
+                .contains_key("bucket/os/latest.json")
+        );
+
+        std::fs::write(source.join("bodies/c.hail.jsonl"), b"{\"c\":3}\n").unwrap();
+        let second = run_backup(&config, &source).await.expect("second backup");
+        assert_eq!((second.bodies, second.bodies_uploaded), (3, 1));
+
+        let target = tempfile_dir("backup-target");
+        verify_backup(&config, &target, None)
+            .await
+            .expect("verify latest");
+        let manifest = restore_backup(&config, &target, Some(&first.snapshot), false)
+            .await
+            .expect("restore first snapshot");
+        assert_eq!(manifest.bodies.len(), 2);
+        assert_eq!(
+            std::fs::read(target.join("bodies/b.hail.jsonl")).unwrap(),
+            b"{\"b\":2}\n"
+        );
+        crate::storage::init_db(&target).expect("restored db opens");
+
+        let refused = restore_backup(&config, &target, None, false).await;
+        assert!(refused.is_err(), "existing data must not be replaced");
+
+        std::fs::remove_dir_all(&source).ok();
+        std::fs::remove_dir_all(&target).ok();
+    }
+
+    #[tokio::test]
+    async fn corrupted_objects_fail_verification() {
+        let (endpoint, objects) = fake_s3().await;
+        let config = test_config(endpoint);
+        let source = tempfile_dir("backup-corrupt");
+        seed_data_dir(&source);
+        run_backup(&config, &source).await.expect("backup");
+
+        for (key, data) in objects.lock().unwrap().iter_mut() {
+            if key.starts_with("bucket/os/bodies/") {
+                data.push(b'!');
+            }
+        }
+        let target = tempfile_dir("backup-corrupt-target");
+        let error = verify_backup(&config, &target, None)
+            .await
+            .expect_err("tampered body");
+        assert!(error.to_string().contains("failed verification"), "{error}");
+
+        std::fs::remove_dir_all(&source).ok();
+        std::fs::remove_dir_all(&target).ok();
+    }
+
+    #[test]
+    fn body_names_cannot_escape_the_bodies_dir() {
+        assert!(is_safe_body_name("abc.hail.jsonl"));
+        assert!(!is_safe_body_name("../opensession.db"));
+        assert!(!is_safe_body_name(".."));
+        assert!(!is_safe_body_name("a\\b"));
+    }
+
+    fn tempfile_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("opensession-{name}-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+}
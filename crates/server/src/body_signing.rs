@@ -0,0 +1,96 @@
+//! Server attestation of uploaded session bodies.
+//!
+//! With `OPENSESSION_BODY_SIGNING_KEY` set to a base64 32-byte Ed25519 seed
+//! (`openssl rand -base64 32`), every upload's body hash is signed and the
+//! signature stored next to it, so a reviewer holding the public key can
+//! prove a body is the one accepted at upload even if the database itself
+//! was edited afterwards.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result, bail};
+use base64::{Engine, prelude::BASE64_STANDARD};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+
+#[derive(Clone)]
+pub struct BodySigner {
+    key_pair: Arc<Ed25519KeyPair>,
+}
+
+impl std::fmt::Debug for BodySigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BodySigner")
+            .field("public_key", &self.public_key())
+            .finish()
+    }
+}
+
+impl BodySigner {
+    /// `None` when no key is configured; an error when it is malformed.
+    pub fn from_env() -> Result<Option<Self>> {
+        match std::env::var("OPENSESSION_BODY_SIGNING_KEY") {
+            Ok(raw) if !raw.trim().is_empty() => Self::from_base64_seed(raw.trim()).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn from_base64_seed(raw: &str) -> Result<Self> {
+        let seed = BASE64_STANDARD
+            .decode(raw)
+            .context("OPENSESSION_BODY_SIGNING_KEY is not valid base64")?;
+        if seed.len() != 32 {
+            bail!(
+                "OPENSESSION_BODY_SIGNING_KEY must decode to 32 bytes, got {}",
+                seed.len()
+            );
+        }
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(&seed)
+            .map_err(|err| anyhow::anyhow!("invalid Ed25519 seed: {err}"))?;
+        Ok(Self {
+            key_pair: Arc::new(key_pair),
+        })
+    }
+
+    /// Base64 public key, published through `GET /api/capabilities`.
+    pub fn public_key(&self) -> String {
+        BASE64_STANDARD.encode(self.key_pair.public_key().as_ref())
+    }
+
+    /// Base64 signature over [`opensession_api::service::body_signing_message`].
+    pub fn sign(&self, session_id: &str, body_sha256: &str) -> String {
+        let message = opensession_api::service::body_signing_message(session_id, body_sha256);
+        BASE64_STANDARD.encode(self.key_pair.sign(message.as_bytes()).as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{ED25519, UnparsedPublicKey};
+
+    #[test]
+    fn signatures_verify_against_the_published_key() {
+        let signer =
+            BodySigner::from_base64_seed(&BASE64_STANDARD.encode([7u8; 32])).expect("signer");
+        let signature = BASE64_STANDARD
+            .decode(signer.sign("s1", "abc123"))
+            .expect("signature base64");
+        let public_key = BASE64_STANDARD
+            .decode(signer.public_key())
+            .expect("key base64");
+        let verifier = UnparsedPublicKey::new(&ED25519, public_key);
+
+        assert!(
+            verifier
+                .verify(b"opensession-body-v1\ns1\nabc123", &signature)
+                .is_ok()
+        );
+        assert!(
+            verifier
+                .verify(b"opensession-body-v1\ns2\nabc123", &signature)
+                .is_err()
+        );
+        assert!(BodySigner::from_base64_seed("c2hvcnQ=").is_err());
+        assert!(BodySigner::from_base64_seed("not base64!").is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! Usage-based archival of session bodies to a cold store.
+//!
+//! Configuration:
+//!
+//! - `OPENSESSION_COLD_STORE_DIR` — directory cold store (e.g. a cheaper mounted volume)
+//! - `OPENSESSION_COLD_STORE_S3_BUCKET` — S3-compatible cold store instead, with
+//!   `OPENSESSION_COLD_STORE_S3_REGION`, `_ENDPOINT`, `_PREFIX` and the `AWS_*` credentials
+//! - `OPENSESSION_TIERING_IDLE_DAYS` — archive bodies not downloaded for this many
+//!   days; requires a cold store
+//! - `OPENSESSION_TIERING_INTERVAL_SECS` — how often to look for idle bodies (default 3600)
+//!
+//! Archived bodies keep their storage key. A `/raw` download moves the body
+//! back to hot storage first, so clients never see the difference.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+
+use crate::app_config::env_trimmed;
+use crate::backup::{S3Client, S3Config};
+use crate::storage::Db;
+
+/// Idle bodies archived per database query during a sweep.
+const SWEEP_BATCH: u32 = 200;
+
+pub enum ColdStore {
+    Dir(PathBuf),
+    S3 { client: S3Client, prefix: String },
+}
+
+impl std::fmt::Debug for ColdStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dir(dir) => f.debug_tuple("Dir").field(dir).finish(),
+            Self::S3 { prefix, .. } => f.debug_struct("S3").field("prefix", prefix).finish(),
+        }
+    }
+}
+
+impl ColdStore {
+    /// `None` when no cold store is configured; an error when both backends
+    /// are, or S3 credentials are missing.
+    pub fn from_env() -> Result<Option<Self>> {
+        let dir = env_trimmed("OPENSESSION_COLD_STORE_DIR");
+        let bucket = env_trimmed("OPENSESSION_COLD_STORE_S3_BUCKET");
+        match (dir, bucket) {
+            (Some(_), Some(_)) => bail!(
+                "set either OPENSESSION_COLD_STORE_DIR or OPENSESSION_COLD_STORE_S3_BUCKET, not both"
+            ),
+            (Some(dir), None) => Ok(Some(Self::Dir(PathBuf::from(dir)))),
+            (None, Some(bucket)) => {
+                let region = env_trimmed("OPENSESSION_COLD_STORE_S3_REGION")
+                    .unwrap_or_else(|| "us-east-1".into());
+                let endpoint = env_trimmed("OPENSESSION_COLD_STORE_S3_ENDPOINT")
+                    .unwrap_or_else(|| format!("https://s3.{region}.amazonaws.com"));
+                let access_key_id = env_trimmed("AWS_ACCESS_KEY_ID")
+                    .context("AWS_ACCESS_KEY_ID is required for the S3 cold store")?;
+                let secret_access_key = env_trimmed("AWS_SECRET_ACCESS_KEY")
+                    .context("AWS_SECRET_ACCESS_KEY is required for the S3 cold store")?;
+                let prefix = env_trimmed("OPENSESSION_COLD_STORE_S3_PREFIX")
+                    .unwrap_or_else(|| "opensession-cold".into())
+                    .trim_matches('/')
+                    .to_string();
+                Ok(Some(Self::S3 {
+                    client: S3Client::new(S3Config {
+                        endpoint,
+                        bucket,
+                        region,
+                        access_key_id,
+                        secret_access_key,
+                    }),
+                    prefix,
+                }))
+            }
+            (None, None) => Ok(None),
+        }
+    }
+
+    pub async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        match self {
+            Self::Dir(dir) => {
+                tokio::fs::create_dir_all(dir)
+                    .await
+                    .with_context(|| format!("create cold store {}", dir.display()))?;
+                tokio::fs::write(dir.join(key), body)
+                    .await
+                    .with_context(|| format!("write cold body {key}"))
+            }
+            Self::S3 { client, prefix } => client.put_object(&s3_key(prefix, key), body).await,
+        }
+    }
+
+    /// `Ok(None)` when the key is not in the store.
+    pub async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match self {
+            Self::Dir(dir) => match tokio::fs::read(dir.join(key)).await {
+                Ok(body) => Ok(Some(body)),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err).with_context(|| format!("read cold body {key}")),
+            },
+            Self::S3 { client, prefix } => client.get_object(&s3_key(prefix, key)).await,
+        }
+    }
+
+    /// Remove a key; removing a missing key succeeds.
+    pub async fn delete(&self, key: &str) -> Result<()> {
+        match self {
+            Self::Dir(dir) => match tokio::fs::remove_file(dir.join(key)).await {
+                Ok(()) => Ok(()),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(err) => Err(err).with_context(|| format!("remove cold body {key}")),
+            },
+            Self::S3 { client, prefix } => client.delete_object(&s3_key(prefix, key)).await,
+        }
+    }
+}
+
+fn s3_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{prefix}/{key}")
+    }
+}
+
+/// When and how often idle bodies are archived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TieringSchedule {
+    pub idle_days: u32,
+    pub interval: Duration,
+}
+
+impl TieringSchedule {
+    /// `None` when `OPENSESSION_TIERING_IDLE_DAYS` is unset or zero.
+    pub fn from_env() -> Result<Option<Self>> {
+        let Some(raw) = env_trimmed("OPENSESSION_TIERING_IDLE_DAYS") else {
+            return Ok(None);
+        };
+        let idle_days: u32 = raw
+            .parse()
+            .with_context(|| format!("OPENSESSION_TIERING_IDLE_DAYS `{raw}` is not a number"))?;
+        if idle_days == 0 {
+            return Ok(None);
+        }
+        let interval_secs = match env_trimmed("OPENSESSION_TIERING_INTERVAL_SECS") {
+            Some(raw) => raw
+                .parse::<u64>()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .with_context(|| format!("invalid OPENSESSION_TIERING_INTERVAL_SECS `{raw}`"))?,
+            None => 3600,
+        };
+        Ok(Some(Self {
+            idle_days,
+            interval: Duration::from_secs(interval_secs),
+        }))
+    }
+}
+
+/// Archive every idle body, in batches, and return how many moved.
+pub async fn sweep(db: &Db, idle_days: u32) -> Result<usize> {
+    let mut archived = 0;
+    loop {
+        let moved = db.archive_idle_bodies(idle_days, SWEEP_BATCH).await?;
+        archived += moved;
+        if moved < SWEEP_BATCH as usize {
+            return Ok(archived);
+        }
+    }
+}
+
+pub async fn run_scheduled(db: Db, schedule: TieringSchedule) {
+    let mut ticker = tokio::time::interval(schedule.interval);
+    loop {
+        ticker.tick().await;
+        match sweep(&db, schedule.idle_days).await {
+            Ok(0) => {}
+            Ok(archived) => tracing::info!("archived {archived} idle session bodies"),
+            Err(error) => tracing::error!("body tiering sweep failed: {error:#}"),
+        }
+    }
+}
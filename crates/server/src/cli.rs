@@ -0,0 +1,71 @@
+use clap::{Parser, Subcommand};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "opensession-server",
+    about = "Self-hosted OpenSession API server"
+)]
+pub(crate) struct Cli {
+    #[command(subcommand)]
+    pub(crate) command: Option<ServerCommand>,
+}
+
+#[derive(Debug, Subcommand)]
+pub(crate) enum ServerCommand {
+    /// Serve the API and web UI (default).
+    Serve,
+    /// Upload a snapshot of the database and session bodies to S3.
+    Backup,
+    /// Restore a snapshot into the data directory. Stop the server first.
+    Restore {
+        /// Snapshot id to restore; defaults to the latest complete backup.
+        #[arg(long)]
+        snapshot: Option<String>,
+        /// Replace an existing database (current bodies are kept aside).
+        #[arg(long)]
+        force: bool,
+    },
+    /// Re-derive summary columns (counts, has_errors, file lists) of every
+    /// stored session from its body.
+    Recompute {
+        /// Sessions per batch.
+        #[arg(long, default_value_t = 100)]
+        batch: u32,
+    },
+    /// Download a snapshot and check it against its manifest without restoring.
+    VerifyBackup {
+        /// Snapshot id to verify; defaults to the latest complete backup.
+        #[arg(long)]
+        snapshot: Option<String>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_defaults_to_serve_when_no_subcommand() {
+        let cli = Cli::try_parse_from(["opensession-server"]).expect("parse cli");
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn cli_accepts_restore_options() {
+        let cli = Cli::try_parse_from([
+            "opensession-server",
+            "restore",
+            "--snapshot",
+            "20260101T000000000Z",
+            "--force",
+        ])
+        .expect("parse cli");
+        match cli.command {
+            Some(ServerCommand::Restore { snapshot, force }) => {
+                assert_eq!(snapshot.as_deref(), Some("20260101T000000000Z"));
+                assert!(force);
+            }
+            other => panic!("unexpected command: {other:?}"),
+        }
+    }
+}
@@ -29,6 +29,14 @@ impl ApiErr {
         Self(ServiceError::Conflict(msg.into()))
     }
 
+    pub fn quota_exceeded(msg: impl Into<String>) -> Self {
+        Self(ServiceError::QuotaExceeded(msg.into()))
+    }
+
+    pub fn too_many_requests(msg: impl Into<String>) -> Self {
+        Self(ServiceError::TooManyRequests(msg.into()))
+    }
+
     pub fn internal(msg: impl Into<String>) -> Self {
         Self(ServiceError::Internal(msg.into()))
     }
@@ -42,6 +50,13 @@ impl ApiErr {
     }
 }
 
+impl ApiErr {
+    /// The JSON body this error renders as, for per-item results in batch responses.
+    pub fn to_body(&self) -> opensession_api::ApiError {
+        opensession_api::ApiError::from(&self.0)
+    }
+}
+
 impl From<ServiceError> for ApiErr {
     fn from(e: ServiceError) -> Self {
         Self(e)
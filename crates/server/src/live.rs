@@ -0,0 +1,419 @@
+//! In-memory relay for sessions shared while they run ("watch my session").
+//!
+//! The owner starts a share with `POST /api/sessions/:id/live` and appends
+//! events with `POST /api/sessions/:id/events`; teammates follow along over
+//! `GET /api/sessions/:id/live` (server-sent events). Nothing here touches the
+//! database: a share ends when the owner stops it, uploads the finished
+//! session, goes idle for [`IDLE_TIMEOUT`], or the server restarts.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use opensession_api::{
+    Agent, Event, LiveSessionInfo, LiveUpdate, LiveViewer, MAX_LIVE_BUFFERED_EVENTS, SessionContext,
+};
+use tokio::sync::broadcast;
+
+/// Shares with no appended events for this long are dropped.
+pub const IDLE_TIMEOUT: chrono::Duration = chrono::Duration::minutes(30);
+
+/// Updates a slow viewer may fall behind before it is sent a fresh snapshot.
+const UPDATE_CAPACITY: usize = 256;
+
+/// Shares one user may run at once; each buffers up to
+/// [`MAX_LIVE_BUFFERED_EVENTS`] events in memory.
+pub const MAX_SHARES_PER_USER: usize = 5;
+
+/// Why [`LiveHub::start`] refused a share.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StartError {
+    /// Another user is sharing that session id.
+    Taken,
+    /// The owner already runs [`MAX_SHARES_PER_USER`] other shares.
+    TooManyShares,
+}
+
+#[derive(Clone, Default)]
+pub struct LiveHub {
+    shares: Arc<Mutex<HashMap<String, LiveShare>>>,
+    next_connection: Arc<AtomicU64>,
+}
+
+struct LiveShare {
+    owner_id: String,
+    owner_nickname: String,
+    agent: Option<Agent>,
+    context: Option<SessionContext>,
+    events: VecDeque<Event>,
+    event_count: u64,
+    started_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    /// Open viewer connections, keyed by connection id.
+    viewers: HashMap<u64, LiveViewer>,
+    updates: broadcast::Sender<LiveUpdate>,
+}
+
+impl LiveShare {
+    fn info(&self, session_id: &str) -> LiveSessionInfo {
+        let mut viewers: Vec<LiveViewer> = Vec::new();
+        let mut connections: Vec<_> = self.viewers.values().collect();
+        connections.sort_by(|a, b| (&a.joined_at, &a.nickname).cmp(&(&b.joined_at, &b.nickname)));
+        for viewer in connections {
+            if !viewers.iter().any(|seen| seen.nickname == viewer.nickname) {
+                viewers.push(viewer.clone());
+            }
+        }
+        LiveSessionInfo {
+            session_id: session_id.to_string(),
+            owner_nickname: self.owner_nickname.clone(),
+            tool: self.agent.as_ref().map(|agent| agent.tool.clone()),
+            title: self
+                .context
+                .as_ref()
+                .and_then(|context| context.title.clone()),
+            started_at: self.started_at.to_rfc3339(),
+            updated_at: self.updated_at.to_rfc3339(),
+            event_count: self.event_count,
+            viewers,
+        }
+    }
+
+    fn snapshot(&self, session_id: &str) -> LiveUpdate {
+        LiveUpdate::Snapshot {
+            info: Box::new(self.info(session_id)),
+            agent: self.agent.clone(),
+            context: self.context.clone().map(Box::new),
+            events: self.events.iter().cloned().collect(),
+        }
+    }
+
+    fn push_events(&mut self, events: Vec<Event>, now: DateTime<Utc>) {
+        self.updated_at = now;
+        self.event_count += events.len() as u64;
+        self.events.extend(events);
+        let overflow = self.events.len().saturating_sub(MAX_LIVE_BUFFERED_EVENTS);
+        self.events.drain(..overflow);
+    }
+
+    fn publish_presence(&self, session_id: &str) {
+        let _ = self.updates.send(LiveUpdate::Presence {
+            viewers: self.info(session_id).viewers,
+        });
+    }
+}
+
+/// What a new viewer receives: the current state, then every update.
+pub struct LiveSubscription {
+    pub snapshot: LiveUpdate,
+    pub updates: broadcast::Receiver<LiveUpdate>,
+    /// Listed as a viewer until this is dropped.
+    pub presence: Option<PresenceGuard>,
+}
+
+pub struct PresenceGuard {
+    hub: LiveHub,
+    session_id: String,
+    connection: u64,
+}
+
+impl Drop for PresenceGuard {
+    fn drop(&mut self) {
+        let mut shares = self.hub.lock();
+        if let Some(share) = shares.get_mut(&self.session_id)
+            && share.viewers.remove(&self.connection).is_some()
+        {
+            share.publish_presence(&self.session_id);
+        }
+    }
+}
+
+impl LiveHub {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, LiveShare>> {
+        self.shares
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Start sharing `session_id` with `events` written so far; the same owner
+    /// starting again replaces the buffered events.
+    pub fn start(
+        &self,
+        session_id: &str,
+        owner_id: &str,
+        owner_nickname: &str,
+        agent: Option<Agent>,
+        context: Option<SessionContext>,
+        events: Vec<Event>,
+    ) -> Result<LiveSessionInfo, StartError> {
+        let now = Utc::now();
+        let mut shares = self.lock();
+        prune_idle(&mut shares, now);
+        match shares.get(session_id) {
+            Some(share) if share.owner_id != owner_id => return Err(StartError::Taken),
+            Some(_) => {}
+            None => {
+                let owned = shares
+                    .values()
+                    .filter(|share| share.owner_id == owner_id)
+                    .count();
+                if owned >= MAX_SHARES_PER_USER {
+                    return Err(StartError::TooManyShares);
+                }
+            }
+        }
+        let share = shares
+            .entry(session_id.to_string())
+            .or_insert_with(|| LiveShare {
+                owner_id: owner_id.to_string(),
+                owner_nickname: owner_nickname.to_string(),
+                agent: None,
+                context: None,
+                events: VecDeque::new(),
+                event_count: 0,
+                started_at: now,
+                updated_at: now,
+                viewers: HashMap::new(),
+                updates: broadcast::channel(UPDATE_CAPACITY).0,
+            });
+        share.events.clear();
+        share.event_count = 0;
+        if agent.is_some() {
+            share.agent = agent;
+        }
+        if context.is_some() {
+            share.context = context;
+        }
+        share.push_events(events, now);
+        let snapshot = share.snapshot(session_id);
+        let _ = share.updates.send(snapshot);
+        Ok(share.info(session_id))
+    }
+
+    /// Relay events the owner appended. Returns the share's event count, or
+    /// `None` when `owner_id` is not sharing `session_id`.
+    pub fn append(&self, session_id: &str, owner_id: &str, events: Vec<Event>) -> Option<u64> {
+        let now = Utc::now();
+        let mut shares = self.lock();
+        prune_idle(&mut shares, now);
+        let share = shares
+            .get_mut(session_id)
+            .filter(|share| share.owner_id == owner_id)?;
+        if !events.is_empty() {
+            share.push_events(events.clone(), now);
+            let _ = share.updates.send(LiveUpdate::Events {
+                events,
+                event_count: share.event_count,
+            });
+        }
+        Some(share.event_count)
+    }
+
+    /// Stop sharing and tell viewers; with `owner_id`, only that owner's share
+    /// is ended. Returns whether a share was removed.
+    pub fn end(&self, session_id: &str, owner_id: Option<&str>, uploaded: bool) -> bool {
+        let mut shares = self.lock();
+        let owned = shares
+            .get(session_id)
+            .is_some_and(|share| owner_id.is_none_or(|owner_id| share.owner_id == owner_id));
+        if !owned {
+            return false;
+        }
+        if let Some(share) = shares.remove(session_id) {
+            let _ = share.updates.send(LiveUpdate::Ended { uploaded });
+        }
+        true
+    }
+
+    /// The user sharing `session_id`, if it is live.
+    pub fn owner_of(&self, session_id: &str) -> Option<String> {
+        self.lock()
+            .get(session_id)
+            .map(|share| share.owner_id.clone())
+    }
+
+    pub fn snapshot(&self, session_id: &str) -> Option<LiveUpdate> {
+        self.lock()
+            .get(session_id)
+            .map(|share| share.snapshot(session_id))
+    }
+
+    /// Every live share with its owner's user id, newest first.
+    pub fn list(&self) -> Vec<(String, LiveSessionInfo)> {
+        let mut shares = self.lock();
+        prune_idle(&mut shares, Utc::now());
+        let mut list: Vec<_> = shares
+            .iter()
+            .map(|(id, share)| (share.owner_id.clone(), share.info(id)))
+            .collect();
+        list.sort_by(|a, b| b.1.started_at.cmp(&a.1.started_at));
+        list
+    }
+
+    /// Follow `session_id`. Viewers other than the owner are listed in
+    /// presence updates while the subscription lives.
+    pub fn subscribe(
+        &self,
+        session_id: &str,
+        user_id: &str,
+        nickname: &str,
+    ) -> Option<LiveSubscription> {
+        let mut shares = self.lock();
+        let share = shares.get_mut(session_id)?;
+        let updates = share.updates.subscribe();
+        let presence = (share.owner_id != user_id).then(|| {
+            let connection = self.next_connection.fetch_add(1, Ordering::Relaxed);
+            share.viewers.insert(
+                connection,
+                LiveViewer {
+                    nickname: nickname.to_string(),
+                    joined_at: Utc::now().to_rfc3339(),
+                },
+            );
+            share.publish_presence(session_id);
+            PresenceGuard {
+                hub: self.clone(),
+                session_id: session_id.to_string(),
+                connection,
+            }
+        });
+        Some(LiveSubscription {
+            snapshot: share.snapshot(session_id),
+            updates,
+            presence,
+        })
+    }
+}
+
+fn prune_idle(shares: &mut HashMap<String, LiveShare>, now: DateTime<Utc>) {
+    shares.retain(|_, share| {
+        let live = now - share.updated_at < IDLE_TIMEOUT;
+        if !live {
+            let _ = share.updates.send(LiveUpdate::Ended { uploaded: false });
+        }
+        live
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opensession_api::{Content, EventType};
+
+    fn event(text: &str) -> Event {
+        Event {
+            event_id: text.to_string(),
+            timestamp: Utc::now(),
+            event_type: EventType::AgentMessage,
+            task_id: None,
+            content: Content::text(text),
+            duration_ms: None,
+            attributes: HashMap::new(),
+        }
+    }
+
+    fn viewer_nicknames(update: &LiveUpdate) -> Vec<String> {
+        match update {
+            LiveUpdate::Presence { viewers } => viewers
+                .iter()
+                .map(|viewer| viewer.nickname.clone())
+                .collect(),
+            other => panic!("expected presence, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn viewers_follow_appends_presence_and_the_end_of_a_share() {
+        let hub = LiveHub::default();
+        let info = hub
+            .start("s1", "u-ada", "ada", None, None, vec![event("hello")])
+            .expect("new share");
+        assert_eq!(info.event_count, 1);
+        assert_eq!(
+            hub.start("s1", "u-bob", "bob", None, None, Vec::new())
+                .err(),
+            Some(StartError::Taken)
+        );
+        assert_eq!(hub.append("s1", "u-bob", vec![event("nope")]), None);
+
+        let mut bob = hub.subscribe("s1", "u-bob", "bob").expect("live");
+        let LiveUpdate::Snapshot { events, .. } = &bob.snapshot else {
+            panic!("expected snapshot");
+        };
+        assert_eq!(events.len(), 1);
+        let owner = hub.subscribe("s1", "u-ada", "ada").expect("live");
+        assert!(owner.presence.is_none());
+        let eve = hub.subscribe("s1", "u-eve", "eve").expect("live");
+        assert_eq!(viewer_nicknames(&bob.updates.try_recv().unwrap()), ["bob"]);
+        assert_eq!(
+            viewer_nicknames(&bob.updates.try_recv().unwrap()),
+            ["bob", "eve"]
+        );
+
+        assert_eq!(hub.append("s1", "u-ada", vec![event("working")]), Some(2));
+        match bob.updates.try_recv().unwrap() {
+            LiveUpdate::Events {
+                events,
+                event_count,
+            } => {
+                assert_eq!(events[0].event_id, "working");
+                assert_eq!(event_count, 2);
+            }
+            other => panic!("expected events, got {other:?}"),
+        }
+
+        drop(eve);
+        assert_eq!(viewer_nicknames(&bob.updates.try_recv().unwrap()), ["bob"]);
+        assert_eq!(hub.list()[0].1.viewers.len(), 1);
+
+        assert!(!hub.end("s1", Some("u-bob"), false));
+        assert!(hub.end("s1", Some("u-ada"), true));
+        assert!(matches!(
+            bob.updates.try_recv().unwrap(),
+            LiveUpdate::Ended { uploaded: true }
+        ));
+        assert!(hub.subscribe("s1", "u-bob", "bob").is_none());
+        drop(bob);
+        assert!(hub.list().is_empty());
+    }
+
+    #[test]
+    fn buffer_keeps_only_the_latest_events() {
+        let hub = LiveHub::default();
+        let events = (0..MAX_LIVE_BUFFERED_EVENTS + 3)
+            .map(|i| event(&i.to_string()))
+            .collect();
+        let info = hub.start("s1", "u", "ada", None, None, events).unwrap();
+        assert_eq!(info.event_count, MAX_LIVE_BUFFERED_EVENTS as u64 + 3);
+        let Some(LiveUpdate::Snapshot { events, .. }) = hub.snapshot("s1") else {
+            panic!("expected snapshot");
+        };
+        assert_eq!(events.len(), MAX_LIVE_BUFFERED_EVENTS);
+        assert_eq!(events[0].event_id, "3");
+    }
+
+    #[test]
+    fn each_user_runs_a_bounded_number_of_shares() {
+        let hub = LiveHub::default();
+        for i in 0..MAX_SHARES_PER_USER {
+            hub.start(&format!("s{i}"), "u-ada", "ada", None, None, Vec::new())
+                .expect("within the cap");
+        }
+        assert_eq!(
+            hub.start("one-more", "u-ada", "ada", None, None, Vec::new())
+                .err(),
+            Some(StartError::TooManyShares)
+        );
+        hub.start("s0", "u-ada", "ada", None, None, vec![event("again")])
+            .expect("restarting an existing share is not a new one");
+        hub.start("one-more", "u-bob", "bob", None, None, Vec::new())
+            .expect("other users are unaffected");
+
+        hub.lock().get_mut("s1").expect("share").updated_at = Utc::now() - IDLE_TIMEOUT;
+        assert_eq!(hub.append("s0", "u-ada", vec![event("tick")]), Some(2));
+        assert!(hub.owner_of("s1").is_none(), "appends prune idle shares");
+        hub.start("another", "u-ada", "ada", None, None, Vec::new())
+            .expect("pruned share frees a slot");
+    }
+}
@@ -1,13 +1,23 @@
 mod app_config;
+mod backup;
+mod body_signing;
+mod body_tiering;
+mod cli;
 mod error;
+mod github_app;
+mod live;
+mod proxy_auth;
+mod retention;
 mod routes;
 mod startup;
 mod storage;
+mod team_quota;
+mod upload_policy;
 
 pub use app_config::AppConfig;
 pub use startup::AppState;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    startup::run().await
+    startup::run_process().await
 }
@@ -0,0 +1,230 @@
+//! Trusted reverse-proxy authentication: an SSO proxy in front of the server
+//! (oauth2-proxy, Cloudflare Access, ...) signs users in and forwards their
+//! identity in a request header.
+//!
+//! Configuration:
+//!
+//! - `OPENSESSION_PROXY_AUTH_EMAIL_HEADER` — header carrying the user's email
+//!   (e.g. `X-Forwarded-Email`, `Cf-Access-Authenticated-User-Email`); enables the mode
+//! - `OPENSESSION_PROXY_AUTH_NAME_HEADER` — optional header with a preferred nickname
+//! - `OPENSESSION_PROXY_AUTH_TRUSTED_IPS` — comma-separated IPs or CIDR ranges of
+//!   the proxy; identity headers from any other peer are dropped
+//!
+//! Unknown emails are provisioned as new accounts on first request, and
+//! password registration and login are disabled while the mode is on.
+
+use std::net::{IpAddr, SocketAddr};
+
+use anyhow::{Context, Result, bail};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, HeaderName},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::AppConfig;
+use crate::app_config::env_trimmed;
+
+#[derive(Debug, Clone)]
+pub struct ProxyAuthConfig {
+    email_header: HeaderName,
+    name_header: Option<HeaderName>,
+    trusted: Vec<IpRange>,
+}
+
+/// Identity asserted by the proxy for one request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyIdentity {
+    pub email: String,
+    pub name: Option<String>,
+}
+
+impl ProxyAuthConfig {
+    /// `None` when no email header is configured; an error when it is but the
+    /// other settings are missing or malformed.
+    pub fn from_env() -> Result<Option<Self>> {
+        let Some(email_header) = env_trimmed("OPENSESSION_PROXY_AUTH_EMAIL_HEADER") else {
+            return Ok(None);
+        };
+        let trusted_ips = env_trimmed("OPENSESSION_PROXY_AUTH_TRUSTED_IPS")
+            .context("OPENSESSION_PROXY_AUTH_TRUSTED_IPS is required")?;
+        let name_header = env_trimmed("OPENSESSION_PROXY_AUTH_NAME_HEADER");
+        Self::new(&email_header, name_header.as_deref(), &trusted_ips).map(Some)
+    }
+
+    pub fn new(email_header: &str, name_header: Option<&str>, trusted_ips: &str) -> Result<Self> {
+        let header = |raw: &str| {
+            HeaderName::try_from(raw.trim()).with_context(|| format!("invalid header name `{raw}`"))
+        };
+        let trusted = trusted_ips
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(IpRange::parse)
+            .collect::<Result<Vec<_>>>()?;
+        if trusted.is_empty() {
+            bail!("OPENSESSION_PROXY_AUTH_TRUSTED_IPS lists no addresses");
+        }
+        Ok(Self {
+            email_header: header(email_header)?,
+            name_header: name_header.map(header).transpose()?,
+            trusted,
+        })
+    }
+
+    /// Whether `peer` is one of the configured proxies.
+    pub fn trusts(&self, peer: IpAddr) -> bool {
+        let peer = match peer {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(peer, IpAddr::V4),
+            v4 => v4,
+        };
+        self.trusted.iter().any(|range| range.contains(peer))
+    }
+
+    /// The proxy-asserted identity, if the request carries one.
+    pub fn identity(&self, headers: &HeaderMap) -> Option<ProxyIdentity> {
+        let text = |name: &HeaderName| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToOwned::to_owned)
+        };
+        Some(ProxyIdentity {
+            email: text(&self.email_header)?,
+            name: self.name_header.as_ref().and_then(text),
+        })
+    }
+
+    fn strip_identity(&self, headers: &mut HeaderMap) {
+        headers.remove(&self.email_header);
+        if let Some(name) = &self.name_header {
+            headers.remove(name);
+        }
+    }
+}
+
+/// Drop identity headers unless the request came straight from a trusted
+/// proxy, so handlers can take them at face value.
+pub async fn strip_untrusted_identity(
+    State(config): State<AppConfig>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if let Some(proxy) = &config.proxy_auth {
+        let peer = request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        if !peer.is_some_and(|ip| proxy.trusts(ip)) {
+            proxy.strip_identity(request.headers_mut());
+        }
+    }
+    next.run(request).await
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IpRange {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpRange {
+    fn parse(raw: &str) -> Result<Self> {
+        let (addr, prefix) = match raw.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (raw, None),
+        };
+        let addr: IpAddr = addr
+            .parse()
+            .with_context(|| format!("invalid trusted proxy address `{raw}`"))?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max)
+                .with_context(|| format!("invalid prefix length in `{raw}`"))?,
+            None => max,
+        };
+        Ok(Self { addr, prefix })
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                prefix_eq(u32::from(net).into(), u32::from(ip).into(), 32, self.prefix)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                prefix_eq(u128::from(net), u128::from(ip), 128, self.prefix)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_eq(net: u128, ip: u128, bits: u8, prefix: u8) -> bool {
+    if prefix == 0 {
+        return true;
+    }
+    let shift = u32::from(bits - prefix);
+    net >> shift == ip >> shift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn trusted_ranges_match_addresses_and_cidrs() {
+        let config = ProxyAuthConfig::new(
+            "X-Forwarded-Email",
+            None,
+            "10.0.0.0/8, 127.0.0.1, fd00::/16",
+        )
+        .expect("config");
+        assert!(config.trusts("10.20.30.40".parse().unwrap()));
+        assert!(config.trusts("127.0.0.1".parse().unwrap()));
+        assert!(config.trusts("::ffff:10.1.2.3".parse().unwrap()));
+        assert!(config.trusts("fd00:1::5".parse().unwrap()));
+        assert!(!config.trusts("127.0.0.2".parse().unwrap()));
+        assert!(!config.trusts("192.168.1.1".parse().unwrap()));
+        assert!(!config.trusts("fe80::1".parse().unwrap()));
+
+        assert!(ProxyAuthConfig::new("X-Forwarded-Email", None, "10.0.0.0/33").is_err());
+        assert!(ProxyAuthConfig::new("X-Forwarded-Email", None, "proxy.internal").is_err());
+        assert!(ProxyAuthConfig::new("X-Forwarded-Email", None, " , ").is_err());
+        assert!(ProxyAuthConfig::new("bad header", None, "10.0.0.1").is_err());
+    }
+
+    #[test]
+    fn identity_reads_configured_headers() {
+        let config = ProxyAuthConfig::new(
+            "Cf-Access-Authenticated-User-Email",
+            Some("X-Forwarded-User"),
+            "0.0.0.0/0",
+        )
+        .expect("config");
+        let mut headers = HeaderMap::new();
+        assert_eq!(config.identity(&headers), None);
+
+        headers.insert(
+            "cf-access-authenticated-user-email",
+            HeaderValue::from_static(" ada@example.com "),
+        );
+        headers.insert("x-forwarded-user", HeaderValue::from_static("ada"));
+        assert_eq!(
+            config.identity(&headers),
+            Some(ProxyIdentity {
+                email: "ada@example.com".to_string(),
+                name: Some("ada".to_string()),
+            })
+        );
+
+        config.strip_identity(&mut headers);
+        assert!(headers.is_empty());
+    }
+}
@@ -0,0 +1,348 @@
+//! Per-team retention of uploaded sessions.
+//!
+//! Teams set a policy through `PUT /api/teams/:team_id/retention`; teams
+//! without one, and uploaders in no team, follow the server default:
+//!
+//! - `OPENSESSION_RETENTION_DAYS` — default retention (180; `0` keeps forever)
+//! - `OPENSESSION_RETENTION_ACTION` — `delete` (default) or `anonymize`
+//! - `OPENSESSION_RETENTION_INTERVAL_SECS` — run the retention job on `serve`
+//!   this often. Unset, nothing is removed; preview with
+//!   `GET /api/teams/:team_id/retention/preview` before turning it on.
+//!
+//! An uploader in several teams keeps sessions for the longest of their
+//! teams' periods. Members may loosen their team's policy; a change that
+//! would remove more sessions also needs the admin key.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use opensession_api::{RetentionAction, RetentionPolicy};
+
+use crate::app_config::env_trimmed;
+use crate::storage::Db;
+
+/// Default retention when `OPENSESSION_RETENTION_DAYS` is unset.
+const DEFAULT_RETAIN_DAYS: u32 = 180;
+
+/// `None` when the default keeps sessions forever.
+pub fn default_policy_from_env() -> Result<Option<RetentionPolicy>> {
+    let retain_days = match env_trimmed("OPENSESSION_RETENTION_DAYS") {
+        Some(raw) => raw
+            .parse()
+            .with_context(|| format!("OPENSESSION_RETENTION_DAYS `{raw}` is not a number"))?,
+        None => DEFAULT_RETAIN_DAYS,
+    };
+    let action = match env_trimmed("OPENSESSION_RETENTION_ACTION") {
+        Some(raw) => RetentionAction::parse(&raw).with_context(|| {
+            format!("OPENSESSION_RETENTION_ACTION `{raw}` is not `delete` or `anonymize`")
+        })?,
+        None => RetentionAction::Delete,
+    };
+    let policy = RetentionPolicy {
+        retain_days,
+        action,
+        exempt_bookmarked: true,
+        exempt_linked: true,
+    };
+    Ok((!policy.keeps_forever()).then_some(policy))
+}
+
+/// How often the retention job runs; `None` when it is off.
+pub fn interval_from_env() -> Result<Option<Duration>> {
+    let Some(raw) = env_trimmed("OPENSESSION_RETENTION_INTERVAL_SECS") else {
+        return Ok(None);
+    };
+    match raw.parse::<u64>() {
+        Ok(0) => Ok(None),
+        Ok(secs) => Ok(Some(Duration::from_secs(secs))),
+        Err(_) => bail!("invalid OPENSESSION_RETENTION_INTERVAL_SECS `{raw}`"),
+    }
+}
+
+/// A session uploaded before the earliest retention cutoff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpiryCandidate {
+    pub session_id: String,
+    pub user_id: Option<String>,
+    pub title: Option<String>,
+    /// `YYYY-MM-DD HH:MM:SS` in UTC, as stored.
+    pub uploaded_at: String,
+    pub bookmarked: bool,
+    pub linked: bool,
+    pub anonymized: bool,
+}
+
+/// What one retention run would do.
+#[derive(Debug, Default)]
+pub struct RetentionPlan {
+    pub actions: Vec<(ExpiryCandidate, RetentionAction)>,
+    /// Expired sessions kept because they are bookmarked or linked.
+    pub exempt: u32,
+}
+
+/// Every team policy plus team memberships, enough to decide any session.
+#[derive(Debug, Default)]
+pub struct RetentionRules {
+    pub default: Option<RetentionPolicy>,
+    pub teams: HashMap<String, RetentionPolicy>,
+    /// `user_id` to the teams it belongs to.
+    pub memberships: HashMap<String, Vec<String>>,
+}
+
+impl RetentionRules {
+    pub async fn load(db: &Db, default: Option<RetentionPolicy>) -> Result<Self> {
+        let teams = db
+            .list_retention_policies()
+            .await
+            .context("load retention policies")?;
+        let mut memberships: HashMap<String, Vec<String>> = HashMap::new();
+        for (user_id, team_id) in db
+            .list_team_memberships()
+            .await
+            .context("load team memberships")?
+        {
+            memberships.entry(user_id).or_default().push(team_id);
+        }
+        Ok(Self {
+            default,
+            teams: teams.into_iter().collect(),
+            memberships,
+        })
+    }
+
+    /// A team's own policy, or the default. `None` keeps sessions forever.
+    pub fn team_policy(&self, team_id: &str) -> Option<RetentionPolicy> {
+        self.teams
+            .get(team_id)
+            .copied()
+            .or(self.default)
+            .filter(|policy| !policy.keeps_forever())
+    }
+
+    /// The policy governing sessions `user_id` uploaded: the longest among
+    /// their teams, since deleting is the step that cannot be undone.
+    pub fn effective_policy(&self, user_id: Option<&str>) -> Option<RetentionPolicy> {
+        let teams = user_id
+            .and_then(|user_id| self.memberships.get(user_id))
+            .filter(|teams| !teams.is_empty());
+        let Some(teams) = teams else {
+            return self.default.filter(|policy| !policy.keeps_forever());
+        };
+        let mut longest: Option<RetentionPolicy> = None;
+        for team_id in teams {
+            let policy = self.team_policy(team_id)?;
+            let rank = |policy: &RetentionPolicy| {
+                (
+                    policy.retain_days,
+                    policy.action == RetentionAction::Anonymize,
+                )
+            };
+            if longest.is_none_or(|current| rank(&policy) > rank(&current)) {
+                longest = Some(policy);
+            }
+        }
+        longest
+    }
+
+    /// The shortest retention period in effect; sessions uploaded since then
+    /// can never expire.
+    pub fn shortest_retain_days(&self) -> Option<u32> {
+        self.default
+            .iter()
+            .chain(self.teams.values())
+            .filter(|policy| !policy.keeps_forever())
+            .map(|policy| policy.retain_days)
+            .min()
+    }
+
+    pub fn plan(
+        &self,
+        candidates: Vec<ExpiryCandidate>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> RetentionPlan {
+        let mut plan = RetentionPlan::default();
+        for candidate in candidates {
+            let Some(policy) = self.effective_policy(candidate.user_id.as_deref()) else {
+                continue;
+            };
+            if candidate.uploaded_at >= cutoff(now, policy.retain_days) {
+                continue;
+            }
+            if (policy.exempt_bookmarked && candidate.bookmarked)
+                || (policy.exempt_linked && candidate.linked)
+            {
+                plan.exempt += 1;
+                continue;
+            }
+            if policy.action == RetentionAction::Anonymize && candidate.anonymized {
+                continue;
+            }
+            plan.actions.push((candidate, policy.action));
+        }
+        plan
+    }
+}
+
+/// Upload time before which a session has outlived `retain_days`, in the
+/// stored `uploaded_at` format.
+pub fn cutoff(now: chrono::DateTime<chrono::Utc>, retain_days: u32) -> String {
+    (now - chrono::Duration::days(i64::from(retain_days)))
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+/// Sessions deleted and anonymized by one run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SweepOutcome {
+    pub deleted: usize,
+    pub anonymized: usize,
+}
+
+/// Apply every team's policy once. A session that fails is logged and
+/// retried on the next run.
+pub async fn sweep(db: &Db, default: Option<RetentionPolicy>) -> Result<SweepOutcome> {
+    let rules = RetentionRules::load(db, default).await?;
+    let Some(days) = rules.shortest_retain_days() else {
+        return Ok(SweepOutcome::default());
+    };
+    let now = chrono::Utc::now();
+    let candidates = db
+        .list_expiry_candidates(&cutoff(now, days), None)
+        .await
+        .context("list expired sessions")?;
+
+    let mut outcome = SweepOutcome::default();
+    for (candidate, action) in rules.plan(candidates, now).actions {
+        let id = candidate.session_id;
+        let result = match action {
+            RetentionAction::Delete => db
+                .purge_session(&id)
+                .await
+                .map(|_| outcome.deleted += 1)
+                .map_err(anyhow::Error::from),
+            RetentionAction::Anonymize => db
+                .anonymize_stored_session(&id)
+                .await
+                .map(|()| outcome.anonymized += 1),
+        };
+        if let Err(err) = result {
+            tracing::error!("retention {} of {id} failed: {err:#}", action.as_str());
+        }
+    }
+    Ok(outcome)
+}
+
+pub async fn run_scheduled(db: Db, default: Option<RetentionPolicy>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match sweep(&db, default).await {
+            Ok(SweepOutcome {
+                deleted: 0,
+                anonymized: 0,
+            }) => {}
+            Ok(outcome) => tracing::info!(
+                "retention deleted {} and anonymized {} session(s)",
+                outcome.deleted,
+                outcome.anonymized
+            ),
+            Err(error) => tracing::error!("retention run failed: {error:#}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(retain_days: u32, action: RetentionAction) -> RetentionPolicy {
+        RetentionPolicy {
+            retain_days,
+            action,
+            exempt_bookmarked: true,
+            exempt_linked: true,
+        }
+    }
+
+    fn candidate(id: &str, user_id: &str, uploaded_at: &str) -> ExpiryCandidate {
+        ExpiryCandidate {
+            session_id: id.to_string(),
+            user_id: Some(user_id.to_string()),
+            title: None,
+            uploaded_at: uploaded_at.to_string(),
+            bookmarked: false,
+            linked: false,
+            anonymized: false,
+        }
+    }
+
+    #[test]
+    fn longest_team_policy_wins_and_exemptions_are_counted() {
+        let mut rules = RetentionRules {
+            default: Some(policy(180, RetentionAction::Delete)),
+            ..Default::default()
+        };
+        rules
+            .teams
+            .insert("legal".into(), policy(30, RetentionAction::Delete));
+        rules
+            .teams
+            .insert("research".into(), policy(0, RetentionAction::Delete));
+        rules
+            .memberships
+            .insert("ada".into(), vec!["legal".into(), "platform".into()]);
+        rules.memberships.insert("bob".into(), vec!["legal".into()]);
+        rules
+            .memberships
+            .insert("eve".into(), vec!["legal".into(), "research".into()]);
+
+        assert_eq!(
+            rules.effective_policy(Some("ada")).unwrap().retain_days,
+            180
+        );
+        assert_eq!(rules.effective_policy(Some("bob")).unwrap().retain_days, 30);
+        assert_eq!(rules.effective_policy(Some("eve")), None);
+        assert_eq!(rules.effective_policy(None).unwrap().retain_days, 180);
+        assert_eq!(rules.shortest_retain_days(), Some(30));
+
+        let now = chrono::DateTime::parse_from_rfc3339("2026-10-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let mut bookmarked = candidate("s3", "bob", "2026-01-01 00:00:00");
+        bookmarked.bookmarked = true;
+        let plan = rules.plan(
+            vec![
+                candidate("s1", "ada", "2026-08-01 00:00:00"),
+                candidate("s2", "bob", "2026-08-01 00:00:00"),
+                bookmarked,
+                candidate("s4", "eve", "2020-01-01 00:00:00"),
+                candidate("s5", "ada", "2026-01-01 00:00:00"),
+            ],
+            now,
+        );
+        let ids: Vec<_> = plan
+            .actions
+            .iter()
+            .map(|(candidate, _)| candidate.session_id.as_str())
+            .collect();
+        assert_eq!(ids, ["s2", "s5"]);
+        assert_eq!(plan.exempt, 1);
+    }
+
+    #[test]
+    fn anonymized_sessions_are_not_anonymized_again() {
+        let rules = RetentionRules {
+            default: Some(policy(10, RetentionAction::Anonymize)),
+            ..Default::default()
+        };
+        let mut done = candidate("s1", "ada", "2020-01-01 00:00:00");
+        done.anonymized = true;
+        let plan = rules.plan(
+            vec![done, candidate("s2", "ada", "2020-01-01 00:00:00")],
+            chrono::Utc::now(),
+        );
+        assert_eq!(plan.actions.len(), 1);
+        assert_eq!(plan.actions[0].1, RetentionAction::Anonymize);
+    }
+}
@@ -3,7 +3,7 @@ use axum::{
     extract::{Path, State},
     http::HeaderMap,
 };
-use opensession_api::OkResponse;
+use opensession_api::{OkResponse, RecomputeSessionsRequest, RecomputeSessionsResponse};
 
 use crate::AppConfig;
 use crate::error::ApiErr;
@@ -16,15 +16,7 @@ pub async fn delete_session(
     Path(id): Path<String>,
     headers: HeaderMap,
 ) -> Result<Json<OkResponse>, ApiErr> {
-    let provided = headers
-        .get("X-OpenSession-Admin-Key")
-        .and_then(|value| value.to_str().ok())
-        .map(str::trim)
-        .unwrap_or("");
-
-    if config.admin_key.trim().is_empty() || provided != config.admin_key.trim() {
-        return Err(ApiErr::unauthorized("invalid admin key"));
-    }
+    require_admin_key(&headers, &config)?;
 
     let deleted = db
         .delete_session(&id)
@@ -35,3 +27,48 @@ pub async fn delete_session(
     }
     Ok(Json(OkResponse { ok: true }))
 }
+
+const DEFAULT_RECOMPUTE_BATCH: u32 = 100;
+const MAX_RECOMPUTE_BATCH: u32 = 500;
+
+/// POST /api/admin/sessions/recompute — re-derive counts, `has_errors` and
+/// file lists for one batch of sessions from their stored bodies (admin key
+/// required). Call again with `after = next_after` until it is absent.
+pub async fn recompute_sessions(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    Json(req): Json<RecomputeSessionsRequest>,
+) -> Result<Json<RecomputeSessionsResponse>, ApiErr> {
+    require_admin_key(&headers, &config)?;
+
+    let limit = req
+        .limit
+        .unwrap_or(DEFAULT_RECOMPUTE_BATCH)
+        .clamp(1, MAX_RECOMPUTE_BATCH);
+    let response = db
+        .recompute_session_columns(req.after, limit)
+        .await
+        .map_err(ApiErr::from_db("recompute sessions"))?;
+    tracing::info!(
+        "recomputed session columns: {} scanned, {} updated, {} failed",
+        response.scanned,
+        response.updated,
+        response.errors.len()
+    );
+    Ok(Json(response))
+}
+
+/// Reject requests without the configured `X-OpenSession-Admin-Key`.
+pub(crate) fn require_admin_key(headers: &HeaderMap, config: &AppConfig) -> Result<(), ApiErr> {
+    let provided = headers
+        .get("X-OpenSession-Admin-Key")
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .unwrap_or("");
+
+    if config.admin_key.trim().is_empty() || provided != config.admin_key.trim() {
+        return Err(ApiErr::unauthorized("invalid admin key"));
+    }
+    Ok(())
+}
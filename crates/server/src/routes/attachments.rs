@@ -0,0 +1,153 @@
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::IntoResponse,
+};
+use opensession_api::{
+    ASCIICAST_CONTENT_TYPE, AttachmentKind, AttachmentUploadQuery, ServiceError, SessionAttachment,
+    SessionAttachmentListResponse, asciicast_recorded_at, is_asciicast, saturating_i64,
+};
+
+use crate::AppConfig;
+use crate::error::ApiErr;
+use crate::routes::auth::{AuthUser, enforce_csrf_if_cookie_auth};
+use crate::routes::guest_tokens::guest_team_from_headers;
+use crate::routes::sessions::readable_session;
+use crate::storage::{Db, NewAttachmentRecord};
+
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// POST /api/sessions/:id/attachments?name=<file> — attach a file to your session.
+///
+/// The body is the raw file content and `Content-Type` its media type.
+/// Asciicast recordings are stored as `terminal_recording` attachments, with
+/// `recorded_at` taken from their header unless the query sets it.
+pub async fn upload_attachment(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    user: AuthUser,
+    Path(session_id): Path<String>,
+    Query(q): Query<AttachmentUploadQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, Json<SessionAttachment>), ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    let file_name = q
+        .file_name()
+        .ok_or_else(|| ApiErr::bad_request("name must be a file name"))?;
+    if body.is_empty() {
+        return Err(ApiErr::bad_request("attachment is empty"));
+    }
+    let detail = db
+        .get_session_detail(&session_id)
+        .await
+        .map_err(|_| ApiErr::not_found("session not found"))?;
+    if detail.summary.user_id.as_deref() != Some(user.user_id.as_str()) {
+        return Err(ServiceError::Forbidden(
+            "only the session owner can add attachments".to_string(),
+        )
+        .into());
+    }
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty() && value.len() <= 255)
+        .unwrap_or(DEFAULT_CONTENT_TYPE)
+        .to_string();
+    let asciicast = content_type == ASCIICAST_CONTENT_TYPE || is_asciicast(&body);
+    let kind = q.kind.unwrap_or(if asciicast {
+        AttachmentKind::TerminalRecording
+    } else {
+        AttachmentKind::File
+    });
+    let recorded_at = match q.recorded_at.as_deref() {
+        Some(raw) => Some(
+            chrono::DateTime::parse_from_rfc3339(raw)
+                .map_err(|_| ApiErr::bad_request("recorded_at must be an RFC 3339 timestamp"))?
+                .with_timezone(&chrono::Utc)
+                .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+        ),
+        None if kind == AttachmentKind::TerminalRecording => asciicast_recorded_at(&body),
+        None => None,
+    };
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let storage_key = db
+        .write_attachment(&session_id, &id, &body)
+        .await
+        .map_err(ApiErr::from_db("write attachment"))?;
+    let attachment = db
+        .insert_attachment(NewAttachmentRecord {
+            id,
+            session_id,
+            user_id: user.user_id,
+            file_name,
+            content_type,
+            size_bytes: saturating_i64(body.len() as u64),
+            storage_key,
+            kind,
+            recorded_at,
+        })
+        .await
+        .map_err(ApiErr::from_db("insert attachment"))?;
+    Ok((StatusCode::CREATED, Json(attachment)))
+}
+
+/// GET /api/sessions/:id/attachments — list a session's attachments.
+///
+/// Visible to whoever may read the session itself.
+pub async fn list_attachments(
+    State(db): State<Db>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<SessionAttachmentListResponse>, ApiErr> {
+    let guest_team = guest_team_from_headers(&headers, &db).await?;
+    readable_session(&db, guest_team.as_deref(), &session_id).await?;
+    let attachments = db
+        .list_attachments(&session_id)
+        .await
+        .map_err(ApiErr::from_db("list attachments"))?;
+    Ok(Json(SessionAttachmentListResponse { attachments }))
+}
+
+/// GET /api/sessions/:id/attachments/:attachment_id — download an attachment.
+///
+/// Always served as a download with `nosniff`, so uploaded HTML or SVG never
+/// renders in the server's origin. Visible to whoever may read the session.
+pub async fn get_attachment(
+    State(db): State<Db>,
+    Path((session_id, attachment_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<axum::response::Response, ApiErr> {
+    let guest_team = guest_team_from_headers(&headers, &db).await?;
+    readable_session(&db, guest_team.as_deref(), &session_id).await?;
+    let (attachment, storage_key) = db
+        .get_attachment(&session_id, &attachment_id)
+        .await
+        .map_err(ApiErr::from_db("lookup attachment"))?
+        .ok_or_else(|| ApiErr::not_found("attachment not found"))?;
+    let data = db
+        .read_body(&storage_key)
+        .await
+        .map_err(ApiErr::from_db("read attachment"))?;
+
+    let content_type = HeaderValue::from_str(&attachment.content_type)
+        .unwrap_or(HeaderValue::from_static(DEFAULT_CONTENT_TYPE));
+    let disposition = HeaderValue::from_str(&format!(
+        "attachment; filename=\"{}\"",
+        attachment.file_name
+    ))
+    .unwrap_or(HeaderValue::from_static("attachment"));
+    let mut response = data.into_response();
+    let response_headers = response.headers_mut();
+    response_headers.insert(header::CONTENT_TYPE, content_type);
+    response_headers.insert(header::CONTENT_DISPOSITION, disposition);
+    response_headers.insert(
+        header::X_CONTENT_TYPE_OPTIONS,
+        HeaderValue::from_static("nosniff"),
+    );
+    Ok(response)
+}
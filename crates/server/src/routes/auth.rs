@@ -1,23 +1,27 @@
 use axum::{
     Json,
-    body::Bytes,
+    body::{Body, Bytes},
     extract::{FromRef, FromRequestParts, Path, State},
     http::{HeaderMap, HeaderValue, StatusCode, header, request::Parts},
     response::{IntoResponse, Response},
 };
+use base64::{Engine, prelude::BASE64_STANDARD};
+use futures_util::{StreamExt, stream};
 use serde::Serialize;
 use uuid::Uuid;
 
 use opensession_api::{
-    AuthRegisterRequest, AuthTokenResponse, ChangePasswordRequest, CreateGitCredentialRequest,
-    GitCredentialSummary, IssueApiKeyResponse, ListGitCredentialsResponse, LoginRequest,
-    OkResponse, RefreshRequest, UserSettingsResponse, VerifyResponse, crypto, service,
-    service::AuthToken,
+    ACCOUNT_EXPORT_CONTENT_TYPE, AccountExportRecord, AuthRegisterRequest, AuthTokenResponse,
+    ChangePasswordRequest, CreateGitCredentialRequest, DEVICE_ID_HEADER, DEVICE_NAME_HEADER,
+    GitCredentialSummary, IssueApiKeyResponse, ListDevicesResponse, ListGitCredentialsResponse,
+    LoginRequest, OkResponse, RefreshRequest, RegisterDeviceKeyRequest, ServiceError,
+    SessionSummary, UserSettingsResponse, VerifyResponse, crypto, service, service::AuthToken,
 };
 
 use crate::AppConfig;
 use crate::error::ApiErr;
-use crate::storage::{Db, NewGitCredentialRecord};
+use crate::proxy_auth::ProxyIdentity;
+use crate::storage::{Db, DeviceUse, NewGitCredentialRecord};
 
 const ACCESS_COOKIE_NAME: &str = "opensession_access_token";
 const REFRESH_COOKIE_NAME: &str = "opensession_refresh_token";
@@ -39,6 +43,39 @@ pub struct AuthUser {
     pub auth_via_cookie: bool,
     #[allow(dead_code)]
     pub email: Option<String>,
+    /// Machine id from [`DEVICE_ID_HEADER`], already checked against revocation.
+    pub device_id: Option<String>,
+    /// `device_id` is vouched for by an interactive login or by an API key
+    /// that was bound to it before this request, not just by the header.
+    pub device_verified: bool,
+}
+
+/// Client machine identity sent by the CLI and daemon.
+struct RequestDevice {
+    id: String,
+    name: Option<String>,
+}
+
+fn request_device(headers: &HeaderMap) -> Option<RequestDevice> {
+    let header_text = |name: &str, max_len: usize| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty() && value.len() <= max_len)
+            .map(ToOwned::to_owned)
+    };
+    let id = header_text(DEVICE_ID_HEADER, 128)?;
+    if !id
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+    {
+        return None;
+    }
+    Some(RequestDevice {
+        id,
+        name: header_text(DEVICE_NAME_HEADER, 120),
+    })
 }
 
 async fn resolve_auth_user(
@@ -46,40 +83,125 @@ async fn resolve_auth_user(
     db: &Db,
     config: &AppConfig,
     auth_via_cookie: bool,
+    device: Option<RequestDevice>,
 ) -> Result<AuthUser, ApiErr> {
     let now = chrono::Utc::now().timestamp() as u64;
     let resolved = service::resolve_auth_token(token, &config.jwt_secret, now)
         .map_err(|e| ApiErr::unauthorized(e.message()))?;
 
-    match resolved {
+    let (user, key_hash) = match resolved {
         AuthToken::ApiKey(key) => {
             let key_hash = service::hash_api_key(&key);
             let user = db
                 .get_auth_user_by_api_key_hash(&key_hash)
                 .await
                 .map_err(|_| ApiErr::unauthorized("invalid API key"))?;
-            Ok(AuthUser {
-                user_id: user.user_id,
-                nickname: user.nickname,
-                auth_via_cookie,
-                email: user.email,
-            })
+            (user, Some(key_hash))
         }
         AuthToken::Jwt(user_id) => {
             let user = db
                 .get_auth_user_by_id(&user_id)
                 .await
                 .map_err(|_| ApiErr::unauthorized("user not found"))?;
-            Ok(AuthUser {
-                user_id: user.user_id,
-                nickname: user.nickname,
-                auth_via_cookie,
-                email: user.email,
-            })
+            (user, None)
         }
+    };
+
+    let (device_id, device_verified) =
+        record_device(db, &user.user_id, device, key_hash.as_deref()).await?;
+
+    Ok(AuthUser {
+        user_id: user.user_id,
+        nickname: user.nickname,
+        auth_via_cookie,
+        email: user.email,
+        device_id,
+        device_verified,
+    })
+}
+
+/// Returns the request's device id and whether it is verified (see
+/// [`AuthUser::device_verified`]).
+async fn record_device(
+    db: &Db,
+    user_id: &str,
+    device: Option<RequestDevice>,
+    key_hash: Option<&str>,
+) -> Result<(Option<String>, bool), ApiErr> {
+    let Some(device) = device else {
+        return Ok((None, false));
+    };
+    match db
+        .record_device_use(user_id, &device.id, device.name.as_deref(), key_hash)
+        .await
+        .map_err(ApiErr::from_db("record device use"))?
+    {
+        DeviceUse::Recorded { key_bound } => Ok((Some(device.id), key_hash.is_none() || key_bound)),
+        DeviceUse::Revoked => Err(ApiErr::unauthorized("this device has been revoked")),
+        DeviceUse::KeyBoundElsewhere => Err(ApiErr::unauthorized(
+            "this API key belongs to another device",
+        )),
     }
 }
 
+/// Sign in the user a trusted reverse proxy vouched for, creating the account
+/// on first sight. Behaves like cookie auth, since browsers send the header
+/// implicitly.
+async fn resolve_proxy_user(
+    identity: ProxyIdentity,
+    db: &Db,
+    device: Option<RequestDevice>,
+) -> Result<AuthUser, ApiErr> {
+    let email = service::validate_email(&identity.email).map_err(ApiErr::from)?;
+    let (user_id, nickname) = match db.get_user_id_and_nickname_by_email(&email).await {
+        Ok(found) => found,
+        Err(err) if err.is_not_found() => {
+            provision_proxy_user(db, &email, identity.name.as_deref()).await?
+        }
+        Err(err) => return Err(ApiErr::from_db("look up proxy user")(err)),
+    };
+    let (device_id, device_verified) = record_device(db, &user_id, device, None).await?;
+    Ok(AuthUser {
+        user_id,
+        nickname,
+        auth_via_cookie: true,
+        email: Some(email),
+        device_id,
+        device_verified,
+    })
+}
+
+async fn provision_proxy_user(
+    db: &Db,
+    email: &str,
+    name: Option<&str>,
+) -> Result<(String, String), ApiErr> {
+    let base = name
+        .and_then(|name| service::validate_nickname(name).ok())
+        .unwrap_or_else(|| email.split('@').next().unwrap_or(email).to_string());
+    let user_id = Uuid::new_v4().to_string();
+    for attempt in 0..5 {
+        let nickname = match attempt {
+            0 => base.clone(),
+            _ => format!("{base}-{}", &Uuid::new_v4().simple().to_string()[..6]),
+        };
+        match db.insert_oauth_user(&user_id, &nickname, Some(email)).await {
+            Ok(()) => {
+                tracing::info!("provisioned proxy-authenticated user {email}");
+                return Ok((user_id, nickname));
+            }
+            Err(err) if err.is_constraint_violation() => {
+                // A concurrent first request may have created the account.
+                if let Ok(found) = db.get_user_id_and_nickname_by_email(email).await {
+                    return Ok(found);
+                }
+            }
+            Err(err) => return Err(ApiErr::from_db("provision proxy user")(err)),
+        }
+    }
+    Err(ApiErr::conflict("could not pick a free nickname"))
+}
+
 fn parse_cookie_value(headers: &HeaderMap, name: &str) -> Option<String> {
     headers
         .get(header::COOKIE)
@@ -98,7 +220,7 @@ fn parse_cookie_value(headers: &HeaderMap, name: &str) -> Option<String> {
         })
 }
 
-fn header_bearer_token(headers: &HeaderMap) -> Option<String> {
+pub(crate) fn header_bearer_token(headers: &HeaderMap) -> Option<String> {
     headers
         .get(header::AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
@@ -111,11 +233,23 @@ pub async fn try_auth_from_headers(
     db: &Db,
     config: &AppConfig,
 ) -> Result<Option<AuthUser>, ApiErr> {
+    let device = request_device(headers);
     if let Some(token) = header_bearer_token(headers) {
-        return resolve_auth_user(&token, db, config, false).await.map(Some);
+        return resolve_auth_user(&token, db, config, false, device)
+            .await
+            .map(Some);
+    }
+    if let Some(identity) = config
+        .proxy_auth
+        .as_ref()
+        .and_then(|proxy| proxy.identity(headers))
+    {
+        return resolve_proxy_user(identity, db, device).await.map(Some);
     }
     if let Some(token) = parse_cookie_value(headers, ACCESS_COOKIE_NAME) {
-        return resolve_auth_user(&token, db, config, true).await.map(Some);
+        return resolve_auth_user(&token, db, config, true, device)
+            .await
+            .map(Some);
     }
     Ok(None)
 }
@@ -271,6 +405,15 @@ pub(crate) fn enforce_csrf_if_cookie_auth(
     if !origin_is_allowed(origin, config) {
         return Err(ApiErr::unauthorized("request origin is not allowed"));
     }
+    // Proxy sessions carry no CSRF cookie; the origin check above is what
+    // guards them.
+    if config
+        .proxy_auth
+        .as_ref()
+        .is_some_and(|proxy| proxy.identity(headers).is_some())
+    {
+        return Ok(());
+    }
     let csrf_cookie = parse_cookie_value(headers, CSRF_COOKIE_NAME)
         .ok_or_else(|| ApiErr::unauthorized("missing csrf cookie"))?;
     let csrf_header = headers
@@ -283,6 +426,17 @@ pub(crate) fn enforce_csrf_if_cookie_auth(
     Ok(())
 }
 
+/// Passwords are not accepted while the SSO proxy owns sign-in.
+fn reject_password_auth(config: &AppConfig) -> Result<(), ApiErr> {
+    if config.proxy_auth.is_some() {
+        return Err(ServiceError::Forbidden(
+            "password sign-in is disabled; sign in through your SSO proxy".into(),
+        )
+        .into());
+    }
+    Ok(())
+}
+
 /// Public wrapper for oauth module to issue tokens.
 pub async fn issue_tokens_pub(
     db: &Db,
@@ -353,6 +507,7 @@ pub async fn auth_register(
         return Err(ApiErr::internal("JWT_SECRET not configured"));
     }
 
+    reject_password_auth(&config)?;
     let email = service::validate_email(&req.email).map_err(ApiErr::from)?;
     service::validate_password(&req.password).map_err(ApiErr::from)?;
     let nickname = service::validate_nickname(&req.nickname).map_err(ApiErr::from)?;
@@ -398,6 +553,7 @@ pub async fn login(
         return Err(ApiErr::internal("JWT_SECRET not configured"));
     }
 
+    reject_password_auth(&config)?;
     let email = service::validate_email(&req.email).map_err(ApiErr::from)?;
 
     let user = db
@@ -482,6 +638,7 @@ pub async fn change_password(
     user: AuthUser,
     Json(req): Json<ChangePasswordRequest>,
 ) -> Result<Json<OkResponse>, ApiErr> {
+    reject_password_auth(&config)?;
     enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
     let fields = db
         .get_password_fields(&user.user_id)
@@ -532,19 +689,126 @@ pub async fn me(
     State(db): State<Db>,
     user: AuthUser,
 ) -> Result<Json<UserSettingsResponse>, ApiErr> {
+    Ok(Json(user_settings(&db, &user).await?))
+}
+
+async fn user_settings(db: &Db, user: &AuthUser) -> Result<UserSettingsResponse, ApiErr> {
     let settings = db
         .get_user_settings_data(&user.user_id)
         .await
         .map_err(ApiErr::from_db("me error"))?;
 
-    Ok(Json(UserSettingsResponse {
-        user_id: user.user_id,
-        nickname: user.nickname,
+    Ok(UserSettingsResponse {
+        user_id: user.user_id.clone(),
+        nickname: user.nickname.clone(),
         created_at: settings.created_at,
         email: settings.email,
         avatar_url: settings.avatar_url,
         oauth_providers: settings.oauth_providers,
-    }))
+        teams: settings.teams,
+    })
+}
+
+/// DELETE /api/auth/me — delete the account, every session it uploaded and
+/// every attachment it added. Not reversible; clients should offer
+/// `GET /api/auth/export` first.
+pub async fn delete_me(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+) -> Result<Response, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    let deleted = db
+        .delete_account(&user.user_id)
+        .await
+        .map_err(ApiErr::from_db("delete account"))?;
+    tracing::info!(
+        "deleted account {}: {} sessions, {} attachments",
+        user.user_id,
+        deleted.sessions_deleted,
+        deleted.attachments_deleted
+    );
+    let cookies = clear_cookie_headers(&headers, &config);
+    response_with_cookies(StatusCode::OK, &deleted, &cookies)
+}
+
+// ---------------------------------------------------------------------------
+// Data export
+// ---------------------------------------------------------------------------
+
+/// GET /api/auth/export — everything stored for the account as
+/// newline-delimited [`AccountExportRecord`]s. Sessions are read and sent one
+/// at a time, so large accounts do not have to fit in memory.
+pub async fn export_account(State(db): State<Db>, user: AuthUser) -> Result<Response, ApiErr> {
+    let account = user_settings(&db, &user).await?;
+    let devices = db
+        .list_devices(&user.user_id)
+        .await
+        .map_err(ApiErr::from_db("export devices"))?;
+    let metadata = db
+        .list_user_metadata(&user.user_id)
+        .await
+        .map_err(ApiErr::from_db("export metadata"))?;
+    let sessions = db
+        .list_user_sessions(&user.user_id)
+        .await
+        .map_err(ApiErr::from_db("export sessions"))?;
+
+    let head = export_line(&AccountExportRecord::Account {
+        account,
+        devices,
+        metadata,
+    });
+    let lines =
+        stream::once(async move { head }).chain(stream::iter(sessions).then(move |session| {
+            let db = db.clone();
+            async move { export_session_lines(&db, session).await }
+        }));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, ACCOUNT_EXPORT_CONTENT_TYPE),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"opensession-export.jsonl\"",
+            ),
+        ],
+        Body::from_stream(lines),
+    )
+        .into_response())
+}
+
+/// The `session` line for one session followed by its `attachment` lines.
+async fn export_session_lines(db: &Db, session: SessionSummary) -> anyhow::Result<Vec<u8>> {
+    let info = db.get_session_storage_info(&session.id).await?;
+    let body = db.read_body(&info.body_storage_key).await?;
+    let attachments = db.list_attachments(&session.id).await?;
+    let session_id = session.id.clone();
+
+    let mut out = export_line(&AccountExportRecord::Session {
+        session: Box::new(session),
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })?;
+    for attachment in attachments {
+        let Some((attachment, storage_key)) =
+            db.get_attachment(&session_id, &attachment.id).await?
+        else {
+            continue;
+        };
+        let content = db.read_body(&storage_key).await?;
+        out.extend(export_line(&AccountExportRecord::Attachment {
+            attachment,
+            content_base64: BASE64_STANDARD.encode(content),
+        })?);
+    }
+    Ok(out)
+}
+
+fn export_line(record: &AccountExportRecord) -> anyhow::Result<Vec<u8>> {
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+    Ok(line)
 }
 
 // ---------------------------------------------------------------------------
@@ -569,16 +833,101 @@ pub async fn issue_api_key(
     let key_prefix = service::key_prefix(&new_key);
     let key_id = Uuid::new_v4().to_string();
 
-    db.move_active_api_keys_to_grace(&user.user_id, &grace_until)
+    let device_id = user.device_id.as_deref();
+    db.move_active_api_keys_to_grace(&user.user_id, &grace_until, device_id)
         .await
         .map_err(ApiErr::from_db("issue api key move old keys"))?;
-    db.insert_active_api_key(&key_id, &user.user_id, &key_hash, &key_prefix)
+    db.insert_active_api_key(&key_id, &user.user_id, &key_hash, &key_prefix, device_id)
         .await
         .map_err(ApiErr::from_db("issue api key insert"))?;
 
     Ok(Json(IssueApiKeyResponse { api_key: new_key }))
 }
 
+/// GET /api/auth/devices — machines that used the caller's credentials.
+pub async fn list_devices(
+    State(db): State<Db>,
+    user: AuthUser,
+) -> Result<Json<ListDevicesResponse>, ApiErr> {
+    let mut devices = db
+        .list_devices(&user.user_id)
+        .await
+        .map_err(ApiErr::from_db("list devices"))?;
+    for device in &mut devices {
+        device.current = user.device_id.as_deref() == Some(device.id.as_str());
+    }
+    Ok(Json(ListDevicesResponse { devices }))
+}
+
+/// DELETE /api/auth/devices/:id — revoke a device, the API keys bound to it,
+/// and the caller's keys not yet bound to any device.
+pub async fn revoke_device(
+    Path(id): Path<String>,
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+) -> Result<Json<OkResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    let revoked = db
+        .revoke_device(&user.user_id, &id)
+        .await
+        .map_err(ApiErr::from_db("revoke device"))?;
+    if revoked.is_none() {
+        return Err(ApiErr::not_found("device not found"));
+    }
+    Ok(Json(OkResponse { ok: true }))
+}
+
+/// PUT /api/auth/devices/:id/signing-key — register the public key the
+/// calling device signs its uploads with.
+///
+/// A device can only register its own key, once; replacing it requires
+/// revoking the device. The device id must come from an interactive login or
+/// an API key already bound to it, so a leaked key cannot enroll a new device.
+pub async fn register_device_key(
+    Path(id): Path<String>,
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(req): Json<RegisterDeviceKeyRequest>,
+) -> Result<Json<OkResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    if user.device_id.as_deref() != Some(id.as_str()) {
+        return Err(ServiceError::Forbidden(
+            "a device can only register its own signing key".to_string(),
+        )
+        .into());
+    }
+    if !user.device_verified {
+        return Err(ServiceError::Forbidden(
+            "sign in on this device, or use an API key issued to it, to register a signing key"
+                .to_string(),
+        )
+        .into());
+    }
+    let public_key = req.public_key.trim();
+    if BASE64_STANDARD
+        .decode(public_key)
+        .map_or(true, |bytes| bytes.len() != 32)
+    {
+        return Err(ApiErr::bad_request(
+            "public_key must be a base64 Ed25519 public key",
+        ));
+    }
+    let registered = db
+        .register_device_key(&user.user_id, &id, public_key)
+        .await
+        .map_err(ApiErr::from_db("register device key"))?;
+    if !registered {
+        return Err(ApiErr::conflict(
+            "device already has a signing key; revoke the device to register a new one",
+        ));
+    }
+    Ok(Json(OkResponse { ok: true }))
+}
+
 fn normalize_header_name(raw: &str) -> Result<String, ApiErr> {
     let trimmed = raw.trim();
     if trimmed.is_empty() {
@@ -5,10 +5,15 @@ use crate::AppConfig;
 
 /// GET /api/capabilities — runtime feature availability.
 pub async fn capabilities(State(config): State<AppConfig>) -> Json<CapabilitiesResponse> {
-    Json(CapabilitiesResponse::for_runtime(
-        !config.jwt_secret.is_empty(),
+    let mut caps = CapabilitiesResponse::for_runtime(
+        !config.jwt_secret.is_empty() || config.proxy_auth.is_some(),
         true,
-    ))
+    );
+    caps.body_signing_key = config
+        .body_signer
+        .as_ref()
+        .map(|signer| signer.public_key());
+    Json(caps)
 }
 
 #[cfg(test)]
@@ -27,6 +32,12 @@ mod tests {
             public_feed_enabled: true,
             local_review_root: None,
             credential_keyring: None,
+            github_app: None,
+            upload_policy: Default::default(),
+            proxy_auth: None,
+            body_signer: None,
+            team_quotas: Default::default(),
+            retention_default: None,
         };
 
         let Json(caps) = capabilities(State(config)).await;
@@ -34,5 +45,6 @@ mod tests {
         assert!(caps.parse_preview_enabled);
         assert_eq!(caps.register_targets, vec!["local", "git"]);
         assert_eq!(caps.share_modes, vec!["web", "git", "quick", "json"]);
+        assert_eq!(caps.body_signing_key, None);
     }
 }
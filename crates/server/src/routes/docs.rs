@@ -90,6 +90,17 @@ pub async fn handle(Query(query): Query<DocsQuery>, headers: HeaderMap) -> Respo
     }
 }
 
+pub async fn llms_txt() -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/markdown; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=3600"),
+        ],
+        LLMS_TXT,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::docs_markdown_for_locale;
@@ -106,14 +117,3 @@ mod tests {
         assert!(selected.starts_with("# Documentation"));
     }
 }
-
-pub async fn llms_txt() -> impl IntoResponse {
-    (
-        StatusCode::OK,
-        [
-            (header::CONTENT_TYPE, "text/markdown; charset=utf-8"),
-            (header::CACHE_CONTROL, "public, max-age=3600"),
-        ],
-        LLMS_TXT,
-    )
-}
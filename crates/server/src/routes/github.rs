@@ -0,0 +1,121 @@
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
+use opensession_api::{SessionListQuery, SessionSummary};
+
+use crate::AppConfig;
+use crate::error::ApiErr;
+use crate::github_app::{self, CheckTarget, GithubAppConfig, MAX_LINKED_SESSIONS};
+use crate::storage::Db;
+
+/// POST /api/integrations/github/webhook — GitHub App deliveries for pushes
+/// and pull requests.
+///
+/// Answers `202` once the signature checks out; the check run is posted in
+/// the background so slow GitHub API calls never time out the delivery.
+pub async fn webhook(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, ApiErr> {
+    let Some(app) = config.github_app.clone() else {
+        return Err(ApiErr::not_found(
+            "GitHub App integration is not configured on this server",
+        ));
+    };
+    let signature = headers
+        .get("x-hub-signature-256")
+        .and_then(|value| value.to_str().ok());
+    if !app.verify_signature(&body, signature) {
+        return Err(ApiErr::unauthorized("invalid webhook signature"));
+    }
+
+    let event = headers
+        .get("x-github-event")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let target = github_app::check_target(event, &body)
+        .map_err(|err| ApiErr::bad_request(err.to_string()))?;
+    let Some(target) = target else {
+        return Ok(StatusCode::NO_CONTENT);
+    };
+
+    let base_url = config.base_url.clone();
+    tokio::spawn(async move {
+        let repo = target.repo.clone();
+        let head_sha = target.head_sha.clone();
+        if let Err(err) = post_check_run(&db, &app, &base_url, target).await {
+            tracing::warn!("GitHub check run for {repo}@{head_sha} failed: {err:#}");
+        }
+    });
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn post_check_run(
+    db: &Db,
+    app: &GithubAppConfig,
+    base_url: &str,
+    target: CheckTarget,
+) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+    let token = app
+        .installation_token(&client, target.installation_id)
+        .await?;
+    let commits = match target.pull_number {
+        Some(number) => {
+            app.pull_request_commits(&client, &token, &target.repo, number)
+                .await?
+        }
+        None => target.commits,
+    };
+
+    let sessions = sessions_for_commits(db, app.team_id(), &target.repo, &commits).await?;
+    if sessions.is_empty() {
+        tracing::debug!("no sessions linked to {}@{}", target.repo, target.head_sha);
+        return Ok(());
+    }
+    let report = github_app::check_report(&sessions, base_url);
+    app.create_check_run(&client, &token, &target.repo, &target.head_sha, &report)
+        .await
+}
+
+/// Sessions uploaded by members of `team_id` from a clone of `repo` with one
+/// of `commits` as their git commit, newest commit first. A SHA alone is not
+/// enough: forks and mirrors share it.
+async fn sessions_for_commits(
+    db: &Db,
+    team_id: &str,
+    repo: &str,
+    commits: &[String],
+) -> anyhow::Result<Vec<SessionSummary>> {
+    let mut sessions: Vec<SessionSummary> = Vec::new();
+    for commit in commits.iter().rev() {
+        let query = SessionListQuery {
+            git_commit: Some(commit.clone()),
+            limit: Some(MAX_LINKED_SESSIONS as u32),
+            ..SessionListQuery::default()
+        };
+        let page = db
+            .list_sessions(opensession_api::db::sessions::list_for_team(
+                &query, team_id,
+            )?)
+            .await
+            .map_err(|err| anyhow::anyhow!("listing sessions for {commit}: {err}"))?;
+        for session in page.sessions {
+            let same_repo = session
+                .git_remote
+                .as_deref()
+                .is_some_and(|remote| github_app::remote_matches_repo(remote, repo));
+            if same_repo && !sessions.iter().any(|known| known.id == session.id) {
+                sessions.push(session);
+            }
+        }
+        if sessions.len() > MAX_LINKED_SESSIONS {
+            break;
+        }
+    }
+    Ok(sessions)
+}
@@ -0,0 +1,125 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+};
+use uuid::Uuid;
+
+use opensession_api::{
+    CreateGuestTokenRequest, CreateGuestTokenResponse, GuestTokenSummary, ListGuestTokensResponse,
+    OkResponse, service,
+};
+
+use crate::AppConfig;
+use crate::error::ApiErr;
+use crate::routes::auth::{AuthUser, enforce_csrf_if_cookie_auth, header_bearer_token};
+use crate::storage::Db;
+
+/// Team a request's `osg_` bearer token grants read access to. `None` when the
+/// request carries no guest token.
+pub async fn guest_team_from_headers(
+    headers: &HeaderMap,
+    db: &Db,
+) -> Result<Option<String>, ApiErr> {
+    let Some(token) = header_bearer_token(headers).filter(|t| service::is_guest_token(t)) else {
+        return Ok(None);
+    };
+    db.resolve_guest_token(&service::hash_api_key(&token))
+        .await
+        .map_err(ApiErr::from_db("resolve guest token"))?
+        .map(Some)
+        .ok_or_else(|| ApiErr::unauthorized("invalid guest token"))
+}
+
+/// Only members of a team (per the sign-in provider's group mapping) manage its tokens.
+pub async fn require_member(db: &Db, user: &AuthUser, team_id: &str) -> Result<(), ApiErr> {
+    let member = db
+        .user_in_team(&user.user_id, team_id)
+        .await
+        .map_err(ApiErr::from_db("team membership lookup"))?;
+    if !member {
+        return Err(ApiErr::not_found("team not found"));
+    }
+    Ok(())
+}
+
+/// GET /api/teams/:team_id/guest-tokens
+pub async fn list_guest_tokens(
+    Path(team_id): Path<String>,
+    State(db): State<Db>,
+    user: AuthUser,
+) -> Result<Json<ListGuestTokensResponse>, ApiErr> {
+    require_member(&db, &user, &team_id).await?;
+    let guest_tokens = db
+        .list_guest_tokens(&team_id)
+        .await
+        .map_err(ApiErr::from_db("list guest tokens"))?;
+    Ok(Json(ListGuestTokensResponse { guest_tokens }))
+}
+
+/// POST /api/teams/:team_id/guest-tokens — issue a read-only token that lists
+/// the team's sessions. The token is returned only once.
+pub async fn create_guest_token(
+    Path(team_id): Path<String>,
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(req): Json<CreateGuestTokenRequest>,
+) -> Result<(StatusCode, Json<CreateGuestTokenResponse>), ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    require_member(&db, &user, &team_id).await?;
+
+    let label = req.label.trim().to_string();
+    if label.is_empty() {
+        return Err(ApiErr::bad_request("label is required"));
+    }
+    if label.len() > 120 {
+        return Err(ApiErr::bad_request("label is too long (max 120 chars)"));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let token = service::generate_guest_token();
+    db.insert_guest_token(
+        &id,
+        &team_id,
+        &label,
+        &service::hash_api_key(&token),
+        &user.user_id,
+    )
+    .await
+    .map_err(ApiErr::from_db("create guest token"))?;
+
+    let guest_token = db
+        .list_guest_tokens(&team_id)
+        .await
+        .map_err(ApiErr::from_db("reload guest token"))?
+        .into_iter()
+        .find(|summary: &GuestTokenSummary| summary.id == id)
+        .ok_or_else(|| ApiErr::internal("guest token vanished after insert"))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateGuestTokenResponse { token, guest_token }),
+    ))
+}
+
+/// DELETE /api/teams/:team_id/guest-tokens/:id
+pub async fn revoke_guest_token(
+    Path((team_id, id)): Path<(String, String)>,
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+) -> Result<Json<OkResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    require_member(&db, &user, &team_id).await?;
+    let affected = db
+        .delete_guest_token(&id, &team_id)
+        .await
+        .map_err(ApiErr::from_db("revoke guest token"))?;
+    if affected == 0 {
+        return Err(ApiErr::not_found("guest token not found"));
+    }
+    Ok(Json(OkResponse { ok: true }))
+}
@@ -0,0 +1,206 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use opensession_api::{
+    CompleteHandoffRequest, HandoffInboxItem, HandoffInboxResponse, HandoffStatus,
+    PublishHandoffRequest,
+};
+
+use crate::AppConfig;
+use crate::error::ApiErr;
+use crate::routes::auth::{AuthUser, enforce_csrf_if_cookie_auth};
+use crate::storage::{Db, NewHandoffRecord};
+
+const ARTIFACT_URI_PREFIX: &str = "os://artifact/";
+const DEFAULT_INBOX_LIMIT: u32 = 50;
+const MAX_INBOX_LIMIT: u32 = 200;
+const MAX_NOTE_CHARS: usize = 2000;
+
+#[derive(Debug, Deserialize)]
+pub struct InboxQuery {
+    pub limit: Option<u32>,
+    pub status: Option<HandoffStatus>,
+}
+
+/// POST /api/handoffs — deliver a handoff artifact to another user's inbox.
+pub async fn publish_handoff(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(req): Json<PublishHandoffRequest>,
+) -> Result<(StatusCode, Json<HandoffInboxItem>), ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+
+    let recipient = normalize_recipient(&req.recipient)?;
+    let artifact_uri = req.artifact_uri.trim().to_string();
+    if !artifact_uri.starts_with(ARTIFACT_URI_PREFIX) {
+        return Err(ApiErr::bad_request(
+            "artifact_uri must be an os://artifact/<sha256> URI",
+        ));
+    }
+    if req.canonical_jsonl.trim().is_empty() {
+        return Err(ApiErr::bad_request("canonical_jsonl is required"));
+    }
+    let note = req
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty())
+        .map(str::to_string);
+    if note
+        .as_ref()
+        .is_some_and(|note| note.chars().count() > MAX_NOTE_CHARS)
+    {
+        return Err(ApiErr::bad_request("note is too long (max 2000 chars)"));
+    }
+
+    let recipient_id = db
+        .find_user_id_by_nickname(recipient)
+        .await
+        .map_err(ApiErr::from_db("lookup handoff recipient"))?
+        .ok_or_else(|| ApiErr::not_found(format!("user @{recipient} not found")))?;
+
+    let id = Uuid::new_v4().to_string();
+    db.insert_handoff(NewHandoffRecord {
+        id: id.clone(),
+        sender_id: user.user_id.clone(),
+        recipient_id,
+        artifact_uri,
+        canonical_jsonl: req.canonical_jsonl,
+        markdown: req.markdown.filter(|markdown| !markdown.trim().is_empty()),
+        note,
+    })
+    .await
+    .map_err(ApiErr::from_db("create handoff"))?;
+
+    let created = reload_handoff(&db, &id).await?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// GET /api/handoffs/inbox — handoffs addressed to the authenticated user.
+pub async fn inbox(
+    State(db): State<Db>,
+    user: AuthUser,
+    Query(query): Query<InboxQuery>,
+) -> Result<Json<HandoffInboxResponse>, ApiErr> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_INBOX_LIMIT)
+        .clamp(1, MAX_INBOX_LIMIT);
+    let items = db
+        .list_handoff_inbox(&user.user_id, query.status, limit)
+        .await
+        .map_err(ApiErr::from_db("list handoff inbox"))?;
+    Ok(Json(HandoffInboxResponse { items }))
+}
+
+/// POST /api/handoffs/{id}/claim — recipient takes ownership of a pending handoff.
+pub async fn claim_handoff(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    user: AuthUser,
+) -> Result<Json<HandoffInboxItem>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+
+    let moved = db
+        .claim_handoff(&id, &user.user_id)
+        .await
+        .map_err(ApiErr::from_db("claim handoff"))?;
+    if moved == 0 {
+        return Err(transition_error(&db, &id, &user, HandoffStatus::Pending).await);
+    }
+    Ok(Json(reload_handoff(&db, &id).await?))
+}
+
+/// POST /api/handoffs/{id}/complete — finish a claimed handoff, optionally
+/// linking the session that carried out the work.
+pub async fn complete_handoff(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(req): Json<CompleteHandoffRequest>,
+) -> Result<Json<HandoffInboxItem>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+
+    let session_id = req
+        .session_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|session_id| !session_id.is_empty());
+    if let Some(session_id) = session_id {
+        let exists = db
+            .session_exists(session_id)
+            .await
+            .map_err(ApiErr::from_db("lookup handoff result session"))?;
+        if !exists {
+            return Err(ApiErr::bad_request(format!(
+                "session {session_id} has not been uploaded"
+            )));
+        }
+    }
+
+    let moved = db
+        .complete_handoff(&id, &user.user_id, session_id)
+        .await
+        .map_err(ApiErr::from_db("complete handoff"))?;
+    if moved == 0 {
+        return Err(transition_error(&db, &id, &user, HandoffStatus::Claimed).await);
+    }
+    Ok(Json(reload_handoff(&db, &id).await?))
+}
+
+async fn reload_handoff(db: &Db, id: &str) -> Result<HandoffInboxItem, ApiErr> {
+    db.find_handoff(id)
+        .await
+        .map_err(ApiErr::from_db("reload handoff"))?
+        .ok_or_else(|| ApiErr::not_found("handoff not found"))
+}
+
+/// Explain why a state transition matched no row: unknown/foreign handoff or wrong state.
+async fn transition_error(db: &Db, id: &str, user: &AuthUser, expected: HandoffStatus) -> ApiErr {
+    match db.find_handoff(id).await {
+        Ok(Some(item)) if item.recipient_nickname == user.nickname => {
+            ApiErr::conflict(format!("handoff is {}, expected {expected}", item.status))
+        }
+        Ok(_) => ApiErr::not_found("handoff not found"),
+        Err(err) => ApiErr::from_db("lookup handoff")(err),
+    }
+}
+
+/// Accepts `@nickname` or `nickname`. Team inboxes are not modeled server-side.
+fn normalize_recipient(raw: &str) -> Result<&str, ApiErr> {
+    let trimmed = raw.trim();
+    let nickname = trimmed.strip_prefix('@').unwrap_or(trimmed).trim();
+    if nickname.is_empty() {
+        return Err(ApiErr::bad_request("recipient is required"));
+    }
+    if nickname.chars().any(char::is_whitespace) {
+        return Err(ApiErr::bad_request(
+            "recipient must be a single user nickname",
+        ));
+    }
+    Ok(nickname)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_recipient;
+
+    #[test]
+    fn recipient_accepts_optional_at_prefix() {
+        assert_eq!(normalize_recipient("@alice").ok(), Some("alice"));
+        assert_eq!(normalize_recipient("  bob ").ok(), Some("bob"));
+        assert!(normalize_recipient("@").is_err());
+        assert!(normalize_recipient("platform team").is_err());
+    }
+}
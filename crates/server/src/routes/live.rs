@@ -0,0 +1,204 @@
+use std::convert::Infallible;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event as SseEvent, KeepAlive, Sse},
+};
+use futures_util::{Stream, stream};
+use tokio::sync::broadcast::error::RecvError;
+
+use opensession_api::{
+    LiveSessionInfo, LiveSessionListResponse, LiveUpdate, OkResponse, StreamEventsRequest,
+    StreamEventsResponse,
+};
+
+use crate::AppConfig;
+use crate::error::ApiErr;
+use crate::live::{LiveHub, LiveSubscription, MAX_SHARES_PER_USER, StartError};
+use crate::routes::auth::{AuthUser, enforce_csrf_if_cookie_auth};
+use crate::storage::Db;
+
+/// POST /api/sessions/:id/live — start sharing a running session with your
+/// teams, seeded with the agent, context and events so far.
+pub async fn start_live(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    State(live): State<LiveHub>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(req): Json<StreamEventsRequest>,
+) -> Result<(StatusCode, Json<LiveSessionInfo>), ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+
+    let session_id = session_id.trim();
+    if session_id.is_empty() {
+        return Err(ApiErr::bad_request("session id is required"));
+    }
+    if db
+        .session_exists(session_id)
+        .await
+        .map_err(ApiErr::from_db("lookup session"))?
+    {
+        return Err(ApiErr::conflict("session is already uploaded"));
+    }
+    let info = live
+        .start(
+            session_id,
+            &user.user_id,
+            &user.nickname,
+            req.agent,
+            req.context,
+            req.events,
+        )
+        .map_err(|err| match err {
+            StartError::Taken => ApiErr::conflict("another user is sharing this session"),
+            StartError::TooManyShares => ApiErr::too_many_requests(format!(
+                "you are already sharing {MAX_SHARES_PER_USER} sessions live; stop one first"
+            )),
+        })?;
+    Ok((StatusCode::CREATED, Json(info)))
+}
+
+/// POST /api/sessions/:id/events — append events to a session you are sharing.
+pub async fn append_live_events(
+    State(config): State<AppConfig>,
+    State(live): State<LiveHub>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(req): Json<StreamEventsRequest>,
+) -> Result<Json<StreamEventsResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+
+    let accepted = req.events.len();
+    live.append(&session_id, &user.user_id, req.events)
+        .ok_or_else(not_sharing)?;
+    Ok(Json(StreamEventsResponse { accepted }))
+}
+
+/// DELETE /api/sessions/:id/live — stop sharing.
+pub async fn stop_live(
+    State(config): State<AppConfig>,
+    State(live): State<LiveHub>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+    user: AuthUser,
+) -> Result<Json<OkResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+
+    if !live.end(&session_id, Some(&user.user_id), false) {
+        return Err(not_sharing());
+    }
+    Ok(Json(OkResponse { ok: true }))
+}
+
+/// GET /api/live — live sessions shared by you or a teammate.
+pub async fn list_live(
+    State(db): State<Db>,
+    State(live): State<LiveHub>,
+    user: AuthUser,
+) -> Result<Json<LiveSessionListResponse>, ApiErr> {
+    let teams = db
+        .user_team_ids(&user.user_id)
+        .await
+        .map_err(ApiErr::from_db("lookup teams"))?;
+    let mut sessions = Vec::new();
+    for (owner_id, info) in live.list() {
+        if shares_team(&db, &user, &teams, &owner_id).await? {
+            sessions.push(info);
+        }
+    }
+    Ok(Json(LiveSessionListResponse { sessions }))
+}
+
+/// GET /api/sessions/:id/live — follow a live session as server-sent events.
+///
+/// Each event's data is one [`LiveUpdate`] JSON object, starting with a
+/// `snapshot`. The stream closes after `ended`.
+pub async fn watch_live(
+    State(db): State<Db>,
+    State(live): State<LiveHub>,
+    Path(session_id): Path<String>,
+    user: AuthUser,
+) -> Result<Sse<impl Stream<Item = Result<SseEvent, Infallible>>>, ApiErr> {
+    let owner_id = live.owner_of(&session_id).ok_or_else(not_live)?;
+    let teams = db
+        .user_team_ids(&user.user_id)
+        .await
+        .map_err(ApiErr::from_db("lookup teams"))?;
+    if !shares_team(&db, &user, &teams, &owner_id).await? {
+        return Err(not_live());
+    }
+    let subscription = live
+        .subscribe(&session_id, &user.user_id, &user.nickname)
+        .ok_or_else(not_live)?;
+
+    let state = WatchState {
+        live,
+        session_id,
+        pending: Some(subscription.snapshot.clone()),
+        subscription,
+        ended: false,
+    };
+    let updates = stream::unfold(state, |mut state| async move {
+        let update = match state.pending.take() {
+            Some(update) => update,
+            None if state.ended => return None,
+            None => match state.subscription.updates.recv().await {
+                Ok(update) => update,
+                Err(RecvError::Lagged(_)) => state
+                    .live
+                    .snapshot(&state.session_id)
+                    .unwrap_or(LiveUpdate::Ended { uploaded: false }),
+                Err(RecvError::Closed) => return None,
+            },
+        };
+        state.ended = matches!(update, LiveUpdate::Ended { .. });
+        let event = SseEvent::default()
+            .json_data(&update)
+            .unwrap_or_else(|_| SseEvent::default().comment("unserializable update"));
+        Some((Ok(event), state))
+    });
+    Ok(Sse::new(updates).keep_alive(KeepAlive::default()))
+}
+
+struct WatchState {
+    live: LiveHub,
+    session_id: String,
+    pending: Option<LiveUpdate>,
+    /// Keeps the viewer listed until the stream is dropped.
+    subscription: LiveSubscription,
+    ended: bool,
+}
+
+/// Owners always see their own share; anyone else must be in one of the
+/// owner's teams.
+async fn shares_team(
+    db: &Db,
+    user: &AuthUser,
+    user_teams: &[String],
+    owner_id: &str,
+) -> Result<bool, ApiErr> {
+    if owner_id == user.user_id {
+        return Ok(true);
+    }
+    if user_teams.is_empty() {
+        return Ok(false);
+    }
+    let owner_teams = db
+        .user_team_ids(owner_id)
+        .await
+        .map_err(ApiErr::from_db("lookup teams"))?;
+    Ok(owner_teams.iter().any(|team| user_teams.contains(team)))
+}
+
+fn not_live() -> ApiErr {
+    ApiErr::not_found("live session not found")
+}
+
+fn not_sharing() -> ApiErr {
+    ApiErr::not_found("you are not sharing this session live")
+}
@@ -0,0 +1,230 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::HeaderMap,
+};
+
+use opensession_api::{
+    EventBookmark, EventBookmarkListResponse, MAX_METADATA_SYNC_ENTRIES, MetadataEntry, OkResponse,
+    PutEventBookmarkRequest, SyncMetadataRequest, SyncMetadataResponse, event_bookmark_key,
+    event_bookmark_prefix,
+};
+
+use crate::AppConfig;
+use crate::error::ApiErr;
+use crate::routes::auth::{AuthUser, enforce_csrf_if_cookie_auth};
+use crate::storage::Db;
+
+const MAX_KEY_CHARS: usize = 256;
+const MAX_VALUE_BYTES: usize = 64 * 1024;
+/// Writes stamped further ahead than this would win every later edit.
+const MAX_CLOCK_SKEW_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// GET /api/sync/metadata — every synced entry for the authenticated user.
+pub async fn list_metadata(
+    State(db): State<Db>,
+    user: AuthUser,
+) -> Result<Json<SyncMetadataResponse>, ApiErr> {
+    let entries = db
+        .list_user_metadata(&user.user_id)
+        .await
+        .map_err(ApiErr::from_db("list user metadata"))?;
+    Ok(Json(SyncMetadataResponse { entries }))
+}
+
+/// POST /api/sync/metadata — merge pushed entries (last writer wins) and
+/// return the resulting state.
+///
+/// Bookmarks of sessions this server does not have are left out; they stay
+/// pending on the client and are pushed again once the session exists.
+pub async fn sync_metadata(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(req): Json<SyncMetadataRequest>,
+) -> Result<Json<SyncMetadataResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+
+    if req.entries.len() > MAX_METADATA_SYNC_ENTRIES {
+        return Err(ApiErr::bad_request(format!(
+            "too many entries (max {MAX_METADATA_SYNC_ENTRIES} per request)"
+        )));
+    }
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let mut accepted = Vec::with_capacity(req.entries.len());
+    for entry in req.entries {
+        validate_entry(&entry, now_ms)?;
+        if entry.value.is_some()
+            && let Some(session_id) = bookmarked_session_id(&entry.key)
+            && !db
+                .session_exists(session_id)
+                .await
+                .map_err(ApiErr::from_db("lookup session"))?
+        {
+            continue;
+        }
+        accepted.push(entry);
+    }
+
+    let entries = db
+        .sync_user_metadata(&user.user_id, accepted)
+        .await
+        .map_err(ApiErr::from_db("sync user metadata"))?;
+    Ok(Json(SyncMetadataResponse { entries }))
+}
+
+/// GET /api/sessions/:id/bookmarks — your bookmarked events of a session.
+pub async fn list_event_bookmarks(
+    State(db): State<Db>,
+    user: AuthUser,
+    Path(session_id): Path<String>,
+) -> Result<Json<EventBookmarkListResponse>, ApiErr> {
+    let entries = db
+        .list_user_metadata_with_prefix(&user.user_id, &event_bookmark_prefix(&session_id))
+        .await
+        .map_err(ApiErr::from_db("list event bookmarks"))?;
+    let bookmarks = entries
+        .iter()
+        .filter_map(EventBookmark::from_entry)
+        .collect();
+    Ok(Json(EventBookmarkListResponse { bookmarks }))
+}
+
+/// PUT /api/sessions/:id/events/:event_id/bookmark — bookmark an event (or
+/// replace its note). Stored as synced metadata, so `opensession meta sync`
+/// brings it to your machines.
+pub async fn put_event_bookmark(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Path((session_id, event_id)): Path<(String, String)>,
+    Json(req): Json<PutEventBookmarkRequest>,
+) -> Result<Json<EventBookmark>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    if !db
+        .session_exists(&session_id)
+        .await
+        .map_err(ApiErr::from_db("lookup session"))?
+    {
+        return Err(ApiErr::not_found("session not found"));
+    }
+    let note = req
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty())
+        .map(str::to_string);
+    let entry = MetadataEntry {
+        key: event_bookmark_key(&session_id, &event_id),
+        value: Some(note.clone().unwrap_or_default()),
+        updated_at: chrono::Utc::now().timestamp_millis(),
+    };
+    validate_entry(&entry, entry.updated_at)?;
+    let updated_at = entry.updated_at;
+    db.put_user_metadata(&user.user_id, entry)
+        .await
+        .map_err(ApiErr::from_db("put event bookmark"))?;
+    Ok(Json(EventBookmark {
+        session_id,
+        event_id,
+        note,
+        updated_at,
+    }))
+}
+
+/// DELETE /api/sessions/:id/events/:event_id/bookmark — remove a bookmark;
+/// the deletion syncs like any other metadata write.
+pub async fn delete_event_bookmark(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Path((session_id, event_id)): Path<(String, String)>,
+) -> Result<Json<OkResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    let entry = MetadataEntry {
+        key: event_bookmark_key(&session_id, &event_id),
+        value: None,
+        updated_at: chrono::Utc::now().timestamp_millis(),
+    };
+    validate_entry(&entry, entry.updated_at)?;
+    db.put_user_metadata(&user.user_id, entry)
+        .await
+        .map_err(ApiErr::from_db("delete event bookmark"))?;
+    Ok(Json(OkResponse { ok: true }))
+}
+
+/// Session a `bookmark:<session-id>` or `bookmark:<session-id>#event-<id>`
+/// key points at.
+fn bookmarked_session_id(key: &str) -> Option<&str> {
+    let rest = key.strip_prefix("bookmark:")?;
+    let session_id = rest.split_once("#event-").map_or(rest, |(id, _)| id);
+    (!session_id.is_empty()).then_some(session_id)
+}
+
+fn validate_entry(entry: &MetadataEntry, now_ms: i64) -> Result<(), ApiErr> {
+    if entry.key.trim().is_empty() || entry.key.trim() != entry.key {
+        return Err(ApiErr::bad_request(
+            "metadata keys must be non-empty without surrounding whitespace",
+        ));
+    }
+    if entry.key.chars().count() > MAX_KEY_CHARS {
+        return Err(ApiErr::bad_request(format!(
+            "metadata key is too long (max {MAX_KEY_CHARS} chars)"
+        )));
+    }
+    if entry
+        .value
+        .as_ref()
+        .is_some_and(|value| value.len() > MAX_VALUE_BYTES)
+    {
+        return Err(ApiErr::bad_request(format!(
+            "metadata value for {} is too large (max 64 KiB)",
+            entry.key
+        )));
+    }
+    if entry.updated_at < 0 || entry.updated_at > now_ms + MAX_CLOCK_SKEW_MS {
+        return Err(ApiErr::bad_request(format!(
+            "metadata updated_at for {} is outside the accepted clock range",
+            entry.key
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_CLOCK_SKEW_MS, bookmarked_session_id, validate_entry};
+    use opensession_api::MetadataEntry;
+
+    fn entry(key: &str, value: Option<&str>, updated_at: i64) -> MetadataEntry {
+        MetadataEntry {
+            key: key.to_string(),
+            value: value.map(str::to_string),
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn entries_need_trimmed_keys_and_sane_timestamps() {
+        let now = 1_000_000;
+        assert!(validate_entry(&entry("bookmark:s1", Some("1"), now), now).is_ok());
+        assert!(validate_entry(&entry("note:s1", None, now), now).is_ok());
+        assert!(validate_entry(&entry(" note:s1", None, now), now).is_err());
+        assert!(validate_entry(&entry("", None, now), now).is_err());
+        assert!(validate_entry(&entry("k", None, -1), now).is_err());
+        assert!(validate_entry(&entry("k", None, now + MAX_CLOCK_SKEW_MS + 1), now).is_err());
+        let large = "x".repeat(64 * 1024 + 1);
+        assert!(validate_entry(&entry("k", Some(&large), now), now).is_err());
+    }
+
+    #[test]
+    fn bookmark_keys_name_their_session() {
+        assert_eq!(bookmarked_session_id("bookmark:s1"), Some("s1"));
+        assert_eq!(bookmarked_session_id("bookmark:s1#event-e2"), Some("s1"));
+        assert_eq!(bookmarked_session_id("bookmark:"), None);
+        assert_eq!(bookmarked_session_id("note:s1"), None);
+    }
+}
@@ -1,9 +1,22 @@
 pub mod admin;
+pub mod attachments;
 pub mod auth;
 pub mod capabilities;
 pub mod docs;
+pub mod github;
+pub mod guest_tokens;
+pub mod handoffs;
 pub mod health;
 pub mod ingest;
+pub mod live;
+pub mod metadata;
 pub mod oauth;
+pub mod playbooks;
+pub mod query;
 pub mod review;
+pub mod review_requests;
+pub mod schema;
 pub mod sessions;
+pub mod teams;
+pub mod telemetry;
+pub mod validate;
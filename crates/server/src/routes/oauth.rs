@@ -363,6 +363,12 @@ pub async fn callback(
         }
     };
     maybe_store_provider_access_token(&db, &config, &user_id, provider, &access_token).await?;
+    if provider.groups_claim.is_some() {
+        let teams = oauth::teams_for_groups(provider, &user_info.groups);
+        db.replace_user_teams(&user_id, &provider_id, &teams)
+            .await
+            .map_err(ApiErr::from_db("oauth team sync"))?;
+    }
 
     // Issue tokens
     let tokens =
@@ -0,0 +1,138 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::HeaderMap,
+};
+use opensession_api::{
+    MAX_PLAYBOOK_BODY_BYTES, OkResponse, Playbook, PlaybookListResponse, PutPlaybookRequest,
+    ServiceError, is_valid_playbook_name,
+};
+
+use crate::AppConfig;
+use crate::error::ApiErr;
+use crate::routes::auth::{AuthUser, enforce_csrf_if_cookie_auth};
+use crate::storage::Db;
+
+const MAX_TITLE_CHARS: usize = 200;
+const MAX_DESCRIPTION_CHARS: usize = 2000;
+
+/// GET /api/playbooks — list the server's shared playbooks.
+pub async fn list_playbooks(
+    State(db): State<Db>,
+    _user: AuthUser,
+) -> Result<Json<PlaybookListResponse>, ApiErr> {
+    let playbooks = db
+        .list_playbooks()
+        .await
+        .map_err(ApiErr::from_db("list playbooks"))?;
+    Ok(Json(PlaybookListResponse { playbooks }))
+}
+
+/// GET /api/playbooks/:name — fetch one playbook.
+pub async fn get_playbook(
+    State(db): State<Db>,
+    _user: AuthUser,
+    Path(name): Path<String>,
+) -> Result<Json<Playbook>, ApiErr> {
+    db.get_playbook(&name)
+        .await
+        .map_err(ApiErr::from_db("get playbook"))?
+        .map(Json)
+        .ok_or_else(|| ApiErr::not_found("playbook not found"))
+}
+
+/// PUT /api/playbooks/:name — create a playbook, or replace one you wrote.
+pub async fn put_playbook(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Path(name): Path<String>,
+    Json(mut req): Json<PutPlaybookRequest>,
+) -> Result<Json<Playbook>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+
+    if !is_valid_playbook_name(&name) {
+        return Err(ApiErr::bad_request(
+            "name must be lowercase letters, digits, '-' or '_' (max 64 chars)",
+        ));
+    }
+    if req.body.trim().is_empty() {
+        return Err(ApiErr::bad_request("body is required"));
+    }
+    if req.body.len() > MAX_PLAYBOOK_BODY_BYTES {
+        return Err(ApiErr::bad_request("body is too large (max 64 KiB)"));
+    }
+    req.title = trimmed(req.title);
+    req.description = trimmed(req.description);
+    if req
+        .title
+        .as_ref()
+        .is_some_and(|title| title.chars().count() > MAX_TITLE_CHARS)
+    {
+        return Err(ApiErr::bad_request("title is too long (max 200 chars)"));
+    }
+    if req
+        .description
+        .as_ref()
+        .is_some_and(|description| description.chars().count() > MAX_DESCRIPTION_CHARS)
+    {
+        return Err(ApiErr::bad_request(
+            "description is too long (max 2000 chars)",
+        ));
+    }
+
+    ensure_author(&db, &name, &user).await?;
+    let playbook = db
+        .upsert_playbook(&name, &user.user_id, req)
+        .await
+        .map_err(ApiErr::from_db("save playbook"))?;
+    Ok(Json(playbook))
+}
+
+/// DELETE /api/playbooks/:name — delete a playbook you wrote.
+pub async fn delete_playbook(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Path(name): Path<String>,
+) -> Result<Json<OkResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+
+    if db
+        .playbook_author(&name)
+        .await
+        .map_err(ApiErr::from_db("lookup playbook"))?
+        .is_none()
+    {
+        return Err(ApiErr::not_found("playbook not found"));
+    }
+    ensure_author(&db, &name, &user).await?;
+    db.delete_playbook(&name)
+        .await
+        .map_err(ApiErr::from_db("delete playbook"))?;
+    Ok(Json(OkResponse { ok: true }))
+}
+
+/// Existing playbooks can only be changed by their author; playbooks whose
+/// author account is gone are open to anyone.
+async fn ensure_author(db: &Db, name: &str, user: &AuthUser) -> Result<(), ApiErr> {
+    match db
+        .playbook_author(name)
+        .await
+        .map_err(ApiErr::from_db("lookup playbook"))?
+    {
+        Some(Some(author_id)) if author_id != user.user_id => Err(ServiceError::Forbidden(
+            "only the playbook's author can change it".to_string(),
+        )
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
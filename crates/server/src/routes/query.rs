@@ -0,0 +1,83 @@
+use axum::{Json, extract::State, http::HeaderMap};
+
+use opensession_api::service::{check_session_list_access, validate_query_size};
+use opensession_api::{
+    QueryOperation, QueryRequest, QueryResponse, QueryResult, SessionRepoListResponse,
+};
+
+use crate::AppConfig;
+use crate::error::ApiErr;
+use crate::routes::auth::AuthUser;
+use crate::routes::guest_tokens::guest_team_from_headers;
+use crate::routes::sessions::readable_session;
+use crate::storage::Db;
+
+/// POST /api/query — run several typed reads in one request.
+///
+/// Operations run in order with the same access rules as their REST
+/// counterparts; a failed operation is reported in its result slot and
+/// never aborts the rest of the batch.
+pub async fn run_query(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    auth_user: Result<AuthUser, ApiErr>,
+    headers: HeaderMap,
+    Json(req): Json<QueryRequest>,
+) -> Result<Json<QueryResponse>, ApiErr> {
+    validate_query_size(req.operations.len())?;
+
+    let guest_team = guest_team_from_headers(&headers, &db).await?;
+    let authenticated = auth_user.is_ok();
+    let mut results = Vec::with_capacity(req.operations.len());
+    for operation in req.operations {
+        let result = run_operation(
+            &db,
+            &config,
+            authenticated,
+            guest_team.as_deref(),
+            operation,
+        )
+        .await
+        .unwrap_or_else(|err| QueryResult::Error(err.to_body()));
+        results.push(result);
+    }
+    Ok(Json(QueryResponse { results }))
+}
+
+async fn run_operation(
+    db: &Db,
+    config: &AppConfig,
+    authenticated: bool,
+    guest_team: Option<&str>,
+    operation: QueryOperation,
+) -> Result<QueryResult, ApiErr> {
+    match operation {
+        QueryOperation::Sessions(query) => {
+            check_session_list_access(
+                config.public_feed_enabled,
+                authenticated || guest_team.is_some(),
+            )?;
+            let built = match guest_team {
+                Some(team_id) => opensession_api::db::sessions::list_for_team(&query, team_id)?,
+                None => opensession_api::db::sessions::list(&query)?,
+            };
+            let sessions = db
+                .list_sessions(built)
+                .await
+                .map_err(ApiErr::from_db("query sessions"))?;
+            Ok(QueryResult::Sessions(Box::new(sessions)))
+        }
+        QueryOperation::Session { id } => {
+            let detail = readable_session(db, guest_team, &id).await?;
+            Ok(QueryResult::Session(Box::new(detail)))
+        }
+        QueryOperation::SessionRepos => {
+            check_session_list_access(config.public_feed_enabled, authenticated)?;
+            let repos = db
+                .list_session_repos()
+                .await
+                .map_err(ApiErr::from_db("query session repos"))?;
+            Ok(QueryResult::SessionRepos(SessionRepoListResponse { repos }))
+        }
+    }
+}
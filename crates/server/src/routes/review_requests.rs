@@ -0,0 +1,164 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+};
+use uuid::Uuid;
+
+use opensession_api::{
+    CreateReviewRequest, ResolveReviewRequest, ReviewRequestItem, ReviewRequestListQuery,
+    ReviewRequestListResponse, ReviewRequestStatus,
+};
+
+use crate::AppConfig;
+use crate::error::ApiErr;
+use crate::routes::auth::{AuthUser, enforce_csrf_if_cookie_auth};
+use crate::storage::Db;
+
+const DEFAULT_LIST_LIMIT: u32 = 50;
+const MAX_LIST_LIMIT: u32 = 200;
+const MAX_TEXT_CHARS: usize = 2000;
+
+/// POST /api/sessions/:id/review-requests — ask another user to review a session.
+pub async fn create_review_request(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    Path(session_id): Path<String>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(req): Json<CreateReviewRequest>,
+) -> Result<(StatusCode, Json<ReviewRequestItem>), ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+
+    let reviewer = normalize_reviewer(&req.reviewer)?;
+    let note = optional_text(req.note, "note")?;
+    if !db
+        .session_exists(&session_id)
+        .await
+        .map_err(ApiErr::from_db("lookup session"))?
+    {
+        return Err(ApiErr::not_found("session not found"));
+    }
+    let reviewer_id = db
+        .find_user_id_by_nickname(reviewer)
+        .await
+        .map_err(ApiErr::from_db("lookup reviewer"))?
+        .ok_or_else(|| ApiErr::not_found(format!("user @{reviewer} not found")))?;
+    if reviewer_id == user.user_id {
+        return Err(ApiErr::bad_request("you cannot request your own review"));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    db.insert_review_request(
+        &id,
+        &session_id,
+        &user.user_id,
+        &reviewer_id,
+        note.as_deref(),
+    )
+    .await
+    .map_err(ApiErr::from_db("create review request"))?;
+
+    let created = reload_review_request(&db, &id).await?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// GET /api/review-requests — review requests assigned to (default) or sent by
+/// the authenticated user.
+pub async fn list_review_requests(
+    State(db): State<Db>,
+    user: AuthUser,
+    Query(query): Query<ReviewRequestListQuery>,
+) -> Result<Json<ReviewRequestListResponse>, ApiErr> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_LIST_LIMIT)
+        .clamp(1, MAX_LIST_LIMIT);
+    let items = db
+        .list_review_requests(&user.user_id, query.role, query.status, limit)
+        .await
+        .map_err(ApiErr::from_db("list review requests"))?;
+    Ok(Json(ReviewRequestListResponse { items }))
+}
+
+/// POST /api/review-requests/:id/resolve — the reviewer approves or asks for changes.
+pub async fn resolve_review_request(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(req): Json<ResolveReviewRequest>,
+) -> Result<Json<ReviewRequestItem>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+
+    if req.status == ReviewRequestStatus::Pending {
+        return Err(ApiErr::bad_request(
+            "status must be approved or changes_requested",
+        ));
+    }
+    let comment = optional_text(req.comment, "comment")?;
+    let moved = db
+        .resolve_review_request(&id, &user.user_id, req.status, comment.as_deref())
+        .await
+        .map_err(ApiErr::from_db("resolve review request"))?;
+    if moved == 0 {
+        return Err(match db.find_review_request(&id).await {
+            Ok(Some(item)) if item.reviewer_nickname == user.nickname => {
+                ApiErr::conflict(format!("review request is already {}", item.status))
+            }
+            Ok(_) => ApiErr::not_found("review request not found"),
+            Err(err) => ApiErr::from_db("lookup review request")(err),
+        });
+    }
+    Ok(Json(reload_review_request(&db, &id).await?))
+}
+
+async fn reload_review_request(db: &Db, id: &str) -> Result<ReviewRequestItem, ApiErr> {
+    db.find_review_request(id)
+        .await
+        .map_err(ApiErr::from_db("reload review request"))?
+        .ok_or_else(|| ApiErr::not_found("review request not found"))
+}
+
+/// Accepts `@nickname` or `nickname`.
+fn normalize_reviewer(raw: &str) -> Result<&str, ApiErr> {
+    let trimmed = raw.trim();
+    let nickname = trimmed.strip_prefix('@').unwrap_or(trimmed).trim();
+    if nickname.is_empty() {
+        return Err(ApiErr::bad_request("reviewer is required"));
+    }
+    if nickname.chars().any(char::is_whitespace) {
+        return Err(ApiErr::bad_request(
+            "reviewer must be a single user nickname",
+        ));
+    }
+    Ok(nickname)
+}
+
+fn optional_text(value: Option<String>, field: &str) -> Result<Option<String>, ApiErr> {
+    let value = value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if value
+        .as_ref()
+        .is_some_and(|value| value.chars().count() > MAX_TEXT_CHARS)
+    {
+        return Err(ApiErr::bad_request(format!(
+            "{field} is too long (max 2000 chars)"
+        )));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_reviewer;
+
+    #[test]
+    fn reviewer_accepts_optional_at_prefix() {
+        assert_eq!(normalize_reviewer("@alice").ok(), Some("alice"));
+        assert!(normalize_reviewer(" @ ").is_err());
+        assert!(normalize_reviewer("platform team").is_err());
+    }
+}
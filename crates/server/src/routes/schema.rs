@@ -0,0 +1,40 @@
+use axum::{
+    http::{HeaderValue, header},
+    response::IntoResponse,
+};
+use opensession_api::openapi::OPENAPI_JSON;
+use opensession_core::schema::SESSION_JSON_SCHEMA;
+
+/// GET /api/schema/session.json — JSON Schema for a HAIL session object.
+pub async fn session_schema() -> impl IntoResponse {
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/schema+json"),
+            ),
+            (
+                header::CACHE_CONTROL,
+                HeaderValue::from_static("public, max-age=3600"),
+            ),
+        ],
+        SESSION_JSON_SCHEMA,
+    )
+}
+
+/// GET /api/openapi.json — OpenAPI 3.1 description of this API.
+pub async fn openapi_document() -> impl IntoResponse {
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            ),
+            (
+                header::CACHE_CONTROL,
+                HeaderValue::from_static("public, max-age=3600"),
+            ),
+        ],
+        OPENAPI_JSON,
+    )
+}
@@ -5,46 +5,42 @@ use axum::{
     response::IntoResponse,
 };
 
+use base64::{Engine, prelude::BASE64_STANDARD};
+use opensession_api::db::sessions::DerivedColumns;
+use opensession_api::service::{
+    RAW_BODY_CONTENT_DISPOSITION, RawBodySource, body_etag, body_sha256, check_session_list_access,
+    if_none_match_hits, prepare_upload_session, raw_body_delta, resolve_raw_body_source,
+    session_content_fingerprint, storage_form, validate_batch_upload_size,
+};
 use opensession_api::{
-    SessionDetail, SessionListQuery, SessionListResponse, SessionRepoListResponse,
+    BatchUploadItemResult, BatchUploadRequest, BatchUploadResponse, DEVICE_ID_HEADER, QuotaNotice,
+    SESSION_BODY_SHA256_HEADER, SESSION_DELTA_HEADER, SessionDetail, SessionListQuery,
+    SessionListResponse, SessionRawQuery, SessionRepoListResponse, SessionValidationError,
+    UploadRequest, UploadResponse,
 };
+use opensession_core::extract::extract_upload_metadata;
+use opensession_core::jsonl::write_jsonl;
+use opensession_core::scoring::SessionScoreRegistry;
+use ring::signature::{ED25519, UnparsedPublicKey};
 
 use crate::AppConfig;
 use crate::error::ApiErr;
-use crate::routes::auth::AuthUser;
-use crate::storage::Db;
+use crate::live::LiveHub;
+use crate::routes::auth::{AuthUser, enforce_csrf_if_cookie_auth};
+use crate::routes::guest_tokens::guest_team_from_headers;
+use crate::storage::{Db, StorageError, UploadedSessionRow};
+use crate::team_quota::TeamUsage;
+use crate::upload_policy::UploadPolicy;
 
 const PUBLIC_LIST_CACHE_CONTROL: &str = "public, max-age=30, stale-while-revalidate=60";
 
-#[derive(Debug, PartialEq, Eq)]
-enum RawBodySource {
-    LocalStorage(String),
-    RedirectUrl(String),
-}
-
-fn resolve_raw_body_source(
-    body_storage_key: String,
-    body_url: Option<String>,
-) -> Result<RawBodySource, ApiErr> {
-    if let Some(url) = body_url.map(|v| v.trim().to_string()) {
-        if !url.is_empty() {
-            return Ok(RawBodySource::RedirectUrl(url));
-        }
-    }
-
-    let key = body_storage_key.trim().to_string();
-    if key.is_empty() {
-        return Err(ApiErr::not_found("session body not found"));
-    }
-
-    Ok(RawBodySource::LocalStorage(key))
-}
-
 // ---------------------------------------------------------------------------
 // List sessions
 // ---------------------------------------------------------------------------
 
 /// GET /api/sessions — list sessions (public, paginated, filtered).
+///
+/// A guest token (`osg_`) sees only sessions uploaded by its team's members.
 pub async fn list_sessions(
     State(db): State<Db>,
     State(config): State<AppConfig>,
@@ -53,15 +49,18 @@ pub async fn list_sessions(
     headers: HeaderMap,
 ) -> Result<axum::response::Response, ApiErr> {
     let has_auth_header = headers.get(header::AUTHORIZATION).is_some();
-    let is_authenticated = auth_user.is_ok();
-    if !can_access_session_list(config.public_feed_enabled, is_authenticated) {
-        return Err(ApiErr::unauthorized(
-            "public session feed is disabled; authentication required",
-        ));
-    }
+    let guest_team = guest_team_from_headers(&headers, &db).await?;
+    check_session_list_access(
+        config.public_feed_enabled,
+        auth_user.is_ok() || guest_team.is_some(),
+    )?;
 
+    let built = match guest_team.as_deref() {
+        Some(team_id) => opensession_api::db::sessions::list_for_team(&q, team_id)?,
+        None => opensession_api::db::sessions::list(&q)?,
+    };
     let payload: SessionListResponse = db
-        .list_sessions(&q)
+        .list_sessions(built)
         .await
         .map_err(ApiErr::from_db("list sessions"))?;
 
@@ -81,22 +80,13 @@ pub async fn list_sessions(
     Ok(resp)
 }
 
-fn can_access_session_list(public_feed_enabled: bool, is_authenticated: bool) -> bool {
-    public_feed_enabled || is_authenticated
-}
-
 /// GET /api/sessions/repos — list known repository names.
 pub async fn list_session_repos(
     State(db): State<Db>,
     State(config): State<AppConfig>,
     auth_user: Result<AuthUser, ApiErr>,
 ) -> Result<Json<SessionRepoListResponse>, ApiErr> {
-    let is_authenticated = auth_user.is_ok();
-    if !can_access_session_list(config.public_feed_enabled, is_authenticated) {
-        return Err(ApiErr::unauthorized(
-            "public session feed is disabled; authentication required",
-        ));
-    }
+    check_session_list_access(config.public_feed_enabled, auth_user.is_ok())?;
 
     let repos = db
         .list_session_repos()
@@ -106,19 +96,354 @@ pub async fn list_session_repos(
     Ok(Json(SessionRepoListResponse { repos }))
 }
 
+// ---------------------------------------------------------------------------
+// Batch upload
+// ---------------------------------------------------------------------------
+
+/// POST /api/sessions/batch — upload several sessions in one request.
+///
+/// Items are processed in order and independently: a failed item is reported
+/// in its result slot and never aborts the rest of the batch. An item whose
+/// content matches an existing upload is rejected with `duplicate_of` set
+/// unless it asks for `force`. An item that would push one of the uploader's
+/// teams past a hard quota is rejected with `quota_exceeded` set; when a team
+/// is already full the whole request fails with `403 quota_exceeded`. A stored
+/// session you were sharing live stops being live.
+pub async fn upload_sessions_batch(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    State(live): State<LiveHub>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(req): Json<BatchUploadRequest>,
+) -> Result<Json<BatchUploadResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    validate_batch_upload_size(req.sessions.len())?;
+    let teams = uploader_team_usage(&db, &config, &user)
+        .await
+        .map_err(ApiErr::from_db("team usage lookup"))?;
+    for (team_id, usage) in teams {
+        if let Err(notice) = config.team_quotas.check_upload(&team_id, usage, 0) {
+            return Err(ApiErr::quota_exceeded(format!("upload would put {notice}")));
+        }
+    }
+
+    let mut results = Vec::with_capacity(req.sessions.len());
+    for item in &req.sessions {
+        let session_id = item.session.session_id.clone();
+        let result = match upload_one(&db, &config, &user, item).await {
+            Ok(upload) => {
+                live.end(&session_id, Some(&user.user_id), true);
+                BatchUploadItemResult {
+                    session_id,
+                    upload: Some(upload),
+                    error: None,
+                    duplicate_of: None,
+                    policy_violations: Vec::new(),
+                    quota_exceeded: None,
+                }
+            }
+            Err(err) => BatchUploadItemResult {
+                session_id,
+                upload: None,
+                error: Some(err.message),
+                duplicate_of: err.duplicate_of,
+                policy_violations: err.policy_violations,
+                quota_exceeded: err.quota_exceeded,
+            },
+        };
+        results.push(result);
+    }
+
+    Ok(Json(BatchUploadResponse { results }))
+}
+
+/// Why a single batch item was not stored.
+#[derive(Debug)]
+struct UploadItemError {
+    message: String,
+    duplicate_of: Option<String>,
+    policy_violations: Vec<SessionValidationError>,
+    quota_exceeded: Option<QuotaNotice>,
+}
+
+impl From<String> for UploadItemError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            duplicate_of: None,
+            policy_violations: Vec::new(),
+            quota_exceeded: None,
+        }
+    }
+}
+
+/// Current usage of every team the uploader belongs to; empty when no
+/// quotas are configured.
+async fn uploader_team_usage(
+    db: &Db,
+    config: &AppConfig,
+    user: &AuthUser,
+) -> Result<Vec<(String, TeamUsage)>, StorageError> {
+    if config.team_quotas.is_unlimited() {
+        return Ok(Vec::new());
+    }
+    let mut usage = Vec::new();
+    for team_id in db.user_team_ids(&user.user_id).await? {
+        let team_usage = db.team_usage(&team_id).await?;
+        usage.push((team_id, team_usage));
+    }
+    Ok(usage)
+}
+
+async fn upload_one(
+    db: &Db,
+    config: &AppConfig,
+    user: &AuthUser,
+    req: &UploadRequest,
+) -> Result<UploadResponse, UploadItemError> {
+    let verified_origin = verify_upload_signature(db, user, req).await?;
+    let mut session = prepare_upload_session(&req.session).map_err(|e| e.message().to_string())?;
+    let team_policies = db
+        .team_upload_policies_for_user(&user.user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("load team upload policies: {e}");
+            "failed to check the upload privacy policy".to_string()
+        })?
+        .iter()
+        .map(UploadPolicy::from_team)
+        .collect::<Vec<_>>();
+    let policies = std::iter::once(&config.upload_policy)
+        .chain(&team_policies)
+        .collect::<Vec<_>>();
+    match UploadPolicy::enforce(&policies, &mut session) {
+        Ok(0) => {}
+        Ok(removed) => tracing::info!(
+            "scrubbed {removed} excluded event(s) from session {}",
+            session.session_id
+        ),
+        Err(violations) => {
+            return Err(UploadItemError {
+                message: format!(
+                    "session references {} event(s) excluded by the upload privacy policy",
+                    violations.len()
+                ),
+                duplicate_of: None,
+                policy_violations: violations,
+                quota_exceeded: None,
+            });
+        }
+    }
+    let (session, kind) = storage_form(session);
+    let session = &session;
+    let id = session.session_id.trim();
+    if db.get_session_storage_info(id).await.is_ok() {
+        return Err("session already exists".to_string().into());
+    }
+
+    let content_fingerprint = session_content_fingerprint(session);
+    let duplicate_of = match content_fingerprint.as_deref() {
+        Some(fingerprint) => db
+            .find_session_by_content_fingerprint(fingerprint)
+            .await
+            .map_err(|e| {
+                tracing::error!("lookup content fingerprint: {e}");
+                "failed to check for duplicate sessions".to_string()
+            })?,
+        None => None,
+    };
+    if let Some(existing_id) = duplicate_of.as_deref()
+        && !req.force
+    {
+        return Err(UploadItemError {
+            message: format!("duplicate session content (matches {existing_id})"),
+            duplicate_of: Some(existing_id.to_string()),
+            policy_violations: Vec::new(),
+            quota_exceeded: None,
+        });
+    }
+
+    let score = match req.score_plugin.as_deref() {
+        Some(plugin) => SessionScoreRegistry::default().score_with(plugin, session),
+        None => SessionScoreRegistry::default().score_default(session),
+    }
+    .map_err(|e| e.to_string())?;
+    let mut body = Vec::new();
+    write_jsonl(session, &mut body).map_err(|e| format!("invalid session body: {e}"))?;
+    let teams = uploader_team_usage(db, config, user).await.map_err(|e| {
+        tracing::error!("team usage lookup: {e}");
+        "failed to check team quotas".to_string()
+    })?;
+    let mut quota_warnings = Vec::new();
+    for (team_id, usage) in teams {
+        match config
+            .team_quotas
+            .check_upload(&team_id, usage, body.len() as u64)
+        {
+            Ok(warnings) => quota_warnings.extend(warnings),
+            Err(notice) => {
+                return Err(UploadItemError {
+                    message: format!("upload would put {notice}"),
+                    duplicate_of: None,
+                    policy_violations: Vec::new(),
+                    quota_exceeded: Some(notice),
+                });
+            }
+        }
+    }
+    let body_storage_key = db.write_body(id, &body).await.map_err(|e| {
+        tracing::error!("write body: {e}");
+        "failed to store session body".to_string()
+    })?;
+    let body_sha256 = body_sha256(&body);
+    let body_signature = config
+        .body_signer
+        .as_ref()
+        .map(|signer| signer.sign(id, &body_sha256));
+
+    let meta = extract_upload_metadata(session);
+    let derived = DerivedColumns::from_session(session);
+    let row = UploadedSessionRow {
+        id: id.to_string(),
+        user_id: user.user_id.clone(),
+        tool: session.agent.tool.clone(),
+        agent_provider: session.agent.provider.clone(),
+        agent_model: session.agent.model.clone(),
+        title: meta.title.unwrap_or_default(),
+        description: meta.description.unwrap_or_default(),
+        tags: meta.tags.unwrap_or_default(),
+        created_at: meta.created_at,
+        message_count: derived.message_count,
+        task_count: derived.task_count,
+        event_count: derived.event_count,
+        duration_seconds: derived.duration_seconds,
+        total_input_tokens: derived.total_input_tokens,
+        total_output_tokens: derived.total_output_tokens,
+        body_storage_key,
+        body_size_bytes: body.len() as i64,
+        body_url: req.body_url.clone(),
+        git_remote: req.git_remote.clone(),
+        git_branch: req.git_branch.clone(),
+        git_commit: req.git_commit.clone(),
+        git_repo_name: req.git_repo_name.clone(),
+        pr_number: req.pr_number,
+        pr_url: req.pr_url.clone(),
+        working_directory: meta.working_directory,
+        files_modified: derived.files_modified,
+        files_read: derived.files_read,
+        has_errors: derived.has_errors,
+        max_active_agents: derived.max_active_agents,
+        session_score: score.score,
+        score_plugin: score.plugin.clone(),
+        content_fingerprint,
+        device_id: user.device_id.clone(),
+        body_sha256: Some(body_sha256),
+        body_signature,
+        verified_origin,
+        kind,
+        linked_session_ids: req.linked_session_ids.clone().unwrap_or_default(),
+        duplicate_of,
+    };
+    db.insert_uploaded_session(row).await.map_err(|e| {
+        if e.is_constraint_violation() {
+            "session already exists".to_string()
+        } else {
+            tracing::error!("insert uploaded session: {e}");
+            "failed to store session".to_string()
+        }
+    })?;
+
+    Ok(UploadResponse {
+        id: id.to_string(),
+        url: format!("{}/session/{id}", config.base_url.trim_end_matches('/')),
+        session_score: score.score,
+        score_plugin: score.plugin,
+        quota_warnings,
+    })
+}
+
+/// Check an upload's device signature, if it carries one.
+///
+/// Unsigned uploads are accepted unverified; a signature that does not verify
+/// against the uploading device's registered key rejects the upload.
+async fn verify_upload_signature(
+    db: &Db,
+    user: &AuthUser,
+    req: &UploadRequest,
+) -> Result<bool, UploadItemError> {
+    let Some(signature) = req.signature.as_deref() else {
+        return Ok(false);
+    };
+    let Some(device_id) = user.device_id.as_deref() else {
+        return Err(format!("signed uploads must send the {DEVICE_ID_HEADER} header").into());
+    };
+    let public_key = db
+        .device_public_key(&user.user_id, device_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("look up device key: {e}");
+            "failed to verify upload signature".to_string()
+        })?
+        .ok_or_else(|| "this device has no registered signing key".to_string())?;
+    let verified = match (
+        BASE64_STANDARD.decode(public_key),
+        BASE64_STANDARD.decode(signature),
+    ) {
+        (Ok(public_key), Ok(signature)) => UnparsedPublicKey::new(&ED25519, public_key)
+            .verify(&req.signing_message(), &signature)
+            .is_ok(),
+        _ => false,
+    };
+    if !verified {
+        return Err("upload signature does not match the device's signing key"
+            .to_string()
+            .into());
+    }
+    Ok(true)
+}
+
 // ---------------------------------------------------------------------------
 // Get session detail
 // ---------------------------------------------------------------------------
 
+/// Load a session the request may read, or 404.
+///
+/// Every per-session read (detail, raw body, attachments) goes through here
+/// so they share one visibility rule: a guest token only reaches sessions
+/// uploaded by its team's members, the same ones it sees in the list.
+pub(crate) async fn readable_session(
+    db: &Db,
+    guest_team: Option<&str>,
+    id: &str,
+) -> Result<SessionDetail, ApiErr> {
+    let detail = db
+        .get_session_detail(id)
+        .await
+        .map_err(|_| ApiErr::not_found("session not found"))?;
+    if let Some(team_id) = guest_team {
+        let in_team = match detail.summary.user_id.as_deref() {
+            Some(user_id) => db
+                .user_in_team(user_id, team_id)
+                .await
+                .map_err(ApiErr::from_db("team membership lookup"))?,
+            None => false,
+        };
+        if !in_team {
+            return Err(ApiErr::not_found("session not found"));
+        }
+    }
+    Ok(detail)
+}
+
 /// GET /api/sessions/:id — get session detail with linked sessions.
 pub async fn get_session(
     State(db): State<Db>,
     Path(id): Path<String>,
+    headers: HeaderMap,
 ) -> Result<Json<SessionDetail>, ApiErr> {
-    let detail: SessionDetail = db
-        .get_session_detail(&id)
-        .await
-        .map_err(|_| ApiErr::not_found("session not found"))?;
+    let guest_team = guest_team_from_headers(&headers, &db).await?;
+    let detail = readable_session(&db, guest_team.as_deref(), &id).await?;
 
     Ok(Json(detail))
 }
@@ -128,89 +453,88 @@ pub async fn get_session(
 // ---------------------------------------------------------------------------
 
 /// GET /api/sessions/:id/raw — download the full HAIL JSONL body.
+///
+/// Locally stored bodies carry a content-hash `ETag`; a matching
+/// `If-None-Match` short-circuits to `304 Not Modified`. With `since_event`,
+/// only the events past that index are returned (the ETag still names the
+/// full body). Full bodies also carry the SHA-256 recorded at upload in
+/// [`SESSION_BODY_SHA256_HEADER`].
 pub async fn get_session_raw(
     State(db): State<Db>,
     Path(id): Path<String>,
+    Query(raw_q): Query<SessionRawQuery>,
+    headers: HeaderMap,
 ) -> Result<axum::response::Response, ApiErr> {
+    let guest_team = guest_team_from_headers(&headers, &db).await?;
+    readable_session(&db, guest_team.as_deref(), &id).await?;
     let info = db
         .get_session_storage_info(&id)
         .await
         .map_err(|_| ApiErr::not_found("session not found"))?;
 
-    match resolve_raw_body_source(info.body_storage_key, info.body_url)? {
-        RawBodySource::RedirectUrl(url) => {
+    match resolve_raw_body_source(&info.body_storage_key, info.body_url.as_deref())? {
+        RawBodySource::Redirect(url) => {
             let location = HeaderValue::from_str(&url)
                 .map_err(|_| ApiErr::internal("invalid session body URL"))?;
             let mut response = StatusCode::FOUND.into_response();
             response.headers_mut().insert(header::LOCATION, location);
             Ok(response)
         }
-        RawBodySource::LocalStorage(storage_key) => {
-            let body = db.read_body(&storage_key).await.map_err(|e| {
+        RawBodySource::Stored(storage_key) => {
+            let body = db.read_session_body(&id, &storage_key).await.map_err(|e| {
                 tracing::error!("read body: {e}");
                 ApiErr::internal("failed to read session body")
             })?;
 
-            Ok((
+            let etag = body_etag(&body);
+            let etag_header = HeaderValue::from_str(&etag)
+                .map_err(|_| ApiErr::internal("invalid session body etag"))?;
+            let not_modified = headers
+                .get(header::IF_NONE_MATCH)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| if_none_match_hits(v, &etag));
+            if not_modified {
+                let mut response = StatusCode::NOT_MODIFIED.into_response();
+                response.headers_mut().insert(header::ETAG, etag_header);
+                return Ok(response);
+            }
+
+            if let Some(since_event) = raw_q.since_event {
+                let delta = raw_body_delta(&body, since_event as usize)?;
+                let mut response = (
+                    StatusCode::OK,
+                    [(axum::http::header::CONTENT_TYPE, "application/jsonl")],
+                    delta,
+                )
+                    .into_response();
+                response.headers_mut().insert(header::ETAG, etag_header);
+                response
+                    .headers_mut()
+                    .insert(SESSION_DELTA_HEADER, HeaderValue::from(since_event));
+                return Ok(response);
+            }
+
+            let mut response = (
                 StatusCode::OK,
                 [
                     (axum::http::header::CONTENT_TYPE, "application/jsonl"),
                     (
                         axum::http::header::CONTENT_DISPOSITION,
-                        "attachment; filename=\"session.hail.jsonl\"",
+                        RAW_BODY_CONTENT_DISPOSITION,
                     ),
                 ],
                 body,
             )
-                .into_response())
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{RawBodySource, can_access_session_list, resolve_raw_body_source};
-
-    #[test]
-    fn session_list_access_rules_follow_public_feed_flag() {
-        assert!(can_access_session_list(true, false));
-        assert!(can_access_session_list(true, true));
-        assert!(can_access_session_list(false, true));
-        assert!(!can_access_session_list(false, false));
-    }
-
-    #[test]
-    fn raw_body_source_prefers_redirect_when_body_url_present() {
-        let source = match resolve_raw_body_source(
-            "".to_string(),
-            Some("https://example.com/a".to_string()),
-        ) {
-            Ok(source) => source,
-            Err(_) => panic!("body_url should resolve to redirect"),
-        };
-        match source {
-            RawBodySource::RedirectUrl(url) => assert_eq!(url, "https://example.com/a"),
-            RawBodySource::LocalStorage(_) => panic!("expected redirect source"),
-        }
-    }
-
-    #[test]
-    fn raw_body_source_uses_storage_key_when_no_body_url() {
-        let source = match resolve_raw_body_source("abc.hail.jsonl".to_string(), None) {
-            Ok(source) => source,
-            Err(_) => panic!("storage key should resolve to local storage"),
-        };
-        match source {
-            RawBodySource::LocalStorage(key) => assert_eq!(key, "abc.hail.jsonl"),
-            RawBodySource::RedirectUrl(_) => panic!("expected local storage source"),
+                .into_response();
+            response.headers_mut().insert(header::ETAG, etag_header);
+            if let Some(sha) = info.body_sha256.as_deref()
+                && let Ok(value) = HeaderValue::from_str(sha)
+            {
+                response
+                    .headers_mut()
+                    .insert(SESSION_BODY_SHA256_HEADER, value);
+            }
+            Ok(response)
         }
     }
-
-    #[test]
-    fn raw_body_source_rejects_empty_storage_and_body_url() {
-        let err = resolve_raw_body_source("".to_string(), Some("   ".to_string()))
-            .expect_err("empty storage/body_url should fail");
-        let response = axum::response::IntoResponse::into_response(err);
-        assert_eq!(response.status(), axum::http::StatusCode::NOT_FOUND);
-    }
 }
@@ -0,0 +1,371 @@
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::HeaderMap,
+};
+
+use opensession_api::{
+    RetentionAction, RetentionPolicy, RetentionPreviewItem, RetentionPreviewResponse, ServiceError,
+    TeamRetentionResponse, TeamUploadPolicy, TeamUploadPolicyResponse, TeamUsageResponse,
+};
+
+use crate::AppConfig;
+use crate::error::ApiErr;
+use crate::retention::{self, RetentionRules};
+use crate::routes::admin::require_admin_key;
+use crate::routes::auth::{AuthUser, enforce_csrf_if_cookie_auth};
+use crate::routes::guest_tokens::require_member;
+use crate::storage::Db;
+
+/// Longest retention period a team can set; `0` keeps sessions forever.
+const MAX_RETAIN_DAYS: u32 = 36_500;
+
+/// Most exclude patterns a team upload policy can hold.
+const MAX_UPLOAD_PATTERNS: usize = 100;
+const MAX_UPLOAD_PATTERN_CHARS: usize = 200;
+
+/// What applies when neither the team nor the server sets a policy.
+const FOREVER: RetentionPolicy = RetentionPolicy {
+    retain_days: 0,
+    action: RetentionAction::Delete,
+    exempt_bookmarked: true,
+    exempt_linked: true,
+};
+
+/// GET /api/teams/:team_id/usage — sessions and body bytes uploaded by the
+/// team's members, with the server's quotas and any limits already reached.
+pub async fn team_usage(
+    Path(team_id): Path<String>,
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    user: AuthUser,
+) -> Result<Json<TeamUsageResponse>, ApiErr> {
+    require_member(&db, &user, &team_id).await?;
+    let usage = db
+        .team_usage(&team_id)
+        .await
+        .map_err(ApiErr::from_db("team usage"))?;
+    let quotas = config.team_quotas;
+    Ok(Json(TeamUsageResponse {
+        warnings: quotas.warnings(&team_id, usage),
+        team_id,
+        session_count: usage.session_count,
+        body_bytes: usage.body_bytes,
+        sessions_quota: quotas.sessions,
+        body_bytes_quota: quotas.body_bytes,
+    }))
+}
+
+/// GET /api/teams/:team_id/retention — the team's policy, or the server
+/// default when it has none.
+pub async fn get_retention(
+    Path(team_id): Path<String>,
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    user: AuthUser,
+) -> Result<Json<TeamRetentionResponse>, ApiErr> {
+    require_member(&db, &user, &team_id).await?;
+    retention_response(&db, &config, team_id).await.map(Json)
+}
+
+/// PUT /api/teams/:team_id/retention — changes that would remove more
+/// sessions also need the admin key.
+pub async fn put_retention(
+    Path(team_id): Path<String>,
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(policy): Json<RetentionPolicy>,
+) -> Result<Json<TeamRetentionResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    require_member(&db, &user, &team_id).await?;
+    if policy.retain_days > MAX_RETAIN_DAYS {
+        return Err(ApiErr::bad_request(format!(
+            "retain_days must be at most {MAX_RETAIN_DAYS} (0 keeps sessions forever)"
+        )));
+    }
+    let current = retention_response(&db, &config, team_id.clone()).await?;
+    require_admin_key_if_stricter(&headers, &config, &current.policy, &policy)?;
+    db.put_retention_policy(&team_id, policy, &user.user_id)
+        .await
+        .map_err(ApiErr::from_db("save retention policy"))?;
+    log_retention_change(&team_id, &user, &current.policy, &policy);
+    retention_response(&db, &config, team_id).await.map(Json)
+}
+
+/// DELETE /api/teams/:team_id/retention — fall back to the server default.
+pub async fn delete_retention(
+    Path(team_id): Path<String>,
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+) -> Result<Json<TeamRetentionResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    require_member(&db, &user, &team_id).await?;
+    let current = retention_response(&db, &config, team_id.clone()).await?;
+    let fallback = config.retention_default.unwrap_or(FOREVER);
+    require_admin_key_if_stricter(&headers, &config, &current.policy, &fallback)?;
+    db.delete_retention_policy(&team_id)
+        .await
+        .map_err(ApiErr::from_db("delete retention policy"))?;
+    log_retention_change(&team_id, &user, &current.policy, &fallback);
+    retention_response(&db, &config, team_id).await.map(Json)
+}
+
+/// GET /api/teams/:team_id/retention/preview — what the next retention run
+/// would do to sessions uploaded by the team's members. Nothing is changed.
+pub async fn preview_retention(
+    Path(team_id): Path<String>,
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    user: AuthUser,
+) -> Result<Json<RetentionPreviewResponse>, ApiErr> {
+    require_member(&db, &user, &team_id).await?;
+    let TeamRetentionResponse {
+        team_id,
+        policy,
+        is_default,
+    } = retention_response(&db, &config, team_id).await?;
+    let rules = RetentionRules::load(&db, config.retention_default)
+        .await
+        .map_err(|err| {
+            tracing::error!("load retention rules: {err:#}");
+            ApiErr::internal("failed to load retention rules")
+        })?;
+
+    let mut preview = RetentionPreviewResponse {
+        team_id,
+        policy,
+        is_default,
+        sessions: Vec::new(),
+        exempt: 0,
+    };
+    let Some(days) = rules.shortest_retain_days() else {
+        return Ok(Json(preview));
+    };
+    let now = chrono::Utc::now();
+    let candidates = db
+        .list_expiry_candidates(&retention::cutoff(now, days), Some(&preview.team_id))
+        .await
+        .map_err(ApiErr::from_db("list expired sessions"))?;
+    let plan = rules.plan(candidates, now);
+    preview.exempt = plan.exempt;
+    preview.sessions = plan
+        .actions
+        .into_iter()
+        .map(|(candidate, action)| RetentionPreviewItem {
+            session_id: candidate.session_id,
+            title: candidate.title,
+            uploaded_at: candidate.uploaded_at,
+            action,
+        })
+        .collect();
+    Ok(Json(preview))
+}
+
+async fn retention_response(
+    db: &Db,
+    config: &AppConfig,
+    team_id: String,
+) -> Result<TeamRetentionResponse, ApiErr> {
+    let own = db
+        .get_retention_policy(&team_id)
+        .await
+        .map_err(ApiErr::from_db("load retention policy"))?;
+    let policy = own.or(config.retention_default).unwrap_or(FOREVER);
+    Ok(TeamRetentionResponse {
+        team_id,
+        policy,
+        is_default: own.is_none(),
+    })
+}
+
+/// Any team member may loosen a policy, but one that would remove sessions
+/// other members still keep needs the server admin.
+fn require_admin_key_if_stricter(
+    headers: &HeaderMap,
+    config: &AppConfig,
+    current: &RetentionPolicy,
+    next: &RetentionPolicy,
+) -> Result<(), ApiErr> {
+    if !next.is_stricter_than(current) {
+        return Ok(());
+    }
+    require_admin_key(headers, config).map_err(|_| {
+        ServiceError::Forbidden(
+            "this change would remove more sessions; check the retention preview, then retry with X-OpenSession-Admin-Key".into(),
+        )
+        .into()
+    })
+}
+
+fn log_retention_change(
+    team_id: &str,
+    user: &AuthUser,
+    from: &RetentionPolicy,
+    to: &RetentionPolicy,
+) {
+    let describe = |policy: &RetentionPolicy| {
+        format!(
+            "{} days, {}, exempt bookmarked={} linked={}",
+            policy.retain_days,
+            policy.action.as_str(),
+            policy.exempt_bookmarked,
+            policy.exempt_linked
+        )
+    };
+    tracing::info!(
+        "team {team_id} retention changed by {} ({}): {} -> {}",
+        user.nickname,
+        user.user_id,
+        describe(from),
+        describe(to)
+    );
+}
+
+/// GET /api/teams/:team_id/upload-policy — globs the team excludes from its
+/// members' uploads, on top of the server-wide policy.
+pub async fn get_upload_policy(
+    Path(team_id): Path<String>,
+    State(db): State<Db>,
+    user: AuthUser,
+) -> Result<Json<TeamUploadPolicyResponse>, ApiErr> {
+    require_member(&db, &user, &team_id).await?;
+    upload_policy_response(&db, team_id).await.map(Json)
+}
+
+/// PUT /api/teams/:team_id/upload-policy — changes that would let more
+/// through also need the admin key.
+pub async fn put_upload_policy(
+    Path(team_id): Path<String>,
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(policy): Json<TeamUploadPolicy>,
+) -> Result<Json<TeamUploadPolicyResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    require_member(&db, &user, &team_id).await?;
+    let policy = normalize_upload_policy(policy)?;
+    let current = upload_policy_response(&db, team_id.clone()).await?;
+    require_admin_key_if_looser(&headers, &config, &current.policy, &policy)?;
+    if policy.exclude_patterns.is_empty() {
+        db.delete_team_upload_policy(&team_id)
+            .await
+            .map_err(ApiErr::from_db("delete upload policy"))?;
+    } else {
+        db.put_team_upload_policy(&team_id, &policy, &user.user_id)
+            .await
+            .map_err(ApiErr::from_db("save upload policy"))?;
+    }
+    log_upload_policy_change(&team_id, &user, &current.policy, &policy);
+    upload_policy_response(&db, team_id).await.map(Json)
+}
+
+/// DELETE /api/teams/:team_id/upload-policy — leave only the server-wide
+/// policy. Needs the admin key when the team had patterns.
+pub async fn delete_upload_policy(
+    Path(team_id): Path<String>,
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+) -> Result<Json<TeamUploadPolicyResponse>, ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    require_member(&db, &user, &team_id).await?;
+    let current = upload_policy_response(&db, team_id.clone()).await?;
+    let none = TeamUploadPolicy::default();
+    require_admin_key_if_looser(&headers, &config, &current.policy, &none)?;
+    db.delete_team_upload_policy(&team_id)
+        .await
+        .map_err(ApiErr::from_db("delete upload policy"))?;
+    log_upload_policy_change(&team_id, &user, &current.policy, &none);
+    upload_policy_response(&db, team_id).await.map(Json)
+}
+
+async fn upload_policy_response(
+    db: &Db,
+    team_id: String,
+) -> Result<TeamUploadPolicyResponse, ApiErr> {
+    let policy = db
+        .get_team_upload_policy(&team_id)
+        .await
+        .map_err(ApiErr::from_db("load upload policy"))?
+        .unwrap_or_default();
+    Ok(TeamUploadPolicyResponse { team_id, policy })
+}
+
+/// Trim and dedupe patterns, rejecting ones the upload check could not use.
+fn normalize_upload_policy(policy: TeamUploadPolicy) -> Result<TeamUploadPolicy, ApiErr> {
+    let mut patterns: Vec<String> = Vec::new();
+    for raw in &policy.exclude_patterns {
+        let pattern = raw.trim();
+        if pattern.is_empty() || patterns.iter().any(|seen| seen == pattern) {
+            continue;
+        }
+        if pattern.chars().count() > MAX_UPLOAD_PATTERN_CHARS || pattern.contains('\n') {
+            return Err(ApiErr::bad_request(format!(
+                "exclude pattern `{pattern}` must be one line of at most {MAX_UPLOAD_PATTERN_CHARS} characters"
+            )));
+        }
+        if let Err(err) = glob::Pattern::new(pattern) {
+            return Err(ApiErr::bad_request(format!(
+                "exclude pattern `{pattern}` is not a valid glob: {err}"
+            )));
+        }
+        patterns.push(pattern.to_string());
+    }
+    if patterns.len() > MAX_UPLOAD_PATTERNS {
+        return Err(ApiErr::bad_request(format!(
+            "a team can exclude at most {MAX_UPLOAD_PATTERNS} patterns"
+        )));
+    }
+    Ok(TeamUploadPolicy {
+        exclude_patterns: patterns,
+        action: policy.action,
+    })
+}
+
+/// Any team member may tighten the policy, but one that would let more of
+/// the team's uploads through needs the server admin.
+fn require_admin_key_if_looser(
+    headers: &HeaderMap,
+    config: &AppConfig,
+    current: &TeamUploadPolicy,
+    next: &TeamUploadPolicy,
+) -> Result<(), ApiErr> {
+    if !next.is_looser_than(current) {
+        return Ok(());
+    }
+    require_admin_key(headers, config).map_err(|_| {
+        ServiceError::Forbidden(
+            "this change would let excluded content through; retry with X-OpenSession-Admin-Key"
+                .into(),
+        )
+        .into()
+    })
+}
+
+fn log_upload_policy_change(
+    team_id: &str,
+    user: &AuthUser,
+    from: &TeamUploadPolicy,
+    to: &TeamUploadPolicy,
+) {
+    let describe = |policy: &TeamUploadPolicy| {
+        format!(
+            "[{}], {}",
+            policy.exclude_patterns.join(", "),
+            policy.action.as_str()
+        )
+    };
+    tracing::info!(
+        "team {team_id} upload policy changed by {} ({}): {} -> {}",
+        user.nickname,
+        user.user_id,
+        describe(from),
+        describe(to)
+    );
+}
@@ -0,0 +1,130 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+};
+use chrono::DateTime;
+
+use opensession_api::{
+    OkResponse, TelemetryReport, TelemetrySummaryQuery, TelemetrySummaryResponse,
+};
+
+use crate::AppConfig;
+use crate::error::ApiErr;
+use crate::routes::admin::require_admin_key;
+use crate::routes::auth::{AuthUser, enforce_csrf_if_cookie_auth};
+use crate::storage::Db;
+
+const DEFAULT_SUMMARY_DAYS: u32 = 7;
+const MAX_SUMMARY_DAYS: u32 = 90;
+const MAX_VERSION_CHARS: usize = 64;
+/// Upper bound for any single counter in one report; larger values are bogus.
+const MAX_COUNTER: u64 = 1_000_000;
+
+/// POST /api/telemetry — store one daemon's anonymous usage counters.
+///
+/// Authentication keeps the endpoint to members of this server, but the
+/// stored row carries no user or device identity.
+pub async fn report_telemetry(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    user: AuthUser,
+    Json(report): Json<TelemetryReport>,
+) -> Result<(StatusCode, Json<OkResponse>), ApiErr> {
+    enforce_csrf_if_cookie_auth(&headers, &config, user.auth_via_cookie)?;
+    validate_report(&report)?;
+    db.insert_telemetry_report(&report)
+        .await
+        .map_err(ApiErr::from_db("store telemetry report"))?;
+    Ok((StatusCode::CREATED, Json(OkResponse { ok: true })))
+}
+
+/// GET /api/admin/telemetry — fleet totals for the last `days` days (admin key required).
+pub async fn telemetry_summary(
+    State(db): State<Db>,
+    State(config): State<AppConfig>,
+    headers: HeaderMap,
+    Query(query): Query<TelemetrySummaryQuery>,
+) -> Result<Json<TelemetrySummaryResponse>, ApiErr> {
+    require_admin_key(&headers, &config)?;
+
+    let days = query
+        .days
+        .unwrap_or(DEFAULT_SUMMARY_DAYS)
+        .clamp(1, MAX_SUMMARY_DAYS);
+    let versions = db
+        .telemetry_totals(days)
+        .await
+        .map_err(ApiErr::from_db("load telemetry"))?;
+    Ok(Json(TelemetrySummaryResponse::from_versions(
+        days, versions,
+    )))
+}
+
+fn validate_report(report: &TelemetryReport) -> Result<(), ApiErr> {
+    let version = report.daemon_version.trim();
+    if version.is_empty() || version.chars().count() > MAX_VERSION_CHARS {
+        return Err(ApiErr::bad_request(
+            "daemon_version must be 1-64 characters",
+        ));
+    }
+    let start = DateTime::parse_from_rfc3339(&report.period_start)
+        .map_err(|_| ApiErr::bad_request("period_start must be an RFC 3339 timestamp"))?;
+    let end = DateTime::parse_from_rfc3339(&report.period_end)
+        .map_err(|_| ApiErr::bad_request("period_end must be an RFC 3339 timestamp"))?;
+    if end < start {
+        return Err(ApiErr::bad_request("period_end is before period_start"));
+    }
+    if [
+        report.sessions_indexed,
+        report.parse_failures,
+        report.upload_errors,
+    ]
+    .iter()
+    .any(|count| *count > MAX_COUNTER)
+    {
+        return Err(ApiErr::bad_request("telemetry counter out of range"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_report;
+    use opensession_api::TelemetryReport;
+
+    #[test]
+    fn reports_need_a_version_an_ordered_period_and_sane_counters() {
+        let report = TelemetryReport {
+            daemon_version: "0.2.0".to_string(),
+            period_start: "2026-10-18T00:00:00Z".to_string(),
+            period_end: "2026-10-18T01:00:00Z".to_string(),
+            sessions_indexed: 12,
+            parse_failures: 1,
+            upload_errors: 0,
+        };
+        assert!(validate_report(&report).is_ok());
+        assert!(
+            validate_report(&TelemetryReport {
+                period_end: "2026-10-17T23:00:00Z".to_string(),
+                ..report.clone()
+            })
+            .is_err()
+        );
+        assert!(
+            validate_report(&TelemetryReport {
+                daemon_version: " ".to_string(),
+                ..report.clone()
+            })
+            .is_err()
+        );
+        assert!(
+            validate_report(&TelemetryReport {
+                sessions_indexed: u64::MAX,
+                ..report
+            })
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,10 @@
+use axum::Json;
+use opensession_api::ValidateSessionResponse;
+
+/// POST /api/validate — strict HAIL validation of a JSONL body.
+///
+/// Always answers 200; problems are reported in `errors` so producers can
+/// check their output without uploading it.
+pub async fn validate_session(body: String) -> Json<ValidateSessionResponse> {
+    Json(ValidateSessionResponse::from_jsonl(&body))
+}
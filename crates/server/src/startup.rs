@@ -11,14 +11,32 @@ use tower_http::cors::CorsLayer;
 use tower_http::services::{ServeDir, ServeFile};
 use tower_http::trace::TraceLayer;
 
+use clap::Parser;
+
+use crate::backup::{self, BackupConfig};
+use crate::body_tiering::{self, ColdStore, TieringSchedule};
+use crate::cli::{Cli, ServerCommand};
+use crate::live::LiveHub;
 use crate::storage::Db;
-use crate::{AppConfig, app_config::load_server_bootstrap, routes, storage};
+use crate::{
+    AppConfig,
+    app_config::{self, load_server_bootstrap},
+    proxy_auth, retention, routes, storage,
+};
+
+/// Request body limit for session uploads and live event streams.
+const SESSION_BODY_LIMIT: usize = 256 * 1024 * 1024;
+/// Request body limit for the remaining JSON endpoints.
+const JSON_BODY_LIMIT: usize = 2 * 1024 * 1024;
+/// GitHub caps webhook payloads at 25 MB.
+const GITHUB_WEBHOOK_BODY_LIMIT: usize = 25 * 1024 * 1024;
 
 /// Application state shared across all handlers.
 #[derive(Clone)]
 pub struct AppState {
     pub db: Db,
     pub config: AppConfig,
+    pub live: LiveHub,
 }
 
 impl FromRef<AppState> for Db {
@@ -33,34 +51,196 @@ impl FromRef<AppState> for AppConfig {
     }
 }
 
-pub async fn run() -> anyhow::Result<()> {
+impl FromRef<AppState> for LiveHub {
+    fn from_ref(state: &AppState) -> Self {
+        state.live.clone()
+    }
+}
+
+pub async fn run_process() -> anyhow::Result<()> {
+    let cli = Cli::parse();
     initialize_tracing();
 
-    let bootstrap = load_server_bootstrap();
+    match cli.command.unwrap_or(ServerCommand::Serve) {
+        ServerCommand::Serve => run().await,
+        ServerCommand::Backup => {
+            let config = BackupConfig::require_from_env()?;
+            let report = backup::run_backup(&config, &load_server_bootstrap()?.data_dir).await?;
+            println!(
+                "backup {} complete ({} bodies, {} uploaded)",
+                report.snapshot, report.bodies, report.bodies_uploaded
+            );
+            Ok(())
+        }
+        ServerCommand::Restore { snapshot, force } => {
+            let config = BackupConfig::require_from_env()?;
+            let data_dir = load_server_bootstrap()?.data_dir;
+            let manifest =
+                backup::restore_backup(&config, &data_dir, snapshot.as_deref(), force).await?;
+            println!(
+                "restored snapshot {} ({} bodies) into {}",
+                manifest.snapshot,
+                manifest.bodies.len(),
+                data_dir.display()
+            );
+            Ok(())
+        }
+        ServerCommand::Recompute { batch } => {
+            let db = open_db(&load_server_bootstrap()?.data_dir)?;
+            let (mut scanned, mut updated, mut after) = (0, 0, None);
+            loop {
+                let response = db.recompute_session_columns(after, batch.max(1)).await?;
+                scanned += response.scanned;
+                updated += response.updated;
+                for error in &response.errors {
+                    eprintln!("{}: {}", error.session_id, error.error);
+                }
+                after = response.next_after;
+                if after.is_none() {
+                    break;
+                }
+            }
+            println!("recomputed {scanned} sessions ({updated} updated)");
+            Ok(())
+        }
+        ServerCommand::VerifyBackup { snapshot } => {
+            let config = BackupConfig::require_from_env()?;
+            let manifest = backup::verify_backup(
+                &config,
+                &load_server_bootstrap()?.data_dir,
+                snapshot.as_deref(),
+            )
+            .await?;
+            println!(
+                "snapshot {} verified ({} bodies)",
+                manifest.snapshot,
+                manifest.bodies.len()
+            );
+            Ok(())
+        }
+    }
+}
+
+async fn run() -> anyhow::Result<()> {
+    let bootstrap = load_server_bootstrap()?;
     let data_dir = bootstrap.data_dir;
     let web_dir = bootstrap.web_dir;
     let port = bootstrap.port;
-    let config = bootstrap.config;
+    let mut config = bootstrap.config;
+    config
+        .oauth_providers
+        .extend(app_config::load_oidc_provider().await);
 
     tracing::info!("data directory: {}", data_dir.display());
 
-    let db = storage::init_db(&data_dir)?;
+    let db = open_db(&data_dir)?;
     tracing::info!("database initialized");
 
     warn_on_disabled_capabilities(&config);
+    spawn_scheduled_backups(&data_dir)?;
+    spawn_body_tiering(&db)?;
+    spawn_retention(&db, &config)?;
 
     let base_url = config.base_url.clone();
-    let state = AppState { db, config };
+    let state = AppState {
+        db,
+        config,
+        live: LiveHub::default(),
+    };
     let app = build_app_router(state, &web_dir);
 
     tracing::info!("starting server at {base_url}");
 
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{port}")).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
 
+/// Open the database, attaching the cold store when one is configured.
+fn open_db(data_dir: &std::path::Path) -> anyhow::Result<Db> {
+    let db = storage::init_db(data_dir)?;
+    Ok(match ColdStore::from_env()? {
+        Some(store) => {
+            tracing::info!("session body cold store: {store:?}");
+            db.with_cold_store(store)
+        }
+        None => db,
+    })
+}
+
+/// Start archiving idle bodies when `OPENSESSION_TIERING_IDLE_DAYS` is set.
+fn spawn_body_tiering(db: &Db) -> anyhow::Result<()> {
+    let Some(schedule) = TieringSchedule::from_env()? else {
+        return Ok(());
+    };
+    if !db.has_cold_store() {
+        anyhow::bail!(
+            "OPENSESSION_TIERING_IDLE_DAYS needs OPENSESSION_COLD_STORE_DIR or OPENSESSION_COLD_STORE_S3_BUCKET"
+        );
+    }
+    tracing::info!(
+        "archiving session bodies idle for {} days, checking every {}s",
+        schedule.idle_days,
+        schedule.interval.as_secs()
+    );
+    tokio::spawn(body_tiering::run_scheduled(db.clone(), schedule));
+    Ok(())
+}
+
+/// Start the retention job when `OPENSESSION_RETENTION_INTERVAL_SECS` is set.
+fn spawn_retention(db: &Db, config: &AppConfig) -> anyhow::Result<()> {
+    let Some(interval) = retention::interval_from_env()? else {
+        return Ok(());
+    };
+    tracing::info!(
+        "applying session retention every {}s (default: {})",
+        interval.as_secs(),
+        config.retention_default.map_or_else(
+            || "keep forever".to_string(),
+            |policy| format!(
+                "{} after {} days",
+                policy.action.as_str(),
+                policy.retain_days
+            )
+        )
+    );
+    tokio::spawn(retention::run_scheduled(
+        db.clone(),
+        config.retention_default,
+        interval,
+    ));
+    Ok(())
+}
+
+/// Start periodic backups when `OPENSESSION_BACKUP_INTERVAL_SECS` and a bucket are set.
+fn spawn_scheduled_backups(data_dir: &std::path::Path) -> anyhow::Result<()> {
+    let Some(interval) = std::env::var("OPENSESSION_BACKUP_INTERVAL_SECS")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+    else {
+        return Ok(());
+    };
+    let Some(config) = BackupConfig::from_env()? else {
+        tracing::warn!(
+            "OPENSESSION_BACKUP_INTERVAL_SECS is set but OPENSESSION_BACKUP_S3_BUCKET is not; backups disabled"
+        );
+        return Ok(());
+    };
+    tracing::info!("scheduled S3 backups every {interval}s");
+    tokio::spawn(backup::run_scheduled(
+        config,
+        data_dir.to_path_buf(),
+        std::time::Duration::from_secs(interval),
+    ));
+    Ok(())
+}
+
 fn initialize_tracing() {
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -97,9 +277,13 @@ fn build_app_router(state: AppState, web_dir: &std::path::Path) -> Router {
         app = app.fallback_service(ServeDir::new(web_dir).fallback(ServeFile::new(index_html)));
     }
 
-    app.layer(TraceLayer::new_for_http())
-        .layer(build_cors_layer(&state.config.allowed_origins))
-        .with_state(state)
+    app.layer(axum::middleware::from_fn_with_state(
+        state.clone(),
+        proxy_auth::strip_untrusted_identity,
+    ))
+    .layer(TraceLayer::new_for_http())
+    .layer(build_cors_layer(&state.config.allowed_origins))
+    .with_state(state)
 }
 
 fn build_api_router() -> Router<AppState> {
@@ -107,13 +291,27 @@ fn build_api_router() -> Router<AppState> {
         .route("/health", get(routes::health::health))
         .route("/capabilities", get(routes::capabilities::capabilities))
         .route("/parse/preview", post(routes::ingest::preview))
+        .route("/validate", post(routes::validate::validate_session))
+        .route("/query", post(routes::query::run_query))
+        .route("/schema/session.json", get(routes::schema::session_schema))
+        .route("/openapi.json", get(routes::schema::openapi_document))
         .route(
             "/review/local/{review_id}",
             get(routes::review::get_local_review_bundle),
         )
         .route("/auth/verify", post(routes::auth::verify))
-        .route("/auth/me", get(routes::auth::me))
+        .route(
+            "/auth/me",
+            get(routes::auth::me).delete(routes::auth::delete_me),
+        )
+        .route("/auth/export", get(routes::auth::export_account))
         .route("/auth/api-keys/issue", post(routes::auth::issue_api_key))
+        .route("/auth/devices", get(routes::auth::list_devices))
+        .route("/auth/devices/{id}", delete(routes::auth::revoke_device))
+        .route(
+            "/auth/devices/{id}/signing-key",
+            put(routes::auth::register_device_key),
+        )
         .route(
             "/auth/git-credentials",
             get(routes::auth::list_git_credentials).post(routes::auth::create_git_credential),
@@ -134,15 +332,124 @@ fn build_api_router() -> Router<AppState> {
             get(routes::oauth::callback),
         )
         .route("/auth/oauth/{provider}/link", post(routes::oauth::link))
-        .layer(DefaultBodyLimit::max(256 * 1024 * 1024))
+        .route(
+            "/teams/{team_id}/guest-tokens",
+            get(routes::guest_tokens::list_guest_tokens)
+                .post(routes::guest_tokens::create_guest_token),
+        )
+        .route(
+            "/teams/{team_id}/guest-tokens/{id}",
+            delete(routes::guest_tokens::revoke_guest_token),
+        )
+        .route("/teams/{team_id}/usage", get(routes::teams::team_usage))
+        .route(
+            "/teams/{team_id}/retention",
+            get(routes::teams::get_retention)
+                .put(routes::teams::put_retention)
+                .delete(routes::teams::delete_retention),
+        )
+        .route(
+            "/teams/{team_id}/retention/preview",
+            get(routes::teams::preview_retention),
+        )
+        .route(
+            "/teams/{team_id}/upload-policy",
+            get(routes::teams::get_upload_policy)
+                .put(routes::teams::put_upload_policy)
+                .delete(routes::teams::delete_upload_policy),
+        )
         .route("/sessions", get(routes::sessions::list_sessions))
         .route("/sessions/repos", get(routes::sessions::list_session_repos))
+        .route(
+            "/sessions/batch",
+            post(routes::sessions::upload_sessions_batch),
+        )
+        .route(
+            "/sessions/{id}/live",
+            get(routes::live::watch_live)
+                .post(routes::live::start_live)
+                .delete(routes::live::stop_live),
+        )
+        .route(
+            "/sessions/{id}/events",
+            post(routes::live::append_live_events),
+        )
+        // Everything above may carry session bodies or event streams.
+        .layer(DefaultBodyLimit::max(SESSION_BODY_LIMIT))
         .route("/sessions/{id}", get(routes::sessions::get_session))
         .route("/sessions/{id}/raw", get(routes::sessions::get_session_raw))
+        .route("/live", get(routes::live::list_live))
+        .route(
+            "/sessions/{id}/attachments",
+            post(routes::attachments::upload_attachment)
+                .layer(DefaultBodyLimit::max(opensession_api::MAX_ATTACHMENT_BYTES))
+                .get(routes::attachments::list_attachments),
+        )
+        .route(
+            "/sessions/{id}/attachments/{attachment_id}",
+            get(routes::attachments::get_attachment),
+        )
+        .route(
+            "/sessions/{id}/bookmarks",
+            get(routes::metadata::list_event_bookmarks),
+        )
+        .route(
+            "/sessions/{id}/events/{event_id}/bookmark",
+            put(routes::metadata::put_event_bookmark)
+                .delete(routes::metadata::delete_event_bookmark),
+        )
+        .route(
+            "/sync/metadata",
+            get(routes::metadata::list_metadata).post(routes::metadata::sync_metadata),
+        )
+        .route("/handoffs", post(routes::handoffs::publish_handoff))
+        .route("/handoffs/inbox", get(routes::handoffs::inbox))
+        .route(
+            "/handoffs/{id}/claim",
+            post(routes::handoffs::claim_handoff),
+        )
+        .route(
+            "/handoffs/{id}/complete",
+            post(routes::handoffs::complete_handoff),
+        )
+        .route("/playbooks", get(routes::playbooks::list_playbooks))
+        .route(
+            "/playbooks/{name}",
+            get(routes::playbooks::get_playbook)
+                .put(routes::playbooks::put_playbook)
+                .delete(routes::playbooks::delete_playbook),
+        )
+        .route(
+            "/sessions/{id}/review-requests",
+            post(routes::review_requests::create_review_request),
+        )
+        .route(
+            "/review-requests",
+            get(routes::review_requests::list_review_requests),
+        )
+        .route(
+            "/review-requests/{id}/resolve",
+            post(routes::review_requests::resolve_review_request),
+        )
+        .route("/telemetry", post(routes::telemetry::report_telemetry))
+        .route(
+            "/admin/telemetry",
+            get(routes::telemetry::telemetry_summary),
+        )
+        .route(
+            "/admin/sessions/recompute",
+            post(routes::admin::recompute_sessions),
+        )
         .route(
             "/admin/sessions/{id}",
             delete(routes::admin::delete_session),
         )
+        .route(
+            "/integrations/github/webhook",
+            post(routes::github::webhook).layer(DefaultBodyLimit::max(GITHUB_WEBHOOK_BODY_LIMIT)),
+        )
+        // Routes registered after the session-body layer take small JSON bodies.
+        .layer(DefaultBodyLimit::max(JSON_BODY_LIMIT))
 }
 
 fn build_cors_layer(allowed_origins: &[String]) -> CorsLayer {
@@ -160,10 +467,532 @@ fn build_cors_layer(allowed_origins: &[String]) -> CorsLayer {
             Method::DELETE,
             Method::OPTIONS,
         ])
-        .allow_headers([CONTENT_TYPE, AUTHORIZATION, csrf_header])
+        .allow_headers([
+            CONTENT_TYPE,
+            AUTHORIZATION,
+            csrf_header,
+            HeaderName::from_static(opensession_api::DEVICE_ID_HEADER),
+            HeaderName::from_static(opensession_api::DEVICE_NAME_HEADER),
+        ])
         .allow_credentials(true);
     if !origin_values.is_empty() {
         cors = cors.allow_origin(origin_values);
     }
     cors
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use opensession_core::{Agent, Content, Event, EventType, Session};
+    use serde_json::{Value, json};
+    use std::path::PathBuf;
+    use tower::ServiceExt;
+
+    struct TestApp {
+        router: Router,
+        db: Db,
+        data_dir: PathBuf,
+    }
+
+    impl Drop for TestApp {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.data_dir);
+        }
+    }
+
+    fn test_config() -> AppConfig {
+        AppConfig {
+            base_url: "http://localhost:3000".to_string(),
+            allowed_origins: vec!["http://localhost:3000".to_string()],
+            oauth_use_request_host: false,
+            jwt_secret: "router-test-secret".to_string(),
+            admin_key: "adminkey".to_string(),
+            oauth_providers: Vec::new(),
+            public_feed_enabled: true,
+            local_review_root: None,
+            credential_keyring: None,
+            github_app: None,
+            upload_policy: Default::default(),
+            proxy_auth: None,
+            body_signer: None,
+            team_quotas: Default::default(),
+            retention_default: None,
+        }
+    }
+
+    fn test_app(name: &str, config: AppConfig) -> TestApp {
+        let data_dir = std::env::temp_dir().join(format!(
+            "opensession-router-{name}-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&data_dir).expect("create temp data dir");
+        let db = storage::init_db(&data_dir).expect("init db");
+        let state = AppState {
+            db: db.clone(),
+            config,
+            live: LiveHub::default(),
+        };
+        let router = build_app_router(state, &data_dir.join("no-web"));
+        TestApp {
+            router,
+            db,
+            data_dir,
+        }
+    }
+
+    async fn send(app: &TestApp, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app
+            .router
+            .clone()
+            .oneshot(request)
+            .await
+            .expect("router responds");
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+        (status, body)
+    }
+
+    fn request(
+        method: &str,
+        uri: &str,
+        bearer: Option<&str>,
+        body: Option<Value>,
+    ) -> Request<Body> {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = bearer {
+            builder = builder.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+        match body {
+            Some(body) => builder
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .expect("build request")
+    }
+
+    /// Register a password user; returns `(user_id, access_token)`.
+    async fn register(app: &TestApp, nickname: &str) -> (String, String) {
+        let (status, body) = send(
+            app,
+            request(
+                "POST",
+                "/api/auth/register",
+                None,
+                Some(json!({
+                    "email": format!("{nickname}@example.com"),
+                    "password": "correct-horse-battery",
+                    "nickname": nickname,
+                })),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "register {nickname}: {body}");
+        (
+            body["user_id"].as_str().expect("user id").to_string(),
+            body["access_token"].as_str().expect("token").to_string(),
+        )
+    }
+
+    async fn upload(app: &TestApp, token: &str, session_id: &str) {
+        let agent = Agent {
+            provider: "openai".to_string(),
+            model: "gpt-5".to_string(),
+            tool: "codex".to_string(),
+            tool_version: None,
+        };
+        let mut session = Session::new(session_id.to_string(), agent);
+        session.context.title = Some(format!("title of {session_id}"));
+        session.events.push(Event {
+            event_id: "e1".to_string(),
+            timestamp: chrono::Utc::now(),
+            event_type: EventType::UserMessage,
+            task_id: None,
+            content: Content::text(format!("hello from {session_id}")),
+            duration_ms: None,
+            attributes: Default::default(),
+        });
+        session.recompute_stats();
+        let (status, body) = send(
+            app,
+            request(
+                "POST",
+                "/api/sessions/batch",
+                Some(token),
+                Some(json!({ "sessions": [{ "session": session }] })),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "upload {session_id}: {body}");
+        assert!(
+            body["results"][0]["error"].is_null(),
+            "upload {session_id}: {body}"
+        );
+    }
+
+    #[tokio::test]
+    async fn guest_tokens_open_the_sessions_they_list() {
+        let app = test_app("guest-read", test_config());
+        let (ada_id, ada) = register(&app, "ada").await;
+        let (_, bob) = register(&app, "bob").await;
+        upload(&app, &ada, "team-session").await;
+        upload(&app, &bob, "other-session").await;
+        app.db
+            .replace_user_teams(&ada_id, "okta", &["platform".to_string()])
+            .await
+            .expect("teams");
+
+        let (status, body) = send(
+            &app,
+            request(
+                "POST",
+                "/api/teams/platform/guest-tokens",
+                Some(&ada),
+                Some(json!({ "label": "dashboard" })),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "{body}");
+        let guest = body["token"].as_str().expect("guest token").to_string();
+
+        let (status, body) = send(&app, request("GET", "/api/sessions", Some(&guest), None)).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        let listed = body["sessions"]
+            .as_array()
+            .expect("sessions")
+            .iter()
+            .map(|s| s["id"].as_str().expect("id").to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(listed, ["team-session"]);
+
+        for id in &listed {
+            let (status, body) = send(
+                &app,
+                request("GET", &format!("/api/sessions/{id}"), Some(&guest), None),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK, "{body}");
+            assert_eq!(body["id"], *id);
+            let (status, _) = send(
+                &app,
+                request(
+                    "GET",
+                    &format!("/api/sessions/{id}/raw"),
+                    Some(&guest),
+                    None,
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        for uri in [
+            "/api/sessions/other-session",
+            "/api/sessions/other-session/raw",
+            "/api/sessions/other-session/attachments",
+        ] {
+            let (status, _) = send(&app, request("GET", uri, Some(&guest), None)).await;
+            assert_eq!(status, StatusCode::NOT_FOUND, "{uri}");
+        }
+
+        let (status, _) = send(
+            &app,
+            request(
+                "GET",
+                "/api/sessions/team-session",
+                Some("osg_unknown"),
+                None,
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    fn proxied(
+        method: &str,
+        uri: &str,
+        peer: &str,
+        email: Option<&str>,
+        origin: Option<&str>,
+    ) -> Request<Body> {
+        let mut request = request(method, uri, None, None);
+        let headers = request.headers_mut();
+        if let Some(email) = email {
+            headers.insert("x-forwarded-email", email.parse().expect("email header"));
+            headers.insert("x-forwarded-user", "ada".parse().expect("name header"));
+        }
+        if let Some(origin) = origin {
+            headers.insert(axum::http::header::ORIGIN, origin.parse().expect("origin"));
+        }
+        let peer: std::net::SocketAddr = format!("{peer}:40000").parse().expect("peer addr");
+        request
+            .extensions_mut()
+            .insert(axum::extract::ConnectInfo(peer));
+        request
+    }
+
+    #[tokio::test]
+    async fn proxy_identity_is_only_trusted_from_configured_peers() {
+        let mut config = test_config();
+        config.proxy_auth = Some(
+            crate::proxy_auth::ProxyAuthConfig::new(
+                "X-Forwarded-Email",
+                Some("X-Forwarded-User"),
+                "10.0.0.0/8",
+            )
+            .expect("proxy config"),
+        );
+        let app = test_app("proxy-auth", config);
+
+        let (status, body) = send(
+            &app,
+            proxied(
+                "GET",
+                "/api/auth/me",
+                "10.1.2.3",
+                Some("ada@example.com"),
+                None,
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(body["nickname"], "ada");
+        assert_eq!(body["email"], "ada@example.com");
+        let user_id = body["user_id"].clone();
+
+        let (status, body) = send(
+            &app,
+            proxied(
+                "GET",
+                "/api/auth/me",
+                "10.9.9.9",
+                Some("ada@example.com"),
+                None,
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(
+            body["user_id"], user_id,
+            "second request reuses the account"
+        );
+
+        let (status, _) = send(
+            &app,
+            proxied(
+                "GET",
+                "/api/auth/me",
+                "192.168.1.5",
+                Some("ada@example.com"),
+                None,
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "untrusted peer");
+
+        let mut no_peer = request("GET", "/api/auth/me", None, None);
+        no_peer
+            .headers_mut()
+            .insert("x-forwarded-email", "ada@example.com".parse().unwrap());
+        let (status, _) = send(&app, no_peer).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "unknown peer");
+
+        for uri in ["/api/auth/register", "/api/auth/login"] {
+            let (status, _) = send(
+                &app,
+                request(
+                    "POST",
+                    uri,
+                    None,
+                    Some(json!({
+                        "email": "bob@example.com",
+                        "password": "correct-horse-battery",
+                        "nickname": "bob",
+                    })),
+                ),
+            )
+            .await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{uri}");
+        }
+
+        // Proxy sessions skip the CSRF cookie but still need an allowed origin.
+        for (origin, expected) in [
+            (Some("http://localhost:3000"), StatusCode::OK),
+            (Some("https://evil.example"), StatusCode::UNAUTHORIZED),
+            (None, StatusCode::UNAUTHORIZED),
+        ] {
+            let (status, body) = send(
+                &app,
+                proxied(
+                    "POST",
+                    "/api/auth/api-keys/issue",
+                    "10.1.2.3",
+                    Some("ada@example.com"),
+                    origin,
+                ),
+            )
+            .await;
+            assert_eq!(status, expected, "{origin:?}: {body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn stricter_retention_needs_the_admin_key() {
+        let app = test_app("retention-gate", test_config());
+        let (ada_id, ada) = register(&app, "ada").await;
+        app.db
+            .replace_user_teams(&ada_id, "okta", &["platform".to_string()])
+            .await
+            .expect("teams");
+        let put = |days: u32, admin_key: Option<&str>| {
+            let mut request = request(
+                "PUT",
+                "/api/teams/platform/retention",
+                Some(&ada),
+                Some(json!({ "retain_days": days })),
+            );
+            if let Some(key) = admin_key {
+                request
+                    .headers_mut()
+                    .insert("x-opensession-admin-key", key.parse().expect("key"));
+            }
+            request
+        };
+
+        let (status, body) = send(&app, put(30, None)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{body}");
+        let (status, _) = send(&app, put(30, Some("wrong"))).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, body) = send(&app, put(30, Some("adminkey"))).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(body["policy"]["retain_days"], 30);
+
+        let (status, body) = send(&app, put(90, None)).await;
+        assert_eq!(status, StatusCode::OK, "longer periods need no key: {body}");
+        let (status, _) = send(&app, put(7, None)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // Dropping the policy falls back to keeping sessions forever.
+        let (status, body) = send(
+            &app,
+            request("DELETE", "/api/teams/platform/retention", Some(&ada), None),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(body["is_default"], true);
+    }
+
+    #[tokio::test]
+    async fn team_upload_policy_rejects_members_uploads_and_loosening_needs_the_admin_key() {
+        let app = test_app("team-upload-policy", test_config());
+        let (ada_id, ada) = register(&app, "ada").await;
+        app.db
+            .replace_user_teams(&ada_id, "okta", &["platform".to_string()])
+            .await
+            .expect("teams");
+        let put = |patterns: &[&str]| {
+            request(
+                "PUT",
+                "/api/teams/platform/upload-policy",
+                Some(&ada),
+                Some(json!({ "exclude_patterns": patterns })),
+            )
+        };
+
+        let (status, body) = send(&app, put(&["*.pem", " *.pem ", "[bad"])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+        let (status, body) = send(&app, put(&["*.pem", " *.pem "])).await;
+        assert_eq!(
+            status,
+            StatusCode::OK,
+            "adding patterns needs no key: {body}"
+        );
+        assert_eq!(body["policy"]["exclude_patterns"], json!(["*.pem"]));
+        assert_eq!(body["policy"]["action"], "reject");
+
+        let mut session = Session::new(
+            "leaky".to_string(),
+            Agent {
+                provider: "openai".to_string(),
+                model: "gpt-5".to_string(),
+                tool: "codex".to_string(),
+                tool_version: None,
+            },
+        );
+        session.events.push(Event {
+            event_id: "e1".to_string(),
+            timestamp: chrono::Utc::now(),
+            event_type: EventType::FileRead {
+                path: "/repo/deploy/server.pem".to_string(),
+            },
+            task_id: None,
+            content: Content::text(""),
+            duration_ms: None,
+            attributes: Default::default(),
+        });
+        session.recompute_stats();
+        let (status, body) = send(
+            &app,
+            request(
+                "POST",
+                "/api/sessions/batch",
+                Some(&ada),
+                Some(json!({ "sessions": [{ "session": session }] })),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(
+            body["results"][0]["policy_violations"][0]["path"], "events[0]",
+            "{body}"
+        );
+
+        let (status, _) = send(&app, put(&[])).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _) = send(
+            &app,
+            request(
+                "DELETE",
+                "/api/teams/platform/upload-policy",
+                Some(&ada),
+                None,
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn body_limits_follow_the_route_kind() {
+        let app = test_app("body-limits", test_config());
+        let (_, token) = register(&app, "ada").await;
+        let oversized_json = format!("{{\"pad\":\"{}\"}}", "x".repeat(JSON_BODY_LIMIT));
+
+        for (uri, expected_large_ok) in [
+            ("/api/sessions/batch", true),
+            ("/api/sessions/s1/events", true),
+            ("/api/sync/metadata", false),
+            ("/api/handoffs", false),
+        ] {
+            let request = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(AUTHORIZATION, format!("Bearer {token}"))
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(oversized_json.clone()))
+                .expect("build request");
+            let (status, _) = send(&app, request).await;
+            assert_eq!(
+                status == StatusCode::PAYLOAD_TOO_LARGE,
+                !expected_large_ok,
+                "{uri}: {status}"
+            );
+        }
+    }
+}
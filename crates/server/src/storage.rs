@@ -1,19 +1,127 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use rusqlite::Connection;
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use opensession_api::{
-    GitCredentialSummary, LinkType, SessionDetail, SessionLink, SessionListQuery,
-    SessionListResponse, SessionSummary, db, oauth,
+    AttachmentKind, DeleteAccountResponse, DeviceSummary, GitCredentialSummary, GuestTokenSummary,
+    HandoffInboxItem, HandoffStatus, LinkType, MetadataEntry, Playbook, PlaybookKind,
+    PutPlaybookRequest, RecomputeSessionError, RecomputeSessionsResponse, RetentionAction,
+    RetentionPolicy, ReviewRequestItem, ReviewRequestRole, ReviewRequestStatus, SessionAttachment,
+    SessionDetail, SessionKind, SessionLink, SessionListResponse, SessionSummary, StorageClass,
+    TeamUploadPolicy, TelemetryReport, TelemetryTotals, UploadPolicyAction, db, oauth,
+    saturating_i64, service::body_sha256,
 };
+use opensession_core::extract::extract_upload_metadata;
+use opensession_core::jsonl::{from_jsonl_str, write_jsonl};
+use opensession_core::session::is_metrics_only_session;
+
+use crate::body_tiering::ColdStore;
+use crate::retention::ExpiryCandidate;
+use crate::team_quota::TeamUsage;
+
+/// Owned column values for a session accepted through an upload route.
+#[derive(Debug, Clone)]
+pub struct UploadedSessionRow {
+    pub id: String,
+    pub user_id: String,
+    pub tool: String,
+    pub agent_provider: String,
+    pub agent_model: String,
+    pub title: String,
+    pub description: String,
+    pub tags: String,
+    pub created_at: String,
+    pub message_count: i64,
+    pub task_count: i64,
+    pub event_count: i64,
+    pub duration_seconds: i64,
+    pub total_input_tokens: i64,
+    pub total_output_tokens: i64,
+    pub body_storage_key: String,
+    pub body_size_bytes: i64,
+    pub body_url: Option<String>,
+    pub git_remote: Option<String>,
+    pub git_branch: Option<String>,
+    pub git_commit: Option<String>,
+    pub git_repo_name: Option<String>,
+    pub pr_number: Option<i64>,
+    pub pr_url: Option<String>,
+    pub working_directory: Option<String>,
+    pub files_modified: Option<String>,
+    pub files_read: Option<String>,
+    pub has_errors: bool,
+    pub max_active_agents: i64,
+    pub session_score: i64,
+    pub score_plugin: String,
+    pub content_fingerprint: Option<String>,
+    /// Machine that uploaded the session, from the device request header.
+    pub device_id: Option<String>,
+    pub body_sha256: Option<String>,
+    pub body_signature: Option<String>,
+    /// The upload carried a valid signature from `device_id`'s key.
+    pub verified_origin: bool,
+    pub kind: SessionKind,
+    pub linked_session_ids: Vec<String>,
+    /// Earlier upload with the same content; recorded as a `related` link.
+    pub duplicate_of: Option<String>,
+}
+
+impl UploadedSessionRow {
+    fn params(&self) -> db::sessions::InsertParams<'_> {
+        db::sessions::InsertParams {
+            id: &self.id,
+            user_id: &self.user_id,
+            team_id: PERSONAL_TEAM_ID,
+            tool: &self.tool,
+            agent_provider: &self.agent_provider,
+            agent_model: &self.agent_model,
+            title: &self.title,
+            description: &self.description,
+            tags: &self.tags,
+            created_at: &self.created_at,
+            message_count: self.message_count,
+            task_count: self.task_count,
+            event_count: self.event_count,
+            duration_seconds: self.duration_seconds,
+            total_input_tokens: self.total_input_tokens,
+            total_output_tokens: self.total_output_tokens,
+            body_storage_key: &self.body_storage_key,
+            body_size_bytes: self.body_size_bytes,
+            body_url: self.body_url.as_deref(),
+            git_remote: self.git_remote.as_deref(),
+            git_branch: self.git_branch.as_deref(),
+            git_commit: self.git_commit.as_deref(),
+            git_repo_name: self.git_repo_name.as_deref(),
+            pr_number: self.pr_number,
+            pr_url: self.pr_url.as_deref(),
+            working_directory: self.working_directory.as_deref(),
+            files_modified: self.files_modified.as_deref(),
+            files_read: self.files_read.as_deref(),
+            has_errors: self.has_errors,
+            max_active_agents: self.max_active_agents,
+            session_score: self.session_score,
+            score_plugin: &self.score_plugin,
+            content_fingerprint: self.content_fingerprint.as_deref(),
+            device_id: self.device_id.as_deref(),
+            body_sha256: self.body_sha256.as_deref(),
+            body_signature: self.body_signature.as_deref(),
+            verified_origin: self.verified_origin,
+            kind: self.kind,
+        }
+    }
+}
+
+/// Uploads are owned by a user; the team column is kept for schema parity only.
+const PERSONAL_TEAM_ID: &str = "personal";
 
 /// Shared database state.
 #[derive(Clone)]
 pub struct Db {
     conn: Arc<Mutex<Connection>>,
     data_dir: PathBuf,
+    cold_store: Option<Arc<ColdStore>>,
 }
 
 #[derive(Debug)]
@@ -24,6 +132,10 @@ pub enum StorageError {
 }
 
 impl StorageError {
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::Sqlite(rusqlite::Error::QueryReturnedNoRows))
+    }
+
     pub fn is_constraint_violation(&self) -> bool {
         matches!(
             self,
@@ -59,6 +171,22 @@ impl From<rusqlite::Error> for StorageError {
     }
 }
 
+/// How often [`Db::record_device_use`] refreshes a device's `last_seen_at`
+/// and an API key's `last_used_at`.
+const DEVICE_TOUCH_INTERVAL_SECS: u32 = 60;
+
+/// Outcome of [`Db::record_device_use`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceUse {
+    Recorded {
+        /// The request's API key was bound to this device before the request.
+        key_bound: bool,
+    },
+    Revoked,
+    /// The request's API key is bound to another device.
+    KeyBoundElsewhere,
+}
+
 #[derive(Debug, Clone)]
 pub struct AuthUserRecord {
     pub user_id: String,
@@ -94,12 +222,28 @@ pub struct UserSettingsData {
     pub avatar_url: Option<String>,
     pub oauth_providers: Vec<oauth::LinkedProvider>,
     pub created_at: String,
+    pub teams: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct SessionStorageInfo {
     pub body_storage_key: String,
     pub body_url: Option<String>,
+    pub body_sha256: Option<String>,
+    pub body_signature: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAttachmentRecord {
+    pub id: String,
+    pub session_id: String,
+    pub user_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+    pub kind: AttachmentKind,
+    pub recorded_at: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -128,6 +272,17 @@ pub struct NewGitCredentialRecord {
     pub header_value_enc: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct NewHandoffRecord {
+    pub id: String,
+    pub sender_id: String,
+    pub recipient_id: String,
+    pub artifact_uri: String,
+    pub canonical_jsonl: String,
+    pub markdown: Option<String>,
+    pub note: Option<String>,
+}
+
 impl Db {
     async fn with_conn<T, F>(&self, op: F) -> std::result::Result<T, StorageError>
     where
@@ -147,6 +302,16 @@ impl Db {
         self.data_dir.join("bodies")
     }
 
+    /// Read archived bodies from, and archive idle bodies to, `store`.
+    pub fn with_cold_store(mut self, store: ColdStore) -> Self {
+        self.cold_store = Some(Arc::new(store));
+        self
+    }
+
+    pub fn has_cold_store(&self) -> bool {
+        self.cold_store.is_some()
+    }
+
     /// Write a session body as HAIL JSONL to disk, return the storage key.
     pub async fn write_body(&self, session_id: &str, data: &[u8]) -> Result<String> {
         let dir = self.bodies_dir();
@@ -159,35 +324,128 @@ impl Db {
         Ok(key)
     }
 
-    /// Read a session body from disk.
+    /// Read a stored body from disk, or from the cold store when it was
+    /// archived. The body stays where it is; see [`Self::read_session_body`].
     pub async fn read_body(&self, storage_key: &str) -> Result<Vec<u8>> {
         let path = self.bodies_dir().join(storage_key);
-        tokio::fs::read(&path).await.context("reading session body")
+        match (tokio::fs::read(&path).await, &self.cold_store) {
+            (Err(err), Some(store)) if err.kind() == std::io::ErrorKind::NotFound => {
+                store.get(storage_key).await?.with_context(|| {
+                    format!("session body {storage_key} is not in hot or cold storage")
+                })
+            }
+            (result, _) => result.context("reading session body"),
+        }
+    }
+
+    /// Read a session body for download. An archived body is moved back to
+    /// hot storage first, and the access is recorded so it stays there.
+    pub async fn read_session_body(&self, session_id: &str, storage_key: &str) -> Result<Vec<u8>> {
+        let path = self.bodies_dir().join(storage_key);
+        let body = match (tokio::fs::read(&path).await, &self.cold_store) {
+            (Err(err), Some(store)) if err.kind() == std::io::ErrorKind::NotFound => {
+                let body = store.get(storage_key).await?.with_context(|| {
+                    format!("session body {storage_key} is not in hot or cold storage")
+                })?;
+                tokio::fs::create_dir_all(self.bodies_dir()).await?;
+                tokio::fs::write(&path, &body)
+                    .await
+                    .context("restoring archived session body")?;
+                self.set_storage_class(session_id, StorageClass::Hot)
+                    .await?;
+                if let Err(err) = store.delete(storage_key).await {
+                    tracing::warn!("remove cold copy of {storage_key}: {err:#}");
+                }
+                body
+            }
+            (result, _) => result.context("reading session body")?,
+        };
+        let id = session_id.to_string();
+        if let Err(err) = self
+            .with_conn(move |conn| sq_execute(conn, db::sessions::touch_body_access(&id)))
+            .await
+        {
+            tracing::warn!("record body access for {session_id}: {err}");
+        }
+        Ok(body)
+    }
+
+    async fn set_storage_class(&self, session_id: &str, class: StorageClass) -> Result<()> {
+        let id = session_id.to_string();
+        self.with_conn(move |conn| sq_execute(conn, db::sessions::set_storage_class(&id, class)))
+            .await?;
+        Ok(())
+    }
+
+    /// Move up to `limit` bodies not downloaded for `idle_days` to the cold
+    /// store and return how many moved. Bodies that fail are logged and
+    /// left in hot storage.
+    pub async fn archive_idle_bodies(&self, idle_days: u32, limit: u32) -> Result<usize> {
+        let store = self
+            .cold_store
+            .clone()
+            .context("body tiering needs a cold store")?;
+        let cutoff = (chrono::Utc::now() - chrono::Duration::days(i64::from(idle_days)))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        let idle = self
+            .with_conn(move |conn| {
+                sq_query_map(
+                    conn,
+                    db::sessions::list_idle_hot_bodies(&cutoff, limit),
+                    |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+                )
+            })
+            .await?;
+
+        let mut archived = 0;
+        for (session_id, storage_key) in idle {
+            let path = self.bodies_dir().join(&storage_key);
+            let moved = async {
+                let body = tokio::fs::read(&path)
+                    .await
+                    .with_context(|| format!("read {}", path.display()))?;
+                store.put(&storage_key, body).await?;
+                self.set_storage_class(&session_id, StorageClass::Cold)
+                    .await?;
+                tokio::fs::remove_file(&path)
+                    .await
+                    .with_context(|| format!("remove {}", path.display()))
+            }
+            .await;
+            match moved {
+                Ok(()) => archived += 1,
+                Err(err) => tracing::warn!("archive body of {session_id}: {err:#}"),
+            }
+        }
+        Ok(archived)
+    }
+
+    /// Store attachment content next to session bodies (so backups include
+    /// it) and return the storage key.
+    pub async fn write_attachment(
+        &self,
+        session_id: &str,
+        attachment_id: &str,
+        data: &[u8],
+    ) -> Result<String> {
+        let dir = self.bodies_dir();
+        tokio::fs::create_dir_all(&dir).await?;
+        let key = format!("{session_id}.{attachment_id}.attachment");
+        tokio::fs::write(dir.join(&key), data)
+            .await
+            .context("writing session attachment")?;
+        Ok(key)
     }
 
     pub async fn list_sessions(
         &self,
-        query: &SessionListQuery,
+        built: db::sessions::BuiltSessionListQuery,
     ) -> std::result::Result<SessionListResponse, StorageError> {
-        let query = SessionListQuery {
-            page: query.page,
-            per_page: query.per_page,
-            search: query.search.clone(),
-            tool: query.tool.clone(),
-            git_repo_name: query.git_repo_name.clone(),
-            sort: query.sort.clone(),
-            time_range: query.time_range.clone(),
-        };
         self.with_conn(move |conn| {
-            let built = db::sessions::list(&query);
-            let total: i64 = sq_query_row(conn, built.count_query, |row| row.get(0))?;
-            let sessions = sq_query_map(conn, built.select_query, session_from_row)?;
-            Ok(SessionListResponse {
-                sessions,
-                total,
-                page: built.page,
-                per_page: built.per_page,
-            })
+            let total: i64 = sq_query_row(conn, built.count_query.clone(), |row| row.get(0))?;
+            let sessions = sq_query_map(conn, built.select_query.clone(), session_from_row)?;
+            Ok(built.into_response(total, sessions))
         })
         .await
     }
@@ -220,9 +478,16 @@ impl Db {
                     created_at: row.get(3)?,
                 })
             })?;
+            let (body_sha256, body_signature, storage_class) =
+                sq_query_row(conn, db::sessions::get_storage_info(&id), |row| {
+                    Ok((row.get(2)?, row.get(3)?, row.get::<_, String>(4)?))
+                })?;
             Ok(SessionDetail {
                 summary,
                 linked_sessions,
+                body_sha256,
+                body_signature,
+                storage_class: StorageClass::parse(&storage_class),
             })
         })
         .await
@@ -238,6 +503,8 @@ impl Db {
                 Ok(SessionStorageInfo {
                     body_storage_key: row.get(0)?,
                     body_url: row.get(1)?,
+                    body_sha256: row.get(2)?,
+                    body_signature: row.get(3)?,
                 })
             })
         })
@@ -264,6 +531,278 @@ impl Db {
         .await
     }
 
+    /// Every session a user uploaded, oldest first.
+    pub async fn list_user_sessions(
+        &self,
+        user_id: &str,
+    ) -> std::result::Result<Vec<SessionSummary>, StorageError> {
+        let user_id = user_id.to_string();
+        self.with_conn(move |conn| {
+            sq_query_map(conn, db::sessions::list_by_user(&user_id), session_from_row)
+        })
+        .await
+    }
+
+    /// Delete a user, their sessions and attachments, then remove the stored
+    /// files. Rows are gone once this returns even if a file could not be
+    /// removed; leftovers are logged.
+    pub async fn delete_account(
+        &self,
+        user_id: &str,
+    ) -> std::result::Result<DeleteAccountResponse, StorageError> {
+        let user_id = user_id.to_string();
+        let (response, storage_keys) = self
+            .with_conn(move |conn| {
+                let sessions =
+                    sq_query_map(conn, db::sessions::list_storage_by_user(&user_id), |row| {
+                        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                    })?;
+                let attachment_keys = sq_query_map(
+                    conn,
+                    db::attachments::list_storage_keys_for_user(&user_id),
+                    |row| row.get::<_, String>(0),
+                )?;
+
+                let tx = conn.unchecked_transaction()?;
+                for (session_id, _) in &sessions {
+                    sq_execute(&tx, db::sessions::delete_links(session_id))?;
+                    let _ = sq_execute(&tx, db::sessions::delete_fts(session_id));
+                    sq_execute(&tx, db::sessions::delete(session_id))?;
+                }
+                sq_execute(&tx, db::attachments::delete_by_user(&user_id))?;
+                sq_execute(&tx, db::users::delete(&user_id))?;
+                tx.commit()?;
+
+                let response = DeleteAccountResponse {
+                    sessions_deleted: u32::try_from(sessions.len()).unwrap_or(u32::MAX),
+                    attachments_deleted: u32::try_from(attachment_keys.len()).unwrap_or(u32::MAX),
+                };
+                let storage_keys = sessions
+                    .into_iter()
+                    .map(|(_, key)| key)
+                    .chain(attachment_keys)
+                    .collect::<Vec<_>>();
+                Ok((response, storage_keys))
+            })
+            .await?;
+
+        self.remove_stored_files(storage_keys, "deleted account")
+            .await;
+        Ok(response)
+    }
+
+    pub async fn insert_attachment(
+        &self,
+        record: NewAttachmentRecord,
+    ) -> std::result::Result<SessionAttachment, StorageError> {
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::attachments::insert(
+                    &record.id,
+                    &record.session_id,
+                    Some(&record.user_id),
+                    &record.file_name,
+                    &record.content_type,
+                    record.size_bytes,
+                    &record.storage_key,
+                    record.kind,
+                    record.recorded_at.as_deref(),
+                ),
+            )?;
+            sq_query_row(
+                conn,
+                db::attachments::get(&record.session_id, &record.id),
+                attachment_from_row,
+            )
+        })
+        .await
+    }
+
+    pub async fn list_attachments(
+        &self,
+        session_id: &str,
+    ) -> std::result::Result<Vec<SessionAttachment>, StorageError> {
+        let session_id = session_id.to_string();
+        self.with_conn(move |conn| {
+            sq_query_map(
+                conn,
+                db::attachments::list_by_session(&session_id),
+                attachment_from_row,
+            )
+        })
+        .await
+    }
+
+    /// An attachment and its storage key, or `None` when the session has no such attachment.
+    pub async fn get_attachment(
+        &self,
+        session_id: &str,
+        id: &str,
+    ) -> std::result::Result<Option<(SessionAttachment, String)>, StorageError> {
+        let session_id = session_id.to_string();
+        let id = id.to_string();
+        self.with_conn(move |conn| {
+            match sq_query_row(conn, db::attachments::get(&session_id, &id), |row| {
+                Ok((attachment_from_row(row)?, row.get(8)?))
+            }) {
+                Ok(found) => Ok(Some(found)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    pub async fn list_playbooks(&self) -> std::result::Result<Vec<Playbook>, StorageError> {
+        self.with_conn(|conn| sq_query_map(conn, db::playbooks::list(), playbook_from_row))
+            .await
+    }
+
+    pub async fn get_playbook(
+        &self,
+        name: &str,
+    ) -> std::result::Result<Option<Playbook>, StorageError> {
+        let name = name.to_string();
+        self.with_conn(move |conn| {
+            match sq_query_row(conn, db::playbooks::get(&name), playbook_from_row) {
+                Ok(playbook) => Ok(Some(playbook)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    /// `None` when the playbook does not exist; `Some(None)` when its author was deleted.
+    pub async fn playbook_author(
+        &self,
+        name: &str,
+    ) -> std::result::Result<Option<Option<String>>, StorageError> {
+        let name = name.to_string();
+        self.with_conn(move |conn| {
+            match sq_query_row(conn, db::playbooks::author_id(&name), |row| row.get(0)) {
+                Ok(author) => Ok(Some(author)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    /// Create or replace a playbook and return the stored version.
+    pub async fn upsert_playbook(
+        &self,
+        name: &str,
+        author_id: &str,
+        req: PutPlaybookRequest,
+    ) -> std::result::Result<Playbook, StorageError> {
+        let name = name.to_string();
+        let author_id = author_id.to_string();
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::playbooks::upsert(
+                    &name,
+                    req.kind.as_str(),
+                    req.title.as_deref(),
+                    req.description.as_deref(),
+                    &req.body,
+                    &author_id,
+                ),
+            )?;
+            sq_query_row(conn, db::playbooks::get(&name), playbook_from_row)
+        })
+        .await
+    }
+
+    /// Delete a playbook; returns whether it existed.
+    pub async fn delete_playbook(&self, name: &str) -> std::result::Result<bool, StorageError> {
+        let name = name.to_string();
+        self.with_conn(move |conn| Ok(sq_execute(conn, db::playbooks::delete(&name))? > 0))
+            .await
+    }
+
+    /// Insert an uploaded session with its FTS entry and links in one transaction.
+    pub async fn insert_uploaded_session(
+        &self,
+        row: UploadedSessionRow,
+    ) -> std::result::Result<(), StorageError> {
+        self.with_conn(move |conn| {
+            let tx = conn.unchecked_transaction()?;
+            sq_execute(&tx, db::sessions::insert(&row.params()))?;
+            let _ = sq_execute(&tx, db::sessions::insert_fts(&row.id));
+            for linked_id in &row.linked_session_ids {
+                sq_execute(
+                    &tx,
+                    db::sessions::insert_link(&row.id, linked_id, LinkType::Handoff),
+                )?;
+            }
+            if let Some(original_id) = &row.duplicate_of {
+                sq_execute(
+                    &tx,
+                    db::sessions::insert_link(&row.id, original_id, LinkType::Related),
+                )?;
+            }
+            tx.commit()
+        })
+        .await
+    }
+
+    /// Re-derive summary columns for up to `limit` sessions after `after`
+    /// from their stored bodies. Unreadable bodies are reported per session.
+    pub async fn recompute_session_columns(
+        &self,
+        after: Option<String>,
+        limit: u32,
+    ) -> std::result::Result<RecomputeSessionsResponse, StorageError> {
+        let batch = self
+            .with_conn(move |conn| {
+                sq_query_map(
+                    conn,
+                    db::sessions::list_bodies_after(after.as_deref(), limit),
+                    |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+                )
+            })
+            .await?;
+
+        let mut response = RecomputeSessionsResponse {
+            next_after: (batch.len() == limit as usize)
+                .then(|| batch.last().map(|(id, _)| id.clone()))
+                .flatten(),
+            ..RecomputeSessionsResponse::default()
+        };
+        for (session_id, storage_key) in batch {
+            response.scanned += 1;
+            let columns = match self.read_body(&storage_key).await.and_then(|body| {
+                let mut session = from_jsonl_str(&String::from_utf8_lossy(&body))?;
+                // A metrics-only body has no events to recount; its stats are all there is.
+                if !is_metrics_only_session(&session) {
+                    session.recompute_stats();
+                }
+                Ok(db::sessions::DerivedColumns::from_session(&session))
+            }) {
+                Ok(columns) => columns,
+                Err(error) => {
+                    response.errors.push(RecomputeSessionError {
+                        session_id,
+                        error: format!("{error:#}"),
+                    });
+                    continue;
+                }
+            };
+            let changed = self
+                .with_conn(move |conn| {
+                    sq_execute(conn, db::sessions::update_derived(&session_id, &columns))
+                })
+                .await?;
+            if changed > 0 {
+                response.updated += 1;
+            }
+        }
+        Ok(response)
+    }
+
     pub async fn get_auth_user_by_api_key_hash(
         &self,
         key_hash: &str,
@@ -497,11 +1036,14 @@ impl Db {
             })
             .unwrap_or_default();
 
+            let teams = sq_query_map(conn, db::oauth::list_user_teams(&user_id), |row| row.get(0))?;
+
             Ok(UserSettingsData {
                 email,
                 avatar_url,
                 oauth_providers,
                 created_at,
+                teams,
             })
         })
         .await
@@ -511,13 +1053,15 @@ impl Db {
         &self,
         user_id: &str,
         grace_until: &str,
+        device_id: Option<&str>,
     ) -> std::result::Result<(), StorageError> {
         let user_id = user_id.to_string();
         let grace_until = grace_until.to_string();
+        let device_id = device_id.map(str::to_string);
         self.with_conn(move |conn| {
             sq_execute(
                 conn,
-                db::api_keys::move_active_to_grace(&user_id, &grace_until),
+                db::api_keys::move_active_to_grace(&user_id, &grace_until, device_id.as_deref()),
             )?;
             Ok(())
         })
@@ -530,230 +1074,320 @@ impl Db {
         user_id: &str,
         key_hash: &str,
         key_prefix: &str,
+        device_id: Option<&str>,
     ) -> std::result::Result<(), StorageError> {
         let key_id = key_id.to_string();
         let user_id = user_id.to_string();
         let key_hash = key_hash.to_string();
         let key_prefix = key_prefix.to_string();
+        let device_id = device_id.map(str::to_string);
         self.with_conn(move |conn| {
             sq_execute(
                 conn,
-                db::api_keys::insert_active(&key_id, &user_id, &key_hash, &key_prefix),
+                db::api_keys::insert_active(
+                    &key_id,
+                    &user_id,
+                    &key_hash,
+                    &key_prefix,
+                    device_id.as_deref(),
+                ),
             )?;
             Ok(())
         })
         .await
     }
 
-    pub async fn list_git_credentials(
+    /// Record a request from one of the user's devices and bind `key_hash`
+    /// (when the request used an API key) to it on first use.
+    ///
+    /// Nothing is touched if the device was revoked or the key is already
+    /// bound to a different device. `last_seen_at`/`last_used_at` are only
+    /// rewritten once per [`DEVICE_TOUCH_INTERVAL_SECS`], so ordinary requests
+    /// stay read-only.
+    pub async fn record_device_use(
         &self,
         user_id: &str,
-    ) -> std::result::Result<Vec<GitCredentialSummary>, StorageError> {
+        device_id: &str,
+        name: Option<&str>,
+        key_hash: Option<&str>,
+    ) -> std::result::Result<DeviceUse, StorageError> {
         let user_id = user_id.to_string();
+        let device_id = device_id.to_string();
+        let name = name.map(str::to_string);
+        let key_hash = key_hash.map(str::to_string);
         self.with_conn(move |conn| {
-            sq_query_map(conn, db::git_credentials::list_by_user(&user_id), |row| {
-                Ok(GitCredentialSummary {
+            let (revoked_at, device_fresh) = match sq_query_row(
+                conn,
+                db::devices::get_use_state(&user_id, &device_id, DEVICE_TOUCH_INTERVAL_SECS),
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, Option<bool>>(1)?.unwrap_or(false),
+                    ))
+                },
+            ) {
+                Ok(state) => state,
+                Err(rusqlite::Error::QueryReturnedNoRows) => (None, false),
+                Err(err) => return Err(err),
+            };
+            if revoked_at.is_some() {
+                return Ok(DeviceUse::Revoked);
+            }
+            let mut key_bound = false;
+            let mut key_fresh = true;
+            if let Some(key_hash) = &key_hash {
+                let (bound_to, used_recently) = match sq_query_row(
+                    conn,
+                    db::api_keys::get_use_state(key_hash, DEVICE_TOUCH_INTERVAL_SECS),
+                    |row| {
+                        Ok((
+                            row.get::<_, Option<String>>(0)?,
+                            row.get::<_, Option<bool>>(1)?.unwrap_or(false),
+                        ))
+                    },
+                ) {
+                    Ok(state) => state,
+                    Err(rusqlite::Error::QueryReturnedNoRows) => (None, false),
+                    Err(err) => return Err(err),
+                };
+                match bound_to {
+                    Some(bound_to) if bound_to != device_id => {
+                        return Ok(DeviceUse::KeyBoundElsewhere);
+                    }
+                    Some(_) => key_bound = true,
+                    None => {}
+                }
+                key_fresh = key_bound && used_recently;
+            }
+            if device_fresh && key_fresh {
+                return Ok(DeviceUse::Recorded { key_bound });
+            }
+
+            let tx = conn.unchecked_transaction()?;
+            sq_execute(
+                &tx,
+                db::devices::touch(&user_id, &device_id, name.as_deref()),
+            )?;
+            if let Some(key_hash) = &key_hash {
+                sq_execute(&tx, db::api_keys::record_use(key_hash, Some(&device_id)))?;
+            }
+            tx.commit()?;
+            Ok(DeviceUse::Recorded { key_bound })
+        })
+        .await
+    }
+
+    pub async fn list_devices(
+        &self,
+        user_id: &str,
+    ) -> std::result::Result<Vec<DeviceSummary>, StorageError> {
+        let user_id = user_id.to_string();
+        self.with_conn(move |conn| {
+            sq_query_map(conn, db::devices::list_by_user(&user_id), |row| {
+                Ok(DeviceSummary {
                     id: row.get(0)?,
-                    label: row.get(1)?,
-                    host: row.get(2)?,
-                    path_prefix: row.get(3)?,
-                    header_name: row.get(4)?,
-                    created_at: row.get(5)?,
-                    updated_at: row.get(6)?,
-                    last_used_at: row.get(7)?,
+                    name: row.get(1)?,
+                    first_seen_at: row.get(2)?,
+                    last_seen_at: row.get(3)?,
+                    revoked_at: row.get(4)?,
+                    session_count: row.get(5)?,
+                    current: false,
+                    signing_key: row.get(6)?,
                 })
             })
         })
         .await
     }
 
-    pub async fn insert_git_credential(
+    /// The upload signing key registered for one of a user's devices.
+    pub async fn device_public_key(
         &self,
-        record: NewGitCredentialRecord,
-    ) -> std::result::Result<(), StorageError> {
+        user_id: &str,
+        device_id: &str,
+    ) -> std::result::Result<Option<String>, StorageError> {
+        let user_id = user_id.to_string();
+        let device_id = device_id.to_string();
         self.with_conn(move |conn| {
-            sq_execute(
+            match sq_query_row(
                 conn,
-                db::git_credentials::insert(
-                    &record.id,
-                    &record.user_id,
-                    &record.label,
-                    &record.host,
-                    &record.path_prefix,
-                    &record.header_name,
-                    &record.header_value_enc,
-                ),
-            )?;
-            Ok(())
+                db::devices::get_public_key(&user_id, &device_id),
+                |row| row.get::<_, Option<String>>(0),
+            ) {
+                Ok(key) => Ok(key),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(err) => Err(err),
+            }
         })
         .await
     }
 
-    pub async fn get_git_credential_by_id_and_user(
+    /// Register a device's upload signing key. Returns `false` when the
+    /// device is unknown, revoked, or already has a key.
+    pub async fn register_device_key(
         &self,
-        id: &str,
         user_id: &str,
-    ) -> std::result::Result<GitCredentialSummary, StorageError> {
-        let id = id.to_string();
+        device_id: &str,
+        public_key: &str,
+    ) -> std::result::Result<bool, StorageError> {
         let user_id = user_id.to_string();
+        let device_id = device_id.to_string();
+        let public_key = public_key.to_string();
         self.with_conn(move |conn| {
-            sq_query_row(
+            let updated = sq_execute(
                 conn,
-                db::git_credentials::get_by_id_and_user(&id, &user_id),
-                |row| {
-                    Ok(GitCredentialSummary {
-                        id: row.get(0)?,
-                        label: row.get(1)?,
-                        host: row.get(2)?,
-                        path_prefix: row.get(3)?,
-                        header_name: row.get(4)?,
-                        created_at: row.get(5)?,
-                        updated_at: row.get(6)?,
-                        last_used_at: row.get(7)?,
-                    })
-                },
-            )
+                db::devices::set_public_key(&user_id, &device_id, &public_key),
+            )?;
+            Ok(updated == 1)
         })
         .await
     }
 
-    pub async fn delete_git_credential_by_id_and_user(
+    /// Revoke a device, every API key bound to it, and the user's keys not
+    /// yet bound to any device.
+    ///
+    /// Returns the number of keys revoked, or `None` if the user has no such device.
+    pub async fn revoke_device(
         &self,
-        id: &str,
         user_id: &str,
-    ) -> std::result::Result<usize, StorageError> {
-        let id = id.to_string();
+        device_id: &str,
+    ) -> std::result::Result<Option<usize>, StorageError> {
         let user_id = user_id.to_string();
+        let device_id = device_id.to_string();
         self.with_conn(move |conn| {
-            sq_execute(
-                conn,
-                db::git_credentials::delete_by_id_and_user(&id, &user_id),
-            )
+            let tx = conn.unchecked_transaction()?;
+            if sq_execute(&tx, db::devices::revoke(&user_id, &device_id))? == 0 {
+                return Ok(None);
+            }
+            let keys = sq_execute(&tx, db::api_keys::revoke_for_device(&user_id, &device_id))?;
+            tx.commit()?;
+            Ok(Some(keys))
         })
         .await
     }
 
-    pub async fn upsert_oauth_provider_access_token(
+    pub async fn list_user_metadata(
         &self,
-        token_id: &str,
         user_id: &str,
-        provider: &str,
-        provider_host: &str,
-        encrypted_token: &str,
-    ) -> std::result::Result<(), StorageError> {
-        let token_id = token_id.to_string();
+    ) -> std::result::Result<Vec<MetadataEntry>, StorageError> {
         let user_id = user_id.to_string();
-        let provider = provider.to_string();
-        let provider_host = provider_host.to_string();
-        let encrypted_token = encrypted_token.to_string();
         self.with_conn(move |conn| {
-            sq_execute(
+            sq_query_map(
                 conn,
-                db::oauth_provider_tokens::upsert_access_token(
-                    &token_id,
-                    &user_id,
-                    &provider,
-                    &provider_host,
-                    &encrypted_token,
-                    None,
-                ),
-            )?;
-            Ok(())
+                db::user_metadata::list_by_user(&user_id),
+                metadata_entry_from_row,
+            )
         })
         .await
     }
 
-    pub async fn insert_oauth_state(
+    /// A user's live entries whose key starts with `prefix`.
+    pub async fn list_user_metadata_with_prefix(
         &self,
-        state: &str,
-        provider: &str,
-        expires_at: &str,
-        user_id: Option<&str>,
-    ) -> std::result::Result<(), StorageError> {
-        let state = state.to_string();
-        let provider = provider.to_string();
-        let expires_at = expires_at.to_string();
-        let user_id = user_id.map(ToOwned::to_owned);
+        user_id: &str,
+        prefix: &str,
+    ) -> std::result::Result<Vec<MetadataEntry>, StorageError> {
+        let user_id = user_id.to_string();
+        let prefix = prefix.to_string();
         self.with_conn(move |conn| {
-            sq_execute(
+            sq_query_map(
                 conn,
-                db::oauth::insert_state(&state, &provider, &expires_at, user_id.as_deref()),
-            )?;
-            Ok(())
+                db::user_metadata::list_live_by_prefix(&user_id, &prefix),
+                metadata_entry_from_row,
+            )
         })
         .await
     }
 
-    pub async fn validate_oauth_state(
+    /// Write one entry unless the stored one is newer.
+    pub async fn put_user_metadata(
         &self,
-        state: &str,
-    ) -> std::result::Result<OAuthStateRecord, StorageError> {
-        let state = state.to_string();
+        user_id: &str,
+        entry: MetadataEntry,
+    ) -> std::result::Result<(), StorageError> {
+        let user_id = user_id.to_string();
         self.with_conn(move |conn| {
-            sq_query_row(conn, db::oauth::validate_state(&state), |row| {
-                Ok(OAuthStateRecord {
-                    provider: row.get(1)?,
-                    expires_at: row.get(2)?,
-                    user_id: row.get(3)?,
-                })
-            })
+            sq_execute(
+                conn,
+                db::user_metadata::upsert_if_newer(
+                    &user_id,
+                    &entry.key,
+                    entry.value.as_deref(),
+                    entry.updated_at,
+                ),
+            )
+            .map(|_| ())
         })
         .await
     }
 
-    pub async fn delete_oauth_state(&self, state: &str) -> std::result::Result<(), StorageError> {
-        let state = state.to_string();
+    /// Apply pushed entries with last-writer-wins and return the merged state.
+    pub async fn sync_user_metadata(
+        &self,
+        user_id: &str,
+        entries: Vec<MetadataEntry>,
+    ) -> std::result::Result<Vec<MetadataEntry>, StorageError> {
+        let user_id = user_id.to_string();
         self.with_conn(move |conn| {
-            sq_execute(conn, db::oauth::delete_state(&state))?;
-            Ok(())
+            let tx = conn.unchecked_transaction()?;
+            for entry in &entries {
+                sq_execute(
+                    &tx,
+                    db::user_metadata::upsert_if_newer(
+                        &user_id,
+                        &entry.key,
+                        entry.value.as_deref(),
+                        entry.updated_at,
+                    ),
+                )?;
+            }
+            let merged = sq_query_map(
+                &tx,
+                db::user_metadata::list_by_user(&user_id),
+                metadata_entry_from_row,
+            )?;
+            tx.commit()?;
+            Ok(merged)
         })
         .await
     }
 
-    pub async fn find_oauth_user_id_by_provider(
+    pub async fn list_git_credentials(
         &self,
-        provider: &str,
-        provider_user_id: &str,
-    ) -> std::result::Result<Option<String>, StorageError> {
-        let provider = provider.to_string();
-        let provider_user_id = provider_user_id.to_string();
+        user_id: &str,
+    ) -> std::result::Result<Vec<GitCredentialSummary>, StorageError> {
+        let user_id = user_id.to_string();
         self.with_conn(move |conn| {
-            match sq_query_row(
-                conn,
-                db::oauth::find_by_provider(&provider, &provider_user_id),
-                |row| row.get(0),
-            ) {
-                Ok(user_id) => Ok(Some(user_id)),
-                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                Err(err) => Err(err),
-            }
+            sq_query_map(conn, db::git_credentials::list_by_user(&user_id), |row| {
+                Ok(GitCredentialSummary {
+                    id: row.get(0)?,
+                    label: row.get(1)?,
+                    host: row.get(2)?,
+                    path_prefix: row.get(3)?,
+                    header_name: row.get(4)?,
+                    created_at: row.get(5)?,
+                    updated_at: row.get(6)?,
+                    last_used_at: row.get(7)?,
+                })
+            })
         })
         .await
     }
 
-    pub async fn upsert_oauth_identity(
+    pub async fn insert_git_credential(
         &self,
-        user_id: &str,
-        provider: &str,
-        provider_user_id: &str,
-        provider_username: Option<&str>,
-        avatar_url: Option<&str>,
+        record: NewGitCredentialRecord,
     ) -> std::result::Result<(), StorageError> {
-        let user_id = user_id.to_string();
-        let provider = provider.to_string();
-        let provider_user_id = provider_user_id.to_string();
-        let provider_username = provider_username.map(ToOwned::to_owned);
-        let avatar_url = avatar_url.map(ToOwned::to_owned);
         self.with_conn(move |conn| {
             sq_execute(
                 conn,
-                db::oauth::upsert_identity(
-                    &user_id,
-                    &provider,
-                    &provider_user_id,
-                    provider_username.as_deref(),
-                    avatar_url.as_deref(),
-                    None,
+                db::git_credentials::insert(
+                    &record.id,
+                    &record.user_id,
+                    &record.label,
+                    &record.host,
+                    &record.path_prefix,
+                    &record.header_name,
+                    &record.header_value_enc,
                 ),
             )?;
             Ok(())
@@ -761,451 +1395,2962 @@ impl Db {
         .await
     }
 
-    pub async fn get_user_nickname(
+    pub async fn get_git_credential_by_id_and_user(
         &self,
+        id: &str,
         user_id: &str,
-    ) -> std::result::Result<String, StorageError> {
+    ) -> std::result::Result<GitCredentialSummary, StorageError> {
+        let id = id.to_string();
         let user_id = user_id.to_string();
         self.with_conn(move |conn| {
-            sq_query_row(conn, db::users::get_nickname(&user_id), |row| row.get(0))
+            sq_query_row(
+                conn,
+                db::git_credentials::get_by_id_and_user(&id, &user_id),
+                |row| {
+                    Ok(GitCredentialSummary {
+                        id: row.get(0)?,
+                        label: row.get(1)?,
+                        host: row.get(2)?,
+                        path_prefix: row.get(3)?,
+                        header_name: row.get(4)?,
+                        created_at: row.get(5)?,
+                        updated_at: row.get(6)?,
+                        last_used_at: row.get(7)?,
+                    })
+                },
+            )
         })
         .await
     }
 
-    pub async fn insert_oauth_user(
+    pub async fn delete_git_credential_by_id_and_user(
         &self,
+        id: &str,
         user_id: &str,
-        nickname: &str,
-        email: Option<&str>,
-    ) -> std::result::Result<(), StorageError> {
+    ) -> std::result::Result<usize, StorageError> {
+        let id = id.to_string();
         let user_id = user_id.to_string();
-        let nickname = nickname.to_string();
-        let email = email.map(ToOwned::to_owned);
         self.with_conn(move |conn| {
             sq_execute(
                 conn,
-                db::users::insert_oauth(&user_id, &nickname, email.as_deref()),
+                db::git_credentials::delete_by_id_and_user(&id, &user_id),
+            )
+        })
+        .await
+    }
+
+    pub async fn insert_guest_token(
+        &self,
+        id: &str,
+        team_id: &str,
+        label: &str,
+        token_hash: &str,
+        created_by: &str,
+    ) -> std::result::Result<(), StorageError> {
+        let (id, team_id, label, token_hash, created_by) = (
+            id.to_string(),
+            team_id.to_string(),
+            label.to_string(),
+            token_hash.to_string(),
+            created_by.to_string(),
+        );
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::guest_tokens::insert(&id, &team_id, &label, &token_hash, &created_by),
             )?;
             Ok(())
         })
         .await
     }
 
-    pub async fn user_has_oauth_provider(
+    pub async fn list_guest_tokens(
         &self,
-        user_id: &str,
-        provider: &str,
-    ) -> std::result::Result<bool, StorageError> {
-        let user_id = user_id.to_string();
-        let provider = provider.to_string();
+        team_id: &str,
+    ) -> std::result::Result<Vec<GuestTokenSummary>, StorageError> {
+        let team_id = team_id.to_string();
         self.with_conn(move |conn| {
-            let count: i64 =
-                sq_query_row(conn, db::oauth::has_provider(&user_id, &provider), |row| {
-                    row.get(0)
-                })?;
-            Ok(count > 0)
+            sq_query_map(conn, db::guest_tokens::list_by_team(&team_id), |row| {
+                Ok(GuestTokenSummary {
+                    id: row.get(0)?,
+                    team_id: row.get(1)?,
+                    label: row.get(2)?,
+                    created_by: row.get(3)?,
+                    created_at: row.get(4)?,
+                    last_used_at: row.get(5)?,
+                })
+            })
         })
         .await
     }
 
-    pub async fn get_provider_token_enc(
+    pub async fn delete_guest_token(
         &self,
-        user_id: &str,
-        provider: &str,
-        provider_host: &str,
+        id: &str,
+        team_id: &str,
+    ) -> std::result::Result<usize, StorageError> {
+        let id = id.to_string();
+        let team_id = team_id.to_string();
+        self.with_conn(move |conn| sq_execute(conn, db::guest_tokens::delete(&id, &team_id)))
+            .await
+    }
+
+    /// The team a guest token grants access to, recording its use.
+    pub async fn resolve_guest_token(
+        &self,
+        token_hash: &str,
     ) -> std::result::Result<Option<String>, StorageError> {
-        let user_id = user_id.to_string();
-        let provider = provider.to_string();
-        let provider_host = provider_host.to_string();
+        let token_hash = token_hash.to_string();
         self.with_conn(move |conn| {
-            match sq_query_row(
-                conn,
-                db::oauth_provider_tokens::get_by_user_provider_host(
-                    &user_id,
-                    &provider,
-                    &provider_host,
-                ),
-                |row| row.get(1),
-            ) {
-                Ok(token) => Ok(Some(token)),
-                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                Err(err) => Err(err),
-            }
+            let (id, team_id): (String, String) =
+                match sq_query_row(conn, db::guest_tokens::find_by_hash(&token_hash), |row| {
+                    Ok((row.get(0)?, row.get(1)?))
+                }) {
+                    Ok(found) => found,
+                    Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+                    Err(err) => return Err(err),
+                };
+            sq_execute(conn, db::guest_tokens::touch(&id))?;
+            Ok(Some(team_id))
         })
         .await
     }
 
-    pub async fn list_git_credential_secrets_for_host(
+    pub async fn user_in_team(
         &self,
         user_id: &str,
-        host: &str,
-    ) -> std::result::Result<Vec<GitCredentialSecretRecord>, StorageError> {
+        team_id: &str,
+    ) -> std::result::Result<bool, StorageError> {
         let user_id = user_id.to_string();
-        let host = host.to_string();
+        let team_id = team_id.to_string();
         self.with_conn(move |conn| {
-            sq_query_map(
-                conn,
-                db::git_credentials::list_for_host_with_secret(&user_id, &host),
-                |row| {
-                    Ok(GitCredentialSecretRecord {
-                        credential_id: row.get(0)?,
-                        path_prefix: row.get(3)?,
-                        header_name: row.get(4)?,
-                        header_value_enc: row.get(5)?,
-                    })
-                },
-            )
+            let teams: Vec<String> =
+                sq_query_map(conn, db::oauth::list_user_teams(&user_id), |row| row.get(0))?;
+            Ok(teams.contains(&team_id))
         })
         .await
     }
 
-    pub async fn touch_git_credential_last_used(
+    /// Every team `user_id` belongs to, deduplicated across providers.
+    pub async fn user_team_ids(
         &self,
-        credential_id: &str,
         user_id: &str,
-    ) -> std::result::Result<(), StorageError> {
-        let credential_id = credential_id.to_string();
+    ) -> std::result::Result<Vec<String>, StorageError> {
         let user_id = user_id.to_string();
         self.with_conn(move |conn| {
-            sq_execute(
-                conn,
-                db::git_credentials::touch_last_used(&credential_id, &user_id),
-            )?;
-            Ok(())
+            let mut teams: Vec<String> =
+                sq_query_map(conn, db::oauth::list_user_teams(&user_id), |row| row.get(0))?;
+            teams.sort();
+            teams.dedup();
+            Ok(teams)
         })
         .await
     }
-}
 
-// ── sea-query ↔ rusqlite helpers ──────────────────────────────────────────
+    /// Sessions and body bytes uploaded by members of `team_id`.
+    pub async fn team_usage(&self, team_id: &str) -> std::result::Result<TeamUsage, StorageError> {
+        let team_id = team_id.to_string();
+        self.with_conn(move |conn| {
+            sq_query_row(conn, db::sessions::team_usage(&team_id), |row| {
+                Ok(TeamUsage {
+                    session_count: row.get::<_, i64>(0)?.max(0) as u64,
+                    body_bytes: row.get::<_, i64>(1)?.max(0) as u64,
+                })
+            })
+        })
+        .await
+    }
 
-/// Built query: `(sql, sea_query::Values)`.
-type Built = (String, sea_query::Values);
+    /// Every team's own retention policy.
+    pub async fn list_retention_policies(
+        &self,
+    ) -> std::result::Result<Vec<(String, RetentionPolicy)>, StorageError> {
+        self.with_conn(move |conn| {
+            sq_query_map(conn, db::retention::list(), |row| {
+                Ok((
+                    row.get(0)?,
+                    RetentionPolicy {
+                        retain_days: u32::try_from(row.get::<_, i64>(1)?).unwrap_or(0),
+                        action: RetentionAction::parse(&row.get::<_, String>(2)?)
+                            .unwrap_or_default(),
+                        exempt_bookmarked: row.get(3)?,
+                        exempt_linked: row.get(4)?,
+                    },
+                ))
+            })
+        })
+        .await
+    }
+
+    pub async fn get_retention_policy(
+        &self,
+        team_id: &str,
+    ) -> std::result::Result<Option<RetentionPolicy>, StorageError> {
+        let team_id = team_id.to_string();
+        Ok(self
+            .list_retention_policies()
+            .await?
+            .into_iter()
+            .find_map(|(id, policy)| (id == team_id).then_some(policy)))
+    }
+
+    pub async fn put_retention_policy(
+        &self,
+        team_id: &str,
+        policy: RetentionPolicy,
+        updated_by: &str,
+    ) -> std::result::Result<(), StorageError> {
+        let team_id = team_id.to_string();
+        let updated_by = updated_by.to_string();
+        self.with_conn(move |conn| {
+            sq_execute(conn, db::retention::upsert(&team_id, &policy, &updated_by))?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Drop a team's policy so it follows the server default again; returns
+    /// whether it had one.
+    pub async fn delete_retention_policy(
+        &self,
+        team_id: &str,
+    ) -> std::result::Result<bool, StorageError> {
+        let team_id = team_id.to_string();
+        self.with_conn(move |conn| Ok(sq_execute(conn, db::retention::delete(&team_id))? > 0))
+            .await
+    }
+
+    pub async fn get_team_upload_policy(
+        &self,
+        team_id: &str,
+    ) -> std::result::Result<Option<TeamUploadPolicy>, StorageError> {
+        let team_id = team_id.to_string();
+        self.with_conn(move |conn| {
+            match sq_query_row(
+                conn,
+                db::upload_policies::get(&team_id),
+                team_upload_policy_from_row,
+            ) {
+                Ok(policy) => Ok(Some(policy)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    /// Upload policies of every team `user_id` belongs to.
+    pub async fn team_upload_policies_for_user(
+        &self,
+        user_id: &str,
+    ) -> std::result::Result<Vec<TeamUploadPolicy>, StorageError> {
+        let user_id = user_id.to_string();
+        self.with_conn(move |conn| {
+            sq_query_map(
+                conn,
+                db::upload_policies::list_for_user(&user_id),
+                team_upload_policy_from_row,
+            )
+        })
+        .await
+    }
+
+    pub async fn put_team_upload_policy(
+        &self,
+        team_id: &str,
+        policy: &TeamUploadPolicy,
+        updated_by: &str,
+    ) -> std::result::Result<(), StorageError> {
+        let team_id = team_id.to_string();
+        let patterns = policy.exclude_patterns.join("\n");
+        let action = policy.action.as_str();
+        let updated_by = updated_by.to_string();
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::upload_policies::upsert(&team_id, &patterns, action, &updated_by),
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Drop a team's upload policy; returns whether it had one.
+    pub async fn delete_team_upload_policy(
+        &self,
+        team_id: &str,
+    ) -> std::result::Result<bool, StorageError> {
+        let team_id = team_id.to_string();
+        self.with_conn(move |conn| Ok(sq_execute(conn, db::upload_policies::delete(&team_id))? > 0))
+            .await
+    }
+
+    /// Every `(user_id, team_id)` membership.
+    pub async fn list_team_memberships(
+        &self,
+    ) -> std::result::Result<Vec<(String, String)>, StorageError> {
+        self.with_conn(move |conn| {
+            sq_query_map(conn, db::retention::list_memberships(), |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+        })
+        .await
+    }
+
+    /// Sessions uploaded before `cutoff`, optionally only by `team_id`'s members.
+    pub async fn list_expiry_candidates(
+        &self,
+        cutoff: &str,
+        team_id: Option<&str>,
+    ) -> std::result::Result<Vec<ExpiryCandidate>, StorageError> {
+        let cutoff = cutoff.to_string();
+        let team_id = team_id.map(str::to_string);
+        self.with_conn(move |conn| {
+            sq_query_map(
+                conn,
+                db::retention::list_expiry_candidates(&cutoff, team_id.as_deref()),
+                |row| {
+                    Ok(ExpiryCandidate {
+                        session_id: row.get(0)?,
+                        user_id: row.get(1)?,
+                        title: row.get(2)?,
+                        uploaded_at: row.get(3)?,
+                        bookmarked: row.get(4)?,
+                        linked: row.get(5)?,
+                        anonymized: row.get(6)?,
+                    })
+                },
+            )
+        })
+        .await
+    }
+
+    /// Delete a session with its links and attachments, then remove the
+    /// stored files. Returns whether the session existed.
+    pub async fn purge_session(&self, id: &str) -> std::result::Result<bool, StorageError> {
+        let id = id.to_string();
+        let storage_keys = self
+            .with_conn(move |conn| {
+                let body_key: String =
+                    match sq_query_row(conn, db::sessions::get_storage_info(&id), |row| row.get(0))
+                    {
+                        Ok(key) => key,
+                        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+                        Err(err) => return Err(err),
+                    };
+                let attachment_keys = sq_query_map(
+                    conn,
+                    db::attachments::list_storage_keys_for_session(&id),
+                    |row| row.get::<_, String>(0),
+                )?;
+
+                let tx = conn.unchecked_transaction()?;
+                sq_execute(&tx, db::sessions::delete_links(&id))?;
+                let _ = sq_execute(&tx, db::sessions::delete_fts(&id));
+                sq_execute(&tx, db::sessions::delete(&id))?;
+                tx.commit()?;
+                Ok(Some(
+                    std::iter::once(body_key)
+                        .chain(attachment_keys)
+                        .collect::<Vec<_>>(),
+                ))
+            })
+            .await?;
+        let Some(storage_keys) = storage_keys else {
+            return Ok(false);
+        };
+        self.remove_stored_files(storage_keys, "expired session")
+            .await;
+        Ok(true)
+    }
+
+    /// Replace a session's body with its anonymized form, keeping the
+    /// session id and stats. Attachments are deleted, and the original body
+    /// is removed from the cold store.
+    pub async fn anonymize_stored_session(&self, id: &str) -> Result<()> {
+        let info = self.get_session_storage_info(id).await?;
+        if info.body_storage_key.is_empty() {
+            bail!("session {id} has no stored body to anonymize");
+        }
+        let body = self.read_body(&info.body_storage_key).await?;
+        let session = from_jsonl_str(&String::from_utf8_lossy(&body))
+            .with_context(|| format!("parse body of {id}"))?;
+        let mut anonymized = opensession_core::anonymize::anonymize_session(&session);
+        anonymized.session_id = session.session_id.clone();
+        let mut body = Vec::new();
+        write_jsonl(&anonymized, &mut body)?;
+
+        let path = self.bodies_dir().join(&info.body_storage_key);
+        tokio::fs::create_dir_all(self.bodies_dir()).await?;
+        tokio::fs::write(&path, &body)
+            .await
+            .context("writing anonymized session body")?;
+        if let Some(store) = &self.cold_store {
+            store.delete(&info.body_storage_key).await?;
+        }
+
+        let meta = extract_upload_metadata(&anonymized);
+        let derived = db::sessions::DerivedColumns::from_session(&anonymized);
+        let sha256 = body_sha256(&body);
+        let size = body.len() as i64;
+        let session_id = id.to_string();
+        let attachment_keys = self
+            .with_conn(move |conn| {
+                let attachment_keys = sq_query_map(
+                    conn,
+                    db::attachments::list_storage_keys_for_session(&session_id),
+                    |row| row.get::<_, String>(0),
+                )?;
+                let tx = conn.unchecked_transaction()?;
+                let _ = sq_execute(&tx, db::sessions::delete_fts(&session_id));
+                sq_execute(
+                    &tx,
+                    db::sessions::set_anonymized(
+                        &session_id,
+                        &db::sessions::AnonymizedParams {
+                            title: meta.title.as_deref(),
+                            description: meta.description.as_deref(),
+                            tags: meta.tags.as_deref(),
+                            working_directory: meta.working_directory.as_deref(),
+                            files_modified: derived.files_modified.as_deref(),
+                            files_read: derived.files_read.as_deref(),
+                            body_sha256: &sha256,
+                            body_size_bytes: size,
+                        },
+                    ),
+                )?;
+                sq_execute(
+                    &tx,
+                    db::sessions::set_storage_class(&session_id, StorageClass::Hot),
+                )?;
+                sq_execute(&tx, db::attachments::delete_for_session(&session_id))?;
+                let _ = sq_execute(&tx, db::sessions::insert_fts(&session_id));
+                tx.commit()?;
+                Ok(attachment_keys)
+            })
+            .await?;
+        self.remove_stored_files(attachment_keys, "anonymized session")
+            .await;
+        Ok(())
+    }
+
+    /// Remove stored bodies and attachments from disk and the cold store,
+    /// logging (not failing on) files that could not be removed.
+    async fn remove_stored_files(&self, storage_keys: Vec<String>, owner: &str) {
+        let dir = self.bodies_dir();
+        for key in storage_keys {
+            match tokio::fs::remove_file(dir.join(&key)).await {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => tracing::warn!("remove {key} of {owner}: {err}"),
+            }
+            if let Some(store) = &self.cold_store {
+                if let Err(err) = store.delete(&key).await {
+                    tracing::warn!("remove cold {key} of {owner}: {err:#}");
+                }
+            }
+        }
+    }
+
+    pub async fn find_user_id_by_nickname(
+        &self,
+        nickname: &str,
+    ) -> std::result::Result<Option<String>, StorageError> {
+        let nickname = nickname.to_string();
+        self.with_conn(move |conn| {
+            match sq_query_row(conn, db::users::get_by_nickname(&nickname), |row| {
+                row.get(0)
+            }) {
+                Ok(user_id) => Ok(Some(user_id)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    pub async fn insert_handoff(
+        &self,
+        record: NewHandoffRecord,
+    ) -> std::result::Result<(), StorageError> {
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::handoffs::insert(
+                    &record.id,
+                    &record.sender_id,
+                    &record.recipient_id,
+                    &record.artifact_uri,
+                    &record.canonical_jsonl,
+                    record.markdown.as_deref(),
+                    record.note.as_deref(),
+                ),
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn find_handoff(
+        &self,
+        id: &str,
+    ) -> std::result::Result<Option<HandoffInboxItem>, StorageError> {
+        let id = id.to_string();
+        self.with_conn(move |conn| {
+            match sq_query_row(conn, db::handoffs::get_by_id(&id), handoff_from_row) {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    /// Claim a pending handoff; returns the number of rows moved (0 or 1).
+    pub async fn claim_handoff(
+        &self,
+        id: &str,
+        recipient_id: &str,
+    ) -> std::result::Result<usize, StorageError> {
+        let id = id.to_string();
+        let recipient_id = recipient_id.to_string();
+        self.with_conn(move |conn| sq_execute(conn, db::handoffs::claim(&id, &recipient_id)))
+            .await
+    }
+
+    /// Complete a claimed handoff; returns the number of rows moved (0 or 1).
+    pub async fn complete_handoff(
+        &self,
+        id: &str,
+        recipient_id: &str,
+        result_session_id: Option<&str>,
+    ) -> std::result::Result<usize, StorageError> {
+        let id = id.to_string();
+        let recipient_id = recipient_id.to_string();
+        let result_session_id = result_session_id.map(str::to_string);
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::handoffs::complete(&id, &recipient_id, result_session_id.as_deref()),
+            )
+        })
+        .await
+    }
+
+    /// Earliest uploaded session whose content matches `fingerprint`.
+    pub async fn find_session_by_content_fingerprint(
+        &self,
+        fingerprint: &str,
+    ) -> std::result::Result<Option<String>, StorageError> {
+        let fingerprint = fingerprint.to_string();
+        self.with_conn(move |conn| {
+            match sq_query_row(
+                conn,
+                db::sessions::find_by_content_fingerprint(&fingerprint),
+                |row| row.get(0),
+            ) {
+                Ok(id) => Ok(Some(id)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    pub async fn session_exists(&self, id: &str) -> std::result::Result<bool, StorageError> {
+        let id = id.to_string();
+        self.with_conn(move |conn| {
+            match sq_query_row(conn, db::sessions::get_storage_info(&id), |_| Ok(())) {
+                Ok(()) => Ok(true),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(false),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    pub async fn list_handoff_inbox(
+        &self,
+        recipient_id: &str,
+        status: Option<HandoffStatus>,
+        limit: u32,
+    ) -> std::result::Result<Vec<HandoffInboxItem>, StorageError> {
+        let recipient_id = recipient_id.to_string();
+        self.with_conn(move |conn| {
+            sq_query_map(
+                conn,
+                db::handoffs::list_inbox(
+                    &recipient_id,
+                    status.as_ref().map(HandoffStatus::as_str),
+                    limit,
+                ),
+                handoff_from_row,
+            )
+        })
+        .await
+    }
+
+    pub async fn insert_review_request(
+        &self,
+        id: &str,
+        session_id: &str,
+        requester_id: &str,
+        reviewer_id: &str,
+        note: Option<&str>,
+    ) -> std::result::Result<(), StorageError> {
+        let id = id.to_string();
+        let session_id = session_id.to_string();
+        let requester_id = requester_id.to_string();
+        let reviewer_id = reviewer_id.to_string();
+        let note = note.map(str::to_string);
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::review_requests::insert(
+                    &id,
+                    &session_id,
+                    &requester_id,
+                    &reviewer_id,
+                    note.as_deref(),
+                ),
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn find_review_request(
+        &self,
+        id: &str,
+    ) -> std::result::Result<Option<ReviewRequestItem>, StorageError> {
+        let id = id.to_string();
+        self.with_conn(move |conn| {
+            match sq_query_row(
+                conn,
+                db::review_requests::get_by_id(&id),
+                review_request_from_row,
+            ) {
+                Ok(item) => Ok(Some(item)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    pub async fn list_review_requests(
+        &self,
+        user_id: &str,
+        role: ReviewRequestRole,
+        status: Option<ReviewRequestStatus>,
+        limit: u32,
+    ) -> std::result::Result<Vec<ReviewRequestItem>, StorageError> {
+        let user_id = user_id.to_string();
+        self.with_conn(move |conn| {
+            let status = status.as_ref().map(ReviewRequestStatus::as_str);
+            let query = match role {
+                ReviewRequestRole::Assigned => {
+                    db::review_requests::list_assigned(&user_id, status, limit)
+                }
+                ReviewRequestRole::Requested => {
+                    db::review_requests::list_requested(&user_id, status, limit)
+                }
+            };
+            sq_query_map(conn, query, review_request_from_row)
+        })
+        .await
+    }
+
+    /// Resolve a pending review request; returns the number of rows moved (0 or 1).
+    pub async fn resolve_review_request(
+        &self,
+        id: &str,
+        reviewer_id: &str,
+        status: ReviewRequestStatus,
+        comment: Option<&str>,
+    ) -> std::result::Result<usize, StorageError> {
+        let id = id.to_string();
+        let reviewer_id = reviewer_id.to_string();
+        let comment = comment.map(str::to_string);
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::review_requests::resolve(
+                    &id,
+                    &reviewer_id,
+                    status.as_str(),
+                    comment.as_deref(),
+                ),
+            )
+        })
+        .await
+    }
+
+    pub async fn insert_telemetry_report(
+        &self,
+        report: &TelemetryReport,
+    ) -> std::result::Result<(), StorageError> {
+        let report = report.clone();
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::telemetry::insert(
+                    &report.daemon_version,
+                    &report.period_start,
+                    &report.period_end,
+                    saturating_i64(report.sessions_indexed),
+                    saturating_i64(report.parse_failures),
+                    saturating_i64(report.upload_errors),
+                ),
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Per-version telemetry totals for reports received in the last `days` days.
+    pub async fn telemetry_totals(
+        &self,
+        days: u32,
+    ) -> std::result::Result<Vec<TelemetryTotals>, StorageError> {
+        self.with_conn(move |conn| {
+            sq_query_map(conn, db::telemetry::totals_by_version(days), |row| {
+                let count = |idx: usize| -> rusqlite::Result<u64> {
+                    Ok(row.get::<_, Option<i64>>(idx)?.unwrap_or(0).max(0) as u64)
+                };
+                Ok(TelemetryTotals {
+                    daemon_version: row.get(0)?,
+                    reports: count(1)?,
+                    sessions_indexed: count(2)?,
+                    parse_failures: count(3)?,
+                    upload_errors: count(4)?,
+                })
+            })
+        })
+        .await
+    }
+
+    pub async fn upsert_oauth_provider_access_token(
+        &self,
+        token_id: &str,
+        user_id: &str,
+        provider: &str,
+        provider_host: &str,
+        encrypted_token: &str,
+    ) -> std::result::Result<(), StorageError> {
+        let token_id = token_id.to_string();
+        let user_id = user_id.to_string();
+        let provider = provider.to_string();
+        let provider_host = provider_host.to_string();
+        let encrypted_token = encrypted_token.to_string();
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::oauth_provider_tokens::upsert_access_token(
+                    &token_id,
+                    &user_id,
+                    &provider,
+                    &provider_host,
+                    &encrypted_token,
+                    None,
+                ),
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn insert_oauth_state(
+        &self,
+        state: &str,
+        provider: &str,
+        expires_at: &str,
+        user_id: Option<&str>,
+    ) -> std::result::Result<(), StorageError> {
+        let state = state.to_string();
+        let provider = provider.to_string();
+        let expires_at = expires_at.to_string();
+        let user_id = user_id.map(ToOwned::to_owned);
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::oauth::insert_state(&state, &provider, &expires_at, user_id.as_deref()),
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn validate_oauth_state(
+        &self,
+        state: &str,
+    ) -> std::result::Result<OAuthStateRecord, StorageError> {
+        let state = state.to_string();
+        self.with_conn(move |conn| {
+            sq_query_row(conn, db::oauth::validate_state(&state), |row| {
+                Ok(OAuthStateRecord {
+                    provider: row.get(1)?,
+                    expires_at: row.get(2)?,
+                    user_id: row.get(3)?,
+                })
+            })
+        })
+        .await
+    }
+
+    pub async fn delete_oauth_state(&self, state: &str) -> std::result::Result<(), StorageError> {
+        let state = state.to_string();
+        self.with_conn(move |conn| {
+            sq_execute(conn, db::oauth::delete_state(&state))?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn find_oauth_user_id_by_provider(
+        &self,
+        provider: &str,
+        provider_user_id: &str,
+    ) -> std::result::Result<Option<String>, StorageError> {
+        let provider = provider.to_string();
+        let provider_user_id = provider_user_id.to_string();
+        self.with_conn(move |conn| {
+            match sq_query_row(
+                conn,
+                db::oauth::find_by_provider(&provider, &provider_user_id),
+                |row| row.get(0),
+            ) {
+                Ok(user_id) => Ok(Some(user_id)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    pub async fn upsert_oauth_identity(
+        &self,
+        user_id: &str,
+        provider: &str,
+        provider_user_id: &str,
+        provider_username: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> std::result::Result<(), StorageError> {
+        let user_id = user_id.to_string();
+        let provider = provider.to_string();
+        let provider_user_id = provider_user_id.to_string();
+        let provider_username = provider_username.map(ToOwned::to_owned);
+        let avatar_url = avatar_url.map(ToOwned::to_owned);
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::oauth::upsert_identity(
+                    &user_id,
+                    &provider,
+                    &provider_user_id,
+                    provider_username.as_deref(),
+                    avatar_url.as_deref(),
+                    None,
+                ),
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn get_user_nickname(
+        &self,
+        user_id: &str,
+    ) -> std::result::Result<String, StorageError> {
+        let user_id = user_id.to_string();
+        self.with_conn(move |conn| {
+            sq_query_row(conn, db::users::get_nickname(&user_id), |row| row.get(0))
+        })
+        .await
+    }
+
+    pub async fn insert_oauth_user(
+        &self,
+        user_id: &str,
+        nickname: &str,
+        email: Option<&str>,
+    ) -> std::result::Result<(), StorageError> {
+        let user_id = user_id.to_string();
+        let nickname = nickname.to_string();
+        let email = email.map(ToOwned::to_owned);
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::users::insert_oauth(&user_id, &nickname, email.as_deref()),
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Replace the teams `provider` grants a user with `teams`.
+    pub async fn replace_user_teams(
+        &self,
+        user_id: &str,
+        provider: &str,
+        teams: &[String],
+    ) -> std::result::Result<(), StorageError> {
+        let user_id = user_id.to_string();
+        let provider = provider.to_string();
+        let teams = teams.to_vec();
+        self.with_conn(move |conn| {
+            let tx = conn.unchecked_transaction()?;
+            sq_execute(&tx, db::oauth::delete_user_teams(&user_id, &provider))?;
+            for team in &teams {
+                sq_execute(&tx, db::oauth::insert_user_team(&user_id, team, &provider))?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn user_has_oauth_provider(
+        &self,
+        user_id: &str,
+        provider: &str,
+    ) -> std::result::Result<bool, StorageError> {
+        let user_id = user_id.to_string();
+        let provider = provider.to_string();
+        self.with_conn(move |conn| {
+            let count: i64 =
+                sq_query_row(conn, db::oauth::has_provider(&user_id, &provider), |row| {
+                    row.get(0)
+                })?;
+            Ok(count > 0)
+        })
+        .await
+    }
+
+    pub async fn get_provider_token_enc(
+        &self,
+        user_id: &str,
+        provider: &str,
+        provider_host: &str,
+    ) -> std::result::Result<Option<String>, StorageError> {
+        let user_id = user_id.to_string();
+        let provider = provider.to_string();
+        let provider_host = provider_host.to_string();
+        self.with_conn(move |conn| {
+            match sq_query_row(
+                conn,
+                db::oauth_provider_tokens::get_by_user_provider_host(
+                    &user_id,
+                    &provider,
+                    &provider_host,
+                ),
+                |row| row.get(1),
+            ) {
+                Ok(token) => Ok(Some(token)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    pub async fn list_git_credential_secrets_for_host(
+        &self,
+        user_id: &str,
+        host: &str,
+    ) -> std::result::Result<Vec<GitCredentialSecretRecord>, StorageError> {
+        let user_id = user_id.to_string();
+        let host = host.to_string();
+        self.with_conn(move |conn| {
+            sq_query_map(
+                conn,
+                db::git_credentials::list_for_host_with_secret(&user_id, &host),
+                |row| {
+                    Ok(GitCredentialSecretRecord {
+                        credential_id: row.get(0)?,
+                        path_prefix: row.get(3)?,
+                        header_name: row.get(4)?,
+                        header_value_enc: row.get(5)?,
+                    })
+                },
+            )
+        })
+        .await
+    }
+
+    pub async fn touch_git_credential_last_used(
+        &self,
+        credential_id: &str,
+        user_id: &str,
+    ) -> std::result::Result<(), StorageError> {
+        let credential_id = credential_id.to_string();
+        let user_id = user_id.to_string();
+        self.with_conn(move |conn| {
+            sq_execute(
+                conn,
+                db::git_credentials::touch_last_used(&credential_id, &user_id),
+            )?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+// ── sea-query ↔ rusqlite helpers ──────────────────────────────────────────
+
+/// Built query: `(sql, sea_query::Values)`.
+type Built = (String, sea_query::Values);
+
+fn sq_params(values: &sea_query::Values) -> Vec<Box<dyn rusqlite::types::ToSql>> {
+    values
+        .0
+        .iter()
+        .map(|v| -> Box<dyn rusqlite::types::ToSql> {
+            match v {
+                sea_query::Value::Bool(Some(b)) => Box::new(*b),
+                sea_query::Value::TinyInt(Some(i)) => Box::new(*i as i32),
+                sea_query::Value::SmallInt(Some(i)) => Box::new(*i as i32),
+                sea_query::Value::Int(Some(i)) => Box::new(*i),
+                sea_query::Value::BigInt(Some(i)) => Box::new(*i),
+                sea_query::Value::TinyUnsigned(Some(u)) => Box::new(*u as i64),
+                sea_query::Value::SmallUnsigned(Some(u)) => Box::new(*u as i64),
+                sea_query::Value::Unsigned(Some(u)) => Box::new(*u as i64),
+                sea_query::Value::BigUnsigned(Some(u)) => Box::new(*u as i64),
+                sea_query::Value::Float(Some(f)) => Box::new(*f as f64),
+                sea_query::Value::Double(Some(f)) => Box::new(*f),
+                sea_query::Value::String(Some(s)) => Box::new(s.as_ref().clone()),
+                sea_query::Value::Bytes(Some(b)) => Box::new(b.as_ref().clone()),
+                _ => Box::new(rusqlite::types::Null),
+            }
+        })
+        .collect()
+}
+
+fn sq_execute(conn: &Connection, (sql, values): Built) -> rusqlite::Result<usize> {
+    let params = sq_params(&values);
+    let refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    conn.execute(&sql, refs.as_slice())
+}
+
+fn sq_query_row<T>(
+    conn: &Connection,
+    (sql, values): Built,
+    f: impl FnOnce(&rusqlite::Row<'_>) -> rusqlite::Result<T>,
+) -> rusqlite::Result<T> {
+    let params = sq_params(&values);
+    let refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    conn.query_row(&sql, refs.as_slice(), f)
+}
+
+fn sq_query_map<T>(
+    conn: &Connection,
+    (sql, values): Built,
+    f: impl FnMut(&rusqlite::Row<'_>) -> rusqlite::Result<T>,
+) -> rusqlite::Result<Vec<T>> {
+    let params = sq_params(&values);
+    let refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(refs.as_slice(), f)?;
+    rows.collect()
+}
+
+fn handoff_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<HandoffInboxItem> {
+    Ok(HandoffInboxItem {
+        id: row.get(0)?,
+        sender_nickname: row.get(1)?,
+        recipient_nickname: row.get(2)?,
+        artifact_uri: row.get(3)?,
+        canonical_jsonl: row.get(4)?,
+        markdown: row.get(5)?,
+        note: row.get(6)?,
+        status: HandoffStatus::parse(&row.get::<_, String>(7)?).unwrap_or_default(),
+        claimed_at: row.get(8)?,
+        completed_at: row.get(9)?,
+        result_session_id: row.get(10)?,
+        created_at: row.get(11)?,
+    })
+}
+
+fn review_request_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ReviewRequestItem> {
+    Ok(ReviewRequestItem {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        session_title: row.get(2)?,
+        requester_nickname: row.get(3)?,
+        reviewer_nickname: row.get(4)?,
+        note: row.get(5)?,
+        status: ReviewRequestStatus::parse(&row.get::<_, String>(6)?).unwrap_or_default(),
+        comment: row.get(7)?,
+        resolved_at: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+fn attachment_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionAttachment> {
+    Ok(SessionAttachment {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        file_name: row.get(2)?,
+        content_type: row.get(3)?,
+        size_bytes: row.get(4)?,
+        created_at: row.get(5)?,
+        kind: AttachmentKind::parse(&row.get::<_, String>(6)?),
+        recorded_at: row.get(7)?,
+    })
+}
+
+fn playbook_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Playbook> {
+    Ok(Playbook {
+        name: row.get(0)?,
+        kind: PlaybookKind::parse(&row.get::<_, String>(1)?).unwrap_or_default(),
+        title: row.get(2)?,
+        description: row.get(3)?,
+        body: row.get(4)?,
+        author_nickname: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+fn session_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionSummary> {
+    Ok(SessionSummary {
+        id: row.get(0)?,
+        user_id: row.get(1)?,
+        nickname: row.get(2)?,
+        tool: row.get(3)?,
+        agent_provider: row.get(4)?,
+        agent_model: row.get(5)?,
+        title: row.get(6)?,
+        description: row.get(7)?,
+        tags: row.get(8)?,
+        created_at: row.get(9)?,
+        uploaded_at: row.get(10)?,
+        message_count: row.get(11)?,
+        task_count: row.get(12)?,
+        event_count: row.get(13)?,
+        duration_seconds: row.get(14)?,
+        total_input_tokens: row.get(15)?,
+        total_output_tokens: row.get(16)?,
+        git_remote: row.get(17)?,
+        git_branch: row.get(18)?,
+        git_commit: row.get(19)?,
+        git_repo_name: row.get(20)?,
+        pr_number: row.get(21)?,
+        pr_url: row.get(22)?,
+        working_directory: row.get(23)?,
+        files_modified: row.get(24)?,
+        files_read: row.get(25)?,
+        has_errors: row.get::<_, i64>(26).unwrap_or(0) != 0,
+        max_active_agents: row.get(27).unwrap_or(1),
+        session_score: row.get(28).unwrap_or(0),
+        score_plugin: row
+            .get::<_, String>(29)
+            .unwrap_or_else(|_| opensession_core::scoring::DEFAULT_SCORE_PLUGIN.to_string()),
+        device_id: row.get(30)?,
+        verified_origin: row.get::<_, i64>(31).unwrap_or(0) != 0,
+        kind: SessionKind::parse(&row.get::<_, String>(32).unwrap_or_default()),
+    })
+}
+
+// ── Database init ─────────────────────────────────────────────────────────
+
+/// Initialize the database: open connection, enable WAL, run migrations.
+pub fn init_db(data_dir: &Path) -> Result<Db> {
+    std::fs::create_dir_all(data_dir)?;
+    let db_path = data_dir.join("opensession.db");
+    let conn = Connection::open(&db_path).context("opening SQLite database")?;
+
+    conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+    conn.execute_batch("PRAGMA foreign_keys=ON;")?;
+
+    run_migrations(&conn, &data_dir.join("bodies"))?;
+
+    Ok(Db {
+        conn: Arc::new(Mutex::new(conn)),
+        data_dir: data_dir.to_path_buf(),
+        cold_store: None,
+    })
+}
+
+fn run_migrations(conn: &Connection, bodies_dir: &Path) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS _migrations (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE,
+            applied_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );",
+    )?;
+
+    for (name, sql) in db::migrations::MIGRATIONS {
+        let already_applied: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM _migrations WHERE name = ?1",
+                [name],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+
+        if !already_applied {
+            conn.execute_batch(sql)
+                .with_context(|| format!("running migration {name}"))?;
+            conn.execute("INSERT INTO _migrations (name) VALUES (?1)", [name])?;
+            tracing::info!("Applied migration: {name}");
+        }
+    }
+
+    if !table_has_column(conn, "sessions", "content_fingerprint")? {
+        conn.execute_batch(
+            "ALTER TABLE sessions ADD COLUMN content_fingerprint TEXT;
+CREATE INDEX IF NOT EXISTS idx_sessions_content_fingerprint ON sessions(content_fingerprint);",
+        )
+        .context("add sessions.content_fingerprint column")?;
+    }
+
+    if !table_has_column(conn, "sessions", "device_id")? {
+        conn.execute_batch("ALTER TABLE sessions ADD COLUMN device_id TEXT;")
+            .context("add sessions.device_id column")?;
+    }
+
+    if !table_has_column(conn, "sessions", "body_sha256")? {
+        conn.execute_batch(
+            "ALTER TABLE sessions ADD COLUMN body_sha256 TEXT;
+ALTER TABLE sessions ADD COLUMN body_signature TEXT;",
+        )
+        .context("add sessions.body_sha256 columns")?;
+    }
+
+    if !table_has_column(conn, "sessions", "verified_origin")? {
+        conn.execute_batch(
+            "ALTER TABLE sessions ADD COLUMN verified_origin BOOLEAN NOT NULL DEFAULT 0;",
+        )
+        .context("add sessions.verified_origin column")?;
+    }
+
+    if !table_has_column(conn, "sessions", "storage_class")? {
+        conn.execute_batch(
+            "ALTER TABLE sessions ADD COLUMN storage_class TEXT NOT NULL DEFAULT 'hot';
+ALTER TABLE sessions ADD COLUMN body_accessed_at TEXT;
+CREATE INDEX IF NOT EXISTS idx_sessions_storage_class ON sessions(storage_class, body_accessed_at);",
+        )
+        .context("add sessions.storage_class columns")?;
+    }
+
+    if !table_has_column(conn, "sessions", "body_size_bytes")? {
+        conn.execute_batch(
+            "ALTER TABLE sessions ADD COLUMN body_size_bytes INTEGER NOT NULL DEFAULT 0;",
+        )
+        .context("add sessions.body_size_bytes column")?;
+        backfill_body_sizes(conn, bodies_dir)?;
+    }
+
+    if !table_has_column(conn, "sessions", "anonymized_at")? {
+        conn.execute_batch("ALTER TABLE sessions ADD COLUMN anonymized_at TEXT;")
+            .context("add sessions.anonymized_at column")?;
+    }
+
+    if !table_has_column(conn, "sessions", "session_kind")? {
+        conn.execute_batch(
+            "ALTER TABLE sessions ADD COLUMN session_kind TEXT NOT NULL DEFAULT 'full';",
+        )
+        .context("add sessions.session_kind column")?;
+    }
+
+    if !table_has_column(conn, "api_keys", "device_id")? {
+        conn.execute_batch("ALTER TABLE api_keys ADD COLUMN device_id TEXT;")
+            .context("add api_keys.device_id column")?;
+    }
+
+    if !oauth_provider_tokens_has_provider_host(conn)? {
+        tracing::warn!(
+            "rebuilding oauth_provider_tokens table for provider_host security upgrade (stored provider tokens will be removed)"
+        );
+        rebuild_oauth_provider_tokens_table(conn)?;
+    }
+
+    conn.execute_batch(
+        r#"
+CREATE TABLE IF NOT EXISTS git_credentials (
+    id               TEXT PRIMARY KEY,
+    user_id          TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    label            TEXT NOT NULL,
+    host             TEXT NOT NULL,
+    path_prefix      TEXT NOT NULL DEFAULT '',
+    header_name      TEXT NOT NULL,
+    header_value_enc TEXT NOT NULL,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now')),
+    last_used_at     TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_git_credentials_user_host ON git_credentials(user_id, host);
+CREATE INDEX IF NOT EXISTS idx_git_credentials_user_host_prefix
+ON git_credentials(user_id, host, path_prefix);
+
+CREATE TABLE IF NOT EXISTS oauth_provider_tokens (
+    id               TEXT PRIMARY KEY,
+    user_id          TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider         TEXT NOT NULL,
+    provider_host    TEXT NOT NULL,
+    access_token_enc TEXT NOT NULL,
+    expires_at       TEXT,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, provider, provider_host)
+);
+DROP INDEX IF EXISTS idx_oauth_provider_tokens_user_provider;
+CREATE INDEX IF NOT EXISTS idx_oauth_provider_tokens_user_provider_host
+ON oauth_provider_tokens(user_id, provider, provider_host);
+
+CREATE TABLE IF NOT EXISTS handoffs (
+    id                TEXT PRIMARY KEY,
+    sender_id         TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    recipient_id      TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    artifact_uri      TEXT NOT NULL,
+    canonical_jsonl   TEXT NOT NULL,
+    markdown          TEXT,
+    note              TEXT,
+    status            TEXT NOT NULL DEFAULT 'pending',
+    claimed_at        TEXT,
+    completed_at      TEXT,
+    result_session_id TEXT,
+    created_at        TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_handoffs_recipient_created ON handoffs(recipient_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_handoffs_recipient_status ON handoffs(recipient_id, status);
+
+CREATE TABLE IF NOT EXISTS devices (
+    user_id       TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    id            TEXT NOT NULL,
+    name          TEXT,
+    first_seen_at TEXT NOT NULL DEFAULT (datetime('now')),
+    last_seen_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    revoked_at    TEXT,
+    public_key    TEXT,
+    PRIMARY KEY (user_id, id)
+);
+
+CREATE TABLE IF NOT EXISTS user_metadata (
+    user_id    TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    key        TEXT NOT NULL,
+    value      TEXT,
+    updated_at INTEGER NOT NULL,
+    PRIMARY KEY (user_id, key)
+);
+
+CREATE TABLE IF NOT EXISTS session_attachments (
+    id           TEXT PRIMARY KEY,
+    session_id   TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    user_id      TEXT,
+    file_name    TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes   INTEGER NOT NULL,
+    storage_key  TEXT NOT NULL,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_session_attachments_session
+    ON session_attachments(session_id, created_at);
+
+CREATE TABLE IF NOT EXISTS playbooks (
+    name        TEXT PRIMARY KEY,
+    kind        TEXT NOT NULL DEFAULT 'prompt',
+    title       TEXT,
+    description TEXT,
+    body        TEXT NOT NULL,
+    author_id   TEXT REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS review_requests (
+    id           TEXT PRIMARY KEY,
+    session_id   TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    requester_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reviewer_id  TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    note         TEXT,
+    status       TEXT NOT NULL DEFAULT 'pending',
+    comment      TEXT,
+    resolved_at  TEXT,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_review_requests_reviewer ON review_requests(reviewer_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_review_requests_requester ON review_requests(requester_id, created_at);
+
+CREATE TABLE IF NOT EXISTS telemetry_reports (
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    daemon_version   TEXT NOT NULL,
+    period_start     TEXT NOT NULL,
+    period_end       TEXT NOT NULL,
+    sessions_indexed INTEGER NOT NULL DEFAULT 0,
+    parse_failures   INTEGER NOT NULL DEFAULT 0,
+    upload_errors    INTEGER NOT NULL DEFAULT 0,
+    received_at      TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_telemetry_reports_received ON telemetry_reports(received_at);
+
+CREATE TABLE IF NOT EXISTS user_teams (
+    user_id    TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    team_id    TEXT NOT NULL,
+    provider   TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (user_id, team_id, provider)
+);
+
+CREATE TABLE IF NOT EXISTS guest_tokens (
+    id           TEXT PRIMARY KEY,
+    team_id      TEXT NOT NULL,
+    label        TEXT NOT NULL,
+    token_hash   TEXT NOT NULL UNIQUE,
+    created_by   TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now')),
+    last_used_at TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_guest_tokens_team ON guest_tokens(team_id);
+
+CREATE TABLE IF NOT EXISTS team_retention_policies (
+    team_id           TEXT PRIMARY KEY,
+    retain_days       INTEGER NOT NULL,
+    action            TEXT NOT NULL DEFAULT 'delete',
+    exempt_bookmarked BOOLEAN NOT NULL DEFAULT 1,
+    exempt_linked     BOOLEAN NOT NULL DEFAULT 1,
+    updated_by        TEXT,
+    updated_at        TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS team_upload_policies (
+    team_id          TEXT PRIMARY KEY,
+    exclude_patterns TEXT NOT NULL,
+    action           TEXT NOT NULL DEFAULT 'reject',
+    updated_by       TEXT,
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now'))
+);
+"#,
+    )?;
+
+    if !table_has_column(conn, "devices", "public_key")? {
+        conn.execute_batch("ALTER TABLE devices ADD COLUMN public_key TEXT;")
+            .context("add devices.public_key column")?;
+    }
+
+    if !table_has_column(conn, "session_attachments", "kind")? {
+        conn.execute_batch(
+            "ALTER TABLE session_attachments ADD COLUMN kind TEXT NOT NULL DEFAULT 'file';
+ALTER TABLE session_attachments ADD COLUMN recorded_at TEXT;",
+        )
+        .context("add session_attachments.kind column")?;
+    }
+
+    Ok(())
+}
+
+fn team_upload_policy_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<TeamUploadPolicy> {
+    Ok(TeamUploadPolicy {
+        exclude_patterns: row
+            .get::<_, String>(0)?
+            .lines()
+            .map(str::to_string)
+            .collect(),
+        action: UploadPolicyAction::parse(&row.get::<_, String>(1)?).unwrap_or_default(),
+    })
+}
+
+fn metadata_entry_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<MetadataEntry> {
+    Ok(MetadataEntry {
+        key: row.get(0)?,
+        value: row.get(1)?,
+        updated_at: row.get(2)?,
+    })
+}
+
+/// Record the size of every body already on disk, so team usage counts
+/// sessions uploaded before sizes were stored. Archived bodies count as 0
+/// until they are uploaded again.
+fn backfill_body_sizes(conn: &Connection, bodies_dir: &Path) -> Result<()> {
+    let keys: Vec<(String, String)> = {
+        let mut stmt = conn
+            .prepare("SELECT id, body_storage_key FROM sessions WHERE body_storage_key != ''")
+            .context("prepare body size backfill")?;
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?
+    };
+    let mut filled = 0usize;
+    for (id, key) in keys {
+        let Ok(meta) = std::fs::metadata(bodies_dir.join(&key)) else {
+            continue;
+        };
+        conn.execute(
+            "UPDATE sessions SET body_size_bytes = ?1 WHERE id = ?2",
+            rusqlite::params![saturating_i64(meta.len()), id],
+        )?;
+        filled += 1;
+    }
+    if filled > 0 {
+        tracing::info!("recorded body sizes for {filled} existing session(s)");
+    }
+    Ok(())
+}
+
+fn table_has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({table})"))
+        .with_context(|| format!("prepare {table} schema inspection"))?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .with_context(|| format!("query {table} schema inspection"))?;
+    for row in rows {
+        if row.unwrap_or_default() == column {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn oauth_provider_tokens_has_provider_host(conn: &Connection) -> Result<bool> {
+    let mut stmt = conn
+        .prepare("PRAGMA table_info(oauth_provider_tokens)")
+        .context("prepare oauth_provider_tokens schema inspection")?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .context("query oauth_provider_tokens schema inspection")?;
+    for row in rows {
+        if row.unwrap_or_default() == "provider_host" {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn rebuild_oauth_provider_tokens_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+DROP TABLE IF EXISTS oauth_provider_tokens;
+CREATE TABLE oauth_provider_tokens (
+    id               TEXT PRIMARY KEY,
+    user_id          TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider         TEXT NOT NULL,
+    provider_host    TEXT NOT NULL,
+    access_token_enc TEXT NOT NULL,
+    expires_at       TEXT,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE (user_id, provider, provider_host)
+);
+DROP INDEX IF EXISTS idx_oauth_provider_tokens_user_provider;
+CREATE INDEX IF NOT EXISTS idx_oauth_provider_tokens_user_provider_host
+ON oauth_provider_tokens(user_id, provider, provider_host);
+"#,
+    )
+    .context("rebuild oauth_provider_tokens table")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opensession_api::SessionListQuery;
+
+    fn test_data_dir(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "opensession-server-{name}-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&path).expect("create temp data dir");
+        path
+    }
+
+    fn cleanup_dir(path: &Path) {
+        std::fs::remove_dir_all(path).expect("remove temp data dir");
+    }
+
+    fn insert_test_user(db: &Db, user_id: &str, nickname: &str) {
+        let conn = db.conn.lock().expect("db conn");
+        sq_execute(
+            &conn,
+            db::users::insert_oauth(user_id, nickname, Some(&format!("{nickname}@example.com"))),
+        )
+        .expect("insert test user");
+    }
+
+    fn insert_test_session(db: &Db, session_id: &str, user_id: &str, storage_key: &str) {
+        let conn = db.conn.lock().expect("db conn");
+        let params = db::sessions::InsertParams {
+            id: session_id,
+            user_id,
+            team_id: "team-1",
+            tool: "codex",
+            agent_provider: "openai",
+            agent_model: "gpt-5",
+            title: "Test Session",
+            description: "Description",
+            tags: "test",
+            created_at: "2026-03-09 12:00:00",
+            message_count: 1,
+            task_count: 1,
+            event_count: 1,
+            duration_seconds: 1,
+            total_input_tokens: 1,
+            total_output_tokens: 1,
+            body_storage_key: storage_key,
+            body_size_bytes: 128,
+            body_url: None,
+            git_remote: Some("https://github.com/hwisu/opensession"),
+            git_branch: Some("main"),
+            git_commit: Some("abc123"),
+            git_repo_name: Some("opensession"),
+            pr_number: None,
+            pr_url: None,
+            working_directory: Some("/tmp"),
+            files_modified: Some("[\"src/lib.rs\"]"),
+            files_read: Some("[\"src/main.rs\"]"),
+            has_errors: false,
+            max_active_agents: 1,
+            session_score: 42,
+            score_plugin: "default",
+            content_fingerprint: None,
+            device_id: None,
+            body_sha256: None,
+            body_signature: None,
+            verified_origin: false,
+            kind: SessionKind::Full,
+        };
+        sq_execute(&conn, db::sessions::insert(&params)).expect("insert test session");
+    }
+
+    #[tokio::test]
+    async fn recompute_rebuilds_stale_derived_columns() {
+        use opensession_core::{Agent, Content, Event, EventType, Session};
+
+        let data_dir = test_data_dir("recompute-columns");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "tester");
+        insert_test_session(&db, "session-1", "user-1", "session-1.hail.jsonl");
+        insert_test_session(&db, "session-2", "user-1", "missing.hail.jsonl");
+
+        let mut session = Session::new(
+            "session-1".to_string(),
+            Agent {
+                provider: "openai".to_string(),
+                model: "gpt-5".to_string(),
+                tool: "codex".to_string(),
+                tool_version: None,
+            },
+        );
+        for (idx, event_type) in [
+            EventType::FileEdit {
+                path: "src/app.rs".to_string(),
+                diff: None,
+            },
+            EventType::ShellCommand {
+                command: "cargo test".to_string(),
+                exit_code: Some(101),
+            },
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            session.events.push(Event {
+                event_id: format!("e{idx}"),
+                timestamp: chrono::Utc::now(),
+                event_type,
+                task_id: None,
+                content: Content::text(""),
+                duration_ms: None,
+                attributes: Default::default(),
+            });
+        }
+        let body = opensession_core::jsonl::to_jsonl_string(&session).expect("encode body");
+        db.write_body("session-1", body.as_bytes())
+            .await
+            .expect("write body");
+
+        let first = db
+            .recompute_session_columns(None, 1)
+            .await
+            .expect("first batch");
+        assert_eq!((first.scanned, first.updated), (1, 1));
+        assert_eq!(first.next_after.as_deref(), Some("session-1"));
+
+        let second = db
+            .recompute_session_columns(first.next_after, 10)
+            .await
+            .expect("second batch");
+        assert_eq!((second.scanned, second.updated), (1, 0));
+        assert_eq!(second.errors.len(), 1);
+        assert_eq!(second.errors[0].session_id, "session-2");
+        assert!(second.next_after.is_none());
+
+        let detail = db.get_session_detail("session-1").await.expect("detail");
+        assert_eq!(detail.summary.event_count, 2);
+        assert!(detail.summary.has_errors);
+        assert_eq!(
+            detail.summary.files_modified.as_deref(),
+            Some("[\"src/app.rs\"]")
+        );
+        assert_eq!(detail.summary.files_read, None);
+
+        let again = db.recompute_session_columns(None, 10).await.expect("rerun");
+        assert_eq!(again.updated, 0);
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn delete_account_removes_sessions_attachments_and_files() {
+        let data_dir = test_data_dir("delete-account");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "leaving");
+        insert_test_user(&db, "user-2", "staying");
+        for (session_id, user_id) in [("session-1", "user-1"), ("session-2", "user-2")] {
+            let key = db
+                .write_body(session_id, b"{}\n")
+                .await
+                .expect("write body");
+            insert_test_session(&db, session_id, user_id, &key);
+        }
+        for (id, session_id, user_id) in [
+            ("a1", "session-1", "user-2"),
+            ("a2", "session-2", "user-1"),
+            ("a3", "session-2", "user-2"),
+        ] {
+            let storage_key = db
+                .write_attachment(session_id, id, b"log")
+                .await
+                .expect("write attachment");
+            db.insert_attachment(NewAttachmentRecord {
+                id: id.to_string(),
+                session_id: session_id.to_string(),
+                user_id: user_id.to_string(),
+                file_name: "run.log".to_string(),
+                content_type: "text/plain".to_string(),
+                size_bytes: 3,
+                storage_key,
+                kind: AttachmentKind::File,
+                recorded_at: None,
+            })
+            .await
+            .expect("insert attachment");
+        }
+
+        let deleted = db.delete_account("user-1").await.expect("delete account");
+        assert_eq!(deleted.sessions_deleted, 1);
+        assert_eq!(deleted.attachments_deleted, 2);
+
+        assert!(db.get_user_nickname("user-1").await.is_err());
+        assert!(!db.session_exists("session-1").await.expect("lookup"));
+        assert!(
+            db.list_user_sessions("user-1")
+                .await
+                .expect("list")
+                .is_empty()
+        );
+        let remaining = db.list_attachments("session-2").await.expect("attachments");
+        assert_eq!(
+            remaining.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(),
+            ["a3"]
+        );
+        let bodies = data_dir.join("bodies");
+        assert!(!bodies.join("session-1.hail.jsonl").exists());
+        assert!(!bodies.join("session-1.a1.attachment").exists());
+        assert!(!bodies.join("session-2.a2.attachment").exists());
+        assert!(bodies.join("session-2.hail.jsonl").exists());
+        assert!(bodies.join("session-2.a3.attachment").exists());
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn replace_user_teams_only_touches_one_provider() {
+        let data_dir = test_data_dir("user-teams");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "ada");
+
+        let teams = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        db.replace_user_teams("user-1", "okta", &teams(&["platform", "infra"]))
+            .await
+            .expect("okta teams");
+        db.replace_user_teams("user-1", "keycloak", &teams(&["infra", "data"]))
+            .await
+            .expect("keycloak teams");
+        db.replace_user_teams("user-1", "okta", &teams(&["security"]))
+            .await
+            .expect("okta teams again");
+
+        let settings = db.get_user_settings_data("user-1").await.expect("settings");
+        assert_eq!(settings.teams, ["data", "infra", "security"]);
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn team_usage_counts_sessions_uploaded_by_members() {
+        let data_dir = test_data_dir("team-usage");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "ada");
+        insert_test_user(&db, "user-2", "bob");
+        insert_test_session(&db, "session-1", "user-1", "session-1.hail.jsonl");
+        insert_test_session(&db, "session-2", "user-1", "session-2.hail.jsonl");
+        insert_test_session(&db, "session-3", "user-2", "session-3.hail.jsonl");
+        db.replace_user_teams("user-1", "okta", &["platform".to_string()])
+            .await
+            .expect("okta teams");
+        db.replace_user_teams("user-1", "keycloak", &["platform".to_string()])
+            .await
+            .expect("keycloak teams");
+
+        assert_eq!(
+            db.user_team_ids("user-1").await.expect("teams"),
+            ["platform"]
+        );
+        assert_eq!(
+            db.team_usage("platform").await.expect("usage"),
+            TeamUsage {
+                session_count: 2,
+                body_bytes: 256,
+            }
+        );
+        assert_eq!(
+            db.team_usage("data").await.expect("usage"),
+            TeamUsage::default()
+        );
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn retention_anonymizes_in_place_and_purges_files() {
+        use opensession_core::{Agent, Content, Event, EventType, Session};
+
+        let data_dir = test_data_dir("retention");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "ada");
+        insert_test_user(&db, "user-2", "bob");
+        insert_test_user(&db, "user-3", "cy");
+        for user_id in ["user-1", "user-3"] {
+            db.replace_user_teams(user_id, "okta", &["platform".to_string()])
+                .await
+                .expect("teams");
+        }
+
+        let mut session = Session::new(
+            "session-1".to_string(),
+            Agent {
+                provider: "openai".to_string(),
+                model: "gpt-5".to_string(),
+                tool: "codex".to_string(),
+                tool_version: None,
+            },
+        );
+        session.context.title = Some("Fix the billing export".to_string());
+        session.events.push(Event {
+            event_id: "e0".to_string(),
+            timestamp: chrono::Utc::now(),
+            event_type: EventType::UserMessage,
+            task_id: None,
+            content: Content::text("export customers to /srv/billing/out.csv"),
+            duration_ms: None,
+            attributes: Default::default(),
+        });
+        let body = opensession_core::jsonl::to_jsonl_string(&session).expect("encode body");
+        for (session_id, user_id) in [
+            ("session-1", "user-1"),
+            ("session-2", "user-1"),
+            ("session-3", "user-2"),
+        ] {
+            let key = db
+                .write_body(session_id, body.as_bytes())
+                .await
+                .expect("write body");
+            insert_test_session(&db, session_id, user_id, &key);
+        }
+        let storage_key = db
+            .write_attachment("session-1", "a1", b"log")
+            .await
+            .expect("write attachment");
+        db.insert_attachment(NewAttachmentRecord {
+            id: "a1".to_string(),
+            session_id: "session-1".to_string(),
+            user_id: "user-1".to_string(),
+            file_name: "run.log".to_string(),
+            content_type: "text/plain".to_string(),
+            size_bytes: 3,
+            storage_key,
+            kind: AttachmentKind::File,
+            recorded_at: None,
+        })
+        .await
+        .expect("insert attachment");
+        {
+            let conn = db.conn.lock().expect("db conn");
+            conn.execute(
+                "INSERT INTO user_metadata (user_id, key, value, updated_at) \
+                 VALUES ('user-3', 'bookmark:session-2#event-e0', '', 0), \
+                        ('user-2', 'bookmark:session-1', '1', 0), \
+                        ('user-2', 'bookmark:session-1x', '1', 0)",
+                [],
+            )
+            .expect("bookmark");
+        }
+
+        let future = "9999-01-01 00:00:00";
+        let candidates = db
+            .list_expiry_candidates(future, Some("platform"))
+            .await
+            .expect("candidates");
+        assert_eq!(
+            candidates
+                .iter()
+                .map(|c| (c.session_id.as_str(), c.bookmarked, c.anonymized))
+                .collect::<Vec<_>>(),
+            [("session-1", false, false), ("session-2", true, false)]
+        );
+
+        db.anonymize_stored_session("session-1")
+            .await
+            .expect("anonymize");
+        let detail = db.get_session_detail("session-1").await.expect("detail");
+        assert_ne!(
+            detail.summary.title.as_deref(),
+            Some("Fix the billing export")
+        );
+        assert_eq!(detail.summary.git_remote, None);
+        assert_eq!(detail.summary.event_count, 1);
+        assert_eq!(detail.body_signature, None);
+        let stored = db
+            .read_body("session-1.hail.jsonl")
+            .await
+            .expect("read anonymized body");
+        let stored = String::from_utf8(stored).expect("utf8");
+        assert!(!stored.contains("/srv/billing"));
+        assert_eq!(
+            from_jsonl_str(&stored).expect("parse").session_id,
+            "session-1"
+        );
+        assert!(
+            db.list_attachments("session-1")
+                .await
+                .expect("list")
+                .is_empty()
+        );
+        let bodies = data_dir.join("bodies");
+        assert!(!bodies.join("session-1.a1.attachment").exists());
+        let candidates = db
+            .list_expiry_candidates(future, Some("platform"))
+            .await
+            .expect("candidates");
+        assert!(candidates[0].anonymized);
+
+        assert!(db.purge_session("session-2").await.expect("purge"));
+        assert!(!db.purge_session("session-2").await.expect("purge again"));
+        assert!(!db.session_exists("session-2").await.expect("lookup"));
+        assert!(!bodies.join("session-2.hail.jsonl").exists());
+        assert!(bodies.join("session-3.hail.jsonl").exists());
+
+        db.put_retention_policy(
+            "platform",
+            RetentionPolicy {
+                retain_days: 30,
+                action: RetentionAction::Anonymize,
+                exempt_bookmarked: false,
+                exempt_linked: true,
+            },
+            "user-1",
+        )
+        .await
+        .expect("put policy");
+        let policy = db
+            .get_retention_policy("platform")
+            .await
+            .expect("get policy")
+            .expect("policy exists");
+        assert_eq!(policy.retain_days, 30);
+        assert_eq!(policy.action, RetentionAction::Anonymize);
+        assert!(!policy.exempt_bookmarked);
+        assert!(
+            db.delete_retention_policy("platform")
+                .await
+                .expect("delete")
+        );
+        assert_eq!(
+            db.get_retention_policy("platform").await.expect("get"),
+            None
+        );
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn guest_tokens_resolve_to_their_team_and_scope_listing() {
+        let data_dir = test_data_dir("guest-tokens");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "ada");
+        insert_test_user(&db, "user-2", "bob");
+        insert_test_session(&db, "session-1", "user-1", "session-1.hail.jsonl");
+        insert_test_session(&db, "session-2", "user-2", "session-2.hail.jsonl");
+        db.replace_user_teams("user-1", "okta", &["platform".to_string()])
+            .await
+            .expect("teams");
+
+        assert!(db.user_in_team("user-1", "platform").await.expect("member"));
+        assert!(!db.user_in_team("user-2", "platform").await.expect("member"));
+
+        db.insert_guest_token("g1", "platform", "dashboard", "hash-1", "user-1")
+            .await
+            .expect("insert guest token");
+        assert_eq!(
+            db.resolve_guest_token("hash-1").await.expect("resolve"),
+            Some("platform".to_string())
+        );
+        assert_eq!(
+            db.resolve_guest_token("hash-2").await.expect("resolve"),
+            None
+        );
+        let tokens = db.list_guest_tokens("platform").await.expect("list");
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].created_by, "ada");
+        assert!(tokens[0].last_used_at.is_some());
+
+        let built = db::sessions::list_for_team(&SessionListQuery::default(), "platform")
+            .expect("build list");
+        let listed = db.list_sessions(built).await.expect("list sessions");
+        assert_eq!(
+            listed
+                .sessions
+                .iter()
+                .map(|s| s.id.as_str())
+                .collect::<Vec<_>>(),
+            ["session-1"]
+        );
+
+        assert_eq!(
+            db.delete_guest_token("g1", "other").await.expect("delete"),
+            0
+        );
+        assert_eq!(
+            db.delete_guest_token("g1", "platform")
+                .await
+                .expect("delete"),
+            1
+        );
+        assert_eq!(
+            db.resolve_guest_token("hash-1").await.expect("resolve"),
+            None
+        );
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn body_round_trip_uses_async_fs() {
+        let data_dir = test_data_dir("body-round-trip");
+        let db = init_db(&data_dir).expect("init db");
+
+        let key = db
+            .write_body("session-1", b"{\"type\":\"message\"}\n")
+            .await
+            .expect("write body");
+        let body = db.read_body(&key).await.expect("read body");
+
+        assert_eq!(key, "session-1.hail.jsonl");
+        assert_eq!(body, b"{\"type\":\"message\"}\n");
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn idle_bodies_archive_to_cold_store_and_rehydrate_on_download() {
+        let data_dir = test_data_dir("body-tiering");
+        let cold_dir = data_dir.join("cold");
+        let db = init_db(&data_dir)
+            .expect("init db")
+            .with_cold_store(ColdStore::Dir(cold_dir.clone()));
+        insert_test_user(&db, "user-1", "ada");
+        for id in ["idle", "recent"] {
+            let key = db.write_body(id, id.as_bytes()).await.expect("write body");
+            insert_test_session(&db, id, "user-1", &key);
+        }
+        db.conn
+            .lock()
+            .expect("db conn")
+            .execute(
+                "UPDATE sessions SET uploaded_at = '2020-01-01 00:00:00' WHERE id = 'idle'",
+                [],
+            )
+            .expect("age session");
 
-fn sq_params(values: &sea_query::Values) -> Vec<Box<dyn rusqlite::types::ToSql>> {
-    values
-        .0
-        .iter()
-        .map(|v| -> Box<dyn rusqlite::types::ToSql> {
-            match v {
-                sea_query::Value::Bool(Some(b)) => Box::new(*b),
-                sea_query::Value::TinyInt(Some(i)) => Box::new(*i as i32),
-                sea_query::Value::SmallInt(Some(i)) => Box::new(*i as i32),
-                sea_query::Value::Int(Some(i)) => Box::new(*i),
-                sea_query::Value::BigInt(Some(i)) => Box::new(*i),
-                sea_query::Value::TinyUnsigned(Some(u)) => Box::new(*u as i64),
-                sea_query::Value::SmallUnsigned(Some(u)) => Box::new(*u as i64),
-                sea_query::Value::Unsigned(Some(u)) => Box::new(*u as i64),
-                sea_query::Value::BigUnsigned(Some(u)) => Box::new(*u as i64),
-                sea_query::Value::Float(Some(f)) => Box::new(*f as f64),
-                sea_query::Value::Double(Some(f)) => Box::new(*f),
-                sea_query::Value::String(Some(s)) => Box::new(s.as_ref().clone()),
-                sea_query::Value::Bytes(Some(b)) => Box::new(b.as_ref().clone()),
-                _ => Box::new(rusqlite::types::Null),
+        assert_eq!(db.archive_idle_bodies(30, 10).await.expect("archive"), 1);
+        assert!(!data_dir.join("bodies/idle.hail.jsonl").exists());
+        assert!(cold_dir.join("idle.hail.jsonl").exists());
+        let detail = db.get_session_detail("idle").await.expect("detail");
+        assert_eq!(detail.storage_class, StorageClass::Cold);
+        assert_eq!(
+            db.read_body("idle.hail.jsonl").await.expect("read cold"),
+            b"idle"
+        );
+
+        let body = db
+            .read_session_body("idle", "idle.hail.jsonl")
+            .await
+            .expect("rehydrate");
+        assert_eq!(body, b"idle");
+        assert!(data_dir.join("bodies/idle.hail.jsonl").exists());
+        assert!(!cold_dir.join("idle.hail.jsonl").exists());
+        let detail = db.get_session_detail("idle").await.expect("detail");
+        assert_eq!(detail.storage_class, StorageClass::Hot);
+        assert_eq!(db.archive_idle_bodies(30, 10).await.expect("archive"), 0);
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn concurrent_session_reads_are_serialized_inside_storage() {
+        let data_dir = test_data_dir("concurrent-session-reads");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "tester");
+        insert_test_session(&db, "session-1", "user-1", "session-1.hail.jsonl");
+
+        let query = SessionListQuery {
+            page: 1,
+            per_page: 20,
+            cursor: None,
+            limit: None,
+            search: None,
+            tool: None,
+            git_repo_name: None,
+            sort: None,
+            time_range: None,
+            model: None,
+            since: None,
+            before: None,
+            touches: None,
+            has_errors: None,
+            git_commit: None,
+        };
+
+        let (list_result, detail_result) = tokio::join!(
+            db.list_sessions(db::sessions::list(&query).expect("build list query")),
+            db.get_session_detail("session-1")
+        );
+
+        let list_result = list_result.expect("list sessions");
+        let detail_result = detail_result.expect("session detail");
+        assert_eq!(list_result.total, 1);
+        assert_eq!(list_result.sessions.len(), 1);
+        assert_eq!(detail_result.summary.id, "session-1");
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn list_sessions_applies_log_filters() {
+        let data_dir = test_data_dir("list-log-filters");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "tester");
+        insert_test_session(&db, "session-1", "user-1", "session-1.hail.jsonl");
+
+        let base = SessionListQuery {
+            page: 1,
+            per_page: 20,
+            cursor: None,
+            limit: None,
+            search: None,
+            tool: None,
+            git_repo_name: None,
+            sort: None,
+            time_range: None,
+            model: None,
+            since: None,
+            before: None,
+            touches: None,
+            has_errors: None,
+            git_commit: None,
+        };
+        let total = |query: SessionListQuery| {
+            let db = db.clone();
+            async move {
+                let built = db::sessions::list(&query).expect("build list query");
+                db.list_sessions(built).await.expect("list sessions").total
             }
+        };
+
+        let matching = SessionListQuery {
+            model: Some("gpt-*".into()),
+            since: Some("2026-03-01".into()),
+            before: Some("2026-04-01".into()),
+            touches: Some("src/lib.rs".into()),
+            has_errors: Some(false),
+            git_commit: Some("abc".into()),
+            ..base.clone()
+        };
+        assert_eq!(total(matching).await, 1);
+
+        for miss in [
+            SessionListQuery {
+                model: Some("claude-*".into()),
+                ..base.clone()
+            },
+            SessionListQuery {
+                since: Some("2026-03-10".into()),
+                ..base.clone()
+            },
+            SessionListQuery {
+                before: Some("2026-03-09".into()),
+                ..base.clone()
+            },
+            SessionListQuery {
+                touches: Some("src/main.rs".into()),
+                ..base.clone()
+            },
+            SessionListQuery {
+                has_errors: Some(true),
+                ..base.clone()
+            },
+            SessionListQuery {
+                git_commit: Some("def".into()),
+                ..base.clone()
+            },
+        ] {
+            assert_eq!(total(miss).await, 0);
+        }
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn list_sessions_cursor_pages_are_stable_under_new_uploads() {
+        let data_dir = test_data_dir("list-cursor");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "tester");
+        for id in ["session-a", "session-b", "session-c"] {
+            insert_test_session(&db, id, "user-1", &format!("{id}.hail.jsonl"));
+        }
+
+        let mut query = SessionListQuery {
+            page: 1,
+            per_page: 20,
+            cursor: None,
+            limit: Some(2),
+            search: None,
+            tool: None,
+            git_repo_name: None,
+            sort: None,
+            time_range: None,
+            model: None,
+            since: None,
+            before: None,
+            touches: None,
+            has_errors: None,
+            git_commit: None,
+        };
+        let first = db
+            .list_sessions(db::sessions::list(&query).expect("build first page"))
+            .await
+            .expect("first page");
+        let first_ids: Vec<_> = first.sessions.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(first_ids, ["session-c", "session-b"]);
+        assert!(first.next_cursor.is_some());
+
+        // A concurrent upload must not shift the next page.
+        insert_test_session(&db, "session-d", "user-1", "session-d.hail.jsonl");
+
+        query.cursor = first.next_cursor;
+        let second = db
+            .list_sessions(db::sessions::list(&query).expect("build second page"))
+            .await
+            .expect("second page");
+        let second_ids: Vec<_> = second.sessions.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(second_ids, ["session-a"]);
+        assert_eq!(second.next_cursor, None);
+
+        query.cursor = Some("not-a-cursor".into());
+        assert!(db::sessions::list(&query).is_err());
+
+        cleanup_dir(&data_dir);
+    }
+
+    fn uploaded_row(id: &str, linked: &[&str]) -> UploadedSessionRow {
+        UploadedSessionRow {
+            id: id.to_string(),
+            user_id: "user-1".to_string(),
+            tool: "codex".to_string(),
+            agent_provider: "openai".to_string(),
+            agent_model: "gpt-5".to_string(),
+            title: "Uploaded".to_string(),
+            description: String::new(),
+            tags: String::new(),
+            created_at: "2026-03-09T12:00:00+00:00".to_string(),
+            message_count: 1,
+            task_count: 0,
+            event_count: 1,
+            duration_seconds: 1,
+            total_input_tokens: 0,
+            total_output_tokens: 0,
+            body_storage_key: format!("{id}.hail.jsonl"),
+            body_size_bytes: 64,
+            body_url: None,
+            git_remote: None,
+            git_branch: None,
+            git_commit: None,
+            git_repo_name: None,
+            pr_number: None,
+            pr_url: None,
+            working_directory: None,
+            files_modified: None,
+            files_read: None,
+            has_errors: false,
+            max_active_agents: 1,
+            session_score: 100,
+            score_plugin: "heuristic_v1".to_string(),
+            content_fingerprint: None,
+            device_id: None,
+            body_sha256: None,
+            body_signature: None,
+            verified_origin: false,
+            kind: SessionKind::Full,
+            linked_session_ids: linked.iter().map(|s| s.to_string()).collect(),
+            duplicate_of: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn insert_uploaded_session_records_links_and_rejects_duplicates() {
+        let data_dir = test_data_dir("insert-uploaded");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "tester");
+
+        db.insert_uploaded_session(uploaded_row("session-a", &[]))
+            .await
+            .expect("insert first");
+        db.insert_uploaded_session(uploaded_row("session-b", &["session-a"]))
+            .await
+            .expect("insert second");
+
+        let detail = db.get_session_detail("session-b").await.expect("detail");
+        assert_eq!(detail.linked_sessions.len(), 1);
+        assert_eq!(detail.linked_sessions[0].linked_session_id, "session-a");
+
+        let err = db
+            .insert_uploaded_session(uploaded_row("session-a", &[]))
+            .await
+            .expect_err("duplicate insert");
+        assert!(err.is_constraint_violation());
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn body_integrity_is_reported_in_detail_and_storage_info() {
+        let data_dir = test_data_dir("body-integrity");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "tester");
+
+        let mut row = uploaded_row("session-a", &[]);
+        row.body_sha256 = Some("ab12".to_string());
+        row.body_signature = Some("c2ln".to_string());
+        db.insert_uploaded_session(row)
+            .await
+            .expect("insert uploaded session");
+        db.insert_uploaded_session(uploaded_row("session-b", &[]))
+            .await
+            .expect("insert unhashed session");
+
+        let detail = db.get_session_detail("session-a").await.expect("detail");
+        assert_eq!(detail.body_sha256.as_deref(), Some("ab12"));
+        assert_eq!(detail.body_signature.as_deref(), Some("c2ln"));
+        let info = db
+            .get_session_storage_info("session-a")
+            .await
+            .expect("storage info");
+        assert_eq!(info.body_sha256.as_deref(), Some("ab12"));
+
+        let legacy = db.get_session_detail("session-b").await.expect("detail");
+        assert_eq!(legacy.body_sha256, None);
+        assert_eq!(legacy.body_signature, None);
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn content_fingerprint_finds_earliest_upload_and_links_forced_copies() {
+        let data_dir = test_data_dir("content-fingerprint");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "tester");
+
+        let mut original = uploaded_row("session-a", &[]);
+        original.content_fingerprint = Some("fp-1".to_string());
+        db.insert_uploaded_session(original)
+            .await
+            .expect("insert original");
+        assert!(
+            db.find_session_by_content_fingerprint("fp-2")
+                .await
+                .expect("lookup unknown")
+                .is_none()
+        );
+
+        let mut forced = uploaded_row("session-b", &[]);
+        forced.content_fingerprint = Some("fp-1".to_string());
+        forced.duplicate_of = Some("session-a".to_string());
+        db.insert_uploaded_session(forced)
+            .await
+            .expect("insert forced copy");
+
+        assert_eq!(
+            db.find_session_by_content_fingerprint("fp-1")
+                .await
+                .expect("lookup fingerprint")
+                .as_deref(),
+            Some("session-a")
+        );
+        let detail = db.get_session_detail("session-b").await.expect("detail");
+        assert_eq!(detail.linked_sessions.len(), 1);
+        assert_eq!(detail.linked_sessions[0].linked_session_id, "session-a");
+        assert_eq!(detail.linked_sessions[0].link_type, LinkType::Related);
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn handoff_inbox_lists_items_for_recipient_only() {
+        let data_dir = test_data_dir("handoff-inbox");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "alice");
+        insert_test_user(&db, "user-2", "bob");
+
+        let recipient_id = db
+            .find_user_id_by_nickname("bob")
+            .await
+            .expect("lookup bob")
+            .expect("bob exists");
+        assert_eq!(recipient_id, "user-2");
+        assert!(
+            db.find_user_id_by_nickname("carol")
+                .await
+                .expect("lookup carol")
+                .is_none()
+        );
+
+        db.insert_handoff(NewHandoffRecord {
+            id: "handoff-1".to_string(),
+            sender_id: "user-1".to_string(),
+            recipient_id,
+            artifact_uri: format!("os://artifact/{}", "a".repeat(64)),
+            canonical_jsonl: "{\"source_session_id\":\"s1\"}\n".to_string(),
+            markdown: Some("# Session Handoff".to_string()),
+            note: Some("please finish the migration".to_string()),
         })
-        .collect()
-}
+        .await
+        .expect("insert handoff");
 
-fn sq_execute(conn: &Connection, (sql, values): Built) -> rusqlite::Result<usize> {
-    let params = sq_params(&values);
-    let refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-    conn.execute(&sql, refs.as_slice())
-}
+        let inbox = db
+            .list_handoff_inbox("user-2", None, 10)
+            .await
+            .expect("inbox");
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].sender_nickname, "alice");
+        assert_eq!(inbox[0].recipient_nickname, "bob");
+        assert_eq!(
+            inbox[0].note.as_deref(),
+            Some("please finish the migration")
+        );
+        assert!(
+            db.list_handoff_inbox("user-1", None, 10)
+                .await
+                .expect("sender inbox")
+                .is_empty()
+        );
 
-fn sq_query_row<T>(
-    conn: &Connection,
-    (sql, values): Built,
-    f: impl FnOnce(&rusqlite::Row<'_>) -> rusqlite::Result<T>,
-) -> rusqlite::Result<T> {
-    let params = sq_params(&values);
-    let refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-    conn.query_row(&sql, refs.as_slice(), f)
-}
+        assert_eq!(inbox[0].status, HandoffStatus::Pending);
+        assert_eq!(
+            db.complete_handoff("handoff-1", "user-2", None)
+                .await
+                .expect("complete before claim"),
+            0
+        );
+        assert_eq!(
+            db.claim_handoff("handoff-1", "user-1")
+                .await
+                .expect("claim by sender"),
+            0
+        );
+        assert_eq!(
+            db.claim_handoff("handoff-1", "user-2")
+                .await
+                .expect("claim"),
+            1
+        );
+        assert_eq!(
+            db.claim_handoff("handoff-1", "user-2")
+                .await
+                .expect("claim twice"),
+            0
+        );
 
-fn sq_query_map<T>(
-    conn: &Connection,
-    (sql, values): Built,
-    f: impl FnMut(&rusqlite::Row<'_>) -> rusqlite::Result<T>,
-) -> rusqlite::Result<Vec<T>> {
-    let params = sq_params(&values);
-    let refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-    let mut stmt = conn.prepare(&sql)?;
-    let rows = stmt.query_map(refs.as_slice(), f)?;
-    rows.collect()
-}
+        insert_test_session(&db, "session-result", "user-2", "key-result");
+        assert!(db.session_exists("session-result").await.expect("exists"));
+        assert!(!db.session_exists("session-missing").await.expect("missing"));
+        assert_eq!(
+            db.complete_handoff("handoff-1", "user-2", Some("session-result"))
+                .await
+                .expect("complete"),
+            1
+        );
 
-fn session_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SessionSummary> {
-    Ok(SessionSummary {
-        id: row.get(0)?,
-        user_id: row.get(1)?,
-        nickname: row.get(2)?,
-        tool: row.get(3)?,
-        agent_provider: row.get(4)?,
-        agent_model: row.get(5)?,
-        title: row.get(6)?,
-        description: row.get(7)?,
-        tags: row.get(8)?,
-        created_at: row.get(9)?,
-        uploaded_at: row.get(10)?,
-        message_count: row.get(11)?,
-        task_count: row.get(12)?,
-        event_count: row.get(13)?,
-        duration_seconds: row.get(14)?,
-        total_input_tokens: row.get(15)?,
-        total_output_tokens: row.get(16)?,
-        git_remote: row.get(17)?,
-        git_branch: row.get(18)?,
-        git_commit: row.get(19)?,
-        git_repo_name: row.get(20)?,
-        pr_number: row.get(21)?,
-        pr_url: row.get(22)?,
-        working_directory: row.get(23)?,
-        files_modified: row.get(24)?,
-        files_read: row.get(25)?,
-        has_errors: row.get::<_, i64>(26).unwrap_or(0) != 0,
-        max_active_agents: row.get(27).unwrap_or(1),
-        session_score: row.get(28).unwrap_or(0),
-        score_plugin: row
-            .get::<_, String>(29)
-            .unwrap_or_else(|_| opensession_core::scoring::DEFAULT_SCORE_PLUGIN.to_string()),
-    })
-}
+        let completed = db
+            .find_handoff("handoff-1")
+            .await
+            .expect("find")
+            .expect("handoff exists");
+        assert_eq!(completed.status, HandoffStatus::Completed);
+        assert!(completed.claimed_at.is_some());
+        assert!(completed.completed_at.is_some());
+        assert_eq!(
+            completed.result_session_id.as_deref(),
+            Some("session-result")
+        );
+        assert!(
+            db.list_handoff_inbox("user-2", Some(HandoffStatus::Pending), 10)
+                .await
+                .expect("pending inbox")
+                .is_empty()
+        );
 
-// ── Database init ─────────────────────────────────────────────────────────
+        cleanup_dir(&data_dir);
+    }
 
-/// Initialize the database: open connection, enable WAL, run migrations.
-pub fn init_db(data_dir: &Path) -> Result<Db> {
-    std::fs::create_dir_all(data_dir)?;
-    let db_path = data_dir.join("opensession.db");
-    let conn = Connection::open(&db_path).context("opening SQLite database")?;
+    #[tokio::test]
+    async fn telemetry_totals_group_reports_by_version() {
+        let data_dir = test_data_dir("telemetry");
+        let db = init_db(&data_dir).expect("init db");
+        let report = |version: &str, indexed: u64, failures: u64| TelemetryReport {
+            daemon_version: version.to_string(),
+            period_start: "2026-10-18T00:00:00Z".to_string(),
+            period_end: "2026-10-18T01:00:00Z".to_string(),
+            sessions_indexed: indexed,
+            parse_failures: failures,
+            upload_errors: 0,
+        };
+        for item in [
+            report("0.2.0", 5, 1),
+            report("0.2.0", 3, 0),
+            report("0.1.9", 1, 2),
+        ] {
+            db.insert_telemetry_report(&item)
+                .await
+                .expect("insert telemetry report");
+        }
 
-    conn.execute_batch("PRAGMA journal_mode=WAL;")?;
-    conn.execute_batch("PRAGMA foreign_keys=ON;")?;
+        let totals = db.telemetry_totals(7).await.expect("telemetry totals");
+        assert_eq!(
+            totals,
+            vec![
+                TelemetryTotals {
+                    daemon_version: "0.2.0".to_string(),
+                    reports: 2,
+                    sessions_indexed: 8,
+                    parse_failures: 1,
+                    upload_errors: 0,
+                },
+                TelemetryTotals {
+                    daemon_version: "0.1.9".to_string(),
+                    reports: 1,
+                    sessions_indexed: 1,
+                    parse_failures: 2,
+                    upload_errors: 0,
+                },
+            ]
+        );
+
+        cleanup_dir(&data_dir);
+    }
+
+    #[tokio::test]
+    async fn review_requests_list_by_role_and_resolve_once() {
+        let data_dir = test_data_dir("review-requests");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "alice");
+        insert_test_user(&db, "user-2", "bob");
+        insert_test_session(&db, "session-1", "user-1", "key-1");
 
-    run_migrations(&conn)?;
+        db.insert_review_request(
+            "review-1",
+            "session-1",
+            "user-1",
+            "user-2",
+            Some("check the migration"),
+        )
+        .await
+        .expect("insert review request");
 
-    Ok(Db {
-        conn: Arc::new(Mutex::new(conn)),
-        data_dir: data_dir.to_path_buf(),
-    })
-}
+        let assigned = db
+            .list_review_requests("user-2", ReviewRequestRole::Assigned, None, 10)
+            .await
+            .expect("assigned");
+        assert_eq!(assigned.len(), 1);
+        assert_eq!(assigned[0].session_title.as_deref(), Some("Test Session"));
+        assert_eq!(assigned[0].requester_nickname, "alice");
+        assert_eq!(assigned[0].reviewer_nickname, "bob");
+        assert_eq!(assigned[0].status, ReviewRequestStatus::Pending);
+        assert!(
+            db.list_review_requests("user-1", ReviewRequestRole::Assigned, None, 10)
+                .await
+                .expect("requester has nothing assigned")
+                .is_empty()
+        );
+        assert_eq!(
+            db.list_review_requests("user-1", ReviewRequestRole::Requested, None, 10)
+                .await
+                .expect("requested")
+                .len(),
+            1
+        );
 
-fn run_migrations(conn: &Connection) -> Result<()> {
-    conn.execute_batch(
-        "CREATE TABLE IF NOT EXISTS _migrations (
-            id INTEGER PRIMARY KEY,
-            name TEXT NOT NULL UNIQUE,
-            applied_at TEXT NOT NULL DEFAULT (datetime('now'))
-        );",
-    )?;
+        assert_eq!(
+            db.resolve_review_request("review-1", "user-1", ReviewRequestStatus::Approved, None)
+                .await
+                .expect("resolve by requester"),
+            0
+        );
+        assert_eq!(
+            db.resolve_review_request(
+                "review-1",
+                "user-2",
+                ReviewRequestStatus::ChangesRequested,
+                Some("split the commit"),
+            )
+            .await
+            .expect("resolve"),
+            1
+        );
+        assert_eq!(
+            db.resolve_review_request("review-1", "user-2", ReviewRequestStatus::Approved, None)
+                .await
+                .expect("resolve twice"),
+            0
+        );
 
-    for (name, sql) in db::migrations::MIGRATIONS {
-        let already_applied: bool = conn
-            .query_row(
-                "SELECT COUNT(*) > 0 FROM _migrations WHERE name = ?1",
-                [name],
-                |row| row.get(0),
+        let resolved = db
+            .find_review_request("review-1")
+            .await
+            .expect("find")
+            .expect("review request exists");
+        assert_eq!(resolved.status, ReviewRequestStatus::ChangesRequested);
+        assert_eq!(resolved.comment.as_deref(), Some("split the commit"));
+        assert!(resolved.resolved_at.is_some());
+        assert!(
+            db.list_review_requests(
+                "user-2",
+                ReviewRequestRole::Assigned,
+                Some(ReviewRequestStatus::Pending),
+                10
             )
-            .unwrap_or(false);
+            .await
+            .expect("pending")
+            .is_empty()
+        );
 
-        if !already_applied {
-            conn.execute_batch(sql)
-                .with_context(|| format!("running migration {name}"))?;
-            conn.execute("INSERT INTO _migrations (name) VALUES (?1)", [name])?;
-            tracing::info!("Applied migration: {name}");
-        }
+        cleanup_dir(&data_dir);
     }
 
-    if !oauth_provider_tokens_has_provider_host(conn)? {
-        tracing::warn!(
-            "rebuilding oauth_provider_tokens table for provider_host security upgrade (stored provider tokens will be removed)"
+    #[tokio::test]
+    async fn playbooks_upsert_keeps_author_and_deletes() {
+        let data_dir = test_data_dir("playbooks");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "alice");
+
+        let request = |body: &str| PutPlaybookRequest {
+            kind: PlaybookKind::Prompt,
+            title: Some("Refactor a module".to_string()),
+            description: None,
+            body: body.to_string(),
+        };
+        let created = db
+            .upsert_playbook("refactor-module", "user-1", request("v1"))
+            .await
+            .expect("create playbook");
+        assert_eq!(created.author_nickname.as_deref(), Some("alice"));
+        let updated = db
+            .upsert_playbook("refactor-module", "user-1", request("v2 {{module}}"))
+            .await
+            .expect("update playbook");
+        assert_eq!(updated.body, "v2 {{module}}");
+        assert_eq!(updated.created_at, created.created_at);
+
+        let listed = db.list_playbooks().await.expect("list playbooks");
+        assert_eq!(listed, vec![updated]);
+        assert_eq!(
+            db.playbook_author("refactor-module").await.expect("author"),
+            Some(Some("user-1".to_string()))
         );
-        rebuild_oauth_provider_tokens_table(conn)?;
+        assert!(db.delete_playbook("refactor-module").await.expect("delete"));
+        assert!(
+            !db.delete_playbook("refactor-module")
+                .await
+                .expect("delete twice")
+        );
+        assert!(
+            db.get_playbook("refactor-module")
+                .await
+                .expect("get")
+                .is_none()
+        );
+
+        cleanup_dir(&data_dir);
     }
 
-    conn.execute_batch(
-        r#"
-CREATE TABLE IF NOT EXISTS git_credentials (
-    id               TEXT PRIMARY KEY,
-    user_id          TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
-    label            TEXT NOT NULL,
-    host             TEXT NOT NULL,
-    path_prefix      TEXT NOT NULL DEFAULT '',
-    header_name      TEXT NOT NULL,
-    header_value_enc TEXT NOT NULL,
-    created_at       TEXT NOT NULL DEFAULT (datetime('now')),
-    updated_at       TEXT NOT NULL DEFAULT (datetime('now')),
-    last_used_at     TEXT
-);
-CREATE INDEX IF NOT EXISTS idx_git_credentials_user_host ON git_credentials(user_id, host);
-CREATE INDEX IF NOT EXISTS idx_git_credentials_user_host_prefix
-ON git_credentials(user_id, host, path_prefix);
+    #[tokio::test]
+    async fn revoking_device_blocks_it_and_its_api_keys() {
+        let data_dir = test_data_dir("device-revoke");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "tester");
 
-CREATE TABLE IF NOT EXISTS oauth_provider_tokens (
-    id               TEXT PRIMARY KEY,
-    user_id          TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
-    provider         TEXT NOT NULL,
-    provider_host    TEXT NOT NULL,
-    access_token_enc TEXT NOT NULL,
-    expires_at       TEXT,
-    created_at       TEXT NOT NULL DEFAULT (datetime('now')),
-    updated_at       TEXT NOT NULL DEFAULT (datetime('now')),
-    UNIQUE (user_id, provider, provider_host)
-);
-DROP INDEX IF EXISTS idx_oauth_provider_tokens_user_provider;
-CREATE INDEX IF NOT EXISTS idx_oauth_provider_tokens_user_provider_host
-ON oauth_provider_tokens(user_id, provider, provider_host);
-"#,
-    )?;
+        db.insert_active_api_key("key-1", "user-1", "hash-1", "osk_1", None)
+            .await
+            .expect("insert key");
+        assert_eq!(
+            db.record_device_use("user-1", "laptop", Some("work laptop"), Some("hash-1"))
+                .await
+                .expect("record device"),
+            DeviceUse::Recorded { key_bound: false }
+        );
+        assert_eq!(
+            db.record_device_use("user-1", "laptop", None, Some("hash-1"))
+                .await
+                .expect("record device again"),
+            DeviceUse::Recorded { key_bound: true }
+        );
+        assert_eq!(
+            db.record_device_use("user-1", "desktop", None, Some("hash-1"))
+                .await
+                .expect("record key from another device"),
+            DeviceUse::KeyBoundElsewhere
+        );
 
-    Ok(())
-}
+        let mut row = uploaded_row("session-a", &[]);
+        row.device_id = Some("laptop".to_string());
+        db.insert_uploaded_session(row)
+            .await
+            .expect("insert uploaded session");
+        let detail = db.get_session_detail("session-a").await.expect("detail");
+        assert_eq!(detail.summary.device_id.as_deref(), Some("laptop"));
 
-fn oauth_provider_tokens_has_provider_host(conn: &Connection) -> Result<bool> {
-    let mut stmt = conn
-        .prepare("PRAGMA table_info(oauth_provider_tokens)")
-        .context("prepare oauth_provider_tokens schema inspection")?;
-    let rows = stmt
-        .query_map([], |row| row.get::<_, String>(1))
-        .context("query oauth_provider_tokens schema inspection")?;
-    for row in rows {
-        if row.unwrap_or_default() == "provider_host" {
-            return Ok(true);
-        }
+        let devices = db.list_devices("user-1").await.expect("list devices");
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name.as_deref(), Some("work laptop"));
+        assert_eq!(devices[0].session_count, 1);
+        assert!(devices[0].revoked_at.is_none());
+
+        db.insert_active_api_key("key-2", "user-1", "hash-2", "osk_2", None)
+            .await
+            .expect("insert unbound key");
+        db.insert_active_api_key("key-3", "user-1", "hash-3", "osk_3", Some("desktop"))
+            .await
+            .expect("insert key bound elsewhere");
+        assert_eq!(
+            db.revoke_device("user-1", "laptop")
+                .await
+                .expect("revoke device"),
+            Some(2),
+            "the laptop's key and the unbound key"
+        );
+        assert!(db.get_auth_user_by_api_key_hash("hash-2").await.is_err());
+        assert!(db.get_auth_user_by_api_key_hash("hash-3").await.is_ok());
+        assert_eq!(
+            db.revoke_device("user-1", "desktop")
+                .await
+                .expect("revoke unknown device"),
+            None
+        );
+        assert!(db.get_auth_user_by_api_key_hash("hash-1").await.is_err());
+        assert_eq!(
+            db.record_device_use("user-1", "laptop", None, None)
+                .await
+                .expect("record revoked device"),
+            DeviceUse::Revoked
+        );
+        let devices = db.list_devices("user-1").await.expect("list devices");
+        assert!(devices[0].revoked_at.is_some());
+
+        cleanup_dir(&data_dir);
     }
-    Ok(false)
-}
 
-fn rebuild_oauth_provider_tokens_table(conn: &Connection) -> Result<()> {
-    conn.execute_batch(
-        r#"
-DROP TABLE IF EXISTS oauth_provider_tokens;
-CREATE TABLE oauth_provider_tokens (
-    id               TEXT PRIMARY KEY,
-    user_id          TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
-    provider         TEXT NOT NULL,
-    provider_host    TEXT NOT NULL,
-    access_token_enc TEXT NOT NULL,
-    expires_at       TEXT,
-    created_at       TEXT NOT NULL DEFAULT (datetime('now')),
-    updated_at       TEXT NOT NULL DEFAULT (datetime('now')),
-    UNIQUE (user_id, provider, provider_host)
-);
-DROP INDEX IF EXISTS idx_oauth_provider_tokens_user_provider;
-CREATE INDEX IF NOT EXISTS idx_oauth_provider_tokens_user_provider_host
-ON oauth_provider_tokens(user_id, provider, provider_host);
-"#,
-    )
-    .context("rebuild oauth_provider_tokens table")
-}
+    #[tokio::test]
+    async fn device_use_is_written_at_most_once_per_interval() {
+        let data_dir = test_data_dir("device-touch");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "tester");
+        let name = |db: Db| async move {
+            db.list_devices("user-1").await.expect("list devices")[0]
+                .name
+                .clone()
+        };
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        db.record_device_use("user-1", "laptop", Some("old name"), None)
+            .await
+            .expect("record device");
+        db.record_device_use("user-1", "laptop", Some("new name"), None)
+            .await
+            .expect("record device again");
+        assert_eq!(name(db.clone()).await.as_deref(), Some("old name"));
 
-    fn test_data_dir(name: &str) -> PathBuf {
-        let path = std::env::temp_dir().join(format!(
-            "opensession-server-{name}-{}",
-            uuid::Uuid::new_v4()
-        ));
-        std::fs::create_dir_all(&path).expect("create temp data dir");
-        path
-    }
+        db.with_conn(|conn| {
+            conn.execute(
+                "UPDATE devices SET last_seen_at = datetime('now', '-2 minutes')",
+                [],
+            )?;
+            Ok(())
+        })
+        .await
+        .expect("age device");
+        db.record_device_use("user-1", "laptop", Some("new name"), None)
+            .await
+            .expect("record aged device");
+        assert_eq!(name(db.clone()).await.as_deref(), Some("new name"));
 
-    fn cleanup_dir(path: &Path) {
-        std::fs::remove_dir_all(path).expect("remove temp data dir");
+        cleanup_dir(&data_dir);
     }
 
-    fn insert_test_user(db: &Db, user_id: &str, nickname: &str) {
-        let conn = db.conn.lock().expect("db conn");
-        sq_execute(
-            &conn,
-            db::users::insert_oauth(user_id, nickname, Some("test@example.com")),
-        )
-        .expect("insert test user");
-    }
+    #[tokio::test]
+    async fn device_signing_key_registers_once_and_marks_verified_uploads() {
+        let data_dir = test_data_dir("device-signing-key");
+        let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "tester");
 
-    fn insert_test_session(db: &Db, session_id: &str, user_id: &str, storage_key: &str) {
-        let conn = db.conn.lock().expect("db conn");
-        let params = db::sessions::InsertParams {
-            id: session_id,
-            user_id,
-            team_id: "team-1",
-            tool: "codex",
-            agent_provider: "openai",
-            agent_model: "gpt-5",
-            title: "Test Session",
-            description: "Description",
-            tags: "test",
-            created_at: "2026-03-09 12:00:00",
-            message_count: 1,
-            task_count: 1,
-            event_count: 1,
-            duration_seconds: 1,
-            total_input_tokens: 1,
-            total_output_tokens: 1,
-            body_storage_key: storage_key,
-            body_url: None,
-            git_remote: Some("https://github.com/hwisu/opensession"),
-            git_branch: Some("main"),
-            git_commit: Some("abc123"),
-            git_repo_name: Some("opensession"),
-            pr_number: None,
-            pr_url: None,
-            working_directory: Some("/tmp"),
-            files_modified: Some("src/lib.rs"),
-            files_read: Some("src/main.rs"),
-            has_errors: false,
-            max_active_agents: 1,
-            session_score: 42,
-            score_plugin: "default",
-        };
-        sq_execute(&conn, db::sessions::insert(&params)).expect("insert test session");
+        assert!(
+            !db.register_device_key("user-1", "laptop", "key-a")
+                .await
+                .expect("register unknown device")
+        );
+        db.record_device_use("user-1", "laptop", None, None)
+            .await
+            .expect("record device");
+        assert!(
+            db.register_device_key("user-1", "laptop", "key-a")
+                .await
+                .expect("register key")
+        );
+        assert!(
+            !db.register_device_key("user-1", "laptop", "key-b")
+                .await
+                .expect("register replacement key")
+        );
+        assert_eq!(
+            db.device_public_key("user-1", "laptop")
+                .await
+                .expect("device key")
+                .as_deref(),
+            Some("key-a")
+        );
+        assert_eq!(
+            db.device_public_key("user-2", "laptop")
+                .await
+                .expect("other user's device key"),
+            None
+        );
+        let devices = db.list_devices("user-1").await.expect("list devices");
+        assert_eq!(devices[0].signing_key.as_deref(), Some("key-a"));
+
+        let mut row = uploaded_row("session-a", &[]);
+        row.verified_origin = true;
+        db.insert_uploaded_session(row)
+            .await
+            .expect("insert verified session");
+        let detail = db.get_session_detail("session-a").await.expect("detail");
+        assert!(detail.summary.verified_origin);
+
+        cleanup_dir(&data_dir);
     }
 
     #[tokio::test]
-    async fn body_round_trip_uses_async_fs() {
-        let data_dir = test_data_dir("body-round-trip");
+    async fn metrics_only_sessions_keep_their_kind_and_stats() {
+        use opensession_core::session::metrics_only_session;
+        use opensession_core::{Agent, Session};
+
+        let data_dir = test_data_dir("metrics-only");
         let db = init_db(&data_dir).expect("init db");
+        insert_test_user(&db, "user-1", "tester");
 
-        let key = db
-            .write_body("session-1", b"{\"type\":\"message\"}\n")
+        let mut session = Session::new(
+            "session-m".to_string(),
+            Agent {
+                provider: "openai".to_string(),
+                model: "gpt-5".to_string(),
+                tool: "codex".to_string(),
+                tool_version: None,
+            },
+        );
+        session.stats.event_count = 40;
+        session.stats.message_count = 1;
+        session.stats.duration_seconds = 1;
+        session.stats.total_input_tokens = 9_000;
+        let body = opensession_core::jsonl::to_jsonl_string(&metrics_only_session(&session))
+            .expect("encode body");
+        db.write_body("session-m", body.as_bytes())
             .await
             .expect("write body");
-        let body = db.read_body(&key).await.expect("read body");
+        let mut row = uploaded_row("session-m", &[]);
+        row.kind = SessionKind::MetricsOnly;
+        row.event_count = 40;
+        row.total_input_tokens = 9_000;
+        db.insert_uploaded_session(row)
+            .await
+            .expect("insert metrics-only session");
 
-        assert_eq!(key, "session-1.hail.jsonl");
-        assert_eq!(body, b"{\"type\":\"message\"}\n");
+        let recomputed = db
+            .recompute_session_columns(None, 10)
+            .await
+            .expect("recompute");
+        assert_eq!((recomputed.scanned, recomputed.updated), (1, 0));
+
+        let detail = db.get_session_detail("session-m").await.expect("detail");
+        assert_eq!(detail.summary.kind, SessionKind::MetricsOnly);
+        assert_eq!(detail.summary.event_count, 40);
+        assert_eq!(detail.summary.total_input_tokens, 9_000);
 
         cleanup_dir(&data_dir);
     }
 
     #[tokio::test]
-    async fn concurrent_session_reads_are_serialized_inside_storage() {
-        let data_dir = test_data_dir("concurrent-session-reads");
+    async fn user_metadata_sync_keeps_the_newest_write() {
+        let data_dir = test_data_dir("user-metadata");
         let db = init_db(&data_dir).expect("init db");
         insert_test_user(&db, "user-1", "tester");
-        insert_test_session(&db, "session-1", "user-1", "session-1.hail.jsonl");
 
-        let query = SessionListQuery {
-            page: 1,
-            per_page: 20,
-            search: None,
-            tool: None,
-            git_repo_name: None,
-            sort: None,
-            time_range: None,
+        let entry = |key: &str, value: Option<&str>, updated_at| MetadataEntry {
+            key: key.to_string(),
+            value: value.map(str::to_string),
+            updated_at,
         };
+        db.sync_user_metadata(
+            "user-1",
+            vec![
+                entry("bookmark:s1", Some("1"), 100),
+                entry("note:s1", Some("first"), 100),
+            ],
+        )
+        .await
+        .expect("first push");
 
-        let (list_result, detail_result) =
-            tokio::join!(db.list_sessions(&query), db.get_session_detail("session-1"));
-
-        let list_result = list_result.expect("list sessions");
-        let detail_result = detail_result.expect("session detail");
-        assert_eq!(list_result.total, 1);
-        assert_eq!(list_result.sessions.len(), 1);
-        assert_eq!(detail_result.summary.id, "session-1");
+        let merged = db
+            .sync_user_metadata(
+                "user-1",
+                vec![
+                    entry("note:s1", Some("stale"), 50),
+                    entry("bookmark:s1", None, 200),
+                ],
+            )
+            .await
+            .expect("second push");
+        assert_eq!(
+            merged,
+            vec![
+                entry("bookmark:s1", None, 200),
+                entry("note:s1", Some("first"), 100),
+            ]
+        );
+        assert_eq!(db.list_user_metadata("user-1").await.expect("list"), merged);
+        assert!(
+            db.list_user_metadata("user-2")
+                .await
+                .expect("list other user")
+                .is_empty()
+        );
 
         cleanup_dir(&data_dir);
     }
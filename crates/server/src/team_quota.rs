@@ -0,0 +1,169 @@
+//! Per-team storage quotas, checked at upload time.
+//!
+//! A team's usage is every session uploaded by its members (from the sign-in
+//! provider's group mapping). Limits apply to every team alike:
+//!
+//! - `OPENSESSION_TEAM_SOFT_QUOTA_SESSIONS` / `OPENSESSION_TEAM_HARD_QUOTA_SESSIONS`
+//! - `OPENSESSION_TEAM_SOFT_QUOTA_BYTES` / `OPENSESSION_TEAM_HARD_QUOTA_BYTES`
+//!   (stored session body bytes)
+//!
+//! Uploads past a soft limit are stored with a warning; uploads that would
+//! pass a hard limit are rejected with `quota_exceeded`.
+
+use anyhow::{Context, Result, bail};
+use opensession_api::{QuotaLimit, QuotaNotice, QuotaResource};
+
+use crate::app_config::env_trimmed;
+
+/// Storage a team's members have uploaded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TeamUsage {
+    pub session_count: u64,
+    pub body_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TeamQuotas {
+    pub sessions: QuotaLimit,
+    pub body_bytes: QuotaLimit,
+}
+
+impl TeamQuotas {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            sessions: limit_from_env(
+                "OPENSESSION_TEAM_SOFT_QUOTA_SESSIONS",
+                "OPENSESSION_TEAM_HARD_QUOTA_SESSIONS",
+            )?,
+            body_bytes: limit_from_env(
+                "OPENSESSION_TEAM_SOFT_QUOTA_BYTES",
+                "OPENSESSION_TEAM_HARD_QUOTA_BYTES",
+            )?,
+        })
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.sessions.is_unlimited() && self.body_bytes.is_unlimited()
+    }
+
+    /// Check one more upload of `body_bytes` against `usage`. Returns the soft
+    /// limits it reaches, or the first hard limit it would pass.
+    pub fn check_upload(
+        &self,
+        team_id: &str,
+        usage: TeamUsage,
+        body_bytes: u64,
+    ) -> Result<Vec<QuotaNotice>, QuotaNotice> {
+        let after = TeamUsage {
+            session_count: usage.session_count.saturating_add(1),
+            body_bytes: usage.body_bytes.saturating_add(body_bytes),
+        };
+        for (resource, limit, used) in self.resources(after) {
+            if let Some(hard) = limit.hard
+                && used > hard
+            {
+                return Err(notice(team_id, resource, used, hard));
+            }
+        }
+        Ok(self.warnings(team_id, after))
+    }
+
+    /// Limits `usage` has reached, soft or hard.
+    pub fn warnings(&self, team_id: &str, usage: TeamUsage) -> Vec<QuotaNotice> {
+        self.resources(usage)
+            .into_iter()
+            .filter_map(|(resource, limit, used)| {
+                let reached = [limit.hard, limit.soft]
+                    .into_iter()
+                    .flatten()
+                    .find(|limit| used >= *limit)?;
+                Some(notice(team_id, resource, used, reached))
+            })
+            .collect()
+    }
+
+    fn resources(&self, usage: TeamUsage) -> [(QuotaResource, QuotaLimit, u64); 2] {
+        [
+            (QuotaResource::Sessions, self.sessions, usage.session_count),
+            (QuotaResource::BodyBytes, self.body_bytes, usage.body_bytes),
+        ]
+    }
+}
+
+fn notice(team_id: &str, resource: QuotaResource, used: u64, limit: u64) -> QuotaNotice {
+    QuotaNotice {
+        team_id: team_id.to_string(),
+        resource,
+        used,
+        limit,
+    }
+}
+
+fn limit_from_env(soft_var: &str, hard_var: &str) -> Result<QuotaLimit> {
+    let limit = QuotaLimit {
+        soft: u64_from_env(soft_var)?,
+        hard: u64_from_env(hard_var)?,
+    };
+    if let (Some(soft), Some(hard)) = (limit.soft, limit.hard)
+        && soft > hard
+    {
+        bail!("{soft_var} ({soft}) is larger than {hard_var} ({hard})");
+    }
+    Ok(limit)
+}
+
+fn u64_from_env(name: &str) -> Result<Option<u64>> {
+    env_trimmed(name)
+        .map(|raw| {
+            raw.parse()
+                .with_context(|| format!("{name} `{raw}` is not a whole number"))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uploads_warn_past_soft_limits_and_fail_past_hard_limits() {
+        let quotas = TeamQuotas {
+            sessions: QuotaLimit {
+                soft: Some(2),
+                hard: Some(3),
+            },
+            body_bytes: QuotaLimit {
+                soft: None,
+                hard: Some(1_000),
+            },
+        };
+        let usage = |session_count, body_bytes| TeamUsage {
+            session_count,
+            body_bytes,
+        };
+
+        assert_eq!(quotas.check_upload("eng", usage(0, 0), 100), Ok(Vec::new()));
+
+        let warnings = quotas
+            .check_upload("eng", usage(1, 0), 100)
+            .expect("soft limit only warns");
+        assert_eq!(warnings, vec![notice("eng", QuotaResource::Sessions, 2, 2)]);
+
+        assert_eq!(
+            quotas.check_upload("eng", usage(3, 0), 100),
+            Err(notice("eng", QuotaResource::Sessions, 4, 3))
+        );
+        assert_eq!(
+            quotas.check_upload("eng", usage(0, 950), 100),
+            Err(notice("eng", QuotaResource::BodyBytes, 1_050, 1_000))
+        );
+        assert_eq!(
+            quotas.warnings("eng", usage(3, 10)),
+            vec![notice("eng", QuotaResource::Sessions, 3, 3)]
+        );
+        assert_eq!(
+            TeamQuotas::default().check_upload("eng", usage(99, 99), 99),
+            Ok(Vec::new())
+        );
+    }
+}
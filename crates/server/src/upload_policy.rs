@@ -0,0 +1,308 @@
+use std::collections::HashSet;
+
+use opensession_api::{SessionValidationError, TeamUploadPolicy, UploadPolicyAction};
+use opensession_core::{EventType, Session};
+
+/// Server-wide privacy policy applied to every upload, so compliance does not
+/// rest on each member's client-side redaction settings.
+///
+/// Patterns are globs (`*.env`, `*secret*`) matched against file paths the
+/// session touched and against the words of shell commands it ran.
+#[derive(Debug, Clone, Default)]
+pub struct UploadPolicy {
+    patterns: Vec<glob::Pattern>,
+    scrub: bool,
+}
+
+impl UploadPolicy {
+    pub fn new(patterns: &[&str], scrub: bool) -> Self {
+        let patterns = patterns
+            .iter()
+            .filter_map(|raw| match glob::Pattern::new(raw) {
+                Ok(pattern) => Some(pattern),
+                Err(err) => {
+                    tracing::error!("ignoring invalid upload exclude pattern `{raw}`: {err}");
+                    None
+                }
+            })
+            .collect();
+        Self { patterns, scrub }
+    }
+
+    pub fn from_team(policy: &TeamUploadPolicy) -> Self {
+        let patterns = policy
+            .exclude_patterns
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        Self::new(&patterns, policy.action == UploadPolicyAction::Scrub)
+    }
+
+    /// `OPENSESSION_UPLOAD_EXCLUDE_PATTERNS` (comma-separated globs) and
+    /// `OPENSESSION_UPLOAD_POLICY` (`reject`, the default, or `scrub`).
+    pub fn from_env() -> Self {
+        let raw = std::env::var("OPENSESSION_UPLOAD_EXCLUDE_PATTERNS").unwrap_or_default();
+        let patterns = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .collect::<Vec<_>>();
+        let scrub = match std::env::var("OPENSESSION_UPLOAD_POLICY")
+            .unwrap_or_default()
+            .trim()
+        {
+            "" | "reject" => false,
+            "scrub" => true,
+            other => {
+                tracing::error!("unknown OPENSESSION_UPLOAD_POLICY `{other}`; rejecting uploads");
+                false
+            }
+        };
+        let policy = Self::new(&patterns, scrub);
+        if !policy.patterns.is_empty() {
+            tracing::info!(
+                "upload privacy policy: {} pattern(s), {}",
+                policy.patterns.len(),
+                if scrub { "scrub" } else { "reject" }
+            );
+        }
+        policy
+    }
+
+    /// Apply the server-wide policy and the uploader's team policies in turn.
+    ///
+    /// If any policy that rejects has a violation, nothing is removed and
+    /// every policy's violations are returned; otherwise offending events are
+    /// scrubbed and the number removed is returned.
+    pub fn enforce(
+        policies: &[&UploadPolicy],
+        session: &mut Session,
+    ) -> Result<usize, Vec<SessionValidationError>> {
+        let mut violations: Vec<SessionValidationError> = Vec::new();
+        let mut rejected = false;
+        for policy in policies {
+            let found = policy.violations(session);
+            rejected |= !found.is_empty() && !policy.scrub;
+            for violation in found {
+                if !violations.iter().any(|seen| seen.path == violation.path) {
+                    violations.push(violation);
+                }
+            }
+        }
+        if rejected {
+            return Err(violations);
+        }
+        Ok(policies.iter().map(|policy| policy.scrub(session)).sum())
+    }
+
+    /// One `events[i]` entry per event that touches an excluded value.
+    pub fn violations(&self, session: &Session) -> Vec<SessionValidationError> {
+        if self.patterns.is_empty() {
+            return Vec::new();
+        }
+        session
+            .events
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, event)| {
+                let (value, pattern) = self.first_match(&event.event_type)?;
+                Some(SessionValidationError {
+                    path: format!("events[{idx}]"),
+                    code: "excluded_by_policy".to_string(),
+                    message: format!(
+                        "event {} references `{value}`, which matches excluded pattern `{pattern}`",
+                        event.event_id
+                    ),
+                })
+            })
+            .collect()
+    }
+
+    /// Drop every event that [`Self::violations`] reports, plus the tool
+    /// results paired with them by call id, which carry the excluded file's
+    /// contents or command output. Returns how many events were removed.
+    pub fn scrub(&self, session: &mut Session) -> usize {
+        let before = session.events.len();
+        let mut removed_calls = HashSet::new();
+        session.events.retain(|event| {
+            if self.first_match(&event.event_type).is_none() {
+                return true;
+            }
+            removed_calls.insert(event.event_id.clone());
+            if let Some(call_id) = event.semantic_call_id() {
+                removed_calls.insert(call_id.to_string());
+            }
+            false
+        });
+        session.events.retain(|event| {
+            !matches!(event.event_type, EventType::ToolResult { .. })
+                || event
+                    .semantic_call_id()
+                    .is_none_or(|call_id| !removed_calls.contains(call_id))
+        });
+        let removed = before - session.events.len();
+        if removed > 0 {
+            session.recompute_stats();
+        }
+        removed
+    }
+
+    fn first_match<'a>(&self, event_type: &'a EventType) -> Option<(&'a str, &glob::Pattern)> {
+        let values: Vec<&str> = match event_type {
+            EventType::FileEdit { path, .. }
+            | EventType::FileCreate { path }
+            | EventType::FileDelete { path }
+            | EventType::FileRead { path } => vec![path.as_str()],
+            EventType::ShellCommand { command, .. } => command.split_whitespace().collect(),
+            _ => return None,
+        };
+        values.into_iter().find_map(|value| {
+            let name = value.rsplit(['/', '\\']).next().unwrap_or(value);
+            self.patterns
+                .iter()
+                .find(|pattern| pattern.matches(value) || pattern.matches(name))
+                .map(|pattern| (value, pattern))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opensession_core::{Agent, Content, Event};
+
+    fn session(event_types: Vec<EventType>) -> Session {
+        session_with_ids(
+            event_types
+                .into_iter()
+                .enumerate()
+                .map(|(idx, event_type)| (format!("e{idx}"), event_type))
+                .collect(),
+        )
+    }
+
+    fn session_with_ids(events: Vec<(String, EventType)>) -> Session {
+        let mut session = Session::new(
+            "s1".to_string(),
+            Agent {
+                provider: "anthropic".to_string(),
+                model: "claude".to_string(),
+                tool: "claude-code".to_string(),
+                tool_version: None,
+            },
+        );
+        for (event_id, event_type) in events {
+            session.events.push(Event {
+                event_id,
+                timestamp: chrono::Utc::now(),
+                event_type,
+                task_id: None,
+                content: Content::text(""),
+                duration_ms: None,
+                attributes: Default::default(),
+            });
+        }
+        session.recompute_stats();
+        session
+    }
+
+    #[test]
+    fn policy_reports_and_scrubs_excluded_paths_and_commands() {
+        let policy = UploadPolicy::new(&["*.env", "*secret*", "[invalid"], true);
+        let mut session = session(vec![
+            EventType::FileRead {
+                path: "/repo/src/main.rs".to_string(),
+            },
+            EventType::FileEdit {
+                path: "/repo/config/.env".to_string(),
+                diff: None,
+            },
+            EventType::ShellCommand {
+                command: "cat deploy/secrets.json".to_string(),
+                exit_code: Some(0),
+            },
+        ]);
+
+        let violations = policy.violations(&session);
+        assert_eq!(
+            violations
+                .iter()
+                .map(|v| v.path.as_str())
+                .collect::<Vec<_>>(),
+            ["events[1]", "events[2]"]
+        );
+        assert_eq!(
+            violations[0].message,
+            "event e1 references `/repo/config/.env`, which matches excluded pattern `*.env`"
+        );
+
+        assert_eq!(policy.scrub(&mut session), 2);
+        assert_eq!(session.events.len(), 1);
+        assert_eq!(session.stats.event_count, 1);
+        assert!(UploadPolicy::default().violations(&session).is_empty());
+    }
+
+    #[test]
+    fn team_policy_that_rejects_wins_over_a_scrubbing_server_policy() {
+        let server = UploadPolicy::new(&["*.env"], true);
+        let team = UploadPolicy::from_team(&TeamUploadPolicy {
+            exclude_patterns: vec!["*.pem".to_string()],
+            action: UploadPolicyAction::Reject,
+        });
+        let read = |path: &str| EventType::FileRead {
+            path: path.to_string(),
+        };
+
+        let mut clean = session(vec![read("/repo/.env"), read("/repo/src/lib.rs")]);
+        assert_eq!(UploadPolicy::enforce(&[&server, &team], &mut clean), Ok(1));
+        assert_eq!(clean.events.len(), 1);
+
+        let mut leaky = session(vec![read("/repo/.env"), read("/repo/deploy/key.pem")]);
+        let violations =
+            UploadPolicy::enforce(&[&server, &team], &mut leaky).expect_err("team policy rejects");
+        assert_eq!(
+            violations
+                .iter()
+                .map(|v| v.path.as_str())
+                .collect::<Vec<_>>(),
+            ["events[0]", "events[1]"]
+        );
+        assert_eq!(leaky.events.len(), 2);
+    }
+
+    #[test]
+    fn scrub_drops_tool_results_paired_with_removed_events() {
+        let policy = UploadPolicy::new(&["*.env"], true);
+        let result = |call_id: &str| EventType::ToolResult {
+            name: "Read".to_string(),
+            is_error: false,
+            call_id: Some(call_id.to_string()),
+        };
+        let mut session = session_with_ids(vec![
+            (
+                "call-1".to_string(),
+                EventType::FileRead {
+                    path: "/repo/.env".to_string(),
+                },
+            ),
+            ("call-1-result".to_string(), result("call-1")),
+            (
+                "call-2".to_string(),
+                EventType::FileRead {
+                    path: "/repo/src/lib.rs".to_string(),
+                },
+            ),
+            ("call-2-result".to_string(), result("call-2")),
+        ]);
+
+        assert_eq!(policy.scrub(&mut session), 2);
+        assert_eq!(
+            session
+                .events
+                .iter()
+                .map(|event| event.event_id.as_str())
+                .collect::<Vec<_>>(),
+            ["call-2", "call-2-result"]
+        );
+    }
+}
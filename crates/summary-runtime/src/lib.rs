@@ -1,15 +1,16 @@
 mod provider;
 
 pub use provider::{
-    LocalSummaryProfile, detect_local_summary_profile, generate_summary, generate_text,
+    LocalSummaryProfile, OllamaHealth, check_ollama_health, detect_local_summary_profile,
+    generate_summary, generate_text, list_ollama_models,
 };
 
 use opensession_core::trace::Session;
 use opensession_runtime_config::SummarySettings;
 use opensession_summary::git::{GitSummaryContext, GitSummaryService, ShellGitCommandRunner};
 use opensession_summary::{
-    GitSummaryRequest, SemanticSummaryArtifact, classify_and_summarize_git_context,
-    summarize_session_with_provider,
+    CachedSemanticSummary, GitSummaryRequest, SemanticSummaryArtifact,
+    classify_and_summarize_git_context, summarize_session_with_cache,
 };
 use std::path::Path;
 
@@ -24,6 +25,17 @@ pub async fn summarize_session(
     session: &Session,
     settings: &SummarySettings,
     git_request: Option<&GitSummaryRequest>,
+) -> Result<SemanticSummaryArtifact, String> {
+    summarize_session_cached(session, settings, git_request, None).await
+}
+
+/// Summarize a session, reusing `cached` instead of calling the provider when
+/// the generated prompt is unchanged.
+pub async fn summarize_session_cached(
+    session: &Session,
+    settings: &SummarySettings,
+    git_request: Option<&GitSummaryRequest>,
+    cached: Option<&CachedSemanticSummary>,
 ) -> Result<SemanticSummaryArtifact, String> {
     let git_context = if settings.allows_git_changes_fallback() {
         git_request.and_then(collect_git_context)
@@ -31,7 +43,14 @@ pub async fn summarize_session(
         None
     };
 
-    summarize_session_with_provider(session, settings, git_context, runtime_generate_summary).await
+    summarize_session_with_cache(
+        session,
+        settings,
+        git_context,
+        cached,
+        runtime_generate_summary,
+    )
+    .await
 }
 
 pub async fn summarize_git_commit(
@@ -102,6 +102,105 @@ fn parse_ollama_list_output(raw: &str) -> Vec<String> {
     models
 }
 
+/// Reachability and installed models of an Ollama endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OllamaHealth {
+    pub endpoint: String,
+    pub reachable: bool,
+    pub models: Vec<String>,
+    pub model_installed: bool,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTagsResponse {
+    #[serde(default)]
+    models: Vec<OllamaTagModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTagModel {
+    name: String,
+}
+
+fn ollama_endpoint(endpoint: &str) -> &str {
+    let endpoint = endpoint.trim();
+    if endpoint.is_empty() {
+        DEFAULT_OLLAMA_ENDPOINT
+    } else {
+        endpoint.trim_end_matches('/')
+    }
+}
+
+/// List models installed on the Ollama endpoint (`GET /api/tags`).
+pub async fn list_ollama_models(endpoint: &str) -> Result<Vec<String>, String> {
+    let url = format!("{}/api/tags", ollama_endpoint(endpoint));
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(2))
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(|err| format!("failed to build local summary HTTP client: {err}"))?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|err| format!("failed to reach ollama: {err}"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "ollama model list returned {}",
+            response.status().as_u16()
+        ));
+    }
+    let payload: OllamaTagsResponse = response
+        .json()
+        .await
+        .map_err(|err| format!("failed to decode ollama model list: {err}"))?;
+    Ok(payload.models.into_iter().map(|model| model.name).collect())
+}
+
+/// Check that Ollama answers and whether `model` is installed.
+pub async fn check_ollama_health(endpoint: &str, model: &str) -> OllamaHealth {
+    let endpoint = ollama_endpoint(endpoint).to_string();
+    match list_ollama_models(&endpoint).await {
+        Ok(models) => {
+            let model = model.trim();
+            let model_installed = ollama_model_installed(&models, model);
+            let message = if model.is_empty() {
+                Some("no summary model selected".to_string())
+            } else if !model_installed {
+                Some(format!(
+                    "model `{model}` is not installed; run `ollama pull {model}`"
+                ))
+            } else {
+                None
+            };
+            OllamaHealth {
+                endpoint,
+                reachable: true,
+                models,
+                model_installed,
+                message,
+            }
+        }
+        Err(error) => OllamaHealth {
+            message: Some(format!(
+                "{error}; start it with `ollama serve` or check the endpoint"
+            )),
+            endpoint,
+            reachable: false,
+            models: Vec::new(),
+            model_installed: false,
+        },
+    }
+}
+
+fn ollama_model_installed(models: &[String], model: &str) -> bool {
+    !model.is_empty()
+        && models
+            .iter()
+            .any(|installed| installed == model || installed.strip_suffix(":latest") == Some(model))
+}
+
 #[derive(Debug, Serialize)]
 struct OllamaGenerateRequest<'a> {
     model: &'a str,
@@ -142,12 +241,10 @@ async fn generate_text_with_ollama(
     settings: &SummarySettings,
     prompt: &str,
 ) -> Result<String, String> {
-    let endpoint = if settings.provider.endpoint.trim().is_empty() {
-        DEFAULT_OLLAMA_ENDPOINT
-    } else {
-        settings.provider.endpoint.trim()
-    };
-    let url = format!("{}/api/generate", endpoint.trim_end_matches('/'));
+    let url = format!(
+        "{}/api/generate",
+        ollama_endpoint(&settings.provider.endpoint)
+    );
     let model = settings.provider.model.trim();
     if model.is_empty() {
         return Err("ollama model is empty".to_string());
@@ -333,7 +430,8 @@ fn run_command_with_timeout(mut command: Command, timeout: Duration) -> Result<O
 #[cfg(test)]
 mod tests {
     use super::{
-        LocalSummaryProfile, SummaryProvider, first_available_profile, parse_ollama_list_output,
+        LocalSummaryProfile, OllamaTagsResponse, SummaryProvider, first_available_profile,
+        ollama_endpoint, ollama_model_installed, parse_ollama_list_output,
     };
 
     #[test]
@@ -357,6 +455,29 @@ qwen2.5-coder:7b          2b0496514337    4.7 GB    1 day ago
         assert!(parse_ollama_list_output(output).is_empty());
     }
 
+    #[test]
+    fn ollama_tags_response_lists_models_and_matches_latest_tag() {
+        let payload: OllamaTagsResponse = serde_json::from_str(
+            r#"{"models":[{"name":"llama3.2:3b","size":1},{"name":"mistral:latest"}]}"#,
+        )
+        .expect("parse tags");
+        let models: Vec<String> = payload.models.into_iter().map(|m| m.name).collect();
+
+        assert!(ollama_model_installed(&models, "llama3.2:3b"));
+        assert!(ollama_model_installed(&models, "mistral"));
+        assert!(!ollama_model_installed(&models, "llama3.2"));
+        assert!(!ollama_model_installed(&models, ""));
+    }
+
+    #[test]
+    fn ollama_endpoint_defaults_and_trims_trailing_slash() {
+        assert_eq!(ollama_endpoint("  "), "http://127.0.0.1:11434");
+        assert_eq!(
+            ollama_endpoint("http://gpu-box:11434/"),
+            "http://gpu-box:11434"
+        );
+    }
+
     #[test]
     fn first_available_profile_preserves_provider_priority() {
         let claude = Some(LocalSummaryProfile {
@@ -95,6 +95,52 @@ pub struct SemanticSummaryArtifact {
     pub error: Option<String>,
 }
 
+/// A previously generated provider summary, reused when the prompt it was built
+/// from (same session window, settings and model) is unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedSemanticSummary {
+    pub provider: SummaryProvider,
+    pub model: String,
+    pub prompt_fingerprint: String,
+    pub summary: SemanticSummary,
+}
+
+impl CachedSemanticSummary {
+    /// Rebuild a cache entry from stored labels/JSON.
+    ///
+    /// Returns `None` for heuristic fallbacks and rows without a fingerprint,
+    /// which are cheap to regenerate and must not shadow a provider call.
+    pub fn from_stored(
+        provider: &str,
+        model: Option<&str>,
+        generation_kind: &str,
+        prompt_fingerprint: Option<&str>,
+        summary_json: &str,
+    ) -> Option<Self> {
+        let generation_kind: SummaryGenerationKind =
+            serde_json::from_value(serde_json::Value::String(generation_kind.to_string())).ok()?;
+        if generation_kind != SummaryGenerationKind::Provider {
+            return None;
+        }
+        let prompt_fingerprint = prompt_fingerprint
+            .map(str::trim)
+            .filter(|value| !value.is_empty())?;
+        Some(Self {
+            provider: serde_json::from_value(serde_json::Value::String(provider.to_string()))
+                .ok()?,
+            model: model.unwrap_or_default().to_string(),
+            prompt_fingerprint: prompt_fingerprint.to_string(),
+            summary: serde_json::from_str(summary_json).ok()?,
+        })
+    }
+
+    fn matches(&self, settings: &SummarySettings, prompt_fingerprint: &str) -> bool {
+        self.provider == settings.provider.id
+            && self.model == settings.provider.model
+            && self.prompt_fingerprint == prompt_fingerprint
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GitSummaryRequest {
     pub repo_root: PathBuf,
@@ -126,6 +172,21 @@ pub async fn summarize_session_with_provider<Generate>(
     git_context: Option<GitSummaryContext>,
     generate_summary: Generate,
 ) -> Result<SemanticSummaryArtifact, String>
+where
+    Generate: for<'a> Fn(&'a SummarySettings, &'a str) -> SummaryGenerateFuture<'a>,
+{
+    summarize_session_with_cache(session, settings, git_context, None, generate_summary).await
+}
+
+/// Like [`summarize_session_with_provider`], but skips the provider call when
+/// `cached` was generated from the same prompt by the same provider/model.
+pub async fn summarize_session_with_cache<Generate>(
+    session: &Session,
+    settings: &SummarySettings,
+    git_context: Option<GitSummaryContext>,
+    cached: Option<&CachedSemanticSummary>,
+    generate_summary: Generate,
+) -> Result<SemanticSummaryArtifact, String>
 where
     Generate: for<'a> Fn(&'a SummarySettings, &'a str) -> SummaryGenerateFuture<'a>,
 {
@@ -147,7 +208,7 @@ where
         }
     }
 
-    summarize_from_signals(signals, settings, generate_summary).await
+    summarize_from_signals(signals, settings, cached, generate_summary).await
 }
 
 pub async fn classify_and_summarize_git_context<Generate>(
@@ -161,6 +222,7 @@ where
     summarize_from_signals(
         summary_signals_from_git(context)?,
         settings,
+        None,
         generate_summary,
     )
     .await
@@ -185,6 +247,7 @@ impl SummarySignals {
 async fn summarize_from_signals<Generate>(
     signals: SummarySignals,
     settings: &SummarySettings,
+    cached: Option<&CachedSemanticSummary>,
     generate_summary: Generate,
 ) -> Result<SemanticSummaryArtifact, String>
 where
@@ -259,6 +322,20 @@ where
         });
     }
 
+    if let Some(cached) = cached.filter(|cached| cached.matches(settings, &prompt_fingerprint)) {
+        return Ok(SemanticSummaryArtifact {
+            summary: cached.summary.clone(),
+            source_kind: signals.source_kind,
+            generation_kind: SummaryGenerationKind::Provider,
+            provider: settings.provider.id.clone(),
+            model: settings.provider.model.clone(),
+            prompt_fingerprint,
+            diff_tree,
+            source_details: signals.source_details,
+            error: None,
+        });
+    }
+
     match generate_summary(settings, &prompt).await {
         Ok(summary) => Ok(SemanticSummaryArtifact {
             summary,
@@ -582,9 +659,9 @@ fn parse_diff_hunks(diff: &str) -> Vec<DiffHunkNode> {
 #[cfg(test)]
 mod tests {
     use super::{
-        DiffLayerNode, SummaryGenerationKind, SummarySourceKind, build_diff_tree,
-        default_event_snippet, heuristic_summary, parse_diff_hunks,
-        summarize_session_with_provider,
+        CachedSemanticSummary, DiffLayerNode, SummaryGenerationKind, SummarySourceKind,
+        build_diff_tree, default_event_snippet, heuristic_summary, parse_diff_hunks,
+        summarize_session_with_cache, summarize_session_with_provider,
     };
     use crate::git::GitSummaryContext;
     use crate::types::HailCompactFileChange;
@@ -784,4 +861,61 @@ mod tests {
         );
         assert_eq!(artifact.error.as_deref(), Some("codex exec failed"));
     }
+
+    #[tokio::test]
+    async fn summarize_session_reuses_cached_summary_for_same_prompt() {
+        let session = session_with_file_edit("src/auth.rs", "@@ -1 +1 @@\n-a\n+b\n");
+        let mut settings = SummarySettings::default();
+        settings.provider.id = SummaryProvider::Ollama;
+        settings.provider.model = "llama3.2:3b".to_string();
+
+        let fresh = summarize_session_with_provider(&session, &settings, None, |_, _| {
+            Box::pin(async {
+                Ok(crate::provider::SemanticSummary {
+                    changes: "Fresh provider summary".to_string(),
+                    auth_security: "none detected".to_string(),
+                    layer_file_changes: Vec::new(),
+                })
+            })
+        })
+        .await
+        .expect("summarize");
+        let cached = CachedSemanticSummary::from_stored(
+            "ollama",
+            Some("llama3.2:3b"),
+            "provider",
+            Some(&fresh.prompt_fingerprint),
+            &serde_json::to_string(&fresh.summary).expect("summary json"),
+        )
+        .expect("cache entry");
+
+        let reused =
+            summarize_session_with_cache(&session, &settings, None, Some(&cached), |_, _| {
+                Box::pin(async { unreachable!("cached summary should skip the provider") })
+            })
+            .await
+            .expect("summarize");
+        assert_eq!(reused.summary.changes, "Fresh provider summary");
+        assert_eq!(reused.generation_kind, SummaryGenerationKind::Provider);
+
+        settings.provider.model = "qwen2.5:7b".to_string();
+        let regenerated =
+            summarize_session_with_cache(&session, &settings, None, Some(&cached), |_, _| {
+                Box::pin(async { Err("provider called".to_string()) })
+            })
+            .await
+            .expect("summarize");
+        assert_eq!(regenerated.error.as_deref(), Some("provider called"));
+
+        assert!(
+            CachedSemanticSummary::from_stored(
+                "ollama",
+                Some("llama3.2:3b"),
+                "heuristic_fallback",
+                Some(&fresh.prompt_fingerprint),
+                "{}",
+            )
+            .is_none()
+        );
+    }
 }
@@ -9,6 +9,8 @@ pub struct WorkerConfig {
     pub jwt_secret: String,
     pub oauth_providers: Vec<OAuthProviderConfig>,
     pub credential_keyring: Option<CredentialKeyring>,
+    /// `OPENSESSION_PUBLIC_FEED_ENABLED`; when false, listing needs a signed-in caller.
+    pub public_feed_enabled: bool,
 }
 
 impl WorkerConfig {
@@ -19,6 +21,13 @@ impl WorkerConfig {
         let jwt_secret = env_trimmed(env, "JWT_SECRET").unwrap_or_default();
         let oauth_providers = load_oauth_providers(env);
         let credential_keyring = load_credential_keyring(env);
+        let public_feed_enabled = opensession_api::deploy::parse_bool_flag(
+            env.var(opensession_api::deploy::ENV_PUBLIC_FEED_ENABLED)
+                .ok()
+                .map(|value| value.to_string())
+                .as_deref(),
+            true,
+        );
 
         Self {
             base_url,
@@ -26,6 +35,7 @@ impl WorkerConfig {
             jwt_secret,
             oauth_providers,
             credential_keyring,
+            public_feed_enabled,
         }
     }
 
@@ -94,6 +94,7 @@ pub async fn main(req: Request, env: Env, _ctx: worker::Context) -> Result<Respo
         .get_async("/api/health", routes::health::handle)
         .get_async("/api/capabilities", routes::capabilities::handle)
         .post_async("/api/parse/preview", routes::parse::preview)
+        .post_async("/api/query", routes::query::run)
         // Public sessions (read-only)
         .get_async("/api/sessions", routes::sessions::list)
         .get_async("/api/sessions/repos", routes::sessions::list_repos)
@@ -498,6 +498,7 @@ pub async fn me(req: Request, ctx: RouteContext<()>) -> Result<Response> {
             email: user.email,
             avatar_url,
             oauth_providers,
+            teams: Vec::new(),
         })
     }
     .await;
@@ -526,13 +527,13 @@ pub async fn issue_api_key(req: Request, ctx: RouteContext<()>) -> Result<Respon
 
         d1_run(
             &d1,
-            dbq::api_keys::move_active_to_grace(&user.user_id, &grace_until),
+            dbq::api_keys::move_active_to_grace(&user.user_id, &grace_until, None),
             "move active keys to grace",
         )
         .await?;
         d1_run(
             &d1,
-            dbq::api_keys::insert_active(&key_id, &user.user_id, &key_hash, &key_prefix),
+            dbq::api_keys::insert_active(&key_id, &user.user_id, &key_hash, &key_prefix, None),
             "insert active api key",
         )
         .await?;
@@ -24,6 +24,7 @@ mod tests {
             jwt_secret: "secret".to_string(),
             oauth_providers: Vec::new(),
             credential_keyring: None,
+            public_feed_enabled: true,
         };
 
         let caps = capabilities_from_config(&config);
@@ -3,4 +3,5 @@ pub mod capabilities;
 pub mod docs;
 pub mod health;
 pub mod parse;
+pub mod query;
 pub mod sessions;
@@ -0,0 +1,73 @@
+use worker::*;
+
+use opensession_api::service;
+use opensession_api::{
+    ApiError, QueryOperation, QueryRequest, QueryResponse, QueryResult, ServiceError,
+};
+
+use crate::config::WorkerConfig;
+use crate::error::IntoErrResponse;
+use crate::routes::sessions::{
+    check_list_access, fetch_repo_names, fetch_session_detail, fetch_session_list,
+};
+use crate::storage;
+
+/// POST /api/query — run several typed reads in one request.
+///
+/// Same operations and access rules as the REST reads; a failed operation is
+/// reported in its result slot and never aborts the rest of the batch.
+pub async fn run(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let body: QueryRequest = match req.json().await {
+        Ok(body) => body,
+        Err(_) => {
+            return ServiceError::BadRequest("invalid query request body".into())
+                .into_err_response();
+        }
+    };
+    if let Err(err) = service::validate_query_size(body.operations.len()) {
+        return err.into_err_response();
+    }
+
+    let config = WorkerConfig::from_env(&ctx.env);
+    let d1 = storage::get_d1(&ctx.env)?;
+    // Only resolved once, and only when some operation needs the feed gate.
+    let needs_feed = body
+        .operations
+        .iter()
+        .any(|op| !matches!(op, QueryOperation::Session { .. }));
+    let list_access = if needs_feed {
+        check_list_access(&req, &d1, &config).await
+    } else {
+        Ok(())
+    };
+    let mut results = Vec::with_capacity(body.operations.len());
+    for operation in body.operations {
+        let result = run_operation(&d1, &list_access, operation)
+            .await
+            .unwrap_or_else(|err| QueryResult::Error(ApiError::from(&err)));
+        results.push(result);
+    }
+    Response::from_json(&QueryResponse { results })
+}
+
+async fn run_operation(
+    d1: &D1Database,
+    list_access: &std::result::Result<(), ServiceError>,
+    operation: QueryOperation,
+) -> std::result::Result<QueryResult, ServiceError> {
+    match operation {
+        QueryOperation::Sessions(query) => {
+            list_access.clone()?;
+            let sessions = fetch_session_list(d1, &query).await?;
+            Ok(QueryResult::Sessions(Box::new(sessions)))
+        }
+        QueryOperation::Session { id } => {
+            let detail = fetch_session_detail(d1, &id).await?;
+            Ok(QueryResult::Session(Box::new(detail)))
+        }
+        QueryOperation::SessionRepos => {
+            list_access.clone()?;
+            Ok(QueryResult::SessionRepos(fetch_repo_names(d1).await?))
+        }
+    }
+}
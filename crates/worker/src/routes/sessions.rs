@@ -2,14 +2,16 @@ use worker::*;
 
 use std::collections::HashMap;
 
-use opensession_api::db;
+use opensession_api::service::{self, RawBodySource};
 use opensession_api::{
-    ServiceError, SessionDetail, SessionLink, SessionListQuery, SessionListResponse,
-    SessionRepoListResponse, SessionSummary,
+    SESSION_DELTA_HEADER, ServiceError, SessionDetail, SessionKind, SessionLink, SessionListQuery,
+    SessionListResponse, SessionRepoListResponse, SessionSummary, db,
 };
 
+use crate::config::WorkerConfig;
 use crate::db_helpers::values_to_js;
 use crate::error::IntoErrResponse;
+use crate::routes::auth::authenticate_optional;
 use crate::storage;
 
 const PUBLIC_LIST_CACHE_CONTROL: &str = "public, max-age=30";
@@ -56,6 +58,9 @@ impl From<storage::SessionRow> for SessionSummary {
             max_active_agents: s.max_active_agents,
             session_score: s.session_score,
             score_plugin: s.score_plugin,
+            device_id: s.device_id,
+            verified_origin: s.verified_origin,
+            kind: SessionKind::parse(s.session_kind.as_deref().unwrap_or_default()),
         }
     }
 }
@@ -72,11 +77,19 @@ fn parse_session_list_query(query_pairs: &[(String, String)]) -> SessionListQuer
             .get("per_page")
             .and_then(|v| v.parse().ok())
             .unwrap_or(20),
+        cursor: params.get("cursor").map(|v| (*v).to_string()),
+        limit: params.get("limit").and_then(|v| v.parse().ok()),
         search: params.get("search").map(|v| (*v).to_string()),
         tool: params.get("tool").map(|v| (*v).to_string()),
         git_repo_name: params.get("git_repo_name").map(|v| (*v).to_string()),
         sort: parse_query_enum(&params, "sort"),
         time_range: parse_query_enum(&params, "time_range"),
+        model: params.get("model").map(|v| (*v).to_string()),
+        since: params.get("since").map(|v| (*v).to_string()),
+        before: params.get("before").map(|v| (*v).to_string()),
+        touches: params.get("touches").map(|v| (*v).to_string()),
+        has_errors: params.get("has_errors").and_then(|v| v.parse().ok()),
+        git_commit: params.get("git_commit").map(|v| (*v).to_string()),
     }
 }
 
@@ -100,9 +113,18 @@ fn public_feed_cache_key(query_pairs: &[(String, String)]) -> String {
     format!("https://cache.opensession.io/api/sessions?{encoded}")
 }
 
-fn worker_public_feed_enabled() -> bool {
-    // Worker deployment is public-feed-first by product policy.
-    true
+/// Shared feed gate: open when the public feed is enabled, otherwise only to
+/// a signed-in caller.
+pub(crate) async fn check_list_access(
+    req: &Request,
+    d1: &D1Database,
+    config: &WorkerConfig,
+) -> std::result::Result<(), ServiceError> {
+    if config.public_feed_enabled {
+        return Ok(());
+    }
+    let user = authenticate_optional(req, d1, config).await?;
+    service::check_session_list_access(false, user.is_some())
 }
 
 /// GET /api/sessions — list sessions (public, paginated, filtered)
@@ -110,7 +132,11 @@ pub async fn list(req: Request, ctx: RouteContext<()>) -> Result<Response> {
     let url = req.url()?;
     let query_pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
     let q = parse_session_list_query(&query_pairs);
-    let public_feed_enabled = worker_public_feed_enabled();
+    let config = WorkerConfig::from_env(&ctx.env);
+    let d1 = storage::get_d1(&ctx.env)?;
+    if let Err(err) = check_list_access(&req, &d1, &config).await {
+        return err.into_err_response();
+    }
     let has_auth_header = req.headers().get("Authorization").ok().flatten().is_some();
     let has_session_cookie = req
         .headers()
@@ -118,8 +144,8 @@ pub async fn list(req: Request, ctx: RouteContext<()>) -> Result<Response> {
         .ok()
         .flatten()
         .is_some_and(|cookie| cookie.contains("session="));
-    let cacheable =
-        public_feed_enabled && q.is_public_feed_cacheable(has_auth_header, has_session_cookie);
+    let cacheable = config.public_feed_enabled
+        && q.is_public_feed_cacheable(has_auth_header, has_session_cookie);
     let cache_key = cacheable.then(|| public_feed_cache_key(&query_pairs));
 
     if let Some(key) = cache_key.as_deref() {
@@ -129,43 +155,11 @@ pub async fn list(req: Request, ctx: RouteContext<()>) -> Result<Response> {
         }
     }
 
-    let built = db::sessions::list(&q);
-    let d1 = storage::get_d1(&ctx.env)?;
-
-    let count_params = values_to_js(&built.count_query.1);
-    let count_stmt = d1.prepare(&built.count_query.0).bind(&count_params)?;
-
-    let select_params = values_to_js(&built.select_query.1);
-    let select_stmt = d1.prepare(&built.select_query.0).bind(&select_params)?;
-
-    // Run count + select in one D1 round-trip.
-    let batch = d1.batch(vec![count_stmt, select_stmt]).await?;
-    let count_result = batch
-        .first()
-        .ok_or_else(|| Error::from("missing count result in D1 batch"))?;
-    let rows_result = batch
-        .get(1)
-        .ok_or_else(|| Error::from("missing rows result in D1 batch"))?;
-
-    let total = count_result
-        .results::<storage::CountRow>()?
-        .into_iter()
-        .next()
-        .map(|r| r.count)
-        .unwrap_or(0);
-
-    let sessions: Vec<SessionSummary> = rows_result
-        .results::<storage::SessionRow>()?
-        .into_iter()
-        .map(SessionSummary::from)
-        .collect();
-
-    let mut resp = Response::from_json(&SessionListResponse {
-        sessions,
-        total,
-        page: built.page,
-        per_page: built.per_page,
-    })?;
+    let payload = match fetch_session_list(&d1, &q).await {
+        Ok(payload) => payload,
+        Err(err) => return err.into_err_response(),
+    };
+    let mut resp = Response::from_json(&payload)?;
 
     if cacheable {
         let _ = resp
@@ -190,20 +184,16 @@ pub async fn list(req: Request, ctx: RouteContext<()>) -> Result<Response> {
 }
 
 /// GET /api/sessions/repos — list known repository names.
-pub async fn list_repos(_req: Request, ctx: RouteContext<()>) -> Result<Response> {
+pub async fn list_repos(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let config = WorkerConfig::from_env(&ctx.env);
     let d1 = storage::get_d1(&ctx.env)?;
-    let (sql, values) = db::sessions::list_repo_names();
-    let repos: Vec<String> = d1
-        .prepare(&sql)
-        .bind(&values_to_js(&values))?
-        .all()
-        .await?
-        .results::<storage::RepoNameRow>()?
-        .into_iter()
-        .map(|row| row.git_repo_name)
-        .collect();
-
-    Response::from_json(&SessionRepoListResponse { repos })
+    if let Err(err) = check_list_access(&req, &d1, &config).await {
+        return err.into_err_response();
+    }
+    match fetch_repo_names(&d1).await {
+        Ok(repos) => Response::from_json(&repos),
+        Err(err) => err.into_err_response(),
+    }
 }
 
 /// GET /api/sessions/:id — get session detail
@@ -211,39 +201,14 @@ pub async fn get(_req: Request, ctx: RouteContext<()>) -> Result<Response> {
     let id = ctx.param("id").ok_or_else(|| Error::from("Missing id"))?;
 
     let d1 = storage::get_d1(&ctx.env)?;
-
-    let (sql, values) = db::sessions::get_by_id(id);
-    let row = d1
-        .prepare(&sql)
-        .bind(&values_to_js(&values))?
-        .first::<storage::SessionRow>(None)
-        .await?;
-
-    match row {
-        Some(s) => {
-            let summary = SessionSummary::from(s);
-
-            // Fetch linked sessions
-            let (sql, values) = db::sessions::links_by_session(id);
-            let linked_sessions: Vec<SessionLink> = d1
-                .prepare(&sql)
-                .bind(&values_to_js(&values))?
-                .all()
-                .await?
-                .results::<SessionLink>()
-                .unwrap_or_default();
-
-            Response::from_json(&SessionDetail {
-                summary,
-                linked_sessions,
-            })
-        }
-        None => ServiceError::NotFound("session not found".into()).into_err_response(),
+    match fetch_session_detail(&d1, id).await {
+        Ok(detail) => Response::from_json(&detail),
+        Err(err) => err.into_err_response(),
     }
 }
 
 /// GET /api/sessions/:id/raw — get the full HAIL JSONL from R2 or redirect to body_url
-pub async fn get_raw(_req: Request, ctx: RouteContext<()>) -> Result<Response> {
+pub async fn get_raw(req: Request, ctx: RouteContext<()>) -> Result<Response> {
     let id = ctx.param("id").ok_or_else(|| Error::from("Missing id"))?;
 
     let d1 = storage::get_d1(&ctx.env)?;
@@ -263,37 +228,158 @@ pub async fn get_raw(_req: Request, ctx: RouteContext<()>) -> Result<Response> {
         }
     };
 
-    // If body_url is set, redirect to external storage
-    if let Some(ref url) = row.body_url {
-        if !url.is_empty() {
-            let headers = Headers::new();
-            headers.set("Location", url)?;
-            return Ok(Response::empty()?.with_status(302).with_headers(headers));
-        }
-    }
+    let storage_key =
+        match service::resolve_raw_body_source(&row.body_storage_key, row.body_url.as_deref()) {
+            Ok(RawBodySource::Redirect(url)) => {
+                let headers = Headers::new();
+                headers.set("Location", &url)?;
+                return Ok(Response::empty()?.with_status(302).with_headers(headers));
+            }
+            Ok(RawBodySource::Stored(key)) => key,
+            Err(err) => return err.into_err_response(),
+        };
 
     // Fetch from R2
-    let body = storage::get_session_body(&ctx.env, &row.body_storage_key).await?;
+    let body = storage::get_session_body(&ctx.env, &storage_key).await?;
     match body {
         Some(bytes) => {
+            let etag = service::body_etag(&bytes);
             let headers = Headers::new();
+            headers.set("ETag", &etag)?;
+            let not_modified = req
+                .headers()
+                .get("If-None-Match")
+                .ok()
+                .flatten()
+                .is_some_and(|v| service::if_none_match_hits(&v, &etag));
+            if not_modified {
+                return Ok(Response::empty()?.with_status(304).with_headers(headers));
+            }
             headers.set("Content-Type", "application/jsonl")?;
-            headers.set(
-                "Content-Disposition",
-                "attachment; filename=\"session.hail.jsonl\"",
-            )?;
+
+            let since_event = req
+                .url()?
+                .query_pairs()
+                .find(|(k, _)| k == "since_event")
+                .and_then(|(_, v)| v.parse::<usize>().ok());
+            if let Some(since_event) = since_event {
+                let delta = match service::raw_body_delta(&bytes, since_event) {
+                    Ok(delta) => delta,
+                    Err(err) => return err.into_err_response(),
+                };
+                headers.set(SESSION_DELTA_HEADER, &since_event.to_string())?;
+                return Ok(Response::ok(delta)?.with_headers(headers));
+            }
+
+            headers.set("Content-Disposition", service::RAW_BODY_CONTENT_DISPOSITION)?;
             Ok(Response::from_bytes(bytes)?.with_headers(headers))
         }
         None => ServiceError::NotFound("session body not found".into()).into_err_response(),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::worker_public_feed_enabled;
+fn d1_error(err: Error) -> ServiceError {
+    ServiceError::Internal(format!("d1: {err}"))
+}
 
-    #[test]
-    fn worker_public_feed_is_forced_enabled() {
-        assert!(worker_public_feed_enabled());
-    }
+/// One page of the session list, counted and selected in a single D1 round trip.
+pub(crate) async fn fetch_session_list(
+    d1: &D1Database,
+    q: &SessionListQuery,
+) -> std::result::Result<SessionListResponse, ServiceError> {
+    let built = db::sessions::list(q)?;
+
+    let count_params = values_to_js(&built.count_query.1);
+    let count_stmt = d1
+        .prepare(&built.count_query.0)
+        .bind(&count_params)
+        .map_err(d1_error)?;
+
+    let select_params = values_to_js(&built.select_query.1);
+    let select_stmt = d1
+        .prepare(&built.select_query.0)
+        .bind(&select_params)
+        .map_err(d1_error)?;
+
+    let batch = d1
+        .batch(vec![count_stmt, select_stmt])
+        .await
+        .map_err(d1_error)?;
+    let (Some(count_result), Some(rows_result)) = (batch.first(), batch.get(1)) else {
+        return Err(ServiceError::Internal(
+            "missing session list results in D1 batch".into(),
+        ));
+    };
+
+    let total = count_result
+        .results::<storage::CountRow>()
+        .map_err(d1_error)?
+        .into_iter()
+        .next()
+        .map(|r| r.count)
+        .unwrap_or(0);
+
+    let sessions: Vec<SessionSummary> = rows_result
+        .results::<storage::SessionRow>()
+        .map_err(d1_error)?
+        .into_iter()
+        .map(SessionSummary::from)
+        .collect();
+
+    Ok(built.into_response(total, sessions))
+}
+
+/// Known repository names.
+pub(crate) async fn fetch_repo_names(
+    d1: &D1Database,
+) -> std::result::Result<SessionRepoListResponse, ServiceError> {
+    let (sql, values) = db::sessions::list_repo_names();
+    let repos: Vec<String> = d1
+        .prepare(&sql)
+        .bind(&values_to_js(&values))
+        .map_err(d1_error)?
+        .all()
+        .await
+        .map_err(d1_error)?
+        .results::<storage::RepoNameRow>()
+        .map_err(d1_error)?
+        .into_iter()
+        .map(|row| row.git_repo_name)
+        .collect();
+    Ok(SessionRepoListResponse { repos })
+}
+
+/// A session's detail with its linked sessions, or `NotFound`.
+pub(crate) async fn fetch_session_detail(
+    d1: &D1Database,
+    id: &str,
+) -> std::result::Result<SessionDetail, ServiceError> {
+    let (sql, values) = db::sessions::get_by_id(id);
+    let row = d1
+        .prepare(&sql)
+        .bind(&values_to_js(&values))
+        .map_err(d1_error)?
+        .first::<storage::SessionRow>(None)
+        .await
+        .map_err(d1_error)?
+        .ok_or_else(|| ServiceError::NotFound("session not found".into()))?;
+
+    let (sql, values) = db::sessions::links_by_session(id);
+    let linked_sessions: Vec<SessionLink> = d1
+        .prepare(&sql)
+        .bind(&values_to_js(&values))
+        .map_err(d1_error)?
+        .all()
+        .await
+        .map_err(d1_error)?
+        .results::<SessionLink>()
+        .unwrap_or_default();
+
+    Ok(SessionDetail {
+        summary: SessionSummary::from(row),
+        linked_sessions,
+        body_sha256: None,
+        body_signature: None,
+        storage_class: Default::default(),
+    })
 }
@@ -98,6 +98,12 @@ pub struct SessionRow {
     pub session_score: i64,
     #[serde(default = "default_score_plugin")]
     pub score_plugin: String,
+    #[serde(default)]
+    pub device_id: Option<String>,
+    #[serde(default, deserialize_with = "bool_from_d1")]
+    pub verified_origin: bool,
+    #[serde(default)]
+    pub session_kind: Option<String>,
 }
 
 fn default_max_active_agents() -> i64 {
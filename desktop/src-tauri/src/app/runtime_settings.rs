@@ -13,7 +13,8 @@ use opensession_api::{
     DesktopRuntimeSummarySettings, DesktopRuntimeSummaryStorageSettings,
     DesktopRuntimeSummaryUiConstraints, DesktopRuntimeVectorSearchSettings,
     DesktopSummaryBatchExecutionMode, DesktopSummaryBatchScope, DesktopSummaryOutputShape,
-    DesktopSummaryProviderDetectResponse, DesktopSummaryProviderId,
+    DesktopSummaryProviderDetectResponse, DesktopSummaryProviderHealthRequest,
+    DesktopSummaryProviderHealthResponse, DesktopSummaryProviderId,
     DesktopSummaryProviderTransport, DesktopSummaryResponseStyle, DesktopSummarySourceMode,
     DesktopSummaryStorageBackend, DesktopSummaryTriggerMode, DesktopVectorChunkingMode,
     DesktopVectorSearchGranularity, DesktopVectorSearchProvider,
@@ -630,3 +631,19 @@ pub(crate) fn desktop_detect_summary_provider() -> DesktopSummaryProviderDetectR
         endpoint: None,
     }
 }
+
+#[tauri::command]
+pub(crate) async fn desktop_summary_provider_health(
+    request: DesktopSummaryProviderHealthRequest,
+) -> DesktopSummaryProviderHealthResponse {
+    let model = request.model.trim().to_string();
+    let health = opensession_summary_runtime::check_ollama_health(&request.endpoint, &model).await;
+    DesktopSummaryProviderHealthResponse {
+        endpoint: health.endpoint,
+        model,
+        reachable: health.reachable,
+        model_installed: health.model_installed,
+        models: health.models,
+        message: health.message,
+    }
+}
@@ -2,8 +2,8 @@ use crate::app::session_query::{SearchMode, build_local_filter_with_mode};
 use crate::app::vector::list_sessions_with_vector_rank;
 use crate::{DesktopApiResult, desktop_error, open_local_db};
 use opensession_api::{
-    DesktopSessionListQuery, LinkType, SessionDetail, SessionLink, SessionListResponse,
-    SessionRepoListResponse, SessionSummary,
+    DesktopSessionListQuery, LinkType, SessionDetail, SessionKind, SessionLink,
+    SessionListResponse, SessionRepoListResponse, SessionSummary,
 };
 use opensession_core::session::{is_auxiliary_session, working_directory};
 use opensession_core::trace::Session as HailSession;
@@ -114,10 +114,11 @@ fn refresh_local_session_index(db: &LocalDb) {
 }
 
 pub(crate) fn session_summary_from_local_row(row: LocalSessionRow) -> SessionSummary {
+    let session_score = row.session_score;
     session_summary_from_local_row_with_score(
         row,
-        0,
-        opensession_core::scoring::DEFAULT_SCORE_PLUGIN,
+        session_score,
+        opensession_core::scoring::USEFULNESS_SCORE_PLUGIN,
     )
 }
 
@@ -157,6 +158,9 @@ pub(crate) fn session_summary_from_local_row_with_score(
         max_active_agents: row.max_active_agents,
         session_score,
         score_plugin: score_plugin.to_string(),
+        device_id: None,
+        verified_origin: false,
+        kind: SessionKind::Full,
     }
 }
 
@@ -227,15 +231,26 @@ pub(crate) fn normalize_session_body_to_hail_jsonl(
     session_to_hail_jsonl(preview.session)
 }
 
-fn read_source_session_text(source_path: &str) -> DesktopApiResult<String> {
-    std::fs::read_to_string(source_path).map_err(|error| {
+/// Read the session source file, falling back to its source archive when the
+/// file was moved there by `opensession archive run`.
+fn read_source_session_text(
+    db: &LocalDb,
+    session_id: &str,
+    source_path: &str,
+) -> DesktopApiResult<String> {
+    let unavailable = |cause: String| {
         desktop_error(
             "desktop.session_source_unavailable",
             404,
             format!("session source file is unavailable ({source_path})"),
-            Some(json!({ "cause": error.to_string(), "source_path": source_path })),
+            Some(json!({ "cause": cause, "source_path": source_path })),
         )
-    })
+    };
+    match db.read_session_source(session_id) {
+        Ok(Some(bytes)) => String::from_utf8(bytes).map_err(|error| unavailable(error.to_string())),
+        Ok(None) => Err(unavailable("source file not found".to_string())),
+        Err(error) => Err(unavailable(format!("{error:#}"))),
+    }
 }
 
 pub(crate) fn load_normalized_session_body(
@@ -278,7 +293,7 @@ pub(crate) fn load_normalized_session_body(
     }
 
     if let Some(source_path) = source_path {
-        let source_body = read_source_session_text(&source_path)?;
+        let source_body = read_source_session_text(db, session_id, &source_path)?;
         let normalized = normalize_session_body_to_hail_jsonl(&source_body, Some(&source_path))?;
         if let Err(error) = db.cache_body(session_id, source_body.as_bytes()) {
             eprintln!("failed to cache normalized session source for {session_id}: {error}");
@@ -336,6 +351,7 @@ pub(crate) fn desktop_list_sessions(
         total,
         page,
         per_page,
+        next_cursor: None,
     })
 }
 
@@ -392,6 +408,9 @@ pub(crate) fn desktop_get_session_detail(id: String) -> DesktopApiResult<Session
     Ok(SessionDetail {
         summary: session_summary_from_local_row(row),
         linked_sessions: links,
+        body_sha256: None,
+        body_signature: None,
+        storage_class: Default::default(),
     })
 }
 
@@ -25,6 +25,7 @@ fn map_sort_order(sort: Option<&str>) -> LocalSortOrder {
     match sort.unwrap_or_default() {
         "popular" => LocalSortOrder::Popular,
         "longest" => LocalSortOrder::Longest,
+        "best" => LocalSortOrder::Best,
         _ => LocalSortOrder::Recent,
     }
 }
@@ -896,6 +896,7 @@ pub(crate) fn list_sessions_with_vector_rank(
         total: response.total_candidates as i64,
         page,
         per_page,
+        next_cursor: None,
     })
 }
 
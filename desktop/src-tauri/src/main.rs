@@ -15,7 +15,8 @@ use app::lifecycle_cleanup::{
     desktop_lifecycle_cleanup_status, maybe_start_lifecycle_cleanup_loop,
 };
 use app::runtime_settings::{
-    desktop_detect_summary_provider, desktop_get_runtime_settings, desktop_update_runtime_settings,
+    desktop_detect_summary_provider, desktop_get_runtime_settings, desktop_summary_provider_health,
+    desktop_update_runtime_settings,
 };
 use app::session_access::{
     desktop_get_session_detail, desktop_get_session_raw, desktop_list_repos, desktop_list_sessions,
@@ -91,21 +92,7 @@ pub(crate) fn enum_label<T: serde::Serialize>(value: &T) -> String {
 }
 
 fn open_local_db() -> DesktopApiResult<LocalDb> {
-    let custom_path = std::env::var("OPENSESSION_LOCAL_DB_PATH")
-        .ok()
-        .and_then(|value| {
-            let trimmed = value.trim().to_string();
-            (!trimmed.is_empty()).then_some(trimmed)
-        })
-        .map(PathBuf::from);
-
-    let db = if let Some(path) = custom_path {
-        LocalDb::open_path(&path)
-    } else {
-        LocalDb::open()
-    };
-
-    db.map_err(|error| {
+    LocalDb::open().map_err(|error| {
         desktop_error(
             "desktop.local_db_open_failed",
             500,
@@ -218,6 +205,9 @@ fn desktop_docs_markdown(locale: Option<&str>) -> &'static str {
 }
 
 fn main() {
+    if let Err(error) = opensession_paths::migrate_legacy_dirs() {
+        eprintln!("legacy data directory migration failed: {error}");
+    }
     maybe_start_summary_batch_on_app_start();
     maybe_start_lifecycle_cleanup_loop();
 
@@ -233,6 +223,7 @@ fn main() {
             desktop_summary_batch_status,
             desktop_summary_batch_run,
             desktop_detect_summary_provider,
+            desktop_summary_provider_health,
             desktop_vector_preflight,
             desktop_vector_install_model,
             desktop_vector_index_rebuild,
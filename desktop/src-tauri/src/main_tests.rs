@@ -212,6 +212,9 @@ fn sample_row() -> opensession_local_db::LocalSessionRow {
         has_errors: false,
         max_active_agents: 1,
         is_auxiliary: false,
+        session_score: 0,
+        issue_refs: None,
+        environment: None,
     }
 }
 
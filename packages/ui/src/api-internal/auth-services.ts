@@ -3,6 +3,8 @@ import type {
 	AuthProvidersResponse,
 	AuthTokenResponse,
 	CapabilitiesResponse,
+	EventBookmark,
+	EventBookmarkListResponse,
 	GitCredentialSummary,
 	IssueApiKeyResponse,
 	ListGitCredentialsResponse,
@@ -95,6 +97,41 @@ export function deleteGitCredentialEffect(id: string): Effect.Effect<void, unkno
 	});
 }
 
+export function listEventBookmarksEffect(
+	sessionId: string,
+): Effect.Effect<EventBookmark[], unknown, RuntimeEnv> {
+	return Effect.gen(function* () {
+		const response = yield* requestEffect<EventBookmarkListResponse>(
+			`/api/sessions/${encodeURIComponent(sessionId)}/bookmarks`,
+		);
+		return response.bookmarks ?? [];
+	});
+}
+
+export function putEventBookmarkEffect(
+	sessionId: string,
+	eventId: string,
+	note: string | null = null,
+): Effect.Effect<EventBookmark, unknown, RuntimeEnv> {
+	return requestEffect<EventBookmark>(
+		`/api/sessions/${encodeURIComponent(sessionId)}/events/${encodeURIComponent(eventId)}/bookmark`,
+		{
+			method: 'PUT',
+			body: JSON.stringify({ note }),
+		},
+	);
+}
+
+export function deleteEventBookmarkEffect(
+	sessionId: string,
+	eventId: string,
+): Effect.Effect<void, unknown, RuntimeEnv> {
+	return requestEffect<void>(
+		`/api/sessions/${encodeURIComponent(sessionId)}/events/${encodeURIComponent(eventId)}/bookmark`,
+		{ method: 'DELETE' },
+	);
+}
+
 export function authRegisterEffect(
 	email: string,
 	password: string,
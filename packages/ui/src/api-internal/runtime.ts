@@ -232,6 +232,10 @@ export function getOAuthUrl(runtime: RuntimeEnv, provider: string): string {
 	return `${getBaseUrl(runtime)}/api/auth/oauth/${encodeURIComponent(provider)}`;
 }
 
+export function getLiveSessionStreamUrl(runtime: RuntimeEnv, sessionId: string): string {
+	return `${getBaseUrl(runtime)}/api/sessions/${encodeURIComponent(sessionId)}/live`;
+}
+
 export function getPreferredLanguages(runtime: RuntimeEnv): string[] {
 	if (typeof runtime.getPreferredLanguages === 'function') {
 		return runtime.getPreferredLanguages();
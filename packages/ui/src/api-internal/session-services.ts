@@ -14,6 +14,8 @@ import type {
 	DesktopSummaryBatchStatusResponse,
 	DesktopSessionSummaryResponse,
 	DesktopSummaryProviderDetectResponse,
+	DesktopSummaryProviderHealthRequest,
+	DesktopSummaryProviderHealthResponse,
 	DesktopVectorIndexStatusResponse,
 	DesktopVectorInstallStatusResponse,
 	DesktopVectorPreflightResponse,
@@ -197,6 +199,16 @@ export function detectSummaryProviderEffect(): Effect.Effect<
 	return withSessionReadCore((core) => core.detectSummaryProvider());
 }
 
+export function summaryProviderHealthEffect(
+	request: DesktopSummaryProviderHealthRequest,
+): Effect.Effect<
+	DesktopSummaryProviderHealthResponse,
+	ReturnType<typeof normalizeSessionAdapterError>,
+	RuntimeEnv
+> {
+	return withSessionReadCore((core) => core.summaryProviderHealth(request));
+}
+
 export function vectorPreflightEffect(): Effect.Effect<
 	DesktopVectorPreflightResponse,
 	ReturnType<typeof normalizeSessionAdapterError>,
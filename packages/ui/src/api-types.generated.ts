@@ -1,7 +1,7 @@
 // AUTO-GENERATED by opensession-api — DO NOT EDIT
 // Regenerate with: cargo test -p opensession-api -- export_typescript
 
-export type SortOrder = "recent" | "popular" | "longest"
+export type SortOrder = "recent" | "popular" | "longest" | "best"
 
 export type TimeRange = "24h" | "7d" | "30d" | "all"
 
@@ -21,12 +21,32 @@ export interface ChangePasswordRequest { current_password: string, new_password:
 
 export interface VerifyResponse { user_id: string, nickname: string, }
 
-export interface UserSettingsResponse { user_id: string, nickname: string, created_at: string, email: string | null, avatar_url: string | null, oauth_providers: Array<LinkedProvider>, }
+export interface UserSettingsResponse { user_id: string, nickname: string, created_at: string, email: string | null, avatar_url: string | null, oauth_providers: Array<LinkedProvider>, 
+/**
+ * Teams granted by the sign-in provider's group mapping.
+ */
+teams: Array<string>, }
 
 export interface OkResponse { ok: boolean, }
 
 export interface IssueApiKeyResponse { api_key: string, }
 
+export interface DeviceSummary { id: string, name: string | null, first_seen_at: string, last_seen_at: string, revoked_at: string | null, 
+/**
+ * Sessions uploaded from this device.
+ */
+session_count: number, 
+/**
+ * Whether this is the device making the request.
+ */
+current: boolean, 
+/**
+ * Base64 Ed25519 public key that signs this device's uploads.
+ */
+signing_key?: string | null, }
+
+export interface ListDevicesResponse { devices: Array<DeviceSummary>, }
+
 export interface GitCredentialSummary { id: string, label: string, host: string, path_prefix: string, header_name: string, created_at: string, updated_at: string, last_used_at: string | null, }
 
 export interface ListGitCredentialsResponse { credentials: Array<GitCredentialSummary>, }
@@ -35,13 +55,100 @@ export interface CreateGitCredentialRequest { label: string, host: string, path_
 
 export interface OAuthLinkResponse { url: string, }
 
-export interface UploadResponse { id: string, url: string, session_score: number, score_plugin: string, }
-
-export interface SessionSummary { id: string, user_id: string | null, nickname: string | null, tool: string, agent_provider: string | null, agent_model: string | null, title: string | null, description: string | null, tags: string | null, created_at: string, uploaded_at: string, message_count: number, task_count: number, event_count: number, duration_seconds: number, total_input_tokens: number, total_output_tokens: number, git_remote?: string | null, git_branch?: string | null, git_commit?: string | null, git_repo_name?: string | null, pr_number?: number | null, pr_url?: string | null, working_directory?: string | null, files_modified?: string | null, files_read?: string | null, has_errors: boolean, max_active_agents: number, session_score: number, score_plugin: string, }
-
-export interface SessionListResponse { sessions: Array<SessionSummary>, total: number, page: number, per_page: number, }
-
-export interface SessionListQuery { page: number, per_page: number, search: string | null, tool: string | null, git_repo_name: string | null, sort: SortOrder | null, time_range: TimeRange | null, }
+export interface UploadResponse { id: string, url: string, session_score: number, score_plugin: string, 
+/**
+ * Team soft quotas this upload reached; the session was still stored.
+ */
+quota_warnings?: Array<QuotaNotice>, }
+
+export interface BatchUploadItemResult { session_id: string, upload?: UploadResponse | null, error?: string | null, 
+/**
+ * Set when the item was rejected because its content matches this
+ * already-uploaded session. Retry with `force` to store it anyway.
+ */
+duplicate_of?: string | null, 
+/**
+ * Events that matched the server's or the uploader's teams' upload
+ * exclude patterns, when the item was rejected by a privacy policy.
+ */
+policy_violations?: Array<SessionValidationError>, 
+/**
+ * Set when the item was rejected because it would push one of the
+ * uploader's teams past a hard storage quota.
+ */
+quota_exceeded?: QuotaNotice | null, }
+
+export interface BatchUploadResponse { results: Array<BatchUploadItemResult>, }
+
+export interface SessionValidationError { 
+/**
+ * Location of the problem, e.g. `agent.tool`, `events[3]` or `line 2`.
+ */
+path: string, 
+/**
+ * Stable machine-readable code, e.g. `missing_field`.
+ */
+code: string, message: string, }
+
+export interface ValidateSessionResponse { valid: boolean, errors: Array<SessionValidationError>, }
+
+export interface SessionSummary { id: string, user_id: string | null, nickname: string | null, tool: string, agent_provider: string | null, agent_model: string | null, title: string | null, description: string | null, tags: string | null, created_at: string, uploaded_at: string, message_count: number, task_count: number, event_count: number, duration_seconds: number, total_input_tokens: number, total_output_tokens: number, git_remote?: string | null, git_branch?: string | null, git_commit?: string | null, git_repo_name?: string | null, pr_number?: number | null, pr_url?: string | null, working_directory?: string | null, files_modified?: string | null, files_read?: string | null, has_errors: boolean, max_active_agents: number, session_score: number, score_plugin: string, 
+/**
+ * Machine that uploaded the session (see `GET /api/auth/devices`).
+ */
+device_id?: string | null, 
+/**
+ * The upload was signed by `device_id`'s registered signing key.
+ */
+verified_origin: boolean, kind: SessionKind, }
+
+export interface SessionListResponse { sessions: Array<SessionSummary>, total: number, page: number, per_page: number, 
+/**
+ * Opaque cursor for the next page; absent on the last page.
+ */
+next_cursor?: string | null, }
+
+export interface SessionListQuery { page: number, per_page: number, 
+/**
+ * Opaque cursor from a previous `SessionListResponse::next_cursor`.
+ */
+cursor: string | null, 
+/**
+ * Page size; takes precedence over `per_page`.
+ */
+limit: number | null, search: string | null, tool: string | null, git_repo_name: string | null, sort: SortOrder | null, time_range: TimeRange | null, 
+/**
+ * Filter by model (glob-like, `*` matches any run of characters).
+ */
+model: string | null, 
+/**
+ * Only sessions created at or after this ISO8601 timestamp.
+ */
+since: string | null, 
+/**
+ * Only sessions created before this ISO8601 timestamp.
+ */
+before: string | null, 
+/**
+ * Only sessions that modified this file path.
+ */
+touches: string | null, 
+/**
+ * Only sessions with (`true`) or without (`false`) errors.
+ */
+has_errors: boolean | null, 
+/**
+ * Filter by git commit (prefix match, so short SHAs work).
+ */
+git_commit: string | null, }
+
+export interface SessionRawQuery { 
+/**
+ * Return only events after the first `since_event` (plus the stats line)
+ * instead of the full body. The response then carries
+ * [`SESSION_DELTA_HEADER`].
+ */
+since_event: number | null, }
 
 export interface DesktopSessionListQuery { page: string | null, per_page: string | null, search: string | null, tool: string | null, git_repo_name: string | null, sort: string | null, time_range: string | null, force_refresh: boolean | null, }
 
@@ -155,6 +262,10 @@ export interface DesktopRuntimeSettingsUpdateRequest { session_default_view?: st
 
 export interface DesktopSummaryProviderDetectResponse { detected: boolean, provider?: DesktopSummaryProviderId | null, transport?: DesktopSummaryProviderTransport | null, model?: string | null, endpoint?: string | null, }
 
+export interface DesktopSummaryProviderHealthRequest { endpoint: string, model: string, }
+
+export interface DesktopSummaryProviderHealthResponse { endpoint: string, model: string, reachable: boolean, model_installed: boolean, models: Array<string>, message?: string | null, }
+
 export interface DesktopSessionSummaryResponse { session_id: string, summary?: any, source_details?: any, diff_tree: any[], source_kind?: string | null, generation_kind?: string | null, error?: string | null, }
 
 export interface DesktopChangeReadRequest { session_id: string, scope?: DesktopChangeReaderScope | null, }
@@ -171,10 +282,292 @@ export interface DesktopChangeQuestionResponse { session_id: string, question: s
 
 export interface DesktopApiError { code: string, status: number, message: string, details?: Record<string, any> | null, }
 
-export interface SessionDetail { linked_sessions?: Array<SessionLink>, id: string, user_id: string | null, nickname: string | null, tool: string, agent_provider: string | null, agent_model: string | null, title: string | null, description: string | null, tags: string | null, created_at: string, uploaded_at: string, message_count: number, task_count: number, event_count: number, duration_seconds: number, total_input_tokens: number, total_output_tokens: number, git_remote?: string | null, git_branch?: string | null, git_commit?: string | null, git_repo_name?: string | null, pr_number?: number | null, pr_url?: string | null, working_directory?: string | null, files_modified?: string | null, files_read?: string | null, has_errors: boolean, max_active_agents: number, session_score: number, score_plugin: string, }
+export type SessionKind = "full" | "metrics_only"
+
+export type StorageClass = "hot" | "cold"
+
+export interface SessionDetail { linked_sessions?: Array<SessionLink>, 
+/**
+ * Hex SHA-256 of the body as stored at upload; the `/raw` download must
+ * hash to the same value. Absent for bodies hosted elsewhere.
+ */
+body_sha256?: string | null, 
+/**
+ * Base64 Ed25519 signature by the server's body signing key over
+ * `opensession-body-v1\n{id}\n{body_sha256}`, when one is configured.
+ */
+body_signature?: string | null, 
+/**
+ * Whether the body is in hot storage or archived to the cold store.
+ * Cold bodies are brought back on the next `/raw` download.
+ */
+storage_class: StorageClass, id: string, user_id: string | null, nickname: string | null, tool: string, agent_provider: string | null, agent_model: string | null, title: string | null, description: string | null, tags: string | null, created_at: string, uploaded_at: string, message_count: number, task_count: number, event_count: number, duration_seconds: number, total_input_tokens: number, total_output_tokens: number, git_remote?: string | null, git_branch?: string | null, git_commit?: string | null, git_repo_name?: string | null, pr_number?: number | null, pr_url?: string | null, working_directory?: string | null, files_modified?: string | null, files_read?: string | null, has_errors: boolean, max_active_agents: number, session_score: number, score_plugin: string, 
+/**
+ * Machine that uploaded the session (see `GET /api/auth/devices`).
+ */
+device_id?: string | null, 
+/**
+ * The upload was signed by `device_id`'s registered signing key.
+ */
+verified_origin: boolean, kind: SessionKind, }
 
 export interface SessionLink { session_id: string, linked_session_id: string, link_type: LinkType, created_at: string, }
 
+export type AttachmentKind = "file" | "terminal_recording"
+
+export interface SessionAttachment { id: string, session_id: string, file_name: string, content_type: string, size_bytes: number, created_at: string, kind: AttachmentKind, 
+/**
+ * RFC 3339 time the recording started, for terminal recordings; playback
+ * position `t` seconds lines up with events at `recorded_at + t`.
+ */
+recorded_at?: string | null, }
+
+export interface SessionAttachmentListResponse { attachments: Array<SessionAttachment>, }
+
+export type QueryOperation = { "op": "sessions" } & SessionListQuery | { "op": "session", id: string, } | { "op": "session_repos" }
+
+export interface QueryRequest { operations: Array<QueryOperation>, }
+
+export type QueryResult = { "op": "sessions" } & SessionListResponse | { "op": "session" } & SessionDetail | { "op": "session_repos" } & SessionRepoListResponse | { "op": "error" } & ApiError
+
+export interface QueryResponse { results: Array<QueryResult>, }
+
+export interface PublishHandoffRequest { 
+/**
+ * Recipient nickname, with or without a leading `@`.
+ */
+recipient: string, 
+/**
+ * Local artifact URI (`os://artifact/<sha256>`).
+ */
+artifact_uri: string, 
+/**
+ * Canonical summary JSONL of the artifact.
+ */
+canonical_jsonl: string, markdown?: string | null, note?: string | null, }
+
+export type HandoffStatus = "pending" | "claimed" | "completed"
+
+export interface HandoffInboxItem { id: string, sender_nickname: string, recipient_nickname: string, artifact_uri: string, canonical_jsonl: string, markdown: string | null, note: string | null, status: HandoffStatus, claimed_at: string | null, completed_at: string | null, 
+/**
+ * Session that picked up the handoff, set on completion.
+ */
+result_session_id: string | null, created_at: string, }
+
+export interface CompleteHandoffRequest { 
+/**
+ * Uploaded session that carried out the handed-off work.
+ */
+session_id?: string | null, }
+
+export interface HandoffInboxResponse { items: Array<HandoffInboxItem>, }
+
+export interface MetadataEntry { key: string, 
+/**
+ * `None` marks a deletion.
+ */
+value: string | null, 
+/**
+ * Writer's clock in unix milliseconds; the newest write wins.
+ */
+updated_at: number, }
+
+export interface SyncMetadataRequest { entries: Array<MetadataEntry>, }
+
+export interface SyncMetadataResponse { entries: Array<MetadataEntry>, }
+
+export interface EventBookmark { session_id: string, event_id: string, note: string | null, 
+/**
+ * Unix milliseconds of the last change.
+ */
+updated_at: number, }
+
+export interface EventBookmarkListResponse { bookmarks: Array<EventBookmark>, }
+
+export interface PutEventBookmarkRequest { note: string | null, }
+
+export interface LiveSessionInfo { session_id: string, owner_nickname: string, tool: string | null, title: string | null, 
+/**
+ * RFC 3339.
+ */
+started_at: string, 
+/**
+ * RFC 3339; the last time the owner appended events.
+ */
+updated_at: string, 
+/**
+ * Events appended since sharing started, including ones no longer buffered.
+ */
+event_count: number, 
+/**
+ * Distinct viewers watching right now, the owner excluded.
+ */
+viewers: Array<LiveViewer>, }
+
+export interface LiveViewer { nickname: string, 
+/**
+ * RFC 3339.
+ */
+joined_at: string, }
+
+export type LiveUpdate = { "type": "snapshot", info: LiveSessionInfo, agent: any, context: any, 
+/**
+ * The buffered events, oldest first.
+ */
+events: any[], } | { "type": "events", events: any[], event_count: number, } | { "type": "presence", viewers: Array<LiveViewer>, } | { "type": "ended", uploaded: boolean, }
+
+export interface LiveSessionListResponse { sessions: Array<LiveSessionInfo>, }
+
+export type PlaybookKind = "prompt" | "handoff_template"
+
+export interface Playbook { name: string, kind: PlaybookKind, title: string | null, description: string | null, body: string, author_nickname: string | null, created_at: string, updated_at: string, }
+
+export interface PutPlaybookRequest { kind: PlaybookKind, title: string | null, description: string | null, body: string, }
+
+export interface PlaybookListResponse { playbooks: Array<Playbook>, }
+
+export interface CreateReviewRequest { 
+/**
+ * Reviewer nickname, with or without a leading `@`.
+ */
+reviewer: string, note?: string | null, }
+
+export type ReviewRequestStatus = "pending" | "approved" | "changes_requested"
+
+export interface ReviewRequestItem { id: string, session_id: string, session_title: string | null, requester_nickname: string, reviewer_nickname: string, note: string | null, status: ReviewRequestStatus, 
+/**
+ * Reviewer's comment, set when the request is resolved.
+ */
+comment: string | null, resolved_at: string | null, created_at: string, }
+
+export type ReviewRequestRole = "assigned" | "requested"
+
+export interface ResolveReviewRequest { 
+/**
+ * `approved` or `changes_requested`.
+ */
+status: ReviewRequestStatus, comment?: string | null, }
+
+export interface ReviewRequestListResponse { items: Array<ReviewRequestItem>, }
+
+export interface TelemetryReport { daemon_version: string, 
+/**
+ * RFC 3339 start of the period the counters cover.
+ */
+period_start: string, 
+/**
+ * RFC 3339 end of the period the counters cover.
+ */
+period_end: string, sessions_indexed: number, parse_failures: number, upload_errors: number, }
+
+export interface TelemetryTotals { daemon_version: string, reports: number, sessions_indexed: number, parse_failures: number, upload_errors: number, }
+
+export interface TelemetrySummaryResponse { days: number, reports: number, sessions_indexed: number, parse_failures: number, upload_errors: number, versions: Array<TelemetryTotals>, }
+
+export interface RecomputeSessionError { session_id: string, error: string, }
+
+export interface RecomputeSessionsResponse { scanned: number, 
+/**
+ * Sessions whose stored columns differed from the recomputed values.
+ */
+updated: number, errors: Array<RecomputeSessionError>, 
+/**
+ * Pass as `after` to process the next batch; absent once every session was scanned.
+ */
+next_after?: string | null, }
+
+export type AccountExportRecord = { "kind": "account", account: UserSettingsResponse, devices: Array<DeviceSummary>, metadata: Array<MetadataEntry>, } | { "kind": "session", session: SessionSummary, 
+/**
+ * Stored HAIL JSONL body.
+ */
+body: string, } | { "kind": "attachment", attachment: SessionAttachment, content_base64: string, }
+
+export interface DeleteAccountResponse { sessions_deleted: number, attachments_deleted: number, }
+
+export interface CreateGuestTokenRequest { 
+/**
+ * Who or what the token is for, e.g. "grafana dashboard".
+ */
+label: string, }
+
+export interface GuestTokenSummary { id: string, team_id: string, label: string, 
+/**
+ * Nickname of the member who created the token.
+ */
+created_by: string, created_at: string, last_used_at: string | null, }
+
+export interface CreateGuestTokenResponse { token: string, guest_token: GuestTokenSummary, }
+
+export interface ListGuestTokensResponse { guest_tokens: Array<GuestTokenSummary>, }
+
+export type QuotaResource = "body_bytes" | "sessions"
+
+export interface QuotaLimit { soft?: number | null, hard?: number | null, }
+
+export interface QuotaNotice { team_id: string, resource: QuotaResource, 
+/**
+ * Usage including the upload being checked, when there is one.
+ */
+used: number, limit: number, }
+
+export interface TeamUsageResponse { team_id: string, session_count: number, body_bytes: number, sessions_quota: QuotaLimit, body_bytes_quota: QuotaLimit, 
+/**
+ * Limits the team has reached, soft or hard.
+ */
+warnings: Array<QuotaNotice>, }
+
+export type RetentionAction = "delete" | "anonymize"
+
+export interface RetentionPolicy { 
+/**
+ * Days after upload a session is kept; `0` keeps sessions forever.
+ */
+retain_days: number, action: RetentionAction, 
+/**
+ * Keep sessions the uploader or a teammate bookmarked (a
+ * `bookmark:<session-id>` metadata entry) or bookmarked an event of.
+ */
+exempt_bookmarked: boolean, 
+/**
+ * Keep sessions that are part of a handoff chain or other link.
+ */
+exempt_linked: boolean, }
+
+export interface TeamRetentionResponse { team_id: string, policy: RetentionPolicy, 
+/**
+ * The team has no policy of its own and follows the server default.
+ */
+is_default: boolean, }
+
+export interface RetentionPreviewItem { session_id: string, title: string | null, uploaded_at: string, action: RetentionAction, }
+
+export interface RetentionPreviewResponse { team_id: string, policy: RetentionPolicy, is_default: boolean, sessions: Array<RetentionPreviewItem>, 
+/**
+ * Expired sessions kept because they are bookmarked or linked.
+ */
+exempt: number, }
+
+export type UploadPolicyAction = "reject" | "scrub"
+
+export interface TeamUploadPolicy { 
+/**
+ * Matched against file paths and shell command words, like
+ * `OPENSESSION_UPLOAD_EXCLUDE_PATTERNS`.
+ */
+exclude_patterns: Array<string>, action: UploadPolicyAction, }
+
+export interface TeamUploadPolicyResponse { team_id: string, 
+/**
+ * Empty when the team has no policy of its own.
+ */
+policy: TeamUploadPolicy, }
+
+export interface RegisterDeviceKeyRequest { 
+/**
+ * Base64 Ed25519 public key (32 bytes).
+ */
+public_key: string, }
+
 export type ParseSource = { "kind": "git", remote: string, ref: string, path: string, } | { "kind": "github", owner: string, repo: string, ref: string, path: string, } | { "kind": "inline", filename: string, content_base64: string, }
 
 export interface ParseCandidate { id: string, confidence: number, reason: string, }
@@ -209,6 +602,10 @@ export interface LinkedProvider { provider: string, provider_username: string, d
 
 export interface HealthResponse { status: string, version: string, }
 
-export interface CapabilitiesResponse { auth_enabled: boolean, parse_preview_enabled: boolean, register_targets: Array<string>, share_modes: Array<string>, }
+export interface CapabilitiesResponse { auth_enabled: boolean, parse_preview_enabled: boolean, register_targets: Array<string>, share_modes: Array<string>, 
+/**
+ * Base64 Ed25519 public key that verifies `SessionDetail.body_signature`.
+ */
+body_signing_key?: string | null, }
 
 export interface ApiError { code: string, message: string, }
@@ -14,10 +14,13 @@ import type {
 	DesktopSummaryBatchStatusResponse,
 	DesktopSessionSummaryResponse,
 	DesktopSummaryProviderDetectResponse,
+	DesktopSummaryProviderHealthRequest,
+	DesktopSummaryProviderHealthResponse,
 	DesktopVectorIndexStatusResponse,
 	DesktopVectorInstallStatusResponse,
 	DesktopVectorPreflightResponse,
 	DesktopVectorSearchResponse,
+	EventBookmark,
 	GitCredentialSummary,
 	IssueApiKeyResponse,
 	LocalReviewBundle,
@@ -39,6 +42,7 @@ import {
 	authLogoutEffect,
 	authRegisterEffect,
 	createGitCredentialEffect,
+	deleteEventBookmarkEffect,
 	deleteGitCredentialEffect,
 	getApiCapabilitiesSafeEffect,
 	getAuthProvidersSafeEffect,
@@ -46,7 +50,9 @@ import {
 	handleAuthCallbackEffect,
 	issueApiKeyEffect,
 	isAuthenticatedEffect,
+	listEventBookmarksEffect,
 	listGitCredentialsEffect,
+	putEventBookmarkEffect,
 	verifyAuthEffect,
 } from './api-internal/auth-services';
 import {
@@ -74,6 +80,7 @@ import {
 	regenerateSessionSemanticSummaryEffect,
 	runSummaryBatchEffect,
 	searchSessionsVectorEffect,
+	summaryProviderHealthEffect,
 	updateRuntimeSettingsEffect,
 	vectorIndexRebuildEffect,
 	vectorIndexStatusEffect,
@@ -81,6 +88,7 @@ import {
 	vectorPreflightEffect,
 } from './api-internal/session-services';
 import {
+	getLiveSessionStreamUrl as getLiveSessionStreamUrlFromRuntime,
 	getOAuthUrl as getOAuthUrlFromRuntime,
 	isAuthenticated as isAuthenticatedInRuntime,
 	readBrowserRuntime,
@@ -194,6 +202,12 @@ export async function detectSummaryProvider(): Promise<DesktopSummaryProviderDet
 	return runUiEffect(detectSummaryProviderEffect());
 }
 
+export async function checkSummaryProviderHealth(
+	request: DesktopSummaryProviderHealthRequest,
+): Promise<DesktopSummaryProviderHealthResponse> {
+	return runUiEffect(summaryProviderHealthEffect(request));
+}
+
 export async function vectorPreflight(): Promise<DesktopVectorPreflightResponse> {
 	return runUiEffect(vectorPreflightEffect());
 }
@@ -250,6 +264,22 @@ export async function deleteGitCredential(id: string): Promise<void> {
 	return runUiEffect(deleteGitCredentialEffect(id));
 }
 
+export async function listEventBookmarks(sessionId: string): Promise<EventBookmark[]> {
+	return runUiEffect(listEventBookmarksEffect(sessionId));
+}
+
+export async function putEventBookmark(
+	sessionId: string,
+	eventId: string,
+	note: string | null = null,
+): Promise<EventBookmark> {
+	return runUiEffect(putEventBookmarkEffect(sessionId, eventId, note));
+}
+
+export async function deleteEventBookmark(sessionId: string, eventId: string): Promise<void> {
+	return runUiEffect(deleteEventBookmarkEffect(sessionId, eventId));
+}
+
 export async function authRegister(
 	email: string,
 	password: string,
@@ -315,6 +345,10 @@ export function getOAuthUrl(provider: string): string {
 	return getOAuthUrlFromRuntime(readBrowserRuntime(), provider);
 }
 
+export function getLiveSessionStreamUrl(sessionId: string): string {
+	return getLiveSessionStreamUrlFromRuntime(readBrowserRuntime(), sessionId);
+}
+
 export async function handleAuthCallback(): Promise<boolean> {
 	return runUiEffect(handleAuthCallbackEffect());
 }
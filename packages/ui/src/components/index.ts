@@ -9,6 +9,7 @@ export { default as EventView } from './EventView.svelte';
 export { default as ExpandableChip } from './ExpandableChip.svelte';
 export { default as HomePage } from './HomePage.svelte';
 export { default as LandingPage } from './LandingPage.svelte';
+export { default as LiveSessionPage } from './LiveSessionPage.svelte';
 export { default as LoginPage } from './LoginPage.svelte';
 export { default as ParserSelectPanel } from './ParserSelectPanel.svelte';
 export { default as ParseSourceBanner } from './ParseSourceBanner.svelte';
@@ -196,6 +196,10 @@ const messages = {
 		'sessionList.refreshJobDetail':
 			'Reindexing is running in the background. You can keep browsing.',
 		'sessionList.messagesShort': '{count} messages',
+		'sessionList.verifiedOrigin': 'verified',
+		'sessionList.verifiedOriginTitle': 'Uploaded and signed by a registered device key',
+		'sessionList.metricsOnly': 'metrics only',
+		'sessionList.metricsOnlyTitle': 'Only aggregate stats were uploaded; there is no transcript',
 		'sessionList.messagesLong': '{count} messages',
 		'sessionList.events': '{count} events',
 		'settings.languageSection': 'Language',
@@ -526,6 +530,10 @@ const messages = {
 		'sessionList.refreshJobDetail':
 			'백그라운드에서 재색인이 진행 중입니다. 계속 둘러볼 수 있습니다.',
 		'sessionList.messagesShort': '{count}개 메시지',
+		'sessionList.verifiedOrigin': '검증됨',
+		'sessionList.verifiedOriginTitle': '등록된 기기 키로 서명되어 업로드됨',
+		'sessionList.metricsOnly': '지표만',
+		'sessionList.metricsOnlyTitle': '집계 통계만 업로드되어 대화 기록이 없습니다',
 		'sessionList.messagesLong': '{count}개 메시지',
 		'sessionList.events': '{count}개 이벤트',
 		'settings.languageSection': '언어',
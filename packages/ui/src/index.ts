@@ -7,9 +7,12 @@ export {
 	authLogout,
 	authRegister,
 	buildSessionHandoff,
+	checkSummaryProviderHealth,
+	deleteEventBookmark,
 	detectSummaryProvider,
 	getApiCapabilities,
 	getAuthProviders,
+	getLiveSessionStreamUrl,
 	getLocalReviewBundle,
 	getOAuthUrl,
 	getParsePreviewError,
@@ -23,12 +26,14 @@ export {
 	isAuthApiAvailable,
 	isAuthenticated,
 	isParsePreviewApiAvailable,
+	listEventBookmarks,
 	listSessionRepos,
 	listSessions,
 	PreviewApiError,
 	previewSessionFromGithubSource,
 	previewSessionFromGitSource,
 	previewSessionFromInlineSource,
+	putEventBookmark,
 	quickShareSession,
 	regenerateSessionSemanticSummary,
 	runSummaryBatch,
@@ -18,6 +18,8 @@ import type {
 	DesktopSessionListQuery,
 	DesktopSessionSummaryResponse,
 	DesktopSummaryProviderDetectResponse,
+	DesktopSummaryProviderHealthRequest,
+	DesktopSummaryProviderHealthResponse,
 	DesktopVectorIndexStatusResponse,
 	DesktopVectorInstallStatusResponse,
 	DesktopVectorPreflightResponse,
@@ -33,8 +35,16 @@ export type SessionListParams = {
 	search?: string;
 	page?: number;
 	per_page?: number;
+	cursor?: string;
+	limit?: number;
 	sort?: string;
 	time_range?: string;
+	model?: string;
+	since?: string;
+	before?: string;
+	touches?: string;
+	has_errors?: boolean;
+	git_commit?: string;
 	force_refresh?: boolean;
 };
 
@@ -86,6 +96,9 @@ export interface SessionReadAdapter {
 	summaryBatchRun(): Promise<DesktopSummaryBatchStatusResponse>;
 	summaryBatchStatus(): Promise<DesktopSummaryBatchStatusResponse>;
 	detectSummaryProvider(): Promise<DesktopSummaryProviderDetectResponse>;
+	summaryProviderHealth(
+		request: DesktopSummaryProviderHealthRequest,
+	): Promise<DesktopSummaryProviderHealthResponse>;
 	vectorPreflight(): Promise<DesktopVectorPreflightResponse>;
 	vectorInstallModel(model: string): Promise<DesktopVectorInstallStatusResponse>;
 	vectorIndexRebuild(): Promise<DesktopVectorIndexStatusResponse>;
@@ -236,7 +249,19 @@ export function createWebSessionReadAdapter(args: {
 			const body = await res.text();
 			throw new SessionAdapterError('http_request_failed', res.status, body);
 		}
-		return res.text();
+		const expected = res.headers.get('x-opensession-body-sha256');
+		if (!expected || !globalThis.crypto?.subtle) return res.text();
+		const bytes = await res.arrayBuffer();
+		const digest = new Uint8Array(await globalThis.crypto.subtle.digest('SHA-256', bytes));
+		const actual = Array.from(digest, (byte) => byte.toString(16).padStart(2, '0')).join('');
+		if (actual !== expected.trim().toLowerCase()) {
+			throw new SessionAdapterError(
+				'body_integrity_mismatch',
+				res.status,
+				`session body does not match its upload hash (expected sha256 ${expected}, got ${actual})`,
+			);
+		}
+		return new TextDecoder().decode(bytes);
 	}
 
 	return {
@@ -387,6 +412,16 @@ export function createWebSessionReadAdapter(args: {
 				}),
 			);
 		},
+		async summaryProviderHealth() {
+			throw new SessionAdapterError(
+				'desktop_runtime_settings_unsupported',
+				501,
+				serializeErrorBody({
+					code: 'desktop_runtime_settings_unsupported',
+					message: 'Provider health checks are available only in desktop runtime.',
+				}),
+			);
+		},
 		async vectorPreflight() {
 			throw new SessionAdapterError(
 				'desktop_vector_unsupported',
@@ -619,6 +654,12 @@ export function createDesktopSessionReadAdapter(invoke: DesktopInvoke): SessionR
 				'desktop_detect_summary_provider',
 			);
 		},
+		async summaryProviderHealth(request) {
+			return invokeAfterContractCheck<DesktopSummaryProviderHealthResponse>(
+				'desktop_summary_provider_health',
+				{ request },
+			);
+		},
 		async vectorPreflight() {
 			return invokeAfterContractCheck<DesktopVectorPreflightResponse>('desktop_vector_preflight');
 		},
@@ -709,6 +750,9 @@ export function createUnavailableDesktopSessionReadAdapter(): SessionReadAdapter
 		async detectSummaryProvider() {
 			throw desktopBridgeUnavailableError();
 		},
+		async summaryProviderHealth() {
+			throw desktopBridgeUnavailableError();
+		},
 		async vectorPreflight() {
 			throw desktopBridgeUnavailableError();
 		},
@@ -17,6 +17,8 @@ import type {
 	DesktopSummaryBatchStatusResponse,
 	DesktopSessionSummaryResponse,
 	DesktopSummaryProviderDetectResponse,
+	DesktopSummaryProviderHealthRequest,
+	DesktopSummaryProviderHealthResponse,
 	DesktopVectorIndexStatusResponse,
 	DesktopVectorInstallStatusResponse,
 	DesktopVectorPreflightResponse,
@@ -100,6 +102,9 @@ export interface SessionReadCore {
 	summaryBatchRun(): Promise<DesktopSummaryBatchStatusResponse>;
 	summaryBatchStatus(): Promise<DesktopSummaryBatchStatusResponse>;
 	detectSummaryProvider(): Promise<DesktopSummaryProviderDetectResponse>;
+	summaryProviderHealth(
+		request: DesktopSummaryProviderHealthRequest,
+	): Promise<DesktopSummaryProviderHealthResponse>;
 	vectorPreflight(): Promise<DesktopVectorPreflightResponse>;
 	vectorInstallModel(model: string): Promise<DesktopVectorInstallStatusResponse>;
 	vectorIndexRebuild(): Promise<DesktopVectorIndexStatusResponse>;
@@ -267,6 +272,15 @@ export function createSessionReadCore(adapter: SessionReadAdapter): SessionReadC
 				throw SessionReadCoreError.fromUnknown(error);
 			}
 		},
+		async summaryProviderHealth(
+			request: DesktopSummaryProviderHealthRequest,
+		): Promise<DesktopSummaryProviderHealthResponse> {
+			try {
+				return await adapter.summaryProviderHealth(request);
+			} catch (error) {
+				throw SessionReadCoreError.fromUnknown(error);
+			}
+		},
 		async vectorPreflight(): Promise<DesktopVectorPreflightResponse> {
 			try {
 				return await adapter.vectorPreflight();
@@ -136,6 +136,8 @@ export type {
 	DesktopSummaryBatchStatusResponse,
 	DesktopSummaryOutputShape,
 	DesktopSummaryProviderDetectResponse,
+	DesktopSummaryProviderHealthRequest,
+	DesktopSummaryProviderHealthResponse,
 	DesktopSummaryProviderId,
 	DesktopSummaryProviderTransport,
 	DesktopSummaryResponseStyle,
@@ -152,12 +154,17 @@ export type {
 	DesktopVectorSearchProvider,
 	DesktopVectorSearchResponse,
 	DesktopVectorSessionMatch,
+	EventBookmark,
+	EventBookmarkListResponse,
 	GitCredentialSummary,
 	HealthResponse,
 	IssueApiKeyResponse,
 	LinkedProvider,
 	LinkType,
 	ListGitCredentialsResponse,
+	LiveSessionInfo,
+	LiveUpdate,
+	LiveViewer,
 	LocalReviewBundle,
 	LocalReviewCommit,
 	LocalReviewLayerFileChange,
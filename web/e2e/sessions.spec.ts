@@ -360,6 +360,25 @@ test.describe('Sessions', () => {
 		await expect(page.locator('[data-timeline-idx]')).toHaveCount(1);
 	});
 
+	test('event permalinks jump to and highlight the event', async ({ page }) => {
+		const fixture = createSessionFixture({
+			title: `PW Permalink ${crypto.randomUUID().slice(0, 8)}`,
+		});
+		await mockSessionApis(page, fixture);
+
+		await page.goto(`/session/${fixture.id}`);
+		const second = page.locator('[data-timeline-idx="1"]');
+		const anchor = await second.getAttribute('id');
+		expect(anchor).toMatch(/^event-/);
+		await expect(second.getByTestId('event-permalink')).toHaveAttribute('href', `#${anchor}`);
+		await expect(second.getByTestId('event-bookmark')).toHaveCount(0);
+
+		await page.goto(`/session/${fixture.id}#${anchor}`);
+		await expect(page.locator(`[id="${anchor}"]`)).toHaveAttribute('data-anchored', 'true');
+		await expect(page.locator(`[id="${anchor}"]`)).toBeInViewport();
+		await expect(page.locator('[data-timeline-idx="0"]')).not.toHaveAttribute('data-anchored', 'true');
+	});
+
 	test('session detail branchpoints mode focuses on semantic decision nodes', async ({ page }) => {
 		const fixture = createSessionFixture({
 			title: `PW Branchpoints ${crypto.randomUUID().slice(0, 8)}`,